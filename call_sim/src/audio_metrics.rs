@@ -0,0 +1,445 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Audio metrics computed in-process, without the visqol, pesq, or plc images and without any
+//! conversions by sox.
+//!
+//! The received (degraded) audio is aligned with the reference by cross-correlation. This is
+//! first done coarsely with the energy envelopes of both, and then per block at the sample level
+//! to follow the jitter buffer as it stretches and shrinks the audio. The reference is treated as
+//! looping, since that is how the sender plays it. From the alignment we get:
+//!
+//! - the latency, the median delay of the received audio behind the reference (which can only be
+//!   known modulo the length of the reference);
+//! - the SNR over all active frames, and the segmental SNR (the average of per-frame SNRs);
+//! - gaps, runs of silent frames where the reference is active;
+//! - concealment, frames that aren't silent but don't resemble the reference at all, which is
+//!   what packet loss concealment tends to produce.
+//!
+//! These are no substitute for MOS, but they are quick and they point at where audio went wrong.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The raw files are 48kHz, 16-bit, two-channel audio.
+const RAW_SAMPLE_RATE: usize = 48000;
+const RAW_CHANNELS: usize = 2;
+/// The audio is analyzed as 16kHz mono, like the speech models do.
+pub const SAMPLE_RATE: usize = 16000;
+const DECIMATION: usize = RAW_SAMPLE_RATE / SAMPLE_RATE;
+
+const FRAME_MS: usize = 10;
+const FRAME_LEN: usize = SAMPLE_RATE * FRAME_MS / 1000;
+/// The alignment is refined for each block of this many frames.
+const BLOCK_FRAMES: usize = 50;
+const BLOCK_LEN: usize = FRAME_LEN * BLOCK_FRAMES;
+/// How far the alignment may move from one block to the next (20ms).
+const MAX_DRIFT: usize = SAMPLE_RATE / 50;
+/// Blocks that correlate less than this with the reference keep the previous alignment.
+const MIN_CORRELATION: f64 = 0.3;
+
+/// Reference frames quieter than this are pauses, which aren't scored.
+const ACTIVE_LEVEL_DBFS: f64 = -45.0;
+/// Received frames quieter than this are silent.
+const SILENT_LEVEL_DBFS: f64 = -60.0;
+/// Received frames with an SNR below this don't resemble the reference.
+const CONCEALMENT_SNR_DB: f64 = 0.0;
+/// SNRs are kept in this range, so that a few frames can't dominate the segmental SNR.
+const MIN_SNR_DB: f64 = -10.0;
+const MAX_SNR_DB: f64 = 35.0;
+/// Only silent runs at least this long count as gaps.
+const MIN_GAP_FRAMES: usize = 2;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMetrics {
+    /// How far the received audio is behind the reference.
+    pub latency_ms: f32,
+    /// The SNR over all active frames, in dB.
+    pub snr_db: f32,
+    /// The average SNR of the active frames, in dB.
+    pub segmental_snr_db: f32,
+    /// The number of silent runs in the received audio where the reference is active.
+    pub gap_count: u32,
+    /// The total length of the gaps.
+    pub gap_ms: u32,
+    /// The total length of frames that are likely to have been concealed.
+    pub concealed_ms: u32,
+    /// The total length of the frames that were compared, where the reference is active.
+    pub active_ms: u32,
+}
+
+/// Decodes raw audio into 16kHz mono samples in [-1, 1). Each sample averages both channels over
+/// three input samples, which is a crude low-pass filter but good enough for these metrics.
+pub fn decode_raw(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2 * RAW_CHANNELS * DECIMATION)
+        .map(|chunk| {
+            let sum: i32 = chunk
+                .chunks_exact(2)
+                .map(|sample| i16::from_le_bytes([sample[0], sample[1]]) as i32)
+                .sum();
+            sum as f32 / (RAW_CHANNELS * DECIMATION) as f32 / 32768.0
+        })
+        .collect()
+}
+
+/// Analyzes the raw audio a client received against the raw reference it was sent from. If
+/// `length_seconds` is given, only that much of the received audio is analyzed. Returns None if
+/// the reference is silent.
+pub fn analyze_raw_files(
+    reference_path: &Path,
+    degraded_path: &Path,
+    length_seconds: Option<u16>,
+) -> Result<Option<AudioMetrics>> {
+    let read = |path: &Path| {
+        fs::read(path)
+            .map(|bytes| decode_raw(&bytes))
+            .with_context(|| format!("failed to read audio file {}", path.display()))
+    };
+    let reference = read(reference_path)?;
+    let mut degraded = read(degraded_path)?;
+    if let Some(length_seconds) = length_seconds {
+        degraded.truncate(length_seconds as usize * SAMPLE_RATE);
+    }
+    Ok(analyze(&reference, &degraded))
+}
+
+fn energy(samples: impl Iterator<Item = f64>) -> f64 {
+    samples.map(|sample| sample * sample).sum()
+}
+
+fn level_dbfs(samples: &[f32]) -> f64 {
+    let mean_square = energy(samples.iter().map(|sample| *sample as f64)) / samples.len() as f64;
+    10.0 * mean_square.max(1e-12).log10()
+}
+
+fn snr_db(signal: f64, noise: f64) -> f64 {
+    if noise <= 0.0 {
+        MAX_SNR_DB
+    } else {
+        (10.0 * (signal / noise).log10()).clamp(MIN_SNR_DB, MAX_SNR_DB)
+    }
+}
+
+/// The reference sample that lines up with degraded sample `n` when the degraded audio is
+/// `delay` samples behind.
+fn reference_at(reference: &[f32], n: usize, delay: usize) -> f64 {
+    let len = reference.len();
+    reference[(n + len - delay % len) % len] as f64
+}
+
+/// Finds the delay of the degraded audio behind the looping reference, to within a frame, by
+/// correlating their energy envelopes. Returns None if the reference is silent.
+fn coarse_delay(reference: &[f32], degraded: &[f32]) -> Option<usize> {
+    let envelope = |samples: &[f32]| -> Vec<f64> {
+        let envelope: Vec<f64> = samples
+            .chunks_exact(FRAME_LEN)
+            .map(|frame| {
+                (energy(frame.iter().map(|sample| *sample as f64)) / FRAME_LEN as f64).sqrt()
+            })
+            .collect();
+        let mean = envelope.iter().sum::<f64>() / envelope.len().max(1) as f64;
+        envelope.into_iter().map(|value| value - mean).collect()
+    };
+    let reference = envelope(reference);
+    let degraded = envelope(degraded);
+    if energy(reference.iter().copied()) <= 0.0 {
+        return None;
+    }
+
+    let len = reference.len();
+    let best = (0..len)
+        .map(|lag| {
+            let (sxy, sxx) = degraded
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(sxy, sxx), (n, y)| {
+                    let x = reference[(n + len - lag) % len];
+                    (sxy + x * y, sxx + x * x)
+                });
+            (lag, sxy / sxx.sqrt())
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    // Silent audio doesn't correlate with anything, but still has metrics (all gaps).
+    Some(
+        best.filter(|(_, score)| *score > 0.0)
+            .map_or(0, |(lag, _)| lag * FRAME_LEN),
+    )
+}
+
+struct Alignment {
+    delay: usize,
+    gain: f64,
+}
+
+/// Finds the delay within [`MAX_DRIFT`] of `previous` where the block starting at degraded sample
+/// `start` correlates best with the reference, and the gain that matches their levels. Returns
+/// None if the block doesn't correlate well anywhere.
+fn align_block(
+    reference: &[f32],
+    block: &[f32],
+    start: usize,
+    previous: usize,
+) -> Option<Alignment> {
+    let syy = energy(block.iter().map(|sample| *sample as f64));
+    if syy <= 0.0 {
+        return None;
+    }
+
+    let mut best: Option<(f64, Alignment)> = None;
+    for delay in previous.saturating_sub(MAX_DRIFT)..=previous + MAX_DRIFT {
+        let (sxy, sxx) = block
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(sxy, sxx), (i, y)| {
+                let x = reference_at(reference, start + i, delay);
+                (sxy + x * *y as f64, sxx + x * x)
+            });
+        if sxx <= 0.0 {
+            continue;
+        }
+        let correlation = sxy / (sxx * syy).sqrt();
+        if best.as_ref().map_or(true, |(best, _)| correlation > *best) {
+            best = Some((
+                correlation,
+                Alignment {
+                    delay,
+                    gain: sxy / sxx,
+                },
+            ));
+        }
+    }
+    best.filter(|(correlation, _)| *correlation >= MIN_CORRELATION)
+        .map(|(_, alignment)| alignment)
+}
+
+#[derive(Default)]
+struct Gaps {
+    run: usize,
+    count: u32,
+    frames: usize,
+}
+
+impl Gaps {
+    fn end_run(&mut self) {
+        if self.run >= MIN_GAP_FRAMES {
+            self.count += 1;
+            self.frames += self.run;
+        }
+        self.run = 0;
+    }
+}
+
+/// Analyzes 16kHz mono degraded audio against its reference. Returns None if there is nothing to
+/// compare, because the reference is silent or the degraded audio too short.
+fn analyze(reference: &[f32], degraded: &[f32]) -> Option<AudioMetrics> {
+    // Nothing was received before the audio first arrived, so the analysis starts there.
+    let start = coarse_delay(reference, degraded)?;
+    let mut alignment = Alignment {
+        delay: start,
+        gain: 1.0,
+    };
+    let mut delays = vec![];
+
+    let mut signal = 0.0;
+    let mut noise = 0.0;
+    let mut snr_sum = 0.0;
+    let mut active_frames = 0;
+    let mut concealed_frames = 0;
+    let mut gaps = Gaps::default();
+
+    let mut block_start = start;
+    while block_start + BLOCK_LEN <= degraded.len() {
+        let block = &degraded[block_start..block_start + BLOCK_LEN];
+        if let Some(block_alignment) = align_block(reference, block, block_start, alignment.delay) {
+            alignment = block_alignment;
+            delays.push(alignment.delay);
+        }
+
+        for (i, frame) in block.chunks_exact(FRAME_LEN).enumerate() {
+            let frame_start = block_start + i * FRAME_LEN;
+            let reference_frame: Vec<f32> = (0..FRAME_LEN)
+                .map(|j| reference_at(reference, frame_start + j, alignment.delay) as f32)
+                .collect();
+            if level_dbfs(&reference_frame) < ACTIVE_LEVEL_DBFS {
+                continue;
+            }
+            active_frames += 1;
+
+            let (frame_signal, frame_noise) =
+                frame
+                    .iter()
+                    .zip(&reference_frame)
+                    .fold((0.0, 0.0), |(signal, noise), (y, x)| {
+                        let x = alignment.gain * *x as f64;
+                        let error = *y as f64 - x;
+                        (signal + x * x, noise + error * error)
+                    });
+            signal += frame_signal;
+            noise += frame_noise;
+            let frame_snr = snr_db(frame_signal, frame_noise);
+            snr_sum += frame_snr;
+
+            if level_dbfs(frame) < SILENT_LEVEL_DBFS {
+                gaps.run += 1;
+            } else {
+                gaps.end_run();
+                if frame_snr < CONCEALMENT_SNR_DB {
+                    concealed_frames += 1;
+                }
+            }
+        }
+
+        block_start += BLOCK_LEN;
+    }
+    gaps.end_run();
+
+    if active_frames == 0 {
+        return None;
+    }
+
+    delays.sort_unstable();
+    let delay = delays.get(delays.len() / 2).copied().unwrap_or(start);
+
+    Some(AudioMetrics {
+        latency_ms: (delay * 1000) as f32 / SAMPLE_RATE as f32,
+        snr_db: snr_db(signal, noise) as f32,
+        segmental_snr_db: (snr_sum / active_frames as f64) as f32,
+        gap_count: gaps.count,
+        gap_ms: (gaps.frames * FRAME_MS) as u32,
+        concealed_ms: (concealed_frames * FRAME_MS) as u32,
+        active_ms: (active_frames * FRAME_MS) as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uniform noise in [-1, 1).
+    fn noise(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    /// The level of the reference, which changes every 100ms so that it has an envelope.
+    fn amplitudes(len: usize) -> Vec<f32> {
+        let levels = noise(len / 1600 + 1, 7);
+        (0..len).map(|n| 0.3 + 0.2 * levels[n / 1600]).collect()
+    }
+
+    const REFERENCE_LEN: usize = 3 * SAMPLE_RATE;
+    const DEGRADED_LEN: usize = 4 * SAMPLE_RATE;
+    const DELAY: usize = 1234;
+    const GAIN: f32 = 0.5;
+
+    fn reference() -> Vec<f32> {
+        noise(REFERENCE_LEN, 1)
+            .into_iter()
+            .zip(amplitudes(REFERENCE_LEN))
+            .map(|(sample, amplitude)| sample * amplitude)
+            .collect()
+    }
+
+    /// The reference delayed and attenuated, looping like the sender plays it.
+    fn degraded(reference: &[f32]) -> Vec<f32> {
+        (0..DEGRADED_LEN)
+            .map(|n| {
+                if n < DELAY {
+                    0.0
+                } else {
+                    GAIN * reference[(n - DELAY) % reference.len()]
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_raw_audio() {
+        let bytes: Vec<u8> = [1000i16, 3000, 2000, 4000, 3000, 5000, -32768, -32768]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        // The incomplete chunk at the end is dropped.
+        assert_eq!(vec![3000.0 / 32768.0], decode_raw(&bytes));
+    }
+
+    #[test]
+    fn clean() {
+        let reference = reference();
+        let metrics = analyze(&reference, &degraded(&reference)).unwrap();
+        assert_eq!(
+            DELAY as f32 * 1000.0 / SAMPLE_RATE as f32,
+            metrics.latency_ms
+        );
+        assert_eq!(MAX_SNR_DB as f32, metrics.snr_db);
+        assert!(metrics.segmental_snr_db > 34.0);
+        assert_eq!(0, metrics.gap_count);
+        assert_eq!(0, metrics.concealed_ms);
+        // Seven whole blocks after the audio arrives.
+        assert_eq!(3500, metrics.active_ms);
+    }
+
+    #[test]
+    fn noisy() {
+        let reference = reference();
+        let mut degraded = degraded(&reference);
+        let amplitudes = amplitudes(REFERENCE_LEN);
+        for (n, (sample, noise)) in degraded
+            .iter_mut()
+            .zip(noise(DEGRADED_LEN, 2))
+            .enumerate()
+            .skip(DELAY)
+        {
+            // 20dB below the signal.
+            *sample += 0.1 * GAIN * noise * amplitudes[(n - DELAY) % REFERENCE_LEN];
+        }
+        let metrics = analyze(&reference, &degraded).unwrap();
+        assert!((metrics.snr_db - 20.0).abs() < 1.0, "{:?}", metrics);
+        assert!(
+            (metrics.segmental_snr_db - 20.0).abs() < 1.0,
+            "{:?}",
+            metrics
+        );
+        assert_eq!(0, metrics.gap_count);
+        assert_eq!(0, metrics.concealed_ms);
+    }
+
+    #[test]
+    fn gaps_and_concealment() {
+        let reference = reference();
+        let mut degraded = degraded(&reference);
+        // 100ms of silence.
+        degraded[20000..21600].fill(0.0);
+        // 200ms of something else entirely.
+        let other = noise(3200, 3);
+        for (sample, other) in degraded[40000..43200].iter_mut().zip(other) {
+            *sample = GAIN * 0.3 * other;
+        }
+        let metrics = analyze(&reference, &degraded).unwrap();
+        assert_eq!(1, metrics.gap_count);
+        assert!((90..=100).contains(&metrics.gap_ms), "{:?}", metrics);
+        assert!((190..=220).contains(&metrics.concealed_ms), "{:?}", metrics);
+        assert!(metrics.segmental_snr_db < 34.0);
+    }
+
+    #[test]
+    fn silence() {
+        let reference = reference();
+        let metrics = analyze(&reference, &vec![0.0; DEGRADED_LEN]).unwrap();
+        assert_eq!(1, metrics.gap_count);
+        assert_eq!(metrics.active_ms, metrics.gap_ms);
+        assert_eq!(0, metrics.concealed_ms);
+
+        assert_eq!(None, analyze(&vec![0.0; REFERENCE_LEN], &reference));
+    }
+}
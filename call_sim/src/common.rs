@@ -5,9 +5,16 @@
 
 use std::{fmt, path::Path, time::Duration};
 
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
+
 /// ChartDimension is used for summary reports, to help automate the summary charting and
 /// display of most tracked `dimensions` that are available.
 #[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ChartDimension {
     MosSpeech,
     MosAudio,
@@ -54,6 +61,10 @@ pub enum ChartDimension {
     VideoReceiveBitrate,
     VideoReceiveFramerate,
     VideoReceiveKeyFramesDecoded,
+
+    AudioDelay,
+    VideoDelay,
+    AudioVideoOffset,
 }
 
 impl ChartDimension {
@@ -112,6 +123,9 @@ impl ChartDimension {
             ChartDimension::VideoReceiveBitrate => ("Video Received Bitrate", "kbps"),
             ChartDimension::VideoReceiveFramerate => ("Received Framerate", "fps"),
             ChartDimension::VideoReceiveKeyFramesDecoded => ("Key Frames Decoded", "frames"),
+            ChartDimension::AudioDelay => ("Audio Delay", "milliseconds"),
+            ChartDimension::VideoDelay => ("Video Delay", "milliseconds"),
+            ChartDimension::AudioVideoOffset => ("Audio/Video Offset", "milliseconds"),
         }
     }
 
@@ -156,11 +170,15 @@ impl ChartDimension {
             ChartDimension::VideoReceiveBitrate => "video_receive_bitrate",
             ChartDimension::VideoReceiveFramerate => "video_receive_framerate",
             ChartDimension::VideoReceiveKeyFramesDecoded => "video_key_frames_decoded",
+            ChartDimension::AudioDelay => "audio_delay",
+            ChartDimension::VideoDelay => "video_delay",
+            ChartDimension::AudioVideoOffset => "audio_video_offset",
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SummaryReportColumns {
     pub show_visqol_mos_speech: bool,
     pub show_visqol_mos_audio: bool,
@@ -184,19 +202,21 @@ impl Default for SummaryReportColumns {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct GroupConfig {
     /// A name to distinguish this group from others.
     pub group_name: String,
     /// Specify the charts to be displayed in the summary report for the group.
     pub chart_dimensions: Vec<ChartDimension>,
     /// The labels to use for the charts on the x-axis.
-    pub x_labels: &'static [&'static str],
+    pub x_labels: Vec<String>,
     /// Columns to show in summary reports.
     pub summary_report_columns: SummaryReportColumns,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TestCaseConfig {
     /// A name to give the test case uniqueness among others.
     pub test_case_name: String,
@@ -207,7 +227,7 @@ pub struct TestCaseConfig {
     /// The overall configuration specific to client B.
     pub client_b_config: CallConfig,
     /// A flag to control recording of packet capture. Enabling this results in a `tcpdump.pcap`
-    /// file among the generated artifacts for the test.
+    /// file among the generated artifacts for the test, which is analyzed for the reports.
     pub tcp_dump: bool,
     /// The number of times to run the test case.
     pub iterations: u16,
@@ -217,6 +237,9 @@ pub struct TestCaseConfig {
     /// Whether to save media files in the output. This takes time and disk space and is sometimes
     /// not needed.
     pub save_media_files: bool,
+    /// More clients to join group calls, named client_c, client_d, and so on. Like client B,
+    /// they receive client A's audio, which is how their audio is analyzed.
+    pub extra_clients: Vec<ExtraClientConfig>,
 }
 
 impl Default for TestCaseConfig {
@@ -230,11 +253,41 @@ impl Default for TestCaseConfig {
             iterations: 1,
             create_charts: true,
             save_media_files: true,
+            extra_clients: vec![],
         }
     }
 }
 
-#[derive(Clone, Debug)]
+impl TestCaseConfig {
+    /// The configurations of all clients, starting with client A and client B.
+    pub fn client_configs(&self) -> impl Iterator<Item = &CallConfig> {
+        [&self.client_a_config, &self.client_b_config]
+            .into_iter()
+            .chain(self.extra_clients.iter().map(|client| &client.call_config))
+    }
+
+    pub fn client_configs_mut(&mut self) -> impl Iterator<Item = &mut CallConfig> {
+        [&mut self.client_a_config, &mut self.client_b_config]
+            .into_iter()
+            .chain(
+                self.extra_clients
+                    .iter_mut()
+                    .map(|client| &mut client.call_config),
+            )
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExtraClientConfig {
+    pub call_config: CallConfig,
+    /// The network to emulate for this client. If not given, the test case's network profile
+    /// is used, as it is for client A and client B.
+    pub network_profile: Option<NetworkProfile>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CallProfile {
     /// Don't set any special profile for the call.
     None,
@@ -245,7 +298,8 @@ pub enum CallProfile {
 }
 
 /// General structure for configuration settings to send to the cli.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CallConfig {
     /// The maximum bitrate allowed for the call (audio and video).
     pub allowed_bitrate_kbps: u16,
@@ -272,6 +326,121 @@ pub struct CallConfig {
     pub stats_initial_offset_secs: u16,
     /// Application of a profile for the call, to be set in the client.
     pub profile: CallProfile,
+    /// When to join a group call, in seconds from the start of the test.
+    pub join_offset_secs: u16,
+    /// When to leave a group call, in seconds from the start of the test. If not given, the
+    /// client stays until the end.
+    pub leave_offset_secs: Option<u16>,
+    /// Actions to take between joining and leaving, such as muting or changing the data mode.
+    /// Clients start unmuted, with video on if they have video to send.
+    pub timeline: Vec<TimelineAction>,
+    /// The older way of muting and unmuting audio, still accepted in test sets. Its changes
+    /// are moved into the `timeline` when the test set is loaded.
+    pub mute_schedule: Vec<MuteChange>,
+    /// Embeds timing markers in the media this client sends: chirps in its audio and a
+    /// timestamp in its video frames. Only used for client A, whose media client B measures
+    /// the one-way delay of.
+    pub timing_markers: bool,
+    /// Exports the client's stats as JSON lines to `<client>.stats.jsonl` in the test case
+    /// directory, as they are posted to the log file. The report reads them from there.
+    pub export_stats: bool,
+    /// How often to hang up and rejoin a group call, for as long as the client is in it.
+    pub reconnect_interval_secs: Option<u16>,
+    /// How often to sample the client's resources and RingRTC's internal counters, for soak
+    /// tests. Series that grow steadily over the test are flagged in the report.
+    pub soak_sample_interval_secs: Option<u16>,
+}
+
+/// Mutes or unmutes a client's audio, in seconds from the start of the test.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MuteChange {
+    pub offset_secs: u16,
+    pub muted: bool,
+}
+
+impl From<MuteChange> for TimelineAction {
+    fn from(change: MuteChange) -> Self {
+        Self {
+            offset_secs: change.offset_secs,
+            action: if change.muted {
+                Action::Mute
+            } else {
+                Action::Unmute
+            },
+        }
+    }
+}
+
+/// An action a client takes during the call, in seconds from the start of the test.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineAction {
+    pub offset_secs: u16,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Action {
+    Mute,
+    Unmute,
+    VideoOn,
+    VideoOff,
+    /// Marks the video being sent as a screenshare (the video itself stays the same).
+    ScreenshareStart,
+    ScreenshareStop,
+    DataMode(DataModeConfig),
+    Hangup,
+    /// Joins a group call again after hanging up.
+    Rejoin,
+    RaiseHand,
+    LowerHand,
+    /// Sends a reaction, such as an emoji.
+    React(String),
+}
+
+impl Action {
+    /// Whether the action can only be taken in group calls.
+    pub fn is_group_call_only(&self) -> bool {
+        matches!(
+            self,
+            Action::Rejoin | Action::RaiseHand | Action::LowerHand | Action::React(_)
+        )
+    }
+}
+
+/// Formatted the way `call_sim-cli` expects in its `--action` argument.
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Mute => write!(f, "mute"),
+            Action::Unmute => write!(f, "unmute"),
+            Action::VideoOn => write!(f, "video-on"),
+            Action::VideoOff => write!(f, "video-off"),
+            Action::ScreenshareStart => write!(f, "screenshare-start"),
+            Action::ScreenshareStop => write!(f, "screenshare-stop"),
+            Action::DataMode(DataModeConfig::Low) => write!(f, "data-mode=low"),
+            Action::DataMode(DataModeConfig::Normal) => write!(f, "data-mode=normal"),
+            Action::DataMode(DataModeConfig::MaxBitrateKbps(kbps)) => {
+                write!(f, "data-mode={}", kbps)
+            }
+            Action::Hangup => write!(f, "hangup"),
+            Action::Rejoin => write!(f, "rejoin"),
+            Action::RaiseHand => write!(f, "raise-hand"),
+            Action::LowerHand => write!(f, "lower-hand"),
+            Action::React(value) => write!(f, "react={}", value),
+        }
+    }
+}
+
+/// The data mode to change to, limiting the bitrate used for media.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum DataModeConfig {
+    Low,
+    Normal,
+    MaxBitrateKbps(u32),
 }
 
 impl CallConfig {
@@ -307,12 +476,21 @@ impl Default for CallConfig {
             stats_interval_secs: 1,
             stats_initial_offset_secs: 0,
             profile: CallProfile::None,
+            join_offset_secs: 0,
+            leave_offset_secs: None,
+            timeline: vec![],
+            mute_schedule: vec![],
+            timing_markers: false,
+            export_stats: false,
+            reconnect_interval_secs: None,
+            soak_sample_interval_secs: None,
         }
     }
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 #[repr(i32)]
 pub enum AudioBandwidth {
     // Constants in libopus.
@@ -338,7 +516,8 @@ impl fmt::Display for AudioBandwidth {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AudioAnalysisMode {
     /// Skip audio analysis. Shows up as None in reports.
     None,
@@ -353,7 +532,8 @@ pub enum AudioAnalysisMode {
 
 /// The configuration to use for all things related to audio. Note that the only audio/speech
 /// codec used is Opus, and most settings are specific for it.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AudioConfig {
     /// The name (without path or extension) of the audio file to use as source material.
     pub input_name: String,
@@ -409,6 +589,9 @@ pub struct AudioConfig {
     pub pesq_speech_analysis: bool,
     /// Flag to enable plc speech analysis.
     pub plc_speech_analysis: bool,
+    /// Flag to enable the built-in analysis (latency, SNR, gaps, and concealment), which runs
+    /// in-process on the raw audio and doesn't need any docker images.
+    pub builtin_analysis: bool,
     /// The mechanism to use when analyzing speech/audio.
     pub analysis_mode: AudioAnalysisMode,
     /// Sometimes spectrogram generation takes too long, so we might want to disable it.
@@ -425,6 +608,12 @@ impl AudioConfig {
     pub fn requires_speech(&self) -> bool {
         self.visqol_speech_analysis || self.pesq_speech_analysis || self.plc_speech_analysis
     }
+
+    /// Whether the received audio has to be converted to wav, which the built-in analysis
+    /// doesn't need.
+    pub fn requires_wav(&self) -> bool {
+        self.visqol_audio_analysis || self.requires_speech() || self.generate_spectrogram
+    }
 }
 
 impl Default for AudioConfig {
@@ -459,13 +648,15 @@ impl Default for AudioConfig {
             visqol_audio_analysis: false,
             pesq_speech_analysis: false,
             plc_speech_analysis: false,
+            builtin_analysis: false,
             analysis_mode: AudioAnalysisMode::Normal,
             generate_spectrogram: true,
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct VideoConfig {
     /// The name (without path or extension) of the video file to use as source material.
     pub input_name: Option<String>,
@@ -500,17 +691,26 @@ impl VideoConfig {
 }
 
 /// A NetworkConfig item to be applied at a particular time offset.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct NetworkConfigWithOffset {
-    /// The offset is a duration, but in practice it will be quantized to 1 second.
+    /// The offset is a duration, but in practice it will be quantized to 1 second. Test set
+    /// files give it in whole seconds as `offset_secs`.
+    #[serde(rename = "offset_secs", deserialize_with = "deserialize_secs")]
     pub offset: Duration,
     /// The network configuration to apply at the given time.
+    #[serde(default)]
     pub network_config: NetworkConfig,
 }
 
+fn deserialize_secs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_secs)
+}
+
 /// General structure for network emulation settings.
 /// (see https://manpages.ubuntu.com/manpages/jammy/man8/tc-netem.8.html)
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct NetworkConfig {
     /// ms (if 0, won't be used)
     pub delay: u32,
@@ -541,7 +741,8 @@ pub struct NetworkConfig {
 }
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DelayVariationStrategy {
     /// %
     Correlation(u8),
@@ -551,7 +752,8 @@ pub enum DelayVariationStrategy {
 
 /// A probability distribution which can be sampled from.
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Distribution {
     Uniform,
     Normal,
@@ -580,7 +782,8 @@ impl fmt::Display for Distribution {
 /// in. Generally one state (the "bad" state) will have higher packet loss. The probability of
 /// transitioning out of the bad state can be kept low to simulate bursty packet loss.
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum GeLossModel {
     Bernoulli {
         p: u8,
@@ -613,7 +816,8 @@ pub enum GeLossModel {
 /// State 3 to to burst losses.
 /// State 4 to independent losses.
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum MarkovLossModel {
     Bernoulli {
         p13: u8,
@@ -638,7 +842,8 @@ pub enum MarkovLossModel {
 }
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Loss {
     /// % of packets to drop.
     Percentage(u8),
@@ -711,6 +916,26 @@ pub enum NetworkProfile {
 }
 
 impl NetworkProfile {
+    /// The inverse of [`NetworkProfile::get_name`] for every profile but `Custom`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(NetworkProfile::None),
+            "default" => Some(NetworkProfile::Default),
+            "moderate" => Some(NetworkProfile::Moderate),
+            "international" => Some(NetworkProfile::International),
+            "spiky_loss" => Some(NetworkProfile::SpikyLoss),
+            _ => {
+                if let Some(loss) = name.strip_prefix("simple_loss_") {
+                    loss.parse().ok().map(NetworkProfile::SimpleLoss)
+                } else if let Some(rate) = name.strip_prefix("limited_bandwidth_") {
+                    rate.parse().ok().map(NetworkProfile::LimitedBandwidth)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
     pub fn get_name(&self) -> String {
         match self {
             NetworkProfile::None => "none".to_string(),
@@ -830,6 +1055,44 @@ impl NetworkProfile {
     }
 }
 
+/// In test set files, a network profile is either given by its name (as in reports, such as
+/// `"moderate"` or `"simple_loss_10"`), or is a custom profile with a `name` and a `timeline`.
+impl<'de> Deserialize<'de> for NetworkProfile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct CustomNetworkProfile {
+            name: String,
+            timeline: Vec<NetworkConfigWithOffset>,
+        }
+
+        struct NetworkProfileVisitor;
+
+        impl<'de> Visitor<'de> for NetworkProfileVisitor {
+            type Value = NetworkProfile;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(
+                    "a network profile name (such as \"moderate\" or \"simple_loss_10\") or a custom profile with a name and a timeline",
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                NetworkProfile::from_name(name)
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(name), &self))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let CustomNetworkProfile { name, timeline } =
+                    CustomNetworkProfile::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(NetworkProfile::Custom(name, timeline))
+            }
+        }
+
+        deserializer.deserialize_any(NetworkProfileVisitor)
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientProfile {
@@ -24,7 +24,7 @@ use crate::{
         CallConfig, CallProfile, ClientProfile, DelayVariationStrategy, GeLossModel, Loss,
         MarkovLossModel, NetworkConfig,
     },
-    test::{CallTypeConfig, MediaFileIo},
+    test::{CallTypeConfig, MediaFileIo, CLIENT_NAMES},
 };
 
 /// This function builds all docker images that we need.
@@ -263,6 +263,8 @@ pub async fn start_client(name: &str, report_path: &str, media_path: &str) -> Re
             "--privileged",
             "--network",
             "ringrtc_default",
+            "--ip",
+            &client_ip(name),
             "-v",
             &format!("{}:/report", report_path),
             "-v",
@@ -684,8 +686,6 @@ pub async fn start_cli(
     profile: bool,
 ) -> Result<()> {
     println!("Starting cli for `{}`", name);
-    let log_file_arg = format!("/report/{}.log", name);
-    let input_file_arg = format!("/media/{}", media_io.audio_input_file);
 
     let mut args = ["exec", "-d", name].map(String::from).to_vec();
 
@@ -709,20 +709,60 @@ pub async fn start_cli(
         );
     }
 
-    args.extend_from_slice(
-        &[
-            "call_sim-cli",
-            "--name",
-            name,
-            "--log-file",
-            &log_file_arg,
-            "--input-file",
-            &input_file_arg,
-        ]
-        .map(String::from),
-    );
+    args.push("call_sim-cli".to_string());
+    args.extend(cli_args(
+        name,
+        media_io,
+        call_config,
+        remote_call_config,
+        client_profile,
+        call_type,
+        "/report",
+        "/media",
+    )?);
+
+    println!("Final Client args: {}", args.join(" "));
+    let _ = Command::new("docker").args(&args).spawn()?.wait().await?;
+
+    Ok(())
+}
+
+/// Clients have addresses on the test network in the order of their names, starting with
+/// client A at 172.28.0.2.
+fn client_ip(name: &str) -> String {
+    let index = CLIENT_NAMES
+        .iter()
+        .position(|client_name| *client_name == name)
+        .expect("clients are named from CLIENT_NAMES");
+    format!("172.28.0.{}", index + 2)
+}
+
+/// Builds the arguments for a `call_sim-cli` endpoint, reading media from `media_dir` and writing
+/// logs and output media to `report_dir`.
+#[allow(clippy::too_many_arguments)]
+pub fn cli_args(
+    name: &str,
+    media_io: MediaFileIo,
+    call_config: &CallConfig,
+    remote_call_config: &CallConfig,
+    client_profile: &ClientProfile,
+    call_type: &CallTypeConfig,
+    report_dir: &str,
+    media_dir: &str,
+) -> Result<Vec<String>> {
+    let mut args = vec![
+        "--name".to_string(),
+        name.to_string(),
+        "--log-file".to_string(),
+        format!("{}/{}.log", report_dir, name),
+        "--input-file".to_string(),
+        format!("{}/{}", media_dir, media_io.audio_input_file),
+    ];
     if let Some(audio_output_file) = media_io.audio_output_file {
-        args.push(format!("--output-file=/report/{}", audio_output_file));
+        args.push(format!(
+            "--output-file={}/{}",
+            report_dir, audio_output_file
+        ));
     }
 
     args.push("--stats-interval-secs".to_string());
@@ -821,10 +861,16 @@ pub async fn start_cli(
     ));
 
     if let Some(input_video_file) = media_io.video_input_file {
-        args.push(format!("--input-video-file=/media/{}", input_video_file));
+        args.push(format!(
+            "--input-video-file={}/{}",
+            media_dir, input_video_file
+        ));
     }
     if let Some(output_video_file) = media_io.video_output_file {
-        args.push(format!("--output-video-file=/report/{}", output_video_file));
+        args.push(format!(
+            "--output-video-file={}/{}",
+            report_dir, output_video_file
+        ));
     }
 
     if let Some((width, height)) = remote_call_config.video.dimensions() {
@@ -832,16 +878,47 @@ pub async fn start_cli(
         args.push(format!("--output-video-height={}", height));
     }
 
-    if name == "client_a" {
-        args.push("--ip=172.28.0.2".to_string());
-    } else {
-        args.push("--ip=172.28.0.3".to_string());
+    // Both sides log markers: the sender when its audio starts, the receiver what it received.
+    if call_config.timing_markers || remote_call_config.timing_markers {
+        args.push("--timing-markers".to_string());
+    }
+
+    if call_config.export_stats {
+        args.push(format!(
+            "--stats-export-file={}/{}.stats.jsonl",
+            report_dir, name
+        ));
     }
 
+    if let Some(interval) = call_config.soak_sample_interval_secs {
+        args.push(format!("--soak-sample-interval-secs={}", interval));
+    }
+
+    args.push(format!("--ip={}", client_ip(name)));
+
     if let CallProfile::DeterministicLoss(loss_rate) = call_config.profile {
         args.push(format!("--deterministic-loss={}", loss_rate));
     }
 
+    if call_config.join_offset_secs > 0 {
+        args.push(format!(
+            "--join-offset-secs={}",
+            call_config.join_offset_secs
+        ));
+    }
+    if let Some(leave_offset_secs) = call_config.leave_offset_secs {
+        args.push(format!("--leave-offset-secs={}", leave_offset_secs));
+    }
+    if let Some(interval) = call_config.reconnect_interval_secs {
+        args.push(format!("--reconnect-interval-secs={}", interval));
+    }
+    for timeline_action in &call_config.timeline {
+        args.push(format!(
+            "--action={}:{}",
+            timeline_action.offset_secs, timeline_action.action
+        ));
+    }
+
     args.extend(call_config.extra_cli_args.iter().cloned());
 
     args.push(format!("--user-id={}", client_profile.user_id));
@@ -880,10 +957,7 @@ pub async fn start_cli(
         args.push(format!("--group-member-info={}", member_info));
     }
 
-    println!("Final Client args: {}", args.join(" "));
-    let _ = Command::new("docker").args(&args).spawn()?.wait().await?;
-
-    Ok(())
+    Ok(args)
 }
 
 pub async fn finish_perf(client: &str) -> Result<()> {
@@ -4,11 +4,17 @@
 //
 
 mod audio;
+mod audio_metrics;
 mod common;
 mod config;
 mod docker;
+mod markers;
+mod pcap;
 mod report;
+mod results;
+mod simnet;
 mod test;
+mod test_set;
 
 use std::{
     env,
@@ -17,7 +23,7 @@ use std::{
     time::{Duration, SystemTime},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use common::ClientProfile;
 use hex::FromHex;
@@ -30,7 +36,9 @@ use crate::{
         TestCaseConfig, VideoConfig,
     },
     docker::{build_images, clean_network, clean_up},
-    test::{CallTypeConfig, Test},
+    results::{compare_with_baseline, SetResults, Tolerances},
+    test::{Backend, CallTypeConfig, Test, CLIENT_NAMES},
+    test_set::TestSet,
 };
 
 fn compile_time_root_directory() -> &'static std::ffi::OsStr {
@@ -42,9 +50,14 @@ fn compile_time_root_directory() -> &'static std::ffi::OsStr {
 
 #[derive(Parser, Debug)]
 struct Args {
-    /// Specifies which tests to run.
+    /// Specifies which tests to run. Each is the name of a built-in test set, the name of a file
+    /// in the test set directory (without its extension), or the path to a TOML or YAML file.
     test_sets: Vec<String>,
 
+    /// The directory with test set files, relative to the root.
+    #[arg(long, default_value = "call_sim/test_sets")]
+    test_set_dir: String,
+
     /// Specifies path to the root of the ringrtc directory.
     /// Usually relative when provided on the command line.
     #[arg(long, default_value = compile_time_root_directory())]
@@ -79,6 +92,32 @@ struct Args {
     /// Run `perf record` on the clients
     #[arg(long)]
     profile: bool,
+
+    /// Runs both clients in one `call_sim-cli` process on a simulated network, instead of in
+    /// Docker containers with netem. Group calls, relay servers, tcpdump and profiling aren't
+    /// supported, and neither are some netem settings.
+    #[arg(long, conflicts_with = "profile")]
+    simnet: bool,
+
+    /// The `call_sim-cli` binary to use with `--simnet`, relative to the root. It must be built
+    /// with the `call_sim` feature.
+    #[arg(long, default_value = "target/release/call_sim-cli")]
+    simnet_cli: String,
+
+    /// A directory of baseline results to compare each test set against, named
+    /// `<test set name>.json` (copied from the `results.json` of a good run). Any regression
+    /// makes the simulator exit with an error once all test sets have run.
+    #[arg(long)]
+    baseline_dir: Option<String>,
+
+    /// A TOML or YAML file of tolerances for comparing against baselines, which take precedence
+    /// over the defaults.
+    #[arg(long, requires = "baseline_dir")]
+    tolerances: Option<String>,
+
+    /// Compares existing `results.json` files against the baselines instead of running tests.
+    #[arg(long, requires = "baseline_dir", num_args = 1..)]
+    compare: Vec<String>,
 }
 
 // Set these two values when running call sim group calls. The Auth Key is used to generate profiles
@@ -125,74 +164,6 @@ async fn run_minimal_example(test: &mut Test) -> Result<()> {
     Ok(())
 }
 
-// This is a test set to test the "normal phrasing" audio over various network profiles. Set
-// packet time to 60ms to align with our production configuration.
-async fn run_baseline(test: &mut Test) -> Result<()> {
-    test.run(
-        GroupConfig {
-            group_name: "baseline".to_string(),
-            // Show all the different measurements in the summary columns. Hide video.
-            summary_report_columns: SummaryReportColumns {
-                show_visqol_mos_speech: true,
-                show_visqol_mos_audio: true,
-                show_visqol_mos_average: true,
-                show_pesq_mos: true,
-                show_plc_mos: true,
-                show_video: false,
-            },
-            ..Default::default()
-        },
-        vec![TestCaseConfig {
-            test_case_name: "ptime-60".to_string(),
-            client_a_config: CallConfig {
-                audio: AudioConfig {
-                    input_name: "normal_phrasing".to_string(),
-                    initial_packet_size_ms: 60,
-                    // We don't look at analysis from client_a's point of view, so there
-                    // is no need to generate anything for it.
-                    generate_spectrogram: false,
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            client_b_config: CallConfig {
-                audio: AudioConfig {
-                    input_name: "normal_phrasing".to_string(),
-                    initial_packet_size_ms: 60,
-                    // Calculate all mos values for these audio tests.
-                    visqol_speech_analysis: true,
-                    visqol_audio_analysis: true,
-                    pesq_speech_analysis: true,
-                    plc_speech_analysis: true,
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            // Run 3 iterations of each test to get an average to help contain the
-            // non-deterministic behavior of the tests.
-            iterations: 3,
-            ..Default::default()
-        }],
-        vec![
-            NetworkProfile::Default,
-            NetworkProfile::Moderate,
-            NetworkProfile::International,
-            NetworkProfile::SpikyLoss,
-            NetworkProfile::LimitedBandwidth(100),
-            NetworkProfile::LimitedBandwidth(50),
-            NetworkProfile::LimitedBandwidth(25),
-            NetworkProfile::SimpleLoss(10),
-            NetworkProfile::SimpleLoss(20),
-            NetworkProfile::SimpleLoss(30),
-            NetworkProfile::SimpleLoss(40),
-            NetworkProfile::SimpleLoss(50),
-        ],
-    )
-    .await?;
-
-    Ok(())
-}
-
 // Test 20ms and 60ms ptime over a range of deterministic loss, with and without dtx.
 // Note that deterministic loss is better than the SimpleLoss network profile, but
 // it is still not completely reliable.
@@ -258,130 +229,6 @@ async fn run_deterministic_loss_test(test: &mut Test) -> Result<()> {
     Ok(())
 }
 
-// Here is a test to run without a TURN server, with a TURN server, and forcing the use of a
-// TURN server over UDP, and then forcing the use of a TURN server over TCP.
-// Notes:
-//  - The default username and password are already set by default
-//  - Both clients will use the TURN server (in this test)
-//  - The `turn` domain name should resolve by Docker to the container with the name `turn`
-async fn run_relay_tests(test: &mut Test) -> Result<()> {
-    test.run(
-        GroupConfig {
-            group_name: "relay_tests".to_string(),
-            summary_report_columns: SummaryReportColumns {
-                show_visqol_mos_audio: false,
-                show_video: false,
-                ..Default::default()
-            },
-            chart_dimensions: vec![ChartDimension::MosSpeech],
-            ..Default::default()
-        },
-        vec![
-            TestCaseConfig {
-                test_case_name: "no_relay".to_string(),
-                client_a_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            TestCaseConfig {
-                test_case_name: "with_relay".to_string(),
-                client_a_config: CallConfig {
-                    relay_servers: vec![
-                        "turn:turn".to_string(),
-                        "turn:turn:80?transport=tcp".to_string(),
-                    ],
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    relay_servers: vec![
-                        "stun:turn".to_string(),
-                        "turn:turn".to_string(),
-                        "turn:turn:80?transport=tcp".to_string(),
-                    ],
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            TestCaseConfig {
-                test_case_name: "force_udp_relay".to_string(),
-                client_a_config: CallConfig {
-                    relay_servers: vec!["turn:turn".to_string()],
-                    force_relay: true,
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    relay_servers: vec!["turn:turn".to_string()],
-                    force_relay: true,
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            TestCaseConfig {
-                test_case_name: "force_tcp_relay".to_string(),
-                client_a_config: CallConfig {
-                    relay_servers: vec!["turn:turn:80?transport=tcp".to_string()],
-                    force_relay: true,
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    relay_servers: vec!["turn:turn:80?transport=tcp".to_string()],
-                    force_relay: true,
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        initial_packet_size_ms: 60,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-        ],
-        vec![NetworkProfile::None],
-    )
-    .await?;
-
-    Ok(())
-}
-
 // A test that sends video.
 async fn run_video_send_over_bandwidth(test: &mut Test) -> Result<()> {
     test.preprocess_sounds(vec!["normal_phrasing"]).await?;
@@ -428,77 +275,6 @@ async fn run_video_send_over_bandwidth(test: &mut Test) -> Result<()> {
     Ok(())
 }
 
-// Bidirectional video test comparing the vp8 and vp9 video codecs.
-async fn run_video_compare_vp8_vs_vp9(test: &mut Test) -> Result<()> {
-    test.preprocess_sounds(vec!["normal_phrasing"]).await?;
-
-    test.run(
-        GroupConfig {
-            group_name: "video_compare_vp8_vs_vp9".to_string(),
-            chart_dimensions: vec![ChartDimension::MosSpeech],
-            ..Default::default()
-        },
-        vec![
-            TestCaseConfig {
-                test_case_name: "vp8".to_string(),
-                client_a_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        ..Default::default()
-                    },
-                    video: VideoConfig {
-                        input_name: Some("ConferenceMotion_50fps@1280x720".to_string()),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        ..Default::default()
-                    },
-                    video: VideoConfig {
-                        input_name: Some("ConferenceMotion_50fps@1280x720".to_string()),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-            TestCaseConfig {
-                test_case_name: "vp9".to_string(),
-                client_a_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        ..Default::default()
-                    },
-                    video: VideoConfig {
-                        input_name: Some("ConferenceMotion_50fps@1280x720".to_string()),
-                        enable_vp9: true,
-                    },
-                    ..Default::default()
-                },
-                client_b_config: CallConfig {
-                    audio: AudioConfig {
-                        input_name: "normal_phrasing".to_string(),
-                        ..Default::default()
-                    },
-                    video: VideoConfig {
-                        input_name: Some("ConferenceMotion_50fps@1280x720".to_string()),
-                        enable_vp9: true,
-                    },
-                    ..Default::default()
-                },
-                ..Default::default()
-            },
-        ],
-        vec![NetworkProfile::Default],
-    )
-    .await?;
-
-    Ok(())
-}
-
 // Test the scenario with changing bandwidth over one minute intervals:
 // 1 minute unlimited -> 1 minute 50kbps -> 1 minute 25kbps -> 1 minute unlimited
 //
@@ -687,31 +463,169 @@ async fn run_perf_test(test: &mut Test) -> Result<()> {
     Ok(())
 }
 
+/// A call that runs for hours, with client B sampling its resources to find any that keep
+/// growing. In group calls, client B also reconnects every so often.
+async fn run_soak_test(test: &mut Test) -> Result<()> {
+    let reconnect_interval_secs = test.is_group_call().then_some(15 * 60);
+
+    test.run(
+        GroupConfig {
+            group_name: "soak_test".to_string(),
+            summary_report_columns: SummaryReportColumns {
+                show_visqol_mos_speech: false,
+                show_visqol_mos_audio: false,
+                show_visqol_mos_average: false,
+                show_pesq_mos: false,
+                show_plc_mos: false,
+                show_video: false,
+            },
+            ..Default::default()
+        },
+        vec![TestCaseConfig {
+            test_case_name: "four_hours".to_string(),
+            length_seconds: 4 * 60 * 60,
+            save_media_files: false,
+            client_a_config: CallConfig {
+                audio: AudioConfig {
+                    input_name: "speaker_b".to_string(),
+                    generate_spectrogram: false,
+                    visqol_speech_analysis: false,
+                    visqol_audio_analysis: false,
+                    pesq_speech_analysis: false,
+                    plc_speech_analysis: false,
+                    analysis_mode: AudioAnalysisMode::None,
+                    ..Default::default()
+                },
+                video: VideoConfig {
+                    input_name: Some("ConferenceMotion_50fps@1280x720".to_string()),
+                    ..Default::default()
+                },
+                // Keep the logs to a manageable size.
+                stats_interval_secs: 10,
+                ..Default::default()
+            },
+            client_b_config: CallConfig {
+                audio: AudioConfig {
+                    input_name: "normal_phrasing".to_string(),
+                    generate_spectrogram: false,
+                    visqol_speech_analysis: false,
+                    visqol_audio_analysis: false,
+                    pesq_speech_analysis: false,
+                    plc_speech_analysis: false,
+                    analysis_mode: AudioAnalysisMode::None,
+                    ..Default::default()
+                },
+                stats_interval_secs: 10,
+                reconnect_interval_secs,
+                soak_sample_interval_secs: Some(60),
+                ..Default::default()
+            },
+            iterations: 1,
+            ..Default::default()
+        }],
+        vec![NetworkProfile::None],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Test sets that are written in Rust above. Others are loaded from files.
+const BUILT_IN_TEST_SETS: [&str; 6] = [
+    "minimal_example",
+    "deterministic_loss_test",
+    "video_send_over_bandwidth",
+    "changing_bandwidth_audio_test",
+    "profiling_suite",
+    "soak_suite",
+];
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
     println!("Starting the call simulator...");
 
-    let mut root_path = env::current_dir()?;
-    root_path.push(args.root);
+    let launch_path = env::current_dir()?;
+    let root_path = launch_path.join(args.root);
     println!("  Using root path: {}", root_path.display());
     env::set_current_dir(&root_path)?;
 
+    // Paths given on the command line are relative to where we were started.
+    let baseline_dir = args.baseline_dir.map(|dir| launch_path.join(dir));
+    let tolerances = match args.tolerances {
+        Some(path) => Tolerances::load(&launch_path.join(path))?,
+        None => Tolerances::default(),
+    };
+    let mut passed = true;
+
+    if let Some(baseline_dir) = &baseline_dir {
+        if !args.compare.is_empty() {
+            for path in args.compare {
+                let results = SetResults::load(&launch_path.join(path))?;
+                passed &= compare_with_baseline(&results, baseline_dir, &tolerances)?;
+            }
+            if !passed {
+                bail!("results regressed from the baseline");
+            }
+            return Ok(());
+        }
+    }
+
+    let mut test_set_names = args.test_sets;
+    if test_set_names.is_empty() {
+        // For quick testing, change this to the name of your test case.
+        test_set_names.push("baseline".to_string());
+    }
+
+    let direct_call_config = CallTypeConfig::Direct;
+    let group_call_config = CallTypeConfig::Group {
+        sfu_url: SFU_URL.to_owned(),
+        group_name: args.group_name,
+    };
+
+    // Load any test set files before doing anything else, so that mistakes in them show up
+    // right away rather than partway through a long run.
+    let test_set_dir = root_path.join(&args.test_set_dir);
+    let test_sets = test_set_names
+        .into_iter()
+        .map(|test_set_name| {
+            let (call_type_config, test_set_name) =
+                if let Some(name) = test_set_name.strip_prefix("group_") {
+                    (group_call_config.clone(), name.to_owned())
+                } else {
+                    (direct_call_config.clone(), test_set_name)
+                };
+            if BUILT_IN_TEST_SETS.contains(&test_set_name.as_str()) {
+                return Ok((call_type_config, test_set_name, None));
+            }
+            let Some(path) = TestSet::find(&test_set_dir, &test_set_name) else {
+                bail!(
+                    "unknown test set \"{test_set_name}\" (it is not built in and not in {})",
+                    test_set_dir.display()
+                );
+            };
+            let test_set = TestSet::load(&launch_path.join(path))?;
+            Ok((call_type_config, test_set.name.clone(), Some(test_set)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
     if args.build {
         build_images().await?;
     }
 
+    let backend = if args.simnet {
+        Backend::Simnet {
+            cli_path: root_path.join(&args.simnet_cli).display().to_string(),
+        }
+    } else {
+        Backend::Docker
+    };
+
     if args.clean {
-        clean_up(vec![
-            "client_a",
-            "client_b",
-            "signaling_server",
-            "turn",
-            "tcpdump",
-            "visqol",
-        ])
-        .await?;
+        let mut containers = CLIENT_NAMES.to_vec();
+        containers.extend(["signaling_server", "turn", "tcpdump", "visqol"]);
+        clean_up(containers).await?;
         clean_network().await?;
     }
 
@@ -721,25 +635,7 @@ async fn main() -> Result<()> {
             get_client_profiles(&client_profile_dir)
         });
 
-    let mut test_sets = args.test_sets;
-    if test_sets.is_empty() {
-        // For quick testing, change this to the name of your test case.
-        test_sets.push("baseline".to_string());
-    }
-
-    let direct_call_config = CallTypeConfig::Direct;
-    let group_call_config = CallTypeConfig::Group {
-        sfu_url: SFU_URL.to_owned(),
-        group_name: args.group_name,
-    };
-
-    for test_set_name in test_sets {
-        let (call_type_config, test_set_name) =
-            if let Some(name) = test_set_name.strip_prefix("group_") {
-                (group_call_config.clone(), name.to_owned())
-            } else {
-                (direct_call_config.clone(), test_set_name)
-            };
+    for (call_type_config, test_set_name, test_set) in test_sets {
         println!(
             "Running test set {} as call type {:?}",
             test_set_name, call_type_config,
@@ -752,19 +648,29 @@ async fn main() -> Result<()> {
             client_profiles.clone(),
             call_type_config,
             args.profile,
+            backend.clone(),
         )?;
-        match test_set_name.as_str() {
-            "minimal_example" => run_minimal_example(test).await?,
-            "baseline" => run_baseline(test).await?,
-            "deterministic_loss_test" => run_deterministic_loss_test(test).await?,
-            "relay_tests" => run_relay_tests(test).await?,
-            "video_send_over_bandwidth" => run_video_send_over_bandwidth(test).await?,
-            "video_compare_vp8_vs_vp9" => run_video_compare_vp8_vs_vp9(test).await?,
-            "changing_bandwidth_audio_test" => run_changing_bandwidth_audio_test(test).await?,
-            "profiling_suite" => run_perf_test(test).await?,
-            _ => panic!("unknown test set \"{test_set_name}\""),
+        if let Some(test_set) = test_set {
+            test_set.run(test).await?;
+        } else {
+            match test_set_name.as_str() {
+                "minimal_example" => run_minimal_example(test).await?,
+                "deterministic_loss_test" => run_deterministic_loss_test(test).await?,
+                "video_send_over_bandwidth" => run_video_send_over_bandwidth(test).await?,
+                "changing_bandwidth_audio_test" => run_changing_bandwidth_audio_test(test).await?,
+                "profiling_suite" => run_perf_test(test).await?,
+                "soak_suite" => run_soak_test(test).await?,
+                _ => panic!("unknown test set \"{test_set_name}\""),
+            }
+        }
+        let results = test.report().await?;
+        if let Some(baseline_dir) = &baseline_dir {
+            passed &= compare_with_baseline(&results, baseline_dir, &tolerances)?;
         }
-        test.report().await?;
+    }
+
+    if !passed {
+        bail!("results regressed from the baseline");
     }
 
     Ok(())
@@ -772,7 +678,7 @@ async fn main() -> Result<()> {
 
 fn generate_client_profiles() -> Vec<ClientProfile> {
     let now = SystemTime::now();
-    config::generate_client_profiles(2, &group_auth_key_gen(), now)
+    config::generate_client_profiles(CLIENT_NAMES.len(), &group_auth_key_gen(), now)
 }
 
 fn get_client_profiles(dir_path: &str) -> Vec<ClientProfile> {
@@ -0,0 +1,274 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Chirps mixed into the audio client A sends, for measuring mouth-to-ear latency.
+//!
+//! A short chirp is added at every whole second of the sound, and the audio a client received is
+//! searched for them with a matched filter. The clients log when their audio starts playing
+//! (`ringrtc_marker!,<client>,audio_start,<unix ms>`), and since the file-based audio device
+//! starts recording and playing at the same time, that is also when client A started sending its
+//! sound. So each chirp found gives the one-way delay of the audio, although only modulo the
+//! spacing of the chirps. See `call_sim-cli`'s `markers` module for the video side.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::audio_metrics::{decode_raw, SAMPLE_RATE};
+
+/// Sounds with this suffix are the sound without it, with chirps added.
+pub const MARKED_SOUND_SUFFIX: &str = "_markers";
+
+/// The raw files are 48kHz, 16-bit, two-channel audio.
+const RAW_SAMPLE_RATE: usize = 48000;
+const RAW_CHANNELS: usize = 2;
+
+const CHIRP_INTERVAL_MS: usize = 1000;
+const CHIRP_MS: usize = 20;
+/// A rising sweep in the range that speech codecs keep, but above most of the energy of speech.
+const CHIRP_START_HZ: f64 = 2000.0;
+const CHIRP_END_HZ: f64 = 6000.0;
+const CHIRP_AMPLITUDE: f64 = 0.25;
+
+/// How well received audio must match a chirp for it to count (normalized cross-correlation).
+const DETECTION_THRESHOLD: f64 = 0.5;
+/// Windows with less energy than this fraction of a chirp's are too quiet to hold one.
+const MIN_CHIRP_ENERGY: f64 = 0.01;
+/// Chirps found closer together than this are the same chirp.
+const MIN_CHIRP_SPACING_MS: usize = CHIRP_INTERVAL_MS / 2;
+/// Chirps can be found slightly before their due time, because the clients' start times are
+/// only logged to within a few milliseconds. Delays are reported in
+/// [-EARLY_MS, CHIRP_INTERVAL_MS - EARLY_MS).
+const EARLY_MS: f64 = 50.0;
+
+/// The chirps found in the audio a client received.
+#[derive(Clone, Debug, Default)]
+pub struct ReceivedChirps {
+    /// When each chirp started, in ms from the start of the received audio.
+    pub positions_ms: Vec<f64>,
+    /// The length of the sound the chirps were sent in, which the sender loops.
+    pub sound_ms: f64,
+}
+
+/// A Hann-windowed linear sweep, in [-CHIRP_AMPLITUDE, CHIRP_AMPLITUDE].
+fn chirp(sample_rate: usize) -> Vec<f64> {
+    let len = sample_rate * CHIRP_MS / 1000;
+    let duration = CHIRP_MS as f64 / 1000.0;
+    let sweep_rate = (CHIRP_END_HZ - CHIRP_START_HZ) / duration;
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            let phase = 2.0 * std::f64::consts::PI * (CHIRP_START_HZ + sweep_rate * t / 2.0) * t;
+            let window = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / len as f64).cos();
+            CHIRP_AMPLITUDE * window * phase.sin()
+        })
+        .collect()
+}
+
+/// Where the chirps go in a sound of `len` samples. Every chirp is followed by at least a whole
+/// interval, even when the sound loops, so that received chirps can't be mistaken for another.
+fn chirp_starts(len: usize, sample_rate: usize) -> impl Iterator<Item = usize> {
+    let interval = sample_rate * CHIRP_INTERVAL_MS / 1000;
+    (0..len / interval).map(move |index| index * interval)
+}
+
+/// Mixes chirps into raw audio.
+pub fn add_chirps(raw: &[u8]) -> Vec<u8> {
+    let chirp = chirp(RAW_SAMPLE_RATE);
+    let mut samples: Vec<i16> = raw
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+        .collect();
+    for start in chirp_starts(samples.len() / RAW_CHANNELS, RAW_SAMPLE_RATE) {
+        for (offset, value) in chirp.iter().enumerate() {
+            let frame = (start + offset) * RAW_CHANNELS;
+            for sample in &mut samples[frame..frame + RAW_CHANNELS] {
+                *sample = (*sample as f64 + value * 32768.0)
+                    .round()
+                    .clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            }
+        }
+    }
+    samples
+        .into_iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect()
+}
+
+/// A first difference, which emphasizes the chirps over speech, most of which is lower.
+fn emphasize(samples: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut previous = 0.0;
+    samples
+        .map(|sample| {
+            let difference = sample - previous;
+            previous = sample;
+            difference
+        })
+        .collect()
+}
+
+/// Finds the chirps in 16kHz mono samples, returning when each started in ms.
+fn detect_chirps(samples: &[f32]) -> Vec<f64> {
+    let chirp = emphasize(chirp(SAMPLE_RATE).into_iter());
+    let samples = emphasize(samples.iter().map(|sample| *sample as f64));
+    let chirp_energy: f64 = chirp.iter().map(|value| value * value).sum();
+    let min_spacing = SAMPLE_RATE * MIN_CHIRP_SPACING_MS / 1000;
+
+    // Keep a running energy of the window so that the correlation can be normalized cheaply.
+    let mut window_energy: f64 = samples
+        .iter()
+        .take(chirp.len())
+        .map(|sample| sample.powi(2))
+        .sum();
+    let mut peaks: Vec<(usize, f64)> = vec![];
+    for (start, window) in samples.windows(chirp.len()).enumerate() {
+        if window_energy > MIN_CHIRP_ENERGY * chirp_energy {
+            let dot: f64 = window
+                .iter()
+                .zip(&chirp)
+                .map(|(sample, value)| sample * value)
+                .sum();
+            let correlation = dot / (window_energy * chirp_energy).sqrt();
+            if correlation >= DETECTION_THRESHOLD {
+                match peaks.last_mut() {
+                    Some(peak) if start - peak.0 < min_spacing => {
+                        if correlation > peak.1 {
+                            *peak = (start, correlation);
+                        }
+                    }
+                    _ => peaks.push((start, correlation)),
+                }
+            }
+        }
+        if let Some(next) = samples.get(start + chirp.len()) {
+            window_energy = (window_energy + next.powi(2) - window[0].powi(2)).max(0.0);
+        }
+    }
+
+    peaks
+        .into_iter()
+        .map(|(start, _)| start as f64 * 1000.0 / SAMPLE_RATE as f64)
+        .collect()
+}
+
+/// Finds the chirps in the raw audio a client received, which was sent from the chirped raw
+/// reference. If `length_seconds` is given, only that much of the received audio is searched.
+pub fn find_chirps_in_raw_files(
+    reference_path: &Path,
+    degraded_path: &Path,
+    length_seconds: Option<u16>,
+) -> Result<ReceivedChirps> {
+    let reference_len = fs::metadata(reference_path)
+        .with_context(|| format!("failed to read audio file {}", reference_path.display()))?
+        .len() as usize;
+    let mut degraded = fs::read(degraded_path)
+        .map(|bytes| decode_raw(&bytes))
+        .with_context(|| format!("failed to read audio file {}", degraded_path.display()))?;
+    if let Some(length_seconds) = length_seconds {
+        degraded.truncate(length_seconds as usize * SAMPLE_RATE);
+    }
+    Ok(ReceivedChirps {
+        positions_ms: detect_chirps(&degraded),
+        sound_ms: (reference_len / (2 * RAW_CHANNELS)) as f64 * 1000.0 / RAW_SAMPLE_RATE as f64,
+    })
+}
+
+/// The delay of a chirp that was received `elapsed_ms` after the sender started playing its
+/// sound, which is `sound_ms` long. Returns None if the sound is too short to have chirps.
+pub fn audio_delay_ms(elapsed_ms: f64, sound_ms: f64) -> Option<f64> {
+    let interval = CHIRP_INTERVAL_MS as f64;
+    let chirp_count = (sound_ms / interval).floor() as usize;
+    let position = elapsed_ms.rem_euclid(sound_ms);
+    (0..chirp_count)
+        .flat_map(|index| {
+            // A chirp at the start of the sound can arrive early, before the sound loops.
+            [0.0, sound_ms].map(|loop_start| position - (loop_start + index as f64 * interval))
+        })
+        .find(|delay| (-EARLY_MS..interval - EARLY_MS).contains(delay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mono 16kHz samples as raw audio.
+    fn encode_raw(samples: &[f32]) -> Vec<u8> {
+        samples
+            .iter()
+            .flat_map(|sample| {
+                let value = (sample * 32768.0) as i16;
+                // Upsample by repeating, in both channels.
+                [value; 6]
+            })
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    /// A vowel-like tone with some noise, standing in for speech.
+    fn speech(len: usize) -> Vec<f32> {
+        let mut seed = 7u32;
+        (0..len)
+            .map(|i| {
+                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                let noise = (seed >> 8) as f32 / (1 << 24) as f32 - 0.5;
+                let t = i as f32 / SAMPLE_RATE as f32;
+                0.2 * (2.0 * std::f32::consts::PI * 220.0 * t).sin() + 0.02 * noise
+            })
+            .collect()
+    }
+
+    fn assert_near(expected: &[f64], actual: &[f64]) {
+        assert_eq!(expected.len(), actual.len(), "{:?}", actual);
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert!((expected - actual).abs() <= 1.0, "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn finds_chirps() {
+        let chirped = decode_raw(&add_chirps(&encode_raw(&speech(SAMPLE_RATE * 3))));
+        assert_near(&[0.0, 1000.0, 2000.0], &detect_chirps(&chirped));
+    }
+
+    #[test]
+    fn finds_delayed_chirps() {
+        let chirped = decode_raw(&add_chirps(&encode_raw(&speech(SAMPLE_RATE * 3))));
+        // Received 123ms late, with the last chirp cut off by the end.
+        let mut received = vec![0.0; SAMPLE_RATE * 123 / 1000];
+        received.extend(&chirped[..SAMPLE_RATE * 2]);
+        assert_near(&[123.0, 1123.0], &detect_chirps(&received));
+    }
+
+    #[test]
+    fn ignores_speech() {
+        assert!(detect_chirps(&speech(SAMPLE_RATE * 3)).is_empty());
+        assert!(detect_chirps(&vec![0.0; SAMPLE_RATE]).is_empty());
+    }
+
+    #[test]
+    fn leaves_room_to_loop() {
+        // No chirp at 2s, since the sound would loop 500ms later.
+        let chirped = decode_raw(&add_chirps(&encode_raw(&speech(SAMPLE_RATE * 5 / 2))));
+        assert_near(&[0.0, 1000.0], &detect_chirps(&chirped));
+        assert!(chirp_starts(RAW_SAMPLE_RATE / 2, RAW_SAMPLE_RATE)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn delays() {
+        assert_eq!(Some(120.0), audio_delay_ms(120.0, 10000.0));
+        assert_eq!(Some(120.0), audio_delay_ms(3120.0, 10000.0));
+        assert_eq!(Some(-20.0), audio_delay_ms(2980.0, 10000.0));
+        // Sent at the start of the second loop of the sound.
+        assert_eq!(Some(400.0), audio_delay_ms(9900.0, 9500.0));
+        assert_eq!(Some(900.0), audio_delay_ms(19900.0, 9500.0));
+        // Sent at 8s.
+        assert_eq!(Some(900.0), audio_delay_ms(8900.0, 9500.0));
+        // Arriving early, just before the sound loops.
+        assert_eq!(Some(-30.0), audio_delay_ms(9470.0, 9500.0));
+        assert_eq!(None, audio_delay_ms(100.0, 500.0));
+    }
+}
@@ -0,0 +1,819 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Analysis of the packet captures made when a test case sets `tcp_dump`.
+//!
+//! The capture is read as classic pcap, which is what tcpdump writes, and every UDP packet is
+//! classified the way the clients demultiplex them (RFC 7983): STUN, DTLS, TURN ChannelData
+//! (whose contents are then classified too), and RTP or RTCP (RFC 5761). Media is encrypted with
+//! SRTP, but the RTP headers and the first header of each RTCP packet are not, and that is all
+//! we need.
+//!
+//! RTP packets are grouped into streams by their SSRC and the addresses they were sent between,
+//! so that each hop through a relay server is its own stream. For each stream we compute the
+//! packet and bit rates, the interarrival jitter of RFC 3550, loss and reordering from the
+//! sequence numbers, and how much of what was sent is headers.
+//!
+//! tcpdump captures on all interfaces, which sees each packet once for every interface it
+//! crosses on its way between containers. So identical datagrams seen within a few milliseconds
+//! of each other are only counted once.
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashSet, VecDeque},
+    fs,
+    hash::{Hash, Hasher},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The payload type RingRTC uses for its own messages between clients.
+const RTP_DATA_PAYLOAD_TYPE: u8 = 101;
+
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b23c4d;
+const PCAPNG_MAGIC: u32 = 0x0a0d0d0a;
+const PCAP_HEADER_LEN: usize = 24;
+const RECORD_HEADER_LEN: usize = 16;
+
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+const LINK_TYPES: [u32; 6] = [
+    LINKTYPE_ETHERNET,
+    LINKTYPE_RAW,
+    LINKTYPE_LINUX_SLL,
+    LINKTYPE_IPV4,
+    LINKTYPE_IPV6,
+    LINKTYPE_LINUX_SLL2,
+];
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+const IPV6_HEADER_LEN: usize = 40;
+const IP_PROTOCOL_UDP: u8 = 17;
+const UDP_HEADER_LEN: usize = 8;
+
+const STUN_MAGIC_COOKIE: u32 = 0x2112a442;
+const TURN_CHANNEL_HEADER_LEN: usize = 4;
+const RTP_HEADER_LEN: usize = 12;
+const RTCP_HEADER_LEN: usize = 8;
+
+const RTCP_SENDER_REPORT: u8 = 200;
+const RTCP_RECEIVER_REPORT: u8 = 201;
+const RTCP_TRANSPORT_FEEDBACK: u8 = 205;
+const RTCP_PAYLOAD_FEEDBACK: u8 = 206;
+
+/// Copies of a datagram captured on different interfaces are this close together.
+const DUPLICATE_WINDOW_SECS: f64 = 0.01;
+
+/// The clock rates that media uses, one of which is picked for each stream by how fast its
+/// timestamps advance.
+const AUDIO_CLOCK_RATES: [u32; 3] = [8000, 16000, 48000];
+const VIDEO_CLOCK_RATE: u32 = 90000;
+/// How far a stream's timestamps can be from advancing at one of the clock rates.
+const CLOCK_RATE_TOLERANCE: f64 = 0.2;
+/// Streams shorter than this don't say enough about their clock rate.
+const MIN_CLOCK_RATE_SECS: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamKind {
+    Audio,
+    Video,
+    /// RingRTC's own messages, such as the ones carrying the remote's video status.
+    RtpData,
+    #[default]
+    Unknown,
+}
+
+/// One direction of an RTP stream, between two addresses.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RtpStreamStats {
+    pub ssrc: String,
+    pub source: String,
+    pub destination: String,
+    pub kind: StreamKind,
+    pub payload_type: u8,
+    /// The clock rate the stream's timestamps advance at, if it's one that media uses.
+    pub clock_rate: Option<u32>,
+    /// Not counting duplicates.
+    pub packets: u32,
+    pub packets_per_second: Option<f32>,
+    /// Of the IP packets, including the headers of IP, UDP, TURN, and RTP.
+    pub bitrate_kbps: Option<f32>,
+    /// Packets missing from the sequence numbers, as a count and as a percent of the packets
+    /// that were expected.
+    pub lost: u32,
+    pub loss_percent: f32,
+    /// Packets that arrived after a packet with a higher sequence number.
+    pub reordered: u32,
+    pub duplicates: u32,
+    /// The interarrival jitter (RFC 3550, section 6.4.1), averaged over the stream and at its
+    /// highest. Only known if the clock rate is.
+    pub jitter_ms: Option<f32>,
+    pub max_jitter_ms: Option<f32>,
+    /// The share of the IP packets that isn't RTP payload (which includes the SRTP
+    /// authentication tag, and any padding).
+    pub overhead_percent: f32,
+}
+
+/// The RTCP packets one client sent from one SSRC to one address. Only the type of the first
+/// packet in each compound packet is known, since the rest is encrypted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RtcpStreamStats {
+    pub ssrc: String,
+    pub source: String,
+    pub destination: String,
+    pub packets: u32,
+    pub packets_per_second: Option<f32>,
+    pub bitrate_kbps: Option<f32>,
+    pub sender_reports: u32,
+    pub receiver_reports: u32,
+    /// Transport and payload-specific feedback, such as transport-cc, NACKs and PLIs.
+    pub feedback: u32,
+    pub other: u32,
+}
+
+/// The analysis of a whole capture. Packets are counted once for each class they fall in, so a
+/// TURN ChannelData message carrying RTP counts for both.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PcapAnalysis {
+    /// Not counting copies captured on other interfaces.
+    pub udp_packets: u32,
+    pub duplicate_packets: u32,
+    pub stun_packets: u32,
+    pub dtls_packets: u32,
+    pub turn_channel_packets: u32,
+    pub rtp_packets: u32,
+    pub rtcp_packets: u32,
+    pub other_packets: u32,
+    /// Sorted by SSRC, then by addresses.
+    pub rtp_streams: Vec<RtpStreamStats>,
+    pub rtcp_streams: Vec<RtcpStreamStats>,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// When a packet was captured, in seconds, and its data starting with the link layer.
+type Record<'a> = (f64, &'a [u8]);
+
+/// Reads the records of a classic pcap capture, returning them with its link type. A record cut
+/// off by the end of the capture is ignored, since tcpdump is killed when the test ends.
+fn read_records(bytes: &[u8]) -> Result<(u32, Vec<Record<'_>>)> {
+    let Some(magic) = read_u32(bytes, 0) else {
+        bail!("the capture is empty");
+    };
+    let (big_endian, nanos) = match magic {
+        PCAP_MAGIC_MICROS => (true, false),
+        PCAP_MAGIC_NANOS => (true, true),
+        PCAPNG_MAGIC => bail!("pcapng captures aren't supported"),
+        _ if magic.swap_bytes() == PCAP_MAGIC_MICROS => (false, false),
+        _ if magic.swap_bytes() == PCAP_MAGIC_NANOS => (false, true),
+        _ => bail!("not a pcap capture (magic number {:#010x})", magic),
+    };
+    let read = |at: usize| {
+        read_u32(bytes, at).map(|value| {
+            if big_endian {
+                value
+            } else {
+                value.swap_bytes()
+            }
+        })
+    };
+
+    if bytes.len() < PCAP_HEADER_LEN {
+        bail!("the capture's header is cut off");
+    }
+    // The upper bits can hold other information about the link.
+    let link_type = read(20).unwrap_or_default() & 0x0fff_ffff;
+    let fraction_per_second = if nanos { 1e9 } else { 1e6 };
+
+    let mut records = vec![];
+    let mut offset = PCAP_HEADER_LEN;
+    while let (Some(seconds), Some(fraction), Some(captured_len)) =
+        (read(offset), read(offset + 4), read(offset + 8))
+    {
+        let start = offset + RECORD_HEADER_LEN;
+        let Some(data) = bytes.get(start..start + captured_len as usize) else {
+            break;
+        };
+        records.push((seconds as f64 + fraction as f64 / fraction_per_second, data));
+        offset = start + captured_len as usize;
+    }
+    Ok((link_type, records))
+}
+
+/// Strips the link layer, returning the IP packet (if it is one).
+fn ip_packet(link_type: u32, frame: &[u8]) -> Option<&[u8]> {
+    let (ethertype, packet) = match link_type {
+        LINKTYPE_ETHERNET => match read_u16(frame, 12)? {
+            ETHERTYPE_VLAN => (read_u16(frame, 16)?, frame.get(18..)?),
+            ethertype => (ethertype, frame.get(14..)?),
+        },
+        LINKTYPE_LINUX_SLL => (read_u16(frame, 14)?, frame.get(16..)?),
+        LINKTYPE_LINUX_SLL2 => (read_u16(frame, 0)?, frame.get(20..)?),
+        _ => return Some(frame),
+    };
+    matches!(ethertype, ETHERTYPE_IPV4 | ETHERTYPE_IPV6).then_some(packet)
+}
+
+struct Datagram<'a> {
+    source: SocketAddr,
+    destination: SocketAddr,
+    /// The length of the IP packet it came in.
+    ip_len: usize,
+    payload: &'a [u8],
+}
+
+/// Parses the UDP datagram in an IP packet. Fragments aren't reassembled, and IPv6 extension
+/// headers aren't followed, neither of which the clients send.
+fn udp_datagram(packet: &[u8]) -> Option<Datagram<'_>> {
+    let (source, destination, ip_len, udp) = match packet.first()? >> 4 {
+        4 => {
+            let header_len = (packet[0] & 0x0f) as usize * 4;
+            let ip_len = read_u16(packet, 2)? as usize;
+            // The more fragments flag and the fragment offset.
+            let fragment = read_u16(packet, 6)? & 0x3fff;
+            if *packet.get(9)? != IP_PROTOCOL_UDP || fragment != 0 {
+                return None;
+            }
+            (
+                IpAddr::V4(Ipv4Addr::from(read_u32(packet, 12)?)),
+                IpAddr::V4(Ipv4Addr::from(read_u32(packet, 16)?)),
+                ip_len,
+                packet.get(header_len..ip_len.min(packet.len()))?,
+            )
+        }
+        6 => {
+            let ip_len = IPV6_HEADER_LEN + read_u16(packet, 4)? as usize;
+            if *packet.get(6)? != IP_PROTOCOL_UDP {
+                return None;
+            }
+            let address = |at: usize| -> Option<IpAddr> {
+                let octets: [u8; 16] = packet.get(at..at + 16)?.try_into().ok()?;
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            };
+            (
+                address(8)?,
+                address(24)?,
+                ip_len,
+                packet.get(IPV6_HEADER_LEN..ip_len.min(packet.len()))?,
+            )
+        }
+        _ => return None,
+    };
+    let udp_len = read_u16(udp, 4)? as usize;
+    Some(Datagram {
+        source: SocketAddr::new(source, read_u16(udp, 0)?),
+        destination: SocketAddr::new(destination, read_u16(udp, 2)?),
+        ip_len,
+        payload: udp.get(UDP_HEADER_LEN..udp_len.min(udp.len()))?,
+    })
+}
+
+struct RtpHeader {
+    payload_type: u8,
+    sequence: u16,
+    timestamp: u32,
+    ssrc: u32,
+    /// Including CSRCs and header extensions.
+    len: usize,
+}
+
+fn rtp_header(packet: &[u8]) -> Option<RtpHeader> {
+    let csrc_count = (packet.first()? & 0x0f) as usize;
+    let has_extension = packet[0] & 0x10 != 0;
+    let mut len = RTP_HEADER_LEN + 4 * csrc_count;
+    if has_extension {
+        // The extension's length is in 32-bit words, after its 4-byte header.
+        len += 4 + 4 * read_u16(packet, len + 2)? as usize;
+    }
+    let header = RtpHeader {
+        payload_type: *packet.get(1)? & 0x7f,
+        sequence: read_u16(packet, 2)?,
+        timestamp: read_u32(packet, 4)?,
+        ssrc: read_u32(packet, 8)?,
+        len,
+    };
+    (len <= packet.len()).then_some(header)
+}
+
+/// Extends a sequence number to the one closest to the highest so far, so that it keeps
+/// counting up when the 16-bit sequence numbers wrap. The first one starts a whole cycle in, so
+/// that packets that were sent before it never go below zero.
+fn extend_sequence(highest: Option<u64>, sequence: u16) -> u64 {
+    match highest {
+        Some(highest) => {
+            let step = sequence.wrapping_sub(highest as u16) as i16;
+            highest.wrapping_add_signed(step as i64)
+        }
+        None => (1 << 16) + sequence as u64,
+    }
+}
+
+struct RtpArrival {
+    time: f64,
+    timestamp: u32,
+}
+
+/// The packets of a stream, other than duplicates, in the order they arrived.
+#[derive(Default)]
+struct RtpStreamState {
+    payload_type: u8,
+    arrivals: Vec<RtpArrival>,
+    sequences: HashSet<u64>,
+    highest_sequence: Option<u64>,
+    reordered: u32,
+    duplicates: u32,
+    ip_bytes: usize,
+    payload_bytes: usize,
+}
+
+#[derive(Default)]
+struct RtcpStreamState {
+    first_time: f64,
+    last_time: f64,
+    packets: u32,
+    ip_bytes: usize,
+    sender_reports: u32,
+    receiver_reports: u32,
+    feedback: u32,
+    other: u32,
+}
+
+/// The key of a stream, by which streams are sorted.
+type StreamKey = (u32, SocketAddr, SocketAddr);
+
+fn rate(count: f64, seconds: f64) -> Option<f32> {
+    (seconds > 0.0).then(|| (count / seconds) as f32)
+}
+
+/// Picks the clock rate a stream's timestamps advance at, if it's close to one that media uses.
+fn estimate_clock_rate(arrivals: &[RtpArrival]) -> Option<u32> {
+    let seconds = arrivals.last()?.time - arrivals.first()?.time;
+    if seconds < MIN_CLOCK_RATE_SECS {
+        return None;
+    }
+    // Add up the steps, so that it doesn't matter if the timestamps wrap.
+    let ticks: f64 = arrivals
+        .windows(2)
+        .map(|pair| pair[1].timestamp.wrapping_sub(pair[0].timestamp) as i32 as f64)
+        .sum();
+    AUDIO_CLOCK_RATES
+        .into_iter()
+        .chain([VIDEO_CLOCK_RATE])
+        .find(|clock_rate| {
+            (ticks / seconds / *clock_rate as f64 - 1.0).abs() <= CLOCK_RATE_TOLERANCE
+        })
+}
+
+/// The interarrival jitter of RFC 3550, averaged over the stream and at its highest, in ms.
+fn jitter_ms(arrivals: &[RtpArrival], clock_rate: u32) -> (f32, f32) {
+    let mut jitter = 0.0;
+    let mut sum = 0.0;
+    let mut max: f64 = 0.0;
+    for pair in arrivals.windows(2) {
+        let difference = (pair[1].time - pair[0].time) * clock_rate as f64
+            - pair[1].timestamp.wrapping_sub(pair[0].timestamp) as i32 as f64;
+        jitter += (difference.abs() - jitter) / 16.0;
+        sum += jitter;
+        max = max.max(jitter);
+    }
+    let ms_per_tick = 1000.0 / clock_rate as f64;
+    let count = arrivals.len().saturating_sub(1).max(1) as f64;
+    (
+        (sum / count * ms_per_tick) as f32,
+        (max * ms_per_tick) as f32,
+    )
+}
+
+impl RtpStreamStats {
+    fn new((ssrc, source, destination): StreamKey, state: RtpStreamState) -> Self {
+        let seconds = match (state.arrivals.first(), state.arrivals.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        };
+        let packets = state.arrivals.len() as u64;
+        let expected = match (state.sequences.iter().min(), state.sequences.iter().max()) {
+            (Some(lowest), Some(highest)) => highest - lowest + 1,
+            _ => 0,
+        };
+        let lost = expected.saturating_sub(packets);
+
+        let clock_rate = estimate_clock_rate(&state.arrivals);
+        let jitter = clock_rate.map(|clock_rate| jitter_ms(&state.arrivals, clock_rate));
+        let kind = match (state.payload_type, clock_rate) {
+            (RTP_DATA_PAYLOAD_TYPE, _) => StreamKind::RtpData,
+            (_, Some(VIDEO_CLOCK_RATE)) => StreamKind::Video,
+            (_, Some(_)) => StreamKind::Audio,
+            (_, None) => StreamKind::Unknown,
+        };
+
+        Self {
+            ssrc: ssrc.to_string(),
+            source: source.to_string(),
+            destination: destination.to_string(),
+            kind,
+            payload_type: state.payload_type,
+            clock_rate,
+            packets: packets as u32,
+            packets_per_second: rate(packets as f64, seconds),
+            bitrate_kbps: rate(state.ip_bytes as f64 * 8.0 / 1000.0, seconds),
+            lost: lost as u32,
+            loss_percent: if expected > 0 {
+                lost as f32 * 100.0 / expected as f32
+            } else {
+                0.0
+            },
+            reordered: state.reordered,
+            duplicates: state.duplicates,
+            jitter_ms: jitter.map(|(average, _)| average),
+            max_jitter_ms: jitter.map(|(_, max)| max),
+            overhead_percent: if state.ip_bytes > 0 {
+                (state.ip_bytes - state.payload_bytes) as f32 * 100.0 / state.ip_bytes as f32
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+impl RtcpStreamStats {
+    fn new((ssrc, source, destination): StreamKey, state: RtcpStreamState) -> Self {
+        let seconds = state.last_time - state.first_time;
+        Self {
+            ssrc: ssrc.to_string(),
+            source: source.to_string(),
+            destination: destination.to_string(),
+            packets: state.packets,
+            packets_per_second: rate(state.packets as f64, seconds),
+            bitrate_kbps: rate(state.ip_bytes as f64 * 8.0 / 1000.0, seconds),
+            sender_reports: state.sender_reports,
+            receiver_reports: state.receiver_reports,
+            feedback: state.feedback,
+            other: state.other,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Analyzer {
+    analysis: PcapAnalysis,
+    /// The hashes of recent datagrams, with when they were captured.
+    recent: VecDeque<(f64, u64)>,
+    rtp_streams: BTreeMap<StreamKey, RtpStreamState>,
+    rtcp_streams: BTreeMap<StreamKey, RtcpStreamState>,
+}
+
+impl Analyzer {
+    /// Returns true if the same datagram was just captured on another interface.
+    fn is_duplicate(&mut self, time: f64, datagram: &Datagram) -> bool {
+        let mut hasher = DefaultHasher::new();
+        (datagram.source, datagram.destination, datagram.payload).hash(&mut hasher);
+        let hash = hasher.finish();
+
+        while self
+            .recent
+            .front()
+            .is_some_and(|(recent_time, _)| time - recent_time > DUPLICATE_WINDOW_SECS)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.iter().any(|(_, recent)| *recent == hash) {
+            return true;
+        }
+        self.recent.push_back((time, hash));
+        false
+    }
+
+    fn add(&mut self, time: f64, datagram: &Datagram) {
+        if self.is_duplicate(time, datagram) {
+            self.analysis.duplicate_packets += 1;
+            return;
+        }
+        self.analysis.udp_packets += 1;
+
+        let mut payload = datagram.payload;
+        if let Some(64..=79) = payload.first() {
+            // TURN ChannelData: a channel number, the length of the data, and the data.
+            self.analysis.turn_channel_packets += 1;
+            let data = read_u16(payload, 2).and_then(|len| {
+                payload.get(TURN_CHANNEL_HEADER_LEN..TURN_CHANNEL_HEADER_LEN + len as usize)
+            });
+            match data {
+                Some(data) => payload = data,
+                None => {
+                    self.analysis.other_packets += 1;
+                    return;
+                }
+            }
+        }
+
+        let key = |ssrc| (ssrc, datagram.source, datagram.destination);
+        match payload.first() {
+            Some(0..=3) if read_u32(payload, 4) == Some(STUN_MAGIC_COOKIE) => {
+                self.analysis.stun_packets += 1;
+            }
+            Some(20..=63) => self.analysis.dtls_packets += 1,
+            // RTCP packet types don't overlap with RTP payload types and the marker bit.
+            Some(128..=191)
+                if payload.len() >= RTCP_HEADER_LEN && (192..=223).contains(&payload[1]) =>
+            {
+                self.analysis.rtcp_packets += 1;
+                let ssrc = read_u32(payload, 4).unwrap_or_default();
+                let stream =
+                    self.rtcp_streams
+                        .entry(key(ssrc))
+                        .or_insert_with(|| RtcpStreamState {
+                            first_time: time,
+                            ..Default::default()
+                        });
+                stream.last_time = time;
+                stream.packets += 1;
+                stream.ip_bytes += datagram.ip_len;
+                match payload[1] {
+                    RTCP_SENDER_REPORT => stream.sender_reports += 1,
+                    RTCP_RECEIVER_REPORT => stream.receiver_reports += 1,
+                    RTCP_TRANSPORT_FEEDBACK | RTCP_PAYLOAD_FEEDBACK => stream.feedback += 1,
+                    _ => stream.other += 1,
+                }
+            }
+            Some(128..=191) => match rtp_header(payload) {
+                Some(header) => {
+                    self.analysis.rtp_packets += 1;
+                    let stream = self.rtp_streams.entry(key(header.ssrc)).or_insert_with(|| {
+                        RtpStreamState {
+                            payload_type: header.payload_type,
+                            ..Default::default()
+                        }
+                    });
+                    let sequence = extend_sequence(stream.highest_sequence, header.sequence);
+                    if !stream.sequences.insert(sequence) {
+                        stream.duplicates += 1;
+                        return;
+                    }
+                    match stream.highest_sequence {
+                        Some(highest) if sequence < highest => stream.reordered += 1,
+                        _ => stream.highest_sequence = Some(sequence),
+                    }
+                    stream.arrivals.push(RtpArrival {
+                        time,
+                        timestamp: header.timestamp,
+                    });
+                    stream.ip_bytes += datagram.ip_len;
+                    stream.payload_bytes += payload.len() - header.len;
+                }
+                None => self.analysis.other_packets += 1,
+            },
+            _ => self.analysis.other_packets += 1,
+        }
+    }
+
+    fn finish(self) -> PcapAnalysis {
+        PcapAnalysis {
+            rtp_streams: self
+                .rtp_streams
+                .into_iter()
+                .map(|(key, state)| RtpStreamStats::new(key, state))
+                .collect(),
+            rtcp_streams: self
+                .rtcp_streams
+                .into_iter()
+                .map(|(key, state)| RtcpStreamStats::new(key, state))
+                .collect(),
+            ..self.analysis
+        }
+    }
+}
+
+/// Analyzes a classic pcap capture.
+pub fn analyze_pcap(bytes: &[u8]) -> Result<PcapAnalysis> {
+    let (link_type, records) = read_records(bytes)?;
+    if !LINK_TYPES.contains(&link_type) {
+        bail!("captures with link type {} aren't supported", link_type);
+    }
+
+    let mut analyzer = Analyzer::default();
+    for (time, frame) in records {
+        if let Some(datagram) = ip_packet(link_type, frame).and_then(udp_datagram) {
+            analyzer.add(time, &datagram);
+        }
+    }
+    Ok(analyzer.finish())
+}
+
+pub fn analyze_pcap_file(path: &Path) -> Result<PcapAnalysis> {
+    let bytes =
+        fs::read(path).with_context(|| format!("failed to read capture {}", path.display()))?;
+    analyze_pcap(&bytes).with_context(|| format!("failed to analyze capture {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_A: &str = "172.28.0.250:10000";
+    const CLIENT_B: &str = "172.28.0.251:20000";
+
+    /// Builds a little-endian, microsecond pcap capture of Linux cooked frames.
+    struct Capture {
+        bytes: Vec<u8>,
+    }
+
+    impl Capture {
+        fn new() -> Self {
+            let mut bytes = vec![];
+            bytes.extend(PCAP_MAGIC_MICROS.to_le_bytes());
+            bytes.extend(2u16.to_le_bytes());
+            bytes.extend(4u16.to_le_bytes());
+            bytes.extend([0; 8]);
+            bytes.extend(65535u32.to_le_bytes());
+            bytes.extend(LINKTYPE_LINUX_SLL.to_le_bytes());
+            Self { bytes }
+        }
+
+        fn add(&mut self, time: f64, source: &str, destination: &str, payload: &[u8]) {
+            let source: SocketAddr = source.parse().unwrap();
+            let destination: SocketAddr = destination.parse().unwrap();
+            let (IpAddr::V4(source_ip), IpAddr::V4(destination_ip)) =
+                (source.ip(), destination.ip())
+            else {
+                panic!("only IPv4 is built");
+            };
+
+            let mut frame = vec![0; 14];
+            frame.extend(ETHERTYPE_IPV4.to_be_bytes());
+            let ip_len = 20 + UDP_HEADER_LEN + payload.len();
+            frame.extend([0x45, 0]);
+            frame.extend((ip_len as u16).to_be_bytes());
+            frame.extend([0, 0, 0x40, 0, 64, IP_PROTOCOL_UDP, 0, 0]);
+            frame.extend(source_ip.octets());
+            frame.extend(destination_ip.octets());
+            frame.extend(source.port().to_be_bytes());
+            frame.extend(destination.port().to_be_bytes());
+            frame.extend(((UDP_HEADER_LEN + payload.len()) as u16).to_be_bytes());
+            frame.extend([0, 0]);
+            frame.extend(payload);
+
+            self.bytes.extend((time.floor() as u32).to_le_bytes());
+            self.bytes
+                .extend(((time.fract() * 1e6).round() as u32).to_le_bytes());
+            self.bytes.extend((frame.len() as u32).to_le_bytes());
+            self.bytes.extend((frame.len() as u32).to_le_bytes());
+            self.bytes.extend(frame);
+        }
+    }
+
+    fn rtp(payload_type: u8, sequence: u16, timestamp: u32, ssrc: u32, len: usize) -> Vec<u8> {
+        let mut packet = vec![0x80, payload_type];
+        packet.extend(sequence.to_be_bytes());
+        packet.extend(timestamp.to_be_bytes());
+        packet.extend(ssrc.to_be_bytes());
+        packet.resize(RTP_HEADER_LEN + len, 0xaa);
+        packet
+    }
+
+    fn turn_channel(data: &[u8]) -> Vec<u8> {
+        let mut message = vec![0x40, 0x00];
+        message.extend((data.len() as u16).to_be_bytes());
+        message.extend(data);
+        message
+    }
+
+    #[test]
+    fn classifies() {
+        let mut capture = Capture::new();
+        let mut stun = vec![0x00, 0x01, 0x00, 0x00];
+        stun.extend(STUN_MAGIC_COOKIE.to_be_bytes());
+        stun.extend([0; 12]);
+        capture.add(1.0, CLIENT_A, CLIENT_B, &stun);
+        capture.add(1.1, CLIENT_A, CLIENT_B, &[22, 0xfe, 0xfd, 0, 0]);
+        let rtcp = [0x81, RTCP_RECEIVER_REPORT, 0, 7, 0, 0, 0x03, 0xe9];
+        capture.add(1.2, CLIENT_A, CLIENT_B, &rtcp);
+        capture.add(1.3, CLIENT_B, CLIENT_A, &turn_channel(&rtcp));
+        capture.add(
+            1.4,
+            CLIENT_A,
+            CLIENT_B,
+            &rtp(RTP_DATA_PAYLOAD_TYPE, 1, 0, 1001, 20),
+        );
+        capture.add(1.5, CLIENT_A, CLIENT_B, b"hello");
+
+        let analysis = analyze_pcap(&capture.bytes).unwrap();
+        assert_eq!(6, analysis.udp_packets);
+        assert_eq!(1, analysis.stun_packets);
+        assert_eq!(1, analysis.dtls_packets);
+        assert_eq!(1, analysis.turn_channel_packets);
+        assert_eq!(2, analysis.rtcp_packets);
+        assert_eq!(1, analysis.rtp_packets);
+        assert_eq!(1, analysis.other_packets);
+
+        assert_eq!(2, analysis.rtcp_streams.len());
+        assert!(analysis
+            .rtcp_streams
+            .iter()
+            .all(|stream| stream.ssrc == "1001" && stream.receiver_reports == 1));
+        assert_eq!(StreamKind::RtpData, analysis.rtp_streams[0].kind);
+    }
+
+    #[test]
+    fn rtp_streams() {
+        let mut capture = Capture::new();
+        // 20ms of 48kHz audio per packet, for 2 seconds.
+        for index in 0..100u16 {
+            let time = 10.0 + index as f64 * 0.02;
+            // Lose one packet, and swap two others.
+            let sequence = match index {
+                50 => continue,
+                60 => 61,
+                61 => 60,
+                _ => index,
+            };
+            let packet = rtp(
+                111,
+                65500u16.wrapping_add(sequence),
+                sequence as u32 * 960,
+                1234,
+                60,
+            );
+            capture.add(time, CLIENT_A, CLIENT_B, &packet);
+            // Seen again on the bridge.
+            capture.add(time + 0.00001, CLIENT_A, CLIENT_B, &packet);
+        }
+        // A duplicate from the network, well after the first.
+        capture.add(12.5, CLIENT_A, CLIENT_B, &rtp(111, 65500, 0, 1234, 60));
+
+        let analysis = analyze_pcap(&capture.bytes).unwrap();
+        assert_eq!(99, analysis.duplicate_packets);
+        assert_eq!(1, analysis.rtp_streams.len());
+        let stream = &analysis.rtp_streams[0];
+        assert_eq!("1234", stream.ssrc);
+        assert_eq!(CLIENT_A, stream.source);
+        assert_eq!(StreamKind::Audio, stream.kind);
+        assert_eq!(Some(48000), stream.clock_rate);
+        assert_eq!(99, stream.packets);
+        assert_eq!(1, stream.lost);
+        assert_eq!(1.0, stream.loss_percent);
+        assert_eq!(1, stream.reordered);
+        assert_eq!(1, stream.duplicates);
+        assert!((stream.packets_per_second.unwrap() - 50.0).abs() < 1.0);
+        // IPv4 + UDP + RTP headers are 40 of each 100 bytes.
+        assert!((stream.overhead_percent - 40.0).abs() < 0.01);
+        // Only the swapped packets arrived off schedule.
+        assert!(stream.jitter_ms.unwrap() < 1.0);
+        assert!(stream.max_jitter_ms.unwrap() > 1.0);
+    }
+
+    #[test]
+    fn jitter() {
+        // Packets every 20ms that arrive alternately 10ms early and late settle at a jitter of
+        // 20ms.
+        let arrivals: Vec<RtpArrival> = (0..1000)
+            .map(|index| RtpArrival {
+                time: index as f64 * 0.02 + if index % 2 == 0 { -0.01 } else { 0.01 },
+                timestamp: (index * 960) as u32,
+            })
+            .collect();
+        assert_eq!(Some(48000), estimate_clock_rate(&arrivals));
+        let (average, max) = jitter_ms(&arrivals, 48000);
+        assert!((average - 20.0).abs() < 1.0, "{}", average);
+        assert!((max - 20.0).abs() < 0.1, "{}", max);
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(65536 + 100, extend_sequence(None, 100));
+        assert_eq!(65536 + 99, extend_sequence(Some(65536 + 100), 99));
+        assert_eq!(2 * 65536 + 1, extend_sequence(Some(65536 + 65535), 1));
+        assert_eq!(65536 + 65535, extend_sequence(Some(2 * 65536 + 1), 65535));
+    }
+
+    #[test]
+    fn bad_captures() {
+        assert!(analyze_pcap(&[]).is_err());
+        assert!(analyze_pcap(&PCAPNG_MAGIC.to_le_bytes()).is_err());
+
+        // A capture cut off in the middle of a record still has the records before it.
+        let mut capture = Capture::new();
+        capture.add(1.0, CLIENT_A, CLIENT_B, &[22, 0xfe, 0xfd, 0, 0]);
+        capture.add(1.1, CLIENT_A, CLIENT_B, &[22, 0xfe, 0xfd, 0, 0]);
+        capture.bytes.truncate(capture.bytes.len() - 3);
+        assert_eq!(1, analyze_pcap(&capture.bytes).unwrap().dtls_packets);
+    }
+}
@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 //
 
-use std::{collections::HashMap, fmt::Write, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    path::Path,
+    str::FromStr,
+};
 
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
@@ -24,7 +29,9 @@ use crate::{
     common::{
         ChartDimension, GroupConfig, NetworkConfigWithOffset, NetworkProfile, TestCaseConfig,
     },
-    test::{AudioTestResults, GroupRun, Sound, TestCase},
+    markers::{audio_delay_ms, ReceivedChirps},
+    pcap::{analyze_pcap_file, PcapAnalysis},
+    test::{AudioTestResults, GroupRun, Sound, TestCase, CLIENT_NAMES},
 };
 
 type ChartPoint = (f32, f32);
@@ -180,7 +187,7 @@ pub enum AnalysisReportMos {
 
 impl AnalysisReportMos {
     /// Return a single MOS value (i.e. the average) or None for display.
-    fn get_mos_for_display(&self) -> Option<f32> {
+    pub fn get_mos_for_display(&self) -> Option<f32> {
         match self {
             AnalysisReportMos::None => None,
             AnalysisReportMos::Single(mos) => Some(*mos),
@@ -299,10 +306,10 @@ impl AnalysisReport {
 #[derive(Debug)]
 pub struct DockerStatsReport {
     timestamp: Vec<u64>,
-    cpu_usage: Stats,
-    mem_usage: Stats,
-    tx_bitrate: Stats,
-    rx_bitrate: Stats,
+    pub cpu_usage: Stats,
+    pub mem_usage: Stats,
+    pub tx_bitrate: Stats,
+    pub rx_bitrate: Stats,
     item_count: usize,
 }
 
@@ -542,6 +549,21 @@ pub struct ClientLogReport {
 }
 
 impl ClientLogReport {
+    /// The average of a stat over every audio stream received, for comparing receivers.
+    pub fn audio_receive_average(
+        &self,
+        stats: impl Fn(&AudioReceiveStats) -> &Stats,
+    ) -> Option<f32> {
+        let averages: Vec<f32> = self
+            .audio_receive_stats_list
+            .iter()
+            .map(|audio_receive_stats| &stats(audio_receive_stats).data)
+            .filter(|data| data.min <= data.max && data.ave.is_finite())
+            .map(|data| data.ave)
+            .collect();
+        (!averages.is_empty()).then(|| averages.iter().sum::<f32>() / averages.len() as f32)
+    }
+
     async fn parse(
         file_name: &str,
     ) -> Result<(
@@ -596,8 +618,18 @@ impl ClientLogReport {
             packet_length: StatsData::new_skip_n(0),
         };
 
+        // If the client exported its stats, they are read from there instead of the log.
+        let stats_export_file = file_name
+            .strip_suffix(".log")
+            .map(|base| format!("{base}.stats.jsonl"))
+            .filter(|path| Path::new(path).is_file());
+
         let mut lines = reader.lines();
         while let Some(line) = lines.next_line().await? {
+            if stats_export_file.is_some() && !line.contains("ringrtc_adapt!") {
+                continue;
+            }
+
             if let Some(cap) = re_connection_line.captures(&line) {
                 connection_stats
                     .timestamp_us
@@ -744,6 +776,138 @@ impl ClientLogReport {
             }
         }
 
+        if let Some(stats_export_file) = stats_export_file {
+            let contents = tokio::fs::read_to_string(&stats_export_file).await?;
+            for line in contents.lines() {
+                let object: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(line)?;
+                // Values that weren't finite were exported as null.
+                let value = |name: &str| -> Result<f32> {
+                    match object.get(name) {
+                        Some(serde_json::Value::Null) => Ok(f32::NAN),
+                        Some(value) => value
+                            .as_f64()
+                            .map(|value| value as f32)
+                            .ok_or_else(|| anyhow!("{name} is not a number in {line}")),
+                        None => Err(anyhow!("{name} is missing from {line}")),
+                    }
+                };
+                let ssrc = object
+                    .get("ssrc")
+                    .map(|ssrc| ssrc.to_string())
+                    .unwrap_or_default();
+                let direction = object.get("direction").and_then(|value| value.as_str());
+
+                match (
+                    object.get("kind").and_then(|value| value.as_str()),
+                    direction,
+                ) {
+                    (Some("connection"), _) => {
+                        let time_ms = object
+                            .get("time_ms")
+                            .and_then(|value| value.as_u64())
+                            .ok_or_else(|| anyhow!("time_ms is missing from {line}"))?;
+                        connection_stats.timestamp_us.push(time_ms * 1000);
+                        connection_stats
+                            .current_round_trip_time
+                            .push(value("current_round_trip_time")?);
+                        connection_stats
+                            .available_outgoing_bitrate
+                            .push(value("available_outgoing_bitrate")? / 1000.0);
+                    }
+                    (Some("audio"), Some("send")) => {
+                        audio_send_stats.ssrc = ssrc;
+                        audio_send_stats
+                            .packets_per_second
+                            .push(value("packets_per_second")?);
+                        audio_send_stats
+                            .average_packet_size
+                            .push(value("average_packet_size")?);
+                        audio_send_stats.bitrate.push(value("bitrate")? / 1000.0);
+                        audio_send_stats
+                            .remote_packet_loss
+                            .push(value("remote_packets_lost_pct")?);
+                        audio_send_stats.remote_jitter.push(value("remote_jitter")?);
+                        audio_send_stats
+                            .remote_round_trip_time
+                            .push(value("remote_round_trip_time")?);
+                        audio_send_stats.audio_energy.push(value("audio_energy")?);
+                    }
+                    (Some("video"), Some("send")) => {
+                        let video_send_stats =
+                            video_send_stats_map.entry(ssrc.clone()).or_default();
+                        video_send_stats.ssrc = ssrc;
+                        video_send_stats
+                            .packets_per_second
+                            .push(value("packets_per_second")?);
+                        video_send_stats
+                            .average_packet_size
+                            .push(value("average_packet_size")?);
+                        video_send_stats.bitrate.push(value("bitrate")? / 1000.0);
+                        video_send_stats.framerate.push(value("framerate")?);
+                        video_send_stats
+                            .key_frames_encoded
+                            .push(value("key_frames_encoded")?);
+                        video_send_stats
+                            .retransmitted_packets_sent
+                            .push(value("retransmitted_packets_sent")?);
+                        video_send_stats
+                            .retransmitted_bitrate
+                            .push(value("retransmitted_bitrate")?);
+                        video_send_stats
+                            .send_delay_per_packet
+                            .push(value("send_delay_per_packet")?);
+                        video_send_stats.nack_count.push(value("nack_count")?);
+                        video_send_stats.pli_count.push(value("pli_count")?);
+                        video_send_stats
+                            .remote_packet_loss
+                            .push(value("remote_packets_lost_pct")?);
+                        video_send_stats.remote_jitter.push(value("remote_jitter")?);
+                        video_send_stats
+                            .remote_round_trip_time
+                            .push(value("remote_round_trip_time")?);
+                    }
+                    (Some("audio"), Some("recv")) => {
+                        let audio_receive_stats =
+                            audio_receive_stats_map.entry(ssrc.clone()).or_default();
+                        audio_receive_stats.ssrc = ssrc;
+                        audio_receive_stats
+                            .packets_per_second
+                            .push(value("packets_per_second")?);
+                        audio_receive_stats.bitrate.push(value("bitrate")? / 1000.0);
+                        audio_receive_stats
+                            .audio_energy
+                            .push(value("audio_energy")?);
+                        audio_receive_stats
+                            .packet_loss
+                            .push(value("packets_lost_pct")?);
+                        audio_receive_stats.jitter.push(value("jitter")?);
+                        audio_receive_stats
+                            .jitter_buffer_delay
+                            .push(value("jitter_buffer_delay")?);
+                    }
+                    (Some("video"), Some("recv")) => {
+                        let video_receive_stats =
+                            video_receive_stats_map.entry(ssrc.clone()).or_default();
+                        video_receive_stats.ssrc = ssrc;
+                        video_receive_stats
+                            .packets_per_second
+                            .push(value("packets_per_second")?);
+                        video_receive_stats.bitrate.push(value("bitrate")? / 1000.0);
+                        video_receive_stats
+                            .packet_loss
+                            .push(value("packets_lost_pct")?);
+                        video_receive_stats.framerate.push(value("framerate")?);
+                        video_receive_stats
+                            .key_frames_decoded
+                            .push(value("key_frames_decoded")?);
+                    }
+                    // The system's CPU usage is only in the container stats.
+                    _ => {}
+                }
+            }
+        }
+
         Ok((
             connection_stats,
             audio_send_stats,
@@ -1248,6 +1412,207 @@ impl ClientLogReport {
     }
 }
 
+/// Audio and video delays closer together than this are compared for the A/V offset.
+const AV_OFFSET_WINDOW_SECS: f32 = 0.5;
+
+/// The one-way delays of client A's media, measured with the timing markers it sends. Everything
+/// is charted against the seconds since the receiver started playing audio.
+#[derive(Debug)]
+pub struct TimingReport {
+    pub audio_delay: Stats,
+    pub video_delay: Stats,
+    /// How far audio lags behind video (negative if it is ahead), wherever there is a video
+    /// delay close to an audio delay.
+    pub av_offset: Stats,
+}
+
+impl TimingReport {
+    /// Returns when the client started playing audio, and the delays of the video frames it
+    /// received with the time it received them.
+    async fn parse(file_name: &str) -> Result<(Option<u64>, Vec<(u64, f32)>)> {
+        let file = File::open(file_name).await?;
+        let reader = BufReader::new(file);
+
+        // Example: ringrtc_marker!,client_b,audio_start,1667611058243
+        let re_audio_start_line = Regex::new(r".*ringrtc_marker!,\w+,audio_start,(?P<time>\d+)")?;
+
+        // Example: ringrtc_marker!,client_b,video,0,1667611058243,85
+        let re_video_line =
+            Regex::new(r".*ringrtc_marker!,\w+,video,\d+,(?P<time>\d+),(?P<delay>-?\d+)")?;
+
+        let mut audio_start = None;
+        let mut video_delays = vec![];
+
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if let Some(cap) = re_audio_start_line.captures(&line) {
+                audio_start = Some(u64::from_str(&cap["time"])?);
+                continue;
+            }
+
+            if let Some(cap) = re_video_line.captures(&line) {
+                video_delays.push((u64::from_str(&cap["time"])?, f32::from_str(&cap["delay"])?));
+            }
+        }
+
+        Ok((audio_start, video_delays))
+    }
+
+    pub async fn build(
+        test_path: &str,
+        sender_name: &str,
+        receiver_name: &str,
+        chirps: Option<&ReceivedChirps>,
+    ) -> Result<Self> {
+        let (sender_audio_start, _) =
+            Self::parse(&format!("{}/{}.log", test_path, sender_name)).await?;
+        let (receiver_audio_start, video_delays) =
+            Self::parse(&format!("{}/{}.log", test_path, receiver_name)).await?;
+
+        let origin = receiver_audio_start
+            .or(video_delays.first().map(|(time, _)| *time))
+            .unwrap_or_default();
+        let seconds = |time: u64| (time as i64 - origin as i64) as f32 / 1000.0;
+
+        // Chirps are found in the recording, which starts when the receiver starts playing.
+        let mut audio_delay = StatsData::new_skip_n(0);
+        if let (Some(sender_audio_start), Some(receiver_audio_start), Some(chirps)) =
+            (sender_audio_start, receiver_audio_start, chirps)
+        {
+            for position_ms in &chirps.positions_ms {
+                let elapsed_ms =
+                    (receiver_audio_start as f64 + position_ms) - sender_audio_start as f64;
+                if let Some(delay_ms) = audio_delay_ms(elapsed_ms, chirps.sound_ms) {
+                    audio_delay.push_with_index((position_ms / 1000.0) as f32, delay_ms as f32);
+                }
+            }
+        }
+
+        let mut video_delay = StatsData::new_skip_n(0);
+        for (time, delay_ms) in video_delays {
+            video_delay.push_with_index(seconds(time), delay_ms);
+        }
+
+        let mut av_offset = StatsData::new_skip_n(0);
+        for (audio_seconds, audio_ms) in &audio_delay.points {
+            let distance = |(video_seconds, _): &&ChartPoint| (video_seconds - audio_seconds).abs();
+            if let Some((_, video_ms)) = video_delay
+                .points
+                .iter()
+                .filter(|point| distance(point) <= AV_OFFSET_WINDOW_SECS)
+                .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            {
+                av_offset.push_with_index(*audio_seconds, audio_ms - video_ms);
+            }
+        }
+
+        let stats = |chart_dimension: ChartDimension, data: StatsData| {
+            let (title, y_label) = chart_dimension.get_title_and_y_label();
+            Stats {
+                config: StatsConfig {
+                    title: title.to_string(),
+                    chart_name: format!(
+                        "{}.timing.{}.svg",
+                        receiver_name,
+                        chart_dimension.get_name()
+                    ),
+                    x_label: "Test Seconds".to_string(),
+                    y_label: y_label.to_string(),
+                    x_max: Some(data.max_index + 5.0),
+                    // The offset can be negative.
+                    y_min: (!data.points.is_empty()).then_some(data.overall_min.min(0.0) * 1.1),
+                    ..Default::default()
+                },
+                data,
+            }
+        };
+
+        Ok(Self {
+            audio_delay: stats(ChartDimension::AudioDelay, audio_delay),
+            video_delay: stats(ChartDimension::VideoDelay, video_delay),
+            av_offset: stats(ChartDimension::AudioVideoOffset, av_offset),
+        })
+    }
+}
+
+/// The resources a client sampled in soak mode, over the whole test.
+#[derive(Debug)]
+pub struct SoakReport {
+    pub client_name: String,
+    /// Each series that was sampled, such as `rss_kb` or `actor_queue.group-call-client`.
+    pub series: BTreeMap<String, Stats>,
+    /// The series that grew steadily, with how high they stayed at the start and at the end.
+    pub growing: BTreeMap<String, (f32, f32)>,
+}
+
+impl SoakReport {
+    pub async fn build(test_path: &str, client_name: &str) -> Result<Self> {
+        let file = File::open(format!("{}/{}.log", test_path, client_name)).await?;
+        let reader = BufReader::new(file);
+
+        // Example: ringrtc_soak!,client_b,1667611058243,rss_kb=81234,threads=42,fds=57
+        let re_sample_line = Regex::new(r".*ringrtc_soak!,\w+,(?P<time>\d+),(?P<values>.*)$")?;
+
+        // Example: ringrtc_soak_growth!,client_b,rss_kb,81234,95321
+        let re_growth_line = Regex::new(
+            r".*ringrtc_soak_growth!,\w+,(?P<name>[^,]+),(?P<first>[^,]+),(?P<last>[^,]+)$",
+        )?;
+
+        let mut origin = None;
+        let mut series: BTreeMap<String, StatsData> = BTreeMap::new();
+        let mut growing = BTreeMap::new();
+
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if let Some(cap) = re_sample_line.captures(&line) {
+                let time = u64::from_str(&cap["time"])?;
+                let minutes = time.saturating_sub(*origin.get_or_insert(time)) as f32 / 60_000.0;
+                for value in cap["values"].split(',') {
+                    let (name, value) = value
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("bad soak sample: {}", value))?;
+                    series
+                        .entry(name.to_string())
+                        .or_insert_with(|| StatsData::new_skip_n(0))
+                        .push_with_index(minutes, f32::from_str(value)?);
+                }
+                continue;
+            }
+
+            if let Some(cap) = re_growth_line.captures(&line) {
+                growing.insert(
+                    cap["name"].to_string(),
+                    (f32::from_str(&cap["first"])?, f32::from_str(&cap["last"])?),
+                );
+            }
+        }
+
+        let series = series
+            .into_iter()
+            .map(|(name, data)| {
+                let stats = Stats {
+                    config: StatsConfig {
+                        title: name.clone(),
+                        chart_name: format!("{}.soak.{}.svg", client_name, name),
+                        x_label: "Test Minutes".to_string(),
+                        y_label: name.clone(),
+                        x_max: Some(data.max_index * 1.05),
+                        ..Default::default()
+                    },
+                    data,
+                };
+                (name, stats)
+            })
+            .collect();
+
+        Ok(Self {
+            client_name: client_name.to_string(),
+            series,
+            growing,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Report {
     pub report_name: String,
@@ -1264,20 +1629,74 @@ pub struct Report {
     pub analysis_report: AnalysisReport,
     pub docker_stats_report: DockerStatsReport,
     pub client_log_report: ClientLogReport,
+    /// Only when client A sends timing markers.
+    pub timing_report: Option<TimingReport>,
+    /// Only when the test case captures packets.
+    pub pcap_analysis: Option<PcapAnalysis>,
+    /// One for each client that samples its resources for a soak test.
+    pub soak_reports: Vec<SoakReport>,
 
     /// If there is no video being tested, don't generate charts or columns for it.
     pub show_video: bool,
     /// Keep track of how many iterations were assigned for the test case.
     pub iterations: u16,
+    /// The other clients receiving client A's media, in group calls with more than two
+    /// clients.
+    pub receivers: Vec<ReceiverReport>,
+}
+
+#[derive(Debug)]
+pub struct ReceiverReport {
+    pub client_name: String,
+    pub audio_test_results: AudioTestResults,
+    pub client_log_report: ClientLogReport,
 }
 
 impl Report {
-    /// Build a report from client_b's perspective.
+    /// Build a report from client_b's perspective, along with the other receivers (if any).
     pub async fn build_b(
         test_case: &TestCase<'_>,
         test_case_config: &TestCaseConfig,
         audio_test_results: AudioTestResults,
+        receiver_audio_test_results: Vec<AudioTestResults>,
     ) -> Result<Self> {
+        let timing_report = if test_case_config.client_a_config.timing_markers {
+            Some(
+                TimingReport::build(
+                    &test_case.test_path,
+                    test_case.client_a.name,
+                    test_case.client_b.name,
+                    audio_test_results.chirps.as_ref(),
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+        let pcap_analysis = if test_case_config.tcp_dump {
+            let path = Path::new(&test_case.test_path).join("tcpdump.pcap");
+            // A capture that can't be analyzed shouldn't cost the rest of the report.
+            let display_path = path.display().to_string();
+            match tokio::task::spawn_blocking(move || analyze_pcap_file(&path)).await {
+                Ok(Ok(pcap_analysis)) => Some(pcap_analysis),
+                Ok(Err(err)) => {
+                    println!("Error analyzing {display_path}! {err:#}");
+                    None
+                }
+                Err(err) => {
+                    println!("Error analyzing {display_path}! {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let mut soak_reports = vec![];
+        for (client, call_config) in test_case.clients().zip(test_case_config.client_configs()) {
+            if call_config.soak_sample_interval_secs.is_some() {
+                soak_reports.push(SoakReport::build(&test_case.test_path, client.name).await?);
+            }
+        }
         let analysis_report = AnalysisReport::build(
             // Note: _Move_ the audio_test_results to the report.
             audio_test_results,
@@ -1303,6 +1722,23 @@ impl Report {
         )
         .await?;
 
+        let mut receivers = vec![];
+        for (client, audio_test_results) in test_case
+            .extra_clients
+            .iter()
+            .zip(receiver_audio_test_results)
+        {
+            receivers.push(ReceiverReport {
+                client_name: client.name.to_string(),
+                audio_test_results,
+                client_log_report: ClientLogReport::build(
+                    &format!("{}/{}.log", test_case.test_path, client.name),
+                    client.name,
+                )
+                .await?,
+            });
+        }
+
         let test_report = Report {
             report_name: test_case.report_name.to_string(),
             test_path: test_case.test_path.to_string(),
@@ -1319,9 +1755,13 @@ impl Report {
             analysis_report,
             docker_stats_report,
             client_log_report,
+            timing_report,
+            pcap_analysis,
+            soak_reports,
             show_video: test_case_config.client_a_config.video.input_name.is_some()
                 || test_case_config.client_b_config.video.input_name.is_some(),
             iterations: test_case_config.iterations,
+            receivers,
         };
 
         if test_case_config.create_charts {
@@ -1520,6 +1960,18 @@ impl Report {
             }));
         }
 
+        if let Some(timing_report) = &self.timing_report {
+            line_chart_stats.extend([
+                &timing_report.audio_delay,
+                &timing_report.video_delay,
+                &timing_report.av_offset,
+            ]);
+        }
+
+        for soak_report in &self.soak_reports {
+            line_chart_stats.extend(soak_report.series.values());
+        }
+
         // Generate charts for audio mos results if they represent a series.
         let audio_reports = [
             &self.analysis_report.audio_test_results.visqol_mos_speech,
@@ -1573,6 +2025,22 @@ impl Report {
         buf.extend_from_slice(html.network_config_section(network_configs).as_bytes());
         buf.extend_from_slice(html.call_config_section(test_case_config).as_bytes());
 
+        if !self.receivers.is_empty() {
+            let mut receivers = vec![(
+                self.client_name.as_str(),
+                &self.analysis_report.audio_test_results,
+                &self.client_log_report,
+            )];
+            receivers.extend(self.receivers.iter().map(|receiver| {
+                (
+                    receiver.client_name.as_str(),
+                    &receiver.audio_test_results,
+                    &receiver.client_log_report,
+                )
+            }));
+            buf.extend_from_slice(html.receivers_section(&receivers).as_bytes());
+        }
+
         // Add charts for audio mos results if they represent a series to the "Audio Core" section.
         let mut audio_core_stats: Vec<&Stats> = vec![];
 
@@ -1634,6 +2102,28 @@ impl Report {
             );
         }
 
+        if let Some(timing_report) = &self.timing_report {
+            let timing_stats = Self::build_stats_rows(
+                &html,
+                &[
+                    &timing_report.audio_delay,
+                    &timing_report.video_delay,
+                    &timing_report.av_offset,
+                ],
+            );
+            buf.extend_from_slice(
+                html.accordion_section(
+                    "timing",
+                    vec![HtmlAccordionItem {
+                        label: "One-Way Delay and A/V Sync".to_string(),
+                        body: timing_stats,
+                        collapsed: true,
+                    }],
+                )
+                .as_bytes(),
+            );
+        }
+
         let container_stats = Self::build_stats_rows(
             &html,
             &[
@@ -1810,6 +2300,40 @@ impl Report {
             }
         }
 
+        if let Some(pcap_analysis) = &self.pcap_analysis {
+            buf.extend_from_slice(
+                html.accordion_section(
+                    "pcap",
+                    vec![HtmlAccordionItem {
+                        label: "Packet Capture".to_string(),
+                        body: html.pcap_section(pcap_analysis),
+                        collapsed: true,
+                    }],
+                )
+                .as_bytes(),
+            );
+        }
+
+        if !self.soak_reports.is_empty() {
+            let soak_items = self
+                .soak_reports
+                .iter()
+                .map(|soak_report| {
+                    let mut soak_body = html.soak_growth_section(&soak_report.growing);
+                    soak_body.push_str(&Self::build_stats_rows(
+                        &html,
+                        &soak_report.series.values().collect::<Vec<_>>(),
+                    ));
+                    HtmlAccordionItem {
+                        label: format!("Soak Resources ({})", soak_report.client_name),
+                        body: soak_body,
+                        collapsed: true,
+                    }
+                })
+                .collect();
+            buf.extend_from_slice(html.accordion_section("soak", soak_items).as_bytes());
+        }
+
         buf.extend_from_slice(html.footer().as_bytes());
 
         let mut file = OpenOptions::new()
@@ -2133,6 +2657,18 @@ impl Report {
                     .map(|stats| stats.key_frames_decoded_stats.data.ave)
                     .collect_vec(),
             ),
+            ChartDimension::AudioDelay => report
+                .timing_report
+                .as_ref()
+                .map_or(0.0, |timing_report| timing_report.audio_delay.data.ave),
+            ChartDimension::VideoDelay => report
+                .timing_report
+                .as_ref()
+                .map_or(0.0, |timing_report| timing_report.video_delay.data.ave),
+            ChartDimension::AudioVideoOffset => report
+                .timing_report
+                .as_ref()
+                .map_or(0.0, |timing_report| timing_report.av_offset.data.ave),
         }
     }
 
@@ -2665,6 +3201,26 @@ impl Html {
             );
         }
 
+        if test_case_config.client_b_config.audio.builtin_analysis {
+            let metrics_string = audio_test_results
+                .audio_metrics
+                .as_ref()
+                .map(|metrics| {
+                    format!(
+                        "Latency: {:.0}ms SNR: {:.1}dB Segmental SNR: {:.1}dB Gaps: {} ({}ms) Concealed: {}ms",
+                        metrics.latency_ms,
+                        metrics.snr_db,
+                        metrics.segmental_snr_db,
+                        metrics.gap_count,
+                        metrics.gap_ms,
+                        metrics.concealed_ms,
+                    )
+                })
+                .unwrap_or_else(|| "Audio Metrics: None".to_string());
+
+            let _ = writeln!(buf, "<h4 class=\"text-right\">{}</h4>", metrics_string);
+        }
+
         buf.push_str("</div>\n");
         buf.push_str("</div>\n");
 
@@ -2739,6 +3295,316 @@ impl Html {
         );
         buf.push_str("</div>\n");
 
+        for (name, extra_client) in CLIENT_NAMES[2..]
+            .iter()
+            .zip(&test_case_config.extra_clients)
+        {
+            buf.push_str("<div class=\"col-md-6\">\n");
+            let _ = writeln!(buf, "<h4>{}</h4>", name);
+            if let Some(network_profile) = &extra_client.network_profile {
+                let _ = writeln!(
+                    buf,
+                    "<p>Network profile: {}</p>",
+                    network_profile.get_name()
+                );
+            }
+            let _ = writeln!(
+                buf,
+                "<p><code><pre>\n{:#?}</pre></code></p>",
+                &extra_client.call_config
+            );
+            buf.push_str("</div>\n");
+        }
+
+        buf.push_str("</div>\n");
+
+        buf
+    }
+
+    /// Compares how each receiver heard client A, along with the average and the worst of
+    /// each column.
+    pub fn receivers_section(
+        &self,
+        receivers: &[(&str, &AudioTestResults, &ClientLogReport)],
+    ) -> String {
+        // Scores are better when higher, and the rest when lower.
+        const COLUMNS: [(&str, bool); 9] = [
+            ("Visqol Speech", true),
+            ("Visqol Audio", true),
+            ("PESQ MOS", true),
+            ("PLC MOS", true),
+            ("Segmental SNR (dB)", true),
+            ("Audio Gaps (ms)", false),
+            ("Audio Packet Loss (%)", false),
+            ("Audio Jitter (ms)", false),
+            ("Audio Jitter Buffer Delay (ms)", false),
+        ];
+
+        let rows: Vec<(&str, [Option<f32>; 9])> = receivers
+            .iter()
+            .map(|(client_name, audio_test_results, client_log_report)| {
+                (
+                    *client_name,
+                    [
+                        audio_test_results.visqol_mos_speech.get_mos_for_display(),
+                        audio_test_results.visqol_mos_audio.get_mos_for_display(),
+                        audio_test_results.pesq_mos.get_mos_for_display(),
+                        audio_test_results.plc_mos.get_mos_for_display(),
+                        audio_test_results
+                            .audio_metrics
+                            .as_ref()
+                            .map(|metrics| metrics.segmental_snr_db),
+                        audio_test_results
+                            .audio_metrics
+                            .as_ref()
+                            .map(|metrics| metrics.gap_ms as f32),
+                        client_log_report.audio_receive_average(|stats| &stats.packet_loss_stats),
+                        client_log_report.audio_receive_average(|stats| &stats.jitter_stats),
+                        client_log_report
+                            .audio_receive_average(|stats| &stats.jitter_buffer_delay_stats),
+                    ],
+                )
+            })
+            .collect();
+
+        let mut average = [None; 9];
+        let mut worst = [None; 9];
+        for (column, (_, higher_is_better)) in COLUMNS.iter().enumerate() {
+            let values: Vec<f32> = rows.iter().filter_map(|(_, row)| row[column]).collect();
+            if values.is_empty() {
+                continue;
+            }
+            average[column] = Some(values.iter().sum::<f32>() / values.len() as f32);
+            worst[column] = values.into_iter().reduce(if *higher_is_better {
+                f32::min
+            } else {
+                f32::max
+            });
+        }
+
+        let mut buf = String::new();
+
+        buf.push_str("<div class=\"p-3 row\">\n");
+        buf.push_str("<div class=\"col-md-12\">\n");
+        buf.push_str("<h3>Receivers</h3>\n");
+
+        buf.push_str("<table class=\"table\">\n");
+        buf.push_str("<thead>\n");
+        buf.push_str("<tr>\n");
+        buf.push_str("<th>Client</th>\n");
+        for (heading, _) in COLUMNS {
+            let _ = writeln!(buf, "<th>{}</th>", heading);
+        }
+        buf.push_str("</tr>\n");
+        buf.push_str("</thead>\n");
+        buf.push_str("<tbody>\n");
+
+        for (label, values) in rows
+            .iter()
+            .map(|(client_name, values)| (*client_name, values))
+            .chain([("Average", &average), ("Worst", &worst)])
+        {
+            buf.push_str("<tr>\n");
+            let _ = writeln!(buf, "<td>{}</td>", label);
+            for value in values {
+                match value {
+                    Some(value) => {
+                        let _ = writeln!(buf, "<td>{:.3}</td>", value);
+                    }
+                    None => buf.push_str("<td>None</td>\n"),
+                }
+            }
+            buf.push_str("</tr>\n");
+        }
+
+        buf.push_str("</tbody>\n");
+        buf.push_str("</table>\n");
+
+        buf.push_str("</div>\n");
+        buf.push_str("</div>\n");
+
+        buf
+    }
+
+    /// A table of the soak series that grew steadily over the test.
+    pub fn soak_growth_section(&self, growing: &BTreeMap<String, (f32, f32)>) -> String {
+        let mut buf = String::new();
+
+        buf.push_str("<div class=\"p-3 row\">\n");
+        buf.push_str("<div class=\"col-md-12\">\n");
+
+        if growing.is_empty() {
+            buf.push_str("<p>No series grew steadily.</p>\n");
+        } else {
+            buf.push_str("<table class=\"table\">\n");
+            buf.push_str("<thead>\n");
+            buf.push_str("<tr>\n");
+            for heading in ["Growing Series", "From", "To", "Growth (%)"] {
+                let _ = writeln!(buf, "<th>{}</th>", heading);
+            }
+            buf.push_str("</tr>\n");
+            buf.push_str("</thead>\n");
+            buf.push_str("<tbody>\n");
+            for (name, (first, last)) in growing {
+                buf.push_str("<tr>\n");
+                let _ = writeln!(buf, "<td>{}</td>", name);
+                let _ = writeln!(buf, "<td>{:.3}</td>", first);
+                let _ = writeln!(buf, "<td>{:.3}</td>", last);
+                if *first != 0.0 {
+                    let _ = writeln!(buf, "<td>{:.1}</td>", (last - first) * 100.0 / first.abs());
+                } else {
+                    buf.push_str("<td>None</td>\n");
+                }
+                buf.push_str("</tr>\n");
+            }
+            buf.push_str("</tbody>\n");
+            buf.push_str("</table>\n");
+        }
+
+        buf.push_str("</div>\n");
+        buf.push_str("</div>\n");
+
+        buf
+    }
+
+    /// Tables of what was found in the packet capture, with a row for each stream.
+    pub fn pcap_section(&self, pcap_analysis: &PcapAnalysis) -> String {
+        fn table(buf: &mut String, headings: &[&str], rows: Vec<Vec<String>>) {
+            buf.push_str("<table class=\"table\">\n");
+            buf.push_str("<thead>\n");
+            buf.push_str("<tr>\n");
+            for heading in headings {
+                let _ = writeln!(buf, "<th>{}</th>", heading);
+            }
+            buf.push_str("</tr>\n");
+            buf.push_str("</thead>\n");
+            buf.push_str("<tbody>\n");
+            for row in rows {
+                buf.push_str("<tr>\n");
+                for value in row {
+                    let _ = writeln!(buf, "<td>{}</td>", value);
+                }
+                buf.push_str("</tr>\n");
+            }
+            buf.push_str("</tbody>\n");
+            buf.push_str("</table>\n");
+        }
+
+        let optional =
+            |value: Option<f32>| value.map_or("None".to_string(), |value| format!("{:.3}", value));
+
+        let mut buf = String::new();
+
+        buf.push_str("<div class=\"p-3 row\">\n");
+        buf.push_str("<div class=\"col-md-12\">\n");
+
+        buf.push_str("<h5>Packets</h5>\n");
+        table(
+            &mut buf,
+            &[
+                "UDP",
+                "Duplicates",
+                "STUN",
+                "DTLS",
+                "TURN ChannelData",
+                "RTP",
+                "RTCP",
+                "Other",
+            ],
+            vec![[
+                pcap_analysis.udp_packets,
+                pcap_analysis.duplicate_packets,
+                pcap_analysis.stun_packets,
+                pcap_analysis.dtls_packets,
+                pcap_analysis.turn_channel_packets,
+                pcap_analysis.rtp_packets,
+                pcap_analysis.rtcp_packets,
+                pcap_analysis.other_packets,
+            ]
+            .iter()
+            .map(u32::to_string)
+            .collect()],
+        );
+
+        buf.push_str("<h5>RTP Streams</h5>\n");
+        table(
+            &mut buf,
+            &[
+                "SSRC",
+                "Kind (PT)",
+                "From",
+                "To",
+                "Packets",
+                "Packets/s",
+                "Bitrate (kbps)",
+                "Lost",
+                "Loss (%)",
+                "Reordered",
+                "Duplicates",
+                "Jitter (ms)",
+                "Max Jitter (ms)",
+                "Overhead (%)",
+            ],
+            pcap_analysis
+                .rtp_streams
+                .iter()
+                .map(|stream| {
+                    vec![
+                        stream.ssrc.clone(),
+                        format!("{:?} ({})", stream.kind, stream.payload_type),
+                        stream.source.clone(),
+                        stream.destination.clone(),
+                        stream.packets.to_string(),
+                        optional(stream.packets_per_second),
+                        optional(stream.bitrate_kbps),
+                        stream.lost.to_string(),
+                        format!("{:.3}", stream.loss_percent),
+                        stream.reordered.to_string(),
+                        stream.duplicates.to_string(),
+                        optional(stream.jitter_ms),
+                        optional(stream.max_jitter_ms),
+                        format!("{:.3}", stream.overhead_percent),
+                    ]
+                })
+                .collect(),
+        );
+
+        buf.push_str("<h5>RTCP Streams</h5>\n");
+        table(
+            &mut buf,
+            &[
+                "SSRC",
+                "From",
+                "To",
+                "Packets",
+                "Packets/s",
+                "Bitrate (kbps)",
+                "Sender Reports",
+                "Receiver Reports",
+                "Feedback",
+                "Other",
+            ],
+            pcap_analysis
+                .rtcp_streams
+                .iter()
+                .map(|stream| {
+                    vec![
+                        stream.ssrc.clone(),
+                        stream.source.clone(),
+                        stream.destination.clone(),
+                        stream.packets.to_string(),
+                        optional(stream.packets_per_second),
+                        optional(stream.bitrate_kbps),
+                        stream.sender_reports.to_string(),
+                        stream.receiver_reports.to_string(),
+                        stream.feedback.to_string(),
+                        stream.other.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+
+        buf.push_str("</div>\n");
         buf.push_str("</div>\n");
 
         buf
@@ -0,0 +1,1204 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Machine-readable test results, and comparing them against a baseline.
+//!
+//! Every test set writes a `results.json` next to its `summary.html`. The schema is versioned
+//! with [`RESULTS_SCHEMA_VERSION`], which is bumped whenever a field is renamed or its meaning
+//! changes (adding fields doesn't bump it).
+//!
+//! For comparisons, each test case is flattened into named metrics such as
+//! `scores.visqol_mos_speech` or `audio_receive.packet_loss.ave`. SSRCs are random, so the streams
+//! in a per-SSRC list are averaged together under the list's name (or, for packet captures, under
+//! their kind) rather than keyed by position. Iterations of a test case are averaged, and each
+//! metric is checked against the first [`Tolerance`] that matches its name.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio_metrics::AudioMetrics,
+    pcap::PcapAnalysis,
+    report::{AnalysisReportMos, ClientLogReport, Report, SoakReport, Stats, TimingReport},
+    test::{AudioTestResults, GroupRun},
+    test_set::Format,
+};
+
+pub const RESULTS_SCHEMA_VERSION: u32 = 2;
+pub const RESULTS_FILE_NAME: &str = "results.json";
+
+/// The statistics of a series, over the range that reports use (usually skipping the first few
+/// seconds).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatsSummary {
+    pub ave: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl StatsSummary {
+    /// Returns None if there was no data in range.
+    fn new(stats: &Stats) -> Option<Self> {
+        let data = &stats.data;
+        (data.min <= data.max && data.ave.is_finite()).then_some(Self {
+            ave: data.ave,
+            min: data.min,
+            max: data.max,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scores {
+    pub visqol_mos_speech: Option<f32>,
+    pub visqol_mos_audio: Option<f32>,
+    pub visqol_mos_average: Option<f32>,
+    pub pesq_mos: Option<f32>,
+    pub plc_mos: Option<f32>,
+    pub vmaf: Option<f32>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerResults {
+    pub cpu_usage: Option<StatsSummary>,
+    pub mem_usage: Option<StatsSummary>,
+    pub tx_bitrate: Option<StatsSummary>,
+    pub rx_bitrate: Option<StatsSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionResults {
+    pub current_round_trip_time: Option<StatsSummary>,
+    pub available_outgoing_bitrate: Option<StatsSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioSendResults {
+    pub ssrc: String,
+    pub packets_per_second: Option<StatsSummary>,
+    pub average_packet_size: Option<StatsSummary>,
+    pub bitrate: Option<StatsSummary>,
+    pub remote_packet_loss: Option<StatsSummary>,
+    pub remote_jitter: Option<StatsSummary>,
+    pub remote_round_trip_time: Option<StatsSummary>,
+    pub audio_energy: Option<StatsSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VideoSendResults {
+    pub ssrc: String,
+    pub packets_per_second: Option<StatsSummary>,
+    pub average_packet_size: Option<StatsSummary>,
+    pub bitrate: Option<StatsSummary>,
+    pub framerate: Option<StatsSummary>,
+    pub key_frames_encoded: Option<StatsSummary>,
+    pub retransmitted_packets_sent: Option<StatsSummary>,
+    pub retransmitted_bitrate: Option<StatsSummary>,
+    pub send_delay_per_packet: Option<StatsSummary>,
+    pub nack_count: Option<StatsSummary>,
+    pub pli_count: Option<StatsSummary>,
+    pub remote_packet_loss: Option<StatsSummary>,
+    pub remote_jitter: Option<StatsSummary>,
+    pub remote_round_trip_time: Option<StatsSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioReceiveResults {
+    pub ssrc: String,
+    pub packets_per_second: Option<StatsSummary>,
+    pub packet_loss: Option<StatsSummary>,
+    pub bitrate: Option<StatsSummary>,
+    pub jitter: Option<StatsSummary>,
+    pub audio_energy: Option<StatsSummary>,
+    pub jitter_buffer_delay: Option<StatsSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VideoReceiveResults {
+    pub ssrc: String,
+    pub packets_per_second: Option<StatsSummary>,
+    pub packet_loss: Option<StatsSummary>,
+    pub bitrate: Option<StatsSummary>,
+    pub framerate: Option<StatsSummary>,
+    pub key_frames_decoded: Option<StatsSummary>,
+}
+
+/// The one-way delays of client A's media, measured with its timing markers.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimingResults {
+    pub audio_delay: Option<StatsSummary>,
+    pub video_delay: Option<StatsSummary>,
+    pub av_offset: Option<StatsSummary>,
+}
+
+impl TimingResults {
+    fn new(timing_report: &TimingReport) -> Self {
+        Self {
+            audio_delay: StatsSummary::new(&timing_report.audio_delay),
+            video_delay: StatsSummary::new(&timing_report.video_delay),
+            av_offset: StatsSummary::new(&timing_report.av_offset),
+        }
+    }
+}
+
+/// A series that grew steadily over a soak test, by how high it stayed at the start and at the
+/// end.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoakGrowth {
+    pub first: f32,
+    pub last: f32,
+}
+
+/// The resources a client sampled in soak mode.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoakResults {
+    /// Keyed by the name of the series, such as `rss_kb`.
+    pub series: BTreeMap<String, StatsSummary>,
+    pub growing: BTreeMap<String, SoakGrowth>,
+    pub growing_count: u32,
+}
+
+impl SoakResults {
+    fn new(soak_report: &SoakReport) -> Self {
+        Self {
+            series: soak_report
+                .series
+                .iter()
+                .filter_map(|(name, stats)| Some((name.clone(), StatsSummary::new(stats)?)))
+                .collect(),
+            growing: soak_report
+                .growing
+                .iter()
+                .map(|(name, &(first, last))| (name.clone(), SoakGrowth { first, last }))
+                .collect(),
+            growing_count: soak_report.growing.len() as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioAdaptationResults {
+    pub bitrate: Option<StatsSummary>,
+    pub packet_length: Option<StatsSummary>,
+}
+
+/// The results of one iteration of a test case, from the perspective of the receiving client.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestCaseResults {
+    pub report_name: String,
+    pub test_case_name: String,
+    pub network_profile: String,
+    pub sound_name: String,
+    pub video_name: Option<String>,
+    pub client_name: String,
+
+    pub scores: Scores,
+    /// The built-in audio analysis, if it was enabled.
+    #[serde(default)]
+    pub audio_metrics: Option<AudioMetrics>,
+    /// Only if client A sent timing markers.
+    #[serde(default)]
+    pub timing: Option<TimingResults>,
+    /// Only if the test case captured packets.
+    #[serde(default)]
+    pub pcap: Option<PcapAnalysis>,
+    /// For each client that sampled its resources for a soak test, by client name.
+    #[serde(default)]
+    pub soak: BTreeMap<String, SoakResults>,
+    pub container: ContainerResults,
+    pub connection: ConnectionResults,
+    pub audio_send: AudioSendResults,
+    /// Sorted by SSRC, as are the receive lists.
+    pub video_send: Vec<VideoSendResults>,
+    pub audio_receive: Vec<AudioReceiveResults>,
+    pub video_receive: Vec<VideoReceiveResults>,
+    pub audio_adaptation: AudioAdaptationResults,
+    /// The other clients receiving client A's media in group calls with more than two
+    /// clients, in the order of their names.
+    #[serde(default)]
+    pub receivers: Vec<ReceiverResults>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReceiverResults {
+    pub client_name: String,
+    pub scores: Scores,
+    #[serde(default)]
+    pub audio_metrics: Option<AudioMetrics>,
+    pub audio_receive: Vec<AudioReceiveResults>,
+}
+
+impl Scores {
+    fn new(audio_results: &AudioTestResults, vmaf: Option<f32>) -> Self {
+        let mos = |mos: &AnalysisReportMos| mos.get_mos_for_display();
+        Self {
+            visqol_mos_speech: mos(&audio_results.visqol_mos_speech),
+            visqol_mos_audio: mos(&audio_results.visqol_mos_audio),
+            visqol_mos_average: mos(&audio_results.visqol_mos_average),
+            pesq_mos: mos(&audio_results.pesq_mos),
+            plc_mos: mos(&audio_results.plc_mos),
+            vmaf,
+        }
+    }
+}
+
+impl AudioReceiveResults {
+    /// Sorted by SSRC.
+    fn list(client_log_report: &ClientLogReport) -> Vec<Self> {
+        let mut audio_receive: Vec<Self> = client_log_report
+            .audio_receive_stats_list
+            .iter()
+            .map(|stats| Self {
+                ssrc: stats.ssrc.clone(),
+                packets_per_second: StatsSummary::new(&stats.packets_per_second_stats),
+                packet_loss: StatsSummary::new(&stats.packet_loss_stats),
+                bitrate: StatsSummary::new(&stats.bitrate_stats),
+                jitter: StatsSummary::new(&stats.jitter_stats),
+                audio_energy: StatsSummary::new(&stats.audio_energy_stats),
+                jitter_buffer_delay: StatsSummary::new(&stats.jitter_buffer_delay_stats),
+            })
+            .collect();
+        audio_receive.sort_by(|a, b| a.ssrc.cmp(&b.ssrc));
+        audio_receive
+    }
+}
+
+impl TestCaseResults {
+    fn new(report: &Report) -> Self {
+        let client_log_report = &report.client_log_report;
+        let docker_stats_report = &report.docker_stats_report;
+        let audio_send = &client_log_report.audio_send_stats;
+
+        let mut video_send: Vec<VideoSendResults> = client_log_report
+            .video_send_stats
+            .iter()
+            .map(|stats| VideoSendResults {
+                ssrc: stats.ssrc.clone(),
+                packets_per_second: StatsSummary::new(&stats.packets_per_second_stats),
+                average_packet_size: StatsSummary::new(&stats.average_packet_size_stats),
+                bitrate: StatsSummary::new(&stats.bitrate_stats),
+                framerate: StatsSummary::new(&stats.framerate_stats),
+                key_frames_encoded: StatsSummary::new(&stats.key_frames_encoded_stats),
+                retransmitted_packets_sent: StatsSummary::new(
+                    &stats.retransmitted_packets_sent_stats,
+                ),
+                retransmitted_bitrate: StatsSummary::new(&stats.retransmitted_bitrate_stats),
+                send_delay_per_packet: StatsSummary::new(&stats.send_delay_per_packet_stats),
+                nack_count: StatsSummary::new(&stats.nack_count_stats),
+                pli_count: StatsSummary::new(&stats.pli_count_stats),
+                remote_packet_loss: StatsSummary::new(&stats.remote_packet_loss_stats),
+                remote_jitter: StatsSummary::new(&stats.remote_jitter_stats),
+                remote_round_trip_time: StatsSummary::new(&stats.remote_round_trip_time_stats),
+            })
+            .collect();
+        video_send.sort_by(|a, b| a.ssrc.cmp(&b.ssrc));
+
+        let mut video_receive: Vec<VideoReceiveResults> = client_log_report
+            .video_receive_stats_list
+            .iter()
+            .map(|stats| VideoReceiveResults {
+                ssrc: stats.ssrc.clone(),
+                packets_per_second: StatsSummary::new(&stats.packets_per_second_stats),
+                packet_loss: StatsSummary::new(&stats.packet_loss_stats),
+                bitrate: StatsSummary::new(&stats.bitrate_stats),
+                framerate: StatsSummary::new(&stats.framerate_stats),
+                key_frames_decoded: StatsSummary::new(&stats.key_frames_decoded_stats),
+            })
+            .collect();
+        video_receive.sort_by(|a, b| a.ssrc.cmp(&b.ssrc));
+
+        Self {
+            report_name: report.report_name.clone(),
+            test_case_name: report.test_case_name.clone(),
+            network_profile: report.network_profile.get_name(),
+            sound_name: report.sound_name.clone(),
+            video_name: (!report.video_name.is_empty()).then(|| report.video_name.clone()),
+            client_name: report.client_name.clone(),
+            scores: Scores::new(
+                &report.analysis_report.audio_test_results,
+                report.analysis_report.vmaf,
+            ),
+            audio_metrics: report
+                .analysis_report
+                .audio_test_results
+                .audio_metrics
+                .clone(),
+            timing: report.timing_report.as_ref().map(TimingResults::new),
+            pcap: report.pcap_analysis.clone(),
+            soak: report
+                .soak_reports
+                .iter()
+                .map(|soak_report| {
+                    (
+                        soak_report.client_name.clone(),
+                        SoakResults::new(soak_report),
+                    )
+                })
+                .collect(),
+            container: ContainerResults {
+                cpu_usage: StatsSummary::new(&docker_stats_report.cpu_usage),
+                mem_usage: StatsSummary::new(&docker_stats_report.mem_usage),
+                tx_bitrate: StatsSummary::new(&docker_stats_report.tx_bitrate),
+                rx_bitrate: StatsSummary::new(&docker_stats_report.rx_bitrate),
+            },
+            connection: ConnectionResults {
+                current_round_trip_time: StatsSummary::new(
+                    &client_log_report
+                        .connection_stats
+                        .current_round_trip_time_stats,
+                ),
+                available_outgoing_bitrate: StatsSummary::new(
+                    &client_log_report
+                        .connection_stats
+                        .available_outgoing_bitrate_stats,
+                ),
+            },
+            audio_send: AudioSendResults {
+                ssrc: audio_send.ssrc.clone(),
+                packets_per_second: StatsSummary::new(&audio_send.packets_per_second_stats),
+                average_packet_size: StatsSummary::new(&audio_send.average_packet_size_stats),
+                bitrate: StatsSummary::new(&audio_send.bitrate_stats),
+                remote_packet_loss: StatsSummary::new(&audio_send.remote_packet_loss_stats),
+                remote_jitter: StatsSummary::new(&audio_send.remote_jitter_stats),
+                remote_round_trip_time: StatsSummary::new(&audio_send.remote_round_trip_time_stats),
+                audio_energy: StatsSummary::new(&audio_send.audio_energy_stats),
+            },
+            video_send,
+            audio_receive: AudioReceiveResults::list(client_log_report),
+            video_receive,
+            audio_adaptation: AudioAdaptationResults {
+                bitrate: StatsSummary::new(&client_log_report.audio_adaptation.bitrate_stats),
+                packet_length: StatsSummary::new(
+                    &client_log_report.audio_adaptation.packet_length_stats,
+                ),
+            },
+            receivers: report
+                .receivers
+                .iter()
+                .map(|receiver| ReceiverResults {
+                    client_name: receiver.client_name.clone(),
+                    scores: Scores::new(&receiver.audio_test_results, None),
+                    audio_metrics: receiver.audio_test_results.audio_metrics.clone(),
+                    audio_receive: AudioReceiveResults::list(&receiver.client_log_report),
+                })
+                .collect(),
+        }
+    }
+
+    /// Flattens every number in the results into a metric named by its path, such as
+    /// `audio_send.bitrate.ave`. Missing values are left out, and per-SSRC lists are averaged
+    /// by stream kind (see the module docs).
+    pub fn metrics(&self) -> BTreeMap<String, f64> {
+        fn flatten(prefix: &str, value: &serde_json::Value, metrics: &mut BTreeMap<String, f64>) {
+            let join = |key: &dyn std::fmt::Display| {
+                if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{prefix}.{key}")
+                }
+            };
+            match value {
+                serde_json::Value::Number(number) => {
+                    if let Some(number) = number.as_f64() {
+                        metrics.insert(prefix.to_string(), number);
+                    }
+                }
+                serde_json::Value::Object(object) => {
+                    for (key, value) in object {
+                        flatten(&join(key), value, metrics);
+                    }
+                }
+                serde_json::Value::Array(array)
+                    if array.iter().all(|value| value.get("ssrc").is_some()) =>
+                {
+                    let mut sums: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+                    for value in array {
+                        let stream_prefix = match value.get("kind").and_then(|kind| kind.as_str()) {
+                            Some(kind) => join(&kind),
+                            None => prefix.to_string(),
+                        };
+                        let mut stream_metrics = BTreeMap::new();
+                        flatten(&stream_prefix, value, &mut stream_metrics);
+                        for (metric, number) in stream_metrics {
+                            let (sum, count) = sums.entry(metric).or_default();
+                            *sum += number;
+                            *count += 1;
+                        }
+                    }
+                    metrics.extend(
+                        sums.into_iter()
+                            .map(|(metric, (sum, count))| (metric, sum / count as f64)),
+                    );
+                }
+                serde_json::Value::Array(array) => {
+                    for (index, value) in array.iter().enumerate() {
+                        flatten(&join(&index), value, metrics);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut metrics = BTreeMap::new();
+        let value = serde_json::to_value(self).expect("results are always valid JSON");
+        flatten("", &value, &mut metrics);
+        metrics
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupResults {
+    pub group_name: String,
+    /// One entry per iteration of each test case that ran successfully.
+    pub test_cases: Vec<TestCaseResults>,
+    /// The errors of test cases that didn't.
+    pub failures: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SetResults {
+    pub schema_version: u32,
+    pub set_name: String,
+    pub time_started: String,
+    pub groups: Vec<GroupResults>,
+}
+
+impl SetResults {
+    pub fn new(set_name: &str, time_started: &str, group_runs: &[GroupRun]) -> Self {
+        Self {
+            schema_version: RESULTS_SCHEMA_VERSION,
+            set_name: set_name.to_string(),
+            time_started: time_started.to_string(),
+            groups: group_runs
+                .iter()
+                .map(|group_run| GroupResults {
+                    group_name: group_run.group_config.group_name.clone(),
+                    test_cases: group_run
+                        .reports
+                        .iter()
+                        .flatten()
+                        .map(TestCaseResults::new)
+                        .collect(),
+                    failures: group_run
+                        .reports
+                        .iter()
+                        .filter_map(|report| report.as_ref().err())
+                        .map(|err| format!("{err:#}"))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read results file {}", path.display()))?;
+        let results: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("invalid results file {}", path.display()))?;
+        if results.schema_version != RESULTS_SCHEMA_VERSION {
+            bail!(
+                "results file {} has schema version {}, but {} is expected",
+                path.display(),
+                results.schema_version,
+                RESULTS_SCHEMA_VERSION
+            );
+        }
+        Ok(results)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write results file {}", path.display()))
+    }
+
+    /// The metrics of each test case, averaged over its iterations and keyed by
+    /// `<group name>/<report name>`.
+    fn averaged_metrics(&self) -> BTreeMap<String, BTreeMap<String, f64>> {
+        let mut sums: BTreeMap<String, BTreeMap<String, (f64, usize)>> = BTreeMap::new();
+        for group in &self.groups {
+            for test_case in &group.test_cases {
+                let sum = sums
+                    .entry(format!("{}/{}", group.group_name, test_case.report_name))
+                    .or_default();
+                for (metric, value) in test_case.metrics() {
+                    let (total, count) = sum.entry(metric).or_default();
+                    *total += value;
+                    *count += 1;
+                }
+            }
+        }
+        sums.into_iter()
+            .map(|(key, sum)| {
+                let averages = sum
+                    .into_iter()
+                    .map(|(metric, (total, count))| (metric, total / count as f64))
+                    .collect();
+                (key, averages)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Better {
+    Higher,
+    Lower,
+}
+
+/// How much a metric may change from the baseline before it counts as a regression.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tolerance {
+    /// The name of the metric, where `*` matches any run of characters.
+    pub metric: String,
+    /// Which way is an improvement. If not given, a change either way beyond the tolerance is a
+    /// regression.
+    #[serde(default)]
+    pub better: Option<Better>,
+    /// The allowed change, in the units of the metric.
+    #[serde(default)]
+    pub absolute: Option<f64>,
+    /// The allowed change, as a fraction of the baseline value.
+    #[serde(default)]
+    pub relative: Option<f64>,
+    /// Don't check the metric at all.
+    #[serde(default)]
+    pub ignore: bool,
+}
+
+impl Tolerance {
+    fn new(metric: &str, better: Better, absolute: Option<f64>, relative: Option<f64>) -> Self {
+        Self {
+            metric: metric.to_string(),
+            better: Some(better),
+            absolute,
+            relative,
+            ignore: false,
+        }
+    }
+
+    /// Returns the limit the current value went past, if it did.
+    fn check(&self, baseline: f64, current: f64) -> Option<f64> {
+        let allowed = self
+            .absolute
+            .unwrap_or(0.0)
+            .max(self.relative.unwrap_or(0.0) * baseline.abs());
+        match self.better {
+            Some(Better::Higher) => Some(baseline - allowed).filter(|limit| current < *limit),
+            Some(Better::Lower) => Some(baseline + allowed).filter(|limit| current > *limit),
+            None => {
+                if current < baseline - allowed {
+                    Some(baseline - allowed)
+                } else if current > baseline + allowed {
+                    Some(baseline + allowed)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tolerances {
+    tolerances: Vec<Tolerance>,
+}
+
+impl Default for Tolerances {
+    /// Only the metrics that reliably reflect call quality are checked by default. Everything
+    /// else depends too much on the test machine.
+    fn default() -> Self {
+        Self {
+            tolerances: vec![
+                Tolerance::new("scores.vmaf", Better::Higher, Some(2.0), None),
+                Tolerance::new("scores.*", Better::Higher, Some(0.15), None),
+                Tolerance::new(
+                    "audio_receive.packet_loss.ave",
+                    Better::Lower,
+                    Some(1.0),
+                    None,
+                ),
+                Tolerance::new("receivers.*.scores.*", Better::Higher, Some(0.15), None),
+                Tolerance::new(
+                    "receivers.*.audio_receive.packet_loss.ave",
+                    Better::Lower,
+                    Some(1.0),
+                    None,
+                ),
+                // Both for client B and the other receivers.
+                Tolerance::new(
+                    "*audio_metrics.segmental_snr_db",
+                    Better::Higher,
+                    Some(1.0),
+                    None,
+                ),
+                Tolerance::new(
+                    "*audio_metrics.gap_ms",
+                    Better::Lower,
+                    Some(100.0),
+                    Some(0.25),
+                ),
+                Tolerance::new(
+                    "*audio_metrics.concealed_ms",
+                    Better::Lower,
+                    Some(100.0),
+                    Some(0.25),
+                ),
+                Tolerance::new(
+                    "*audio_metrics.latency_ms",
+                    Better::Lower,
+                    Some(20.0),
+                    Some(0.25),
+                ),
+                Tolerance::new("timing.*_delay.ave", Better::Lower, Some(20.0), Some(0.25)),
+                // Lip sync gets worse whichever of audio and video falls behind.
+                Tolerance {
+                    metric: "timing.av_offset.ave".to_string(),
+                    better: None,
+                    absolute: Some(40.0),
+                    relative: None,
+                    ignore: false,
+                },
+                // Any more series growing than in the baseline could be a leak.
+                Tolerance::new("soak.*.growing_count", Better::Lower, None, None),
+                Tolerance::new(
+                    "video_receive.packet_loss.ave",
+                    Better::Lower,
+                    Some(1.0),
+                    None,
+                ),
+                Tolerance::new(
+                    "connection.current_round_trip_time.ave",
+                    Better::Lower,
+                    Some(10.0),
+                    Some(0.25),
+                ),
+            ],
+        }
+    }
+}
+
+impl Tolerances {
+    /// Loads tolerances from a TOML or YAML file. They take precedence over the defaults.
+    pub fn load(path: &Path) -> Result<Self> {
+        let Some(format) = Format::from_path(path) else {
+            bail!(
+                "tolerances file {} must have a .toml, .yaml, or .yml extension",
+                path.display()
+            );
+        };
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read tolerances file {}", path.display()))?;
+        let mut tolerances: Self = format
+            .deserialize(&contents)
+            .with_context(|| format!("invalid tolerances file {}", path.display()))?;
+        tolerances.tolerances.extend(Self::default().tolerances);
+        Ok(tolerances)
+    }
+
+    fn find(&self, metric: &str) -> Option<&Tolerance> {
+        self.tolerances
+            .iter()
+            .find(|tolerance| wildcard_match(&tolerance.metric, metric))
+            .filter(|tolerance| !tolerance.ignore)
+    }
+}
+
+/// Matches `name` against `pattern`, where `*` matches any run of characters.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|i| name.is_char_boundary(*i))
+                .any(|i| wildcard_match(rest, &name[i..]))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    /// `<group name>/<report name>`
+    pub test_case: String,
+    pub metric: String,
+    pub baseline: f64,
+    /// None if the metric is missing.
+    pub current: Option<f64>,
+    /// The limit that was crossed.
+    pub limit: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+pub struct Comparison {
+    pub regressions: Vec<Regression>,
+    /// Test cases in the baseline but not in the current results.
+    pub missing_test_cases: Vec<String>,
+    /// Test cases in the current results but not in the baseline, which can't be checked.
+    pub new_test_cases: Vec<String>,
+    pub checked_metric_count: usize,
+}
+
+impl Comparison {
+    pub fn new(baseline: &SetResults, current: &SetResults, tolerances: &Tolerances) -> Self {
+        let baseline = baseline.averaged_metrics();
+        let current = current.averaged_metrics();
+        let mut comparison = Self {
+            new_test_cases: current
+                .keys()
+                .filter(|key| !baseline.contains_key(*key))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        for (test_case, baseline_metrics) in &baseline {
+            let Some(current_metrics) = current.get(test_case) else {
+                comparison.missing_test_cases.push(test_case.clone());
+                continue;
+            };
+            for (metric, baseline_value) in baseline_metrics {
+                let Some(tolerance) = tolerances.find(metric) else {
+                    continue;
+                };
+                comparison.checked_metric_count += 1;
+                let current_value = current_metrics.get(metric).copied();
+                let limit = match current_value {
+                    Some(current_value) => {
+                        let Some(limit) = tolerance.check(*baseline_value, current_value) else {
+                            continue;
+                        };
+                        Some(limit)
+                    }
+                    None => None,
+                };
+                comparison.regressions.push(Regression {
+                    test_case: test_case.clone(),
+                    metric: metric.clone(),
+                    baseline: *baseline_value,
+                    current: current_value,
+                    limit,
+                });
+            }
+        }
+        comparison
+    }
+
+    pub fn passed(&self) -> bool {
+        self.regressions.is_empty() && self.missing_test_cases.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!(
+            "  Checked {} metrics against the baseline.",
+            self.checked_metric_count
+        );
+        for test_case in &self.new_test_cases {
+            println!("  New (not in the baseline): {}", test_case);
+        }
+        for test_case in &self.missing_test_cases {
+            println!("  MISSING: {}", test_case);
+        }
+        for regression in &self.regressions {
+            match (regression.current, regression.limit) {
+                (Some(current), Some(limit)) => println!(
+                    "  REGRESSION: {} {}: {:.3} -> {:.3} (limit {:.3})",
+                    regression.test_case, regression.metric, regression.baseline, current, limit
+                ),
+                _ => println!(
+                    "  REGRESSION: {} {}: {:.3} -> missing",
+                    regression.test_case, regression.metric, regression.baseline
+                ),
+            }
+        }
+    }
+}
+
+/// Compares results against their baseline, which is `<baseline dir>/<set name>.json`, and prints
+/// what regressed. Returns whether there were no regressions.
+pub fn compare_with_baseline(
+    results: &SetResults,
+    baseline_dir: &Path,
+    tolerances: &Tolerances,
+) -> Result<bool> {
+    let baseline_path = baseline_dir.join(format!("{}.json", results.set_name));
+    println!(
+        "\nComparing test set {} with the baseline {}",
+        results.set_name,
+        baseline_path.display()
+    );
+    if !baseline_path.is_file() {
+        // Passing would hide a misconfigured gate.
+        println!(
+            "  There is no baseline. To make one, copy the {} of a good run.",
+            RESULTS_FILE_NAME
+        );
+        return Ok(false);
+    }
+    let baseline = SetResults::load(&baseline_path)?;
+    let comparison = Comparison::new(&baseline, results, tolerances);
+    comparison.print();
+    Ok(comparison.passed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcap::{RtpStreamStats, StreamKind};
+
+    fn test_case(report_name: &str, mos: f32, loss: f32) -> TestCaseResults {
+        TestCaseResults {
+            report_name: report_name.to_string(),
+            scores: Scores {
+                visqol_mos_speech: Some(mos),
+                ..Default::default()
+            },
+            audio_receive: vec![AudioReceiveResults {
+                ssrc: "1234".to_string(),
+                packet_loss: Some(StatsSummary {
+                    ave: loss,
+                    min: 0.0,
+                    max: loss * 2.0,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn set_results(test_cases: Vec<TestCaseResults>) -> SetResults {
+        SetResults {
+            schema_version: RESULTS_SCHEMA_VERSION,
+            set_name: "baseline".to_string(),
+            time_started: "2026-10-18 12:00:00".to_string(),
+            groups: vec![GroupResults {
+                group_name: "baseline".to_string(),
+                test_cases,
+                failures: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("scores.vmaf", "scores.vmaf"));
+        assert!(!wildcard_match("scores.vmaf", "scores.vmaf2"));
+        assert!(wildcard_match("scores.*", "scores.pesq_mos"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match(
+            "audio_receive.*.packet_loss.ave",
+            "audio_receive.10.packet_loss.ave"
+        ));
+        assert!(!wildcard_match(
+            "audio_receive.*.packet_loss.ave",
+            "audio_receive.0.packet_loss.max"
+        ));
+        assert!(wildcard_match("*.ave", "container.cpu_usage.ave"));
+    }
+
+    #[test]
+    fn metrics() {
+        let metrics = test_case("a", 4.0, 2.0).metrics();
+        assert_eq!(Some(&4.0), metrics.get("scores.visqol_mos_speech"));
+        assert_eq!(Some(&2.0), metrics.get("audio_receive.packet_loss.ave"));
+        assert_eq!(Some(&4.0), metrics.get("audio_receive.packet_loss.max"));
+        // Missing values and strings aren't metrics.
+        assert!(!metrics.contains_key("scores.pesq_mos"));
+        assert!(!metrics.keys().any(|metric| metric.contains("ssrc")));
+
+        // Streams are averaged, whatever order their SSRCs put them in.
+        let mut results = test_case("a", 4.0, 2.0);
+        results.audio_receive.insert(
+            0,
+            AudioReceiveResults {
+                ssrc: "5678".to_string(),
+                ..test_case("b", 4.0, 4.0).audio_receive[0].clone()
+            },
+        );
+        assert_eq!(
+            Some(&3.0),
+            results.metrics().get("audio_receive.packet_loss.ave")
+        );
+
+        let receiver = test_case("c", 3.5, 3.0);
+        let metrics = TestCaseResults {
+            receivers: vec![ReceiverResults {
+                client_name: "client_c".to_string(),
+                scores: receiver.scores,
+                audio_metrics: None,
+                audio_receive: receiver.audio_receive,
+            }],
+            ..test_case("a", 4.0, 2.0)
+        }
+        .metrics();
+        assert_eq!(
+            Some(&3.5),
+            metrics.get("receivers.0.scores.visqol_mos_speech")
+        );
+        assert_eq!(
+            Some(&3.0),
+            metrics.get("receivers.0.audio_receive.packet_loss.ave")
+        );
+        assert!(Tolerances::default()
+            .find("receivers.0.audio_receive.packet_loss.ave")
+            .is_some_and(|tolerance| tolerance.better == Some(Better::Lower)));
+    }
+
+    #[test]
+    fn audio_metrics() {
+        let results = TestCaseResults {
+            audio_metrics: Some(AudioMetrics {
+                latency_ms: 120.0,
+                segmental_snr_db: 12.5,
+                gap_ms: 40,
+                ..Default::default()
+            }),
+            ..test_case("a", 4.0, 2.0)
+        };
+        let metrics = results.metrics();
+        assert_eq!(Some(&120.0), metrics.get("audio_metrics.latency_ms"));
+        assert_eq!(Some(&12.5), metrics.get("audio_metrics.segmental_snr_db"));
+        assert_eq!(Some(&40.0), metrics.get("audio_metrics.gap_ms"));
+
+        let tolerances = Tolerances::default();
+        for metric in [
+            "audio_metrics.segmental_snr_db",
+            "receivers.1.audio_metrics.segmental_snr_db",
+        ] {
+            assert!(tolerances
+                .find(metric)
+                .is_some_and(|tolerance| tolerance.better == Some(Better::Higher)));
+        }
+        assert!(tolerances
+            .find("receivers.1.audio_metrics.gap_ms")
+            .is_some_and(|tolerance| tolerance.better == Some(Better::Lower)));
+        assert!(tolerances.find("audio_metrics.active_ms").is_none());
+
+        // Results from before the built-in analysis existed still load.
+        let mut value = serde_json::to_value(test_case("a", 4.0, 2.0)).unwrap();
+        value.as_object_mut().unwrap().remove("audio_metrics");
+        let old: TestCaseResults = serde_json::from_value(value).unwrap();
+        assert_eq!(None, old.audio_metrics);
+    }
+
+    #[test]
+    fn timing() {
+        let summary = |ave| {
+            Some(StatsSummary {
+                ave,
+                min: ave,
+                max: ave,
+            })
+        };
+        let results = TestCaseResults {
+            timing: Some(TimingResults {
+                audio_delay: summary(150.0),
+                video_delay: summary(100.0),
+                av_offset: summary(50.0),
+            }),
+            ..test_case("a", 4.0, 2.0)
+        };
+        let metrics = results.metrics();
+        assert_eq!(Some(&150.0), metrics.get("timing.audio_delay.ave"));
+        assert_eq!(Some(&50.0), metrics.get("timing.av_offset.ave"));
+
+        let tolerances = Tolerances::default();
+        for metric in ["timing.audio_delay.ave", "timing.video_delay.ave"] {
+            assert!(tolerances
+                .find(metric)
+                .is_some_and(|tolerance| tolerance.better == Some(Better::Lower)));
+        }
+        let av_offset = tolerances.find("timing.av_offset.ave").unwrap();
+        assert_eq!(Some(10.0), av_offset.check(50.0, 0.0));
+        assert_eq!(Some(90.0), av_offset.check(50.0, 100.0));
+        assert!(tolerances.find("timing.av_offset.max").is_none());
+    }
+
+    #[test]
+    fn pcap() {
+        let results = TestCaseResults {
+            pcap: Some(PcapAnalysis {
+                rtp_packets: 1000,
+                rtp_streams: vec![RtpStreamStats {
+                    ssrc: "1234".to_string(),
+                    kind: StreamKind::Audio,
+                    loss_percent: 2.5,
+                    jitter_ms: Some(4.0),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..test_case("a", 4.0, 2.0)
+        };
+        let metrics = results.metrics();
+        assert_eq!(Some(&1000.0), metrics.get("pcap.rtp_packets"));
+        assert_eq!(
+            Some(&2.5),
+            metrics.get("pcap.rtp_streams.audio.loss_percent")
+        );
+        assert_eq!(Some(&4.0), metrics.get("pcap.rtp_streams.audio.jitter_ms"));
+        assert!(!metrics.contains_key("pcap.rtp_streams.audio.max_jitter_ms"));
+        // Captures can pick up other traffic, so their streams aren't compared by default.
+        assert!(Tolerances::default()
+            .find("pcap.rtp_streams.audio.loss_percent")
+            .is_none());
+
+        let value = serde_json::to_value(&results).unwrap();
+        assert_eq!("audio", value["pcap"]["rtp_streams"][0]["kind"]);
+        let loaded: TestCaseResults = serde_json::from_value(value).unwrap();
+        assert_eq!(results, loaded);
+    }
+
+    #[test]
+    fn soak() {
+        let results = TestCaseResults {
+            soak: BTreeMap::from([(
+                "client_b".to_string(),
+                SoakResults {
+                    series: BTreeMap::from([(
+                        "rss_kb".to_string(),
+                        StatsSummary {
+                            ave: 90000.0,
+                            min: 80000.0,
+                            max: 100000.0,
+                        },
+                    )]),
+                    growing: BTreeMap::from([(
+                        "rss_kb".to_string(),
+                        SoakGrowth {
+                            first: 80000.0,
+                            last: 95000.0,
+                        },
+                    )]),
+                    growing_count: 1,
+                },
+            )]),
+            ..test_case("a", 4.0, 2.0)
+        };
+        let metrics = results.metrics();
+        assert_eq!(
+            Some(&100000.0),
+            metrics.get("soak.client_b.series.rss_kb.max")
+        );
+        assert_eq!(
+            Some(&95000.0),
+            metrics.get("soak.client_b.growing.rss_kb.last")
+        );
+
+        let tolerances = Tolerances::default();
+        let growing_count = tolerances.find("soak.client_b.growing_count").unwrap();
+        assert_eq!(None, growing_count.check(1.0, 1.0));
+        assert_eq!(Some(1.0), growing_count.check(1.0, 2.0));
+        // Resources depend on the test machine.
+        assert!(tolerances.find("soak.client_b.series.rss_kb.max").is_none());
+
+        let value = serde_json::to_value(&results).unwrap();
+        let loaded: TestCaseResults = serde_json::from_value(value).unwrap();
+        assert_eq!(results, loaded);
+    }
+
+    #[test]
+    fn check_tolerance() {
+        let higher = Tolerance::new("", Better::Higher, Some(0.1), None);
+        assert_eq!(None, higher.check(4.0, 3.95));
+        assert_eq!(None, higher.check(4.0, 5.0));
+        assert_eq!(Some(3.9), higher.check(4.0, 3.8));
+
+        let lower = Tolerance::new("", Better::Lower, Some(1.0), Some(0.5));
+        assert_eq!(None, lower.check(10.0, 14.0));
+        assert_eq!(Some(15.0), lower.check(10.0, 16.0));
+
+        let either = Tolerance {
+            better: None,
+            ..Tolerance::new("", Better::Lower, Some(1.0), None)
+        };
+        assert_eq!(Some(9.0), either.check(10.0, 8.0));
+        assert_eq!(Some(11.0), either.check(10.0, 12.0));
+        assert_eq!(None, either.check(10.0, 10.5));
+    }
+
+    #[test]
+    fn compare() {
+        let baseline = set_results(vec![
+            test_case("a", 4.0, 1.0),
+            test_case("a", 4.2, 1.0),
+            test_case("b", 4.0, 1.0),
+        ]);
+
+        // Iterations are averaged, so "a" is compared against 4.1.
+        let current = set_results(vec![test_case("a", 4.0, 1.5), test_case("b", 4.0, 1.0)]);
+        let comparison = Comparison::new(&baseline, &current, &Tolerances::default());
+        assert!(comparison.passed(), "{:?}", comparison);
+        assert_eq!(4, comparison.checked_metric_count);
+
+        let current = set_results(vec![test_case("a", 3.8, 3.0), test_case("c", 4.0, 1.0)]);
+        let comparison = Comparison::new(&baseline, &current, &Tolerances::default());
+        assert!(!comparison.passed());
+        assert_eq!(
+            vec!["baseline/b".to_string()],
+            comparison.missing_test_cases
+        );
+        assert_eq!(vec!["baseline/c".to_string()], comparison.new_test_cases);
+        let regressed_metrics: Vec<&str> = comparison
+            .regressions
+            .iter()
+            .map(|regression| regression.metric.as_str())
+            .collect();
+        assert_eq!(
+            vec!["audio_receive.packet_loss.ave", "scores.visqol_mos_speech"],
+            regressed_metrics
+        );
+
+        // Tolerances from a file take precedence.
+        let tolerances = Tolerances {
+            tolerances: [
+                Format::Toml
+                    .deserialize::<Tolerances>(
+                        r#"
+                        [[tolerances]]
+                        metric = "audio_receive.*"
+                        ignore = true
+
+                        [[tolerances]]
+                        metric = "scores.visqol_mos_speech"
+                        better = "higher"
+                        absolute = 0.5
+                        "#,
+                    )
+                    .unwrap()
+                    .tolerances,
+                Tolerances::default().tolerances,
+            ]
+            .concat(),
+        };
+        let current = set_results(vec![test_case("a", 3.8, 3.0), test_case("b", 4.0, 9.0)]);
+        let comparison = Comparison::new(&baseline, &current, &tolerances);
+        assert!(comparison.passed(), "{:?}", comparison);
+    }
+
+    #[test]
+    fn missing_metric() {
+        let baseline = set_results(vec![test_case("a", 4.0, 1.0)]);
+        let mut current = set_results(vec![test_case("a", 4.0, 1.0)]);
+        current.groups[0].test_cases[0].scores.visqol_mos_speech = None;
+        let comparison = Comparison::new(&baseline, &current, &Tolerances::default());
+        assert_eq!(
+            vec![Regression {
+                test_case: "baseline/a".to_string(),
+                metric: "scores.visqol_mos_speech".to_string(),
+                baseline: 4.0,
+                current: None,
+                limit: None,
+            }],
+            comparison.regressions
+        );
+    }
+}
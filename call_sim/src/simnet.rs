@@ -0,0 +1,286 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Runs test cases with `call_sim-cli --simnet-config`, which runs both clients in one process
+//! linked by a simulated network. No containers, tc/netem, signaling server or TURN server are
+//! needed, so this is quicker to start and works where Docker networking isn't available.
+//!
+//! The clients write the same logs, stats and media files as they do in containers, so the
+//! artifacts and reports are generated the same way.
+
+use std::{fs::File, process::Stdio};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::{
+    common::{
+        CallConfig, ClientProfile, GeLossModel, Loss, MarkovLossModel, NetworkConfig,
+        NetworkConfigWithOffset, TestCaseConfig,
+    },
+    docker::cli_args,
+    test::{CallTypeConfig, Client, TestCase},
+};
+
+/// The queue used when a rate is given without a limit, in packets. This is netem's default.
+const DEFAULT_LIMIT: u32 = 1000;
+/// Used to convert a limit in packets to a queue size.
+const MAX_PACKET_SIZE_BYTES: u64 = 1500;
+
+/// Mirrors `SimnetConfig` in `call_sim-cli`.
+#[derive(Serialize)]
+struct SimnetConfig {
+    length_secs: u64,
+    endpoints: Vec<SimnetEndpointConfig>,
+    timeline: Vec<SimnetLinkChange>,
+}
+
+#[derive(Serialize)]
+struct SimnetEndpointConfig {
+    role: &'static str,
+    args: Vec<String>,
+    stats_file: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SimnetLinkChange {
+    offset_secs: u64,
+    link: SimnetLink,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct SimnetLink {
+    delay_min_ms: u64,
+    delay_max_ms: u64,
+    loss_probability: f64,
+    repeated_loss_probability: f64,
+    rate_kbps: u64,
+    queue_size_bytes: u64,
+}
+
+impl SimnetLink {
+    /// Converts netem settings to a simulated link, failing for settings that it can't emulate.
+    fn from_network_config(network_config: &NetworkConfig) -> Result<Self> {
+        if network_config.duplication > 0
+            || network_config.corruption > 0
+            || network_config.reorder > 0
+            || network_config.slot > 0
+        {
+            bail!("duplication, corruption, reordering and slots can't be simulated");
+        }
+
+        if network_config.delay_variation_strategy.is_some() {
+            println!("  Note: delay variation is always uniform when simulated.");
+        }
+        let delay = u64::from(network_config.delay);
+        let delay_variability = u64::from(network_config.delay_variability);
+
+        // The bad state always loses packets, so the chance of losing another packet after one
+        // was lost is the chance of staying in that state.
+        let (loss, repeated_loss) = match network_config.loss {
+            None => (0, 0),
+            Some(Loss::Percentage(p))
+            | Some(Loss::GeModel(GeLossModel::Bernoulli { p }))
+            | Some(Loss::State(MarkovLossModel::Bernoulli { p13: p })) => (p, p),
+            Some(Loss::GeModel(GeLossModel::SimpleGilbert { p, r }))
+            | Some(Loss::State(MarkovLossModel::TwoState { p13: p, p31: r })) => {
+                (p, 100u8.saturating_sub(r))
+            }
+            Some(loss) => bail!("{:?} can't be simulated", loss),
+        };
+
+        let limit = match (network_config.limit, network_config.rate) {
+            (0, 0) => 0,
+            (0, _) => DEFAULT_LIMIT,
+            (limit, _) => limit,
+        };
+
+        Ok(Self {
+            delay_min_ms: delay.saturating_sub(delay_variability),
+            delay_max_ms: delay + delay_variability,
+            loss_probability: f64::from(loss) / 100.0,
+            repeated_loss_probability: f64::from(repeated_loss) / 100.0,
+            rate_kbps: u64::from(network_config.rate),
+            queue_size_bytes: u64::from(limit) * MAX_PACKET_SIZE_BYTES,
+        })
+    }
+}
+
+/// Runs a direct call between the test case's clients, returning when it has finished.
+pub async fn run_test(
+    cli_path: &str,
+    set_path: &str,
+    test_case: &TestCase<'_>,
+    test_case_config: &TestCaseConfig,
+    network_configs: &[NetworkConfigWithOffset],
+    client_profiles: &[ClientProfile],
+    call_type: &CallTypeConfig,
+) -> Result<()> {
+    if *call_type != CallTypeConfig::Direct {
+        bail!("group calls can't be simulated");
+    }
+    if !test_case_config.client_a_config.relay_servers.is_empty()
+        || !test_case_config.client_b_config.relay_servers.is_empty()
+    {
+        bail!("relay servers can't be simulated");
+    }
+    if test_case_config.tcp_dump {
+        bail!("tcp_dump isn't available when simulated");
+    }
+    if test_case_config.client_a_config.export_stats
+        || test_case_config.client_b_config.export_stats
+    {
+        bail!("export_stats isn't available when simulated");
+    }
+    if test_case_config
+        .client_configs()
+        .any(|call_config| call_config.soak_sample_interval_secs.is_some())
+    {
+        bail!("soak sampling isn't available when simulated");
+    }
+
+    let timeline = network_configs
+        .iter()
+        .map(|timed_network_config| {
+            Ok(SimnetLinkChange {
+                offset_secs: timed_network_config.offset.as_secs(),
+                link: SimnetLink::from_network_config(&timed_network_config.network_config)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let endpoint = |role,
+                    client: &Client,
+                    local_config: &CallConfig,
+                    remote_config: &CallConfig,
+                    client_profile: &ClientProfile| {
+        Ok::<_, anyhow::Error>(SimnetEndpointConfig {
+            role,
+            args: cli_args(
+                client.name,
+                client.media_io(test_case_config.save_media_files),
+                local_config,
+                remote_config,
+                client_profile,
+                call_type,
+                &test_case.test_path,
+                set_path,
+            )?,
+            stats_file: Some(format!("{}/{}_stats.log", test_case.test_path, client.name)),
+        })
+    };
+
+    let config = SimnetConfig {
+        length_secs: u64::from(test_case_config.length_seconds),
+        endpoints: vec![
+            endpoint(
+                "caller",
+                test_case.client_a,
+                &test_case_config.client_a_config,
+                &test_case_config.client_b_config,
+                &client_profiles[0],
+            )?,
+            endpoint(
+                "callee",
+                test_case.client_b,
+                &test_case_config.client_b_config,
+                &test_case_config.client_a_config,
+                &client_profiles[1],
+            )?,
+        ],
+        timeline,
+    };
+
+    let config_path = format!("{}/simnet.json", test_case.test_path);
+    serde_json::to_writer_pretty(File::create(&config_path)?, &config)?;
+
+    println!("\nRunning test...");
+    let output = File::create(format!("{}/simnet_output.txt", test_case.test_path))?;
+    let status = Command::new(cli_path)
+        .args([
+            "--simnet-config",
+            &config_path,
+            "--log-file",
+            &format!("{}/simnet.log", test_case.test_path),
+        ])
+        .stdout(Stdio::from(output.try_clone()?))
+        .stderr(Stdio::from(output))
+        .spawn()
+        .with_context(|| format!("failed to run {}", cli_path))?
+        .wait()
+        .await?;
+    if !status.success() {
+        bail!("{} failed with {}", cli_path, status);
+    }
+    println!("  Test complete.");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_from_network_config() {
+        assert_eq!(
+            SimnetLink::default(),
+            SimnetLink::from_network_config(&NetworkConfig::default()).unwrap()
+        );
+        assert_eq!(
+            SimnetLink {
+                delay_min_ms: 80,
+                delay_max_ms: 120,
+                loss_probability: 0.05,
+                repeated_loss_probability: 0.05,
+                rate_kbps: 500,
+                queue_size_bytes: 1000 * 1500,
+            },
+            SimnetLink::from_network_config(&NetworkConfig {
+                delay: 100,
+                delay_variability: 20,
+                loss: Some(Loss::Percentage(5)),
+                rate: 500,
+                ..Default::default()
+            })
+            .unwrap()
+        );
+        assert_eq!(
+            SimnetLink {
+                delay_max_ms: 10,
+                loss_probability: 0.02,
+                repeated_loss_probability: 0.75,
+                queue_size_bytes: 20 * 1500,
+                ..Default::default()
+            },
+            SimnetLink::from_network_config(&NetworkConfig {
+                delay_variability: 10,
+                loss: Some(Loss::GeModel(GeLossModel::SimpleGilbert { p: 2, r: 25 })),
+                limit: 20,
+                ..Default::default()
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn link_from_unsupported_network_config() {
+        assert!(SimnetLink::from_network_config(&NetworkConfig {
+            reorder: 10,
+            ..Default::default()
+        })
+        .is_err());
+        assert!(SimnetLink::from_network_config(&NetworkConfig {
+            loss: Some(Loss::GeModel(GeLossModel::Gilbert {
+                p: 2,
+                r: 25,
+                one_minus_h: 50
+            })),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -10,7 +10,7 @@ pub mod calling {
 use std::{
     collections::HashMap,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     thread,
     time::{Duration, Instant},
 };
@@ -26,9 +26,10 @@ use tower::timeout::Timeout;
 
 use crate::{
     audio::{chop_audio_and_analyze, get_audio_and_analyze, AudioFiles},
+    audio_metrics::{analyze_raw_files, AudioMetrics},
     common::{
-        AudioAnalysisMode, ClientProfile, GroupConfig, NetworkConfigWithOffset, NetworkProfile,
-        TestCaseConfig,
+        AudioAnalysisMode, CallConfig, ClientProfile, GroupConfig, NetworkConfig,
+        NetworkConfigWithOffset, NetworkProfile, TestCaseConfig,
     },
     docker::{
         analyze_video, analyze_visqol_mos, clean_network, clean_up, convert_mp4_to_yuv,
@@ -37,9 +38,18 @@ use crate::{
         get_signaling_server_logs, get_turn_server_logs, start_cli, start_client,
         start_signaling_server, start_tcp_dump, start_turn_server, DockerStats,
     },
+    markers::{add_chirps, find_chirps_in_raw_files, ReceivedChirps, MARKED_SOUND_SUFFIX},
     report::{AnalysisReport, AnalysisReportMos, Report},
+    results::{SetResults, RESULTS_FILE_NAME},
+    simnet,
 };
 
+/// The names of the clients (and their containers) that can take part in a test case. Client A
+/// is always the caller, and client B the callee. The rest only join group calls.
+pub const CLIENT_NAMES: [&str; 8] = [
+    "client_a", "client_b", "client_c", "client_d", "client_e", "client_f", "client_g", "client_h",
+];
+
 pub struct Client<'a> {
     pub name: &'a str,
     pub sound: &'a Sound,
@@ -52,6 +62,25 @@ pub struct Client<'a> {
     pub output_mp4: Option<String>,
 }
 
+impl Client<'_> {
+    pub fn media_io(&self, save_media_files: bool) -> MediaFileIo {
+        MediaFileIo {
+            audio_input_file: self.sound.raw(),
+            audio_output_file: if save_media_files {
+                Some(self.output_raw.clone())
+            } else {
+                None
+            },
+            video_input_file: self.video.map(|v| v.raw()),
+            video_output_file: if save_media_files {
+                self.output_yuv.clone()
+            } else {
+                None
+            },
+        }
+    }
+}
+
 /// A property bag used to attach results and artifacts to tests. Normally, artifacts are
 /// saved to the file system and processed when reporting, but it is more efficient to
 /// record and pass some things along as we create them.
@@ -68,6 +97,10 @@ pub struct AudioTestResults {
     pub pesq_mos: AnalysisReportMos,
     /// MOS analysis using plc.
     pub plc_mos: AnalysisReportMos,
+    /// The built-in analysis, if it was enabled and there was anything to compare.
+    pub audio_metrics: Option<AudioMetrics>,
+    /// The chirps found in the received audio, if client A sent timing markers.
+    pub chirps: Option<ReceivedChirps>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -88,6 +121,17 @@ pub struct TestCase<'a> {
 
     pub client_a: &'a Client<'a>,
     pub client_b: &'a Client<'a>,
+    /// More clients in group calls, which receive client A's media like client B does.
+    pub extra_clients: &'a [Client<'a>],
+}
+
+impl<'a> TestCase<'a> {
+    /// All clients, starting with client A and client B.
+    pub fn clients(&self) -> impl Iterator<Item = &'a Client<'a>> {
+        [self.client_a, self.client_b]
+            .into_iter()
+            .chain(self.extra_clients)
+    }
 }
 
 pub struct Sound {
@@ -129,11 +173,51 @@ impl Video {
     }
 }
 
+/// Steps through one client's network emulation timeline during a test.
+struct NetworkEmulation {
+    client_name: String,
+    network_configs: Vec<NetworkConfigWithOffset>,
+    next: usize,
+    started: bool,
+}
+
+impl NetworkEmulation {
+    fn new(client_name: &str, network_configs: &[NetworkConfigWithOffset]) -> Self {
+        Self {
+            client_name: client_name.to_string(),
+            network_configs: network_configs.to_vec(),
+            next: 0,
+            started: false,
+        }
+    }
+
+    /// Returns the next network config once it is due, along with whether emulation had
+    /// already started for the client (in which case it should be changed rather than started).
+    fn next_due(&mut self, elapsed: Duration) -> Option<(NetworkConfig, bool)> {
+        let timed_network_config = self.network_configs.get(self.next)?;
+        if elapsed < timed_network_config.offset {
+            return None;
+        }
+        self.next += 1;
+        let started = std::mem::replace(&mut self.started, true);
+        Some((timed_network_config.network_config, started))
+    }
+}
+
 pub struct GroupRun {
     pub group_config: GroupConfig,
     pub reports: Vec<Result<Report>>,
 }
 
+/// How clients are run and connected.
+#[derive(Clone, Debug)]
+pub enum Backend {
+    /// Each client runs in its own container, with netem emulating the network.
+    Docker,
+    /// Both clients run in one `call_sim-cli` process, linked by a simulated network.
+    Simnet { cli_path: String },
+}
+
 #[allow(dead_code)]
 pub struct Test {
     time_started: DateTime<Local>,
@@ -158,6 +242,8 @@ pub struct Test {
 
     // Whether to run `perf record` (and report)
     profile: bool,
+
+    backend: Backend,
 }
 
 pub struct MediaFileIo {
@@ -176,6 +262,7 @@ impl Test {
         client_profiles: Vec<ClientProfile>,
         call_type: CallTypeConfig,
         profile: bool,
+        backend: Backend,
     ) -> Result<Self> {
         let time_started = chrono::Local::now();
 
@@ -211,6 +298,7 @@ impl Test {
             client_profiles,
             call_type,
             profile,
+            backend,
         })
     }
 
@@ -238,8 +326,9 @@ impl Test {
         create_network().await?;
         start_signaling_server().await?;
 
-        if !test_case_config.client_a_config.relay_servers.is_empty()
-            || !test_case_config.client_a_config.relay_servers.is_empty()
+        if test_case_config
+            .client_configs()
+            .any(|call_config| !call_config.relay_servers.is_empty())
         {
             // We'll assume any relay server configuration should start the test turn server.
             start_turn_server().await?;
@@ -263,114 +352,100 @@ impl Test {
         if let Ok(response) = response {
             let mut stream = response.into_inner();
 
-            start_client(
-                test_case.client_a.name,
-                &test_case.test_path,
-                &self.set_path,
-            )
-            .await?;
-            start_client(
-                test_case.client_b.name,
-                &test_case.test_path,
-                &self.set_path,
-            )
-            .await?;
+            for client in test_case.clients() {
+                start_client(client.name, &test_case.test_path, &self.set_path).await?;
+            }
 
             println!();
 
-            start_cli(
-                test_case.client_a.name,
-                MediaFileIo {
-                    audio_input_file: test_case.client_a.sound.raw(),
-                    audio_output_file: if test_case_config.save_media_files {
-                        Some(test_case.client_a.output_raw.clone())
-                    } else {
-                        None
-                    },
-                    video_input_file: test_case.client_a.video.map(|v| v.raw()),
-                    video_output_file: if test_case_config.save_media_files {
-                        test_case.client_a.output_yuv.clone()
-                    } else {
-                        None
-                    },
-                },
-                &test_case_config.client_a_config,
-                &test_case_config.client_b_config,
-                &self.client_profiles[0],
-                &self.call_type,
-                /*profile=*/ false, // Never profile client a
-            )
-            .await?;
-
-            start_cli(
-                test_case.client_b.name,
-                MediaFileIo {
-                    audio_input_file: test_case.client_b.sound.raw(),
-                    audio_output_file: if test_case_config.save_media_files {
-                        Some(test_case.client_b.output_raw.clone())
-                    } else {
-                        None
-                    },
-                    video_input_file: test_case.client_b.video.map(|v| v.raw()),
-                    video_output_file: if test_case_config.save_media_files {
-                        test_case.client_b.output_yuv.clone()
-                    } else {
-                        None
-                    },
-                },
-                &test_case_config.client_b_config,
-                &test_case_config.client_a_config,
-                &self.client_profiles[1],
-                &self.call_type,
-                self.profile,
-            )
-            .await?;
+            for (index, (client, call_config)) in test_case
+                .clients()
+                .zip(test_case_config.client_configs())
+                .enumerate()
+            {
+                // Client A sends to and receives from everyone else, so everyone else treats it
+                // as the remote client.
+                let remote_call_config = if index == 0 {
+                    &test_case_config.client_b_config
+                } else {
+                    &test_case_config.client_a_config
+                };
+
+                start_cli(
+                    client.name,
+                    client.media_io(test_case_config.save_media_files),
+                    call_config,
+                    remote_call_config,
+                    &self.client_profiles[index],
+                    &self.call_type,
+                    // Only profile client b.
+                    self.profile && index == 1,
+                )
+                .await?;
+            }
 
             println!("Waiting for clients...");
 
+            let client_count = test_case.clients().count();
+
             let mut done = false;
             loop {
                 match stream.message().await {
                     Ok(Some(event)) => {
-                        // We wait for both clients to indicate that they are ready and already
+                        // We wait for all clients to indicate that they are ready and already
                         // registered with the relay server.
-                        if !done && event.ready_count == 2 {
+                        if !done && event.ready_count as usize == client_count {
                             println!("\nRunning test...");
 
-                            let mut network_configs = network_configs.iter();
-                            let mut timed_config_next = network_configs.next();
-                            let mut emulation_started = false;
-
-                            if let Some(timed_network_config) = timed_config_next {
-                                if timed_network_config.offset == Duration::from_secs(0) {
-                                    println!("  Setting up network emulation.");
-                                    emulate_network_start(
-                                        test_case.client_a.name,
-                                        &timed_network_config.network_config,
-                                    )
-                                    .await?;
-                                    emulate_network_start(
-                                        test_case.client_b.name,
-                                        &timed_network_config.network_config,
-                                    )
-                                    .await?;
-                                    emulation_started = true;
-                                    timed_config_next = network_configs.next();
+                            // Client A and client B always use the test case's network profile,
+                            // but other clients can have their own.
+                            let mut emulations = vec![
+                                NetworkEmulation::new(test_case.client_a.name, network_configs),
+                                NetworkEmulation::new(test_case.client_b.name, network_configs),
+                            ];
+                            for (client, extra_client) in test_case
+                                .extra_clients
+                                .iter()
+                                .zip(&test_case_config.extra_clients)
+                            {
+                                emulations.push(match &extra_client.network_profile {
+                                    Some(network_profile) => NetworkEmulation::new(
+                                        client.name,
+                                        &network_profile.get_config(),
+                                    ),
+                                    None => NetworkEmulation::new(client.name, network_configs),
+                                });
+                            }
+
+                            let mut emulation_announced = false;
+                            for emulation in &mut emulations {
+                                if let Some((network_config, _)) =
+                                    emulation.next_due(Duration::from_secs(0))
+                                {
+                                    if !emulation_announced {
+                                        println!("  Setting up network emulation.");
+                                        emulation_announced = true;
+                                    }
+                                    emulate_network_start(&emulation.client_name, &network_config)
+                                        .await?;
                                 }
                             }
 
                             // Start monitoring docker stats. They will end when the associated container stops.
                             let docker_stats = DockerStats::new().await?;
-                            docker_stats.start(test_case.client_a.name, &test_case.test_path)?;
-                            docker_stats.start(test_case.client_b.name, &test_case.test_path)?;
+                            for client in test_case.clients() {
+                                docker_stats.start(client.name, &test_case.test_path)?;
+                            }
 
-                            // Tell client_b to start as a callee.
-                            let request = tonic::Request::new(CommandMessage {
-                                client: test_case.client_b.name.to_string(),
-                                command: Command::StartAsCallee.into(),
-                            });
+                            // Tell everyone but client_a to start as a callee.
+                            for client in test_case.clients().skip(1) {
+                                let request = tonic::Request::new(CommandMessage {
+                                    client: client.name.to_string(),
+                                    command: Command::StartAsCallee.into(),
+                                });
 
-                            test_manager.send_command(request).await?;
+                                test_manager.send_command(request).await?;
+                            }
 
                             // Tell client_a to start as a caller.
                             let request = tonic::Request::new(CommandMessage {
@@ -391,85 +466,71 @@ impl Test {
                                 eprint!("\r{} seconds remaining...", i);
                                 tokio::time::sleep(Duration::from_secs(1)).await;
 
-                                if let Some(timed_network_config) = timed_config_next {
-                                    if start_time.elapsed() >= timed_network_config.offset {
-                                        // Changing the network emulation takes time, so do it concurrently.
-                                        let network_config = timed_network_config.network_config;
-                                        let client_name_a = test_case.client_a.name.to_string();
-                                        let client_name_b = test_case.client_b.name.to_string();
-
-                                        // For now we will be ignoring errors when changing the emulation settings.
-                                        tokio::spawn(async move {
-                                            eprint!(
-                                                "\n  Applying new emulated network settings..."
-                                            );
-
-                                            let join_handle_a: tokio::task::JoinHandle<
-                                                Result<(), anyhow::Error>,
-                                            > = tokio::spawn(async move {
-                                                if emulation_started {
-                                                    emulate_network_change(
-                                                        &client_name_a,
-                                                        &network_config,
-                                                    )
-                                                    .await?;
-                                                } else {
-                                                    emulate_network_start(
-                                                        &client_name_a,
-                                                        &network_config,
-                                                    )
-                                                    .await?;
-                                                }
-                                                Ok(())
-                                            });
-                                            let join_handle_b: tokio::task::JoinHandle<
-                                                Result<(), anyhow::Error>,
-                                            > = tokio::spawn(async move {
-                                                if emulation_started {
-                                                    emulate_network_change(
-                                                        &client_name_b,
-                                                        &network_config,
-                                                    )
-                                                    .await?;
-                                                } else {
-                                                    emulate_network_start(
-                                                        &client_name_b,
-                                                        &network_config,
-                                                    )
-                                                    .await?;
-                                                }
-                                                Ok(())
-                                            });
-
-                                            // NOTE: We assume this block completes fairly quickly! To avoid issues,
-                                            // emulation shouldn't change more than once every 2 seconds!
-
-                                            let _ = tokio::join!(join_handle_a, join_handle_b);
-                                            eprintln!(" Done.");
-                                        });
-
-                                        emulation_started = true;
-
-                                        timed_config_next = network_configs.next();
-                                    }
+                                let elapsed = start_time.elapsed();
+                                let changes: Vec<(String, NetworkConfig, bool)> = emulations
+                                    .iter_mut()
+                                    .filter_map(|emulation| {
+                                        emulation.next_due(elapsed).map(
+                                            |(network_config, started)| {
+                                                (
+                                                    emulation.client_name.clone(),
+                                                    network_config,
+                                                    started,
+                                                )
+                                            },
+                                        )
+                                    })
+                                    .collect();
+
+                                if !changes.is_empty() {
+                                    // Changing the network emulation takes time, so do it concurrently.
+                                    // For now we will be ignoring errors when changing the emulation settings.
+                                    tokio::spawn(async move {
+                                        eprint!("\n  Applying new emulated network settings...");
+
+                                        let join_handles: Vec<
+                                            tokio::task::JoinHandle<Result<(), anyhow::Error>>,
+                                        > = changes
+                                            .into_iter()
+                                            .map(|(client_name, network_config, started)| {
+                                                tokio::spawn(async move {
+                                                    if started {
+                                                        emulate_network_change(
+                                                            &client_name,
+                                                            &network_config,
+                                                        )
+                                                        .await
+                                                    } else {
+                                                        emulate_network_start(
+                                                            &client_name,
+                                                            &network_config,
+                                                        )
+                                                        .await
+                                                    }
+                                                })
+                                            })
+                                            .collect();
+
+                                        // NOTE: We assume this block completes fairly quickly! To avoid issues,
+                                        // emulation shouldn't change more than once every 2 seconds!
+
+                                        for join_handle in join_handles {
+                                            let _ = join_handle.await;
+                                        }
+                                        eprintln!(" Done.");
+                                    });
                                 }
                             }
 
-                            // Tell client_a to stop.
-                            let request = tonic::Request::new(CommandMessage {
-                                client: test_case.client_a.name.to_string(),
-                                command: Command::Stop.into(),
-                            });
-
-                            test_manager.send_command(request).await?;
+                            // Tell everyone to stop.
+                            for client in test_case.clients() {
+                                let request = tonic::Request::new(CommandMessage {
+                                    client: client.name.to_string(),
+                                    command: Command::Stop.into(),
+                                });
 
-                            // Tell client_b to stop.
-                            let request = tonic::Request::new(CommandMessage {
-                                client: test_case.client_b.name.to_string(),
-                                command: Command::Stop.into(),
-                            });
-
-                            test_manager.send_command(request).await?;
+                                test_manager.send_command(request).await?;
+                            }
 
                             done = true;
 
@@ -497,69 +558,77 @@ impl Test {
         Ok(())
     }
 
-    /// Generates report artifacts by performing analysis on all media outputs. Performs
-    /// the necessary conversions to do so.
-    async fn generate_artifacts(
+    /// Converts the audio a client received from client A and compares it with what client A
+    /// sent, as configured by the receiving client's `call_config`.
+    async fn analyze_audio_from_a(
         &self,
         test_case: &TestCase<'_>,
         test_case_config: &TestCaseConfig,
+        client: &Client<'_>,
+        call_config: &CallConfig,
     ) -> Result<AudioTestResults> {
         let mut audio_test_results = AudioTestResults::default();
 
-        if !test_case_config.save_media_files {
-            anyhow::bail!("Skipping artifacts");
+        if call_config.audio.builtin_analysis
+            && call_config.audio.analysis_mode != AudioAnalysisMode::None
+        {
+            let reference_path = Path::new(&self.set_path).join(test_case.client_a.sound.raw());
+            let degraded_path = Path::new(&test_case.test_path).join(&client.output_raw);
+            let length_seconds = test_case_config.length_seconds;
+            audio_test_results.audio_metrics = tokio::task::spawn_blocking(move || {
+                analyze_raw_files(&reference_path, &degraded_path, Some(length_seconds))
+            })
+            .await??;
         }
 
-        // Perform conversions of audio data.
-        convert_raw_to_wav(
-            &test_case.test_path,
-            &test_case.client_a.output_raw,
-            &test_case.client_a.output_wav,
-            Some(test_case_config.length_seconds),
-        )
-        .await?;
+        if test_case_config.client_a_config.timing_markers {
+            let reference_path = Path::new(&self.set_path).join(test_case.client_a.sound.raw());
+            let degraded_path = Path::new(&test_case.test_path).join(&client.output_raw);
+            let length_seconds = test_case_config.length_seconds;
+            audio_test_results.chirps = Some(
+                tokio::task::spawn_blocking(move || {
+                    find_chirps_in_raw_files(&reference_path, &degraded_path, Some(length_seconds))
+                })
+                .await??,
+            );
+        }
 
-        if test_case_config.client_a_config.audio.requires_speech() {
-            convert_wav_to_16khz_mono(
-                &test_case.test_path,
-                &test_case.client_a.output_wav,
-                &test_case.client_a.output_wav_speech,
-            )
-            .await?;
+        if !call_config.audio.requires_wav() {
+            return Ok(audio_test_results);
         }
 
         convert_raw_to_wav(
             &test_case.test_path,
-            &test_case.client_b.output_raw,
-            &test_case.client_b.output_wav,
+            &client.output_raw,
+            &client.output_wav,
             Some(test_case_config.length_seconds),
         )
         .await?;
 
-        if test_case_config.client_b_config.audio.requires_speech() {
+        if call_config.audio.requires_speech() {
             convert_wav_to_16khz_mono(
                 &test_case.test_path,
-                &test_case.client_b.output_wav,
-                &test_case.client_b.output_wav_speech,
+                &client.output_wav,
+                &client.output_wav_speech,
             )
             .await?;
         }
 
         let audio_files = AudioFiles {
             degraded_path: &test_case.test_path,
-            degraded_file: &test_case.client_b.output_wav,
+            degraded_file: &client.output_wav,
             ref_path: &self.set_path,
             ref_file: &test_case.client_a.sound.wav(false),
         };
 
         let speech_files = AudioFiles {
             degraded_path: &test_case.test_path,
-            degraded_file: &test_case.client_b.output_wav_speech,
+            degraded_file: &client.output_wav_speech,
             ref_path: &self.set_path,
             ref_file: &test_case.client_a.sound.wav(true),
         };
 
-        match test_case_config.client_b_config.audio.analysis_mode {
+        match call_config.audio.analysis_mode {
             AudioAnalysisMode::None => {
                 // Do nothing, no analysis is requested.
             }
@@ -567,8 +636,8 @@ impl Test {
                 get_audio_and_analyze(
                     &audio_files,
                     &speech_files,
-                    test_case.client_b.name,
-                    &test_case_config.client_b_config.audio,
+                    client.name,
+                    &call_config.audio,
                     &mut audio_test_results,
                 )
                 .await?;
@@ -577,23 +646,67 @@ impl Test {
                 chop_audio_and_analyze(
                     &audio_files,
                     &speech_files,
-                    test_case.client_b.name,
-                    &test_case_config.client_b_config.audio,
+                    client.name,
+                    &call_config.audio,
                     &mut audio_test_results,
                 )
                 .await?;
             }
         }
 
-        if test_case_config.client_b_config.audio.generate_spectrogram {
+        if call_config.audio.generate_spectrogram {
             generate_spectrogram(
                 &test_case.test_path,
-                &test_case.client_b.output_wav,
-                test_case.client_b.sound.spectrogram_extension(),
+                &client.output_wav,
+                client.sound.spectrogram_extension(),
             )
             .await?;
         }
 
+        Ok(audio_test_results)
+    }
+
+    /// Generates report artifacts by performing analysis on all media outputs. Performs
+    /// the necessary conversions to do so. Returns the audio results for client B, then for
+    /// each extra client.
+    async fn generate_artifacts(
+        &self,
+        test_case: &TestCase<'_>,
+        test_case_config: &TestCaseConfig,
+    ) -> Result<(AudioTestResults, Vec<AudioTestResults>)> {
+        if !test_case_config.save_media_files {
+            anyhow::bail!("Skipping artifacts");
+        }
+
+        // Perform conversions of audio data.
+        if test_case_config.client_a_config.audio.requires_wav() {
+            convert_raw_to_wav(
+                &test_case.test_path,
+                &test_case.client_a.output_raw,
+                &test_case.client_a.output_wav,
+                Some(test_case_config.length_seconds),
+            )
+            .await?;
+        }
+
+        if test_case_config.client_a_config.audio.requires_speech() {
+            convert_wav_to_16khz_mono(
+                &test_case.test_path,
+                &test_case.client_a.output_wav,
+                &test_case.client_a.output_wav_speech,
+            )
+            .await?;
+        }
+
+        let audio_test_results = self
+            .analyze_audio_from_a(
+                test_case,
+                test_case_config,
+                test_case.client_b,
+                &test_case_config.client_b_config,
+            )
+            .await?;
+
         if let (Some(client_a_video), Some(dimensions)) = (
             test_case.client_a.video,
             test_case_config.client_a_config.video.dimensions(),
@@ -646,7 +759,24 @@ impl Test {
             .await?;
         }
 
-        Ok(audio_test_results)
+        let mut receiver_audio_test_results = vec![];
+        for (client, extra_client) in test_case
+            .extra_clients
+            .iter()
+            .zip(&test_case_config.extra_clients)
+        {
+            receiver_audio_test_results.push(
+                self.analyze_audio_from_a(
+                    test_case,
+                    test_case_config,
+                    client,
+                    &extra_client.call_config,
+                )
+                .await?,
+            );
+        }
+
+        Ok((audio_test_results, receiver_audio_test_results))
     }
 
     /// Generates reports by parsing/checking artifacts for the test, and returns summary
@@ -657,8 +787,15 @@ impl Test {
         test_case_config: &TestCaseConfig,
         network_configs: &Vec<NetworkConfigWithOffset>,
         test_results: AudioTestResults,
+        receiver_test_results: Vec<AudioTestResults>,
     ) -> Result<Report> {
-        let report = Report::build_b(test_case, test_case_config, test_results).await?;
+        let report = Report::build_b(
+            test_case,
+            test_case_config,
+            test_results,
+            receiver_test_results,
+        )
+        .await?;
 
         report
             .create_test_case_report(
@@ -679,6 +816,9 @@ impl Test {
     /// Process a reference sound by copying to the output directory and converting it to wav.
     /// Optionally, analyze it and store the reference mos value. This function will always
     /// process sounds in full-band (48kHz/two-channel) and wide-band (16kHz/mono).
+    ///
+    /// A sound named with [`MARKED_SOUND_SUFFIX`] is made from the sound without it, by adding
+    /// timing markers.
     async fn process_sound(&mut self, name: &str, analyze: bool) -> Result<()> {
         // Only process each sound once. So if we already have it, don't do anything.
         // Note: This means that mos analysis can only happen when sounds are pre-processed.
@@ -693,11 +833,16 @@ impl Test {
             let wav_name = sound.wav(false);
             let wav_name_speech = sound.wav(true);
 
-            // Copy the reference file to our test directory.
-            fs::copy(
-                format!("{}/{}", self.media_path, raw_name),
-                format!("{}/{}", self.set_path, raw_name),
-            )?;
+            // Copy the reference file to our test directory (or make it, if it is marked).
+            if let Some(unmarked_name) = name.strip_suffix(MARKED_SOUND_SUFFIX) {
+                let raw = fs::read(format!("{}/{}.raw", self.media_path, unmarked_name))?;
+                fs::write(format!("{}/{}", self.set_path, raw_name), add_chirps(&raw))?;
+            } else {
+                fs::copy(
+                    format!("{}/{}", self.media_path, raw_name),
+                    format!("{}/{}", self.set_path, raw_name),
+                )?;
+            }
 
             // Make sure there is a wav version of the file available.
             convert_raw_to_wav(&self.set_path, &raw_name, &wav_name, None).await?;
@@ -804,88 +949,114 @@ impl Test {
         Ok(())
     }
 
-    async fn run_test_case_and_get_report(
+    /// Runs a test case in containers, collecting logs from the servers and cleaning up
+    /// afterwards.
+    async fn run_docker_test(
         &self,
         test_case: &TestCase<'_>,
         test_case_config: &TestCaseConfig,
-        network_configs: &Vec<NetworkConfigWithOffset>,
-    ) -> Result<Report> {
-        match self
+        network_configs: &[NetworkConfigWithOffset],
+    ) -> Result<()> {
+        let mut containers: Vec<&str> = test_case.clients().map(|client| client.name).collect();
+        containers.extend(["signaling_server", "turn", "tcpdump"]);
+
+        if let Err(err) = self
             .run_test(test_case, test_case_config, network_configs)
             .await
         {
-            Ok(_) => {
-                if self.profile {
-                    // allow perf to finish and collect reports.
-                    println!("waiting for perf... ");
-                    if let Err(e) = finish_perf(test_case.client_b.name).await {
-                        println!("couldn't wait for perf {:?}", e);
-                    }
-                    println!("... done");
-                }
+            clean_up(containers).await?;
+            clean_network().await?;
+            return Err(err);
+        }
 
-                // For debugging, dump the signaling_server logs.
-                get_signaling_server_logs(&test_case.test_path).await?;
+        if self.profile {
+            // allow perf to finish and collect reports.
+            println!("waiting for perf... ");
+            if let Err(e) = finish_perf(test_case.client_b.name).await {
+                println!("couldn't wait for perf {:?}", e);
+            }
+            println!("... done");
+        }
 
-                if !test_case_config.client_a_config.relay_servers.is_empty()
-                    || !test_case_config.client_a_config.relay_servers.is_empty()
-                {
-                    // Also dump the turn server logs if it was used.
-                    get_turn_server_logs(&test_case.test_path).await?;
-                }
+        // For debugging, dump the signaling_server logs.
+        get_signaling_server_logs(&test_case.test_path).await?;
 
-                // We are done with the containers.
-                clean_up(vec![
-                    test_case.client_a.name,
-                    test_case.client_b.name,
-                    "signaling_server",
-                    "turn",
-                    "tcpdump",
-                ])
-                .await?;
-                clean_network().await?;
-
-                match self.generate_artifacts(test_case, test_case_config).await {
-                    Ok(test_results) => {
-                        match self
-                            .generate_test_report(
-                                test_case,
-                                test_case_config,
-                                network_configs,
-                                test_results,
-                            )
-                            .await
-                        {
-                            Ok(report) => Ok(report),
-                            Err(err) => {
-                                println!("Error generating test report: {}", err);
-                                Err(err)
-                            }
+        if test_case_config
+            .client_configs()
+            .any(|call_config| !call_config.relay_servers.is_empty())
+        {
+            // Also dump the turn server logs if it was used.
+            get_turn_server_logs(&test_case.test_path).await?;
+        }
+
+        // We are done with the containers.
+        clean_up(containers).await?;
+        clean_network().await?;
+
+        Ok(())
+    }
+
+    async fn run_test_case_and_get_report(
+        &self,
+        test_case: &TestCase<'_>,
+        test_case_config: &TestCaseConfig,
+        network_configs: &Vec<NetworkConfigWithOffset>,
+    ) -> Result<Report> {
+        let result = match &self.backend {
+            Backend::Docker => {
+                self.run_docker_test(test_case, test_case_config, network_configs)
+                    .await
+            }
+            Backend::Simnet { cli_path } => {
+                simnet::run_test(
+                    cli_path,
+                    &self.set_path,
+                    test_case,
+                    test_case_config,
+                    network_configs,
+                    &self.client_profiles,
+                    &self.call_type,
+                )
+                .await
+            }
+        };
+
+        match result {
+            Ok(_) => match self.generate_artifacts(test_case, test_case_config).await {
+                Ok((test_results, receiver_test_results)) => {
+                    match self
+                        .generate_test_report(
+                            test_case,
+                            test_case_config,
+                            network_configs,
+                            test_results,
+                            receiver_test_results,
+                        )
+                        .await
+                    {
+                        Ok(report) => Ok(report),
+                        Err(err) => {
+                            println!("Error generating test report: {}", err);
+                            Err(err)
                         }
                     }
-                    Err(err) => {
-                        println!("Error generating artifacts: {}", err);
-                        Err(err)
-                    }
                 }
-            }
+                Err(err) => {
+                    println!("Error generating artifacts: {}", err);
+                    Err(err)
+                }
+            },
             Err(err) => {
                 println!("Error running test: {}", err);
-                clean_up(vec![
-                    test_case.client_a.name,
-                    test_case.client_b.name,
-                    "signaling_server",
-                    "turn",
-                    "tcpdump",
-                ])
-                .await?;
-                clean_network().await?;
-
                 Err(err)
             }
         }
     }
 
+    pub fn is_group_call(&self) -> bool {
+        matches!(self.call_type, CallTypeConfig::Group { .. })
+    }
+
     /// Runs the provided test permutations as individual test cases.
     pub async fn run(
         &mut self,
@@ -896,12 +1067,59 @@ impl Test {
         let mut reports: Vec<Result<Report>> = vec![];
 
         for test in tests {
+            let extra_client_count = test.extra_clients.len();
+            if extra_client_count > 0 {
+                if self.call_type == CallTypeConfig::Direct {
+                    anyhow::bail!(
+                        "{}: extra clients can only join group calls",
+                        test.test_case_name
+                    );
+                }
+                if extra_client_count > CLIENT_NAMES.len() - 2 {
+                    anyhow::bail!(
+                        "{}: at most {} extra clients are supported",
+                        test.test_case_name,
+                        CLIENT_NAMES.len() - 2
+                    );
+                }
+            }
+            if self.client_profiles.len() < extra_client_count + 2 {
+                anyhow::bail!(
+                    "{}: {} clients need profiles, but only {} were provided",
+                    test.test_case_name,
+                    extra_client_count + 2,
+                    self.client_profiles.len()
+                );
+            }
+            if self.call_type == CallTypeConfig::Direct
+                && test.client_configs().any(|call_config| {
+                    call_config.join_offset_secs > 0
+                        || call_config.leave_offset_secs.is_some()
+                        || call_config.reconnect_interval_secs.is_some()
+                        || call_config
+                            .timeline
+                            .iter()
+                            .any(|timeline_action| timeline_action.action.is_group_call_only())
+                })
+            {
+                anyhow::bail!(
+                    "{}: joining, leaving, rejoining, reconnecting, hands and reactions are only supported for group calls",
+                    test.test_case_name
+                );
+            }
+
             let a_to_b_sound = test.client_a_config.audio.input_name.as_str();
             let b_to_a_sound = test.client_b_config.audio.input_name.as_str();
+            // Client A sends its sound with chirps added if it sends timing markers.
+            let a_to_b_marked_sound = if test.client_a_config.timing_markers {
+                format!("{}{}", a_to_b_sound, MARKED_SOUND_SUFFIX)
+            } else {
+                a_to_b_sound.to_string()
+            };
 
             // Make sure the sounds are copied and converted, but they don't need to be
             // analyzed if not already.
-            self.process_sound(a_to_b_sound, false).await?;
+            self.process_sound(&a_to_b_marked_sound, false).await?;
             self.process_sound(b_to_a_sound, false).await?;
 
             let a_to_b_video = test.client_a_config.video.input_name.as_deref();
@@ -914,6 +1132,35 @@ impl Test {
                 self.process_video(b_to_a_video).await?;
             }
 
+            for extra_client in &test.extra_clients {
+                self.process_sound(&extra_client.call_config.audio.input_name, false)
+                    .await?;
+                if let Some(video) = &extra_client.call_config.video.input_name {
+                    self.process_video(video).await?;
+                }
+            }
+
+            // Extra clients only save the audio they receive, which is analyzed like client B's.
+            let extra_clients: Vec<Client> = CLIENT_NAMES[2..]
+                .iter()
+                .zip(&test.extra_clients)
+                .map(|(&name, extra_client)| Client {
+                    name,
+                    sound: &self.sounds[extra_client.call_config.audio.input_name.as_str()],
+                    video: extra_client
+                        .call_config
+                        .video
+                        .input_name
+                        .as_deref()
+                        .map(|v| &self.videos[v]),
+                    output_raw: format!("{}_output.raw", name),
+                    output_wav: format!("{}_output.wav", name),
+                    output_wav_speech: format!("{}_output.16kHz.mono.wav", name),
+                    output_yuv: None,
+                    output_mp4: None,
+                })
+                .collect();
+
             for network_profile in &network_profiles {
                 for i in 1..=test.iterations {
                     let report_name = format!(
@@ -946,7 +1193,7 @@ impl Test {
                         client_a: &Client {
                             name: "client_a",
                             // The sound should have been processed.
-                            sound: &self.sounds[a_to_b_sound],
+                            sound: &self.sounds[a_to_b_marked_sound.as_str()],
                             video: a_to_b_video.map(|v| &self.videos[v]),
                             output_raw: "client_a_output.raw".to_string(),
                             output_wav: "client_a_output.wav".to_string(),
@@ -965,6 +1212,7 @@ impl Test {
                             output_yuv: a_to_b_video.map(|_| "client_b_output.yuv".to_string()),
                             output_mp4: a_to_b_video.map(|_| "client_b_output.mp4".to_string()),
                         },
+                        extra_clients: &extra_clients,
                     };
 
                     reports.push(
@@ -988,19 +1236,23 @@ impl Test {
         Ok(())
     }
 
-    // Publish a report and clear history.
-    pub async fn report(&mut self) -> Result<()> {
+    // Publish a report and clear history. The results are also saved as JSON and returned.
+    pub async fn report(&mut self) -> Result<SetResults> {
+        let time_started = self.time_started.format("%Y-%m-%d %H:%M:%S").to_string();
         Report::create_summary_report(
             &self.set_name,
             &self.set_path,
-            &self.time_started.format("%Y-%m-%d %H:%M:%S").to_string(),
+            &time_started,
             &self.group_runs,
             &self.sounds,
         )
         .await?;
 
+        let results = SetResults::new(&self.set_name, &time_started, &self.group_runs);
+        results.save(&Path::new(&self.set_path).join(RESULTS_FILE_NAME))?;
+
         self.group_runs.clear();
 
-        Ok(())
+        Ok(results)
     }
 }
@@ -0,0 +1,747 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Test sets that are loaded from TOML or YAML files rather than written in Rust.
+//!
+//! A test set file describes a single group of test cases, using the same fields as the
+//! corresponding Rust structs. Fields that aren't given take their default values. For example:
+//!
+//! ```toml
+//! preprocess_sounds = ["normal_phrasing"]
+//! network_profiles = ["none", "moderate", "simple_loss_10"]
+//!
+//! [group]
+//! chart_dimensions = ["mos_speech"]
+//!
+//! [[test_cases]]
+//! test_case_name = "ptime-60"
+//! client_a_config = { audio = { input_name = "normal_phrasing", initial_packet_size_ms = 60 } }
+//! client_b_config = { audio = { input_name = "normal_phrasing", initial_packet_size_ms = 60 } }
+//! ```
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    common::{
+        Action, AudioConfig, CallConfig, CallProfile, DataModeConfig, DelayVariationStrategy,
+        GeLossModel, GroupConfig, Loss, MarkovLossModel, NetworkConfig, NetworkProfile,
+        TestCaseConfig, TimelineAction,
+    },
+    test::{Test, CLIENT_NAMES},
+};
+
+/// The packet sizes that Opus supports.
+const AUDIO_PACKET_SIZES_MS: [i32; 7] = [10, 20, 40, 60, 80, 100, 120];
+/// The bitrates that Opus supports.
+const AUDIO_BITRATE_RANGE_BPS: std::ops::RangeInclusive<i32> = 500..=512000;
+
+/// The formats of files that QA engineers write by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Format::Toml),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(self, contents: &str) -> Result<T> {
+        Ok(match self {
+            Format::Toml => toml::from_str(contents)?,
+            Format::Yaml => serde_yaml::from_str(contents)?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestSet {
+    /// Taken from the file name.
+    #[serde(skip)]
+    pub name: String,
+    /// If the group name isn't given, the name of the test set is used.
+    #[serde(default)]
+    pub group: GroupConfig,
+    /// Sounds to analyze up front so that reports include their reference MOS.
+    #[serde(default)]
+    pub preprocess_sounds: Vec<String>,
+    pub test_cases: Vec<TestCaseConfig>,
+    /// Each test case is run with each of these profiles.
+    pub network_profiles: Vec<NetworkProfile>,
+}
+
+impl TestSet {
+    /// Finds the file for a test set given on the command line, which is either a path to a
+    /// TOML or YAML file, or the name of a file in `test_set_dir` (without its extension).
+    pub fn find(test_set_dir: &Path, name_or_path: &str) -> Option<PathBuf> {
+        let path = Path::new(name_or_path);
+        if Format::from_path(path).is_some() {
+            return Some(path.to_path_buf());
+        }
+        ["toml", "yaml", "yml"]
+            .iter()
+            .map(|extension| test_set_dir.join(format!("{name_or_path}.{extension}")))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let Some(format) = Format::from_path(path) else {
+            bail!(
+                "test set file {} must have a .toml, .yaml, or .yml extension",
+                path.display()
+            );
+        };
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .with_context(|| format!("invalid test set file name {}", path.display()))?;
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read test set file {}", path.display()))?;
+        Self::parse(name, &contents, format)
+            .with_context(|| format!("invalid test set file {}", path.display()))
+    }
+
+    fn parse(name: &str, contents: &str, format: Format) -> Result<Self> {
+        let mut test_set: Self = format.deserialize(contents)?;
+        test_set.name = name.to_string();
+        if test_set.group.group_name.is_empty() {
+            test_set.group.group_name = name.to_string();
+        }
+        for test_case in &mut test_set.test_cases {
+            for call_config in test_case.client_configs_mut() {
+                let mute_changes = std::mem::take(&mut call_config.mute_schedule);
+                call_config
+                    .timeline
+                    .extend(mute_changes.into_iter().map(TimelineAction::from));
+            }
+        }
+        test_set.validate()?;
+        Ok(test_set)
+    }
+
+    /// Checks the things that would otherwise only fail once the test is running, or that would
+    /// silently do something other than what was intended.
+    pub fn validate(&self) -> Result<()> {
+        validate_name("test set name", &self.name)?;
+        validate_name("group_name", &self.group.group_name)?;
+
+        for sound in &self.preprocess_sounds {
+            validate_name("preprocess_sounds entry", sound)?;
+        }
+
+        if self.test_cases.is_empty() {
+            bail!("at least one test case is required");
+        }
+        let mut test_case_names = HashSet::new();
+        for test_case in &self.test_cases {
+            validate_test_case(test_case)
+                .with_context(|| format!("test case \"{}\"", test_case.test_case_name))?;
+            if !test_case_names.insert(&test_case.test_case_name) {
+                bail!(
+                    "test case name \"{}\" is used more than once",
+                    test_case.test_case_name
+                );
+            }
+        }
+
+        if self.network_profiles.is_empty() {
+            bail!("at least one network profile is required");
+        }
+        let mut network_profile_names = HashSet::new();
+        for network_profile in &self.network_profiles {
+            let name = network_profile.get_name();
+            validate_network_profile(network_profile, &self.test_cases)
+                .with_context(|| format!("network profile \"{}\"", name))?;
+            if !network_profile_names.insert(name.clone()) {
+                bail!("network profile \"{}\" is used more than once", name);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn run(self, test: &mut Test) -> Result<()> {
+        test.preprocess_sounds(self.preprocess_sounds.iter().map(String::as_str).collect())
+            .await?;
+        test.run(self.group, self.test_cases, self.network_profiles)
+            .await
+    }
+}
+
+/// Names end up in the paths of test artifacts.
+fn validate_name(field: &str, name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("{} must not be empty", field);
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '@'))
+    {
+        bail!(
+            "{} \"{}\" may only contain ASCII letters, digits, '_', '-', '.', and '@'",
+            field,
+            name
+        );
+    }
+    Ok(())
+}
+
+fn validate_test_case(test_case: &TestCaseConfig) -> Result<()> {
+    validate_name("test_case_name", &test_case.test_case_name)?;
+    if test_case.length_seconds == 0 {
+        bail!("length_seconds must be greater than 0");
+    }
+    if test_case.iterations == 0 {
+        bail!("iterations must be greater than 0");
+    }
+    validate_call_config(&test_case.client_a_config, test_case.length_seconds)
+        .context("client_a_config")?;
+    validate_call_config(&test_case.client_b_config, test_case.length_seconds)
+        .context("client_b_config")?;
+    if test_case.extra_clients.len() > CLIENT_NAMES.len() - 2 {
+        bail!(
+            "at most {} extra_clients are supported",
+            CLIENT_NAMES.len() - 2
+        );
+    }
+    for (index, extra_client) in test_case.extra_clients.iter().enumerate() {
+        validate_call_config(&extra_client.call_config, test_case.length_seconds)
+            .with_context(|| format!("extra_clients[{}].call_config", index))?;
+        if let Some(network_profile) = &extra_client.network_profile {
+            validate_network_profile(network_profile, std::slice::from_ref(test_case))
+                .with_context(|| format!("extra_clients[{}].network_profile", index))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_call_config(config: &CallConfig, length_seconds: u16) -> Result<()> {
+    if config.join_offset_secs >= length_seconds {
+        bail!(
+            "join_offset_secs of {} is not within the {}s test case",
+            config.join_offset_secs,
+            length_seconds
+        );
+    }
+    if let Some(leave_offset_secs) = config.leave_offset_secs {
+        if leave_offset_secs <= config.join_offset_secs || leave_offset_secs >= length_seconds {
+            bail!(
+                "leave_offset_secs of {} must be after join_offset_secs and within the {}s test case",
+                leave_offset_secs,
+                length_seconds
+            );
+        }
+    }
+    for timeline_action in &config.timeline {
+        if timeline_action.offset_secs >= length_seconds {
+            bail!(
+                "timeline offset_secs of {} is not within the {}s test case",
+                timeline_action.offset_secs,
+                length_seconds
+            );
+        }
+        // Actions are only taken while the client is in the call.
+        if timeline_action.offset_secs < config.join_offset_secs
+            || config
+                .leave_offset_secs
+                .is_some_and(|leave_offset_secs| timeline_action.offset_secs >= leave_offset_secs)
+        {
+            bail!(
+                "timeline offset_secs of {} must be after join_offset_secs and before leave_offset_secs",
+                timeline_action.offset_secs
+            );
+        }
+        match &timeline_action.action {
+            Action::DataMode(DataModeConfig::MaxBitrateKbps(0)) => {
+                bail!("timeline data_mode max_bitrate_kbps must be greater than 0");
+            }
+            Action::React(value) if value.is_empty() => {
+                bail!("timeline react must not be empty");
+            }
+            _ => {}
+        }
+    }
+    if config.allowed_bitrate_kbps == 0 {
+        bail!("allowed_bitrate_kbps must be greater than 0");
+    }
+    if config.stats_interval_secs == 0 {
+        bail!("stats_interval_secs must be greater than 0");
+    }
+    if config.force_relay && config.relay_servers.is_empty() {
+        bail!("force_relay requires relay_servers");
+    }
+    if let CallProfile::DeterministicLoss(loss) = config.profile {
+        validate_percentage("profile.deterministic_loss", loss)?;
+    }
+    validate_audio_config(&config.audio).context("audio")?;
+    if let Some(input_name) = &config.video.input_name {
+        validate_name("video.input_name", input_name)?;
+        // The dimensions of the raw frames are taken from the file name.
+        let dimensions = input_name
+            .rsplit_once('@')
+            .and_then(|(_, dimensions)| dimensions.split_once('x'))
+            .and_then(|(width, height)| {
+                Some((width.parse::<u16>().ok()?, height.parse::<u16>().ok()?))
+            });
+        if dimensions.is_none() {
+            bail!(
+                "video.input_name \"{}\" must end with its dimensions, such as \"@1280x720\"",
+                input_name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_audio_config(config: &AudioConfig) -> Result<()> {
+    validate_name("input_name", &config.input_name)?;
+
+    for (field, packet_size_ms) in [
+        ("initial_packet_size_ms", config.initial_packet_size_ms),
+        ("min_packet_size_ms", config.min_packet_size_ms),
+        ("max_packet_size_ms", config.max_packet_size_ms),
+    ] {
+        if !AUDIO_PACKET_SIZES_MS.contains(&packet_size_ms) {
+            bail!(
+                "{} of {} is not one of {:?}",
+                field,
+                packet_size_ms,
+                AUDIO_PACKET_SIZES_MS
+            );
+        }
+    }
+    if config.min_packet_size_ms > config.max_packet_size_ms {
+        bail!("min_packet_size_ms must not be greater than max_packet_size_ms");
+    }
+
+    for (field, bitrate_bps) in [
+        ("initial_bitrate_bps", config.initial_bitrate_bps),
+        ("min_bitrate_bps", config.min_bitrate_bps),
+        ("max_bitrate_bps", config.max_bitrate_bps),
+    ] {
+        if !AUDIO_BITRATE_RANGE_BPS.contains(&bitrate_bps) {
+            bail!(
+                "{} of {} is not in the range {:?}",
+                field,
+                bitrate_bps,
+                AUDIO_BITRATE_RANGE_BPS
+            );
+        }
+    }
+    if config.min_bitrate_bps > config.max_bitrate_bps {
+        bail!("min_bitrate_bps must not be greater than max_bitrate_bps");
+    }
+
+    if !(0..=10).contains(&config.complexity) {
+        bail!(
+            "complexity of {} is not in the range 0..=10",
+            config.complexity
+        );
+    }
+    Ok(())
+}
+
+fn validate_network_profile(
+    network_profile: &NetworkProfile,
+    test_cases: &[TestCaseConfig],
+) -> Result<()> {
+    match network_profile {
+        NetworkProfile::Custom(name, timeline) => {
+            validate_name("name", name)?;
+            if NetworkProfile::from_name(name).is_some() {
+                bail!("the name of a custom profile must not be the name of a built-in profile");
+            }
+            if timeline.is_empty() {
+                bail!("timeline must not be empty");
+            }
+        }
+        NetworkProfile::SimpleLoss(loss) => validate_percentage("simple_loss", *loss)?,
+        _ => {}
+    }
+
+    let timeline = network_profile.get_config();
+    for window in timeline.windows(2) {
+        if window[1].offset < window[0].offset {
+            bail!(
+                "timeline offsets must not decrease ({}s comes after {}s)",
+                window[1].offset.as_secs(),
+                window[0].offset.as_secs()
+            );
+        }
+    }
+    for entry in &timeline {
+        let offset_secs = entry.offset.as_secs();
+        validate_network_config(&entry.network_config)
+            .with_context(|| format!("timeline entry at {}s", offset_secs))?;
+        // Changes are made while the test case counts down its length, so later ones never happen.
+        if let Some(test_case) = test_cases
+            .iter()
+            .find(|test_case| offset_secs >= test_case.length_seconds.into())
+        {
+            bail!(
+                "the change at {}s would not happen in test case \"{}\", which is {}s long",
+                offset_secs,
+                test_case.test_case_name,
+                test_case.length_seconds
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_network_config(config: &NetworkConfig) -> Result<()> {
+    validate_percentage("duplication", config.duplication)?;
+    validate_percentage("corruption", config.corruption)?;
+    validate_percentage("reorder", config.reorder)?;
+    validate_percentage("reorder_correlation", config.reorder_correlation)?;
+    if config.reorder > 0 && config.delay == 0 {
+        // netem only reorders packets by delaying the others.
+        bail!("reorder requires delay");
+    }
+    if let Some(DelayVariationStrategy::Correlation(correlation)) = config.delay_variation_strategy
+    {
+        validate_percentage("delay_variation_strategy.correlation", correlation)?;
+    }
+    match config.loss {
+        None => {}
+        Some(Loss::Percentage(loss)) => validate_percentage("loss.percentage", loss)?,
+        Some(Loss::GeModel(model)) => {
+            let probabilities = match model {
+                GeLossModel::Bernoulli { p } => vec![p],
+                GeLossModel::SimpleGilbert { p, r } => vec![p, r],
+                GeLossModel::Gilbert { p, r, one_minus_h } => vec![p, r, one_minus_h],
+                GeLossModel::GilbertElliot {
+                    p,
+                    r,
+                    one_minus_h,
+                    one_minus_k,
+                } => vec![p, r, one_minus_h, one_minus_k],
+            };
+            for probability in probabilities {
+                validate_percentage("loss.ge_model", probability)?;
+            }
+        }
+        Some(Loss::State(model)) => {
+            let probabilities = match model {
+                MarkovLossModel::Bernoulli { p13 } => vec![p13],
+                MarkovLossModel::TwoState { p13, p31 } => vec![p13, p31],
+                MarkovLossModel::ThreeState { p13, p31, p32, p23 } => vec![p13, p31, p32, p23],
+                MarkovLossModel::FourState {
+                    p13,
+                    p31,
+                    p32,
+                    p23,
+                    p14,
+                } => vec![p13, p31, p32, p23, p14],
+            };
+            for probability in probabilities {
+                validate_percentage("loss.state", probability)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_percentage(field: &str, value: u8) -> Result<()> {
+    if value > 100 {
+        bail!("{} of {}% is more than 100%", field, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_toml(contents: &str) -> Result<TestSet> {
+        TestSet::parse("test", contents, Format::Toml)
+    }
+
+    fn error_chain(result: Result<TestSet>) -> String {
+        format!("{:#}", result.err().expect("should fail"))
+    }
+
+    #[test]
+    fn bundled_test_sets() {
+        for (name, contents, format) in [
+            (
+                "baseline",
+                include_str!("../test_sets/baseline.toml"),
+                Format::Toml,
+            ),
+            (
+                "relay_tests",
+                include_str!("../test_sets/relay_tests.yaml"),
+                Format::Yaml,
+            ),
+            (
+                "video_compare_vp8_vs_vp9",
+                include_str!("../test_sets/video_compare_vp8_vs_vp9.toml"),
+                Format::Toml,
+            ),
+        ] {
+            let test_set = TestSet::parse(name, contents, format)
+                .unwrap_or_else(|err| panic!("{name}: {err:#}"));
+            assert_eq!(name, test_set.group.group_name);
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        let test_set = parse_toml(
+            r#"
+            network_profiles = ["none", "simple_loss_10", "limited_bandwidth_100"]
+
+            [[test_cases]]
+            test_case_name = "ptime-60"
+            client_a_config = { audio = { input_name = "normal_phrasing", initial_packet_size_ms = 60, max_packet_size_ms = 60 } }
+            "#,
+        )
+        .unwrap();
+        assert_eq!("test", test_set.group.group_name);
+        assert!(test_set.group.summary_report_columns.show_video);
+
+        let test_case = &test_set.test_cases[0];
+        assert_eq!(30, test_case.length_seconds);
+        assert_eq!(1, test_case.iterations);
+        assert_eq!(60, test_case.client_a_config.audio.initial_packet_size_ms);
+        assert_eq!(20, test_case.client_a_config.audio.min_packet_size_ms);
+        assert_eq!("silence", test_case.client_b_config.audio.input_name);
+
+        let names: Vec<String> = test_set
+            .network_profiles
+            .iter()
+            .map(NetworkProfile::get_name)
+            .collect();
+        assert_eq!(
+            vec!["none", "simple_loss_10", "limited_bandwidth_100"],
+            names
+        );
+    }
+
+    #[test]
+    fn custom_network_profile() {
+        let test_set = parse_toml(
+            r#"
+            [[test_cases]]
+            test_case_name = "default"
+            length_seconds = 60
+
+            [[network_profiles]]
+            name = "bursty"
+
+            [[network_profiles.timeline]]
+            offset_secs = 0
+
+            [[network_profiles.timeline]]
+            offset_secs = 30
+            network_config = { delay = 50, loss = { ge_model = { simple_gilbert = { p = 5, r = 50 } } } }
+            "#,
+        )
+        .unwrap();
+        let NetworkProfile::Custom(name, timeline) = &test_set.network_profiles[0] else {
+            panic!("expected a custom profile");
+        };
+        assert_eq!("bursty", name);
+        assert_eq!(30, timeline[1].offset.as_secs());
+        assert_eq!(50, timeline[1].network_config.delay);
+        assert!(matches!(
+            timeline[1].network_config.loss,
+            Some(Loss::GeModel(GeLossModel::SimpleGilbert { p: 5, r: 50 }))
+        ));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            test_case_nmae = "typo"
+            "#,
+        ));
+        assert!(error.contains("unknown field `test_case_nmae`"), "{error}");
+
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["simple_loss"]
+            [[test_cases]]
+            "#,
+        ));
+        assert!(
+            error.contains("invalid value: string \"simple_loss\""),
+            "{error}"
+        );
+
+        let error = error_chain(TestSet::parse(
+            "test",
+            "network_profiles: [none]\ntest_cases:\n  - client_a_config:\n      audio:\n        bandwidth: wideband\n",
+            Format::Yaml,
+        ));
+        assert!(error.contains("unknown variant `wideband`"), "{error}");
+    }
+
+    #[test]
+    fn validation_errors() {
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            test_case_name = "same"
+            [[test_cases]]
+            test_case_name = "same"
+            "#,
+        ));
+        assert!(error.contains("\"same\" is used more than once"), "{error}");
+
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            test_case_name = "ptime"
+            client_b_config = { audio = { initial_packet_size_ms = 30 } }
+            "#,
+        ));
+        assert_eq!(
+            "test case \"ptime\": client_b_config: audio: initial_packet_size_ms of 30 is not one of [10, 20, 40, 60, 80, 100, 120]",
+            error
+        );
+
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["simple_loss_150"]
+            [[test_cases]]
+            "#,
+        ));
+        assert!(
+            error.contains("simple_loss of 150% is more than 100%"),
+            "{error}"
+        );
+
+        let error = error_chain(parse_toml(
+            r#"
+            [[test_cases]]
+            test_case_name = "short"
+            length_seconds = 10
+            [[network_profiles]]
+            name = "late"
+            timeline = [{ offset_secs = 0 }, { offset_secs = 20, network_config = { rate = 50 } }]
+            "#,
+        ));
+        assert!(
+            error.contains("the change at 20s would not happen in test case \"short\""),
+            "{error}"
+        );
+
+        let error = error_chain(parse_toml(
+            r#"
+            [[test_cases]]
+            [[network_profiles]]
+            name = "moderate"
+            timeline = [{ offset_secs = 0 }]
+            "#,
+        ));
+        assert!(error.contains("name of a built-in profile"), "{error}");
+
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            test_case_name = "early_mute"
+            client_b_config = { join_offset_secs = 10, timeline = [{ offset_secs = 5, action = "mute" }] }
+            "#,
+        ));
+        assert_eq!(
+            "test case \"early_mute\": client_b_config: timeline offset_secs of 5 must be after join_offset_secs and before leave_offset_secs",
+            error
+        );
+    }
+
+    #[test]
+    fn mute_schedule() {
+        let test_set = parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            client_b_config = { mute_schedule = [{ offset_secs = 10, muted = true }, { offset_secs = 20, muted = false }] }
+            "#,
+        )
+        .unwrap();
+        let actions: Vec<String> = test_set.test_cases[0]
+            .client_b_config
+            .timeline
+            .iter()
+            .map(|timeline_action| {
+                format!("{}:{}", timeline_action.offset_secs, timeline_action.action)
+            })
+            .collect();
+        assert_eq!(vec!["10:mute", "20:unmute"], actions);
+    }
+
+    #[test]
+    fn extra_clients() {
+        let test_set = parse_toml(
+            r#"
+            network_profiles = ["none"]
+
+            [[test_cases]]
+            test_case_name = "three"
+            client_b_config = { timeline = [{ offset_secs = 10, action = "mute" }, { offset_secs = 12, action = { data_mode = { max_bitrate_kbps = 300 } } }, { offset_secs = 15, action = { react = "👍" } }] }
+
+            [[test_cases.extra_clients]]
+            call_config = { join_offset_secs = 5, leave_offset_secs = 20 }
+            network_profile = "simple_loss_10"
+            "#,
+        )
+        .unwrap();
+        let test_case = &test_set.test_cases[0];
+        assert_eq!(3, test_case.client_configs().count());
+        let actions: Vec<String> = test_case
+            .client_b_config
+            .timeline
+            .iter()
+            .map(|timeline_action| timeline_action.action.to_string())
+            .collect();
+        assert_eq!(vec!["mute", "data-mode=300", "react=👍"], actions);
+        let extra_client = &test_case.extra_clients[0];
+        assert_eq!(5, extra_client.call_config.join_offset_secs);
+        assert_eq!(Some(20), extra_client.call_config.leave_offset_secs);
+        assert_eq!(
+            Some("simple_loss_10".to_string()),
+            extra_client
+                .network_profile
+                .as_ref()
+                .map(NetworkProfile::get_name)
+        );
+
+        let error = error_chain(parse_toml(
+            r#"
+            network_profiles = ["none"]
+            [[test_cases]]
+            test_case_name = "late"
+            length_seconds = 10
+            [[test_cases.extra_clients]]
+            call_config = { join_offset_secs = 5, leave_offset_secs = 5 }
+            "#,
+        ));
+        assert_eq!(
+            "test case \"late\": extra_clients[0].call_config: leave_offset_secs of 5 must be after join_offset_secs and within the 10s test case",
+            error
+        );
+    }
+}
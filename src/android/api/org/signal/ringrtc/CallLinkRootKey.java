@@ -35,6 +35,16 @@ public class CallLinkRootKey {
     }
   }
 
+  /** The root key of breakout room {@code roomIndex} of this call link's call. */
+  @NonNull
+  public CallLinkRootKey deriveBreakoutRoomKey(int roomIndex) {
+    try {
+      return new CallLinkRootKey(nativeDeriveBreakoutRoomKey(rawKey, roomIndex));
+    } catch (CallException e) {
+      throw new AssertionError(e);
+    }
+  }
+
   /** Returns the internal storage, so don't modify it! */
   @NonNull
   public byte[] getKeyBytes() {
@@ -54,5 +64,6 @@ public class CallLinkRootKey {
   private static native byte[] nativeParseKeyString(String keyString) throws CallException;
   private static native void nativeValidateKeyBytes(byte[] keyBytes) throws CallException;
   private static native byte[] nativeDeriveRoomId(byte[] keyBytes) throws CallException;
+  private static native byte[] nativeDeriveBreakoutRoomKey(byte[] keyBytes, int roomIndex) throws CallException;
   private static native String nativeToFormattedString(byte[] keyBytes) throws CallException;
 }
\ No newline at end of file
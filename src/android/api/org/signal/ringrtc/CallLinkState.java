@@ -6,7 +6,10 @@
 package org.signal.ringrtc;
 
 import androidx.annotation.NonNull;
+import androidx.annotation.Nullable;
 import java.time.Instant;
+import java.util.List;
+import java.util.UUID;
 
 public class CallLinkState {
   public enum Restrictions {
@@ -15,6 +18,41 @@ public class CallLinkState {
     UNKNOWN,
   }
 
+  /** When the link's meeting takes place and who is invited to it. */
+  public static class Schedule {
+    @NonNull
+    private final Instant start;
+    @NonNull
+    private final Instant end;
+    @NonNull
+    private final List<UUID> invitees;
+
+    public Schedule(@NonNull Instant start, @NonNull Instant end, @NonNull List<UUID> invitees) {
+      this.start = start;
+      this.end = end;
+      this.invitees = invitees;
+    }
+
+    @NonNull
+    public Instant getStart() {
+      return start;
+    }
+
+    @NonNull
+    public Instant getEnd() {
+      return end;
+    }
+
+    @NonNull
+    public List<UUID> getInvitees() {
+      return invitees;
+    }
+
+    long getDurationSeconds() {
+      return end.getEpochSecond() - start.getEpochSecond();
+    }
+  }
+
   /** Is never null, but may be empty. */
   @NonNull
   private final String name;
@@ -23,17 +61,26 @@ public class CallLinkState {
   private final boolean revoked;
   @NonNull
   private final Instant expiration;
+  @Nullable
+  private final Schedule schedule;
 
   /** Should only be used for testing. */
   public CallLinkState(@NonNull String name, @NonNull Restrictions restrictions, boolean revoked, @NonNull Instant expiration) {
+    this(name, restrictions, revoked, expiration, null);
+  }
+
+  /** Should only be used for testing. */
+  public CallLinkState(@NonNull String name, @NonNull Restrictions restrictions, boolean revoked, @NonNull Instant expiration, @Nullable Schedule schedule) {
     this.name = name;
     this.restrictions = restrictions;
     this.revoked = revoked;
     this.expiration = expiration;
+    this.schedule = schedule;
   }
 
+  /** A zero scheduleDurationSeconds means there is no schedule. */
   @CalledByNative
-  private CallLinkState(@NonNull String name, int rawRestrictions, boolean revoked, long expirationEpochSecond) {
+  private CallLinkState(@NonNull String name, int rawRestrictions, boolean revoked, long expirationEpochSecond, long scheduleStartEpochSecond, long scheduleDurationSeconds, @NonNull byte[] flattenedInvitees) {
     this.name = name;
     switch (rawRestrictions) {
     case 0:
@@ -47,6 +94,12 @@ public class CallLinkState {
     }
     this.revoked = revoked;
     this.expiration = Instant.ofEpochSecond(expirationEpochSecond);
+    if (scheduleDurationSeconds == 0) {
+      this.schedule = null;
+    } else {
+      Instant start = Instant.ofEpochSecond(scheduleStartEpochSecond);
+      this.schedule = new Schedule(start, start.plusSeconds(scheduleDurationSeconds), Util.unflattenUuids(flattenedInvitees));
+    }
   }
 
   /** Is never null, but may be empty. */
@@ -68,4 +121,9 @@ public class CallLinkState {
   public Instant getExpiration() {
     return expiration;
   }
+
+  @Nullable
+  public Schedule getSchedule() {
+    return schedule;
+  }
 }
\ No newline at end of file
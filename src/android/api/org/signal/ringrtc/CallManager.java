@@ -358,6 +358,52 @@ public class CallManager {
     ringrtcSetSelfUuid(nativeCallManager, Util.getBytesFromUuid(uuid));
   }
 
+  /**
+   *
+   * Returns the outstanding group rings and call messages that arrived
+   * ahead of their offer, for the application to save and pass to
+   * {@link #restorePersistedState} if the process is restarted.
+   *
+   * @return the state to save, or null if there is nothing worth saving
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  @Nullable
+  public byte[] getPersistedState()
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "getPersistedState():");
+
+    return ringrtcGetPersistedState(nativeCallManager);
+  }
+
+  /**
+   *
+   * Restores state saved by an earlier process using
+   * {@link #getPersistedState}.
+   *
+   * Anything learned since this CallManager was created takes precedence
+   * over the saved state. Rings that expired while the application wasn't
+   * running are reported as expired.
+   *
+   * @param state  the saved state
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void restorePersistedState(@NonNull byte[] state)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "restorePersistedState():");
+
+    ringrtcRestorePersistedState(nativeCallManager, state);
+  }
+
   /**
    *
    * Indication from application to start a new outgoing call
@@ -381,6 +427,34 @@ public class CallManager {
     ringrtcCall(nativeCallManager, remote, callMediaType.ordinal(), localDeviceId);
   }
 
+  /**
+   *
+   * Indication from application to take over a call that was accepted
+   * on another device of the local user
+   *
+   * The other device will be notified via onAcceptedOnAnotherDevice().
+   *
+   * @param remote          remote side of the call
+   * @param replacedCallId  callId of the call being taken over
+   * @param callMediaType   used to specify origination as an audio or video call
+   * @param localDeviceId   the local deviceId of the client
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void pullCall(         Remote        remote,
+                       @NonNull CallId        replacedCallId,
+                       @NonNull CallMediaType callMediaType,
+                       @NonNull Integer       localDeviceId)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "pullCall(): replacing call: " + replacedCallId);
+
+    ringrtcPullCall(nativeCallManager, remote, replacedCallId.longValue(), callMediaType.ordinal(), localDeviceId);
+  }
+
   /**
    *
    * Indication from application to proceed with call
@@ -875,6 +949,70 @@ public class CallManager {
     ringrtcCancelGroupRing(nativeCallManager, groupId, ringId, rawReason);
   }
 
+  /**
+   *
+   * Tells the sender of a group ring that this device is ringing.
+   *
+   * Nothing is sent back to the ringer otherwise, since this reveals that
+   * the user has a device online. Only call this if the user has opted in
+   * to sharing that.
+   *
+   * @param groupId the unique identifier for the group
+   * @param ringId  identifies the ring being acknowledged
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void acknowledgeGroupRing(@NonNull byte[] groupId, long ringId)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "acknowledgeGroupRing():");
+    ringrtcAcknowledgeGroupRing(nativeCallManager, groupId, ringId);
+  }
+
+  /**
+   *
+   * Rings the invitees of a scheduled call link if its meeting is starting.
+   *
+   * Meant to be called from the application's timer at the scheduled start time. Calling it
+   * again (or from other devices) is harmless.
+   *
+   * @param linkRootKey the root key for the call link
+   * @param schedule    the link's schedule, as found in its CallLinkState
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void ringScheduledCallLink(@NonNull CallLinkRootKey linkRootKey, @NonNull CallLinkState.Schedule schedule)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "ringScheduledCallLink():");
+    ringrtcRingScheduledCallLink(nativeCallManager, linkRootKey.getKeyBytes(), schedule.getStart().getEpochSecond(), schedule.getDurationSeconds(), Util.flattenUuids(schedule.getInvitees()));
+  }
+
+  /**
+   *
+   * Stops the ring of a scheduled call link's meeting, such as when the meeting is cancelled.
+   *
+   * @param linkRootKey the root key for the call link
+   * @param schedule    the schedule the invitees were rung for
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void cancelCallLinkRing(@NonNull CallLinkRootKey linkRootKey, @NonNull CallLinkState.Schedule schedule)
+    throws CallException
+  {
+    checkCallManagerExists();
+
+    Log.i(TAG, "cancelCallLinkRing():");
+    ringrtcCancelCallLinkRing(nativeCallManager, linkRootKey.getKeyBytes(), schedule.getStart().getEpochSecond(), schedule.getDurationSeconds(), Util.flattenUuids(schedule.getInvitees()));
+  }
+
   // Group Calls and Call Links
 
   public interface ResponseHandler<T> {
@@ -1025,6 +1163,45 @@ public class CallManager {
     ringrtcCreateCallLink(nativeCallManager, sfuUrl, createCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, callLinkPublicParams, restrictions.ordinal(), requestId);
   }
 
+  /**
+   *
+   * Asynchronous request to create the call link for one of the breakout rooms of a call link's
+   * call, before starting the breakout.
+   *
+   * The room's root key is {@code linkRootKey.deriveBreakoutRoomKey(roomIndex)}, and its admin
+   * passkey is derived from {@code adminPasskey}, so the main room's admins can manage it. The
+   * credential must be requested for the breakout room's ID; otherwise this works like
+   * {@link #createCallLink}.
+   *
+   * @param sfuUrl                       the URL to use when accessing the SFU
+   * @param createCredentialPresentation a serialized CreateCallLinkCredentialPresentation for the breakout room
+   * @param linkRootKey                  the root key for the main room's call link
+   * @param roomIndex                    which breakout room to create
+   * @param adminPasskey                 the main room's admin passkey
+   * @param callLinkPublicParams         the serialized CallLinkPublicParams for the breakout room
+   * @param handler                      a handler function which is invoked with the newly-created room's initial state, or an error status code
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void createBreakoutRoomLink(
+    @NonNull String                                     sfuUrl,
+    @NonNull byte[]                                     createCredentialPresentation,
+    @NonNull CallLinkRootKey                            linkRootKey,
+             int                                        roomIndex,
+    @NonNull byte[]                                     adminPasskey,
+    @NonNull byte[]                                     callLinkPublicParams,
+    @NonNull CallLinkState.Restrictions                 restrictions,
+    @NonNull ResponseHandler<HttpResult<CallLinkState>> handler)
+    throws CallException
+  {
+    checkCallManagerExists();
+    Log.i(TAG, "createBreakoutRoomLink():");
+
+    long requestId = this.callLinkRequests.add(handler);
+    ringrtcCreateBreakoutRoomLink(nativeCallManager, sfuUrl, createCredentialPresentation, linkRootKey.getKeyBytes(), roomIndex, adminPasskey, callLinkPublicParams, restrictions.ordinal(), requestId);
+  }
+
   /**
    *
    * Asynchronous request to update a call link's name.
@@ -1060,7 +1237,7 @@ public class CallManager {
     Log.i(TAG, "updateCallLinkName():");
 
     long requestId = this.callLinkRequests.add(handler);
-    ringrtcUpdateCallLink(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, newName, -1, -1, requestId);
+    ringrtcUpdateCallLink(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, newName, -1, -1, 0, -1, new byte[0], requestId);
   }
 
   /**
@@ -1102,7 +1279,57 @@ public class CallManager {
     }
 
     long requestId = this.callLinkRequests.add(handler);
-    ringrtcUpdateCallLink(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, null, restrictions.ordinal(), -1, requestId);
+    ringrtcUpdateCallLink(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, null, restrictions.ordinal(), -1, 0, -1, new byte[0], requestId);
+  }
+
+  /**
+   *
+   * Asynchronous request to update a call link's schedule.
+   *
+   * Possible failure codes include:
+   * <ul>
+   *   <li>401: the room does not exist (and this is the wrong API to create a new room)
+   *   <li>403: the admin passkey is incorrect
+   * </ul>
+   *
+   * This request is idempotent; if it fails due to a network issue, it is safe to retry.
+   *
+   * @param sfuUrl                     the URL to use when accessing the SFU
+   * @param authCredentialPresentation a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey                the root key for the call link
+   * @param adminPasskey               the passkey specified when the link was created
+   * @param schedule                   the new schedule to use, or null to remove it
+   * @param handler                    a handler function which is invoked with the room's updated state, or an error status code
+   *
+   * @throws CallException for native code failures
+   *
+   */
+  public void updateCallLinkSchedule(
+    @NonNull String                                     sfuUrl,
+    @NonNull byte[]                                     authCredentialPresentation,
+    @NonNull CallLinkRootKey                            linkRootKey,
+    @NonNull byte[]                                     adminPasskey,
+    @Nullable CallLinkState.Schedule                    schedule,
+    @NonNull ResponseHandler<HttpResult<CallLinkState>> handler)
+    throws CallException
+  {
+    checkCallManagerExists();
+    Log.i(TAG, "updateCallLinkSchedule():");
+
+    long startEpochSecond = 0;
+    long durationSeconds = 0;
+    byte[] flattenedInvitees = new byte[0];
+    if (schedule != null) {
+      if (schedule.getDurationSeconds() <= 0) {
+        throw new IllegalArgumentException("a call link's schedule must end after it starts");
+      }
+      startEpochSecond = schedule.getStart().getEpochSecond();
+      durationSeconds = schedule.getDurationSeconds();
+      flattenedInvitees = Util.flattenUuids(schedule.getInvitees());
+    }
+
+    long requestId = this.callLinkRequests.add(handler);
+    ringrtcUpdateCallLink(nativeCallManager, sfuUrl, authCredentialPresentation, linkRootKey.getKeyBytes(), adminPasskey, null, -1, -1, startEpochSecond, durationSeconds, flattenedInvitees, requestId);
   }
 
   /**
@@ -1471,6 +1698,12 @@ public class CallManager {
     observer.onCallEvent(remote, event);
   }
 
+  @CalledByNative
+  private void onAcceptedOnAnotherDevice(Remote remote, int deviceId) {
+    Log.i(TAG, "onAcceptedOnAnotherDevice(): deviceId: " + deviceId);
+    observer.onCallAcceptedOnAnotherDevice(remote, deviceId);
+  }
+
   @CalledByNative
   private void onNetworkRouteChanged(Remote remote, int localNetworkAdapterType) {
     Log.i(TAG, "onNetworkRouteChange():");
@@ -1598,6 +1831,16 @@ public class CallManager {
     observer.onGroupCallRingUpdate(groupId, ringId, Util.getUuidFromBytes(sender), RingUpdate.values()[state]);
   }
 
+  @CalledByNative
+  private void callLinkRingUpdate(@NonNull byte[] rootKey, long ringId, @NonNull byte[] sender, int state) {
+    Log.i(TAG, "callLinkRingUpdate():");
+    try {
+      observer.onCallLinkRingUpdate(new CallLinkRootKey(rootKey), ringId, Util.getUuidFromBytes(sender), RingUpdate.values()[state]);
+    } catch (CallException e) {
+      Log.w(TAG, "callLinkRingUpdate(): invalid root key", e);
+    }
+  }
+
   @CalledByNative
   private void handlePeekResponse(long requestId, HttpResult<PeekInfo> info) {
     if (!this.peekRequests.resolve(requestId, info)) {
@@ -1717,6 +1960,144 @@ public class CallManager {
     groupCall.handleRaisedHands(raisedHands);
   }
 
+  @CalledByNative
+  private void handleSpeakerQueueChanged(long clientId, List<Long> raisedHands, long currentSpeaker) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    // A negative demux ID means no one has been called on.
+    Long currentSpeakerOrNull = currentSpeaker >= 0 ? currentSpeaker : null;
+    groupCall.handleSpeakerQueueChanged(new GroupCall.SpeakerQueue(raisedHands, currentSpeakerOrNull));
+  }
+
+  @CalledByNative
+  private void handleForwardedVideoLayersChanged(long clientId, int outgoingLayerCount, List<GroupCall.IncomingVideoHeight> incomingHeights) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleForwardedVideoLayersChanged(new GroupCall.ForwardedVideoLayers(outgoingLayerCount, incomingHeights));
+  }
+
+  @CalledByNative
+  private void handleBreakoutRoomsChanged(long clientId, int roomCount, List<GroupCall.BreakoutRoomAssignment> assignments, long returnAtEpochMillis) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    // Zero rooms means the breakout has ended.
+    GroupCall.BreakoutRooms breakoutRooms = null;
+    if (roomCount > 0) {
+      Long returnAt = returnAtEpochMillis >= 0 ? returnAtEpochMillis : null;
+      breakoutRooms = new GroupCall.BreakoutRooms(roomCount, assignments, returnAt);
+    }
+    groupCall.handleBreakoutRoomsChanged(breakoutRooms);
+  }
+
+  @CalledByNative
+  private void handlePollsChanged(long clientId, List<GroupCall.Poll> polls) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handlePollsChanged(polls);
+  }
+
+  @CalledByNative
+  private void handleChatMessage(long clientId, long demuxId, long seqnum, String text, long replyToDemuxId, long replyToSeqnum) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    // Seqnums start at 1, so zero means the message isn't a reply.
+    GroupCall.ChatMessageId replyTo = replyToSeqnum > 0 ? new GroupCall.ChatMessageId(replyToDemuxId, replyToSeqnum) : null;
+    groupCall.handleChatMessage(new GroupCall.ChatMessage(new GroupCall.ChatMessageId(demuxId, seqnum), text, replyTo));
+  }
+
+  @CalledByNative
+  private void handleLobbyChanged(long clientId, int participantCount, List<byte[]> admins, int position, @Nullable String message) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    List<UUID> adminUuids = new ArrayList<>(admins.size());
+    for (byte[] admin : admins) {
+      adminUuids.add(Util.getUuidFromBytes(admin));
+    }
+
+    // A negative position means it's unknown.
+    Integer positionOrNull = position >= 0 ? position : null;
+    groupCall.handleLobbyChanged(new GroupCall.LobbyInfo(participantCount, adminUuids, positionOrNull, message));
+  }
+
+  @CalledByNative
+  private void handleMutedByAdmin(long clientId, long adminDemuxId) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleMutedByAdmin(adminDemuxId);
+  }
+
+  @CalledByNative
+  private void handleUnmuteRequested(long clientId, long adminDemuxId) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleUnmuteRequested(adminDemuxId);
+  }
+
+  @CalledByNative
+  private void handleSpotlightChanged(long clientId, List<Long> spotlight) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleSpotlightChanged(spotlight);
+  }
+
+  @CalledByNative
+  private void handleVideoLayoutChanged(long clientId, List<Long> tiles) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleVideoLayoutChanged(tiles);
+  }
+
+  @CalledByNative
+  private void handleRingRecipientsChanged(long clientId, long ringId, List<GroupCall.RingRecipient> recipients) {
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleRingRecipientsChanged(ringId, recipients);
+  }
+
   @CalledByNative
   private void handleJoinStateChanged(long clientId, GroupCall.JoinState joinState, Long demuxId) {
     Log.i(TAG, "handleJoinStateChanged():");
@@ -1770,6 +2151,32 @@ public class CallManager {
     groupCall.handlePeekChanged(info);
   }
 
+  @CalledByNative
+  private void handleHandoffStateReceived(long clientId, @Nullable Boolean audioMuted, @Nullable Boolean videoMuted) {
+    Log.i(TAG, "handleHandoffStateReceived():");
+
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleHandoffStateReceived(audioMuted, videoMuted);
+  }
+
+  @CalledByNative
+  private void handleHandedOff(long clientId, long newDemuxId) {
+    Log.i(TAG, "handleHandedOff():");
+
+    GroupCall groupCall = this.groupCallByClientId.get(clientId);
+    if (groupCall == null) {
+      Log.w(TAG, "groupCall not found by clientId: " + clientId);
+      return;
+    }
+
+    groupCall.handleHandedOff(newDemuxId);
+  }
+
   @CalledByNative
   private void handleEnded(long clientId, GroupCall.GroupCallEndReason reason) {
     Log.i(TAG, "handleEnded():");
@@ -2135,6 +2542,20 @@ public class CallManager {
      */
     void onCallEvent(Remote remote, CallEvent event);
 
+    /**
+     *
+     * Notification that the active call ended because another device of
+     * the local user accepted it or took it over with pullCall()
+     *
+     * This is sent instead of ENDED_REMOTE_HANGUP_ACCEPTED when the
+     * accepting device is known.
+     *
+     * @param remote    remote peer of the call
+     * @param deviceId  the deviceId of the device that accepted the call
+     *
+     */
+    void onCallAcceptedOnAnotherDevice(Remote remote, int deviceId);
+
     /**
      *
      * Notification that the network route changed
@@ -2290,6 +2711,17 @@ public class CallManager {
      * @param update  the updated state to handle
      */
     void onGroupCallRingUpdate(@NonNull byte[] groupId, long ringId, @NonNull UUID sender, RingUpdate update);
+
+    /**
+     *
+     * An admin of a call link rang the local user because the link's scheduled meeting is starting.
+     *
+     * @param rootKey the root key of the call link
+     * @param ringId  identifies the scheduled meeting's ring
+     * @param sender  the admin who sent the ring
+     * @param update  one of REQUESTED, EXPIRED_REQUEST, or BUSY_LOCALLY
+     */
+    void onCallLinkRingUpdate(@NonNull CallLinkRootKey rootKey, long ringId, @NonNull UUID sender, RingUpdate update);
   }
 
   /**
@@ -2323,6 +2755,14 @@ public class CallManager {
     void ringrtcSetSelfUuid(long nativeCallManager, byte[] uuid)
     throws CallException;
 
+  private native
+    byte[] ringrtcGetPersistedState(long nativeCallManager)
+    throws CallException;
+
+  private native
+    void ringrtcRestorePersistedState(long nativeCallManager, byte[] state)
+    throws CallException;
+
   private native
     long ringrtcCreatePeerConnection(long                            nativePeerConnectionFactory,
                                      long                            nativeConnection,
@@ -2334,6 +2774,10 @@ public class CallManager {
     void ringrtcCall(long nativeCallManager, Remote remote, int callMediaType, int localDeviceId)
     throws CallException;
 
+  private native
+    void ringrtcPullCall(long nativeCallManager, Remote remote, long replacedCallId, int callMediaType, int localDeviceId)
+    throws CallException;
+
   private native
     void ringrtcProceed(long        nativeCallManager,
                         long        callId,
@@ -2358,6 +2802,18 @@ public class CallManager {
     void ringrtcCancelGroupRing(long nativeCallManager, byte[] groupId, long ringId, int reason)
     throws CallException;
 
+  private native
+    void ringrtcAcknowledgeGroupRing(long nativeCallManager, byte[] groupId, long ringId)
+    throws CallException;
+
+  private native
+    void ringrtcRingScheduledCallLink(long nativeCallManager, byte[] rootKeyBytes, long startEpochSecond, long durationSeconds, byte[] flattenedInvitees)
+    throws CallException;
+
+  private native
+    void ringrtcCancelCallLinkRing(long nativeCallManager, byte[] rootKeyBytes, long startEpochSecond, long durationSeconds, byte[] flattenedInvitees)
+    throws CallException;
+
   private native
     void ringrtcReceivedAnswer(long    nativeCallManager,
                                long    callId,
@@ -2485,6 +2941,18 @@ public class CallManager {
                                long   requestId)
     throws CallException;
 
+  private native
+    void ringrtcCreateBreakoutRoomLink(long   nativeCallManager,
+                                       String sfuUrl,
+                                       byte[] createCredentialPresentation,
+                                       byte[] rootKeyBytes,
+                                       int    roomIndex,
+                                       byte[] adminPasskey,
+                                       byte[] callLinkPublicParams,
+                                       int    restrictions,
+                                       long   requestId)
+    throws CallException;
+
   private native
     void ringrtcUpdateCallLink(long   nativeCallManager,
                                String sfuUrl,
@@ -2494,6 +2962,9 @@ public class CallManager {
                                String newName,
                                int    newRestrictions,
                                int    newRevoked,
+                               long   newScheduleStartEpochSecond,
+                               long   newScheduleDurationSeconds,
+                               byte[] newScheduleInvitees,
                                long   requestId)
     throws CallException;
   
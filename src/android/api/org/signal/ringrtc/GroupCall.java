@@ -273,6 +273,27 @@ public final class GroupCall {
         ringrtcJoin(nativeCallManager, this.clientId);
     }
 
+    /**
+     *
+     * Joins the group call by taking it over from another device of the
+     * same user that is currently in the call.
+     *
+     * Once this device is connected, the other device hands over its state
+     * (see Observer.onHandoffStateReceived) and leaves.
+     *
+     * @param fromDemuxId  the demuxId of the device being replaced
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void joinByHandoff(long fromDemuxId)
+        throws CallException
+    {
+        Log.i(TAG, "joinByHandoff(): fromDemuxId: " + fromDemuxId);
+
+        ringrtcJoinByHandoff(nativeCallManager, this.clientId, fromDemuxId);
+    }
+
     /**
      *
      * Leaves the group call terminating media flow.
@@ -436,6 +457,42 @@ public final class GroupCall {
         ringrtcRing(nativeCallManager, this.clientId, null);
     }
 
+    /**
+     *
+     * Rings the given recipients of the current ring again, such as those
+     * who haven't responded yet.
+     *
+     * @param recipients  the users to ring again
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void ringAgain(@NonNull Collection<UUID> recipients)
+        throws CallException
+    {
+        Log.i(TAG, "ringAgain():");
+
+        ringrtcRingAgain(nativeCallManager, this.clientId, Util.flattenUuids(recipients));
+    }
+
+    /**
+     *
+     * Reports that the message carrying the current ring was delivered
+     * to the given recipient.
+     *
+     * @param recipient  the user the ring was delivered to
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void ringDelivered(@NonNull UUID recipient)
+        throws CallException
+    {
+        Log.i(TAG, "ringDelivered():");
+
+        ringrtcRingDelivered(nativeCallManager, this.clientId, Util.getBytesFromUuid(recipient));
+    }
+
     /**
      *
      * Forces the group call object to send the latest media keys to
@@ -494,6 +551,55 @@ public final class GroupCall {
                             activeSpeakerHeight);
     }
 
+    /**
+     *
+     * Asks everyone in the call to feature the given devices, in order.
+     *
+     * Only admins may do this; the calling server ignores the request
+     * otherwise.
+     *
+     * @param demuxIds  the devices to feature; an empty list clears the spotlight
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void setSpotlight(@NonNull List<Long> demuxIds)
+        throws CallException
+    {
+        Log.i(TAG, "setSpotlight():");
+
+        ringrtcSetSpotlight(nativeCallManager, this.clientId, new ArrayList<>(demuxIds));
+    }
+
+    /**
+     *
+     * Lets RingRTC choose which remote device to show in each of the
+     * application's tiles and request their video, instead of the
+     * application calling requestVideo() itself. The chosen devices are
+     * reported with onVideoLayoutChanged().
+     *
+     * @param viewport  what the application can display, or null to go back
+     *                  to calling requestVideo() directly
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void setViewport(@Nullable Viewport viewport)
+        throws CallException
+    {
+        Log.i(TAG, "setViewport():");
+
+        if (viewport == null) {
+            ringrtcSetViewport(nativeCallManager, this.clientId, -1, new ArrayList<>(), new ArrayList<>());
+        } else {
+            ringrtcSetViewport(nativeCallManager,
+                               this.clientId,
+                               viewport.mode.ordinal(),
+                               new ArrayList<>(viewport.tiles),
+                               new ArrayList<>(viewport.pinned));
+        }
+    }
+
     /**
      *
      * Approves a user to join the call.
@@ -570,6 +676,83 @@ public final class GroupCall {
         ringrtcBlockClient(nativeCallManager, this.clientId, otherClientDemuxId);
     }
 
+    /**
+     *
+     * Mutes another client's audio. The other client is told who muted it.
+     *
+     * Should only be called if the current client is an admin for the call.
+     *
+     * @param otherClientDemuxId  the demux ID of the client to mute, retrieved from RemoteDeviceState
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void muteClient(long otherClientDemuxId)
+        throws CallException
+    {
+        Log.i(TAG, "muteClient():");
+
+        ringrtcMuteClient(nativeCallManager, this.clientId, otherClientDemuxId);
+    }
+
+    /**
+     *
+     * Asks another client to unmute. It's up to the other user whether to do so.
+     *
+     * Should only be called if the current client is an admin for the call.
+     *
+     * @param otherClientDemuxId  the demux ID of the client to ask, retrieved from RemoteDeviceState
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void askClientToUnmute(long otherClientDemuxId)
+        throws CallException
+    {
+        Log.i(TAG, "askClientToUnmute():");
+
+        ringrtcAskClientToUnmute(nativeCallManager, this.clientId, otherClientDemuxId);
+    }
+
+    /**
+     *
+     * Lowers another client's raised hand.
+     *
+     * Should only be called if the current client is an admin for the call.
+     *
+     * @param otherClientDemuxId  the demux ID of the client whose hand to lower, retrieved from RemoteDeviceState
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void lowerHand(long otherClientDemuxId)
+        throws CallException
+    {
+        Log.i(TAG, "lowerHand():");
+
+        ringrtcLowerHand(nativeCallManager, this.clientId, otherClientDemuxId);
+    }
+
+    /**
+     *
+     * Calls on the first client in the speaker queue, lowering its hand, or
+     * clears the current speaker if no hands are raised.
+     *
+     * Should only be called if the current client is an admin for the call.
+     *
+     * @param askToUnmute  whether to also ask the new speaker to unmute, as with askClientToUnmute
+     *
+     * @throws CallException for native code failures
+     *
+     */
+    public void callNextSpeaker(boolean askToUnmute)
+        throws CallException
+    {
+        Log.i(TAG, "callNextSpeaker(): askToUnmute: " + askToUnmute);
+
+        ringrtcCallNextSpeaker(nativeCallManager, this.clientId, askToUnmute);
+    }
+
     /**
      *
      * Provides a collection of GroupMemberInfo objects representing all
@@ -620,6 +803,91 @@ public final class GroupCall {
         ringrtcReact(nativeCallManager, this.clientId, value);
     }
 
+    /**
+     *
+     * Send a chat message to everyone in the call.
+     *
+     * @param text     The message, non-empty and at most 4096 bytes of UTF-8
+     * @param replyTo  The message being replied to, if any
+     *
+     * @throws CallException for native code failures
+     */
+    public void sendChatMessage(@NonNull String text, @Nullable ChatMessageId replyTo)
+        throws CallException {
+        Log.i(TAG, "sendChatMessage():");
+
+        // Seqnums start at 1, so zero means the message isn't a reply.
+        long replyToDemuxId = replyTo != null ? replyTo.demuxId : 0;
+        long replyToSeqnum = replyTo != null ? replyTo.seqnum : 0;
+        ringrtcSendChatMessage(nativeCallManager, this.clientId, text, replyToDemuxId, replyToSeqnum);
+    }
+
+    /**
+     *
+     * Create a poll for everyone in the call.
+     *
+     * @param question The question, at most 256 bytes of UTF-8
+     * @param options  Between 2 and 10 options, each at most 100 bytes of UTF-8
+     *
+     * @throws CallException for native code failures
+     */
+    public void createPoll(@NonNull String question, @NonNull List<String> options)
+        throws CallException {
+        Log.i(TAG, "createPoll():");
+
+        ringrtcCreatePoll(nativeCallManager, this.clientId, question, options);
+    }
+
+    /**
+     *
+     * Vote in a poll, replacing any earlier vote in the same poll.
+     *
+     * @param pollId       The poll to vote in
+     * @param optionIndex  The index of the option to vote for, or null to
+     *                     withdraw the vote
+     *
+     * @throws CallException for native code failures
+     */
+    public void voteInPoll(long pollId, @Nullable Integer optionIndex)
+        throws CallException {
+        Log.i(TAG, "voteInPoll(): pollId: " + pollId + ", optionIndex: " + optionIndex);
+
+        // A negative index withdraws the vote.
+        ringrtcVoteInPoll(nativeCallManager, this.clientId, pollId, optionIndex != null ? optionIndex : -1);
+    }
+
+    /**
+     *
+     * Stop a poll from accepting votes. Only the poll's creator or an admin
+     * can do this.
+     *
+     * @param pollId The poll to close
+     *
+     * @throws CallException for native code failures
+     */
+    public void closePoll(long pollId)
+        throws CallException {
+        Log.i(TAG, "closePoll(): pollId: " + pollId);
+
+        ringrtcClosePoll(nativeCallManager, this.clientId, pollId);
+    }
+
+    /**
+     *
+     * Leave a note for devices waiting for admin approval, replacing any
+     * previous one. Only call link admins can do this.
+     *
+     * @param message The note, or an empty string to clear it
+     *
+     * @throws CallException for native code failures
+     */
+    public void sendLobbyMessage(@NonNull String message)
+        throws CallException {
+        Log.i(TAG, "sendLobbyMessage():");
+
+        ringrtcSendLobbyMessage(nativeCallManager, this.clientId, message);
+    }
+
     /**
      *
      * Raise your hand.
@@ -757,6 +1025,50 @@ public final class GroupCall {
         this.observer.onRaisedHands(this, raisedHands);
     }
 
+    void handleRingRecipientsChanged(long ringId, List<RingRecipient> recipients) {
+        this.observer.onRingRecipientsChanged(this, ringId, recipients);
+    }
+
+    void handleSpotlightChanged(List<Long> spotlight) {
+        this.observer.onSpotlightChanged(this, spotlight);
+    }
+
+    void handleBreakoutRoomsChanged(@Nullable BreakoutRooms breakoutRooms) {
+        this.observer.onBreakoutRoomsChanged(this, breakoutRooms);
+    }
+
+    void handleChatMessage(ChatMessage message) {
+        this.observer.onChatMessage(this, message);
+    }
+
+    void handleSpeakerQueueChanged(SpeakerQueue queue) {
+        this.observer.onSpeakerQueueChanged(this, queue);
+    }
+
+    void handleForwardedVideoLayersChanged(ForwardedVideoLayers layers) {
+        this.observer.onForwardedVideoLayersChanged(this, layers);
+    }
+
+    void handlePollsChanged(List<Poll> polls) {
+        this.observer.onPollsChanged(this, polls);
+    }
+
+    void handleLobbyChanged(LobbyInfo lobby) {
+        this.observer.onLobbyChanged(this, lobby);
+    }
+
+    void handleMutedByAdmin(long adminDemuxId) {
+        this.observer.onMutedByAdmin(this, adminDemuxId);
+    }
+
+    void handleUnmuteRequested(long adminDemuxId) {
+        this.observer.onUnmuteRequested(this, adminDemuxId);
+    }
+
+    void handleVideoLayoutChanged(List<Long> tiles) {
+        this.observer.onVideoLayoutChanged(this, tiles);
+    }
+
     /**
      *
      * Callback from RingRTC when the remote device states have changed.
@@ -826,6 +1138,30 @@ public final class GroupCall {
         this.observer.onPeekChanged(this);
     }
 
+    /**
+     *
+     * Callback from RingRTC on the device taking over the call once the
+     * other device has sent its state. Called via the CallManager.
+     *
+     */
+    void handleHandoffStateReceived(@Nullable Boolean audioMuted, @Nullable Boolean videoMuted) {
+        Log.i(TAG, "handleHandoffStateReceived():");
+
+        this.observer.onHandoffStateReceived(this, audioMuted, videoMuted);
+    }
+
+    /**
+     *
+     * Callback from RingRTC on the device handing off the call, right
+     * before it leaves. Called via the CallManager.
+     *
+     */
+    void handleHandedOff(long newDemuxId) {
+        Log.i(TAG, "handleHandedOff():");
+
+        this.observer.onHandedOff(this, newDemuxId);
+    }
+
     /**
      *
      * Callback from RingRTC when the group call ends. Called via the
@@ -1195,6 +1531,180 @@ public final class GroupCall {
         }
     }
 
+    /**
+     * The height the SFU allocated to a remote device whose video it
+     * forwards to the local device.
+     */
+    public static class IncomingVideoHeight {
+        public long demuxId;
+        public int  height;
+
+        @CalledByNative
+        public IncomingVideoHeight(long demuxId, int height) {
+            this.demuxId = demuxId;
+            this.height = height;
+        }
+    }
+
+    /**
+     * Which layers of the simulcast ladder the SFU is using.
+     */
+    public static class ForwardedVideoLayers {
+        /** How many of the local device's layers the SFU asks for; 0 if no one is receiving the local video. */
+        public int                                outgoingLayerCount;
+        public @NonNull List<IncomingVideoHeight> incomingHeights;
+
+        public ForwardedVideoLayers(int outgoingLayerCount, @NonNull List<IncomingVideoHeight> incomingHeights) {
+            this.outgoingLayerCount = outgoingLayerCount;
+            this.incomingHeights = incomingHeights;
+        }
+    }
+
+    /**
+     * How the tiles of a Viewport are filled.
+     */
+    public enum ViewportMode {
+
+        /**
+         * The first tile shows the first pinned device or else the active
+         * speaker. The other tiles show the most recent speakers.
+         */
+        SPEAKER,
+
+        /**
+         * Tiles show devices in the order they joined, keeping the most
+         * recent speakers when not everyone fits.
+         */
+        GRID
+    }
+
+    /**
+     * The size of a tile, in physical pixels.
+     */
+    public static class TileSize {
+        public int width;  // UInt16
+        public int height; // UInt16
+
+        public TileSize(int width, int height) {
+            this.width = width;
+            this.height = height;
+        }
+    }
+
+    /**
+     * What the application is able to display.
+     */
+    public static class Viewport {
+        public @NonNull ViewportMode   mode;
+        /** The tiles in display order. In speaker mode, the first tile is the speaker's. */
+        public @NonNull List<TileSize> tiles;
+        /** Devices that always get a tile, ahead of everyone else. */
+        public @NonNull List<Long>     pinned;
+
+        public Viewport(@NonNull ViewportMode mode, @NonNull List<TileSize> tiles, @NonNull List<Long> pinned) {
+            this.mode = mode;
+            this.tiles = tiles;
+            this.pinned = pinned;
+        }
+    }
+
+    /**
+     * How a recipient has responded to a ring sent by this device.
+     */
+    public enum RingRecipientStatus {
+
+        /** The ring was sent, but nothing has been heard back yet. */
+        SENT,
+
+        /** The application reported that the ring was delivered to the recipient. */
+        DELIVERED,
+
+        /** At least one of the recipient's devices is ringing. */
+        RINGING,
+
+        /** The recipient declined the ring. */
+        DECLINED,
+
+        /** The recipient is in another call. */
+        BUSY,
+
+        /** The recipient joined the call from one of their devices. */
+        ACCEPTED;
+
+        static RingRecipientStatus fromNativeIndex(int nativeIndex) {
+            return values()[nativeIndex];
+        }
+    }
+
+    /**
+     * Which breakout room one device should move to.
+     */
+    public static class BreakoutRoomAssignment {
+        public long demuxId;
+        public int  roomIndex;
+
+        @CalledByNative
+        public BreakoutRoomAssignment(long demuxId, int roomIndex) {
+            this.demuxId = demuxId;
+            this.roomIndex = roomIndex;
+        }
+    }
+
+    /**
+     * How an admin has split a call link call into breakout rooms. Room
+     * {@code i} is the call link whose root key is
+     * {@code deriveBreakoutRoomKey(i)} of the main room's key.
+     */
+    public static class BreakoutRooms {
+        public int                                   roomCount;
+        /** Devices without an assignment stay in the main room. */
+        public @NonNull List<BreakoutRoomAssignment> assignments;
+        /** When everyone should return to the main room (epoch millis), if there's a countdown. */
+        public @Nullable Long                        returnAtEpochMillis;
+
+        public BreakoutRooms(int roomCount, @NonNull List<BreakoutRoomAssignment> assignments, @Nullable Long returnAtEpochMillis) {
+            this.roomCount = roomCount;
+            this.assignments = assignments;
+            this.returnAtEpochMillis = returnAtEpochMillis;
+        }
+    }
+
+    /**
+     * What a device waiting for admin approval ({@link JoinState#PENDING})
+     * can see of the call.
+     */
+    public static class LobbyInfo {
+        /** The number of devices in the call, not counting those still waiting. */
+        public int                 participantCount;
+        /** The call link admins currently in the call. */
+        public @NonNull List<UUID> admins;
+        /** How many devices have been waiting longer than the local device, if known. */
+        public @Nullable Integer   position;
+        /** The note an admin left for waiting devices, if any. */
+        public @Nullable String    message;
+
+        public LobbyInfo(int participantCount, @NonNull List<UUID> admins, @Nullable Integer position, @Nullable String message) {
+            this.participantCount = participantCount;
+            this.admins = admins;
+            this.position = position;
+            this.message = message;
+        }
+    }
+
+    /**
+     * A class used to store the status of one recipient of a ring.
+     */
+    public static class RingRecipient {
+        public @NonNull UUID                userId;
+        public @NonNull RingRecipientStatus status;
+
+        @CalledByNative
+        public RingRecipient(@NonNull byte[] userId, int status) {
+            this.userId = Util.getUuidFromBytes(userId);
+            this.status = RingRecipientStatus.fromNativeIndex(status);
+        }
+    }
+
     /**
      * A class used to store a reaction from a group member.
      */
@@ -1208,6 +1718,90 @@ public final class GroupCall {
         }
     }
 
+    /**
+     * Identifies a chat message within a call. Seqnums count up from 1 for
+     * each sending device.
+     */
+    public static class ChatMessageId {
+        public long demuxId;
+        public long seqnum;
+
+        public ChatMessageId(long demuxId, long seqnum) {
+            this.demuxId = demuxId;
+            this.seqnum = seqnum;
+        }
+    }
+
+    /**
+     * A class used to store a chat message from a group member.
+     */
+    public static class ChatMessage {
+        public @NonNull  ChatMessageId id;
+        public @NonNull  String        text;
+        public @Nullable ChatMessageId replyTo;
+
+        public ChatMessage(@NonNull ChatMessageId id, @NonNull String text, @Nullable ChatMessageId replyTo) {
+            this.id = id;
+            this.text = text;
+            this.replyTo = replyTo;
+        }
+    }
+
+    /**
+     * The raised hands in a call, in the order they were raised, and who an
+     * admin called on to speak (see {@link #callNextSpeaker}).
+     */
+    public static class SpeakerQueue {
+        public @NonNull List<Long> raisedHands;
+        public @Nullable Long      currentSpeaker;
+
+        public SpeakerQueue(@NonNull List<Long> raisedHands, @Nullable Long currentSpeaker) {
+            this.raisedHands = raisedHands;
+            this.currentSpeaker = currentSpeaker;
+        }
+    }
+
+    /**
+     * One of the answers to a poll.
+     */
+    public static class PollOption {
+        public @NonNull String text;
+        public int             votes;
+
+        @CalledByNative
+        public PollOption(@NonNull String text, int votes) {
+            this.text = text;
+            this.votes = votes;
+        }
+    }
+
+    /**
+     * A poll kept by the calling server, so that everyone sees the same
+     * tallies. Poll IDs count up from 1 within a call.
+     */
+    public static class Poll {
+        public long                      id;
+        /** The demux ID of the device that created the poll. */
+        public long                      creator;
+        public @NonNull String           question;
+        public @NonNull List<PollOption> options;
+        /** The index of the option the local device voted for, if any. */
+        public @Nullable Integer         ownVote;
+        /** Closed polls keep their results but no longer accept votes. */
+        public boolean                   closed;
+
+        /** A negative {@code ownVote} means the local device hasn't voted. */
+        @CalledByNative
+        public Poll(long id, long creator, @NonNull String question, @NonNull List<PollOption> options, int ownVote, boolean closed) {
+            this.id = id;
+            this.creator = creator;
+            this.question = question;
+            this.options = options;
+            this.ownVote = ownVote >= 0 ? ownVote : null;
+            this.closed = closed;
+        }
+    }
+
     /**
      * The client must provide an observer for each group call object
      * which is used to convey callbacks and notifications from
@@ -1261,11 +1855,99 @@ public final class GroupCall {
          */
         void onReactions(GroupCall groupCall, List<Reaction> reactions);
 
+        /**
+         * Notification that a chat message is ready to be shown. The local
+         * device's own messages are included, and each device's messages
+         * arrive in the order it sent them.
+         */
+        void onChatMessage(GroupCall groupCall, @NonNull ChatMessage message);
+
+        /**
+         * Notification that the call's polls changed, or that the local
+         * device's latest poll action took effect.
+         *
+         * @param polls  every poll, open or closed, in the order they were
+         *               created
+         */
+        void onPollsChanged(GroupCall groupCall, @NonNull List<Poll> polls);
+
         /**
          * Notification that the list of raised hands has changed.
          */
         void onRaisedHands(GroupCall groupCall, List<Long> raisedHands);
 
+        /**
+         * Notification that the raised hands or the current speaker
+         * changed. Sent after onRaisedHands when both apply.
+         */
+        void onSpeakerQueueChanged(GroupCall groupCall, @NonNull SpeakerQueue queue);
+
+        /**
+         * Notification that the layers the SFU uses from the local device,
+         * or the heights it forwards from remote devices, changed.
+         */
+        void onForwardedVideoLayersChanged(GroupCall groupCall, @NonNull ForwardedVideoLayers layers);
+
+        /**
+         * Notification that the recipients of a ring sent by this device
+         * have responded.
+         *
+         * @param ringId      the ring the recipients are responding to
+         * @param recipients  the status of every recipient of the ring
+         */
+        void onRingRecipientsChanged(GroupCall groupCall, long ringId, List<RingRecipient> recipients);
+
+        /**
+         * Notification that the devices shown in the Viewport's tiles have
+         * changed.
+         *
+         * @param tiles  the demux ID to show in each tile, or null for an
+         *               empty tile
+         */
+        void onVideoLayoutChanged(GroupCall groupCall, List<Long> tiles);
+
+        /**
+         * Notification that an admin changed the devices everyone should
+         * feature.
+         *
+         * @param spotlight  the demux IDs of the devices to feature, in order
+         */
+        void onSpotlightChanged(GroupCall groupCall, List<Long> spotlight);
+
+        /**
+         * Notification that an admin muted the local device. RingRTC has
+         * already stopped sending audio; the app should show itself as
+         * muted.
+         *
+         * @param adminDemuxId  the demux ID of the admin who muted us
+         */
+        void onMutedByAdmin(GroupCall groupCall, long adminDemuxId);
+
+        /**
+         * Notification that an admin started, changed, or ended breakout
+         * rooms. The app moves the local device by leaving this call and
+         * joining the assigned room's call link.
+         *
+         * @param breakoutRooms  the breakout, or null if it has ended and
+         *                       everyone should return to the main room
+         */
+        void onBreakoutRoomsChanged(GroupCall groupCall, @Nullable BreakoutRooms breakoutRooms);
+
+        /**
+         * Notification of what the local device can see of the call while
+         * it waits for admin approval. Only sent while the join state is
+         * {@link JoinState#PENDING}.
+         */
+        void onLobbyChanged(GroupCall groupCall, @NonNull LobbyInfo lobby);
+
+        /**
+         * Notification that an admin asked the local user to unmute. It's up
+         * to the user whether to do so.
+         *
+         * @param adminDemuxId  the demux ID of the admin who asked
+         */
+        void onUnmuteRequested(GroupCall groupCall, long adminDemuxId);
+
         /**
          * Notification that the remote device states have changed.
          */
@@ -1280,6 +1962,26 @@ public final class GroupCall {
          * Notification that the group call has ended.
          */
         void onEnded(GroupCall groupCall, GroupCallEndReason reason);
+
+        /**
+         * Notification that another device of the same user handed over its
+         * state after this device called joinByHandoff().
+         *
+         * Raised hands and video requests have already been applied; the
+         * application should apply the mute states to its local tracks.
+         *
+         * @param audioMuted  the audio mute state of the replaced device, if known
+         * @param videoMuted  the video mute state of the replaced device, if known
+         */
+        void onHandoffStateReceived(GroupCall groupCall, @Nullable Boolean audioMuted, @Nullable Boolean videoMuted);
+
+        /**
+         * Notification that another device of the same user took over the call.
+         * This device is about to leave the call.
+         *
+         * @param newDemuxId  the demuxId of the device that took over
+         */
+        void onHandedOff(GroupCall groupCall, long newDemuxId);
     }
 
     /* Native methods below here. */
@@ -1323,6 +2025,12 @@ public final class GroupCall {
                          long clientId)
         throws CallException;
 
+    private native
+        void ringrtcJoinByHandoff(long nativeCallManager,
+                                  long clientId,
+                                  long fromDemuxId)
+        throws CallException;
+
     private native
         void ringrtcLeave(long nativeCallManager,
                           long clientId)
@@ -1351,6 +2059,18 @@ public final class GroupCall {
                          @Nullable byte[] recipient)
         throws CallException;
 
+    private native
+        void ringrtcRingAgain(long   nativeCallManager,
+                              long   clientId,
+                              byte[] flattenedRecipients)
+        throws CallException;
+
+    private native
+        void ringrtcRingDelivered(long   nativeCallManager,
+                                  long   clientId,
+                                  byte[] recipient)
+        throws CallException;
+
     private native
         void ringrtcResendMediaKeys(long nativeCallManager,
                                     long clientId)
@@ -1369,6 +2089,20 @@ public final class GroupCall {
                                  int activeSpeakerHeight)
         throws CallException;
 
+    private native
+        void ringrtcSetSpotlight(long       nativeCallManager,
+                                 long       clientId,
+                                 List<Long> demuxIds)
+        throws CallException;
+
+    private native
+        void ringrtcSetViewport(long           nativeCallManager,
+                                long           clientId,
+                                int            mode,
+                                List<TileSize> tiles,
+                                List<Long>     pinned)
+        throws CallException;
+
     private native
         void ringrtcApproveUser(long nativeCallManager,
                                 long clientId,
@@ -1393,6 +2127,30 @@ public final class GroupCall {
                                 long otherClientDemuxId)
         throws CallException;
 
+    private native
+        void ringrtcMuteClient(long nativeCallManager,
+                               long clientId,
+                               long otherClientDemuxId)
+        throws CallException;
+
+    private native
+        void ringrtcAskClientToUnmute(long nativeCallManager,
+                                      long clientId,
+                                      long otherClientDemuxId)
+        throws CallException;
+
+    private native
+        void ringrtcLowerHand(long nativeCallManager,
+                              long clientId,
+                              long otherClientDemuxId)
+        throws CallException;
+
+    private native
+        void ringrtcCallNextSpeaker(long nativeCallManager,
+                                    long clientId,
+                                    boolean askToUnmute)
+        throws CallException;
+
     private native
         void ringrtcSetGroupMembers(long nativeCallManager,
                                     long clientId,
@@ -1411,6 +2169,40 @@ public final class GroupCall {
                           String value)
         throws CallException;
 
+    private native
+        void ringrtcSendChatMessage(long nativeCallManager,
+                                    long clientId,
+                                    String text,
+                                    long replyToDemuxId,
+                                    long replyToSeqnum)
+        throws CallException;
+
+    private native
+        void ringrtcCreatePoll(long nativeCallManager,
+                               long clientId,
+                               String question,
+                               List<String> options)
+        throws CallException;
+
+    private native
+        void ringrtcVoteInPoll(long nativeCallManager,
+                               long clientId,
+                               long pollId,
+                               int optionIndex)
+        throws CallException;
+
+    private native
+        void ringrtcClosePoll(long nativeCallManager,
+                              long clientId,
+                              long pollId)
+        throws CallException;
+
+    private native
+        void ringrtcSendLobbyMessage(long nativeCallManager,
+                                     long clientId,
+                                     String message)
+        throws CallException;
+
     private native
         void ringrtcRaiseHand(long nativeCallManager,
                               long clientId,
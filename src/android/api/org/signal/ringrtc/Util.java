@@ -7,7 +7,9 @@ package org.signal.ringrtc;
 import androidx.annotation.NonNull;
 
 import java.nio.ByteBuffer;
+import java.util.ArrayList;
 import java.util.Collection;
+import java.util.List;
 import java.util.UUID;
 
 public final class Util {
@@ -28,6 +30,28 @@ public final class Util {
         return new UUID(high, low);
     }
 
+    // Concatenate UUIDs as 16-byte chunks.
+    public static byte[] flattenUuids(@NonNull Collection<UUID> uuids) {
+        ByteBuffer bytes = ByteBuffer.wrap(new byte[uuids.size() * 16]);
+        for (UUID uuid : uuids) {
+            bytes.putLong(uuid.getMostSignificantBits());
+            bytes.putLong(uuid.getLeastSignificantBits());
+        }
+
+        return bytes.array();
+    }
+
+    // Split 16-byte chunks into UUIDs.
+    public static List<UUID> unflattenUuids(@NonNull byte[] flattened) {
+        ByteBuffer bytes = ByteBuffer.wrap(flattened);
+        List<UUID> uuids = new ArrayList<>(flattened.length / 16);
+        while (bytes.remaining() >= 16) {
+            uuids.add(new UUID(bytes.getLong(), bytes.getLong()));
+        }
+
+        return uuids;
+    }
+
     // Convert an array of GroupMemberInfo classes to a byte[] using 32-byte chunks.
     public static byte[] serializeFromGroupMemberInfo(@NonNull Collection<GroupCall.GroupMemberInfo> groupMembers) {
         if (groupMembers != null && groupMembers.size() > 0) {
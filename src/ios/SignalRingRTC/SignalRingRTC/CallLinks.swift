@@ -63,6 +63,21 @@ public struct CallLinkRootKey: CustomStringConvertible {
         return result!
     }
 
+    /// The root key of breakout room `roomIndex` of this call link's call. Breakout rooms are
+    /// created with `SFUClient.createBreakoutRoomLink`.
+    public func deriveBreakoutRoomKey(_ roomIndex: UInt32) -> Self {
+        var result: Self? = nil
+        let errorCStr = bytes.withRtcBytes { bytes in
+            rtc_calllinks_CallLinkRootKey_deriveBreakoutRoomKey(bytes, roomIndex, &result) { resultOpaquePtr, roomKeyBytes in
+                resultOpaquePtr!.assumingMemoryBound(to: Optional<Self>.self).pointee = Self(validatedBytes: roomKeyBytes)
+            }
+        }
+        if let errorCStr {
+            fail(String(cString: errorCStr))
+        }
+        return result!
+    }
+
     public var description: String {
         var result: String? = nil
         let errorCStr = bytes.withRtcBytes { bytes in
@@ -77,6 +92,39 @@ public struct CallLinkRootKey: CustomStringConvertible {
     }
 }
 
+/// When a call link's meeting takes place and who is invited to it.
+public struct CallLinkSchedule {
+    public var start: Date
+    public var end: Date
+    public var invitees: [UUID]
+
+    public init(start: Date, end: Date, invitees: [UUID]) {
+        self.start = start
+        self.end = end
+        self.invitees = invitees
+    }
+
+    var startEpochSeconds: UInt64 {
+        return UInt64(start.timeIntervalSince1970)
+    }
+
+    var durationSeconds: Int64 {
+        return Int64(end.timeIntervalSince(start))
+    }
+
+    var flattenedInvitees: Data {
+        return invitees.reduce(into: Data()) { $0.append($1.data) }
+    }
+
+    static func fromRtc(_ rtcSchedule: rtc_calllinks_CallLinkSchedule) -> Self? {
+        if rtcSchedule.duration_seconds == 0 {
+            return nil
+        }
+        let start = Date(timeIntervalSince1970: TimeInterval(rtcSchedule.start_epoch_seconds))
+        return Self(start: start, end: start.addingTimeInterval(TimeInterval(rtcSchedule.duration_seconds)), invitees: rtcSchedule.invitees.toUUIDs())
+    }
+}
+
 public struct CallLinkState {
     public enum Restrictions {
       case none, adminApproval, unknown
@@ -98,12 +146,14 @@ public struct CallLinkState {
     public var restrictions: Restrictions
     public var revoked: Bool
     public var expiration: Date
+    public var schedule: CallLinkSchedule?
 
-    public init(name: String, restrictions: Restrictions, revoked: Bool, expiration: Date) {
+    public init(name: String, restrictions: Restrictions, revoked: Bool, expiration: Date, schedule: CallLinkSchedule? = nil) {
         self.name = name
         self.restrictions = restrictions
         self.revoked = revoked
         self.expiration = expiration
+        self.schedule = schedule
     }
 
     static func fromRtc(_ rtcResponse: rtc_calllinks_CallLinkState) -> Self {
@@ -118,6 +168,6 @@ public struct CallLinkState {
             restrictions = .unknown
         }
         let expiration = Date(timeIntervalSince1970: TimeInterval(rtcResponse.expiration_epoch_seconds))
-        return Self(name: name, restrictions: restrictions, revoked: rtcResponse.revoked, expiration: expiration)
+        return Self(name: name, restrictions: restrictions, revoked: rtcResponse.revoked, expiration: expiration, schedule: CallLinkSchedule.fromRtc(rtcResponse.schedule))
     }
 }
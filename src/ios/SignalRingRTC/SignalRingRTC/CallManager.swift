@@ -227,6 +227,16 @@ public protocol CallManagerDelegate: AnyObject {
     @MainActor
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onLowBandwidthForVideoFor call: CallManagerDelegateCallType, recovered: Bool)
 
+    /**
+     * onAcceptedOnAnotherDeviceFor will be invoked when the call ended because
+     * another device of the local user accepted it or took it over with pullCall().
+     *
+     * This is sent instead of the .endedRemoteHangupAccepted event when the
+     * accepting device is known.
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, onAcceptedOnAnotherDeviceFor call: CallManagerDelegateCallType, deviceId: UInt32)
+
     /**
      * An Offer message should be sent to the given remote.
      * If there is any error, the UI can reset UI state and invoke the reset() API.
@@ -307,6 +317,15 @@ public protocol CallManagerDelegate: AnyObject {
      */
     @MainActor
     func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, didUpdateRingForGroup groupId: Data, ringId: Int64, sender: UUID, update: RingUpdate)
+
+    /**
+     * An admin of the call link identified by `rootKey` rang the current user because
+     * the link's scheduled meeting is starting.
+     *
+     * `update` is one of .requested, .expiredRequest, or .busyLocally.
+     */
+    @MainActor
+    func callManager(_ callManager: CallManager<CallManagerDelegateCallType, Self>, didUpdateRingForCallLink rootKey: CallLinkRootKey, ringId: Int64, sender: UUID, update: RingUpdate)
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -380,6 +399,42 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Returns the outstanding group rings and the call messages that arrived ahead of their
+    /// offer, for the application to save and pass to `restorePersistedState` if the process is
+    /// restarted. Returns nil if there is nothing worth saving.
+    @MainActor
+    public func persistedState() -> Data? {
+        Logger.debug("persistedState")
+
+        var state: Data?
+        let success = withUnsafeMutablePointer(to: &state) { statePtr in
+            ringrtcGetPersistedState(ringRtcCallManager, UnsafeMutableRawPointer(statePtr)) { bytes, context in
+                context!.assumingMemoryBound(to: Data?.self).pointee = bytes.asData()
+            }
+        }
+        if !success {
+            failDebug("persistedState had an error")
+        }
+        return state
+    }
+
+    /// Restores state saved by an earlier process using `persistedState`.
+    ///
+    /// Anything learned since this CallManager was created takes precedence over the saved
+    /// state. Rings that expired while the application wasn't running are reported as expired.
+    @MainActor
+    public func restorePersistedState(_ state: Data) {
+        Logger.debug("restorePersistedState")
+
+        let stateSlice = allocatedAppByteSliceFromData(maybe_data: state)
+        defer { stateSlice.bytes?.deallocate() }
+
+        let retPtr = ringrtcRestorePersistedState(ringRtcCallManager, stateSlice)
+        if retPtr == nil {
+            failDebug("restorePersistedState had an error")
+        }
+    }
+
     deinit {
         // Close the RingRTC Call Manager.
         let retPtr = ringrtcClose(self.ringRtcCallManager)
@@ -413,6 +468,30 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         _ = unmanagedCall.retain()
     }
 
+    /// Take over a call that was accepted on another device of the local user.
+    ///
+    /// The other device will be notified via onAcceptedOnAnotherDeviceFor.
+    ///
+    /// - Parameters:
+    ///   - call: The application call context
+    ///   - replacedCallId: The ID of the call being taken over
+    ///   - callMediaType: The type of call to place (audio or video)
+    ///   - localDevice: The local device ID of the client (must be valid for lifetime of the call)
+    @MainActor
+    public func pullCall(call: CallType, replacedCallId: UInt64, callMediaType: CallMediaType, localDevice: UInt32) throws {
+        Logger.debug("pullCall")
+
+        let unmanagedCall: Unmanaged<CallType> = Unmanaged.passUnretained(call)
+
+        let retPtr = ringrtcPullCall(ringRtcCallManager, unmanagedCall.toOpaque(), replacedCallId, callMediaType.rawValue, localDevice)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "pullCall() function failure")
+        }
+
+        // Keep the call reference around until rust says we're done with the call.
+        _ = unmanagedCall.retain()
+    }
+
     @MainActor
     public func accept(callId: UInt64) throws {
         Logger.debug("accept")
@@ -446,6 +525,60 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    /// Tells the sender of a group ring that this device is ringing.
+    ///
+    /// Nothing is sent back to the ringer otherwise, since this reveals that the user has a device
+    /// online. Only call this if the user has opted in to sharing that.
+    @MainActor
+    public func acknowledgeGroupRing(groupId: Data, ringId: Int64) throws {
+        Logger.debug("acknowledgeGroupRing")
+
+        let groupId = allocatedAppByteSliceFromData(maybe_data: groupId)
+        defer { groupId.bytes?.deallocate() }
+
+        let retPtr = ringrtcAcknowledgeGroupRing(ringRtcCallManager, groupId, ringId)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "acknowledgeGroupRing() function failure")
+        }
+    }
+
+    /// Rings the invitees of a scheduled call link if its meeting is starting.
+    ///
+    /// Meant to be called from the app's timer at the scheduled start time. Calling it again
+    /// (or from other devices) is harmless.
+    @MainActor
+    public func ringScheduledCallLink(rootKey: CallLinkRootKey, schedule: CallLinkSchedule) throws {
+        Logger.debug("ringScheduledCallLink")
+
+        let rootKeySlice = allocatedAppByteSliceFromData(maybe_data: rootKey.bytes)
+        defer { rootKeySlice.bytes?.deallocate() }
+        let invitees = allocatedAppByteSliceFromData(maybe_data: schedule.flattenedInvitees)
+        defer { invitees.bytes?.deallocate() }
+
+        let retPtr = ringrtcRingScheduledCallLink(ringRtcCallManager, rootKeySlice, schedule.startEpochSeconds, UInt64(max(schedule.durationSeconds, 0)), invitees)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "ringScheduledCallLink() function failure")
+        }
+    }
+
+    /// Stops the ring of a scheduled call link's meeting, such as when the meeting is cancelled.
+    ///
+    /// Takes the schedule the invitees were rung for.
+    @MainActor
+    public func cancelCallLinkRing(rootKey: CallLinkRootKey, schedule: CallLinkSchedule) throws {
+        Logger.debug("cancelCallLinkRing")
+
+        let rootKeySlice = allocatedAppByteSliceFromData(maybe_data: rootKey.bytes)
+        defer { rootKeySlice.bytes?.deallocate() }
+        let invitees = allocatedAppByteSliceFromData(maybe_data: schedule.flattenedInvitees)
+        defer { invitees.bytes?.deallocate() }
+
+        let retPtr = ringrtcCancelCallLinkRing(ringRtcCallManager, rootKeySlice, schedule.startEpochSeconds, UInt64(max(schedule.durationSeconds, 0)), invitees)
+        if retPtr == nil {
+            throw CallManagerError.apiFailed(description: "cancelCallLinkRing() function failure")
+        }
+    }
+
     // MARK: - Flow API
 
     /// Proceed with a call after the shouldStartCall delegate was invoked.
@@ -841,6 +974,19 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func onAcceptedOnAnotherDeviceFor(remote: UnsafeRawPointer, deviceId: UInt32) {
+        Logger.debug("onAcceptedOnAnotherDevice")
+
+        Task { @MainActor in
+            Logger.debug("onAcceptedOnAnotherDevice - main.async")
+
+            guard let delegate = self.delegate else { return }
+
+            let callReference: CallType = Unmanaged.fromOpaque(remote).takeUnretainedValue()
+            delegate.callManager(self, onAcceptedOnAnotherDeviceFor: callReference, deviceId: deviceId)
+        }
+    }
+
     // MARK: - Signaling Observers
 
     func onSendOffer(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType) {
@@ -942,6 +1088,16 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func callLinkRingUpdate(rootKey: CallLinkRootKey, ringId: Int64, sender: UUID, update: RingUpdate) {
+        Logger.debug("callLinkRingUpdate")
+
+        Task { @MainActor in
+            Logger.debug("callLinkRingUpdate - main.async")
+
+            self.delegate?.callManager(self, didUpdateRingForCallLink: rootKey, ringId: ringId, sender: sender, update: update)
+        }
+    }
+
     // MARK: - Utility Observers
 
     func onCreateConnection(pcObserverOwned: UnsafeMutableRawPointer?, deviceId: UInt32, appCallContext: CallContext, audioJitterBufferMaxPackets: Int32, audioJitterBufferMaxTargetDelayMs: Int32) -> (connection: Connection, pc: UnsafeMutableRawPointer?) {
@@ -1148,6 +1304,160 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
         }
     }
 
+    func handleVideoLayoutChanged(clientId: UInt32, tiles: [UInt32?]) {
+        Logger.debug("handleVideoLayoutChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleVideoLayoutChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleVideoLayoutChanged(tiles: tiles)
+        }
+    }
+
+    func handleBreakoutRoomsChanged(clientId: UInt32, breakoutRooms: BreakoutRooms?) {
+        Logger.debug("handleBreakoutRoomsChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleBreakoutRoomsChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleBreakoutRoomsChanged(breakoutRooms: breakoutRooms)
+        }
+    }
+
+    func handleSpeakerQueueChanged(clientId: UInt32, queue: SpeakerQueue) {
+        Logger.debug("handleSpeakerQueueChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleSpeakerQueueChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleSpeakerQueueChanged(queue: queue)
+        }
+    }
+
+    func handleForwardedVideoLayersChanged(clientId: UInt32, layers: ForwardedVideoLayers) {
+        Logger.debug("handleForwardedVideoLayersChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleForwardedVideoLayersChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleForwardedVideoLayersChanged(layers: layers)
+        }
+    }
+
+    func handlePollsChanged(clientId: UInt32, polls: [Poll]) {
+        Logger.debug("handlePollsChanged")
+
+        Task { @MainActor in
+            Logger.debug("handlePollsChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handlePollsChanged(polls: polls)
+        }
+    }
+
+    func handleChatMessage(clientId: UInt32, message: ChatMessage) {
+        Logger.debug("handleChatMessage")
+
+        Task { @MainActor in
+            Logger.debug("handleChatMessage - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleChatMessage(message: message)
+        }
+    }
+
+    func handleLobbyChanged(clientId: UInt32, lobby: LobbyInfo) {
+        Logger.debug("handleLobbyChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleLobbyChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleLobbyChanged(lobby: lobby)
+        }
+    }
+
+    func handleMutedByAdmin(clientId: UInt32, adminDemuxId: UInt32) {
+        Logger.debug("handleMutedByAdmin")
+
+        Task { @MainActor in
+            Logger.debug("handleMutedByAdmin - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleMutedByAdmin(adminDemuxId: adminDemuxId)
+        }
+    }
+
+    func handleUnmuteRequested(clientId: UInt32, adminDemuxId: UInt32) {
+        Logger.debug("handleUnmuteRequested")
+
+        Task { @MainActor in
+            Logger.debug("handleUnmuteRequested - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleUnmuteRequested(adminDemuxId: adminDemuxId)
+        }
+    }
+
+    func handleSpotlightChanged(clientId: UInt32, spotlight: [UInt32]) {
+        Logger.debug("handleSpotlightChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleSpotlightChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleSpotlightChanged(spotlight: spotlight)
+        }
+    }
+
+    func handleRingRecipientsChanged(clientId: UInt32, ringId: Int64, recipients: [RingRecipient]) {
+        Logger.debug("handleRingRecipientsChanged")
+
+        Task { @MainActor in
+            Logger.debug("handleRingRecipientsChanged - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleRingRecipientsChanged(ringId: ringId, recipients: recipients)
+        }
+    }
+
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?) {
         Logger.debug("handleJoinStateChanged")
 
@@ -1244,6 +1554,34 @@ public class CallManager<CallType, CallManagerDelegateType>: CallManagerInterfac
             groupCall.handleSpeakingNotification(event: event)
         }
     }
+
+    func handleHandoffStateReceived(clientId: UInt32, audioMuted: Bool?, videoMuted: Bool?) {
+        Logger.debug("handleHandoffStateReceived")
+
+        Task { @MainActor in
+            Logger.debug("handleHandoffStateReceived - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleHandoffStateReceived(audioMuted: audioMuted, videoMuted: videoMuted)
+        }
+    }
+
+    func handleHandedOff(clientId: UInt32, newDemuxId: UInt32) {
+        Logger.debug("handleHandedOff")
+
+        Task { @MainActor in
+            Logger.debug("handleHandedOff - main.async")
+
+            guard let groupCall = self.groupCallByClientId[clientId] else {
+                return
+            }
+
+            groupCall.handleHandedOff(newDemuxId: newDemuxId)
+        }
+    }
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -13,6 +13,7 @@ protocol CallManagerInterfaceDelegate: AnyObject {
     func onNetworkRouteChangedFor(remote: UnsafeRawPointer, networkRoute: NetworkRoute)
     func onAudioLevelsFor(remote: UnsafeRawPointer, capturedLevel: UInt16, receivedLevel: UInt16)
     func onLowBandwidthForVideoFor(remote: UnsafeRawPointer, recovered: Bool)
+    func onAcceptedOnAnotherDeviceFor(remote: UnsafeRawPointer, deviceId: UInt32)
     func onSendOffer(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType)
     func onSendAnswer(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, opaque: Data)
     func onSendIceCandidates(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, candidates: [Data])
@@ -37,12 +38,26 @@ protocol CallManagerInterfaceDelegate: AnyObject {
     func handleLowBandwidthForVideo(clientId: UInt32, recovered: Bool)
     func handleReactions(clientId: UInt32, reactions: [Reaction])
     func handleRaisedHands(clientId: UInt32, raisedHands: [UInt32])
+    func handleRingRecipientsChanged(clientId: UInt32, ringId: Int64, recipients: [RingRecipient])
+    func handleVideoLayoutChanged(clientId: UInt32, tiles: [UInt32?])
+    func handleSpotlightChanged(clientId: UInt32, spotlight: [UInt32])
+    func handleMutedByAdmin(clientId: UInt32, adminDemuxId: UInt32)
+    func handleUnmuteRequested(clientId: UInt32, adminDemuxId: UInt32)
+    func handleBreakoutRoomsChanged(clientId: UInt32, breakoutRooms: BreakoutRooms?)
+    func handleLobbyChanged(clientId: UInt32, lobby: LobbyInfo)
+    func handleChatMessage(clientId: UInt32, message: ChatMessage)
+    func handlePollsChanged(clientId: UInt32, polls: [Poll])
+    func handleSpeakerQueueChanged(clientId: UInt32, queue: SpeakerQueue)
+    func handleForwardedVideoLayersChanged(clientId: UInt32, layers: ForwardedVideoLayers)
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?)
     func handleRemoteDevicesChanged(clientId: UInt32, remoteDeviceStates: [RemoteDeviceState])
     func handleIncomingVideoTrack(clientId: UInt32, remoteDemuxId: UInt32, nativeVideoTrackBorrowedRc: UnsafeMutableRawPointer?)
     func handlePeekChanged(clientId: UInt32, peekInfo: PeekInfo)
     func handleEnded(clientId: UInt32, reason: GroupCallEndReason)
     func handleSpeakingNotification(clientId: UInt32, event: SpeechEvent)
+    func handleHandoffStateReceived(clientId: UInt32, audioMuted: Bool?, videoMuted: Bool?)
+    func handleHandedOff(clientId: UInt32, newDemuxId: UInt32)
+    func callLinkRingUpdate(rootKey: CallLinkRootKey, ringId: Int64, sender: UUID, update: RingUpdate)
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -71,6 +86,7 @@ class CallManagerInterface {
             onNetworkRouteChanged: callManagerInterfaceOnNetworkRouteChanged,
             onAudioLevels: callManagerInterfaceOnAudioLevels,
             onLowBandwidthForVideo: callManagerInterfaceOnLowBandwidthForVideo,
+            onAcceptedOnAnotherDevice: callManagerInterfaceOnAcceptedOnAnotherDevice,
             onSendOffer: callManagerInterfaceOnSendOffer,
             onSendAnswer: callManagerInterfaceOnSendAnswer,
             onSendIceCandidates: callManagerInterfaceOnSendIceCandidates,
@@ -101,7 +117,21 @@ class CallManagerInterface {
             handleIncomingVideoTrack: callManagerInterfaceHandleIncomingVideoTrack,
             handlePeekChanged: callManagerInterfaceHandlePeekChanged,
             handleEnded: callManagerInterfaceHandleEnded,
-            handleSpeakingNotification: callManagerInterfaceHandleSpeakingNotification
+            handleSpeakingNotification: callManagerInterfaceHandleSpeakingNotification,
+            handleHandoffStateReceived: callManagerInterfaceHandleHandoffStateReceived,
+            handleHandedOff: callManagerInterfaceHandleHandedOff,
+            callLinkRingUpdate: callManagerInterfaceCallLinkRingUpdate,
+            handleRingRecipientsChanged: callManagerInterfaceHandleRingRecipientsChanged,
+            handleVideoLayoutChanged: callManagerInterfaceHandleVideoLayoutChanged,
+            handleSpotlightChanged: callManagerInterfaceHandleSpotlightChanged,
+            handleMutedByAdmin: callManagerInterfaceHandleMutedByAdmin,
+            handleUnmuteRequested: callManagerInterfaceHandleUnmuteRequested,
+            handleBreakoutRoomsChanged: callManagerInterfaceHandleBreakoutRoomsChanged,
+            handleLobbyChanged: callManagerInterfaceHandleLobbyChanged,
+            handleChatMessage: callManagerInterfaceHandleChatMessage,
+            handlePollsChanged: callManagerInterfaceHandlePollsChanged,
+            handleSpeakerQueueChanged: callManagerInterfaceHandleSpeakerQueueChanged,
+            handleForwardedVideoLayersChanged: callManagerInterfaceHandleForwardedVideoLayersChanged
         )
     }
 
@@ -157,6 +187,14 @@ class CallManagerInterface {
         delegate.onLowBandwidthForVideoFor(remote: remote, recovered: recovered)
     }
 
+    func onAcceptedOnAnotherDeviceFor(remote: UnsafeRawPointer, deviceId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.onAcceptedOnAnotherDeviceFor(remote: remote, deviceId: deviceId)
+    }
+
     func onSendOffer(callId: UInt64, remote: UnsafeRawPointer, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -319,6 +357,94 @@ class CallManagerInterface {
         delegate.handleRaisedHands(clientId: clientId, raisedHands: raisedHands)
     }
 
+    func handleRingRecipientsChanged(clientId: UInt32, ringId: Int64, recipients: [RingRecipient]) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleRingRecipientsChanged(clientId: clientId, ringId: ringId, recipients: recipients)
+    }
+
+    func handleVideoLayoutChanged(clientId: UInt32, tiles: [UInt32?]) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleVideoLayoutChanged(clientId: clientId, tiles: tiles)
+    }
+
+    func handleSpotlightChanged(clientId: UInt32, spotlight: [UInt32]) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleSpotlightChanged(clientId: clientId, spotlight: spotlight)
+    }
+
+    func handleMutedByAdmin(clientId: UInt32, adminDemuxId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleMutedByAdmin(clientId: clientId, adminDemuxId: adminDemuxId)
+    }
+
+    func handleUnmuteRequested(clientId: UInt32, adminDemuxId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleUnmuteRequested(clientId: clientId, adminDemuxId: adminDemuxId)
+    }
+
+    func handleBreakoutRoomsChanged(clientId: UInt32, breakoutRooms: BreakoutRooms?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleBreakoutRoomsChanged(clientId: clientId, breakoutRooms: breakoutRooms)
+    }
+
+    func handleLobbyChanged(clientId: UInt32, lobby: LobbyInfo) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleLobbyChanged(clientId: clientId, lobby: lobby)
+    }
+
+    func handleChatMessage(clientId: UInt32, message: ChatMessage) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleChatMessage(clientId: clientId, message: message)
+    }
+
+    func handlePollsChanged(clientId: UInt32, polls: [Poll]) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handlePollsChanged(clientId: clientId, polls: polls)
+    }
+
+    func handleSpeakerQueueChanged(clientId: UInt32, queue: SpeakerQueue) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleSpeakerQueueChanged(clientId: clientId, queue: queue)
+    }
+
+    func handleForwardedVideoLayersChanged(clientId: UInt32, layers: ForwardedVideoLayers) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleForwardedVideoLayersChanged(clientId: clientId, layers: layers)
+    }
+
     func handleJoinStateChanged(clientId: UInt32, joinState: JoinState, demuxId: UInt32?) {
         guard let delegate = self.callManagerObserverDelegate else {
             return
@@ -366,6 +492,30 @@ class CallManagerInterface {
 
         delegate.handleSpeakingNotification(clientId: clientId, event: event)
     }
+
+    func handleHandoffStateReceived(clientId: UInt32, audioMuted: Bool?, videoMuted: Bool?) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleHandoffStateReceived(clientId: clientId, audioMuted: audioMuted, videoMuted: videoMuted)
+    }
+
+    func handleHandedOff(clientId: UInt32, newDemuxId: UInt32) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.handleHandedOff(clientId: clientId, newDemuxId: newDemuxId)
+    }
+
+    func callLinkRingUpdate(rootKey: CallLinkRootKey, ringId: Int64, sender: UUID, update: RingUpdate) {
+        guard let delegate = self.callManagerObserverDelegate else {
+            return
+        }
+
+        delegate.callLinkRingUpdate(rootKey: rootKey, ringId: ringId, sender: sender, update: update)
+    }
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -468,6 +618,22 @@ func callManagerInterfaceOnLowBandwidthForVideo(object: UnsafeMutableRawPointer?
     obj.onLowBandwidthForVideoFor(remote: remote, recovered: recovered)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceOnAcceptedOnAnotherDevice(object: UnsafeMutableRawPointer?, remote: UnsafeRawPointer?, deviceId: UInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let remote = remote else {
+        failDebug("remote was unexpectedly nil")
+        return
+    }
+
+    obj.onAcceptedOnAnotherDeviceFor(remote: remote, deviceId: deviceId)
+}
+
 @available(iOSApplicationExtension, unavailable)
 func callManagerInterfaceOnSendOffer(object: UnsafeMutableRawPointer?, callId: UInt64, remote: UnsafeRawPointer?, destinationDeviceId: UInt32, broadcast: Bool, opaque: AppByteSlice, mediaType: Int32) {
     guard let object = object else {
@@ -950,6 +1116,237 @@ func callManagerInterfaceHandleRaisedHands(object: UnsafeMutableRawPointer?, cli
     obj.handleRaisedHands(clientId: clientId, raisedHands: finalRaisedHands)
 }
 
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleSpeakerQueueChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, queue: AppSpeakerQueue) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalRaisedHands: [UInt32] = []
+    for index in 0..<queue.raisedHands.count {
+        finalRaisedHands.append(queue.raisedHands.raised_hands[index])
+    }
+
+    var currentSpeaker: UInt32?
+    if queue.currentSpeaker.valid {
+        currentSpeaker = queue.currentSpeaker.value
+    }
+
+    obj.handleSpeakerQueueChanged(clientId: clientId, queue: SpeakerQueue(raisedHands: finalRaisedHands, currentSpeaker: currentSpeaker))
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleForwardedVideoLayersChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, layers: AppForwardedVideoLayers) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var incomingHeights: [UInt32: UInt16] = [:]
+    for index in 0..<layers.incomingHeightsCount {
+        let incomingHeight = layers.incomingHeights[index]
+        incomingHeights[incomingHeight.demuxId] = incomingHeight.height
+    }
+
+    obj.handleForwardedVideoLayersChanged(clientId: clientId, layers: ForwardedVideoLayers(outgoingLayerCount: layers.outgoingLayerCount, incomingHeights: incomingHeights))
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleRingRecipientsChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, ringId: Int64, recipients: AppRingRecipientArray) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalRecipients: [RingRecipient] = []
+    for index in 0..<recipients.count {
+        let recipient = recipients.recipients[index]
+
+        guard let userId = recipient.userId.toUUID() else {
+            Logger.debug("missing userId for ring recipient")
+            continue
+        }
+
+        guard let status = RingRecipientStatus(rawValue: recipient.status) else {
+            Logger.debug("unrecognized ring recipient status: \(recipient.status)")
+            continue
+        }
+
+        finalRecipients.append(RingRecipient(userId: userId, status: status))
+    }
+
+    obj.handleRingRecipientsChanged(clientId: clientId, ringId: ringId, recipients: finalRecipients)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleVideoLayoutChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, tiles: AppVideoLayoutTileArray) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalTiles: [UInt32?] = []
+    for index in 0..<tiles.count {
+        let tile = tiles.tiles[index]
+        finalTiles.append(tile.valid ? tile.value : nil)
+    }
+
+    obj.handleVideoLayoutChanged(clientId: clientId, tiles: finalTiles)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleSpotlightChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, spotlight: AppDemuxIdArray) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalSpotlight: [UInt32] = []
+    for index in 0..<spotlight.count {
+        finalSpotlight.append(spotlight.demuxIds[index])
+    }
+
+    obj.handleSpotlightChanged(clientId: clientId, spotlight: finalSpotlight)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleMutedByAdmin(object: UnsafeMutableRawPointer?, clientId: UInt32, adminDemuxId: UInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleMutedByAdmin(clientId: clientId, adminDemuxId: adminDemuxId)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleUnmuteRequested(object: UnsafeMutableRawPointer?, clientId: UInt32, adminDemuxId: UInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleUnmuteRequested(clientId: clientId, adminDemuxId: adminDemuxId)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleBreakoutRoomsChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, breakoutRooms: AppBreakoutRooms) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    // Zero rooms means the breakout has ended.
+    guard breakoutRooms.roomCount > 0 else {
+        obj.handleBreakoutRoomsChanged(clientId: clientId, breakoutRooms: nil)
+        return
+    }
+
+    var assignments: [UInt32: UInt32] = [:]
+    for index in 0..<breakoutRooms.assignmentsCount {
+        let assignment = breakoutRooms.assignments[index]
+        assignments[assignment.demuxId] = assignment.roomIndex
+    }
+
+    var returnAt: Date? = nil
+    if breakoutRooms.returnAtEpochMillis >= 0 {
+        returnAt = Date(timeIntervalSince1970: TimeInterval(breakoutRooms.returnAtEpochMillis) / 1000)
+    }
+
+    obj.handleBreakoutRoomsChanged(clientId: clientId, breakoutRooms: BreakoutRooms(roomCount: breakoutRooms.roomCount, assignments: assignments, returnAt: returnAt))
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleLobbyChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, lobby: AppLobbyInfo) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var admins: [UUID] = []
+    for index in 0..<lobby.admins.count {
+        guard let userId = lobby.admins.uuids[index].toUUID() else {
+            Logger.debug("missing userId")
+            continue
+        }
+
+        admins.append(userId)
+    }
+
+    var position: UInt32?
+    if lobby.position.valid {
+        position = lobby.position.value
+    }
+
+    obj.handleLobbyChanged(clientId: clientId, lobby: LobbyInfo(participantCount: lobby.participantCount, admins: admins, position: position, message: lobby.message.asString()))
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleChatMessage(object: UnsafeMutableRawPointer?, clientId: UInt32, message: AppChatMessage) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let text = message.text.asString() else {
+        failDebug("missing text")
+        return
+    }
+
+    // Seqnums start at 1, so zero means the message isn't a reply.
+    var replyTo: ChatMessageId?
+    if message.replyTo.seqnum > 0 {
+        replyTo = ChatMessageId(demuxId: message.replyTo.demuxId, seqnum: message.replyTo.seqnum)
+    }
+
+    obj.handleChatMessage(clientId: clientId, message: ChatMessage(id: ChatMessageId(demuxId: message.id.demuxId, seqnum: message.id.seqnum), text: text, replyTo: replyTo))
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandlePollsChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, polls: AppPollArray) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    var finalPolls: [Poll] = []
+    for index in 0..<polls.count {
+        let poll = polls.polls[index]
+
+        guard let question = poll.question.asString() else {
+            Logger.debug("missing question")
+            continue
+        }
+
+        var options: [PollOption] = []
+        for optionIndex in 0..<poll.optionsCount {
+            let option = poll.options[optionIndex]
+            options.append(PollOption(text: option.text.asString() ?? "", votes: option.votes))
+        }
+
+        var ownVote: UInt32?
+        if poll.ownVote.valid {
+            ownVote = poll.ownVote.value
+        }
+
+        finalPolls.append(Poll(id: poll.id, creator: poll.creator, question: question, options: options, ownVote: ownVote, closed: poll.closed))
+    }
+
+    obj.handlePollsChanged(clientId: clientId, polls: finalPolls)
+}
+
 @available(iOSApplicationExtension, unavailable)
 func callManagerInterfaceHandleJoinStateChanged(object: UnsafeMutableRawPointer?, clientId: UInt32, joinState: Int32, demuxId: AppOptionalUInt32) {
     guard let object = object else {
@@ -1139,3 +1536,55 @@ func callManagerInterfaceHandleSpeakingNotification(object: UnsafeMutableRawPoin
 
     obj.handleSpeakingNotification(clientId: clientId, event: _event)
 }
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleHandoffStateReceived(object: UnsafeMutableRawPointer?, clientId: UInt32, audioMuted: AppOptionalBool, videoMuted: AppOptionalBool) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleHandoffStateReceived(
+        clientId: clientId,
+        audioMuted: audioMuted.valid ? audioMuted.value : nil,
+        videoMuted: videoMuted.valid ? videoMuted.value : nil
+    )
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceHandleHandedOff(object: UnsafeMutableRawPointer?, clientId: UInt32, newDemuxId: UInt32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    obj.handleHandedOff(clientId: clientId, newDemuxId: newDemuxId)
+}
+
+@available(iOSApplicationExtension, unavailable)
+func callManagerInterfaceCallLinkRingUpdate(object: UnsafeMutableRawPointer?, rootKey: AppByteSlice, ringId: Int64, sender: AppByteSlice, update: Int32) {
+    guard let object = object else {
+        failDebug("object was unexpectedly nil")
+        return
+    }
+    let obj: CallManagerInterface = Unmanaged.fromOpaque(object).takeUnretainedValue()
+
+    guard let rootKey = rootKey.asData().flatMap({ try? CallLinkRootKey($0) }) else {
+        failDebug("rootKey was unexpectedly invalid")
+        return
+    }
+
+    guard let sender = sender.toUUID() else {
+        failDebug("sender was unexpectedly empty")
+        return
+    }
+
+    guard let update = RingUpdate(rawValue: update) else {
+        failDebug("unrecognized update")
+        return
+    }
+
+    obj.callLinkRingUpdate(rootKey: rootKey, ringId: ringId, sender: sender, update: update)
+}
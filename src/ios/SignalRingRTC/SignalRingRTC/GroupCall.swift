@@ -94,6 +94,143 @@ public class Reaction {
     }
 }
 
+/// Identifies a chat message within a call. Seqnums count up from 1 for each sending device.
+public struct ChatMessageId: Hashable {
+    public let demuxId: UInt32
+    public let seqnum: UInt64
+
+    public init(demuxId: UInt32, seqnum: UInt64) {
+        self.demuxId = demuxId
+        self.seqnum = seqnum
+    }
+}
+
+public struct ChatMessage {
+    public let id: ChatMessageId
+    public let text: String
+    public let replyTo: ChatMessageId?
+}
+
+/// The raised hands in a call, in the order they were raised, and who an admin called on to speak
+/// (see `GroupCall.callNextSpeaker(askToUnmute:)`).
+public struct SpeakerQueue {
+    public let raisedHands: [UInt32]
+    public let currentSpeaker: UInt32?
+}
+
+/// Which layers of the simulcast ladder the SFU is using.
+public struct ForwardedVideoLayers {
+    /// How many of the local device's layers the SFU asks for; 0 if no one is receiving the local
+    /// video.
+    public let outgoingLayerCount: UInt32
+    /// The height the SFU allocated to each remote device whose video it forwards, by demux ID.
+    public let incomingHeights: [UInt32: UInt16]
+}
+
+public struct PollOption {
+    public let text: String
+    public let votes: UInt32
+}
+
+/// A poll kept by the calling server, so that everyone sees the same tallies. Poll IDs count up
+/// from 1 within a call.
+public struct Poll {
+    public let id: UInt32
+    /// The demux ID of the device that created the poll.
+    public let creator: UInt32
+    public let question: String
+    public let options: [PollOption]
+    /// The index of the option the local device voted for, if any.
+    public let ownVote: UInt32?
+    /// Closed polls keep their results but no longer accept votes.
+    public let closed: Bool
+}
+
+/// How the tiles of a `Viewport` are filled.
+public enum ViewportMode: Int32 {
+    /// The first tile shows the first pinned device or else the active speaker.
+    /// The other tiles show the most recent speakers.
+    case speaker = 0
+    /// Tiles show devices in the order they joined, keeping the most recent speakers when not
+    /// everyone fits.
+    case grid
+}
+
+/// The size of a tile, in physical pixels.
+public struct TileSize {
+    public let width: UInt16
+    public let height: UInt16
+
+    public init(width: UInt16, height: UInt16) {
+        self.width = width
+        self.height = height
+    }
+}
+
+/// What the app is able to display.
+public struct Viewport {
+    public let mode: ViewportMode
+    /// The tiles in display order. In speaker mode, the first tile is the speaker's.
+    public let tiles: [TileSize]
+    /// Devices that always get a tile, ahead of everyone else.
+    public let pinned: [UInt32]
+
+    public init(mode: ViewportMode, tiles: [TileSize], pinned: [UInt32]) {
+        self.mode = mode
+        self.tiles = tiles
+        self.pinned = pinned
+    }
+}
+
+/// How a recipient has responded to a ring sent by this device.
+public enum RingRecipientStatus: Int32 {
+    /// The ring was sent, but nothing has been heard back yet.
+    case sent = 0
+    /// The app reported that the ring was delivered to the recipient.
+    case delivered
+    /// At least one of the recipient's devices is ringing.
+    case ringing
+    /// The recipient declined the ring.
+    case declined
+    /// The recipient is in another call.
+    case busy
+    /// The recipient joined the call from one of their devices.
+    case accepted
+}
+
+/// How an admin has split a call link call into breakout rooms. Room `i` is the call link whose
+/// root key is `deriveBreakoutRoomKey(i)` of the main room's key.
+public struct BreakoutRooms {
+    public let roomCount: UInt32
+    /// The room each device should move to, by demux ID. Devices without an assignment stay in
+    /// the main room.
+    public let assignments: [UInt32: UInt32]
+    /// When everyone should return to the main room, if there's a countdown.
+    public let returnAt: Date?
+}
+
+/// What a device waiting for admin approval (`JoinState.pending`) can see of the call.
+public struct LobbyInfo {
+    /// The number of devices in the call, not counting those still waiting.
+    public let participantCount: UInt32
+    /// The call link admins currently in the call.
+    public let admins: [UUID]
+    /// How many devices have been waiting longer than the local device, if known.
+    public let position: UInt32?
+    /// The note an admin left for waiting devices, if any.
+    public let message: String?
+}
+
+public class RingRecipient {
+    public let userId: UUID
+    public let status: RingRecipientStatus
+
+    init(userId: UUID, status: RingRecipientStatus) {
+        self.userId = userId
+        self.status = status
+    }
+}
+
 /// All remote devices in a group call and their associated state.
 @available(iOSApplicationExtension, unavailable)
 public class RemoteDeviceState: Hashable {
@@ -231,6 +368,20 @@ public protocol GroupCallDelegate: AnyObject {
     @MainActor
     func groupCall(onReactions groupCall: GroupCall, reactions: [Reaction])
 
+    /**
+     * Indication that a chat message is ready to be shown. The local device's own messages are
+     * included, and each device's messages arrive in the order it sent them.
+     */
+    @MainActor
+    func groupCall(onChatMessage groupCall: GroupCall, message: ChatMessage)
+
+    /**
+     * Indication that the call's polls changed, or that the local device's latest poll action
+     * took effect. Includes every poll, open or closed, in the order they were created.
+     */
+    @MainActor
+    func groupCall(onPollsChanged groupCall: GroupCall, polls: [Poll])
+
     /**
      * Indication that the application should notify the user that raised hands
      * changed.
@@ -238,6 +389,67 @@ public protocol GroupCallDelegate: AnyObject {
     @MainActor
     func groupCall(onRaisedHands groupCall: GroupCall, raisedHands: [UInt32])
 
+    /**
+     * Indication that the raised hands or the current speaker changed. Sent after
+     * `onRaisedHands` when both apply.
+     */
+    @MainActor
+    func groupCall(onSpeakerQueueChanged groupCall: GroupCall, queue: SpeakerQueue)
+
+    /**
+     * Indication that the layers the SFU uses from the local device, or the heights it forwards
+     * from remote devices, changed.
+     */
+    @MainActor
+    func groupCall(onForwardedVideoLayersChanged groupCall: GroupCall, layers: ForwardedVideoLayers)
+
+    /**
+     * Indication that the recipients of a ring sent by this device have responded.
+     */
+    @MainActor
+    func groupCall(onRingRecipientsChanged groupCall: GroupCall, ringId: Int64, recipients: [RingRecipient])
+
+    /**
+     * Indication that the devices shown in the `Viewport`'s tiles changed. Each element is the
+     * demux ID to show in that tile, or nil for an empty tile.
+     */
+    @MainActor
+    func groupCall(onVideoLayoutChanged groupCall: GroupCall, tiles: [UInt32?])
+
+    /**
+     * Indication that the devices spotlighted for everyone in the call changed.
+     */
+    @MainActor
+    func groupCall(onSpotlightChanged groupCall: GroupCall, spotlight: [UInt32])
+
+    /**
+     * Indication that an admin muted the local device. RingRTC has already stopped sending
+     * audio; the app should show itself as muted.
+     */
+    @MainActor
+    func groupCall(onMutedByAdmin groupCall: GroupCall, adminDemuxId: UInt32)
+
+    /**
+     * Indication that an admin started, changed, or ended (nil) breakout rooms. The app moves
+     * the local device by leaving this call and joining the assigned room's call link.
+     */
+    @MainActor
+    func groupCall(onBreakoutRoomsChanged groupCall: GroupCall, breakoutRooms: BreakoutRooms?)
+
+    /**
+     * Indication of what the local device can see of the call while it waits for admin
+     * approval. Only sent while the join state is `pending`.
+     */
+    @MainActor
+    func groupCall(onLobbyChanged groupCall: GroupCall, lobby: LobbyInfo)
+
+    /**
+     * Indication that an admin asked the local user to unmute. It's up to the user whether to
+     * do so.
+     */
+    @MainActor
+    func groupCall(onUnmuteRequested groupCall: GroupCall, adminDemuxId: UInt32)
+
     /**
      * Indication that the application can retrieve an updated PeekInfo which
      * includes a list of users that are actively in the group call.
@@ -257,6 +469,23 @@ public protocol GroupCallDelegate: AnyObject {
      */
     @MainActor
     func groupCall(onSpeakingNotification groupCall: GroupCall, event: SpeechEvent)
+
+    /**
+     * Indication that another device of the same user handed over its state after
+     * this device called joinByHandoff(fromDemuxId:).
+     *
+     * Raised hands and video requests have already been applied; the application
+     * should apply the mute states to its local tracks.
+     */
+    @MainActor
+    func groupCall(onHandoffStateReceived groupCall: GroupCall, audioMuted: Bool?, videoMuted: Bool?)
+
+    /**
+     * Indication that another device of the same user took over the call. This
+     * device is about to leave the call.
+     */
+    @MainActor
+    func groupCall(onHandedOff groupCall: GroupCall, newDemuxId: UInt32)
 }
 
 @available(iOSApplicationExtension, unavailable)
@@ -452,6 +681,23 @@ public class GroupCall {
         ringrtcJoin(self.ringRtcCallManager, clientId)
     }
 
+    /// Joins the call by taking it over from another device of the same user that
+    /// is in the call as `fromDemuxId`.
+    ///
+    /// Once this device is connected, the other device hands over its state (see
+    /// `groupCall(onHandoffStateReceived:audioMuted:videoMuted:)`) and leaves.
+    @MainActor
+    public func joinByHandoff(fromDemuxId: UInt32) {
+        Logger.debug("joinByHandoff")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcJoinByHandoff(self.ringRtcCallManager, clientId, fromDemuxId)
+    }
+
     @MainActor
     public func leave() {
         Logger.debug("leave")
@@ -499,6 +745,101 @@ public class GroupCall {
         ringrtcReact(self.ringRtcCallManager, clientId, valueSlice)
     }
 
+    /// Sends a chat message to everyone in the call. The text must be non-empty and at most 4096
+    /// bytes of UTF-8.
+    @MainActor
+    public func sendChatMessage(_ text: String, replyTo: ChatMessageId? = nil) {
+        Logger.debug("sendChatMessage")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let textSlice = allocatedAppByteSliceFromString(maybe_string: text)
+        defer { textSlice.bytes?.deallocate() }
+
+        // Seqnums start at 1, so zero means the message isn't a reply.
+        let appReplyTo = AppChatMessageId(demuxId: replyTo?.demuxId ?? 0, seqnum: replyTo?.seqnum ?? 0)
+
+        ringrtcSendChatMessage(self.ringRtcCallManager, clientId, textSlice, appReplyTo)
+    }
+
+    /// Creates a poll with between 2 and 10 options.
+    @MainActor
+    public func createPoll(question: String, options: [String]) {
+        Logger.debug("createPoll")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let questionSlice = allocatedAppByteSliceFromString(maybe_string: question)
+        defer { questionSlice.bytes?.deallocate() }
+
+        let optionSlices = options.map { allocatedAppByteSliceFromString(maybe_string: $0) }
+        defer {
+            for optionSlice in optionSlices {
+                optionSlice.bytes?.deallocate()
+            }
+        }
+
+        optionSlices.withUnsafeBufferPointer { optionsBuffer in
+            ringrtcCreatePoll(
+                self.ringRtcCallManager,
+                clientId,
+                questionSlice,
+                AppStringArray(strings: optionsBuffer.baseAddress, count: optionsBuffer.count)
+            )
+        }
+    }
+
+    /// Votes for the option at `optionIndex`, replacing any earlier vote in the same poll, or
+    /// withdraws the vote if `optionIndex` is nil.
+    @MainActor
+    public func voteInPoll(pollId: UInt32, optionIndex: UInt32?) {
+        Logger.debug("voteInPoll")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let appOptionIndex = AppOptionalUInt32(value: optionIndex ?? 0, valid: optionIndex != nil)
+        ringrtcVoteInPoll(self.ringRtcCallManager, clientId, pollId, appOptionIndex)
+    }
+
+    /// Stops a poll from accepting votes. Only the poll's creator or an admin can do this.
+    @MainActor
+    public func closePoll(pollId: UInt32) {
+        Logger.debug("closePoll")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcClosePoll(self.ringRtcCallManager, clientId, pollId)
+    }
+
+    /// Leaves a note for devices waiting for admin approval, replacing any previous one. An
+    /// empty message clears it. Only call link admins can do this.
+    @MainActor
+    public func sendLobbyMessage(_ message: String) {
+        Logger.debug("sendLobbyMessage")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let messageSlice = allocatedAppByteSliceFromString(maybe_string: message)
+        defer { messageSlice.bytes?.deallocate() }
+
+        ringrtcSendLobbyMessage(self.ringRtcCallManager, clientId, messageSlice)
+    }
+
     @MainActor
     public func raiseHand(raise: Bool) {
         Logger.debug("raiseHand")
@@ -565,6 +906,39 @@ public class GroupCall {
         ringrtcGroupRing(self.ringRtcCallManager, clientId, AppByteSlice(bytes: nil, len: 0))
     }
 
+    /// Rings the given recipients of the current ring again, such as those who haven't responded.
+    @MainActor
+    public func ringAgain(_ recipients: [UUID]) {
+        Logger.debug("ringAgain")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let flattened = recipients.reduce(into: Data()) { $0.append($1.data) }
+        let recipientsSlice = allocatedAppByteSliceFromData(maybe_data: flattened)
+        defer { recipientsSlice.bytes?.deallocate() }
+
+        ringrtcGroupRingAgain(self.ringRtcCallManager, clientId, recipientsSlice)
+    }
+
+    /// Reports that the message carrying the current ring was delivered to `recipient`.
+    @MainActor
+    public func ringDelivered(_ recipient: UUID) {
+        Logger.debug("ringDelivered")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        let recipientSlice = allocatedAppByteSliceFromData(maybe_data: recipient.data)
+        defer { recipientSlice.bytes?.deallocate() }
+
+        ringrtcGroupRingDelivered(self.ringRtcCallManager, clientId, recipientSlice)
+    }
+
     @MainActor
     public func resendMediaKeys() {
         Logger.debug("resendMediaKeys")
@@ -627,6 +1001,60 @@ public class GroupCall {
         ringrtcRequestVideo(self.ringRtcCallManager, clientId, &appResolutionArray, activeSpeakerHeight)
     }
 
+    /// Lets RingRTC choose which remote device to show in each of the app's tiles and request
+    /// their video, instead of the app calling `updateVideoRequests`. The chosen devices are
+    /// reported with `onVideoLayoutChanged`.
+    ///
+    /// - parameter viewport: what the app can display, or nil to go back to `updateVideoRequests`
+    @MainActor
+    public func setViewport(_ viewport: Viewport?) {
+        Logger.debug("setViewport")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        guard let viewport = viewport else {
+            ringrtcSetViewport(self.ringRtcCallManager, clientId, nil)
+            return
+        }
+
+        let appTiles = viewport.tiles.map { AppTileSize(width: $0.width, height: $0.height) }
+        appTiles.withUnsafeBufferPointer { appTilesBuffer in
+            viewport.pinned.withUnsafeBufferPointer { pinnedBuffer in
+                var appViewport = AppViewport(
+                    mode: viewport.mode.rawValue,
+                    tiles: appTilesBuffer.baseAddress,
+                    tilesCount: appTilesBuffer.count,
+                    pinned: pinnedBuffer.baseAddress,
+                    pinnedCount: pinnedBuffer.count
+                )
+                ringrtcSetViewport(self.ringRtcCallManager, clientId, &appViewport)
+            }
+        }
+    }
+
+    /// Spotlights the given devices for everyone in the call (or clears the spotlight if
+    /// empty). Changes are reported with `onSpotlightChanged`.
+    @MainActor
+    public func setSpotlight(_ demuxIds: [UInt32]) {
+        Logger.debug("setSpotlight")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        demuxIds.withUnsafeBufferPointer { demuxIdsBuffer in
+            ringrtcSetSpotlight(
+                self.ringRtcCallManager,
+                clientId,
+                AppDemuxIdArray(demuxIds: demuxIdsBuffer.baseAddress, count: demuxIdsBuffer.count)
+            )
+        }
+    }
+
     @MainActor
     public func approveUser(_ userId: UUID) {
         Logger.debug("approveUser")
@@ -681,6 +1109,57 @@ public class GroupCall {
         ringrtcBlockClient(self.ringRtcCallManager, clientId, otherClientDemuxId)
     }
 
+    @MainActor
+    public func muteClient(demuxId otherClientDemuxId: UInt32) {
+        Logger.debug("muteClient")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcMuteClient(self.ringRtcCallManager, clientId, otherClientDemuxId)
+    }
+
+    @MainActor
+    public func askClientToUnmute(demuxId otherClientDemuxId: UInt32) {
+        Logger.debug("askClientToUnmute")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcAskClientToUnmute(self.ringRtcCallManager, clientId, otherClientDemuxId)
+    }
+
+    /// Lowers another device's raised hand. Only admins can do this.
+    @MainActor
+    public func lowerHand(demuxId otherClientDemuxId: UInt32) {
+        Logger.debug("lowerHand")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcLowerHand(self.ringRtcCallManager, clientId, otherClientDemuxId)
+    }
+
+    /// Calls on the first device in the speaker queue, lowering its hand, or clears the current
+    /// speaker if no hands are raised. Only admins can do this.
+    @MainActor
+    public func callNextSpeaker(askToUnmute: Bool) {
+        Logger.debug("callNextSpeaker")
+
+        guard let clientId = self.clientId else {
+            Logger.warn("no clientId defined for groupCall")
+            return
+        }
+
+        ringrtcCallNextSpeaker(self.ringRtcCallManager, clientId, askToUnmute)
+    }
+
     @MainActor
     public func updateGroupMembers(members: [GroupMember]) {
         Logger.debug("updateGroupMembers")
@@ -798,6 +1277,61 @@ public class GroupCall {
         self.delegate?.groupCall(onRaisedHands: self, raisedHands: raisedHands)
     }
 
+    @MainActor
+    func handleSpeakerQueueChanged(queue: SpeakerQueue) {
+        self.delegate?.groupCall(onSpeakerQueueChanged: self, queue: queue)
+    }
+
+    @MainActor
+    func handleForwardedVideoLayersChanged(layers: ForwardedVideoLayers) {
+        self.delegate?.groupCall(onForwardedVideoLayersChanged: self, layers: layers)
+    }
+
+    @MainActor
+    func handleRingRecipientsChanged(ringId: Int64, recipients: [RingRecipient]) {
+        self.delegate?.groupCall(onRingRecipientsChanged: self, ringId: ringId, recipients: recipients)
+    }
+
+    @MainActor
+    func handleVideoLayoutChanged(tiles: [UInt32?]) {
+        self.delegate?.groupCall(onVideoLayoutChanged: self, tiles: tiles)
+    }
+
+    @MainActor
+    func handleSpotlightChanged(spotlight: [UInt32]) {
+        self.delegate?.groupCall(onSpotlightChanged: self, spotlight: spotlight)
+    }
+
+    @MainActor
+    func handleBreakoutRoomsChanged(breakoutRooms: BreakoutRooms?) {
+        self.delegate?.groupCall(onBreakoutRoomsChanged: self, breakoutRooms: breakoutRooms)
+    }
+
+    @MainActor
+    func handlePollsChanged(polls: [Poll]) {
+        self.delegate?.groupCall(onPollsChanged: self, polls: polls)
+    }
+
+    @MainActor
+    func handleChatMessage(message: ChatMessage) {
+        self.delegate?.groupCall(onChatMessage: self, message: message)
+    }
+
+    @MainActor
+    func handleLobbyChanged(lobby: LobbyInfo) {
+        self.delegate?.groupCall(onLobbyChanged: self, lobby: lobby)
+    }
+
+    @MainActor
+    func handleMutedByAdmin(adminDemuxId: UInt32) {
+        self.delegate?.groupCall(onMutedByAdmin: self, adminDemuxId: adminDemuxId)
+    }
+
+    @MainActor
+    func handleUnmuteRequested(adminDemuxId: UInt32) {
+        self.delegate?.groupCall(onUnmuteRequested: self, adminDemuxId: adminDemuxId)
+    }
+
     @MainActor
     func handleJoinStateChanged(joinState: JoinState, demuxId: UInt32?) {
        self.localDeviceState.joinState = joinState
@@ -877,4 +1411,14 @@ public class GroupCall {
     func handleSpeakingNotification(event: SpeechEvent) {
         self.delegate?.groupCall(onSpeakingNotification: self, event: event)
     }
+
+    @MainActor
+    func handleHandoffStateReceived(audioMuted: Bool?, videoMuted: Bool?) {
+        self.delegate?.groupCall(onHandoffStateReceived: self, audioMuted: audioMuted, videoMuted: videoMuted)
+    }
+
+    @MainActor
+    func handleHandedOff(newDemuxId: UInt32) {
+        self.delegate?.groupCall(onHandedOff: self, newDemuxId: newDemuxId)
+    }
 }
@@ -317,6 +317,43 @@ public class SFUClient {
         }
     }
 
+    /// Asynchronous request to create the call link for one of the breakout rooms of a call link's
+    /// call, before starting the breakout.
+    ///
+    /// The room's root key is `linkRootKey.deriveBreakoutRoomKey(roomIndex)`, and its admin passkey
+    /// is derived from `adminPasskey`, so the main room's admins can manage it. The credential must
+    /// be requested for the breakout room's ID; otherwise this works like `createCallLink`.
+    ///
+    /// - Parameter sfuUrl: the URL to use when accessing the SFU
+    /// - Parameter createCredentialPresentation: a serialized CreateCallLinkCredentialPresentation for the breakout room
+    /// - Parameter linkRootKey: the root key for the main room's call link
+    /// - Parameter roomIndex: which breakout room to create
+    /// - Parameter adminPasskey: the main room's admin passkey
+    /// - Parameter callLinkPublicParams: the serialized CallLinkPublicParams for the breakout room
+    /// - Parameter restrictions: the restrictions for joining the room. Restrictions.unknown is invalid for creation
+    @MainActor
+    public func createBreakoutRoomLink(sfuUrl: String, createCredentialPresentation: [UInt8], linkRootKey: CallLinkRootKey, roomIndex: UInt32, adminPasskey: Data, callLinkPublicParams: [UInt8], restrictions: CallLinkState.Restrictions) async -> SFUResult<CallLinkState> {
+        return await withCheckedContinuation { continuation in
+            Logger.debug("createBreakoutRoomLink")
+
+            let requestId = self.callLinkRequests.add(continuation)
+            let delegateWrapper = SFUDelegateWrapper(self)
+            createCredentialPresentation.withRtcBytes { createCredentialPresentation in
+                linkRootKey.bytes.withRtcBytes { linkRootKey in
+                    adminPasskey.withRtcBytes { adminPasskey in
+                        callLinkPublicParams.withRtcBytes { callLinkPublicParams in
+                            let rawRestrictions = restrictions.toOrdinal()
+                            if rawRestrictions < 0 {
+                                preconditionFailure("cannot create call link with restrictions 'unknown'")
+                            }
+                            rtc_sfu_createBreakoutRoomLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, roomIndex, adminPasskey, callLinkPublicParams, rawRestrictions, delegateWrapper.asRtc())
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Asynchronous request to update a call link's name.
     ///
     /// Possible failure codes include:
@@ -340,7 +377,7 @@ public class SFUClient {
             authCredentialPresentation.withRtcBytes { createCredentialPresentation in
                 linkRootKey.bytes.withRtcBytes { linkRootKey in
                     adminPasskey.withRtcBytes { adminPasskey in
-                        rtc_sfu_updateCallLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, adminPasskey, newName, -1, -1, delegateWrapper.asRtc())
+                        rtc_sfu_updateCallLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, adminPasskey, newName, -1, -1, 0, -1, rtc_Bytes.empty(), delegateWrapper.asRtc())
                     }
                 }
             }
@@ -375,7 +412,43 @@ public class SFUClient {
                         if rawRestrictions < 0 {
                             preconditionFailure("cannot update restrictions to 'unknown'")
                         }
-                        rtc_sfu_updateCallLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, adminPasskey, nil, rawRestrictions, -1, delegateWrapper.asRtc())
+                        rtc_sfu_updateCallLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, adminPasskey, nil, rawRestrictions, -1, 0, -1, rtc_Bytes.empty(), delegateWrapper.asRtc())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Asynchronous request to update a call link's schedule.
+    ///
+    /// Possible failure codes include:
+    /// - 401: the room does not exist (and this is the wrong API to create a new room)
+    /// - 403: the admin passkey is incorrect
+    ///
+    /// This request is idempotent; if it fails due to a network issue, it is safe to retry.
+    ///
+    /// - Parameter sfuUrl: the URL to use when accessing the SFU
+    /// - Parameter authCredentialPresentation: a serialized CallLinkAuthCredentialPresentation
+    /// - Parameter linkRootKey: the root key for the call link
+    /// - Parameter adminPasskey: the passkey specified when the link was created
+    /// - Parameter schedule: the new schedule, or nil to remove it
+    @MainActor
+    public func updateCallLinkSchedule(sfuUrl: String, authCredentialPresentation: [UInt8], linkRootKey: CallLinkRootKey, adminPasskey: Data, schedule: CallLinkSchedule?) async -> SFUResult<CallLinkState> {
+        return await withCheckedContinuation { continuation in
+            Logger.debug("updateCallLinkSchedule")
+
+            let requestId = self.callLinkRequests.add(continuation)
+            let delegateWrapper = SFUDelegateWrapper(self)
+            authCredentialPresentation.withRtcBytes { createCredentialPresentation in
+                linkRootKey.bytes.withRtcBytes { linkRootKey in
+                    adminPasskey.withRtcBytes { adminPasskey in
+                        (schedule?.flattenedInvitees ?? Data()).withRtcBytes { invitees in
+                            let durationSeconds = schedule?.durationSeconds ?? 0
+                            if schedule != nil && durationSeconds <= 0 {
+                                preconditionFailure("a schedule must end after it starts")
+                            }
+                            rtc_sfu_updateCallLink(self.httpClient.rtcClient, requestId, sfuUrl, createCredentialPresentation, linkRootKey, adminPasskey, nil, -1, -1, schedule?.startEpochSeconds ?? 0, durationSeconds, invitees, delegateWrapper.asRtc())
+                        }
                     }
                 }
             }
@@ -351,6 +351,10 @@ final class TestDelegate: CallManagerDelegate & HTTPDelegate {
         Logger.debug("TestDelegate:onLowBandwidthForVideoFor - \(recovered)")
     }
 
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, onAcceptedOnAnotherDeviceFor call: OpaqueCallData, deviceId: UInt32) {
+        Logger.debug("TestDelegate:onAcceptedOnAnotherDeviceFor - \(deviceId)")
+    }
+
     func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldSendOffer callId: UInt64, call: OpaqueCallData, destinationDeviceId: UInt32?, opaque: Data, callMediaType: CallMediaType) {
         Logger.debug("TestDelegate:shouldSendOffer")
         generalInvocationDetected = true
@@ -622,6 +626,11 @@ final class TestDelegate: CallManagerDelegate & HTTPDelegate {
         didUpdateRingForGroupUpdate = update
     }
 
+    func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, didUpdateRingForCallLink rootKey: CallLinkRootKey, ringId: Int64, sender: UUID, update: RingUpdate) {
+        Logger.debug("TestDelegate:didUpdateRingForCallLink")
+        generalInvocationDetected = true
+    }
+
     func callManager(_ callManager: CallManager<OpaqueCallData, TestDelegate>, shouldCompareCalls call1: OpaqueCallData, call2: OpaqueCallData) -> Bool {
         Logger.debug("TestDelegate:shouldCompareCalls")
         generalInvocationDetected = true
@@ -14,7 +14,18 @@ class TestGroupCallDelegate: GroupCallDelegate {
     var onAudioLevelsCount = 0
     var onLowBandwidthForVideoCount = 0
     var onReactionsCount = 0
+    var onChatMessageCount = 0
+    var onPollsChangedCount = 0
     var onRaisedHandsCount = 0
+    var onSpeakerQueueChangedCount = 0
+    var onForwardedVideoLayersChangedCount = 0
+    var onRingRecipientsChangedCount = 0
+    var onVideoLayoutChangedCount = 0
+    var onSpotlightChangedCount = 0
+    var onMutedByAdminCount = 0
+    var onUnmuteRequestedCount = 0
+    var onBreakoutRoomsChangedCount = 0
+    var onLobbyChangedCount = 0
     var onPeekChangedCount = 0
     var onEndedCount = 0
     var onSpeakingCount = 0
@@ -49,10 +60,54 @@ class TestGroupCallDelegate: GroupCallDelegate {
         onReactionsCount += 1
     }
 
+    func groupCall(onChatMessage groupCall: GroupCall, message: ChatMessage) {
+        onChatMessageCount += 1
+    }
+
+    func groupCall(onPollsChanged groupCall: GroupCall, polls: [Poll]) {
+        onPollsChangedCount += 1
+    }
+
     func groupCall(onRaisedHands groupCall: GroupCall, raisedHands: [UInt32]) {
         onRaisedHandsCount += 1
     }
 
+    func groupCall(onSpeakerQueueChanged groupCall: GroupCall, queue: SpeakerQueue) {
+        onSpeakerQueueChangedCount += 1
+    }
+
+    func groupCall(onForwardedVideoLayersChanged groupCall: GroupCall, layers: ForwardedVideoLayers) {
+        onForwardedVideoLayersChangedCount += 1
+    }
+
+    func groupCall(onRingRecipientsChanged groupCall: GroupCall, ringId: Int64, recipients: [RingRecipient]) {
+        onRingRecipientsChangedCount += 1
+    }
+
+    func groupCall(onVideoLayoutChanged groupCall: GroupCall, tiles: [UInt32?]) {
+        onVideoLayoutChangedCount += 1
+    }
+
+    func groupCall(onSpotlightChanged groupCall: GroupCall, spotlight: [UInt32]) {
+        onSpotlightChangedCount += 1
+    }
+
+    func groupCall(onMutedByAdmin groupCall: GroupCall, adminDemuxId: UInt32) {
+        onMutedByAdminCount += 1
+    }
+
+    func groupCall(onUnmuteRequested groupCall: GroupCall, adminDemuxId: UInt32) {
+        onUnmuteRequestedCount += 1
+    }
+
+    func groupCall(onBreakoutRoomsChanged groupCall: GroupCall, breakoutRooms: BreakoutRooms?) {
+        onBreakoutRoomsChangedCount += 1
+    }
+
+    func groupCall(onLobbyChanged groupCall: GroupCall, lobby: LobbyInfo) {
+        onLobbyChangedCount += 1
+    }
+
     func groupCall(onPeekChanged groupCall: GroupCall) {
         onPeekChangedCount += 1
     }
@@ -66,4 +121,10 @@ class TestGroupCallDelegate: GroupCallDelegate {
         onSpeakingCount += 1
         lastOnSpeakingEvent = event
     }
+
+    func groupCall(onHandoffStateReceived groupCall: GroupCall, audioMuted: Bool?, videoMuted: Bool?) {
+    }
+
+    func groupCall(onHandedOff groupCall: GroupCall, newDemuxId: UInt32) {
+    }
 }
@@ -9,6 +9,8 @@ export {
   AnswerMessage,
   AudioDevice,
   DataMode,
+  BreakoutRoomAssignment,
+  BreakoutRooms,
   BusyMessage,
   Call,
   CallEndedReason,
@@ -18,19 +20,25 @@ export {
   CallSettings,
   CallState,
   CallingMessage,
+  ChatMessage,
+  ChatMessageId,
   ConnectionState,
   DeviceId,
+  ForwardedVideoLayers,
   GroupCall,
   GroupCallEndReason,
   GroupCallKind,
   GroupCallObserver,
   GroupMemberInfo,
+  HandoffState,
   HangupMessage,
   HangupType,
   HttpMethod,
   HttpResult,
   IceCandidateMessage,
+  IncomingVideoHeight,
   JoinState,
+  LobbyInfo,
   LocalDeviceState,
   OfferMessage,
   OfferType,
@@ -38,16 +46,24 @@ export {
   PeekDeviceInfo,
   PeekInfo,
   PeekStatusCodes,
+  Poll,
+  PollOption,
   Reaction,
   RemoteDeviceState,
   RingCancelReason,
+  RingRecipient,
+  RingRecipientStatus,
   RingRTCType,
   RingUpdate,
+  SpeakerQueue,
   SpeechEvent,
+  TileSize,
   UserId,
   VideoCapturer,
   VideoRenderer,
   VideoRequest,
+  Viewport,
+  ViewportMode,
   callIdFromEra,
   callIdFromRingId,
 } from './ringrtc/Service';
@@ -65,6 +81,7 @@ export {
 export {
   CallLinkRootKey,
   CallLinkRestrictions,
+  CallLinkSchedule,
   CallLinkState,
 } from './ringrtc/CallLinks';
 
@@ -35,18 +35,37 @@ export class CallLinkRootKey {
     return Native.CallLinkRootKey_deriveRoomId(this.bytes);
   }
 
+  /**
+   * The root key of breakout room `roomIndex` of this call link's call. Breakout rooms are
+   * created with `RingRTC.createBreakoutRoomLink`.
+   */
+  deriveBreakoutRoomKey(roomIndex: number): CallLinkRootKey {
+    return new CallLinkRootKey(
+      Native.CallLinkRootKey_deriveBreakoutRoomKey(this.bytes, roomIndex)
+    );
+  }
+
   toString(): string {
     // eslint-disable-next-line @typescript-eslint/no-unsafe-return
     return Native.CallLinkRootKey_toFormattedString(this.bytes);
   }
 }
 
+export class CallLinkSchedule {
+  constructor(
+    public start: Date,
+    public end: Date,
+    public invitees: Array<Buffer>
+  ) {}
+}
+
 export class CallLinkState {
   constructor(
     public name: string,
     public restrictions: CallLinkRestrictions,
     public revoked: boolean,
-    public expiration: Date
+    public expiration: Date,
+    public schedule?: CallLinkSchedule
   ) {}
 }
 
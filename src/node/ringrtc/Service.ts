@@ -10,6 +10,7 @@ import {
   CallLinkState,
   CallLinkRestrictions,
   CallLinkRootKey,
+  CallLinkSchedule,
 } from './CallLinks';
 import Native from './Native';
 
@@ -86,14 +87,25 @@ class NativeCallManager {
 // Mirror methods onto NativeCallManager.
 // This is done through direct assignment rather than wrapper methods to avoid indirection.
 (NativeCallManager.prototype as any).setSelfUuid = Native.cm_setSelfUuid;
+(NativeCallManager.prototype as any).getPersistedState =
+  Native.cm_getPersistedState;
+(NativeCallManager.prototype as any).restorePersistedState =
+  Native.cm_restorePersistedState;
 (NativeCallManager.prototype as any).createOutgoingCall =
   Native.cm_createOutgoingCall;
+(NativeCallManager.prototype as any).pullCall = Native.cm_pullCall;
 (NativeCallManager.prototype as any).proceed = Native.cm_proceed;
 (NativeCallManager.prototype as any).accept = Native.cm_accept;
 (NativeCallManager.prototype as any).ignore = Native.cm_ignore;
 (NativeCallManager.prototype as any).hangup = Native.cm_hangup;
 (NativeCallManager.prototype as any).cancelGroupRing =
   Native.cm_cancelGroupRing;
+(NativeCallManager.prototype as any).acknowledgeGroupRing =
+  Native.cm_acknowledgeGroupRing;
+(NativeCallManager.prototype as any).ringScheduledCallLink =
+  Native.cm_ringScheduledCallLink;
+(NativeCallManager.prototype as any).cancelCallLinkRing =
+  Native.cm_cancelCallLinkRing;
 (NativeCallManager.prototype as any).signalingMessageSent =
   Native.cm_signalingMessageSent;
 (NativeCallManager.prototype as any).signalingMessageSendFailed =
@@ -130,10 +142,21 @@ class NativeCallManager {
   Native.cm_deleteGroupCallClient;
 (NativeCallManager.prototype as any).connect = Native.cm_connect;
 (NativeCallManager.prototype as any).join = Native.cm_join;
+(NativeCallManager.prototype as any).joinByHandoff = Native.cm_joinByHandoff;
 (NativeCallManager.prototype as any).leave = Native.cm_leave;
 (NativeCallManager.prototype as any).disconnect = Native.cm_disconnect;
 (NativeCallManager.prototype as any).groupRing = Native.cm_groupRing;
+(NativeCallManager.prototype as any).groupRingAgain = Native.cm_groupRingAgain;
+(NativeCallManager.prototype as any).groupRingDelivered =
+  Native.cm_groupRingDelivered;
 (NativeCallManager.prototype as any).groupReact = Native.cm_groupReact;
+(NativeCallManager.prototype as any).sendChatMessage =
+  Native.cm_sendChatMessage;
+(NativeCallManager.prototype as any).createPoll = Native.cm_createPoll;
+(NativeCallManager.prototype as any).voteInPoll = Native.cm_voteInPoll;
+(NativeCallManager.prototype as any).closePoll = Native.cm_closePoll;
+(NativeCallManager.prototype as any).sendLobbyMessage =
+  Native.cm_sendLobbyMessage;
 (NativeCallManager.prototype as any).groupRaiseHand = Native.cm_groupRaiseHand;
 (NativeCallManager.prototype as any).setOutgoingAudioMuted =
   Native.cm_setOutgoingAudioMuted;
@@ -146,16 +169,26 @@ class NativeCallManager {
   Native.cm_resendMediaKeys;
 (NativeCallManager.prototype as any).setDataMode = Native.cm_setDataMode;
 (NativeCallManager.prototype as any).requestVideo = Native.cm_requestVideo;
+(NativeCallManager.prototype as any).setViewport = Native.cm_setViewport;
+(NativeCallManager.prototype as any).setSpotlight = Native.cm_setSpotlight;
 (NativeCallManager.prototype as any).approveUser = Native.cm_approveUser;
 (NativeCallManager.prototype as any).denyUser = Native.cm_denyUser;
 (NativeCallManager.prototype as any).removeClient = Native.cm_removeClient;
 (NativeCallManager.prototype as any).blockClient = Native.cm_blockClient;
+(NativeCallManager.prototype as any).muteClient = Native.cm_muteClient;
+(NativeCallManager.prototype as any).askClientToUnmute =
+  Native.cm_askClientToUnmute;
+(NativeCallManager.prototype as any).lowerHand = Native.cm_lowerHand;
+(NativeCallManager.prototype as any).callNextSpeaker =
+  Native.cm_callNextSpeaker;
 (NativeCallManager.prototype as any).setGroupMembers =
   Native.cm_setGroupMembers;
 (NativeCallManager.prototype as any).setMembershipProof =
   Native.cm_setMembershipProof;
 (NativeCallManager.prototype as any).readCallLink = Native.cm_readCallLink;
 (NativeCallManager.prototype as any).createCallLink = Native.cm_createCallLink;
+(NativeCallManager.prototype as any).createBreakoutRoomLink =
+  Native.cm_createBreakoutRoomLink;
 (NativeCallManager.prototype as any).updateCallLink = Native.cm_updateCallLink;
 (NativeCallManager.prototype as any).deleteCallLink = Native.cm_deleteCallLink;
 (NativeCallManager.prototype as any).peekGroupCall = Native.cm_peekGroupCall;
@@ -183,6 +216,44 @@ export interface Reaction {
   value: string;
 }
 
+// Identifies a chat message within a call. Seqnums count up from 1 for each
+// sending device.
+export interface ChatMessageId {
+  demuxId: number;
+  seqnum: number;
+}
+
+export interface ChatMessage {
+  id: ChatMessageId;
+  text: string;
+  replyTo?: ChatMessageId;
+}
+
+export interface PollOption {
+  text: string;
+  votes: number;
+}
+
+// Poll IDs are assigned by the calling server, counting up from 1 within a call.
+export interface Poll {
+  id: number;
+  // The demux ID of the device that created the poll.
+  creator: number;
+  question: string;
+  options: Array<PollOption>;
+  // The index of the option the local device voted for, if any.
+  ownVote?: number;
+  // Closed polls keep their results but no longer accept votes.
+  closed: boolean;
+}
+
+// The state handed over when this device takes over a call from another
+// device of the same user (see GroupCall.joinByHandoff).
+export interface HandoffState {
+  audioMuted?: boolean;
+  videoMuted?: boolean;
+}
+
 /** type returned by Rust */
 export interface RawPeekInfo {
   devices: Array<PeekDeviceInfo>;
@@ -271,6 +342,7 @@ interface RawCallLinkState {
   rawRestrictions: number;
   revoked: boolean;
   expiration: Date;
+  schedule?: CallLinkSchedule;
 }
 
 function normalizeAudioLevel(raw: RawAudioLevel): NormalizedAudioLevel {
@@ -297,7 +369,13 @@ function rawCallLinkStateToCallLinkState(
       raw.name,
       restrictions,
       raw.revoked,
-      raw.expiration
+      raw.expiration,
+      raw.schedule &&
+        new CallLinkSchedule(
+          raw.schedule.start,
+          raw.schedule.end,
+          raw.schedule.invitees
+        )
     );
   } else {
     return undefined;
@@ -441,6 +519,15 @@ export class RingRTCType {
       ) => void)
     | null = null;
 
+  handleCallLinkRingUpdate:
+    | ((
+        rootKey: CallLinkRootKey,
+        ringId: bigint,
+        sender: Buffer,
+        update: RingUpdate
+      ) => void)
+    | null = null;
+
   handleRtcStatsReport: ((reportJson: string) => void) | null = null;
 
   constructor() {
@@ -462,6 +549,18 @@ export class RingRTCType {
     this.callManager.setSelfUuid(uuid);
   }
 
+  // Called by UX
+  // Returns the outstanding group rings and early call messages, for the app to save and pass to
+  // restorePersistedState() after a restart. Returns null if there's nothing worth saving.
+  getPersistedState(): Buffer | null {
+    return this.callManager.getPersistedState();
+  }
+
+  // Called by UX
+  restorePersistedState(state: Buffer): void {
+    this.callManager.restorePersistedState(state);
+  }
+
   // Called by UX
   startOutgoingCall(
     remoteUserId: UserId,
@@ -487,6 +586,35 @@ export class RingRTCType {
     return call;
   }
 
+  // Called by UX
+  // Takes over a call that was accepted on another of the local user's devices.
+  // The other device will see its call end with AcceptedOnAnotherDevice.
+  pullCall(
+    remoteUserId: UserId,
+    replacedCallId: CallId,
+    isVideoCall: boolean,
+    localDeviceId: DeviceId
+  ): Call {
+    const callId = this.callManager.pullCall(
+      remoteUserId,
+      replacedCallId,
+      isVideoCall,
+      localDeviceId
+    );
+    const isIncoming = false;
+    const call = new Call(
+      this.callManager,
+      remoteUserId,
+      callId,
+      isIncoming,
+      isVideoCall,
+      CallState.Prering
+    );
+    this._call = call;
+    call.outgoingVideoEnabled = isVideoCall;
+    return call;
+  }
+
   // Called by UX
   cancelGroupRing(
     groupId: GroupId,
@@ -498,6 +626,52 @@ export class RingRTCType {
     });
   }
 
+  /**
+   * Tells the sender of a group ring that this device is ringing.
+   *
+   * Nothing is sent back to the ringer otherwise, since this reveals that the user has a device
+   * online. Only call this if the user has opted in to sharing that.
+   */
+  // Called by UX
+  acknowledgeGroupRing(groupId: GroupId, ringId: bigint): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.acknowledgeGroupRing(groupId, ringId.toString());
+    });
+  }
+
+  /**
+   * Rings the invitees of a scheduled call link if its meeting is starting.
+   *
+   * Meant to be called from the app's timer at the scheduled start time. Calling it again
+   * (or from other devices) is harmless.
+   *
+   * @param linkRootKey - the root key for the call link
+   * @param schedule - the link's schedule, as found in its CallLinkState
+   */
+  ringScheduledCallLink(
+    linkRootKey: CallLinkRootKey,
+    schedule: CallLinkSchedule
+  ): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.ringScheduledCallLink(linkRootKey.bytes, schedule);
+    });
+  }
+
+  /**
+   * Stops the ring of a scheduled call link's meeting, such as when the meeting is cancelled.
+   *
+   * @param linkRootKey - the root key for the call link
+   * @param schedule - the schedule the invitees were rung for
+   */
+  cancelCallLinkRing(
+    linkRootKey: CallLinkRootKey,
+    schedule: CallLinkSchedule
+  ): void {
+    sillyDeadlockProtection(() => {
+      this.callManager.cancelCallLinkRing(linkRootKey.bytes, schedule);
+    });
+  }
+
   // Called by Rust
   onStartOutgoingCall(remoteUserId: UserId, callId: CallId): void {
     const call = this._call;
@@ -626,7 +800,8 @@ export class RingRTCType {
     remoteUserId: UserId,
     callId: CallId,
     reason: CallEndedReason,
-    ageSec: number
+    ageSec: number,
+    acceptedOnDeviceId: DeviceId | null
   ): void {
     const callInfo = this._callInfoByCallId.get(this.getCallInfoKey(callId));
     const { isVideoCall, receivedAtCounter, receivedAtDate } = callInfo || {
@@ -692,6 +867,7 @@ export class RingRTCType {
     // Send the end reason first because setting the state triggers
     // call.handleStateChanged, which may look at call.endedReason.
     call.endedReason = reason;
+    call.acceptedOnDeviceId = acceptedOnDeviceId ?? undefined;
     call.state = CallState.Ended;
   }
 
@@ -1003,6 +1179,48 @@ export class RingRTCType {
     return promise;
   }
 
+  /**
+   * Asynchronous request to create the call link for one of the breakout rooms of a call link's
+   * call, before starting the breakout.
+   *
+   * The room's root key is `linkRootKey.deriveBreakoutRoomKey(roomIndex)`, and its admin passkey
+   * is derived from `adminPasskey`, so the main room's admins can manage it. The credential must
+   * be requested for the breakout room's ID, otherwise this works like `createCallLink`.
+   *
+   * @param sfuUrl - the URL to use when accessing the SFU
+   * @param createCredentialPresentation - a serialized CreateCallLinkCredentialPresentation for
+   *   the breakout room
+   * @param linkRootKey - the root key for the main room's call link
+   * @param roomIndex - which breakout room to create
+   * @param adminPasskey - the main room's admin passkey
+   * @param callLinkPublicParams - the serialized CallLinkPublicParams for the breakout room
+   */
+  createBreakoutRoomLink(
+    sfuUrl: string,
+    createCredentialPresentation: Buffer,
+    linkRootKey: CallLinkRootKey,
+    roomIndex: number,
+    adminPasskey: Buffer,
+    callLinkPublicParams: Buffer,
+    restrictions: Exclude<CallLinkRestrictions, CallLinkRestrictions.Unknown>
+  ): Promise<HttpResult<CallLinkState>> {
+    const [requestId, promise] = this._callLinkRequests.add();
+    // Response comes back via handleCallLinkResponse
+    sillyDeadlockProtection(() => {
+      this.callManager.createBreakoutRoomLink(
+        requestId,
+        sfuUrl,
+        createCredentialPresentation,
+        linkRootKey.bytes,
+        roomIndex,
+        adminPasskey,
+        callLinkPublicParams,
+        restrictions
+      );
+    });
+    return promise;
+  }
+
   /**
    * Asynchronous request to update a call link's name.
    *
@@ -1036,6 +1254,7 @@ export class RingRTCType {
         adminPasskey,
         newName,
         undefined,
+        undefined,
         undefined
       );
     });
@@ -1076,12 +1295,53 @@ export class RingRTCType {
         adminPasskey,
         undefined,
         restrictions,
+        undefined,
         undefined
       );
     });
     return promise;
   }
 
+  /**
+   * Asynchronous request to update a call link's schedule.
+   *
+   * Possible failure codes include:
+   * - 401: the room does not exist (and this is the wrong API to create a new room)
+   * - 403: the admin passkey is incorrect
+   *
+   * This request is idempotent; if it fails due to a network issue, it is safe to retry.
+   *
+   * @param sfuUrl - the URL to use when accessing the SFU
+   * @param authCredentialPresentation - a serialized CallLinkAuthCredentialPresentation
+   * @param linkRootKey - the root key for the call link
+   * @param adminPasskey - the passkey specified when the link was created
+   * @param schedule - the new schedule to use, or undefined to remove it
+   */
+  updateCallLinkSchedule(
+    sfuUrl: string,
+    authCredentialPresentation: Buffer,
+    linkRootKey: CallLinkRootKey,
+    adminPasskey: Buffer,
+    schedule: CallLinkSchedule | undefined
+  ): Promise<HttpResult<CallLinkState>> {
+    const [requestId, promise] = this._callLinkRequests.add();
+    // Response comes back via handleCallLinkResponse
+    sillyDeadlockProtection(() => {
+      this.callManager.updateCallLink(
+        requestId,
+        sfuUrl,
+        authCredentialPresentation,
+        linkRootKey.bytes,
+        adminPasskey,
+        undefined,
+        undefined,
+        undefined,
+        schedule ?? null
+      );
+    });
+    return promise;
+  }
+
   /**
    * Asynchronous request to delete a call link.
    *
@@ -1391,6 +1651,138 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  handlePollsChanged(clientId: GroupCallClientId, polls: Array<Poll>): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handlePollsChanged(polls);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleChatMessage(clientId: GroupCallClientId, message: ChatMessage): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleChatMessage(message);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleForwardedVideoLayersChanged(
+    clientId: GroupCallClientId,
+    layers: ForwardedVideoLayers
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleForwardedVideoLayersChanged(layers);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleSpeakerQueueChanged(
+    clientId: GroupCallClientId,
+    queue: SpeakerQueue
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleSpeakerQueueChanged(queue);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleLobbyChanged(clientId: GroupCallClientId, lobby: LobbyInfo): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleLobbyChanged(lobby);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleBreakoutRoomsChanged(
+    clientId: GroupCallClientId,
+    breakoutRooms: BreakoutRooms | undefined
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleBreakoutRoomsChanged(breakoutRooms);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleMutedByAdmin(clientId: GroupCallClientId, adminDemuxId: number): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleMutedByAdmin(adminDemuxId);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleUnmuteRequested(
+    clientId: GroupCallClientId,
+    adminDemuxId: number
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleUnmuteRequested(adminDemuxId);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleSpotlightChanged(
+    clientId: GroupCallClientId,
+    spotlight: Array<number>
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleSpotlightChanged(spotlight);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleVideoLayoutChanged(
+    clientId: GroupCallClientId,
+    tiles: Array<number | undefined>
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleVideoLayoutChanged(tiles);
+      }
+    });
+  }
+
+  // Called by Rust
+  handleRingRecipientsChanged(
+    clientId: GroupCallClientId,
+    ringId: bigint,
+    recipients: Array<RingRecipient>
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleRingRecipientsChanged(ringId, recipients);
+      }
+    });
+  }
+
   // Called by Rust
   handleRemoteDevicesChanged(
     clientId: GroupCallClientId,
@@ -1521,6 +1913,27 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  callLinkRingUpdate(
+    rootKey: Buffer,
+    ringId: bigint,
+    sender: GroupCallUserId,
+    state: RingUpdate
+  ): void {
+    sillyDeadlockProtection(() => {
+      if (this.handleCallLinkRingUpdate) {
+        this.handleCallLinkRingUpdate(
+          CallLinkRootKey.fromBytes(rootKey),
+          ringId,
+          sender,
+          state
+        );
+      } else {
+        this.logError('RingRTC.handleCallLinkRingUpdate is not set!');
+      }
+    });
+  }
+
   // Called by Rust
   handleRtcStatsReportComplete(reportJson: string): void {
     if (this.handleRtcStatsReport) {
@@ -1538,6 +1951,30 @@ export class RingRTCType {
     });
   }
 
+  // Called by Rust
+  handleHandoffStateReceived(
+    clientId: GroupCallClientId,
+    audioMuted: boolean | undefined,
+    videoMuted: boolean | undefined
+  ): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleHandoffStateReceived({ audioMuted, videoMuted });
+      }
+    });
+  }
+
+  // Called by Rust
+  handleHandedOff(clientId: GroupCallClientId, newDemuxId: number): void {
+    sillyDeadlockProtection(() => {
+      const groupCall = this._groupCallByClientId.get(clientId);
+      if (groupCall) {
+        groupCall.handleHandedOff(newDemuxId);
+      }
+    });
+  }
+
   // Called by Rust
   onLogMessage(
     level: number,
@@ -1952,6 +2389,9 @@ export class Call {
   private _videoCapturer: VideoCapturer | null = null;
   private _videoRenderer: VideoRenderer | null = null;
   endedReason?: CallEndedReason;
+  // Set along with an endedReason of AcceptedOnAnotherDevice when the
+  // accepting device is known.
+  acceptedOnDeviceId?: DeviceId;
 
   // These callbacks should be set by the UX code.
   handleStateChanged?: () => void;
@@ -2367,6 +2807,30 @@ export class GroupMemberInfo {
 
 // Used for the application to communicate the actual resolutions of
 // each device in a group call to RingRTC and the SFU.
+export enum ViewportMode {
+  /// The first tile shows the first pinned device or else the active speaker.
+  /// The other tiles show the most recent speakers.
+  Speaker = 0,
+  /// Tiles show devices in the order they joined, keeping the most recent
+  /// speakers when not everyone fits.
+  Grid,
+}
+
+/// The size of a tile, in physical pixels.
+export interface TileSize {
+  width: number; // UInt16
+  height: number; // UInt16
+}
+
+/// What the app is able to display.
+export interface Viewport {
+  mode: ViewportMode;
+  /// The tiles in display order. In speaker mode, the first tile is the speaker's.
+  tiles: Array<TileSize>;
+  /// Devices that always get a tile, ahead of everyone else.
+  pinned: Array<number>;
+}
+
 export class VideoRequest {
   demuxId: number; // UInt32
   width: number; // UInt16
@@ -2386,6 +2850,73 @@ export class VideoRequest {
   }
 }
 
+/// How a recipient has responded to a ring sent by this device.
+export enum RingRecipientStatus {
+  /// The ring was sent, but nothing has been heard back yet.
+  Sent = 0,
+  /// The app reported that the ring was delivered to the recipient.
+  Delivered,
+  /// At least one of the recipient's devices is ringing.
+  Ringing,
+  /// The recipient declined the ring.
+  Declined,
+  /// The recipient is in another call.
+  Busy,
+  /// The recipient joined the call from one of their devices.
+  Accepted,
+}
+
+export interface RingRecipient {
+  userId: Buffer;
+  status: RingRecipientStatus;
+}
+
+export interface IncomingVideoHeight {
+  demuxId: number;
+  height: number;
+}
+
+export interface ForwardedVideoLayers {
+  // How many of the local device's layers the SFU asks for; 0 if no one is
+  // receiving the local video.
+  outgoingLayerCount: number;
+  // The height the SFU allocated to each remote device whose video it forwards.
+  incomingHeights: Array<IncomingVideoHeight>;
+}
+
+// The raised hands in a call, in the order they were raised, and who an admin
+// called on to speak (see GroupCall.callNextSpeaker).
+export interface SpeakerQueue {
+  raisedHands: Array<number>;
+  currentSpeaker?: number;
+}
+
+// What a device waiting for admin approval (JoinState.Pending) can see of the call.
+export interface LobbyInfo {
+  // The number of devices in the call, not counting those still waiting.
+  participantCount: number;
+  // The call link admins currently in the call.
+  admins: Array<Buffer>;
+  // How many devices have been waiting longer than the local device.
+  position?: number;
+  // The note an admin left for waiting devices (see GroupCall.sendLobbyMessage).
+  message?: string;
+}
+
+export interface BreakoutRoomAssignment {
+  demuxId: number;
+  roomIndex: number;
+}
+
+// Room `i` is the call link whose root key is `deriveBreakoutRoomKey(i)` of the main room's key.
+export interface BreakoutRooms {
+  roomCount: number;
+  // Devices without an assignment stay in the main room.
+  assignments: Array<BreakoutRoomAssignment>;
+  // When everyone should return to the main room, if there's a countdown.
+  returnAt?: Date;
+}
+
 export enum GroupCallKind {
   SignalGroup,
   CallLink,
@@ -2400,9 +2931,42 @@ export interface GroupCallObserver {
   onLowBandwidthForVideo(groupCall: GroupCall, recovered: boolean): void;
   onReactions(groupCall: GroupCall, reactions: Array<Reaction>): void;
   onRaisedHands(groupCall: GroupCall, raisedHands: Array<number>): void;
+  // Called after onRaisedHands when both apply.
+  onSpeakerQueueChanged(groupCall: GroupCall, queue: SpeakerQueue): void;
+  onForwardedVideoLayersChanged(
+    groupCall: GroupCall,
+    layers: ForwardedVideoLayers
+  ): void;
+  // Includes the local device's own messages, and each device's messages
+  // arrive in the order it sent them.
+  onChatMessage(groupCall: GroupCall, message: ChatMessage): void;
+  // Includes every poll, open or closed, in the order they were created.
+  onPollsChanged(groupCall: GroupCall, polls: Array<Poll>): void;
+  onRingRecipientsChanged(
+    groupCall: GroupCall,
+    ringId: bigint,
+    recipients: Array<RingRecipient>
+  ): void;
+  onVideoLayoutChanged(
+    groupCall: GroupCall,
+    tiles: Array<number | undefined>
+  ): void;
+  onSpotlightChanged(groupCall: GroupCall, spotlight: Array<number>): void;
+  // RingRTC has already stopped sending audio; the app should show itself as muted.
+  onMutedByAdmin(groupCall: GroupCall, adminDemuxId: number): void;
+  // Only while the local device's join state is Pending.
+  onLobbyChanged(groupCall: GroupCall, lobby: LobbyInfo): void;
+  // Undefined means the breakout has ended and everyone should return to the main room.
+  onBreakoutRoomsChanged(
+    groupCall: GroupCall,
+    breakoutRooms: BreakoutRooms | undefined
+  ): void;
+  onUnmuteRequested(groupCall: GroupCall, adminDemuxId: number): void;
   onPeekChanged(groupCall: GroupCall): void;
   onEnded(groupCall: GroupCall, reason: GroupCallEndReason): void;
   onSpeechEvent(groupCall: GroupCall, event: SpeechEvent): void;
+  onHandoffStateReceived(groupCall: GroupCall, state: HandoffState): void;
+  onHandedOff(groupCall: GroupCall, newDemuxId: number): void;
 }
 
 export class GroupCall {
@@ -2449,6 +3013,14 @@ export class GroupCall {
     this._callManager.join(this._clientId);
   }
 
+  // Called by UI
+  // Joins by taking over the call from another device of the same user that is
+  // in the call as fromDemuxId. Once this device is connected, the other
+  // device hands over its state (see onHandoffStateReceived) and leaves.
+  joinByHandoff(fromDemuxId: number): void {
+    this._callManager.joinByHandoff(this._clientId, fromDemuxId);
+  }
+
   // Called by UI
   leave(): void {
     this._callManager.leave(this._clientId);
@@ -2497,6 +3069,37 @@ export class GroupCall {
     this._callManager.groupReact(this._clientId, value);
   }
 
+  // Called by UI
+  // The text must be non-empty and at most 4096 bytes of UTF-8.
+  sendChatMessage(text: string, replyTo?: ChatMessageId): void {
+    this._callManager.sendChatMessage(this._clientId, text, replyTo);
+  }
+
+  // Called by UI
+  // The question must have between 2 and 10 options.
+  createPoll(question: string, options: Array<string>): void {
+    this._callManager.createPoll(this._clientId, question, options);
+  }
+
+  // Called by UI
+  // Replaces any earlier vote in the same poll; undefined withdraws the vote.
+  voteInPoll(pollId: number, optionIndex: number | undefined): void {
+    this._callManager.voteInPoll(this._clientId, pollId, optionIndex);
+  }
+
+  // Called by UI
+  // Only the poll's creator or an admin can close it.
+  closePoll(pollId: number): void {
+    this._callManager.closePoll(this._clientId, pollId);
+  }
+
+  // Called by UI
+  // Leaves a note for devices waiting for approval; an empty message clears it.
+  // Only admins may do this; the calling server ignores the request otherwise.
+  sendLobbyMessage(message: string): void {
+    this._callManager.sendLobbyMessage(this._clientId, message);
+  }
+
   // Called by UI
   raiseHand(raise: boolean): void {
     this._callManager.groupRaiseHand(this._clientId, raise);
@@ -2531,6 +3134,18 @@ export class GroupCall {
     this._callManager.groupRing(this._clientId, undefined);
   }
 
+  // Called by UI
+  // Rings the given recipients of the current ring again, e.g. those who haven't responded.
+  ringAgain(recipients: Array<Buffer>): void {
+    this._callManager.groupRingAgain(this._clientId, recipients);
+  }
+
+  // Called by UI
+  // Reports that the app's message carrying the ring reached the recipient.
+  ringDelivered(recipient: Buffer): void {
+    this._callManager.groupRingDelivered(this._clientId, recipient);
+  }
+
   // Called by UI
   resendMediaKeys(): void {
     this._callManager.resendMediaKeys(this._clientId);
@@ -2553,6 +3168,20 @@ export class GroupCall {
     );
   }
 
+  // Called by UI
+  // Lets RingRTC choose who to show in each tile and request their video.
+  // Pass null to go back to calling requestVideo directly.
+  setViewport(viewport: Viewport | null): void {
+    this._callManager.setViewport(this._clientId, viewport);
+  }
+
+  // Called by UI
+  // Asks everyone in the call to feature these devices, in order. An empty list clears it.
+  // Only admins may do this; the calling server ignores the request otherwise.
+  setSpotlight(demuxIds: Array<number>): void {
+    this._callManager.setSpotlight(this._clientId, demuxIds);
+  }
+
   // Called by UI
   approveUser(otherUserId: Buffer): void {
     this._callManager.approveUser(this._clientId, otherUserId);
@@ -2573,6 +3202,29 @@ export class GroupCall {
     this._callManager.blockClient(this._clientId, otherClientDemuxId);
   }
 
+  // Called by UI
+  muteClient(otherClientDemuxId: number): void {
+    this._callManager.muteClient(this._clientId, otherClientDemuxId);
+  }
+
+  // Called by UI
+  askClientToUnmute(otherClientDemuxId: number): void {
+    this._callManager.askClientToUnmute(this._clientId, otherClientDemuxId);
+  }
+
+  // Called by UI
+  // Only admins may lower another device's hand.
+  lowerHand(otherClientDemuxId: number): void {
+    this._callManager.lowerHand(this._clientId, otherClientDemuxId);
+  }
+
+  // Called by UI
+  // Calls on the first device in the speaker queue, lowering its hand, or
+  // clears the current speaker if no hands are raised. Only admins may do this.
+  callNextSpeaker(askToUnmute: boolean): void {
+    this._callManager.callNextSpeaker(this._clientId, askToUnmute);
+  }
+
   // Called by UI
   setGroupMembers(members: Array<GroupMemberInfo>): void {
     this._callManager.setGroupMembers(this._clientId, members);
@@ -2653,6 +3305,54 @@ export class GroupCall {
     this._observer.onRaisedHands(this, raisedHands);
   }
 
+  handleSpeakerQueueChanged(queue: SpeakerQueue): void {
+    this._observer.onSpeakerQueueChanged(this, queue);
+  }
+
+  handleForwardedVideoLayersChanged(layers: ForwardedVideoLayers): void {
+    this._observer.onForwardedVideoLayersChanged(this, layers);
+  }
+
+  handleChatMessage(message: ChatMessage): void {
+    this._observer.onChatMessage(this, message);
+  }
+
+  handlePollsChanged(polls: Array<Poll>): void {
+    this._observer.onPollsChanged(this, polls);
+  }
+
+  handleRingRecipientsChanged(
+    ringId: bigint,
+    recipients: Array<RingRecipient>
+  ): void {
+    this._observer.onRingRecipientsChanged(this, ringId, recipients);
+  }
+
+  handleSpotlightChanged(spotlight: Array<number>): void {
+    this._observer.onSpotlightChanged(this, spotlight);
+  }
+
+  handleLobbyChanged(lobby: LobbyInfo): void {
+    this._observer.onLobbyChanged(this, lobby);
+  }
+
+  handleBreakoutRoomsChanged(breakoutRooms: BreakoutRooms | undefined): void {
+    this._observer.onBreakoutRoomsChanged(this, breakoutRooms);
+  }
+
+  handleMutedByAdmin(adminDemuxId: number): void {
+    this._observer.onMutedByAdmin(this, adminDemuxId);
+  }
+
+  handleUnmuteRequested(adminDemuxId: number): void {
+    this._observer.onUnmuteRequested(this, adminDemuxId);
+  }
+
+  // The demux ID to show in each of the viewport's tiles, or undefined for an empty tile.
+  handleVideoLayoutChanged(tiles: Array<number | undefined>): void {
+    this._observer.onVideoLayoutChanged(this, tiles);
+  }
+
   // Called by Rust via RingRTC object
   handleRemoteDevicesChanged(
     remoteDeviceStates: Array<RemoteDeviceState>
@@ -2722,6 +3422,14 @@ export class GroupCall {
   handleSpeechEvent(event: SpeechEvent): void {
     this._observer.onSpeechEvent(this, event);
   }
+
+  handleHandoffStateReceived(state: HandoffState): void {
+    this._observer.onHandoffStateReceived(this, state);
+  }
+
+  handleHandedOff(newDemuxId: number): void {
+    this._observer.onHandedOff(this, newDemuxId);
+  }
 }
 
 // Implements VideoSource for use in CanvasVideoRenderer
@@ -2883,11 +3591,19 @@ export enum RingCancelReason {
 export interface CallManager {
   setConfig(config: Config): void;
   setSelfUuid(uuid: Buffer): void;
+  getPersistedState(): Buffer | null;
+  restorePersistedState(state: Buffer): void;
   createOutgoingCall(
     remoteUserId: UserId,
     isVideoCall: boolean,
     localDeviceId: DeviceId
   ): CallId;
+  pullCall(
+    remoteUserId: UserId,
+    replacedCallId: CallId,
+    isVideoCall: boolean,
+    localDeviceId: DeviceId
+  ): CallId;
   proceed(
     callId: CallId,
     iceServers: Array<IceServer>,
@@ -2903,6 +3619,9 @@ export interface CallManager {
     ringId: string,
     reason: RingCancelReason | null
   ): void;
+  acknowledgeGroupRing(groupId: GroupId, ringId: string): void;
+  ringScheduledCallLink(linkRootKey: Buffer, schedule: CallLinkSchedule): void;
+  cancelCallLinkRing(linkRootKey: Buffer, schedule: CallLinkSchedule): void;
   signalingMessageSent(callId: CallId): void;
   signalingMessageSendFailed(callId: CallId): void;
   setOutgoingAudioEnabled(enabled: boolean): void;
@@ -2987,6 +3706,7 @@ export interface CallManager {
   deleteGroupCallClient(clientId: GroupCallClientId): void;
   connect(clientId: GroupCallClientId): void;
   join(clientId: GroupCallClientId): void;
+  joinByHandoff(clientId: GroupCallClientId, fromDemuxId: number): void;
   leave(clientId: GroupCallClientId): void;
   disconnect(clientId: GroupCallClientId): void;
   setOutgoingAudioMuted(clientId: GroupCallClientId, muted: boolean): void;
@@ -2997,7 +3717,26 @@ export interface CallManager {
     isScreenShare: boolean
   ): void;
   groupRing(clientId: GroupCallClientId, recipient: Buffer | undefined): void;
+  groupRingAgain(clientId: GroupCallClientId, recipients: Array<Buffer>): void;
+  groupRingDelivered(clientId: GroupCallClientId, recipient: Buffer): void;
   groupReact(clientId: GroupCallClientId, value: string): void;
+  sendChatMessage(
+    clientId: GroupCallClientId,
+    text: string,
+    replyTo: ChatMessageId | undefined
+  ): void;
+  createPoll(
+    clientId: GroupCallClientId,
+    question: string,
+    options: Array<string>
+  ): void;
+  voteInPoll(
+    clientId: GroupCallClientId,
+    pollId: number,
+    optionIndex: number | undefined
+  ): void;
+  closePoll(clientId: GroupCallClientId, pollId: number): void;
+  sendLobbyMessage(clientId: GroupCallClientId, message: string): void;
   groupRaiseHand(clientId: GroupCallClientId, raise: boolean): void;
   resendMediaKeys(clientId: GroupCallClientId): void;
   setDataMode(clientId: GroupCallClientId, dataMode: DataMode): void;
@@ -3006,10 +3745,19 @@ export interface CallManager {
     resolutions: Array<VideoRequest>,
     activeSpeakerHeight: number
   ): void;
+  setViewport(clientId: GroupCallClientId, viewport: Viewport | null): void;
+  setSpotlight(clientId: GroupCallClientId, demuxIds: Array<number>): void;
   approveUser(clientId: GroupCallClientId, otherUserId: Buffer): void;
   denyUser(clientId: GroupCallClientId, otherUserId: Buffer): void;
   removeClient(clientId: GroupCallClientId, otherClientDemuxId: number): void;
   blockClient(clientId: GroupCallClientId, otherClientDemuxId: number): void;
+  muteClient(clientId: GroupCallClientId, otherClientDemuxId: number): void;
+  askClientToUnmute(
+    clientId: GroupCallClientId,
+    otherClientDemuxId: number
+  ): void;
+  lowerHand(clientId: GroupCallClientId, otherClientDemuxId: number): void;
+  callNextSpeaker(clientId: GroupCallClientId, askToUnmute: boolean): void;
   setGroupMembers(
     clientId: GroupCallClientId,
     members: Array<GroupMemberInfo>
@@ -3043,6 +3791,16 @@ export interface CallManager {
     callLinkPublicParams: Buffer,
     restrictions: number | undefined
   ): void;
+  createBreakoutRoomLink(
+    requestId: number,
+    sfuUrl: string,
+    createCredentialPresentation: Buffer,
+    linkRootKey: Buffer,
+    roomIndex: number,
+    adminPasskey: Buffer,
+    callLinkPublicParams: Buffer,
+    restrictions: number | undefined
+  ): void;
   updateCallLink(
     requestId: number,
     sfuUrl: string,
@@ -3051,7 +3809,8 @@ export interface CallManager {
     adminPasskey: Buffer,
     newName: string | undefined,
     newRestrictions: number | undefined,
-    newRevoked: boolean | undefined
+    newRevoked: boolean | undefined,
+    newSchedule: CallLinkSchedule | null | undefined
   ): void;
   deleteCallLink(
     requestId: number,
@@ -3093,7 +3852,8 @@ export interface CallManagerCallbacks {
     remoteUserId: UserId,
     callId: CallId,
     endedReason: CallEndedReason,
-    ageSec: number
+    ageSec: number,
+    acceptedOnDeviceId: DeviceId | null
   ): void;
   onRemoteAudioEnabled(remoteUserId: UserId, enabled: boolean): void;
   onRemoteVideoEnabled(remoteUserId: UserId, enabled: boolean): void;
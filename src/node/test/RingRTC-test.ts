@@ -9,19 +9,27 @@ import { assert, expect, use } from 'chai';
 import chaiAsPromised from 'chai-as-promised';
 import { createHash, randomBytes } from 'crypto';
 import {
+  BreakoutRooms,
   CallEndedReason,
   CallLinkRestrictions,
   CallLinkRootKey,
   CallState,
   CallingMessage,
+  ChatMessage,
+  ForwardedVideoLayers,
   GroupCall,
   GroupCallEndReason,
   GroupCallKind,
   GroupMemberInfo,
+  HandoffState,
   HttpMethod,
+  LobbyInfo,
   OfferType,
   PeekStatusCodes,
+  Poll,
   Reaction,
+  RingRecipient,
+  SpeakerQueue,
   SpeechEvent,
   RingRTC,
   callIdFromEra,
@@ -906,9 +914,35 @@ describe('RingRTC', () => {
       onLowBandwidthForVideo(_call: GroupCall, _recovered: boolean) {}
       onReactions(_call: GroupCall, _reactions: Array<Reaction>) {}
       onRaisedHands(_call: GroupCall, _raisedHands: Array<number>) {}
+      onSpeakerQueueChanged(_call: GroupCall, _queue: SpeakerQueue) {}
+      onForwardedVideoLayersChanged(
+        _call: GroupCall,
+        _layers: ForwardedVideoLayers
+      ) {}
+      onChatMessage(_call: GroupCall, _message: ChatMessage) {}
+      onPollsChanged(_call: GroupCall, _polls: Array<Poll>) {}
+      onRingRecipientsChanged(
+        _call: GroupCall,
+        _ringId: bigint,
+        _recipients: Array<RingRecipient>
+      ) {}
+      onVideoLayoutChanged(
+        _call: GroupCall,
+        _tiles: Array<number | undefined>
+      ) {}
+      onSpotlightChanged(_call: GroupCall, _spotlight: Array<number>) {}
+      onMutedByAdmin(_call: GroupCall, _adminDemuxId: number) {}
+      onLobbyChanged(_call: GroupCall, _lobby: LobbyInfo) {}
+      onBreakoutRoomsChanged(
+        _call: GroupCall,
+        _breakoutRooms: BreakoutRooms | undefined
+      ) {}
+      onUnmuteRequested(_call: GroupCall, _adminDemuxId: number) {}
       onPeekChanged(_call: GroupCall) {}
       onEnded(_call: GroupCall, _reason: GroupCallEndReason) {}
       onSpeechEvent(_call: GroupCall, _event: SpeechEvent) {}
+      onHandoffStateReceived(_call: GroupCall, _state: HandoffState) {}
+      onHandedOff(_call: GroupCall, _newDemuxId: number) {}
       /* eslint-enable @typescript-eslint/no-empty-function */
     }
 
@@ -6,7 +6,7 @@
 //! Android Platform Interface.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt,
     sync::Arc,
     time::{Duration, SystemTime},
@@ -31,7 +31,7 @@ use crate::{
         signaling,
     },
     lite::{
-        call_links::{CallLinkRestrictions, CallLinkState, Empty},
+        call_links::{BreakoutRooms, CallLinkRestrictions, CallLinkRootKey, CallLinkState, Empty},
         http, sfu,
         sfu::{DemuxId, PeekInfo, PeekResult, UserId},
     },
@@ -43,13 +43,20 @@ use crate::{
 };
 
 const RINGRTC_PACKAGE: &str = jni_class_name!(org.signal.ringrtc);
+const BREAKOUT_ROOM_ASSIGNMENT_CLASS: &str =
+    jni_class_name!(org.signal.ringrtc.GroupCall::BreakoutRoomAssignment);
 const CALL_LINK_STATE_CLASS: &str = jni_class_name!(org.signal.ringrtc.CallLinkState);
 const CALL_MANAGER_CLASS: &str = "CallManager";
 const GROUP_CALL_CLASS: &str = "GroupCall";
 const HTTP_HEADER_CLASS: &str = jni_class_name!(org.signal.ringrtc.HttpHeader);
 const HTTP_RESULT_CLASS: &str = jni_class_name!(org.signal.ringrtc.CallManager::HttpResult);
+const INCOMING_VIDEO_HEIGHT_CLASS: &str =
+    jni_class_name!(org.signal.ringrtc.GroupCall::IncomingVideoHeight);
 const PEEK_INFO_CLASS: &str = jni_class_name!(org.signal.ringrtc.PeekInfo);
+const POLL_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::Poll);
+const POLL_OPTION_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::PollOption);
 const REACTION_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::Reaction);
+const RING_RECIPIENT_CLASS: &str = jni_class_name!(org.signal.ringrtc.GroupCall::RingRecipient);
 const REMOTE_DEVICE_STATE_CLASS: &str =
     jni_class_name!(org.signal.ringrtc.GroupCall::RemoteDeviceState);
 const RECEIVED_AUDIO_LEVEL_CLASS: &str =
@@ -889,6 +896,28 @@ impl Platform for AndroidPlatform {
         self.on_event(remote_peer, call_id, ApplicationEvent::ReceivedOfferExpired)
     }
 
+    fn on_accepted_on_another_device(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        _call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        info!("on_accepted_on_another_device(): device_id: {}", device_id);
+
+        let env = &mut self.java_env()?;
+
+        jni_call_method(
+            env,
+            self.jni_call_manager.as_obj(),
+            "onAcceptedOnAnotherDevice",
+            jni_args!((
+                remote_peer.as_obj() => org.signal.ringrtc.Remote,
+                device_id as jint => int,
+            ) -> void),
+        )?;
+        Ok(())
+    }
+
     fn on_call_concluded(&self, remote_peer: &Self::AppRemotePeer, _call_id: CallId) -> Result<()> {
         info!("on_call_concluded():");
 
@@ -955,6 +984,50 @@ impl Platform for AndroidPlatform {
         }
     }
 
+    fn call_link_ring_update(
+        &self,
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender: UserId,
+        update: group_call::RingUpdate,
+    ) {
+        info!("call_link_ring_update():");
+
+        if let Ok(env) = &mut self.java_env() {
+            let root_key = match env.byte_array_from_slice(&root_key.bytes()) {
+                Ok(slice) => JObject::from(slice),
+                Err(error) => {
+                    error!("{:?}", error);
+                    return;
+                }
+            };
+            let ring_id = jlong::from(ring_id);
+            let sender = match env.byte_array_from_slice(&sender) {
+                Ok(slice) => JObject::from(slice),
+                Err(error) => {
+                    error!("{:?}", error);
+                    return;
+                }
+            };
+            let update = update as jint;
+
+            let result = jni_call_method(
+                env,
+                self.jni_call_manager.as_obj(),
+                "callLinkRingUpdate",
+                jni_args!((
+                    root_key => [byte],
+                    ring_id => long,
+                    sender => [byte],
+                    update => int,
+                ) -> void),
+            );
+            if result.is_err() {
+                error!("jni_call_method: {:?}", result.err());
+            }
+        }
+    }
+
     fn request_membership_proof(&self, client_id: group_call::ClientId) {
         info!("request_membership_proof():");
         request_update_via_jni!(self, "requestMembershipProof", client_id);
@@ -1261,6 +1334,693 @@ impl Platform for AndroidPlatform {
         }
     }
 
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layers: group_call::ForwardedVideoLayers,
+    ) {
+        info!(
+            "handle_forwarded_video_layers_changed(): client_id: {}, outgoing: {}, incoming: {:?}",
+            client_id, layers.outgoing_layer_count, layers.incoming_heights,
+        );
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + N elements.
+            let capacity = (5 + 1 + layers.incoming_heights.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<GroupCall.IncomingVideoHeight>
+                let incoming_video_height_class =
+                    self.class_cache.get_class(INCOMING_VIDEO_HEIGHT_CLASS)?;
+
+                let list = jni_new_arraylist(env, layers.incoming_heights.len())?;
+                let incoming_heights_list = env.get_list(&list)?;
+
+                for (demux_id, height) in layers.incoming_heights {
+                    let args = jni_args!((
+                        demux_id as jlong => long,
+                        height as jint => int,
+                    ) -> void);
+
+                    let incoming_height_obj =
+                        match env.new_object(incoming_video_height_class, args.sig, &args.args) {
+                            Ok(v) => v,
+                            Err(error) => {
+                                error!("jni_incoming_video_height: {:?}", error);
+                                continue;
+                            }
+                        };
+
+                    let result = incoming_heights_list.add(env, &incoming_height_obj);
+                    if result.is_err() {
+                        error!("jni_incoming_video_height.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleForwardedVideoLayersChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        layers.outgoing_layer_count as jint => int,
+                        incoming_heights_list => java.util.List,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_forwarded_video_layers_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_speaker_queue_changed(
+        &self,
+        client_id: group_call::ClientId,
+        queue: group_call::SpeakerQueue,
+    ) {
+        info!(
+            "handle_speaker_queue_changed(): client_id: {}, raised_hands: {:?}, current_speaker: {:?}",
+            client_id, queue.raised_hands, queue.current_speaker,
+        );
+
+        // A negative demux ID means no one has been called on.
+        let current_speaker = queue
+            .current_speaker
+            .map_or(-1, |current_speaker| current_speaker as jlong);
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + N elements.
+            let capacity = (5 + 1 + queue.raised_hands.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<Long>
+                let long_class = self
+                    .class_cache
+                    .get_class(jni_class_name!(java.lang.Long))?;
+
+                let list = jni_new_arraylist(env, queue.raised_hands.len())?;
+                let raised_hands_list = env.get_list(&list)?;
+
+                for raised_hand in queue.raised_hands {
+                    let args = jni_args!((
+                        raised_hand as jlong => long,
+                    ) -> void);
+
+                    let raised_hand_obj = match env.new_object(long_class, args.sig, &args.args) {
+                        Ok(v) => v,
+                        Err(error) => {
+                            error!("jni_raised_hands: {:?}", error);
+                            continue;
+                        }
+                    };
+
+                    let result = raised_hands_list.add(env, &raised_hand_obj);
+                    if result.is_err() {
+                        error!("jni_raised_hands.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleSpeakerQueueChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        raised_hands_list => java.util.List,
+                        current_speaker => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_speaker_queue_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_breakout_rooms_changed(
+        &self,
+        client_id: group_call::ClientId,
+        breakout_rooms: Option<BreakoutRooms>,
+    ) {
+        info!(
+            "handle_breakout_rooms_changed(): client_id: {}, room_count: {:?}",
+            client_id,
+            breakout_rooms
+                .as_ref()
+                .map(|breakout_rooms| breakout_rooms.room_count),
+        );
+
+        // Zero rooms means the breakout has ended, and a negative time means no countdown.
+        let (room_count, assignments, return_at_epoch_millis) = match breakout_rooms {
+            None => (0, HashMap::new(), -1),
+            Some(breakout_rooms) => (
+                breakout_rooms.room_count,
+                breakout_rooms.assignments,
+                breakout_rooms.return_at.map_or(-1, |return_at| {
+                    return_at
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as jlong
+                }),
+            ),
+        };
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + N elements.
+            let capacity = (5 + 1 + assignments.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<GroupCall.BreakoutRoomAssignment>
+                let assignment_class =
+                    self.class_cache.get_class(BREAKOUT_ROOM_ASSIGNMENT_CLASS)?;
+
+                let list = jni_new_arraylist(env, assignments.len())?;
+                let assignments_list = env.get_list(&list)?;
+
+                for (demux_id, room_index) in assignments {
+                    let args = jni_args!((
+                        demux_id as jlong => long,
+                        room_index as jint => int,
+                    ) -> void);
+
+                    let assignment_obj =
+                        match env.new_object(assignment_class, args.sig, &args.args) {
+                            Ok(v) => v,
+                            Err(error) => {
+                                error!("jni_breakout_room_assignment: {:?}", error);
+                                continue;
+                            }
+                        };
+
+                    let result = assignments_list.add(env, &assignment_obj);
+                    if result.is_err() {
+                        error!("jni_breakout_room_assignment.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleBreakoutRoomsChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        room_count as jint => int,
+                        assignments_list => java.util.List,
+                        return_at_epoch_millis => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_breakout_rooms_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_chat_message(
+        &self,
+        client_id: group_call::ClientId,
+        message: group_call::ChatMessage,
+    ) {
+        trace!(
+            "handle_chat_message(): client_id: {}, id: {:?}",
+            client_id,
+            message.id,
+        );
+
+        // Seqnums start at 1, so zero means the message isn't a reply.
+        let reply_to = message.reply_to.unwrap_or(group_call::ChatMessageId {
+            demux_id: 0,
+            seqnum: 0,
+        });
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1).
+            let capacity = 6;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                let jni_text = JObject::from(env.new_string(message.text)?);
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleChatMessage",
+                    jni_args!((
+                        client_id as jlong => long,
+                        message.id.demux_id as jlong => long,
+                        message.id.seqnum as jlong => long,
+                        jni_text => java.lang.String,
+                        reply_to.demux_id as jlong => long,
+                        reply_to.seqnum as jlong => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_chat_message: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_polls_changed(&self, client_id: group_call::ClientId, polls: Vec<group_call::Poll>) {
+        info!(
+            "handle_polls_changed(): client_id: {}, polls: {}",
+            client_id,
+            polls.len(),
+        );
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + elements (3 per poll + 2 per option).
+            let option_count: usize = polls.iter().map(|poll| poll.options.len()).sum();
+            let capacity = (5 + 1 + polls.len() * 3 + option_count * 2) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<GroupCall.Poll>
+                let poll_class = self.class_cache.get_class(POLL_CLASS)?;
+                let poll_option_class = self.class_cache.get_class(POLL_OPTION_CLASS)?;
+
+                let list = jni_new_arraylist(env, polls.len())?;
+                let polls_list = env.get_list(&list)?;
+
+                for poll in polls {
+                    let options = jni_new_arraylist(env, poll.options.len())?;
+                    let options_list = env.get_list(&options)?;
+                    for option in poll.options {
+                        let jni_text = JObject::from(env.new_string(option.text)?);
+                        let args = jni_args!((
+                            jni_text => java.lang.String,
+                            option.votes as jint => int,
+                        ) -> void);
+
+                        let option_obj =
+                            match env.new_object(poll_option_class, args.sig, &args.args) {
+                                Ok(v) => v,
+                                Err(error) => {
+                                    error!("jni_poll_option: {:?}", error);
+                                    continue;
+                                }
+                            };
+
+                        let result = options_list.add(env, &option_obj);
+                        if result.is_err() {
+                            error!("jni_poll_option.add: {:?}", result.err());
+                            continue;
+                        }
+                    }
+
+                    // A negative index means the local device hasn't voted.
+                    let own_vote = poll.own_vote.map_or(-1, |own_vote| own_vote as jint);
+                    let jni_question = JObject::from(env.new_string(poll.question)?);
+                    let args = jni_args!((
+                        poll.id as jlong => long,
+                        poll.creator as jlong => long,
+                        jni_question => java.lang.String,
+                        options_list => java.util.List,
+                        own_vote => int,
+                        poll.closed => boolean,
+                    ) -> void);
+
+                    let poll_obj = match env.new_object(poll_class, args.sig, &args.args) {
+                        Ok(v) => v,
+                        Err(error) => {
+                            error!("jni_poll: {:?}", error);
+                            continue;
+                        }
+                    };
+
+                    let result = polls_list.add(env, &poll_obj);
+                    if result.is_err() {
+                        error!("jni_poll.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handlePollsChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        polls_list => java.util.List,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_polls_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_lobby_changed(&self, client_id: group_call::ClientId, lobby: group_call::LobbyInfo) {
+        info!(
+            "handle_lobby_changed(): client_id: {}, participant_count: {}, position: {:?}",
+            client_id, lobby.participant_count, lobby.position,
+        );
+
+        // A negative position means the position in the queue is unknown.
+        let position = lobby.position.map_or(-1, |position| position as jint);
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (2) + N elements.
+            let capacity = (5 + 2 + lobby.admins.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<byte[]>
+                let list = jni_new_arraylist(env, lobby.admins.len())?;
+                let admins_list = env.get_list(&list)?;
+
+                for admin in &lobby.admins {
+                    let jni_admin = match env.byte_array_from_slice(admin) {
+                        Ok(v) => JObject::from(v),
+                        Err(error) => {
+                            error!("jni_admin: {:?}", error);
+                            continue;
+                        }
+                    };
+
+                    let result = admins_list.add(env, &jni_admin);
+                    if result.is_err() {
+                        error!("jni_admin.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let jni_message = match lobby.message {
+                    None => JObject::null(),
+                    Some(message) => JObject::from(env.new_string(message)?),
+                };
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleLobbyChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        lobby.participant_count as jint => int,
+                        admins_list => java.util.List,
+                        position => int,
+                        jni_message => java.lang.String,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_lobby_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_muted_by_admin(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!(
+            "handle_muted_by_admin(): client_id: {}, admin_demux_id: {}",
+            client_id, admin_demux_id
+        );
+
+        if let Ok(env) = &mut self.java_env() {
+            // Set a frame capacity of min (5).
+            let capacity = 5;
+            let _ = env.with_local_frame(capacity, |env| -> Result<()> {
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleMutedByAdmin",
+                    jni_args!((
+                        client_id as jlong => long,
+                        admin_demux_id as jlong => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            });
+        }
+    }
+
+    fn handle_unmute_requested(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!(
+            "handle_unmute_requested(): client_id: {}, admin_demux_id: {}",
+            client_id, admin_demux_id
+        );
+
+        if let Ok(env) = &mut self.java_env() {
+            // Set a frame capacity of min (5).
+            let capacity = 5;
+            let _ = env.with_local_frame(capacity, |env| -> Result<()> {
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleUnmuteRequested",
+                    jni_args!((
+                        client_id as jlong => long,
+                        admin_demux_id as jlong => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            });
+        }
+    }
+
+    fn handle_spotlight_changed(&self, client_id: group_call::ClientId, spotlight: Vec<DemuxId>) {
+        info!(
+            "handle_spotlight_changed(): client_id: {}, spotlight: {:?}",
+            client_id, spotlight,
+        );
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + N elements.
+            let capacity = (5 + 1 + spotlight.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<Long>
+                let long_class = self
+                    .class_cache
+                    .get_class(jni_class_name!(java.lang.Long))?;
+
+                let list = jni_new_arraylist(env, spotlight.len())?;
+                let spotlight_list = env.get_list(&list)?;
+
+                for demux_id in spotlight {
+                    let args = jni_args!((
+                        demux_id as jlong => long,
+                    ) -> void);
+
+                    let demux_id_obj = match env.new_object(long_class, args.sig, &args.args) {
+                        Ok(v) => v,
+                        Err(error) => {
+                            error!("jni_spotlight: {:?}", error);
+                            continue;
+                        }
+                    };
+
+                    let result = spotlight_list.add(env, &demux_id_obj);
+                    if result.is_err() {
+                        error!("jni_spotlight.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleSpotlightChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        spotlight_list => java.util.List,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_spotlight_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_video_layout_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layout: group_call::VideoLayout,
+    ) {
+        info!(
+            "handle_video_layout_changed(): client_id: {}, tiles: {:?}",
+            client_id, layout.tiles,
+        );
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + N elements.
+            let capacity = (5 + 1 + layout.tiles.len()) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<Long>, with null for empty tiles
+                let long_class = self
+                    .class_cache
+                    .get_class(jni_class_name!(java.lang.Long))?;
+
+                let list = jni_new_arraylist(env, layout.tiles.len())?;
+                let tiles_list = env.get_list(&list)?;
+
+                for tile in layout.tiles {
+                    let tile_obj = match tile {
+                        Some(demux_id) => {
+                            let args = jni_args!((
+                                demux_id as jlong => long,
+                            ) -> void);
+                            match env.new_object(long_class, args.sig, &args.args) {
+                                Ok(v) => v,
+                                Err(error) => {
+                                    error!("jni_video_layout: {:?}", error);
+                                    continue;
+                                }
+                            }
+                        }
+                        None => JObject::null(),
+                    };
+
+                    let result = tiles_list.add(env, &tile_obj);
+                    if result.is_err() {
+                        error!("jni_video_layout.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleVideoLayoutChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        tiles_list => java.util.List,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_video_layout_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_ring_recipients_changed(
+        &self,
+        client_id: group_call::ClientId,
+        ring_id: group_call::RingId,
+        recipients: HashMap<UserId, group_call::RingRecipientStatus>,
+    ) {
+        info!(
+            "handle_ring_recipients_changed(): client_id: {}, ring_id: {}",
+            client_id, ring_id,
+        );
+
+        if let Ok(mut env) = self.java_env() {
+            // Set a frame capacity of min (5) + objects (1) + elements (N * 2 per recipient).
+            let capacity = (5 + 1 + recipients.len() * 2) as i32;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                // create Java List<GroupCall.RingRecipient>
+                let recipient_class = self.class_cache.get_class(RING_RECIPIENT_CLASS)?;
+
+                let list = jni_new_arraylist(env, recipients.len())?;
+                let recipients_list = env.get_list(&list)?;
+
+                for (user_id, status) in recipients {
+                    let jni_user_id = JObject::from(env.byte_array_from_slice(&user_id)?);
+                    let args = jni_args!((
+                        jni_user_id => [byte],
+                        status as jint => int,
+                    ) -> void);
+
+                    let recipient_obj = match env.new_object(recipient_class, args.sig, &args.args)
+                    {
+                        Ok(v) => v,
+                        Err(error) => {
+                            error!("jni_ring_recipient: {:?}", error);
+                            continue;
+                        }
+                    };
+
+                    let result = recipients_list.add(env, &recipient_obj);
+                    if result.is_err() {
+                        error!("jni_ring_recipient.add: {:?}", result.err());
+                        continue;
+                    }
+                }
+
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleRingRecipientsChanged",
+                    jni_args!((
+                        client_id as jlong => long,
+                        jlong::from(ring_id) => long,
+                        recipients_list => java.util.List,
+                    ) -> void),
+                );
+
+                Ok(())
+            }) {
+                error!("handle_ring_recipients_changed: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_handoff_state_received(
+        &self,
+        client_id: group_call::ClientId,
+        state: group_call::HandoffState,
+    ) {
+        info!(
+            "handle_handoff_state_received(): client_id: {}, state: {:?}",
+            client_id, state
+        );
+
+        if let Ok(env) = &mut self.java_env() {
+            // Set a frame capacity of min (5) + objects (2).
+            let capacity = 7;
+            if let Err(e) = env.with_local_frame(capacity, |env| -> Result<()> {
+                let jni_audio_muted = self.get_optional_boolean_object(env, state.audio_muted)?;
+                let jni_video_muted = self.get_optional_boolean_object(env, state.video_muted)?;
+
+                jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleHandoffStateReceived",
+                    jni_args!((
+                        client_id as jlong => long,
+                        jni_audio_muted => java.lang.Boolean,
+                        jni_video_muted => java.lang.Boolean,
+                    ) -> void),
+                )?;
+
+                Ok(())
+            }) {
+                error!("handle_handoff_state_received: {:?}", e);
+            }
+        }
+    }
+
+    fn handle_handed_off(&self, client_id: group_call::ClientId, new_demux_id: DemuxId) {
+        info!(
+            "handle_handed_off(): client_id: {}, new_demux_id: {}",
+            client_id, new_demux_id
+        );
+
+        if let Ok(env) = &mut self.java_env() {
+            // Set a frame capacity of min (5).
+            let capacity = 5;
+            let _ = env.with_local_frame(capacity, |env| -> Result<()> {
+                let _ = jni_call_method(
+                    env,
+                    self.jni_call_manager.as_obj(),
+                    "handleHandedOff",
+                    jni_args!((
+                        client_id as jlong => long,
+                        new_demux_id as jlong => long,
+                    ) -> void),
+                );
+
+                Ok(())
+            });
+        }
+    }
+
     fn handle_join_state_changed(
         &self,
         client_id: group_call::ClientId,
@@ -1556,11 +2316,16 @@ impl AndroidPlatform {
             jni_class_name!(org.signal.ringrtc.GroupCall::GroupCallEndReason),
             jni_class_name!(org.signal.ringrtc.GroupCall::JoinState),
             jni_class_name!(org.signal.ringrtc.GroupCall::SpeechEvent),
+            BREAKOUT_ROOM_ASSIGNMENT_CLASS,
             CALL_LINK_STATE_CLASS,
             HTTP_HEADER_CLASS,
             HTTP_RESULT_CLASS,
+            INCOMING_VIDEO_HEIGHT_CLASS,
             PEEK_INFO_CLASS,
+            POLL_CLASS,
+            POLL_OPTION_CLASS,
             REACTION_CLASS,
+            RING_RECIPIENT_CLASS,
             REMOTE_DEVICE_STATE_CLASS,
             RECEIVED_AUDIO_LEVEL_CLASS,
             jni_class_name!(java.lang.Boolean),
@@ -1946,11 +2711,27 @@ impl AndroidPlatform {
                     .unwrap_or_default()
                     .as_secs();
 
+                // A zero duration tells Java there is no schedule.
+                let (schedule_start_epoch_seconds, schedule_duration_seconds, flattened_invitees) =
+                    match &state.schedule {
+                        Some(schedule) => (
+                            schedule.start_epoch_seconds(),
+                            schedule.duration.as_secs(),
+                            schedule.invitees.concat(),
+                        ),
+                        None => (0, 0, vec![]),
+                    };
+                let flattened_invitees =
+                    JObject::from(env.byte_array_from_slice(&flattened_invitees)?);
+
                 let args = jni_args!((
                     name_object => java.lang.String,
                     raw_restrictions => int,
                     state.revoked => boolean,
                     expiration_in_epoch_seconds as jlong => long,
+                    schedule_start_epoch_seconds as jlong => long,
+                    schedule_duration_seconds as jlong => long,
+                    flattened_invitees => [byte],
                 ) -> void);
 
                 let object = env.new_object(call_link_state_class, args.sig, &args.args);
@@ -7,6 +7,7 @@ use std::borrow::Cow;
 
 use jni::{
     objects::{JByteArray, JClass, JObject, JString},
+    sys::jint,
     JNIEnv,
 };
 
@@ -105,6 +106,28 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallLinkRootKey_nativeDeriveRoo
     })
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallLinkRootKey_nativeDeriveBreakoutRoomKey<
+    'local,
+>(
+    mut env: JNIEnv<'local>,
+    _class: JClass,
+    key_bytes: JByteArray,
+    room_index: jint,
+) -> JByteArray<'local> {
+    try_scoped(|| {
+        let key_bytes = env.convert_byte_array(key_bytes)?;
+        let key = CallLinkRootKey::try_from(key_bytes.as_slice())?;
+        let room_key = key.derive_breakout_room_key(room_index as u32);
+        Ok(env.byte_array_from_slice(&room_key.bytes())?)
+    })
+    .unwrap_or_else(|e| {
+        error::throw_error(&mut env, e);
+        JByteArray::default()
+    })
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallLinkRootKey_nativeToFormattedString<'local>(
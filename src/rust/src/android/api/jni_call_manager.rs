@@ -111,6 +111,42 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcSetSelfUuid(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcGetPersistedState<'local>(
+    mut env: JNIEnv<'local>,
+    _object: JObject,
+    call_manager: jlong,
+) -> JObject<'local> {
+    match call_manager::get_persisted_state(&mut env, call_manager as *mut AndroidCallManager) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+            JObject::default()
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcRestorePersistedState(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    state: JByteArray,
+) {
+    match call_manager::restore_persisted_state(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        state,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCall(
@@ -135,6 +171,32 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCall(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcPullCall(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    jni_remote: JObject,
+    replaced_call_id: jlong,
+    call_media_type: jint,
+    local_device: jint,
+) {
+    match call_manager::pull_call(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        jni_remote,
+        replaced_call_id,
+        CallMediaType::from_i32(call_media_type),
+        local_device as DeviceId,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcProceed(
@@ -238,6 +300,80 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCancelGroupR
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcAcknowledgeGroupRing(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    group_id: JByteArray,
+    ring_id: jlong,
+) {
+    match call_manager::acknowledge_group_ring(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        group_id,
+        ring_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcRingScheduledCallLink(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    root_key: JByteArray,
+    start_epoch_seconds: jlong,
+    duration_seconds: jlong,
+    flattened_invitees: JByteArray,
+) {
+    match call_manager::ring_scheduled_call_link(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        root_key,
+        start_epoch_seconds,
+        duration_seconds,
+        flattened_invitees,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCancelCallLinkRing(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    root_key: JByteArray,
+    start_epoch_seconds: jlong,
+    duration_seconds: jlong,
+    flattened_invitees: JByteArray,
+) {
+    match call_manager::cancel_call_link_ring(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        root_key,
+        start_epoch_seconds,
+        duration_seconds,
+        flattened_invitees,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcReceivedAnswer(
@@ -649,6 +785,40 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCreateCallLi
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcCreateBreakoutRoomLink(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    sfu_url: JString,
+    create_credential_presentation: JByteArray,
+    root_key: JByteArray,
+    room_index: jint,
+    admin_passkey: JByteArray,
+    call_link_public_params: JByteArray,
+    restrictions: jint,
+    request_id: jlong,
+) {
+    match call_manager::create_breakout_room_link(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        sfu_url,
+        create_credential_presentation,
+        root_key,
+        room_index,
+        admin_passkey,
+        call_link_public_params,
+        restrictions,
+        request_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcUpdateCallLink(
@@ -662,6 +832,9 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcUpdateCallLi
     new_name: JString,
     new_restrictions: jint,
     new_revoked: jint,
+    new_schedule_start_epoch_seconds: jlong,
+    new_schedule_duration_seconds: jlong,
+    new_schedule_invitees: JByteArray,
     request_id: jlong,
 ) {
     match call_manager::update_call_link(
@@ -674,6 +847,9 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_CallManager_ringrtcUpdateCallLi
         new_name,
         new_restrictions,
         new_revoked,
+        new_schedule_start_epoch_seconds,
+        new_schedule_duration_seconds,
+        new_schedule_invitees,
         request_id,
     ) {
         Ok(v) => v,
@@ -892,6 +1068,27 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcJoin(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcJoinByHandoff(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    from_demux_id: jlong,
+) {
+    match call_manager::join_by_handoff(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        from_demux_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcLeave(
@@ -994,6 +1191,50 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRing(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRingAgain(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    flattened_recipients: JByteArray,
+) {
+    match call_manager::group_ring_again(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        flattened_recipients,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRingDelivered(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    recipient: JByteArray,
+) {
+    match call_manager::group_ring_delivered(
+        &env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        recipient,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcResendMediaKeys(
@@ -1058,6 +1299,54 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRequestVideo(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSetSpotlight(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    jni_demux_ids: JObject,
+) {
+    match call_manager::set_spotlight(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        jni_demux_ids,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSetViewport(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    mode: jint,
+    jni_tiles: JObject,
+    jni_pinned: JObject,
+) {
+    match call_manager::set_viewport(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        mode,
+        jni_tiles,
+        jni_pinned,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcApproveUser(
@@ -1144,6 +1433,48 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcBlockClient(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcMuteClient(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    other_client_demux_id: jlong,
+) {
+    match call_manager::mute_client(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        other_client_demux_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcAskClientToUnmute(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    other_client_demux_id: jlong,
+) {
+    match call_manager::ask_client_to_unmute(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        other_client_demux_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSetGroupMembers(
@@ -1188,6 +1519,122 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSetMembershipP
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSendChatMessage(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    text: JString,
+    reply_to_demux_id: jlong,
+    reply_to_seqnum: jlong,
+) {
+    match call_manager::send_chat_message(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        text,
+        reply_to_demux_id,
+        reply_to_seqnum,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcCreatePoll(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    question: JString,
+    options: JObject,
+) {
+    match call_manager::create_poll(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        question,
+        options,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcVoteInPoll(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    poll_id: jlong,
+    option_index: jint,
+) {
+    match call_manager::vote_in_poll(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        poll_id,
+        option_index,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcClosePoll(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    poll_id: jlong,
+) {
+    match call_manager::close_poll(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        poll_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcSendLobbyMessage(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    message: JString,
+) {
+    match call_manager::send_lobby_message(
+        &mut env,
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        message,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcReact(
@@ -1210,6 +1657,48 @@ pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcReact(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcLowerHand(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    other_client_demux_id: jlong,
+) {
+    match call_manager::lower_hand(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        other_client_demux_id,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcCallNextSpeaker(
+    mut env: JNIEnv,
+    _object: JObject,
+    call_manager: jlong,
+    client_id: jlong,
+    ask_to_unmute: bool,
+) {
+    match call_manager::call_next_speaker(
+        call_manager as *mut AndroidCallManager,
+        client_id as group_call::ClientId,
+        ask_to_unmute,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            error::throw_error(&mut env, e);
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub unsafe extern "C" fn Java_org_signal_ringrtc_GroupCall_ringrtcRaiseHand(
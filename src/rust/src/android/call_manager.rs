@@ -5,7 +5,13 @@
 
 //! Android CallManager Interface.
 
-use std::{borrow::Cow, convert::TryFrom, panic, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    panic,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use jni::{
     objects::{GlobalRef, JByteArray, JClass, JObject, JString},
@@ -24,7 +30,7 @@ use crate::{
     },
     common::{CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result},
     core::{
-        call_manager::CallManager,
+        call_manager::{CallManager, PersistedState},
         connection::Connection,
         group_call, signaling,
         util::{ptr_as_box, ptr_as_mut},
@@ -168,6 +174,31 @@ pub fn set_self_uuid(
     call_manager.set_self_uuid(env.convert_byte_array(uuid)?)
 }
 
+/// Application request for the state to save across app restarts, or null if there's
+/// nothing worth saving.
+pub fn get_persisted_state<'a>(
+    env: &mut JNIEnv<'a>,
+    call_manager: *mut AndroidCallManager,
+) -> Result<JObject<'a>> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let state = call_manager.persisted_state()?;
+    if state.is_empty() {
+        return Ok(JObject::null());
+    }
+    Ok(JObject::from(env.byte_array_from_slice(&state.to_bytes())?))
+}
+
+/// Application notification to restore state saved before the app restarted.
+pub fn restore_persisted_state(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    state: JByteArray,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let state = PersistedState::from_bytes(&env.convert_byte_array(state)?)?;
+    call_manager.restore_persisted_state(state)
+}
+
 /// Application notification to start a new call
 pub fn call(
     env: &JNIEnv,
@@ -181,6 +212,26 @@ pub fn call(
     call_manager.call(app_remote_peer, call_media_type, local_device_id)
 }
 
+/// Application notification to take over a call accepted on another device
+pub fn pull_call(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    jni_remote: JObject,
+    replaced_call_id: jlong,
+    call_media_type: CallMediaType,
+    local_device_id: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let app_remote_peer = env.new_global_ref(jni_remote)?;
+    call_manager.pull_call(
+        app_remote_peer,
+        CallId::random(),
+        CallId::from(replaced_call_id),
+        call_media_type,
+        local_device_id,
+    )
+}
+
 /// Application notification to proceed with a new call
 pub fn proceed(
     env: &JNIEnv,
@@ -246,6 +297,64 @@ pub fn cancel_group_ring(
     )
 }
 
+/// Application request to tell the sender of a group ring that this device is ringing
+pub fn acknowledge_group_ring(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    group_id: JByteArray,
+    ring_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.acknowledge_group_ring(
+        env.convert_byte_array(group_id)?,
+        group_call::RingId::from(ring_id),
+    )
+}
+
+/// Application request to ring the invitees of a scheduled call link.
+pub fn ring_scheduled_call_link(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    root_key: JByteArray,
+    start_epoch_seconds: jlong,
+    duration_seconds: jlong,
+    flattened_invitees: JByteArray,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+
+    let root_key =
+        call_links::CallLinkRootKey::try_from(env.convert_byte_array(root_key)?.as_slice())?;
+    let schedule = call_links::CallLinkSchedule::from_flattened(
+        start_epoch_seconds as u64,
+        duration_seconds as u64,
+        &env.convert_byte_array(flattened_invitees)?,
+    )?;
+    call_manager.ring_scheduled_call_link(&root_key, &schedule, SystemTime::now())?;
+    Ok(())
+}
+
+/// Application request to stop the ring of a scheduled call link.
+pub fn cancel_call_link_ring(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    root_key: JByteArray,
+    start_epoch_seconds: jlong,
+    duration_seconds: jlong,
+    flattened_invitees: JByteArray,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+
+    let root_key =
+        call_links::CallLinkRootKey::try_from(env.convert_byte_array(root_key)?.as_slice())?;
+    let schedule = call_links::CallLinkSchedule::from_flattened(
+        start_epoch_seconds as u64,
+        duration_seconds as u64,
+        &env.convert_byte_array(flattened_invitees)?,
+    )?;
+    call_manager.cancel_call_link_ring(&root_key, &schedule)?;
+    Ok(())
+}
+
 /// Application notification of received answer message
 #[allow(clippy::too_many_arguments)]
 pub fn received_answer(
@@ -609,6 +718,46 @@ pub fn create_call_link(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn create_breakout_room_link(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    sfu_url: JString,
+    create_credential_presentation: JByteArray,
+    root_key: JByteArray,
+    room_index: jint,
+    admin_passkey: JByteArray,
+    call_link_public_params: JByteArray,
+    restrictions: jint,
+    request_id: jlong,
+) -> Result<()> {
+    let sfu_url = env.get_string(&sfu_url)?;
+    let create_credential_presentation = env.convert_byte_array(create_credential_presentation)?;
+    let root_key =
+        call_links::CallLinkRootKey::try_from(env.convert_byte_array(root_key)?.as_slice())?;
+    let admin_passkey = env.convert_byte_array(admin_passkey)?;
+    let call_link_public_params = env.convert_byte_array(call_link_public_params)?;
+    let restrictions = jint_to_restrictions(restrictions);
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    let platform = call_manager.platform()?.try_clone()?;
+    call_links::create_breakout_room_link(
+        call_manager.http_client(),
+        &Cow::from(&sfu_url),
+        &root_key,
+        room_index as u32,
+        &create_credential_presentation,
+        &admin_passkey,
+        &call_link_public_params,
+        restrictions,
+        Box::new(move |result| {
+            platform.handle_call_link_result(request_id as u32, result);
+        }),
+    );
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn update_call_link(
     env: &mut JNIEnv,
@@ -620,6 +769,9 @@ pub fn update_call_link(
     new_name: JString,
     new_restrictions: jint,
     new_revoked: jint,
+    new_schedule_start_epoch_seconds: jlong,
+    new_schedule_duration_seconds: jlong,
+    new_schedule_invitees: JByteArray,
     request_id: jlong,
 ) -> Result<()> {
     let sfu_url = env.get_string(&sfu_url)?;
@@ -646,6 +798,19 @@ pub fn update_call_link(
         1 => Some(true),
         _ => None,
     };
+    // A negative duration leaves the schedule unchanged, and zero removes it.
+    let encrypted_schedule = match new_schedule_duration_seconds {
+        ..=-1 => None,
+        0 => Some(vec![]),
+        duration_seconds => Some(
+            call_links::CallLinkSchedule::from_flattened(
+                new_schedule_start_epoch_seconds as u64,
+                duration_seconds as u64,
+                &env.convert_byte_array(new_schedule_invitees)?,
+            )?
+            .encrypt(&root_key, rand::rngs::OsRng),
+        ),
+    };
 
     let call_manager = unsafe { ptr_as_mut(call_manager)? };
     let platform = call_manager.platform()?.try_clone()?;
@@ -659,6 +824,7 @@ pub fn update_call_link(
             encrypted_name: encrypted_name.as_deref(),
             restrictions: new_restrictions,
             revoked: new_revoked,
+            encrypted_schedule: encrypted_schedule.as_deref(),
         },
         Box::new(move |result| {
             platform.handle_call_link_result(request_id as u32, result);
@@ -940,6 +1106,16 @@ pub fn join(call_manager: *mut AndroidCallManager, client_id: group_call::Client
     Ok(())
 }
 
+pub fn join_by_handoff(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    from_demux_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.join_by_handoff(client_id, from_demux_id as u32);
+    Ok(())
+}
+
 pub fn leave(call_manager: *mut AndroidCallManager, client_id: group_call::ClientId) -> Result<()> {
     let call_manager = unsafe { ptr_as_mut(call_manager)? };
     call_manager.leave(client_id);
@@ -992,6 +1168,44 @@ pub fn group_ring(
     Ok(())
 }
 
+pub fn group_ring_again(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    flattened_recipients: JByteArray,
+) -> Result<()> {
+    const UUID_LEN: usize = 16;
+    let flattened_recipients = env.convert_byte_array(flattened_recipients)?;
+    if flattened_recipients.len() % UUID_LEN != 0 {
+        return Err(anyhow::anyhow!(
+            "recipients are not a multiple of {} bytes: {}",
+            UUID_LEN,
+            flattened_recipients.len()
+        ));
+    }
+    let recipients = flattened_recipients
+        .chunks_exact(UUID_LEN)
+        .map(<[u8]>::to_vec)
+        .collect();
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.group_ring_again(client_id, recipients);
+    Ok(())
+}
+
+pub fn group_ring_delivered(
+    env: &JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    recipient: JByteArray,
+) -> Result<()> {
+    let recipient = env.convert_byte_array(recipient)?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.group_ring_delivered(client_id, recipient);
+    Ok(())
+}
+
 pub fn resend_media_keys(
     call_manager: *mut AndroidCallManager,
     client_id: group_call::ClientId,
@@ -1090,6 +1304,71 @@ pub fn request_video(
     Ok(())
 }
 
+/// Converts a Java list of Long into Rust Vec<u32>.
+fn demux_ids_from_jni_list(env: &mut JNIEnv, jni_demux_ids: JObject) -> Result<Vec<u32>> {
+    let jni_demux_id_list = env.get_list(&jni_demux_ids)?;
+    let mut demux_ids = Vec::new();
+    let mut iterator = jni_demux_id_list.iter(env)?;
+    while let Some(jni_demux_id) = iterator.next(env)? {
+        let demux_id = env
+            .call_method(jni_demux_id, "longValue", jni_signature!(() -> long), &[])?
+            .j()?;
+        demux_ids.push(demux_id as u32);
+    }
+    Ok(demux_ids)
+}
+
+pub fn set_spotlight(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    jni_demux_ids: JObject,
+) -> Result<()> {
+    let demux_ids = demux_ids_from_jni_list(env, jni_demux_ids)?;
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_spotlight(client_id, demux_ids);
+    Ok(())
+}
+
+pub fn set_viewport(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    mode: jint,
+    jni_tiles: JObject,
+    jni_pinned: JObject,
+) -> Result<()> {
+    let viewport = if mode == -1 {
+        None
+    } else {
+        const INT_TYPE: &str = jni_signature!(int);
+
+        // Convert Java list of TileSize into Rust Vec<group_call::TileSize>.
+        let jni_tile_list = env.get_list(&jni_tiles)?;
+        let mut tiles = Vec::new();
+        let mut iterator = jni_tile_list.iter(env)?;
+        while let Some(jni_tile) = iterator.next(env)? {
+            let width = jni_get_field(env, &jni_tile, "width", INT_TYPE)?.i()?;
+            let height = jni_get_field(env, &jni_tile, "height", INT_TYPE)?.i()?;
+            tiles.push(group_call::TileSize {
+                width: width as u16,
+                height: height as u16,
+            });
+        }
+
+        Some(group_call::Viewport {
+            mode: group_call::ViewportMode::try_from(mode)?,
+            tiles,
+            pinned: demux_ids_from_jni_list(env, jni_pinned)?,
+        })
+    };
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_viewport(client_id, viewport);
+    Ok(())
+}
+
 pub fn approve_user(
     env: &JNIEnv,
     call_manager: *mut AndroidCallManager,
@@ -1134,6 +1413,46 @@ pub fn block_client(
     Ok(())
 }
 
+pub fn mute_client(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.mute_client(client_id, other_client_demux_id as u32);
+    Ok(())
+}
+
+pub fn lower_hand(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.lower_hand(client_id, other_client_demux_id as u32);
+    Ok(())
+}
+
+pub fn call_next_speaker(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    ask_to_unmute: bool,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.call_next_speaker(client_id, ask_to_unmute);
+    Ok(())
+}
+
+pub fn ask_client_to_unmute(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.ask_client_to_unmute(client_id, other_client_demux_id as u32);
+    Ok(())
+}
+
 pub fn set_group_members(
     env: &JNIEnv,
     call_manager: *mut AndroidCallManager,
@@ -1171,6 +1490,82 @@ pub fn react(
     Ok(())
 }
 
+pub fn send_chat_message(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    text: JString,
+    reply_to_demux_id: jlong,
+    reply_to_seqnum: jlong,
+) -> Result<()> {
+    let text = env.get_string(&text)?.into();
+    // Seqnums start at 1, so zero means the message isn't a reply.
+    let reply_to = (reply_to_seqnum > 0).then_some(group_call::ChatMessageId {
+        demux_id: reply_to_demux_id as u32,
+        seqnum: reply_to_seqnum as u64,
+    });
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.send_chat_message(client_id, text, reply_to);
+    Ok(())
+}
+
+pub fn create_poll(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    question: JString,
+    jni_options: JObject,
+) -> Result<()> {
+    let question = env.get_string(&question)?.into();
+
+    let jni_option_list = env.get_list(&jni_options)?;
+    let mut options = Vec::new();
+    let mut iterator = jni_option_list.iter(env)?;
+    while let Some(jni_option) = iterator.next(env)? {
+        let option = env.get_string(&JString::from(jni_option))?.into();
+        options.push(option);
+    }
+
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.create_poll(client_id, question, options);
+    Ok(())
+}
+
+pub fn vote_in_poll(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    poll_id: jlong,
+    option_index: jint,
+) -> Result<()> {
+    // A negative index withdraws the vote.
+    let option_index = (option_index >= 0).then_some(option_index as u32);
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.vote_in_poll(client_id, poll_id as group_call::PollId, option_index);
+    Ok(())
+}
+
+pub fn close_poll(
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    poll_id: jlong,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.close_poll(client_id, poll_id as group_call::PollId);
+    Ok(())
+}
+
+pub fn send_lobby_message(
+    env: &mut JNIEnv,
+    call_manager: *mut AndroidCallManager,
+    client_id: group_call::ClientId,
+    message: JString,
+) -> Result<()> {
+    let message = env.get_string(&message)?.into();
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.send_lobby_message(client_id, message);
+    Ok(())
+}
+
 pub fn raise_hand(
     call_manager: *mut AndroidCallManager,
     client_id: group_call::ClientId,
@@ -14,7 +14,8 @@ use base64::{engine::general_purpose::STANDARD as base64, Engine};
 use rand::SeedableRng;
 use ringrtc::lite::{
     call_links::{
-        CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkUpdateRequest,
+        CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkSchedule,
+        CallLinkUpdateRequest,
     },
     http::{self, sim as sim_http, Client},
 };
@@ -192,6 +193,7 @@ help                         - show this message
 create <id>                  - create a new link
 read <id>                    - fetch the current state of a link
 set-title <id> <new-title>   - change the title of a link
+schedule <id> <in> <length>  - schedule a meeting starting <in> minutes from now, lasting <length> minutes
 admin-approval <id> (on|off) - turn on/off admin approval for a link
 reset-approvals <id>         - reset a link's list of approved users (if the server has this enabled)
 reset-expiration <id>        - reset a link's expiration (if the server has this enabled)
@@ -296,6 +298,38 @@ The admin passkey for any created links is a constant {ADMIN_PASSKEY:?}.
                     Box::new(show_result),
                 );
             }
+            ["schedule", id, starts_in, length] => {
+                let (Ok(starts_in), Ok(length)) = (starts_in.parse::<u64>(), length.parse::<u64>())
+                else {
+                    println!("<in> and <length> must be numbers of minutes");
+                    prompt("> ");
+                    continue;
+                };
+                let root_key = root_key_from_id(id);
+                let encrypted_schedule = CallLinkSchedule {
+                    start: SystemTime::now() + Duration::from_secs(starts_in * 60),
+                    duration: Duration::from_secs(length * 60),
+                    invitees: vec![],
+                }
+                .encrypt(&root_key, rand::thread_rng());
+                let auth_credential_presentation = issue_and_present_auth_credential(
+                    &server_zkparams,
+                    &public_zkparams,
+                    &root_key,
+                );
+                ringrtc::lite::call_links::update_call_link(
+                    &http_client,
+                    url,
+                    root_key,
+                    &bincode::serialize(&auth_credential_presentation).unwrap(),
+                    &CallLinkUpdateRequest {
+                        admin_passkey: ADMIN_PASSKEY,
+                        encrypted_schedule: Some(&encrypted_schedule),
+                        ..CallLinkUpdateRequest::default()
+                    },
+                    Box::new(show_result),
+                );
+            }
             ["admin-approval", id, on_or_off @ ("on" | "off")] => {
                 let root_key = root_key_from_id(id);
                 let auth_credential_presentation = issue_and_present_auth_credential(
@@ -8,6 +8,7 @@ mod group_call_sim;
 
 use std::{
     collections::{HashMap, HashSet},
+    net::IpAddr,
     sync::mpsc::Sender,
     time::{Duration, Instant},
 };
@@ -31,6 +32,7 @@ use ringrtc::{
         sfu::{GroupMember, UserId},
     },
     native::{NativeCallContext, NativePlatform, PeerId, SignalingSender},
+    simnet::router::{LinkConfig, Router},
     webrtc::{
         media::{AudioTrack, VideoSink, VideoSource, VideoTrack},
         peer_connection_factory::{AudioConfig, IceServer, PeerConnectionFactory},
@@ -38,7 +40,7 @@ use ringrtc::{
 };
 
 use crate::{
-    network::DeterministicLossNetwork,
+    network::{DeterministicLoss, DeterministicLossNetwork, TrafficCounters},
     relay::SignalingRelay,
     util,
     video::{LoggingVideoSink, VideoInput},
@@ -273,6 +275,34 @@ impl CallEndpoint {
         });
     }
 
+    pub fn add_simnet_interface(
+        &self,
+        ip: IpAddr,
+        router: &Router,
+        send_config: LinkConfig,
+        receive_config: LinkConfig,
+        deterministic_loss: Option<DeterministicLoss>,
+        traffic: TrafficCounters,
+    ) {
+        // To send across threads
+        let router = router.clone();
+
+        self.actor.send(move |state| {
+            if let Some(ref network) = state.network {
+                network.add_simnet_interface(
+                    ip,
+                    &router,
+                    send_config,
+                    receive_config,
+                    deterministic_loss,
+                    traffic,
+                );
+            } else {
+                error!("Error: Injectable network not set properly!");
+            }
+        });
+    }
+
     pub fn stop_network(&self) {
         self.actor.send(move |state| {
             if let Some(ref network) = state.network {
@@ -441,7 +471,7 @@ impl SignalingSender for CallEndpoint {
         &self,
         recipient_id: &str,
         call_id: CallId,
-        _receiver_device_id: Option<DeviceId>,
+        receiver_device_id: Option<DeviceId>,
         msg: signaling::Message,
     ) -> Result<()> {
         // To send across threads
@@ -453,6 +483,7 @@ impl SignalingSender for CallEndpoint {
                 &sender_id,
                 state.device_id,
                 &recipient_id,
+                receiver_device_id,
                 call_id,
                 msg,
             );
@@ -6,11 +6,13 @@
 use log::*;
 use ringrtc::{
     common::{CallConfig, CallId, Result},
+    core::signaling::SenderStatus,
     native::{CallState, CallStateHandler, NativeCallContext},
     webrtc::{peer_connection::AudioLevel, peer_connection_observer::NetworkRoute},
 };
 
 use super::CallEndpoint;
+use crate::scenario::ScenarioAction;
 
 /// Metadata for 1:1 Calls
 #[derive(Clone)]
@@ -28,6 +30,68 @@ impl DirectCall {
     }
 }
 
+/// Implement direct call specific functions
+impl CallEndpoint {
+    /// Performs an action in the direct call, if it is one that direct calls support.
+    pub fn perform_direct_call_action(&self, action: &ScenarioAction) {
+        let action = action.clone();
+        self.actor.send(move |state| {
+            let sender_status = match action {
+                ScenarioAction::Mute | ScenarioAction::Unmute => {
+                    let enabled = action == ScenarioAction::Unmute;
+                    state.outgoing_audio_track.set_enabled(enabled);
+                    SenderStatus {
+                        audio_enabled: Some(enabled),
+                        ..Default::default()
+                    }
+                }
+                ScenarioAction::VideoOn | ScenarioAction::VideoOff => {
+                    let enabled = action == ScenarioAction::VideoOn;
+                    state.outgoing_video_track.set_enabled(enabled);
+                    SenderStatus {
+                        video_enabled: Some(enabled),
+                        ..Default::default()
+                    }
+                }
+                ScenarioAction::ScreenshareStart | ScenarioAction::ScreenshareStop => {
+                    // The same video is sent, but it is treated as a screenshare.
+                    SenderStatus {
+                        sharing_screen: Some(action == ScenarioAction::ScreenshareStart),
+                        ..Default::default()
+                    }
+                }
+                ScenarioAction::DataMode(data_mode) => {
+                    if let Err(err) = state
+                        .call_manager
+                        .active_connection()
+                        .and_then(|mut connection| connection.inject_update_data_mode(data_mode))
+                    {
+                        warn!("Failed to update the data mode: {}", err);
+                    }
+                    return;
+                }
+                ScenarioAction::Hangup => {
+                    if let Err(err) = state.call_manager.hangup() {
+                        warn!("Failed to hang up: {}", err);
+                    }
+                    return;
+                }
+                action => {
+                    warn!("Can't {} in a direct call", action);
+                    return;
+                }
+            };
+            if let Err(err) = state
+                .call_manager
+                .active_connection()
+                .and_then(|mut connection| connection.update_sender_status(sender_status))
+            {
+                warn!("Failed to update the sender status: {}", err);
+            }
+        });
+    }
+}
+
 impl CallStateHandler for CallEndpoint {
     fn handle_call_state(
         &self,
@@ -20,6 +20,7 @@ use ringrtc::{
 };
 
 use super::CallEndpoint;
+use crate::scenario::ScenarioAction;
 
 impl Default for LocalDeviceState {
     fn default() -> Self {
@@ -72,7 +73,7 @@ pub struct LocalDeviceState {
     audio_level: u16,
     network_route: Option<NetworkRoute>,
 
-    // TODO: add support for the following
+    // These are only tracked for now.
     #[allow(dead_code)]
     audio_muted: bool,
     #[allow(dead_code)]
@@ -114,6 +115,9 @@ impl CallEndpoint {
                 )
                 .expect("create group call client");
 
+            // Rejoining unmutes, like joining for the first time.
+            state.outgoing_audio_track.set_enabled(true);
+            state.outgoing_video_track.set_enabled(true);
             state
                 .call_manager
                 .set_outgoing_audio_muted(client_id, false);
@@ -134,6 +138,63 @@ impl CallEndpoint {
         });
     }
 
+    /// Performs an action in the group call. Hanging up and rejoining are done with
+    /// [`CallEndpoint::hangup_group_call`] and [`CallEndpoint::join_group_call`].
+    pub fn perform_group_call_action(&self, action: &ScenarioAction) {
+        let action = action.clone();
+        self.actor.send(move |state| {
+            let Some(group_call) = state.group_call.as_mut() else {
+                warn!("Did not find group call to {}...", action);
+                return;
+            };
+            let client_id = group_call.client_id;
+            let local_device_state = &mut group_call.local_device_state;
+            match action {
+                ScenarioAction::Mute | ScenarioAction::Unmute => {
+                    let muted = action == ScenarioAction::Mute;
+                    // Like the apps, stop sending audio as well as telling the others.
+                    state.outgoing_audio_track.set_enabled(!muted);
+                    state
+                        .call_manager
+                        .set_outgoing_audio_muted(client_id, muted);
+                    local_device_state.audio_muted = muted;
+                }
+                ScenarioAction::VideoOn | ScenarioAction::VideoOff => {
+                    let muted = action == ScenarioAction::VideoOff;
+                    state.outgoing_video_track.set_enabled(!muted);
+                    state
+                        .call_manager
+                        .set_outgoing_video_muted(client_id, muted);
+                    local_device_state.video_muted = muted;
+                }
+                ScenarioAction::ScreenshareStart | ScenarioAction::ScreenshareStop => {
+                    // The same video is sent, but it is treated as a screenshare.
+                    let sharing_screen = action == ScenarioAction::ScreenshareStart;
+                    state.call_manager.set_presenting(client_id, sharing_screen);
+                    state
+                        .call_manager
+                        .set_sharing_screen(client_id, sharing_screen);
+                    local_device_state.presenting = sharing_screen;
+                    local_device_state.sharing_screen = sharing_screen;
+                }
+                ScenarioAction::DataMode(data_mode) => {
+                    state.call_manager.set_data_mode(client_id, data_mode);
+                }
+                ScenarioAction::RaiseHand | ScenarioAction::LowerHand => {
+                    state
+                        .call_manager
+                        .raise_hand(client_id, action == ScenarioAction::RaiseHand);
+                }
+                ScenarioAction::React(value) => {
+                    state.call_manager.react(client_id, value);
+                }
+                ScenarioAction::Hangup | ScenarioAction::Rejoin => {
+                    warn!("Can't {} as an action in the group call", action);
+                }
+            }
+        });
+    }
+
     pub fn hangup_group_call(&self) {
         info!("Starting to leave group call...");
         self.actor.send(move |state| {
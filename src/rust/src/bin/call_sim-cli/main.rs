@@ -4,13 +4,16 @@
 //
 
 mod endpoint;
+mod markers;
 mod network;
 mod relay;
 mod scenario;
+mod simnet;
+mod soak;
 mod util;
 mod video;
 
-use std::ffi::CString;
+use std::{ffi::CString, path::PathBuf, time::Duration};
 
 use anyhow::Result;
 use base64::prelude::*;
@@ -27,11 +30,12 @@ use ringrtc::{
             AudioConfig, AudioJitterBufferConfig, FileBasedAdmConfig, IceServer,
             RffiAudioDeviceModuleType,
         },
+        stats_exporter::{self, StatsExportFormat, StatsExportTarget},
     },
 };
-use scenario::ScenarioCallTypeConfig;
+use scenario::{parse_timed_action, ScenarioAction, ScenarioCallTypeConfig, ScenarioSchedule};
 
-use crate::scenario::ScenarioManager;
+use crate::{scenario::ScenarioManager, soak::SoakSampler};
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -51,6 +55,19 @@ struct Args {
     #[arg(long, default_value = "2")]
     stats_initial_offset_secs: u16,
 
+    /// If set, stats are also exported to this file, as they are posted to the log file.
+    #[arg(long)]
+    stats_export_file: Option<PathBuf>,
+
+    /// If set, stats are also exported to this Unix socket, which must be listening.
+    #[cfg(unix)]
+    #[arg(long, conflicts_with = "stats_export_file")]
+    stats_export_socket: Option<PathBuf>,
+
+    /// The format of exported stats.
+    #[arg(long, default_value_t = StatsExportFormat::JsonLines, value_enum)]
+    stats_export_format: StatsExportFormat,
+
     /// Specifies the file (including path) to use for audio input.
     #[arg(long, default_value = "")]
     input_file: String,
@@ -84,6 +101,12 @@ struct Args {
     #[arg(long)]
     output_video_file: Option<String>,
 
+    /// Stamps outgoing video with the time each frame was sent, and logs the delay of received
+    /// frames that were stamped and when audio playout starts, so that the test manager can
+    /// measure end-to-end latency.
+    #[arg(long)]
+    timing_markers: bool,
+
     /// The allowed bitrate for all media.
     #[arg(long, default_value = "2000", value_parser = clap::value_parser!(u16).range(30..))]
     allowed_bitrate_kbps: u16,
@@ -208,6 +231,40 @@ struct Args {
     #[arg(short = 'g', long)]
     is_group_call: bool,
 
+    /// How long to wait before joining a group call, after being told to start.
+    #[arg(long, default_value = "0")]
+    join_offset_secs: u64,
+
+    /// When to leave a group call, after being told to start. If not given, the client stays
+    /// until it is told to stop.
+    #[arg(long)]
+    leave_offset_secs: Option<u64>,
+
+    /// Something to do during the call, as `<offset secs>:<action>` after being told to start,
+    /// such as `10:mute` or `20:data-mode=low`. Can be repeated. Rejoining, raising and
+    /// lowering a hand, and reacting are only supported in group calls.
+    #[arg(long = "action", value_parser = parse_timed_action)]
+    actions: Vec<(Duration, ScenarioAction)>,
+
+    /// When to mute outgoing audio, after being told to start. Can be repeated. The same as
+    /// `--action=<secs>:mute`.
+    #[arg(long)]
+    mute_at_secs: Vec<u64>,
+
+    /// When to unmute outgoing audio, after being told to start. Can be repeated. The same as
+    /// `--action=<secs>:unmute`.
+    #[arg(long)]
+    unmute_at_secs: Vec<u64>,
+
+    /// Hangs up and rejoins a group call this often, for as long as the client is in it.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    reconnect_interval_secs: Option<u64>,
+
+    /// Samples the resources used by the process and RingRTC's internal state this often,
+    /// logging them and, at the end, anything that grew steadily. Only supported on Linux.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    soak_sample_interval_secs: Option<u64>,
+
     /// Our UUID we use to identify our selves to the SFU. Should be a UUID.
     #[arg(long, value_parser = parse_uuid)]
     user_id: Option<UserId>,
@@ -231,11 +288,22 @@ struct Args {
     /// Formatted as `{userId}:{memberId}`.
     #[arg(short = 'm', long, value_delimiter = ',', value_parser = parse_group_member_info)]
     pub group_member_info: Option<Vec<GroupMember>>,
+
+    /// Runs all the endpoints described in the given JSON file in this process, linked by a
+    /// simulated network, instead of a single endpoint driven by the test manager. Each
+    /// endpoint has its own arguments in the file, and `--log-file` is used for the log of
+    /// the whole process.
+    #[arg(long)]
+    simnet_config: Option<String>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(simnet_config) = &args.simnet_config {
+        return simnet::run(simnet_config, args.log_file.as_deref());
+    }
+
     let fern_logger = Dispatch::new()
         .format(|out, message, record| {
             out.finish(format_args!(
@@ -249,7 +317,7 @@ fn main() -> Result<()> {
         })
         .level(LevelFilter::Debug);
 
-    if let Some(log_file) = args.log_file {
+    if let Some(log_file) = &args.log_file {
         fern_logger.chain(fern::log_file(log_file)?).apply()?;
     } else {
         fern_logger.chain(std::io::stdout()).apply()?;
@@ -261,7 +329,74 @@ fn main() -> Result<()> {
     info!("Setting field trials to {}", &args.field_trials);
     ringrtc::webrtc::field_trial::init(&args.field_trials).expect("no null characters");
 
-    let ice_server = if args.relay_servers.is_empty() {
+    if let Some(target) = stats_export_target(&args) {
+        stats_exporter::init(args.stats_export_format, target)?;
+    }
+
+    let ice_server = ice_server(&args);
+    let call_config = call_config(&args);
+
+    let schedule = schedule(&args);
+    if !args.is_group_call {
+        schedule.validate_for_direct_call()?;
+    }
+
+    if args.timing_markers && !args.output_file.is_empty() {
+        markers::log_audio_start(&args.name, args.output_file.clone().into());
+    }
+
+    let soak_sampler = args
+        .soak_sample_interval_secs
+        .map(|secs| SoakSampler::start(&args.name, Duration::from_secs(secs)))
+        .transpose()?;
+
+    let mut scenario = ScenarioManager::new()?;
+    let call_type_config = if args.is_group_call {
+        ScenarioCallTypeConfig::GroupCallConfig {
+            sfu_url: args.sfu_url.expect("sfu url should be provided"),
+            group_id: args.group_id.expect("group_id should be provided"),
+            membership_proof: args
+                .membership_proof
+                .expect("membership proof should be provided"),
+            group_member_info: args
+                .group_member_info
+                .expect("group_member_info should be provided"),
+        }
+    } else {
+        ScenarioCallTypeConfig::DirectCallConfig {
+            ice_server,
+            force_relay: args.force_relay,
+        }
+    };
+    scenario.run(
+        &args.name,
+        &args.ip,
+        args.user_id,
+        args.device_id,
+        call_config,
+        scenario::ScenarioConfig {
+            video_width: args.input_video_width,
+            video_height: args.input_video_height,
+            video_input: args.input_video_file.map(Into::into),
+            output_video_width: args.output_video_width,
+            output_video_height: args.output_video_height,
+            video_output: args.output_video_file.map(Into::into),
+            timing_markers: args.timing_markers,
+            deterministic_loss: args.deterministic_loss,
+            call_type_config,
+            schedule,
+        },
+    );
+
+    if let Some(soak_sampler) = soak_sampler {
+        soak_sampler.finish();
+    }
+
+    Ok(())
+}
+
+fn ice_server(args: &Args) -> IceServer {
+    if args.relay_servers.is_empty() {
         IceServer::none()
     } else {
         info!("Setting relay servers: {:?}", args.relay_servers);
@@ -270,16 +405,50 @@ fn main() -> Result<()> {
         info!("     force: {}", args.force_relay);
 
         IceServer::new(
-            args.relay_username,
-            args.relay_password,
+            args.relay_username.clone(),
+            args.relay_password.clone(),
             // TODO: Add support for hostname when TLS TURN is supported with the call sim
             "".to_string(),
-            args.relay_servers,
+            args.relay_servers.clone(),
         )
-    };
+    }
+}
+
+/// Collects when the client should join and leave, and what it should do in between.
+fn schedule(args: &Args) -> ScenarioSchedule {
+    let join_offset = Duration::from_secs(args.join_offset_secs);
+    let leave_offset = args.leave_offset_secs.map(Duration::from_secs);
+    let mut actions = args.actions.clone();
+    actions.extend(
+        args.mute_at_secs
+            .iter()
+            .map(|offset| (Duration::from_secs(*offset), ScenarioAction::Mute)),
+    );
+    actions.extend(
+        args.unmute_at_secs
+            .iter()
+            .map(|offset| (Duration::from_secs(*offset), ScenarioAction::Unmute)),
+    );
+    if let Some(reconnect_interval_secs) = args.reconnect_interval_secs {
+        actions.extend(soak::reconnect_actions(
+            join_offset,
+            leave_offset,
+            Duration::from_secs(reconnect_interval_secs),
+        ));
+    }
+    // Actions at the same offset keep the order they were given in.
+    actions.sort_by_key(|(offset, _)| *offset);
+
+    ScenarioSchedule {
+        join_offset,
+        leave_offset,
+        actions,
+    }
+}
 
-    // Create a call configuration that should be used for the call.
-    let call_config = CallConfig {
+/// Creates a call configuration that should be used for the call.
+fn call_config(args: &Args) -> CallConfig {
+    CallConfig {
         // This configuration is currently the same as `Normal`.
         data_mode: DataMode::Custom {
             max_bitrate: units::DataRate::from_kbps(args.allowed_bitrate_kbps as u64),
@@ -290,8 +459,8 @@ fn main() -> Result<()> {
         audio_config: AudioConfig {
             audio_device_module_type: RffiAudioDeviceModuleType::File,
             file_based_adm_config: Some(FileBasedAdmConfig {
-                input_file: CString::new(args.input_file).expect("CString::new failed"),
-                output_file: CString::new(args.output_file).expect("CString::new failed"),
+                input_file: CString::new(args.input_file.as_str()).expect("CString::new failed"),
+                output_file: CString::new(args.output_file.as_str()).expect("CString::new failed"),
             }),
             high_pass_filter_enabled: args.high_pass_filter,
             aec_enabled: args.aec,
@@ -324,45 +493,15 @@ fn main() -> Result<()> {
         },
         audio_rtcp_report_interval_ms: args.audio_rtcp_report_interval_ms,
         enable_vp9: args.vp9,
-    };
-
-    let mut scenario = ScenarioManager::new()?;
-    let call_type_config = if args.is_group_call {
-        ScenarioCallTypeConfig::GroupCallConfig {
-            sfu_url: args.sfu_url.expect("sfu url should be provided"),
-            group_id: args.group_id.expect("group_id should be provided"),
-            membership_proof: args
-                .membership_proof
-                .expect("membership proof should be provided"),
-            group_member_info: args
-                .group_member_info
-                .expect("group_member_info should be provided"),
-        }
-    } else {
-        ScenarioCallTypeConfig::DirectCallConfig {
-            ice_server,
-            force_relay: args.force_relay,
-        }
-    };
-    scenario.run(
-        &args.name,
-        &args.ip,
-        args.user_id,
-        args.device_id,
-        call_config,
-        scenario::ScenarioConfig {
-            video_width: args.input_video_width,
-            video_height: args.input_video_height,
-            video_input: args.input_video_file.map(Into::into),
-            output_video_width: args.output_video_width,
-            output_video_height: args.output_video_height,
-            video_output: args.output_video_file.map(Into::into),
-            deterministic_loss: args.deterministic_loss,
-            call_type_config,
-        },
-    );
+    }
+}
 
-    Ok(())
+fn stats_export_target(args: &Args) -> Option<StatsExportTarget> {
+    #[cfg(unix)]
+    if let Some(path) = &args.stats_export_socket {
+        return Some(StatsExportTarget::UnixSocket(path.clone()));
+    }
+    args.stats_export_file.clone().map(StatsExportTarget::File)
 }
 
 fn parse_base64(s: &str) -> Result<GroupId, String> {
@@ -0,0 +1,192 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Timing markers for measuring end-to-end latency.
+//!
+//! Outgoing video frames are stamped with a barcode holding the sender's wall clock, and the
+//! receiver logs how long ago each frame it receives was stamped. Audio is marked by the test
+//! manager (chirps mixed into the sound file), so all we log for audio is when the recording of
+//! what was played out starts, which lets the test manager place the chirps it finds in the
+//! recording on the same clock.
+//!
+//! Both clocks are the host clock when running under docker or simnet, so no synchronization
+//! is needed.
+
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use log::info;
+
+/// The barcode is a row of cells across the top of the frame:
+///   4 sync bits (1010), 32 bits of the low half of the UNIX time in ms, and a 4 bit checksum.
+const SYNC_BITS: u64 = 0b1010;
+const SYNC_CELLS: u32 = 4;
+const TIME_CELLS: u32 = 32;
+const CHECKSUM_CELLS: u32 = 4;
+const CELLS: u32 = SYNC_CELLS + TIME_CELLS + CHECKSUM_CELLS;
+
+/// Narrower frames can't hold a barcode that survives encoding.
+const MIN_WIDTH: u32 = CELLS * 4;
+const MIN_HEIGHT: u32 = 16;
+
+/// Studio-range black and white, and neutral chroma.
+const LUMA_ONE: u8 = 235;
+const LUMA_ZERO: u8 = 16;
+const LUMA_THRESHOLD: u32 = 128;
+const CHROMA_NEUTRAL: u8 = 128;
+
+/// How long to wait for playout to start before giving up.
+const AUDIO_START_TIMEOUT: Duration = Duration::from_secs(600);
+const AUDIO_START_POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// The recording is 48kHz stereo 16-bit PCM, as written by the file-based audio device.
+const AUDIO_BYTES_PER_MS: u64 = 48 * 2 * 2;
+/// Samples no louder than this are silence (or dither), not audio being played.
+const AUDIO_SILENCE_THRESHOLD: u16 = 64;
+
+/// The current UNIX time in milliseconds.
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis() as u64
+}
+
+fn checksum(time: u32) -> u64 {
+    (0..8).fold(0, |acc, nibble| acc ^ ((time >> (nibble * 4)) & 0xf)) as u64
+}
+
+/// The barcode covers the top rows of the frame, rounded to keep chroma rows aligned.
+fn band_height(height: u32) -> u32 {
+    (height / 16).max(MIN_HEIGHT / 2) & !1
+}
+
+fn has_room(width: u32, height: u32) -> bool {
+    width >= MIN_WIDTH && height >= MIN_HEIGHT
+}
+
+/// Stamps the current time into the top of an I420 frame.
+pub fn stamp_i420(width: u32, height: u32, buffer: &mut [u8]) {
+    if !has_room(width, height) {
+        return;
+    }
+
+    let time = now_ms() as u32;
+    let bits = (SYNC_BITS << (TIME_CELLS + CHECKSUM_CELLS))
+        | ((time as u64) << CHECKSUM_CELLS)
+        | checksum(time);
+
+    let width = width as usize;
+    let band = band_height(height) as usize;
+    for line in buffer[..band * width].chunks_exact_mut(width) {
+        for (x, luma) in line.iter_mut().enumerate() {
+            let cell = (x * CELLS as usize / width) as u32;
+            let bit = (bits >> (CELLS - 1 - cell)) & 1;
+            *luma = if bit == 1 { LUMA_ONE } else { LUMA_ZERO };
+        }
+    }
+
+    // Keep the band gray so that color doesn't bleed into the luma decision.
+    let luma_size = width * height as usize;
+    let chroma_width = width.div_ceil(2);
+    let chroma_size = chroma_width * (height as usize).div_ceil(2);
+    for plane in [luma_size, luma_size + chroma_size] {
+        buffer[plane..plane + chroma_width * band / 2].fill(CHROMA_NEUTRAL);
+    }
+}
+
+/// Reads the time stamped by [`stamp_i420`], if the frame has a valid barcode.
+pub fn read_i420(width: u32, height: u32, buffer: &[u8]) -> Option<u32> {
+    if !has_room(width, height) {
+        return None;
+    }
+
+    let width = width as usize;
+    let band = band_height(height) as usize;
+    // Sample the middle of each cell, away from edges blurred by encoding and scaling.
+    let rows = band / 4..band - band / 4;
+    let mut bits = 0u64;
+    for cell in 0..CELLS as usize {
+        let start = (cell * 4 + 1) * width / (CELLS as usize * 4);
+        let end = ((cell * 4 + 3) * width / (CELLS as usize * 4)).max(start + 1);
+        let mut sum = 0u32;
+        let mut count = 0u32;
+        for row in rows.clone() {
+            for &luma in &buffer[row * width + start..row * width + end] {
+                sum += luma as u32;
+                count += 1;
+            }
+        }
+        bits = (bits << 1) | (sum / count > LUMA_THRESHOLD) as u64;
+    }
+
+    let time = (bits >> CHECKSUM_CELLS) as u32;
+    if bits >> (TIME_CELLS + CHECKSUM_CELLS) != SYNC_BITS
+        || bits & ((1 << CHECKSUM_CELLS) - 1) != checksum(time)
+    {
+        return None;
+    }
+    Some(time)
+}
+
+/// Logs the delay of a received frame that has a barcode.
+pub fn log_video_marker(client_name: &str, demux_id: u32, width: u32, height: u32, data: &[u8]) {
+    if let Some(sent) = read_i420(width, height, data) {
+        let now = now_ms();
+        let delay = (now as u32).wrapping_sub(sent) as i32;
+        // Example: ringrtc_marker!,client_b,video,0,1667611058243,85
+        info!(
+            "ringrtc_marker!,{},video,{},{},{}",
+            client_name, demux_id, now, delay
+        );
+    }
+}
+
+/// Reads what was added to the recording after the first `checked` bytes, returning the length
+/// of the recording if any of it is audible.
+fn audible_recording_len(path: &Path, checked: &mut u64) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(*checked)).ok()?;
+    let mut added = vec![];
+    file.read_to_end(&mut added).ok()?;
+
+    // A partly written sample is left for next time.
+    let samples = &added[..added.len() & !1];
+    *checked += samples.len() as u64;
+    samples
+        .chunks_exact(2)
+        .any(|sample| {
+            i16::from_le_bytes([sample[0], sample[1]]).unsigned_abs() > AUDIO_SILENCE_THRESHOLD
+        })
+        .then_some(*checked)
+}
+
+/// Logs when the recording in the output file started, which is when playout starts. This waits
+/// for the first audible samples rather than the first bytes, which can be written before the
+/// audio device is running in real time, and works the start out from the recording's length
+/// at that point so that it is on the audio's own clock.
+pub fn log_audio_start(client_name: &str, output_file: PathBuf) {
+    let client_name = client_name.to_string();
+    thread::Builder::new()
+        .name(format!("markers-{}", client_name))
+        .spawn(move || {
+            let start = Instant::now();
+            let mut checked = 0;
+            while start.elapsed() < AUDIO_START_TIMEOUT {
+                if let Some(len) = audible_recording_len(&output_file, &mut checked) {
+                    let started = now_ms().saturating_sub(len / AUDIO_BYTES_PER_MS);
+                    // Example: ringrtc_marker!,client_b,audio_start,1667611058243
+                    info!("ringrtc_marker!,{},audio_start,{}", client_name, started);
+                    return;
+                }
+                thread::sleep(AUDIO_START_POLL_INTERVAL);
+            }
+        })
+        .expect("can spawn marker thread");
+}
@@ -6,7 +6,11 @@
 use std::{
     io,
     iter::{Cycle, StepBy},
-    net::{SocketAddr, UdpSocket},
+    net::{IpAddr, SocketAddr, UdpSocket},
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc, Mutex,
+    },
     thread,
 };
 
@@ -18,6 +22,7 @@ use bitvec::{
 use log::*;
 use ringrtc::{
     common::Result,
+    simnet::router::{self, LinkConfig, Router},
     webrtc::{
         injectable_network::{self, InjectableNetwork},
         network::NetworkInterfaceType,
@@ -119,7 +124,26 @@ impl DeterministicLoss {
     }
 }
 
-/// Wrapper around InjectableNetwork that allows adding DeterministicLoss
+/// Counts the bytes an endpoint sends and receives through a simulated network, for
+/// resource stats.
+#[derive(Clone, Default)]
+pub struct TrafficCounters {
+    sent_bytes: Arc<AtomicU64>,
+    received_bytes: Arc<AtomicU64>,
+}
+
+impl TrafficCounters {
+    pub fn sent_bytes(&self) -> u64 {
+        self.sent_bytes.load(atomic::Ordering::Relaxed)
+    }
+
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes.load(atomic::Ordering::Relaxed)
+    }
+}
+
+/// Wrapper around InjectableNetwork that allows adding DeterministicLoss, or routing packets
+/// through a simulated network.
 pub struct DeterministicLossNetwork {
     injectable_network: InjectableNetwork,
     socket: Option<UdpSocket>,
@@ -194,6 +218,61 @@ impl DeterministicLossNetwork {
         });
     }
 
+    /// Connects the injectable network to a simnet router instead of a UDP socket, so packets
+    /// go to other endpoints in the same process. Deterministic loss, if given, is applied to
+    /// received packets just like with a socket.
+    pub fn add_simnet_interface(
+        &self,
+        ip: IpAddr,
+        router: &Router,
+        send_config: LinkConfig,
+        receive_config: LinkConfig,
+        deterministic_loss: Option<DeterministicLoss>,
+        traffic: TrafficCounters,
+    ) {
+        let network = self.injectable_network.clone();
+
+        let router_as_sender = router.clone();
+        let sent_bytes = traffic.sent_bytes.clone();
+        network.set_sender(Box::new(move |packet: injectable_network::Packet| {
+            sent_bytes.fetch_add(packet.data.len() as u64, atomic::Ordering::Relaxed);
+            router_as_sender.send_packet(router::Packet {
+                source: packet.source,
+                dest: packet.dest,
+                data: packet.data,
+            });
+        }));
+
+        // As with deterministic loss, we just assume "wifi" for simplicity.
+        network.add_interface("wifi", NetworkInterfaceType::Wifi, ip, 1);
+
+        let network_as_receiver = network.clone();
+        let deterministic_loss = Mutex::new(deterministic_loss);
+        let received_bytes = traffic.received_bytes;
+        router
+            .add_interface(
+                ip,
+                send_config,
+                receive_config,
+                Box::new(move |packet: router::Packet| {
+                    if let Some(deterministic_loss) =
+                        deterministic_loss.lock().expect("not poisoned").as_mut()
+                    {
+                        if deterministic_loss.next_is_loss() {
+                            return;
+                        }
+                    }
+                    received_bytes.fetch_add(packet.data.len() as u64, atomic::Ordering::Relaxed);
+                    network_as_receiver.receive_udp(injectable_network::Packet {
+                        source: packet.source,
+                        dest: packet.dest,
+                        data: packet.data,
+                    });
+                }),
+            )
+            .expect("add router interface");
+    }
+
     pub fn stop_network(&self) {
         if let Some(socket) = &self.socket {
             socket
@@ -43,6 +43,7 @@ pub trait SignalingRelay {
         sender_id: &PeerId,
         sender_device_id: DeviceId,
         recipient_id: &PeerId,
+        recipient_device_id: Option<DeviceId>,
         call_id: CallId,
         msg: Message,
     );
@@ -307,6 +308,7 @@ impl SignalingRelay for CallSimSignalingRelayClient {
         sender_id: &PeerId,
         sender_device_id: DeviceId,
         recipient_id: &PeerId,
+        _recipient_device_id: Option<DeviceId>,
         call_id: CallId,
         msg: Message,
     ) {
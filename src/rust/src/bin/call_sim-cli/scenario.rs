@@ -5,17 +5,19 @@
 
 use std::{
     collections::HashMap,
+    fmt,
     fs::File,
     path::PathBuf,
-    sync::mpsc::{channel, Receiver},
+    str::FromStr,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use log::*;
 use ringrtc::{
-    common::{actor::Stopper, CallConfig, CallId, CallMediaType, DeviceId},
+    common::{actor::Stopper, units, CallConfig, CallId, CallMediaType, DataMode, DeviceId},
     core::group_call::GroupId,
     lite::sfu::{GroupMember, MembershipProof, UserId},
     native::PeerId,
@@ -56,8 +58,174 @@ pub struct ScenarioConfig {
     pub output_video_width: u32,
     pub output_video_height: u32,
     pub video_output: Option<PathBuf>,
+    pub timing_markers: bool,
     pub deterministic_loss: Option<u8>,
     pub call_type_config: ScenarioCallTypeConfig,
+    pub schedule: ScenarioSchedule,
+}
+
+/// When a client joins and leaves a group call, and what it does while in the call, relative
+/// to being told to start.
+#[derive(Clone, Debug, Default)]
+pub struct ScenarioSchedule {
+    pub join_offset: Duration,
+    pub leave_offset: Option<Duration>,
+    /// Sorted by offset.
+    pub actions: Vec<(Duration, ScenarioAction)>,
+}
+
+impl ScenarioSchedule {
+    pub fn is_empty(&self) -> bool {
+        self.join_offset.is_zero() && self.leave_offset.is_none() && self.actions.is_empty()
+    }
+
+    /// Direct calls start as soon as the caller is told to, and can't be rejoined.
+    pub fn validate_for_direct_call(&self) -> Result<()> {
+        if !self.join_offset.is_zero() || self.leave_offset.is_some() {
+            bail!("joining and leaving at an offset is only supported for group calls");
+        }
+        if let Some((_, action)) = self
+            .actions
+            .iter()
+            .find(|(_, action)| action.is_group_call_only())
+        {
+            bail!("{} is only supported for group calls", action);
+        }
+        Ok(())
+    }
+}
+
+/// Something a client does during a call, given on the command line as `mute`, `unmute`,
+/// `video-on`, `video-off`, `screenshare-start`, `screenshare-stop`, `data-mode=low`,
+/// `data-mode=normal`, `data-mode=<max kbps>`, `hangup`, `rejoin`, `raise-hand`, `lower-hand`
+/// or `react=<value>`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScenarioAction {
+    Mute,
+    Unmute,
+    VideoOn,
+    VideoOff,
+    ScreenshareStart,
+    ScreenshareStop,
+    DataMode(DataMode),
+    Hangup,
+    Rejoin,
+    RaiseHand,
+    LowerHand,
+    React(String),
+}
+
+impl ScenarioAction {
+    pub fn is_group_call_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Rejoin | Self::RaiseHand | Self::LowerHand | Self::React(_)
+        )
+    }
+}
+
+impl fmt::Display for ScenarioAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Mute => write!(f, "mute"),
+            Self::Unmute => write!(f, "unmute"),
+            Self::VideoOn => write!(f, "video-on"),
+            Self::VideoOff => write!(f, "video-off"),
+            Self::ScreenshareStart => write!(f, "screenshare-start"),
+            Self::ScreenshareStop => write!(f, "screenshare-stop"),
+            Self::DataMode(DataMode::Low) => write!(f, "data-mode=low"),
+            Self::DataMode(DataMode::Normal) => write!(f, "data-mode=normal"),
+            Self::DataMode(DataMode::Custom { max_bitrate, .. }) => {
+                write!(f, "data-mode={}", max_bitrate.as_kbps())
+            }
+            Self::Hangup => write!(f, "hangup"),
+            Self::Rejoin => write!(f, "rejoin"),
+            Self::RaiseHand => write!(f, "raise-hand"),
+            Self::LowerHand => write!(f, "lower-hand"),
+            Self::React(value) => write!(f, "react={}", value),
+        }
+    }
+}
+
+impl FromStr for ScenarioAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.split_once('=') {
+            None => match s {
+                "mute" => Self::Mute,
+                "unmute" => Self::Unmute,
+                "video-on" => Self::VideoOn,
+                "video-off" => Self::VideoOff,
+                "screenshare-start" => Self::ScreenshareStart,
+                "screenshare-stop" => Self::ScreenshareStop,
+                "hangup" => Self::Hangup,
+                "rejoin" => Self::Rejoin,
+                "raise-hand" => Self::RaiseHand,
+                "lower-hand" => Self::LowerHand,
+                _ => bail!("unknown action \"{}\"", s),
+            },
+            Some(("data-mode", "low")) => Self::DataMode(DataMode::Low),
+            Some(("data-mode", "normal")) => Self::DataMode(DataMode::Normal),
+            Some(("data-mode", max_kbps)) => Self::DataMode(DataMode::Custom {
+                max_bitrate: units::DataRate::from_kbps(max_kbps.parse().with_context(|| {
+                    format!(
+                        "data-mode must be low, normal or a bitrate in kbps, not \"{max_kbps}\""
+                    )
+                })?),
+                max_group_call_receive_rate: units::DataRate::default(),
+            }),
+            Some(("react", value)) if !value.is_empty() => Self::React(value.to_string()),
+            _ => bail!("unknown action \"{}\"", s),
+        })
+    }
+}
+
+/// Parses `<offset secs>:<action>`.
+pub fn parse_timed_action(s: &str) -> Result<(Duration, ScenarioAction)> {
+    let (offset_secs, action) = s
+        .split_once(':')
+        .context("actions must be given as <offset secs>:<action>")?;
+    Ok((Duration::from_secs(offset_secs.parse()?), action.parse()?))
+}
+
+/// Waits until `offset` after `start`, returning false if told to stop first.
+pub fn wait_until(stop: &Receiver<()>, start: Instant, offset: Duration) -> bool {
+    matches!(
+        stop.recv_timeout(offset.saturating_sub(start.elapsed())),
+        Err(RecvTimeoutError::Timeout)
+    )
+}
+
+/// Performs the actions of a direct call at their offsets, until told to stop.
+pub fn perform_direct_call_actions(
+    client: &CallEndpoint,
+    actions: &[(Duration, ScenarioAction)],
+    start: Instant,
+    stop: &Receiver<()>,
+) {
+    for (offset, action) in actions {
+        if !wait_until(stop, start, *offset) {
+            return;
+        }
+        info!("Performing {}...", action);
+        client.perform_direct_call_action(action);
+    }
+}
+
+/// A client following its schedule on its own thread, so that commands can still be received.
+struct ScheduledActions {
+    /// Dropped to tell the thread to stop.
+    stop: Sender<()>,
+    /// Returns whether the client still needs to hang up.
+    thread: thread::JoinHandle<bool>,
+}
+
+impl ScheduledActions {
+    fn stop_and_join(self) -> bool {
+        drop(self.stop);
+        self.thread.join().unwrap_or(true)
+    }
 }
 
 #[derive(Clone)]
@@ -128,11 +296,16 @@ impl ScenarioManager {
         };
 
         let video_sink = scenario_config.video_output.as_ref().map(|path| {
-            Box::new(video::WriterVideoSink::new(
+            let sink = video::WriterVideoSink::new(
                 File::create(path).expect("open video output"),
                 scenario_config.output_video_width,
                 scenario_config.output_video_height,
-            )) as Box<dyn VideoSink>
+            );
+            if scenario_config.timing_markers {
+                Box::new(sink.with_timing_markers(name)) as Box<dyn VideoSink>
+            } else {
+                Box::new(sink) as Box<dyn VideoSink>
+            }
         });
 
         let packet_size_ms = call_config.audio_encoder_config.initial_packet_size_ms;
@@ -184,7 +357,7 @@ impl ScenarioManager {
         (client, client_sync)
     }
 
-    fn initialize_video_input(scenario_config: &ScenarioConfig) -> Option<I420Source<File>> {
+    pub fn initialize_video_input(scenario_config: &ScenarioConfig) -> Option<I420Source<File>> {
         scenario_config.video_input.as_ref().map(|path| {
             let ScenarioConfig {
                 mut video_width,
@@ -207,11 +380,16 @@ impl ScenarioManager {
                     .parse()
                     .expect("cannot parse video height from filename");
             }
-            video::I420Source::new(
+            let source = video::I420Source::new(
                 video_width,
                 video_height,
                 File::open(path).expect("open video input"),
-            )
+            );
+            if scenario_config.timing_markers {
+                source.with_timing_markers()
+            } else {
+                source
+            }
         })
     }
 
@@ -253,11 +431,19 @@ impl ScenarioManager {
                             client_sync,
                             video_input,
                             scenario_config.call_type_config,
+                            scenario_config.schedule,
                         )
                         .await
                     }
                     ScenarioCallTypeConfig::DirectCallConfig { .. } => {
-                        Self::run_direct(stream, client, client_sync, video_input).await
+                        Self::run_direct(
+                            stream,
+                            client,
+                            client_sync,
+                            video_input,
+                            scenario_config.schedule,
+                        )
+                        .await
                     }
                 }
             });
@@ -285,8 +471,10 @@ impl ScenarioManager {
         client: CallEndpoint,
         client_sync: ClientSync,
         mut video_input: Option<I420Source<File>>,
+        schedule: ScenarioSchedule,
     ) {
         info!("run_direct(): starting loop");
+        let mut scheduled_actions = None;
         loop {
             // Wait forever for commands, until STOP. Most operations are handled
             // asynchronously by an Actor...
@@ -296,6 +484,7 @@ impl ScenarioManager {
                     match Command::try_from(message.command) {
                         Ok(Command::StartAsCaller) => {
                             info!("command_message::Command::StartAsCaller");
+                            let start = Instant::now();
                             // Run the call (the callee_id doesn't need to be the actual
                             // id for testing).
                             let call_id = CallId::new(0xCA111D);
@@ -317,9 +506,13 @@ impl ScenarioManager {
                                     Duration::from_secs(1),
                                 )
                             }
+
+                            scheduled_actions =
+                                Self::schedule_direct_call_actions(&client, &schedule, start);
                         }
                         Ok(Command::StartAsCallee) => {
                             info!("command_message::Command::StartAsCallee");
+                            let start = Instant::now();
                             // We should know what the incoming call_id is, but for now,
                             // hardcode it like the original implementation.
                             let call_id = CallId::new(0xCA111D);
@@ -341,9 +534,15 @@ impl ScenarioManager {
                                     Duration::from_secs(1),
                                 )
                             }
+
+                            scheduled_actions =
+                                Self::schedule_direct_call_actions(&client, &schedule, start);
                         }
                         Ok(Command::Stop) => {
                             info!("command_message::Command::Stop");
+                            if let Some(scheduled_actions) = scheduled_actions.take() {
+                                scheduled_actions.stop_and_join();
+                            }
                             client.hangup();
                             client.stop_network();
 
@@ -376,6 +575,7 @@ impl ScenarioManager {
         client_sync: ClientSync,
         mut video_input: Option<I420Source<File>>,
         group_scenario_config: ScenarioCallTypeConfig,
+        schedule: ScenarioSchedule,
     ) {
         info!("run_group(): starting loop");
 
@@ -389,6 +589,12 @@ impl ScenarioManager {
             panic!("expected a group call config")
         };
 
+        let ClientSync {
+            connected, stopper, ..
+        } = client_sync;
+        let mut connected = Some(connected);
+        let mut participation = None;
+
         loop {
             // Wait forever for commands, until STOP. Most operations are handled
             // asynchronously by an Actor...
@@ -396,59 +602,36 @@ impl ScenarioManager {
                 Ok(Some(message)) => {
                     info!("ready(): Message to us? {}", message.client);
                     match Command::try_from(message.command) {
-                        Ok(Command::StartAsCaller) => {
-                            info!("command_message::Command::StartAsCaller");
-                            client.join_group_call(
-                                sfu_url.clone(),
-                                group_id.clone(),
-                                membership_proof.clone(),
-                            );
-
-                            info!("Waiting to be connected...");
-                            let _ = client_sync.connected.recv();
-                            info!("Now in the group call...");
-
-                            if let Some(video_input) = video_input.take() {
-                                client.send_video(
-                                    video_input,
-                                    video::FRAME_INTERVAL_30FPS,
-                                    Duration::from_secs(1),
-                                )
-                            }
-
-                            info!("finished command_message::Command::StartAsCaller");
-                        }
-                        Ok(Command::StartAsCallee) => {
-                            info!("command_message::Command::StartAsCallee");
+                        Ok(command @ (Command::StartAsCaller | Command::StartAsCallee)) => {
+                            info!("command_message::Command::{:?}", command);
                             // TODO: implement wait for group ring
-                            client.join_group_call(
-                                sfu_url.clone(),
-                                group_id.clone(),
-                                membership_proof.clone(),
-                            );
-
-                            info!("Waiting to be connected...");
-                            let _ = client_sync.connected.recv();
-                            info!("Now in the call...");
-
-                            if let Some(video_input) = video_input.take() {
-                                client.send_video(
-                                    video_input,
-                                    video::FRAME_INTERVAL_30FPS,
-                                    Duration::from_secs(1),
-                                )
+                            if let Some(connected) = connected.take() {
+                                participation = Some(Self::participate_in_group_call(
+                                    client.clone(),
+                                    connected,
+                                    video_input.take(),
+                                    sfu_url.clone(),
+                                    group_id.clone(),
+                                    membership_proof.clone(),
+                                    schedule.clone(),
+                                ));
                             }
-                            info!("finished command_message::Command::StartAsCallee");
+                            info!("finished command_message::Command::{:?}", command);
                         }
                         Ok(Command::Stop) => {
                             info!("command_message::Command::Stop");
-                            client.hangup_group_call();
+                            let in_call = participation
+                                .take()
+                                .map_or(true, ScheduledActions::stop_and_join);
+                            if in_call {
+                                client.hangup_group_call();
+                            }
                             client.stop_network();
 
                             // Then let the hangup settle.
                             thread::sleep(Duration::from_millis(100));
 
-                            client_sync.stopper.stop_all_and_join();
+                            stopper.stop_all_and_join();
 
                             info!("finished command_message::Command::Stop");
                             break;
@@ -468,4 +651,130 @@ impl ScenarioManager {
         }
         info!("Done with scenario.");
     }
+
+    /// Performs a direct call's actions on another thread, if it has any.
+    fn schedule_direct_call_actions(
+        client: &CallEndpoint,
+        schedule: &ScenarioSchedule,
+        start: Instant,
+    ) -> Option<ScheduledActions> {
+        if schedule.actions.is_empty() {
+            return None;
+        }
+        let (stop_tx, stop_rx) = channel::<()>();
+        let client = client.clone();
+        let actions = schedule.actions.clone();
+        let thread = thread::spawn(move || {
+            perform_direct_call_actions(&client, &actions, start, &stop_rx);
+            true
+        });
+        Some(ScheduledActions {
+            stop: stop_tx,
+            thread,
+        })
+    }
+
+    /// Joins the group call and then performs its actions and leaves according to the
+    /// schedule, until told to stop.
+    fn participate_in_group_call(
+        client: CallEndpoint,
+        connected: Receiver<()>,
+        video_input: Option<I420Source<File>>,
+        sfu_url: String,
+        group_id: GroupId,
+        membership_proof: MembershipProof,
+        schedule: ScenarioSchedule,
+    ) -> ScheduledActions {
+        let (stop_tx, stop_rx) = channel::<()>();
+        let start = Instant::now();
+
+        let thread = thread::spawn(move || {
+            if !wait_until(&stop_rx, start, schedule.join_offset) {
+                return false;
+            }
+            client.join_group_call(sfu_url.clone(), group_id.clone(), membership_proof.clone());
+
+            info!("Waiting to be connected...");
+            loop {
+                match connected.recv_timeout(Duration::from_millis(100)) {
+                    Ok(()) => break,
+                    Err(RecvTimeoutError::Disconnected) => return true,
+                    Err(RecvTimeoutError::Timeout) => {
+                        if !matches!(stop_rx.try_recv(), Err(TryRecvError::Empty)) {
+                            return true;
+                        }
+                    }
+                }
+            }
+            info!("Now in the group call...");
+
+            if let Some(video_input) = video_input {
+                client.send_video(
+                    video_input,
+                    video::FRAME_INTERVAL_30FPS,
+                    Duration::from_secs(1),
+                )
+            }
+
+            let mut in_call = true;
+            for (offset, action) in &schedule.actions {
+                if schedule
+                    .leave_offset
+                    .is_some_and(|leave_offset| leave_offset <= *offset)
+                {
+                    break;
+                }
+                if !wait_until(&stop_rx, start, *offset) {
+                    return in_call;
+                }
+                info!("Performing {}...", action);
+                match action {
+                    ScenarioAction::Hangup | ScenarioAction::Rejoin
+                        if in_call == (*action == ScenarioAction::Rejoin) =>
+                    {
+                        warn!(
+                            "Ignoring {}, since the client is {} the call",
+                            action,
+                            if in_call { "already in" } else { "not in" }
+                        );
+                    }
+                    ScenarioAction::Hangup => {
+                        client.hangup_group_call();
+                        in_call = false;
+                    }
+                    ScenarioAction::Rejoin => {
+                        client.join_group_call(
+                            sfu_url.clone(),
+                            group_id.clone(),
+                            membership_proof.clone(),
+                        );
+                        in_call = true;
+                    }
+                    action => client.perform_group_call_action(action),
+                }
+            }
+
+            match schedule.leave_offset {
+                Some(leave_offset) => {
+                    if !wait_until(&stop_rx, start, leave_offset) {
+                        return in_call;
+                    }
+                    if in_call {
+                        client.hangup_group_call();
+                    }
+                    false
+                }
+                None => {
+                    // Stay in the call until told to stop.
+                    let _ = stop_rx.recv();
+                    in_call
+                }
+            }
+        });
+
+        ScheduledActions {
+            stop: stop_tx,
+            thread,
+        }
+    }
 }
@@ -0,0 +1,796 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Runs several endpoints in one process, linked by a simulated network rather than by Docker
+//! containers with netem. Signaling is relayed in-process too, so nothing outside the process
+//! is needed.
+//!
+//! The call simulator describes the run in a JSON file (see [SimnetConfig]). Each endpoint gets
+//! the same arguments it would get when running alone, and writes the same log and stats files,
+//! so the results can be reported the same way.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    iter,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle, ThreadId},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use log::*;
+use ringrtc::{
+    common::{
+        actor::{Actor, Stopper},
+        units::{DataRate, DataSize},
+        CallId, CallMediaType, DeviceId,
+    },
+    core::{signaling::Message, util::uuid_to_string},
+    native::PeerId,
+    simnet::router::{LinkConfig, Router},
+    webrtc::{media::VideoSink, peer_connection_factory::IceServer},
+};
+use serde::Deserialize;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::{
+    call_config,
+    endpoint::{CallEndpoint, EventSync},
+    markers,
+    network::{DeterministicLoss, TrafficCounters},
+    relay::SignalingRelay,
+    scenario::{perform_direct_call_actions, ScenarioConfig, ScenarioManager, ScenarioSchedule},
+    schedule, stats_export_target, video, Args,
+};
+
+/// The call ID used by the endpoint at index 0. Each endpoint sees the call with its own ID
+/// (this plus its index), so that stats logged on WebRTC threads can be told apart.
+const BASE_CALL_ID: u64 = 0xCA111D;
+
+/// How long to let hangups settle before stopping everything.
+const HANGUP_SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// A run of endpoints in one process. This is written by the call simulator.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimnetConfig {
+    /// How long to run the call, from when it is started.
+    pub length_secs: u64,
+    /// One caller and any number of callees, which are devices of the user the caller calls.
+    pub endpoints: Vec<SimnetEndpointConfig>,
+    /// Changes to the links of every endpoint over time. Links don't add any delay, loss or
+    /// bandwidth limit until the first change.
+    #[serde(default)]
+    pub timeline: Vec<SimnetLinkChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimnetEndpointConfig {
+    pub role: SimnetRole,
+    /// The same arguments the endpoint would be given when running alone. `--ip` gives the
+    /// address of its interface on the simulated network.
+    pub args: Vec<String>,
+    /// Where to write resource stats once a second, in the same format as the call simulator
+    /// uses for Docker stats.
+    pub stats_file: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimnetRole {
+    Caller,
+    Callee,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimnetLinkChange {
+    pub offset_secs: u64,
+    pub link: SimnetLink,
+}
+
+/// How packets sent by an endpoint behave.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimnetLink {
+    pub delay_min_ms: u64,
+    pub delay_max_ms: u64,
+    pub loss_probability: f64,
+    /// The probability of losing a packet right after another was lost.
+    pub repeated_loss_probability: f64,
+    /// 0 means unlimited.
+    pub rate_kbps: u64,
+    /// 0 means unlimited.
+    pub queue_size_bytes: u64,
+}
+
+impl SimnetLink {
+    fn link_config(&self) -> LinkConfig {
+        LinkConfig {
+            delay_min: Duration::from_millis(self.delay_min_ms),
+            delay_max: Duration::from_millis(self.delay_max_ms.max(self.delay_min_ms)),
+            loss_probability: self.loss_probability,
+            repeated_loss_probability: self.repeated_loss_probability,
+            // Unlimited still has to be some rate, but this is much more than a call needs.
+            rate: if self.rate_kbps == 0 {
+                DataRate::from_mbps(1000)
+            } else {
+                DataRate::from_kbps(self.rate_kbps)
+            },
+            queue_size: if self.queue_size_bytes == 0 {
+                DataSize::from_megabytes(1000)
+            } else {
+                DataSize::from_bytes(self.queue_size_bytes)
+            },
+        }
+    }
+}
+
+/// Relays signaling between endpoints in the same process. Unlike the call simulator's
+/// signaling server, which sends every message to every other client, messages only go to the
+/// endpoints of the user they're addressed to (and only to the device, if one is given).
+///
+/// Each endpoint gets its own handle (see [SimnetSignalingRelay::for_endpoint]), which knows
+/// the endpoint's index so call IDs can be translated.
+#[derive(Clone)]
+pub struct SimnetSignalingRelay {
+    index: usize,
+    actor: Actor<SimnetSignalingRelayState>,
+}
+
+struct SimnetSignalingRelayState {
+    endpoints: BTreeMap<usize, CallEndpoint>,
+}
+
+impl SimnetSignalingRelay {
+    pub fn start(stopper: &Stopper) -> Result<Self> {
+        Ok(Self {
+            index: 0,
+            actor: Actor::start("SimnetSignalingRelay", stopper.clone(), move |_actor| {
+                Ok(SimnetSignalingRelayState {
+                    endpoints: BTreeMap::new(),
+                })
+            })?,
+        })
+    }
+
+    pub fn for_endpoint(&self, index: usize) -> Self {
+        Self {
+            index,
+            actor: self.actor.clone(),
+        }
+    }
+
+    pub fn local_call_id(index: usize) -> CallId {
+        CallId::new(BASE_CALL_ID.wrapping_add(index as u64))
+    }
+
+    fn translate_call_id(call_id: CallId, from: usize, to: usize) -> CallId {
+        CallId::new(
+            call_id
+                .as_u64()
+                .wrapping_sub(from as u64)
+                .wrapping_add(to as u64),
+        )
+    }
+}
+
+impl SignalingRelay for SimnetSignalingRelay {
+    fn register(&self, endpoint: &CallEndpoint) {
+        // To send across threads
+        let index = self.index;
+        let endpoint = endpoint.clone();
+
+        self.actor.send(move |state| {
+            info!("SimnetSignalingRelay: Registered {}", endpoint.relay_id());
+            state.endpoints.insert(index, endpoint);
+        });
+    }
+
+    fn send_signaling(
+        &self,
+        sender_id: &PeerId,
+        sender_device_id: DeviceId,
+        recipient_id: &PeerId,
+        recipient_device_id: Option<DeviceId>,
+        call_id: CallId,
+        msg: Message,
+    ) {
+        // To send across threads
+        let index = self.index;
+        let sender_id = sender_id.clone();
+        let recipient_id = recipient_id.clone();
+
+        self.actor.send(move |state| {
+            let mut delivered = false;
+            for (&recipient_index, endpoint) in &state.endpoints {
+                if recipient_index != index
+                    && endpoint.peer_id() == recipient_id
+                    && recipient_device_id.is_none_or(|device_id| device_id == endpoint.device_id)
+                {
+                    endpoint.receive_signaling(
+                        &sender_id,
+                        sender_device_id,
+                        Self::translate_call_id(call_id, index, recipient_index),
+                        msg.clone(),
+                    );
+                    delivered = true;
+                }
+            }
+            if !delivered {
+                warn!(
+                    "SimnetSignalingRelay: No endpoint for {}:{:?}",
+                    recipient_id, recipient_device_id
+                );
+            }
+        });
+    }
+
+    fn send_call_message(
+        &self,
+        sender_id: &PeerId,
+        sender_device_id: DeviceId,
+        recipient_id: Vec<u8>,
+        opaque_message: Vec<u8>,
+    ) {
+        // To send across threads
+        let index = self.index;
+        let sender_id = sender_id.clone();
+
+        self.actor.send(move |state| {
+            let mut delivered = false;
+            for (&recipient_index, endpoint) in &state.endpoints {
+                if recipient_index != index && endpoint.user_id.as_ref() == Some(&recipient_id) {
+                    endpoint.receive_call_message(
+                        &sender_id,
+                        sender_device_id,
+                        Instant::now(),
+                        opaque_message.clone(),
+                    );
+                    delivered = true;
+                }
+            }
+            if !delivered {
+                warn!(
+                    "SimnetSignalingRelay: No endpoint for user {}",
+                    uuid_to_string(&recipient_id)
+                );
+            }
+        });
+    }
+}
+
+/// Writes everything to the process log, and also writes what can be attributed to an endpoint
+/// to that endpoint's log.
+///
+/// Records are attributed by thread: an endpoint's own thread is known by its name, and the
+/// WebRTC thread that logs an endpoint's stats is known by the call ID in its first stats line.
+/// Timing markers name their endpoint, since they can be logged from threads that are shared.
+struct EndpointLogger {
+    state: Mutex<EndpointLoggerState>,
+}
+
+struct EndpointLoggerState {
+    /// None means stdout.
+    process_log: Option<BufWriter<File>>,
+    endpoints: Vec<LoggedEndpoint>,
+    endpoint_by_thread: HashMap<ThreadId, usize>,
+}
+
+struct LoggedEndpoint {
+    thread_name: String,
+    call_id: String,
+    log: Option<BufWriter<File>>,
+}
+
+impl EndpointLogger {
+    fn install(process_log_file: Option<&str>, endpoint_args: &[Args]) -> Result<()> {
+        let process_log = process_log_file
+            .map(|path| File::create(path).map(BufWriter::new))
+            .transpose()?;
+        let endpoints = endpoint_args
+            .iter()
+            .enumerate()
+            .map(|(index, args)| {
+                Ok(LoggedEndpoint {
+                    thread_name: format!("endpoint-{}", args.name),
+                    call_id: SimnetSignalingRelay::local_call_id(index).to_string(),
+                    log: args
+                        .log_file
+                        .as_ref()
+                        .map(|path| File::create(path).map(BufWriter::new))
+                        .transpose()?,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        log::set_boxed_logger(Box::new(EndpointLogger {
+            state: Mutex::new(EndpointLoggerState {
+                process_log,
+                endpoints,
+                endpoint_by_thread: HashMap::new(),
+            }),
+        }))?;
+        log::set_max_level(LevelFilter::Debug);
+        Ok(())
+    }
+}
+
+impl EndpointLoggerState {
+    fn endpoint_for_current_thread(&mut self, message: &str) -> Option<usize> {
+        // Example: ringrtc_marker!,client_b,video,0,1667611058243,85
+        if let Some(marker) = message.strip_prefix("ringrtc_marker!,") {
+            let name = marker.split(',').next()?;
+            return self
+                .endpoints
+                .iter()
+                .position(|endpoint| endpoint.thread_name.strip_prefix("endpoint-") == Some(name));
+        }
+
+        let thread = thread::current();
+        if let Some(index) = self.endpoint_by_thread.get(&thread.id()) {
+            return Some(*index);
+        }
+
+        let index = thread
+            .name()
+            .and_then(|name| {
+                self.endpoints
+                    .iter()
+                    .position(|endpoint| endpoint.thread_name == name)
+            })
+            .or_else(|| {
+                // Example: ringrtc_stats!,connection,0xca111d,1667611058243536,0ms,100000bps
+                let call_id = message
+                    .strip_prefix("ringrtc_stats!,connection,")?
+                    .split(',')
+                    .next()?;
+                self.endpoints
+                    .iter()
+                    .position(|endpoint| endpoint.call_id == call_id)
+            })?;
+        self.endpoint_by_thread.insert(thread.id(), index);
+        Some(index)
+    }
+}
+
+impl log::Log for EndpointLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Debug
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = record.args().to_string();
+        let line = format!(
+            "[{} {} {}:{}] {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.file().unwrap_or_default(),
+            record.line().unwrap_or_default(),
+            message
+        );
+
+        let mut state = self.state.lock().expect("not poisoned");
+        if let Some(process_log) = &mut state.process_log {
+            let _ = process_log.write_all(line.as_bytes());
+        } else {
+            let _ = io::stdout().write_all(line.as_bytes());
+        }
+        if let Some(index) = state.endpoint_for_current_thread(&message) {
+            if let Some(log) = &mut state.endpoints[index].log {
+                let _ = log.write_all(line.as_bytes());
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let mut state = self.state.lock().expect("not poisoned");
+        if let Some(process_log) = &mut state.process_log {
+            let _ = process_log.flush();
+        }
+        for endpoint in &mut state.endpoints {
+            if let Some(log) = &mut endpoint.log {
+                let _ = log.flush();
+            }
+        }
+    }
+}
+
+/// Writes CPU and memory usage of the process, and the bitrates of each endpoint, once a
+/// second until stopped.
+///
+/// Since the endpoints share the process, they all report the same CPU and memory usage.
+fn start_resource_stats(
+    endpoints: Vec<(String, TrafficCounters)>,
+    stop: Arc<AtomicBool>,
+) -> Result<JoinHandle<()>> {
+    let pid = sysinfo::get_current_pid().map_err(|err| anyhow!(err))?;
+    let mut files = endpoints
+        .into_iter()
+        .map(|(path, traffic)| {
+            let mut file = BufWriter::new(File::create(&path)?);
+            file.write_all(b"Timestamp\tCPU\tMEM\tTX_Bitrate\tRX_Bitrate\n")?;
+            Ok((file, traffic, 0u64, 0u64))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(thread::spawn(move || {
+        let mut system = System::new();
+        let mut previous_time = Instant::now();
+        while !stop.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_secs(1));
+
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                ProcessRefreshKind::new().with_cpu().with_memory(),
+            );
+            let (cpu_usage, memory) = system
+                .process(pid)
+                .map_or((0.0, 0), |process| (process.cpu_usage(), process.memory()));
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("after the epoch")
+                .as_millis();
+            let seconds = previous_time.elapsed().as_secs_f64();
+            previous_time = Instant::now();
+
+            for (file, traffic, previous_sent, previous_received) in &mut files {
+                let sent = traffic.sent_bytes();
+                let received = traffic.received_bytes();
+                let _ = writeln!(
+                    file,
+                    "{}\t{:.2}\t{}\t{:.0}\t{:.0}",
+                    timestamp,
+                    cpu_usage,
+                    memory,
+                    (sent - *previous_sent) as f64 * 8.0 / seconds,
+                    (received - *previous_received) as f64 * 8.0 / seconds,
+                );
+                *previous_sent = sent;
+                *previous_received = received;
+            }
+        }
+        for (file, ..) in &mut files {
+            let _ = file.flush();
+        }
+    }))
+}
+
+struct SimnetClient {
+    endpoint: CallEndpoint,
+    ip: IpAddr,
+    role: SimnetRole,
+    callee_id: PeerId,
+    call_id: CallId,
+    ringing: Receiver<()>,
+    connected: Receiver<()>,
+    video_input: Option<video::I420Source<File>>,
+    schedule: ScenarioSchedule,
+}
+
+/// The ID an endpoint started with these arguments is known by (see [CallEndpoint::peer_id]).
+fn peer_id(args: &Args) -> PeerId {
+    args.user_id
+        .as_ref()
+        .map_or(args.name.clone(), |id| uuid_to_string(id))
+}
+
+/// Runs the endpoints described by the given file until the call is over.
+pub fn run(config_file: &str, log_file: Option<&str>) -> Result<()> {
+    let config: SimnetConfig = serde_json::from_reader(BufReader::new(
+        File::open(config_file).with_context(|| format!("failed to open {config_file}"))?,
+    ))
+    .with_context(|| format!("failed to parse {config_file}"))?;
+
+    if config.endpoints.len() < 2 {
+        bail!("a simnet run needs at least two endpoints");
+    }
+    if config
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint.role == SimnetRole::Caller)
+        .count()
+        != 1
+    {
+        bail!("a simnet run needs exactly one caller");
+    }
+
+    let endpoint_args = config
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            Args::try_parse_from(
+                iter::once("call_sim-cli").chain(endpoint.args.iter().map(String::as_str)),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for args in &endpoint_args {
+        if args.is_group_call {
+            bail!(
+                "{}: group calls can't be run on a simulated network",
+                args.name
+            );
+        }
+        schedule(args)
+            .validate_for_direct_call()
+            .with_context(|| args.name.clone())?;
+        if !args.relay_servers.is_empty() {
+            bail!(
+                "{}: relay servers can't be used on a simulated network",
+                args.name
+            );
+        }
+        if args.soak_sample_interval_secs.is_some() {
+            bail!(
+                "{}: soak mode can't be used on a simulated network",
+                args.name
+            );
+        }
+        if stats_export_target(args).is_some() {
+            bail!(
+                "{}: stats can't be exported on a simulated network",
+                args.name
+            );
+        }
+        if args.field_trials != endpoint_args[0].field_trials {
+            bail!("all endpoints in a simnet run must use the same field trials");
+        }
+    }
+
+    // The caller calls one user, so every callee must be one of that user's devices.
+    let callee_ids: BTreeSet<PeerId> = config
+        .endpoints
+        .iter()
+        .zip(&endpoint_args)
+        .filter(|(endpoint, _)| endpoint.role == SimnetRole::Callee)
+        .map(|(_, args)| peer_id(args))
+        .collect();
+    let Some(callee_id) = callee_ids.first().cloned() else {
+        bail!("a simnet run needs at least one callee");
+    };
+    if callee_ids.len() > 1 {
+        bail!("all callees in a simnet run must have the same --user-id");
+    }
+    let relay_ids: BTreeSet<(PeerId, DeviceId)> = endpoint_args
+        .iter()
+        .map(|args| (peer_id(args), args.device_id))
+        .collect();
+    if relay_ids.len() != endpoint_args.len() {
+        bail!("endpoints in a simnet run with the same user must have different --device-id");
+    }
+
+    EndpointLogger::install(log_file, &endpoint_args)?;
+
+    // Show WebRTC logs via application Logger while debugging.
+    ringrtc::webrtc::logging::set_logger(log::LevelFilter::Debug);
+
+    info!("Setting field trials to {}", &endpoint_args[0].field_trials);
+    ringrtc::webrtc::field_trial::init(&endpoint_args[0].field_trials).expect("no null characters");
+
+    info!("Starting simnet run...");
+
+    let stopper = Stopper::new();
+    let router = Router::start(&stopper)?;
+    let relay = SimnetSignalingRelay::start(&stopper)?;
+    let ideal_link = SimnetLink::default().link_config();
+
+    let mut clients = vec![];
+    let mut stats_files = vec![];
+    for (index, (endpoint_config, args)) in config.endpoints.iter().zip(&endpoint_args).enumerate()
+    {
+        let call_config = call_config(args);
+        let ip: IpAddr = args
+            .ip
+            .parse()
+            .with_context(|| format!("{}: invalid IP address \"{}\"", args.name, args.ip))?;
+
+        let (ringing_tx, ringing_rx) = channel();
+        let (connected_tx, connected_rx) = channel();
+        let event_sync = EventSync {
+            ringing: Some(ringing_tx),
+            connected: Some(connected_tx),
+        };
+
+        let video_sink = args
+            .output_video_file
+            .as_ref()
+            .map(|path| -> Result<Box<dyn VideoSink>> {
+                let sink = video::WriterVideoSink::new(
+                    File::create(path)?,
+                    args.output_video_width,
+                    args.output_video_height,
+                );
+                if args.timing_markers {
+                    Ok(Box::new(sink.with_timing_markers(&args.name)))
+                } else {
+                    Ok(Box::new(sink))
+                }
+            })
+            .transpose()?;
+
+        let deterministic_loss = args
+            .deterministic_loss
+            .map(|loss_rate| {
+                DeterministicLoss::new(
+                    loss_rate,
+                    call_config.audio_encoder_config.initial_packet_size_ms,
+                    10,
+                )
+            })
+            .transpose()?;
+
+        let mut endpoint = CallEndpoint::new(
+            &args.name,
+            args.device_id,
+            args.user_id.clone(),
+            &call_config.audio_config,
+            Box::new(relay.for_endpoint(index)),
+            &stopper,
+            event_sync,
+            video_sink,
+            true,
+        )?;
+        endpoint.init_direct_settings(false, &IceServer::none(), call_config);
+
+        let traffic = TrafficCounters::default();
+        endpoint.add_simnet_interface(
+            ip,
+            &router,
+            ideal_link.clone(),
+            ideal_link.clone(),
+            deterministic_loss,
+            traffic.clone(),
+        );
+        if let Some(stats_file) = &endpoint_config.stats_file {
+            stats_files.push((stats_file.clone(), traffic));
+        }
+
+        let video_input = ScenarioManager::initialize_video_input(&ScenarioConfig {
+            video_width: args.input_video_width,
+            video_height: args.input_video_height,
+            video_input: args.input_video_file.as_ref().map(Into::into),
+            timing_markers: args.timing_markers,
+            ..Default::default()
+        });
+        if args.timing_markers && !args.output_file.is_empty() {
+            markers::log_audio_start(&args.name, args.output_file.clone().into());
+        }
+
+        clients.push(SimnetClient {
+            endpoint,
+            ip,
+            role: endpoint_config.role,
+            callee_id: callee_id.clone(),
+            call_id: SimnetSignalingRelay::local_call_id(index),
+            ringing: ringing_rx,
+            connected: connected_rx,
+            video_input,
+            schedule: schedule(args),
+        });
+    }
+
+    let ips: Vec<IpAddr> = clients.iter().map(|client| client.ip).collect();
+    let apply_link = |link: &SimnetLink| {
+        for ip in &ips {
+            // Like netem on each container's interface, the link only affects what is sent.
+            router.set_interface_configs(*ip, link.link_config(), ideal_link.clone());
+        }
+    };
+
+    let mut timeline = config.timeline.iter().peekable();
+    while let Some(change) = timeline.next_if(|change| change.offset_secs == 0) {
+        info!("Setting up the simulated network.");
+        apply_link(&change.link);
+    }
+
+    let stop_stats = Arc::new(AtomicBool::new(false));
+    let resource_stats = start_resource_stats(stats_files, stop_stats.clone())?;
+
+    let endpoints: Vec<CallEndpoint> = clients
+        .iter()
+        .map(|client| client.endpoint.clone())
+        .collect();
+    // Dropped to stop performing actions.
+    let mut stop_actions = vec![];
+    let call_threads: Vec<JoinHandle<()>> = clients
+        .into_iter()
+        .map(|client| {
+            let (stop_tx, stop_rx) = channel();
+            stop_actions.push(stop_tx);
+            thread::spawn(move || {
+                let SimnetClient {
+                    endpoint,
+                    role,
+                    callee_id,
+                    call_id,
+                    ringing,
+                    connected,
+                    video_input,
+                    schedule,
+                    ..
+                } = client;
+                let start = Instant::now();
+                match role {
+                    SimnetRole::Caller => {
+                        endpoint.create_outgoing_direct_call(
+                            &callee_id,
+                            call_id,
+                            CallMediaType::Audio,
+                            endpoint.device_id,
+                        );
+                    }
+                    SimnetRole::Callee => {
+                        info!("{} is waiting to be ringing...", endpoint.name);
+                        if ringing.recv().is_err() {
+                            return;
+                        }
+                        endpoint.accept_incoming_direct_call(call_id);
+                    }
+                }
+
+                info!("{} is waiting to be connected...", endpoint.name);
+                if connected.recv().is_err() {
+                    return;
+                }
+                info!("{} is now in the call...", endpoint.name);
+
+                if let Some(video_input) = video_input {
+                    endpoint.send_video(
+                        video_input,
+                        video::FRAME_INTERVAL_30FPS,
+                        Duration::from_secs(1),
+                    );
+                }
+
+                perform_direct_call_actions(&endpoint, &schedule.actions, start, &stop_rx);
+            })
+        })
+        .collect();
+
+    let start_time = Instant::now();
+    while start_time.elapsed() < Duration::from_secs(config.length_secs) {
+        thread::sleep(Duration::from_secs(1));
+        while let Some(change) = timeline
+            .next_if(|change| start_time.elapsed() >= Duration::from_secs(change.offset_secs))
+        {
+            info!("Changing the simulated network.");
+            apply_link(&change.link);
+        }
+    }
+
+    info!("Stopping simnet run...");
+    drop(stop_actions);
+    for endpoint in &endpoints {
+        endpoint.hangup();
+    }
+
+    // Then let the hangup settle.
+    thread::sleep(HANGUP_SETTLE_TIME);
+
+    stop_stats.store(true, Ordering::Relaxed);
+    stopper.stop_all_and_join();
+    for call_thread in call_threads {
+        let _ = call_thread.join();
+    }
+    let _ = resource_stats.join();
+
+    info!("Done with simnet run.");
+    log::logger().flush();
+
+    Ok(())
+}
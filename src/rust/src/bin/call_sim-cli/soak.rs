@@ -0,0 +1,245 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Soak mode, for finding resources that keep growing over long calls.
+//!
+//! Every interval, the resources used by the process and RingRTC's internal counters (see
+//! `ringrtc::common::counters`) are sampled and logged. When the client is done, each series is
+//! checked for steady growth, and any that grew are logged too.
+//!
+//! Resources are read from `/proc`, so this only works on Linux.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use log::*;
+use ringrtc::common::counters;
+
+use crate::scenario::ScenarioAction;
+
+/// How long to stay out of the call when reconnecting.
+const RECONNECT_GAP: Duration = Duration::from_secs(2);
+/// Reconnects are scheduled until the client leaves, or for this long.
+const RECONNECT_HORIZON: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Samples from the start of the run are left out of growth checks, since things are still
+/// being set up.
+const WARM_UP_FRACTION: f64 = 0.1;
+/// The rest of the run is split into this many segments, which have to get steadily higher.
+const SEGMENTS: usize = 4;
+const MIN_SAMPLES_PER_SEGMENT: usize = 3;
+/// Growth smaller than this fraction of the starting level is just noise.
+const MIN_RELATIVE_GROWTH: f64 = 0.05;
+
+/// Hangs up and rejoins every `interval` while the client is in the call.
+pub fn reconnect_actions(
+    join_offset: Duration,
+    leave_offset: Option<Duration>,
+    interval: Duration,
+) -> Vec<(Duration, ScenarioAction)> {
+    let end = leave_offset.unwrap_or(join_offset + RECONNECT_HORIZON);
+    (1..)
+        .map(|count| join_offset + interval * count)
+        .take_while(|hangup| *hangup + RECONNECT_GAP < end)
+        .flat_map(|hangup| {
+            [
+                (hangup, ScenarioAction::Hangup),
+                (hangup + RECONNECT_GAP, ScenarioAction::Rejoin),
+            ]
+        })
+        .collect()
+}
+
+/// The resources used by this process.
+struct ProcessSampler {
+    clock_ticks_per_second: f64,
+    previous_cpu: Option<(Instant, u64)>,
+}
+
+impl ProcessSampler {
+    fn new() -> Result<Self> {
+        if !cfg!(target_os = "linux") {
+            bail!("soak mode is only supported on Linux");
+        }
+        // SAFETY: sysconf has no preconditions.
+        let clock_ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        if clock_ticks_per_second <= 0 {
+            bail!("can't get the clock tick rate");
+        }
+        Ok(Self {
+            clock_ticks_per_second: clock_ticks_per_second as f64,
+            previous_cpu: None,
+        })
+    }
+
+    /// User and system CPU time, in clock ticks.
+    fn cpu_ticks() -> Result<u64> {
+        let stat = fs::read_to_string("/proc/self/stat")?;
+        // The command can have spaces in it, but it's the only field in parentheses.
+        let fields: Vec<&str> = stat
+            .rsplit_once(')')
+            .context("unexpected /proc/self/stat")?
+            .1
+            .split_whitespace()
+            .collect();
+        // utime and stime are the 14th and 15th fields, counting from the PID.
+        let field = |index: usize| -> Result<u64> {
+            Ok(fields
+                .get(index)
+                .context("unexpected /proc/self/stat")?
+                .parse()?)
+        };
+        Ok(field(11)? + field(12)?)
+    }
+
+    fn sample(&mut self, values: &mut Vec<(String, f64)>) -> Result<()> {
+        let status = fs::read_to_string("/proc/self/status")?;
+        for line in status.lines() {
+            if let Some((name, value)) = line.split_once(':') {
+                let name = match name {
+                    "VmRSS" => "rss_kb",
+                    "Threads" => "threads",
+                    _ => continue,
+                };
+                let value = value.trim().trim_end_matches(" kB").parse::<f64>()?;
+                values.push((name.to_string(), value));
+            }
+        }
+
+        values.push((
+            "fds".to_string(),
+            fs::read_dir("/proc/self/fd")?.count() as f64,
+        ));
+
+        let now = Instant::now();
+        let cpu_ticks = Self::cpu_ticks()?;
+        if let Some((previous_time, previous_cpu_ticks)) = self.previous_cpu {
+            let seconds = (now - previous_time).as_secs_f64();
+            let cpu_seconds =
+                cpu_ticks.saturating_sub(previous_cpu_ticks) as f64 / self.clock_ticks_per_second;
+            values.push(("cpu_pct".to_string(), cpu_seconds * 100.0 / seconds));
+        }
+        self.previous_cpu = Some((now, cpu_ticks));
+        Ok(())
+    }
+}
+
+/// If a series grew steadily, returns how high it stayed at the start and at the end.
+///
+/// The lowest value in each segment is used, so that bursts (such as while reconnecting) don't
+/// count, and growth only counts if it happened in every segment.
+fn steady_growth(values: &[f64]) -> Option<(f64, f64)> {
+    let values = &values[(values.len() as f64 * WARM_UP_FRACTION) as usize..];
+    let segment_len = values.len() / SEGMENTS;
+    if segment_len < MIN_SAMPLES_PER_SEGMENT {
+        return None;
+    }
+    let floors: Vec<f64> = values
+        .chunks_exact(segment_len)
+        .take(SEGMENTS)
+        .map(|segment| segment.iter().copied().fold(f64::INFINITY, f64::min))
+        .collect();
+    let (first, last) = (floors[0], floors[SEGMENTS - 1]);
+    (floors.windows(2).all(|pair| pair[1] > pair[0])
+        && last - first > first.abs() * MIN_RELATIVE_GROWTH)
+        .then_some((first, last))
+}
+
+/// Samples on its own thread until finished.
+pub struct SoakSampler {
+    client_name: String,
+    /// Dropped to tell the thread to stop.
+    stop: Sender<()>,
+    thread: JoinHandle<BTreeMap<String, Vec<f64>>>,
+}
+
+impl SoakSampler {
+    pub fn start(client_name: &str, interval: Duration) -> Result<Self> {
+        let mut process = ProcessSampler::new()?;
+        let (stop, stop_rx) = channel::<()>();
+        let name = client_name.to_string();
+        let thread = thread::Builder::new()
+            .name(format!("soak-{}", client_name))
+            .spawn(move || {
+                let mut series: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+                loop {
+                    let mut values = vec![];
+                    if let Err(err) = process.sample(&mut values) {
+                        warn!("Failed to sample process resources: {}", err);
+                    }
+                    values.extend(
+                        counters::snapshot()
+                            .into_iter()
+                            .map(|(name, value)| (name, value as f64)),
+                    );
+
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("after the epoch")
+                        .as_millis();
+                    let line = values
+                        .iter()
+                        .map(|(name, value)| format!("{}={}", name, value))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    // Example: ringrtc_soak!,client_b,1667611058243,rss_kb=81234,threads=42,fds=57,cpu_pct=12.5,crypto_senders=1
+                    info!("ringrtc_soak!,{},{},{}", name, now, line);
+
+                    for (name, value) in values {
+                        series.entry(name).or_default().push(value);
+                    }
+
+                    if !matches!(
+                        stop_rx.recv_timeout(interval),
+                        Err(RecvTimeoutError::Timeout)
+                    ) {
+                        return series;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            client_name: client_name.to_string(),
+            stop,
+            thread,
+        })
+    }
+
+    /// Stops sampling and logs anything that grew steadily.
+    pub fn finish(self) {
+        drop(self.stop);
+        let Ok(series) = self.thread.join() else {
+            warn!("Soak sampling failed");
+            return;
+        };
+
+        let mut growing = 0;
+        for (name, values) in &series {
+            if let Some((first, last)) = steady_growth(values) {
+                warn!(
+                    "{} grew steadily over the run, from {} to {}",
+                    name, first, last
+                );
+                // Example: ringrtc_soak_growth!,client_b,rss_kb,81234,95321
+                info!(
+                    "ringrtc_soak_growth!,{},{},{},{}",
+                    self.client_name, name, first, last
+                );
+                growing += 1;
+            }
+        }
+        info!(
+            "Soak sampling found {} of {} series growing steadily",
+            growing,
+            series.len()
+        );
+    }
+}
@@ -20,6 +20,8 @@ use ringrtc::{
     webrtc::media::{VideoFrame, VideoPixelFormat, VideoSink},
 };
 
+use crate::markers;
+
 pub const FRAME_INTERVAL_30FPS: Duration = Duration::from_millis(33);
 
 pub trait VideoInput {
@@ -34,6 +36,7 @@ pub struct I420Source<T> {
     height: u32,
     buffer: Vec<u8>,
     input: Option<T>,
+    timing_markers: bool,
 }
 
 impl<T: Seek> I420Source<T> {
@@ -54,8 +57,15 @@ impl<T: Seek> I420Source<T> {
             height,
             buffer: vec![0; frame_size],
             input: Some(input),
+            timing_markers: false,
         }
     }
+
+    /// Stamps each frame with the time it was produced, see [`markers`].
+    pub fn with_timing_markers(mut self) -> Self {
+        self.timing_markers = true;
+        self
+    }
 }
 
 impl<T: Read + Seek + Send> VideoInput for I420Source<T> {
@@ -78,6 +88,9 @@ impl<T: Read + Seek + Send> VideoInput for I420Source<T> {
                 }
             }
         }
+        if self.timing_markers {
+            markers::stamp_i420(self.width, self.height, &mut self.buffer);
+        }
         VideoFrame::copy_from_slice(
             self.width,
             self.height,
@@ -113,6 +126,8 @@ pub struct WriterVideoSink<T> {
     epoch: Instant,
     width: u32,
     height: u32,
+    /// The name of the client to log received timing markers for, if any.
+    marker_client: Option<Arc<str>>,
 }
 
 impl<T> WriterVideoSink<T> {
@@ -122,8 +137,16 @@ impl<T> WriterVideoSink<T> {
             epoch: Instant::now(),
             width,
             height,
+            marker_client: None,
         }
     }
+
+    /// Logs the delay of each received frame that was stamped by
+    /// [`I420Source::with_timing_markers`].
+    pub fn with_timing_markers(mut self, client_name: &str) -> Self {
+        self.marker_client = Some(client_name.into());
+        self
+    }
 }
 
 impl<T> Clone for WriterVideoSink<T> {
@@ -133,6 +156,7 @@ impl<T> Clone for WriterVideoSink<T> {
             epoch: self.epoch,
             width: self.width,
             height: self.height,
+            marker_client: self.marker_client.clone(),
         }
     }
 }
@@ -141,7 +165,7 @@ impl<T: Send + Sync + 'static> VideoSink for WriterVideoSink<T>
 where
     for<'a> &'a T: Write,
 {
-    fn on_video_frame(&self, _demux_id: DemuxId, frame: VideoFrame) {
+    fn on_video_frame(&self, demux_id: DemuxId, frame: VideoFrame) {
         let write_frame_data = |frame_data| {
             (&self.shared_state.0)
                 .write_all(frame_data)
@@ -166,6 +190,15 @@ where
         };
 
         let frame_data = input_frame.as_i420().expect("I420 data not available");
+        if let Some(client_name) = &self.marker_client {
+            markers::log_video_marker(
+                client_name,
+                demux_id,
+                input_frame.width(),
+                input_frame.height(),
+                frame_data,
+            );
+        }
         let elapsed = self.epoch.elapsed();
 
         let mut next_frame_elapsed =
@@ -8,7 +8,7 @@ use std::{
     collections::BinaryHeap,
     sync::{
         atomic,
-        atomic::AtomicBool,
+        atomic::{AtomicBool, AtomicUsize},
         mpsc::{channel, RecvError, RecvTimeoutError, Sender},
         Arc, Mutex,
     },
@@ -16,10 +16,15 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{common::Result, error::RingRtcError};
+use crate::{
+    common::{counters, Result},
+    error::RingRtcError,
+};
 
 pub struct Actor<State> {
     sender: Sender<Task<State>>,
+    /// Tasks that were sent but haven't been run yet, including delayed ones.
+    queued: Arc<AtomicUsize>,
     stopper: Stopper,
 }
 
@@ -29,7 +34,9 @@ impl<State: 'static> Actor<State> {
         stopper: Stopper,
         gen_state: impl FnOnce(Actor<State>) -> Result<State> + Send + 'static,
     ) -> Result<Self> {
+        let name = name.into();
         let (sender, receiver) = channel::<Task<State>>();
+        let queued = counters::register_actor_queue(&name);
 
         let stopper_to_register = stopper.clone();
 
@@ -39,16 +46,22 @@ impl<State: 'static> Actor<State> {
         let stopped = Arc::new(AtomicBool::new(false));
         let stopped_to_register = stopped.clone();
 
-        let actor = Self { sender, stopper };
+        let actor = Self {
+            sender,
+            queued: queued.clone(),
+            stopper,
+        };
         let actor_to_register = actor.clone();
         let actor_to_return = actor.clone();
         // Moves in actor and stopped
-        let join_handle = thread::Builder::new().name(name.into()).spawn(move || {
+        let join_handle = thread::Builder::new().name(name).spawn(move || {
             let mut state = match gen_state(actor) {
                 Ok(state) => state,
                 Err(e) => {
                     warn!("Failed to start actor because of {:?}", e);
                     // Never enter the loop.  As if we had been stopped before starting.
+                    drop(receiver);
+                    queued.store(0, atomic::Ordering::Relaxed);
                     return;
                 }
             };
@@ -89,9 +102,13 @@ impl<State: 'static> Actor<State> {
                         warn!("delayed_tasks exceed 2, len(): {}", delayed_tasks.len());
                     }
                 } else {
+                    queued.fetch_sub(1, atomic::Ordering::Relaxed);
                     (received_task.run)(&mut state);
                 }
             }
+            // Whatever is left will never be run.
+            drop(receiver);
+            queued.store(0, atomic::Ordering::Relaxed);
         })?;
         stopper_to_register.register_actor(
             Box::new(actor_to_register),
@@ -102,11 +119,11 @@ impl<State: 'static> Actor<State> {
     }
 
     pub fn send(&self, run: impl FnOnce(&mut State) + Send + 'static) {
-        let _ = self.sender.send(Task::immediate(Box::new(run)));
+        self.enqueue(Task::immediate(Box::new(run)));
     }
 
     pub fn send_delayed(&self, delay: Duration, run: impl FnOnce(&mut State) + Send + 'static) {
-        let _ = self.sender.send(Task::delayed(Box::new(run), delay));
+        self.enqueue(Task::delayed(Box::new(run), delay));
     }
 
     pub fn stopper(&self) -> &Stopper {
@@ -114,11 +131,27 @@ impl<State: 'static> Actor<State> {
     }
 }
 
+impl<State> Actor<State> {
+    fn enqueue(&self, task: Task<State>) {
+        // Counted before sending, so that it can't be run before it's counted.
+        self.queued.fetch_add(1, atomic::Ordering::Relaxed);
+        if self.sender.send(task).is_err() {
+            // The thread is gone, and may have already reset the count.
+            let _ = self.queued.fetch_update(
+                atomic::Ordering::Relaxed,
+                atomic::Ordering::Relaxed,
+                |queued| Some(queued.saturating_sub(1)),
+            );
+        }
+    }
+}
+
 // This doesn't #[derive] for some reason.
 impl<State> Clone for Actor<State> {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
+            queued: self.queued.clone(),
             stopper: self.stopper.clone(),
         }
     }
@@ -128,7 +161,7 @@ impl<State> Stop for Actor<State> {
     fn stop(&self, stopped: &AtomicBool) {
         stopped.store(true, atomic::Ordering::SeqCst);
         // Sending an empty message kicks the message loop if it's stuck.
-        let _ = self.sender.send(Task::immediate(Box::new(|_state| {})));
+        self.enqueue(Task::immediate(Box::new(|_state| {})));
     }
 }
 
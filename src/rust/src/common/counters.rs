@@ -0,0 +1,159 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Process-wide counts of internal state that should stay bounded over a call.
+//!
+//! They're cheap enough to always keep, and are meant to be sampled by long-running tests
+//! looking for state that grows without bound. Each object contributes its own part of a count
+//! with a [`CounterShare`], which it gives back when it's dropped.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+#[derive(Debug)]
+pub struct Counter {
+    name: &'static str,
+    value: AtomicUsize,
+}
+
+impl Counter {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            value: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// Senders with receiver states in every frame crypto context.
+pub static CRYPTO_SENDERS: Counter = Counter::new("crypto_senders");
+/// Receiver states in every frame crypto context.
+pub static CRYPTO_RECEIVER_STATES: Counter = Counter::new("crypto_receiver_states");
+/// Packets waiting to be acked by the SFU, in every group call's reliable stream.
+pub static MRP_SEND_WINDOW: Counter = Counter::new("mrp_send_window");
+/// Packets waiting for earlier ones from the SFU, in every group call's reliable stream.
+pub static MRP_RECEIVE_WINDOW: Counter = Counter::new("mrp_receive_window");
+
+const COUNTERS: [&Counter; 4] = [
+    &CRYPTO_SENDERS,
+    &CRYPTO_RECEIVER_STATES,
+    &MRP_SEND_WINDOW,
+    &MRP_RECEIVE_WINDOW,
+];
+
+/// An object's part of a [`Counter`].
+#[derive(Debug)]
+pub struct CounterShare {
+    counter: &'static Counter,
+    value: usize,
+}
+
+impl CounterShare {
+    pub fn new(counter: &'static Counter) -> Self {
+        Self { counter, value: 0 }
+    }
+
+    pub fn set(&mut self, value: usize) {
+        if value > self.value {
+            self.counter
+                .value
+                .fetch_add(value - self.value, Ordering::Relaxed);
+        } else {
+            self.counter
+                .value
+                .fetch_sub(self.value - value, Ordering::Relaxed);
+        }
+        self.value = value;
+    }
+}
+
+impl Drop for CounterShare {
+    fn drop(&mut self) {
+        self.set(0);
+    }
+}
+
+/// The queues of running actors, with the names of their threads.
+static ACTOR_QUEUES: Mutex<Vec<(String, Weak<AtomicUsize>)>> = Mutex::new(Vec::new());
+
+/// Registers the queue of an actor that is starting, returning the count of its tasks that
+/// haven't been run yet. The queue is forgotten once the count is dropped.
+pub(crate) fn register_actor_queue(name: &str) -> Arc<AtomicUsize> {
+    let queue = Arc::new(AtomicUsize::new(0));
+    let mut queues = ACTOR_QUEUES.lock().unwrap();
+    queues.retain(|(_, queue)| queue.strong_count() > 0);
+    queues.push((name.to_string(), Arc::downgrade(&queue)));
+    queue
+}
+
+/// The current value of every counter, along with the number of tasks queued for actors
+/// (summed by the names of their threads, as `actor_queue.<name>`), in order of name.
+pub fn snapshot() -> Vec<(String, usize)> {
+    let mut values: BTreeMap<String, usize> = COUNTERS
+        .iter()
+        .map(|counter| (counter.name().to_string(), counter.get()))
+        .collect();
+    for (name, queue) in ACTOR_QUEUES.lock().unwrap().iter() {
+        if let Some(queue) = queue.upgrade() {
+            *values.entry(format!("actor_queue.{}", name)).or_default() +=
+                queue.load(Ordering::Relaxed);
+        }
+    }
+    values.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_COUNTER: Counter = Counter::new("test");
+
+    #[test]
+    fn shares() {
+        let mut a = CounterShare::new(&TEST_COUNTER);
+        let mut b = CounterShare::new(&TEST_COUNTER);
+        a.set(3);
+        b.set(5);
+        assert_eq!(8, TEST_COUNTER.get());
+        a.set(1);
+        assert_eq!(6, TEST_COUNTER.get());
+        drop(b);
+        assert_eq!(1, TEST_COUNTER.get());
+        drop(a);
+        assert_eq!(0, TEST_COUNTER.get());
+    }
+
+    #[test]
+    fn actor_queues() {
+        let first = register_actor_queue("counters-test");
+        let second = register_actor_queue("counters-test");
+        first.store(2, Ordering::Relaxed);
+        second.store(1, Ordering::Relaxed);
+        let queued = |snapshot: Vec<(String, usize)>| {
+            snapshot
+                .into_iter()
+                .find(|(name, _)| name == "actor_queue.counters-test")
+                .map(|(_, value)| value)
+        };
+        assert_eq!(Some(3), queued(snapshot()));
+        drop(first);
+        assert_eq!(Some(1), queued(snapshot()));
+        drop(second);
+        assert_eq!(None, queued(snapshot()));
+    }
+}
@@ -6,6 +6,7 @@
 //! Common types used throughout the library.
 
 pub mod actor;
+pub mod counters;
 pub mod jni_signature;
 pub mod units;
 
@@ -326,6 +327,12 @@ pub enum ApplicationEvent {
     EndedRemoteHangupNeedPermission,
 
     /// The call ended because the call was accepted by a different device.
+    ///
+    /// When the accepting device is known, the CallManager calls
+    /// [`on_accepted_on_another_device`][] instead, whose default implementation
+    /// reports this event.
+    ///
+    /// [`on_accepted_on_another_device`]: crate::core::platform::Platform::on_accepted_on_another_device
     EndedRemoteHangupAccepted,
 
     /// The call ended because the call was declined by a different device.
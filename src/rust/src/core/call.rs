@@ -79,6 +79,8 @@ where
     media_type: CallMediaType,
     /// The local DeviceId of the client.
     local_device_id: DeviceId,
+    /// The call this one takes over from another device of the same user, if any.
+    replaced_call_id: Option<CallId>,
     /// The application specific remote peer of this call
     app_remote_peer: Arc<CallMutex<<T as Platform>::AppRemotePeer>>,
     /// The application specific context for this call
@@ -174,6 +176,7 @@ where
             direction: self.direction,
             media_type: self.media_type,
             local_device_id: self.local_device_id,
+            replaced_call_id: self.replaced_call_id,
             app_remote_peer: Arc::clone(&self.app_remote_peer),
             app_call_context: Arc::clone(&self.app_call_context),
             state: Arc::clone(&self.state),
@@ -203,6 +206,7 @@ where
         direction: CallDirection,
         media_type: CallMediaType,
         local_device_id: DeviceId,
+        replaced_call_id: Option<CallId>,
         call_manager: CallManager<T>,
     ) -> Result<Self> {
         info!("new(): call_id: {}", call_id);
@@ -220,6 +224,7 @@ where
             direction,
             media_type,
             local_device_id,
+            replaced_call_id,
             app_remote_peer: Arc::new(CallMutex::new(app_remote_peer, "app_remote_peer")),
             app_call_context: Arc::new(CallMutex::new(None, "app_call_context")),
             state: Arc::new(CallMutex::new(CallState::NotYetStarted, "state")),
@@ -335,6 +340,11 @@ where
         self.local_device_id
     }
 
+    /// Return the call this one takes over, if it is a handoff.
+    pub fn replaced_call_id(&self) -> Option<CallId> {
+        self.replaced_call_id
+    }
+
     /// Store the application specific CallContext associated with this call.
     pub fn set_call_context(&self, call_context: <T as Platform>::AppCallContext) -> Result<()> {
         let mut app_call_context = self.app_call_context.lock()?;
@@ -599,6 +609,10 @@ where
                 )?;
                 let (local_secret, ice_gatherer, offer) =
                     parent_connection.start_outgoing_parent(self.media_type)?;
+                let offer = match self.replaced_call_id {
+                    Some(replaced_call_id) => offer.with_replaced_call_id(replaced_call_id)?,
+                    None => offer,
+                };
 
                 // Keep around so that it's not closed until all the connections are closed.
                 *(self.forking.lock()?) = Some(ForkingState {
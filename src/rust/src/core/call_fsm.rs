@@ -620,8 +620,13 @@ where
             // Everything else is unexpected: warn, and mostly treat like normal, no propagation.
             // TODO: Isn't NeedPermission for incoming normal because it's propagated above?
             // Should we make this no_app_event_and_no_propagation?
+            // Caller gets AcceptedOnAnotherDevice: another of the caller's own devices took over
+            // the call (see CallManager::pull_call).
+            (signaling::HangupType::AcceptedOnAnotherDevice, CallDirection::Outgoing) => {
+                app_event_without_propagation(ApplicationEvent::EndedRemoteHangupAccepted)
+            }
+
             (signaling::HangupType::NeedPermission, CallDirection::Incoming) => unexpected,
-            (signaling::HangupType::AcceptedOnAnotherDevice, CallDirection::Outgoing) => unexpected,
             (signaling::HangupType::DeclinedOnAnotherDevice, CallDirection::Outgoing) => unexpected,
             (signaling::HangupType::BusyOnAnotherDevice, CallDirection::Outgoing) => unexpected,
         };
@@ -649,14 +654,24 @@ where
             }
         }
 
+        // Report which device accepted the call when we know it.
+        let accepted_on_device_id = match (hangup_type, hangup_device_id) {
+            (signaling::HangupType::AcceptedOnAnotherDevice, Some(device_id)) => Some(device_id),
+            _ => None,
+        };
+
         // Send a Hangup event to the UX, if a call is being remotely hungup, the user
         // should always know.
         self.schedule_work_even_when_terminating(
             call,
             "Processing remote hangup event failed",
             move |call| {
-                call.call_manager()?
-                    .remote_hangup(call.call_id(), app_event_override)
+                let mut call_manager = call.call_manager()?;
+                match accepted_on_device_id {
+                    Some(device_id) => call_manager
+                        .remote_hangup_accepted_on_another_device(call.call_id(), device_id),
+                    None => call_manager.remote_hangup(call.call_id(), app_event_override),
+                }
             },
         );
         Ok(())
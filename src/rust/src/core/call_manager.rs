@@ -19,6 +19,7 @@ use std::{
 use bytes::{Bytes, BytesMut};
 use lazy_static::lazy_static;
 use prost::Message;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
@@ -39,7 +40,9 @@ use crate::{
     },
     error::RingRtcError,
     lite::{
-        call_links::{self, CallLinkMemberResolver, CallLinkRootKey},
+        call_links::{
+            self, BreakoutRooms, CallLinkMemberResolver, CallLinkRootKey, CallLinkSchedule,
+        },
         http,
         sfu::{
             self, DemuxId, GroupMember, MemberMap, MembershipProof, ObfuscatedResolver, PeekInfo,
@@ -48,7 +51,7 @@ use crate::{
     },
     protobuf,
     webrtc::{
-        media::{AudioTrack, MediaStream, VideoSink, VideoTrack},
+        media::{AudioTrack, MediaStream, VideoEncoderConfig, VideoSink, VideoTrack},
         peer_connection::{AudioLevel, ReceivedAudioLevel},
         peer_connection_factory::PeerConnectionFactory,
         peer_connection_observer::NetworkRoute,
@@ -196,6 +199,7 @@ where
 #[derive(Debug)]
 struct OutstandingGroupRing {
     ring_id: group_call::RingId,
+    sender_uuid: UserId,
     received: Instant,
 }
 
@@ -220,6 +224,9 @@ enum ReceivedOfferCollision {
     /// An active call with the same user, but we were already connected but they
     /// are recalling us, so drop our call, no need to send hangup, they already ended
     ReCall,
+    /// An active call with the same user, but another of their devices is taking it
+    /// over, so drop our call, telling the device we were connected to about the new one
+    Handoff,
 }
 
 /// Management of 1:1 call messages that arrive before the offer for a particular call.
@@ -300,6 +307,165 @@ impl Default for PendingCallMessages {
     }
 }
 
+/// The version of [`PersistedState`] written by this build.
+const PERSISTED_STATE_VERSION: u32 = 1;
+
+/// A snapshot of the CallManager state that isn't tied to any media session.
+///
+/// If the app process is killed while a group ring is outstanding (as is routine for push-woken
+/// processes on mobile), the ring and any 1:1 messages that arrived ahead of their offer are
+/// lost. The app can save this snapshot using [`CallManager::persisted_state`] and hand it to the
+/// next CallManager using [`CallManager::restore_persisted_state`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedState {
+    version: u32,
+    /// When the snapshot was taken; used to age the saved rings and messages on restore.
+    saved_at: SystemTime,
+    self_uuid: Option<UserId>,
+    outstanding_group_rings: Vec<PersistedGroupRing>,
+    pending_call_messages: PersistedPendingCallMessages,
+}
+
+impl PersistedState {
+    /// Encodes the snapshot for the app to store.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("can always serialize persisted state")
+    }
+
+    /// Decodes a snapshot produced by [`PersistedState::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let state: Self = serde_json::from_slice(bytes)?;
+        if state.version != PERSISTED_STATE_VERSION {
+            return Err(anyhow::anyhow!(
+                "unsupported persisted state version {}",
+                state.version
+            ));
+        }
+        Ok(state)
+    }
+
+    /// Returns true if there's nothing worth restoring, so the app can skip saving it.
+    pub fn is_empty(&self) -> bool {
+        self.self_uuid.is_none()
+            && self.outstanding_group_rings.is_empty()
+            && self.pending_call_messages == PersistedPendingCallMessages::None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct PersistedGroupRing {
+    group_id: group_call::GroupId,
+    ring_id: i64,
+    sender_uuid: UserId,
+    /// How long ago the ring was received, as of `saved_at`.
+    age: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct PersistedIce {
+    sender_device_id: DeviceId,
+    candidates: Vec<Vec<u8>>,
+}
+
+/// The serializable form of [`PendingCallMessages`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum PersistedPendingCallMessages {
+    None,
+    IceCandidates {
+        call_id: u64,
+        received: Vec<PersistedIce>,
+    },
+    Hangup {
+        call_id: u64,
+        hangup_type: i32,
+        hangup_device_id: Option<DeviceId>,
+        sender_device_id: DeviceId,
+    },
+}
+
+impl From<&PendingCallMessages> for PersistedPendingCallMessages {
+    fn from(pending: &PendingCallMessages) -> Self {
+        match pending {
+            PendingCallMessages::None => Self::None,
+            PendingCallMessages::IceCandidates { call_id, received } => Self::IceCandidates {
+                call_id: call_id.as_u64(),
+                received: received
+                    .iter()
+                    .map(|received| PersistedIce {
+                        sender_device_id: received.sender_device_id,
+                        candidates: received
+                            .ice
+                            .candidates
+                            .iter()
+                            .map(|candidate| candidate.opaque.clone())
+                            .collect(),
+                    })
+                    .collect(),
+            },
+            PendingCallMessages::Hangup { call_id, received } => {
+                let (hangup_type, hangup_device_id) = received.hangup.to_type_and_device_id();
+                Self::Hangup {
+                    call_id: call_id.as_u64(),
+                    hangup_type: hangup_type as i32,
+                    hangup_device_id,
+                    sender_device_id: received.sender_device_id,
+                }
+            }
+        }
+    }
+}
+
+impl PersistedPendingCallMessages {
+    /// Returns None if the saved messages can't be understood by this build.
+    fn into_pending(self) -> Option<PendingCallMessages> {
+        match self {
+            Self::None => Some(PendingCallMessages::None),
+            Self::IceCandidates { call_id, received } => Some(PendingCallMessages::IceCandidates {
+                call_id: CallId::new(call_id),
+                received: received
+                    .into_iter()
+                    .map(|received| signaling::ReceivedIce {
+                        ice: signaling::Ice {
+                            candidates: received
+                                .candidates
+                                .into_iter()
+                                .map(signaling::IceCandidate::new)
+                                .collect(),
+                        },
+                        sender_device_id: received.sender_device_id,
+                    })
+                    .collect(),
+            }),
+            Self::Hangup {
+                call_id,
+                hangup_type,
+                hangup_device_id,
+                sender_device_id,
+            } => {
+                let hangup = match (
+                    signaling::HangupType::from_i32(hangup_type)?,
+                    hangup_device_id,
+                ) {
+                    (signaling::HangupType::NeedPermission, None) => {
+                        signaling::Hangup::NeedPermission(None)
+                    }
+                    (hangup_type, hangup_device_id) => signaling::Hangup::from_type_and_device_id(
+                        hangup_type,
+                        hangup_device_id.unwrap_or_default(),
+                    ),
+                };
+                Some(PendingCallMessages::Hangup {
+                    call_id: CallId::new(call_id),
+                    received: signaling::ReceivedHangup {
+                        hangup,
+                        sender_device_id,
+                    },
+                })
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum OfferValidationError {
     Expired,
@@ -546,15 +712,52 @@ where
         let mut call_manager = self.clone();
         self.worker_spawn(move || {
             let remote_peer_error = remote_peer.clone();
-            if let Err(err) =
-                call_manager.handle_call(remote_peer, call_id, call_media_type, local_device_id)
-            {
+            if let Err(err) = call_manager.handle_call(
+                remote_peer,
+                call_id,
+                call_media_type,
+                local_device_id,
+                None,
+            ) {
                 error!("Handle call failed: {}", err);
                 call_manager.internal_create_api_error(&remote_peer_error, call_id, err);
             }
         })
     }
 
+    /// Take over a call that was accepted on another device of the current user.
+    ///
+    /// This places a new outgoing call to the same remote peer. When the remote peer
+    /// receives it, it hangs up on the other device (which will see
+    /// [`Platform::on_accepted_on_another_device`] with this device's ID) and reports
+    /// the replaced call as [`ApplicationEvent::EndedRemoteReCall`] before handling the
+    /// new call as an incoming one.
+    pub fn pull_call(
+        &mut self,
+        remote_peer: <T as Platform>::AppRemotePeer,
+        call_id: CallId,
+        replaced_call_id: CallId,
+        call_media_type: CallMediaType,
+        local_device_id: DeviceId,
+    ) -> Result<()> {
+        info!("API:pull_call({} -> {}):", replaced_call_id, call_id);
+
+        let mut call_manager = self.clone();
+        self.worker_spawn(move || {
+            let remote_peer_error = remote_peer.clone();
+            if let Err(err) = call_manager.handle_call(
+                remote_peer,
+                call_id,
+                call_media_type,
+                local_device_id,
+                Some(replaced_call_id),
+            ) {
+                error!("Handle pull call failed: {}", err);
+                call_manager.internal_create_api_error(&remote_peer_error, call_id, err);
+            }
+        })
+    }
+
     /// Accept an incoming call.
     pub fn accept_call(&mut self, call_id: CallId) -> Result<()> {
         handle_active_call_api!(self, CallManager::handle_accept_call, call_id)
@@ -598,18 +801,48 @@ where
         handle_active_call_api!(self, CallManager::handle_hangup)
     }
 
+    /// Returns the user who sent the ring, if it was outstanding.
     fn remove_outstanding_group_ring(
         &mut self,
         group_id: group_call::GroupIdRef,
         ring_id: group_call::RingId,
-    ) -> Result<()> {
+    ) -> Result<Option<UserId>> {
         let mut outstanding_group_rings = self.outstanding_group_rings.lock()?;
         if let Some(ring) = outstanding_group_rings.get(group_id) {
             if ring.ring_id == ring_id {
-                outstanding_group_rings.remove(group_id);
+                return Ok(outstanding_group_rings
+                    .remove(group_id)
+                    .map(|ring| ring.sender_uuid));
             }
         }
-        Ok(())
+        Ok(None)
+    }
+
+    /// Sends a RingResponse, unless `recipient` is the current user and `to_self` is false.
+    fn send_ring_response(
+        &mut self,
+        recipient: UserId,
+        to_self: bool,
+        group_id: group_call::GroupId,
+        ring_id: group_call::RingId,
+        response_type: protobuf::signaling::call_message::ring_response::Type,
+    ) {
+        if !to_self && self.self_uuid.lock().expect("get self UUID").as_ref() == Some(&recipient) {
+            return;
+        }
+        let message = protobuf::signaling::CallMessage {
+            ring_response: Some(protobuf::signaling::call_message::RingResponse {
+                group_id: Some(group_id),
+                ring_id: Some(ring_id.into()),
+                r#type: Some(response_type.into()),
+            }),
+            ..Default::default()
+        };
+        self.send_signaling_message(
+            recipient,
+            message,
+            group_call::SignalingMessageUrgency::HandleImmediately,
+        );
     }
 
     /// Cancel a group ring.
@@ -621,9 +854,20 @@ where
     ) -> Result<()> {
         info!("cancel_group_ring(): ring_id: {}", ring_id);
 
-        self.remove_outstanding_group_ring(&group_id, ring_id)?;
+        let ringer = self.remove_outstanding_group_ring(&group_id, ring_id)?;
 
         if let Some(reason) = reason {
+            use protobuf::signaling::call_message::ring_response::Type as ResponseType;
+            let response_type = match reason {
+                group_call::RingCancelReason::DeclinedByUser => ResponseType::Declined,
+                group_call::RingCancelReason::Busy => ResponseType::Busy,
+            };
+
+            // Let the ringer know too, so they can show who declined.
+            if let Some(ringer) = ringer {
+                self.send_ring_response(ringer, false, group_id.clone(), ring_id, response_type);
+            }
+
             let self_uuid = self
                 .self_uuid
                 .lock()
@@ -631,30 +875,151 @@ where
                 .as_ref()
                 .cloned();
             if let Some(self_uuid) = self_uuid {
+                self.send_ring_response(self_uuid, true, group_id, ring_id, response_type);
+            } else {
+                error!("self UUID unknown; cannot notify other devices of cancellation");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tells the user who sent a group ring that this device is ringing.
+    ///
+    /// Nothing is sent back to the ringer automatically, since doing so reveals that the user
+    /// has a device online. Only call this if the user has opted in to sharing that.
+    pub fn acknowledge_group_ring(
+        &mut self,
+        group_id: group_call::GroupId,
+        ring_id: group_call::RingId,
+    ) -> Result<()> {
+        info!("acknowledge_group_ring(): ring_id: {}", ring_id);
+
+        let ringer = self
+            .outstanding_group_rings
+            .lock()?
+            .get(&group_id)
+            .filter(|ring| ring.ring_id == ring_id && !ring.has_expired())
+            .map(|ring| ring.sender_uuid.clone());
+        match ringer {
+            Some(ringer) => {
                 use protobuf::signaling::call_message::ring_response::Type as ResponseType;
-                let response_type = match reason {
-                    group_call::RingCancelReason::DeclinedByUser => ResponseType::Declined,
-                    group_call::RingCancelReason::Busy => ResponseType::Busy,
-                };
-                let message = protobuf::signaling::CallMessage {
-                    ring_response: Some(protobuf::signaling::call_message::RingResponse {
-                        group_id: Some(group_id),
-                        ring_id: Some(ring_id.into()),
-                        r#type: Some(response_type.into()),
-                    }),
-                    ..Default::default()
-                };
+                self.send_ring_response(ringer, false, group_id, ring_id, ResponseType::Ringing);
+            }
+            None => info!("acknowledge_group_ring(): ring is no longer outstanding"),
+        }
+        Ok(())
+    }
+
+    /// Rings the invitees of a scheduled call link if its meeting is starting.
+    ///
+    /// Meant to be called from the app's timer at the scheduled start time. Calling it again
+    /// (or from other devices) is harmless: the ring ID is derived from the schedule, so
+    /// recipients treat repeated rings as the same ring. Returns the ring ID if rings were sent.
+    pub fn ring_scheduled_call_link(
+        &mut self,
+        root_key: &CallLinkRootKey,
+        schedule: &CallLinkSchedule,
+        now: SystemTime,
+    ) -> Result<Option<group_call::RingId>> {
+        info!("ring_scheduled_call_link():");
+
+        if !schedule.is_ring_due(now) {
+            info!("  not ringing; the scheduled call isn't starting");
+            return Ok(None);
+        }
+
+        let ring_id = self.send_call_link_ring(root_key, schedule, false)?;
+        info!(
+            "  rang {} invitees with ring_id: {}",
+            schedule.invitees.len(),
+            ring_id
+        );
+
+        Ok(Some(ring_id))
+    }
+
+    /// Stops the ring of a scheduled call link's meeting, such as when the meeting is cancelled.
+    ///
+    /// Takes the schedule the invitees were rung for, since that is what the ring ID is derived
+    /// from. Recipients only accept it while a ring for that schedule could be going on.
+    pub fn cancel_call_link_ring(
+        &mut self,
+        root_key: &CallLinkRootKey,
+        schedule: &CallLinkSchedule,
+    ) -> Result<()> {
+        let ring_id = self.send_call_link_ring(root_key, schedule, true)?;
+        info!("cancel_call_link_ring(): ring_id: {}", ring_id);
+        Ok(())
+    }
+
+    fn send_call_link_ring(
+        &mut self,
+        root_key: &CallLinkRootKey,
+        schedule: &CallLinkSchedule,
+        cancelled: bool,
+    ) -> Result<group_call::RingId> {
+        let ring_id = group_call::RingId::from(schedule.ring_id(root_key));
+        let message = protobuf::signaling::CallMessage {
+            call_link_ring: Some(protobuf::signaling::call_message::CallLinkRing {
+                root_key: Some(root_key.bytes().to_vec()),
+                ring_id: Some(ring_id.into()),
+                encrypted_schedule: Some(schedule.encrypt(root_key, rand::rngs::OsRng)),
+                cancelled: Some(cancelled),
+            }),
+            ..Default::default()
+        };
+        let self_uuid = self.self_uuid.lock()?.clone();
+        for invitee in &schedule.invitees {
+            if Some(invitee) != self_uuid.as_ref() {
                 self.send_signaling_message(
-                    self_uuid,
-                    message,
+                    invitee.clone(),
+                    message.clone(),
                     group_call::SignalingMessageUrgency::HandleImmediately,
                 );
-            } else {
-                error!("self UUID unknown; cannot notify other devices of cancellation");
             }
         }
+        Ok(ring_id)
+    }
 
-        Ok(())
+    /// Takes a snapshot of the state that should survive an app restart.
+    ///
+    /// See [`PersistedState`].
+    pub fn persisted_state(&self) -> Result<PersistedState> {
+        info!("persisted_state():");
+
+        let self_uuid = self.self_uuid.lock()?.clone();
+        let outstanding_group_rings = self
+            .outstanding_group_rings
+            .lock()?
+            .iter()
+            .filter(|(_group_id, ring)| !ring.has_expired())
+            .map(|(group_id, ring)| PersistedGroupRing {
+                group_id: group_id.clone(),
+                ring_id: ring.ring_id.into(),
+                sender_uuid: ring.sender_uuid.clone(),
+                age: ring.received.elapsed(),
+            })
+            .collect();
+        let pending_call_messages = (&*self.pending_call_messages.lock()?).into();
+
+        Ok(PersistedState {
+            version: PERSISTED_STATE_VERSION,
+            saved_at: SystemTime::now(),
+            self_uuid,
+            outstanding_group_rings,
+            pending_call_messages,
+        })
+    }
+
+    /// Restores a snapshot taken by [`CallManager::persisted_state`], usually by an
+    /// earlier instance of the app process.
+    ///
+    /// Anything this CallManager has learned since it was created takes precedence over
+    /// the snapshot. Rings that expired while the app wasn't running are reported to the
+    /// application as [`group_call::RingUpdate::ExpiredRequest`].
+    pub fn restore_persisted_state(&mut self, state: PersistedState) -> Result<()> {
+        handle_api!(self, CallManager::handle_restore_persisted_state, state)
     }
 
     /// Received offer from application.
@@ -1073,13 +1438,14 @@ where
         call_id: CallId,
         call_media_type: CallMediaType,
         local_device_id: DeviceId,
+        replaced_call_id: Option<CallId>,
     ) -> Result<()> {
         ringbench!(
             RingBench::App,
             RingBench::Cm,
             format!(
-                "call()\t{}\t{}\t{}",
-                call_id, call_media_type, local_device_id
+                "call()\t{}\t{}\t{}\t{:?}",
+                call_id, call_media_type, local_device_id, replaced_call_id
             )
         );
 
@@ -1098,6 +1464,7 @@ where
                         CallDirection::Outgoing,
                         call_media_type,
                         local_device_id,
+                        replaced_call_id,
                         self.clone(),
                     )?;
 
@@ -1341,6 +1708,7 @@ where
             CallDirection::Incoming,
             received.offer.call_media_type,
             received.receiver_device_id,
+            received.offer.replaced_call_id(),
             self.clone(),
         )?;
 
@@ -1361,7 +1729,7 @@ where
 
         enum ActiveCallAction {
             DontTerminate,
-            TerminateAndSendHangup(ApplicationEvent),
+            TerminateAndSendHangup(signaling::Hangup, ApplicationEvent),
             TerminateWithoutSendingHangup(ApplicationEvent),
         }
 
@@ -1384,11 +1752,17 @@ where
                 IncomingCallAction::Ignore(ApplicationEvent::ReceivedOfferWithGlare),
             ),
             ReceivedOfferCollision::GlareLoser => (
-                ActiveCallAction::TerminateAndSendHangup(ApplicationEvent::EndedRemoteGlare),
+                ActiveCallAction::TerminateAndSendHangup(
+                    signaling::Hangup::Normal,
+                    ApplicationEvent::EndedRemoteGlare,
+                ),
                 IncomingCallAction::Start,
             ),
             ReceivedOfferCollision::GlareDoubleLoser => (
-                ActiveCallAction::TerminateAndSendHangup(ApplicationEvent::EndedRemoteGlare),
+                ActiveCallAction::TerminateAndSendHangup(
+                    signaling::Hangup::Normal,
+                    ApplicationEvent::EndedRemoteGlare,
+                ),
                 IncomingCallAction::RejectAsBusy(ApplicationEvent::EndedGlareHandlingFailure),
             ),
             ReceivedOfferCollision::ReCall => (
@@ -1397,18 +1771,21 @@ where
                 ),
                 IncomingCallAction::Start,
             ),
+            ReceivedOfferCollision::Handoff => (
+                ActiveCallAction::TerminateAndSendHangup(
+                    signaling::Hangup::AcceptedOnAnotherDevice(received.sender_device_id),
+                    ApplicationEvent::EndedRemoteReCall,
+                ),
+                IncomingCallAction::Start,
+            ),
         };
 
         match active_call_action {
             ActiveCallAction::DontTerminate => {}
-            ActiveCallAction::TerminateAndSendHangup(app_event) => {
+            ActiveCallAction::TerminateAndSendHangup(hangup, app_event) => {
                 self.clear_active_call()?;
                 *busy = false;
-                self.terminate_call(
-                    active_call.unwrap(),
-                    Some(signaling::Hangup::Normal),
-                    Some(app_event),
-                )?;
+                self.terminate_call(active_call.unwrap(), Some(hangup), Some(app_event))?;
             }
             ActiveCallAction::TerminateWithoutSendingHangup(app_event) => {
                 self.clear_active_call()?;
@@ -1654,6 +2031,7 @@ where
                                         group_id.clone(),
                                         ring_id.into(),
                                         sender_uuid.clone(),
+                                        Duration::ZERO,
                                     )?;
                                     group_call::RingUpdate::Requested
                                 }
@@ -1680,23 +2058,18 @@ where
                 ring_response: Some(mut ring_response),
                 ..
             } => {
-                {
+                use protobuf::signaling::call_message::ring_response::Type as ResponseType;
+
+                let from_self = {
                     let self_uuid = self.self_uuid.lock().expect("get self UUID");
-                    if self_uuid.as_ref() != Some(&sender_uuid) {
-                        info!(
-                            concat!(
-                                "Discarding ring response from another user {} for ring ID {}.",
-                                "If that's the current user, make sure you told CallManager the ",
-                                "current user's UUID!"
-                            ),
-                            uuid_to_string(&sender_uuid),
-                            ring_response.ring_id.unwrap_or(0)
-                        );
-                        return Ok(());
-                    }
+                    self_uuid.as_ref() == Some(&sender_uuid)
+                };
+                if !from_self {
+                    // A recipient of a ring we sent.
+                    return self
+                        .handle_received_ring_recipient_response(sender_uuid, ring_response);
                 }
 
-                use protobuf::signaling::call_message::ring_response::Type as ResponseType;
                 match (
                     &mut ring_response.group_id,
                     ring_response
@@ -1731,15 +2104,73 @@ where
                     }
                 }
             }
+            protobuf::signaling::CallMessage {
+                call_link_ring: Some(call_link_ring),
+                ..
+            } => {
+                let root_key = call_link_ring
+                    .root_key
+                    .as_deref()
+                    .and_then(|root_key| CallLinkRootKey::try_from(root_key).ok());
+                let schedule = root_key.as_ref().and_then(|root_key| {
+                    CallLinkSchedule::decrypt(
+                        call_link_ring.encrypted_schedule.as_deref()?,
+                        root_key,
+                    )
+                    .ok()
+                });
+                match (root_key, schedule, call_link_ring.ring_id) {
+                    (Some(root_key), Some(schedule), Some(ring_id)) => {
+                        // Anyone with the link could send a ring, so only take rings that match
+                        // a meeting that is starting and that we're invited to.
+                        let self_uuid = self.self_uuid.lock()?.clone();
+                        if ring_id != schedule.ring_id(&root_key) {
+                            warn!("Dropping CallLinkRing whose ring ID doesn't match its schedule");
+                            return Ok(());
+                        }
+                        if !self_uuid
+                            .is_some_and(|self_uuid| schedule.invitees.contains(&self_uuid))
+                        {
+                            warn!("Dropping CallLinkRing for a meeting we're not invited to");
+                            return Ok(());
+                        }
+                        let sent = SystemTime::now()
+                            .checked_sub(message_age)
+                            .unwrap_or(SystemTime::UNIX_EPOCH);
+                        if !schedule.is_ring_plausible(sent) {
+                            warn!(
+                                "Dropping CallLinkRing sent outside of its meeting's ring window"
+                            );
+                            return Ok(());
+                        }
+
+                        let ring_update = if call_link_ring.cancelled == Some(true) {
+                            group_call::RingUpdate::CancelledByRinger
+                        } else if message_age > MAX_MESSAGE_AGE {
+                            group_call::RingUpdate::ExpiredRequest
+                        } else if *self.busy.lock()? {
+                            group_call::RingUpdate::BusyLocally
+                        } else {
+                            group_call::RingUpdate::Requested
+                        };
+                        self.platform.lock()?.call_link_ring_update(
+                            root_key,
+                            ring_id.into(),
+                            sender_uuid,
+                            ring_update,
+                        );
+                    }
+                    _ => {
+                        warn!("Received malformed CallLinkRing: {:?}", call_link_ring);
+                    }
+                }
+            }
             protobuf::signaling::CallMessage {
                 group_call_message: Some(group_call_message),
                 ..
             } => {
                 if let Some(group_id) = group_call_message.group_id.as_ref() {
-                    let group_calls = self
-                        .group_call_by_client_id
-                        .lock()
-                        .expect("lock group_call_by_client_id");
+                    let group_calls = self.group_call_by_client_id.lock()?;
                     let group_call = group_calls
                         .values()
                         .find(|c| &c.client.group_id == group_id && c.active);
@@ -1758,11 +2189,62 @@ where
         Ok(())
     }
 
+    /// Passes a response to a ring sent by this device on to the group call that sent it.
+    fn handle_received_ring_recipient_response(
+        &mut self,
+        sender_uuid: UserId,
+        ring_response: protobuf::signaling::call_message::RingResponse,
+    ) -> Result<()> {
+        use protobuf::signaling::call_message::ring_response::Type as ResponseType;
+        let (Some(group_id), Some(response_type), Some(ring_id)) = (
+            ring_response.group_id.as_ref(),
+            ring_response
+                .r#type
+                .and_then(|ty| ResponseType::try_from(ty).ok()),
+            ring_response.ring_id,
+        ) else {
+            warn!("Received malformed RingResponse: {:?}", ring_response);
+            return Ok(());
+        };
+        let status = match response_type {
+            ResponseType::Ringing => group_call::RingRecipientStatus::Ringing,
+            ResponseType::Accepted => group_call::RingRecipientStatus::Accepted,
+            ResponseType::Declined => group_call::RingRecipientStatus::Declined,
+            ResponseType::Busy => group_call::RingRecipientStatus::Busy,
+        };
+
+        let group_calls = self.group_call_by_client_id.lock()?;
+        match group_calls
+            .values()
+            .find(|c| &c.client.group_id == group_id && c.active)
+        {
+            Some(group_call) => {
+                group_call
+                    .client
+                    .on_ring_response_received(sender_uuid, ring_id.into(), status)
+            }
+            None => info!(
+                concat!(
+                    "Discarding ring response from another user {} for ring ID {}.",
+                    "If that's the current user, make sure you told CallManager the ",
+                    "current user's UUID!"
+                ),
+                uuid_to_string(&sender_uuid),
+                ring_id
+            ),
+        }
+        Ok(())
+    }
+
+    /// Tracks a ring until it is accepted, cancelled, or times out.
+    ///
+    /// `age` is how long ago the ring was received, which is only non-zero for restored rings.
     fn start_group_ring(
         &mut self,
         group_id: group_call::GroupId,
         ring_id: group_call::RingId,
         sender_uuid: UserId,
+        age: Duration,
     ) -> Result<()> {
         {
             let mut outstanding_group_rings = self.outstanding_group_rings.lock()?;
@@ -1771,11 +2253,13 @@ where
             outstanding_group_rings.retain(|_group_id, ring| !ring.has_expired());
             // If there's an existing, non-expired ring, replace it so that the
             // newly received ring will get cancelled upon joining.
+            let now = Instant::now();
             outstanding_group_rings.insert(
                 group_id.clone(),
                 OutstandingGroupRing {
                     ring_id,
-                    received: Instant::now(),
+                    sender_uuid: sender_uuid.clone(),
+                    received: now.checked_sub(age).unwrap_or(now),
                 },
             );
         }
@@ -1794,8 +2278,9 @@ where
         }
 
         let mut self_for_timeout = self.clone();
-        self.worker
-            .send_delayed(*INCOMING_GROUP_CALL_RING_TIME, move |_| {
+        self.worker.send_delayed(
+            INCOMING_GROUP_CALL_RING_TIME.saturating_sub(age),
+            move |_| {
                 let result = try_scoped(|| {
                     self_for_timeout.remove_outstanding_group_ring(&group_id, ring_id)?;
                     self_for_timeout.platform.lock()?.group_call_ring_update(
@@ -1809,7 +2294,8 @@ where
                 if let Err(err) = result {
                     error!("error handling group ring timeout: {}", err)
                 }
-            });
+            },
+        );
 
         Ok(())
     }
@@ -1821,6 +2307,61 @@ where
         }
     }
 
+    /// Handle restore_persisted_state() API from application.
+    fn handle_restore_persisted_state(&mut self, state: PersistedState) -> Result<()> {
+        let time_since_saved = SystemTime::now()
+            .duration_since(state.saved_at)
+            .unwrap_or(Duration::ZERO);
+        info!(
+            "handle_restore_persisted_state(): saved {:?} ago",
+            time_since_saved
+        );
+
+        if let Some(saved_uuid) = state.self_uuid {
+            let mut self_uuid = self.self_uuid.lock()?;
+            if self_uuid.is_none() {
+                *self_uuid = Some(saved_uuid);
+            }
+        }
+
+        for ring in state.outstanding_group_rings {
+            if self
+                .outstanding_group_rings
+                .lock()?
+                .contains_key(&ring.group_id)
+            {
+                info!("ring {} superseded since it was saved", ring.ring_id);
+                continue;
+            }
+            let ring_id = group_call::RingId::from(ring.ring_id);
+            let age = ring.age.saturating_add(time_since_saved);
+            if age >= *INCOMING_GROUP_CALL_RING_TIME {
+                self.platform.lock()?.group_call_ring_update(
+                    ring.group_id,
+                    ring_id,
+                    ring.sender_uuid,
+                    group_call::RingUpdate::ExpiredRequest,
+                );
+            } else {
+                self.start_group_ring(ring.group_id, ring_id, ring.sender_uuid, age)?;
+            }
+        }
+
+        if time_since_saved > MAX_MESSAGE_AGE {
+            // Any offer these were waiting for would be rejected as expired anyway.
+            info!("dropping saved pending call messages");
+        } else if let Some(saved) = state.pending_call_messages.into_pending() {
+            let mut pending_call_messages = self.pending_call_messages.lock()?;
+            if matches!(*pending_call_messages, PendingCallMessages::None) {
+                *pending_call_messages = saved;
+            }
+        } else {
+            warn!("dropping unrecognized saved pending call messages");
+        }
+
+        Ok(())
+    }
+
     /// Handle receiving an HTTP response from the application.
     fn handle_received_http_response(
         &mut self,
@@ -1939,6 +2480,11 @@ where
                             info!("Glare, not yet accepted and peer devices match");
                             glare_tiebreaker()
                         }
+                    } else if received.offer.replaced_call_id() == Some(active_call.call_id())
+                        && active_call_state >= CallState::ConnectedAndAccepted
+                    {
+                        info!("Handoff, peer's other device is taking over the call");
+                        ReceivedOfferCollision::Handoff
                     } else {
                         info!("Call from different device, sending busy for received offer");
                         ReceivedOfferCollision::Busy
@@ -2291,6 +2837,48 @@ where
         }
     }
 
+    /// Terminate the active call because another device of the local user accepted it.
+    pub(super) fn remote_hangup_accepted_on_another_device(
+        &mut self,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        info!(
+            "remote_hangup_accepted_on_another_device(): call_id: {} device_id: {}",
+            call_id, device_id
+        );
+
+        if !self.call_is_active(call_id)? {
+            info!("remote_hangup_accepted_on_another_device(): ignoring for inactive call");
+            return Ok(());
+        }
+
+        let call = self.active_call()?;
+        self.clear_active_call()?;
+        self.release_busy()?;
+
+        let remote_peer = call.remote_peer()?.clone();
+        self.notify_accepted_on_another_device(&remote_peer, call_id, device_id)?;
+        self.terminate_call(call, None, None)
+    }
+
+    /// Notify application that the call was accepted on another device.
+    pub(super) fn notify_accepted_on_another_device(
+        &self,
+        remote_peer: &<T as Platform>::AppRemotePeer,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        ringbench!(
+            RingBench::Cm,
+            RingBench::App,
+            format!("accepted_on_another_device()\t{}\t{}", call_id, device_id)
+        );
+
+        let platform = self.platform.lock()?;
+        platform.on_accepted_on_another_device(remote_peer, call_id, device_id)
+    }
+
     /// Notify application that the call is concluded.
     pub(super) fn notify_offer_expired(
         &self,
@@ -2655,6 +3243,130 @@ where
         platform_handler!(self, handle_raised_hands, client_id, raised_hands);
     }
 
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layers: &group_call::ForwardedVideoLayers,
+    ) {
+        info!(
+            "handle_forwarded_video_layers_changed(): outgoing: {}",
+            layers.outgoing_layer_count
+        );
+        platform_handler!(
+            self,
+            handle_forwarded_video_layers_changed,
+            client_id,
+            layers.clone()
+        );
+    }
+
+    fn handle_speaker_queue_changed(
+        &self,
+        client_id: group_call::ClientId,
+        queue: &group_call::SpeakerQueue,
+    ) {
+        info!(
+            "handle_speaker_queue_changed(): {:?}, current_speaker: {:?}",
+            queue.raised_hands, queue.current_speaker
+        );
+        platform_handler!(self, handle_speaker_queue_changed, client_id, queue.clone());
+    }
+
+    fn handle_handoff_state_received(
+        &self,
+        client_id: group_call::ClientId,
+        state: group_call::HandoffState,
+    ) {
+        info!("handle_handoff_state_received(): {:?}", state);
+        platform_handler!(self, handle_handoff_state_received, client_id, state);
+    }
+
+    fn handle_handed_off(&self, client_id: group_call::ClientId, new_demux_id: DemuxId) {
+        info!("handle_handed_off(): {}", new_demux_id);
+        platform_handler!(self, handle_handed_off, client_id, new_demux_id);
+    }
+
+    fn handle_spotlight_changed(&self, client_id: group_call::ClientId, spotlight: Vec<DemuxId>) {
+        info!("handle_spotlight_changed(): {:?}", spotlight);
+        platform_handler!(self, handle_spotlight_changed, client_id, spotlight);
+    }
+
+    fn handle_muted_by_admin(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!("handle_muted_by_admin(): {}", admin_demux_id);
+        platform_handler!(self, handle_muted_by_admin, client_id, admin_demux_id);
+    }
+
+    fn handle_unmute_requested(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!("handle_unmute_requested(): {}", admin_demux_id);
+        platform_handler!(self, handle_unmute_requested, client_id, admin_demux_id);
+    }
+
+    fn handle_chat_message(
+        &self,
+        client_id: group_call::ClientId,
+        message: &group_call::ChatMessage,
+    ) {
+        info!("handle_chat_message(): {:?}", message.id);
+        platform_handler!(self, handle_chat_message, client_id, message.clone());
+    }
+
+    fn handle_polls_changed(&self, client_id: group_call::ClientId, polls: &[group_call::Poll]) {
+        info!("handle_polls_changed(): {} polls", polls.len());
+        platform_handler!(self, handle_polls_changed, client_id, polls.to_vec());
+    }
+
+    fn handle_lobby_changed(&self, client_id: group_call::ClientId, lobby: &group_call::LobbyInfo) {
+        info!(
+            "handle_lobby_changed(): participant_count: {}, position: {:?}",
+            lobby.participant_count, lobby.position
+        );
+        platform_handler!(self, handle_lobby_changed, client_id, lobby.clone());
+    }
+
+    fn handle_breakout_rooms_changed(
+        &self,
+        client_id: group_call::ClientId,
+        breakout_rooms: Option<BreakoutRooms>,
+    ) {
+        info!(
+            "handle_breakout_rooms_changed(): {:?}",
+            breakout_rooms
+                .as_ref()
+                .map(|breakout_rooms| breakout_rooms.room_count)
+        );
+        platform_handler!(
+            self,
+            handle_breakout_rooms_changed,
+            client_id,
+            breakout_rooms
+        );
+    }
+
+    fn handle_video_layout_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layout: &group_call::VideoLayout,
+    ) {
+        info!("handle_video_layout_changed(): {:?}", layout.tiles);
+        platform_handler!(self, handle_video_layout_changed, client_id, layout.clone());
+    }
+
+    fn handle_ring_recipients_changed(
+        &self,
+        client_id: group_call::ClientId,
+        ring_id: group_call::RingId,
+        recipients: &HashMap<UserId, group_call::RingRecipientStatus>,
+    ) {
+        info!("handle_ring_recipients_changed(): ring_id: {}", ring_id);
+        platform_handler!(
+            self,
+            handle_ring_recipients_changed,
+            client_id,
+            ring_id,
+            recipients.clone()
+        );
+    }
+
     fn handle_rtc_stats_report(&self, report_json: String) {
         platform_handler!(self, handle_rtc_stats_report, report_json);
     }
@@ -3010,12 +3722,73 @@ where
         }
     }
 
+    // Like with_group_call, but passes on the group call's failure.
+    fn try_with_group_call(
+        &mut self,
+        client_id: group_call::ClientId,
+        use_group_call: impl FnOnce(&mut group_call::Client) -> Result<()>,
+    ) -> Result<()> {
+        let mut result = Ok(());
+        self.with_group_call(client_id, |group_call| {
+            result = use_group_call(group_call);
+        });
+        result
+    }
+
+    pub fn set_video_encoder_config(
+        &mut self,
+        client_id: group_call::ClientId,
+        config: VideoEncoderConfig,
+    ) -> Result<()> {
+        info!("set_video_encoder_config(): id: {}", client_id);
+        self.try_with_group_call(client_id, |group_call| {
+            group_call.set_video_encoder_config(config)
+        })
+    }
+
+    pub fn set_remote_audio_gain(
+        &mut self,
+        client_id: group_call::ClientId,
+        demux_id: DemuxId,
+        gain: f32,
+    ) -> Result<()> {
+        info!("set_remote_audio_gain(): id: {}", client_id);
+        self.try_with_group_call(client_id, |group_call| {
+            group_call.set_remote_audio_gain(demux_id, gain)
+        })
+    }
+
+    pub fn set_remote_audio_muted(
+        &mut self,
+        client_id: group_call::ClientId,
+        demux_id: DemuxId,
+        muted: bool,
+    ) -> Result<()> {
+        info!("set_remote_audio_muted(): id: {}", client_id);
+        self.try_with_group_call(client_id, |group_call| {
+            group_call.set_remote_audio_muted(demux_id, muted)
+        })
+    }
+
     forward_group_call_api!(connect());
     forward_group_call_api!(join());
+    forward_group_call_api!(join_by_handoff(from_demux_id: DemuxId));
     forward_group_call_api!(leave());
     forward_group_call_api!(react(value: String));
+    forward_group_call_api!(send_chat_message(
+        text: String,
+        reply_to: Option<group_call::ChatMessageId>,
+    ));
+    forward_group_call_api!(create_poll(question: String, options: Vec<String>));
+    forward_group_call_api!(vote_in_poll(
+        poll_id: group_call::PollId,
+        option_index: Option<u32>,
+    ));
+    forward_group_call_api!(close_poll(poll_id: group_call::PollId));
     forward_group_call_api!(raise_hand(raise: bool));
     forward_group_call_api!(group_ring => ring(recipient: Option<UserId>));
+    forward_group_call_api!(group_ring_again => ring_again(recipients: Vec<UserId>));
+    forward_group_call_api!(group_ring_delivered => on_ring_delivered(recipient: UserId));
     forward_group_call_api!(set_outgoing_audio_muted(muted: bool));
     forward_group_call_api!(set_outgoing_video_muted(muted: bool));
     forward_group_call_api!(set_presenting(presenting: bool));
@@ -3026,10 +3799,24 @@ where
         rendered_resolutions: Vec<group_call::VideoRequest>,
         active_speaker_height: u16,
     ), false);
+    forward_group_call_api!(set_viewport(viewport: Option<group_call::Viewport>));
     forward_group_call_api!(approve_user(user_id: UserId));
     forward_group_call_api!(deny_user(user_id: UserId));
     forward_group_call_api!(remove_client(other_client_id: DemuxId));
     forward_group_call_api!(block_client(other_client_id: DemuxId));
+    forward_group_call_api!(set_spotlight(demux_ids: Vec<DemuxId>));
+    forward_group_call_api!(mute_client(other_client_id: DemuxId));
+    forward_group_call_api!(ask_client_to_unmute(other_client_id: DemuxId));
+    forward_group_call_api!(lower_hand(other_client_id: DemuxId));
+    forward_group_call_api!(call_next_speaker(ask_to_unmute: bool));
+    forward_group_call_api!(start_breakout_rooms(
+        room_count: u32,
+        device_assignments: HashMap<DemuxId, u32>,
+        user_assignments: HashMap<UserId, u32>,
+        duration: Option<Duration>,
+    ));
+    forward_group_call_api!(end_breakout_rooms());
+    forward_group_call_api!(send_lobby_message(message: String));
     forward_group_call_api!(set_group_members(members: Vec<GroupMember>));
     forward_group_call_api!(set_membership_proof(proof: Vec<u8>));
     forward_group_call_api!(set_rtc_stats_interval(interval: Duration));
@@ -3092,6 +3879,94 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_persisted_state_round_trip() {
+        let state = PersistedState {
+            version: PERSISTED_STATE_VERSION,
+            saved_at: SystemTime::now(),
+            self_uuid: Some(vec![1, 0, 1]),
+            outstanding_group_rings: vec![PersistedGroupRing {
+                group_id: vec![1, 1, 1],
+                ring_id: 42,
+                sender_uuid: vec![1, 2, 3],
+                age: Duration::from_secs(5),
+            }],
+            pending_call_messages: PersistedPendingCallMessages::Hangup {
+                call_id: 7,
+                hangup_type: signaling::HangupType::NeedPermission as i32,
+                hangup_device_id: None,
+                sender_device_id: 2,
+            },
+        };
+        assert!(!state.is_empty());
+        assert_eq!(
+            state,
+            PersistedState::from_bytes(&state.to_bytes()).expect("valid")
+        );
+
+        let future_state = PersistedState {
+            version: PERSISTED_STATE_VERSION + 1,
+            ..state
+        };
+        assert!(PersistedState::from_bytes(&future_state.to_bytes()).is_err());
+        assert!(PersistedState::from_bytes(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_persisted_pending_call_messages() {
+        let pending = PendingCallMessages::IceCandidates {
+            call_id: CallId::new(7),
+            received: vec![signaling::ReceivedIce {
+                ice: signaling::Ice {
+                    candidates: vec![signaling::IceCandidate::new(vec![1, 2, 3])],
+                },
+                sender_device_id: 2,
+            }],
+        };
+        let persisted = PersistedPendingCallMessages::from(&pending);
+        match persisted.clone().into_pending() {
+            Some(PendingCallMessages::IceCandidates { call_id, received }) => {
+                assert_eq!(CallId::new(7), call_id);
+                assert_eq!(1, received.len());
+                assert_eq!(2, received[0].sender_device_id);
+                assert_eq!(vec![1, 2, 3], received[0].ice.candidates[0].opaque);
+            }
+            _ => panic!("unexpected pending messages: {:?}", persisted),
+        }
+
+        for hangup in [
+            signaling::Hangup::Normal,
+            signaling::Hangup::AcceptedOnAnotherDevice(3),
+            signaling::Hangup::NeedPermission(None),
+            signaling::Hangup::NeedPermission(Some(3)),
+        ] {
+            let pending = PendingCallMessages::Hangup {
+                call_id: CallId::new(7),
+                received: signaling::ReceivedHangup {
+                    hangup,
+                    sender_device_id: 2,
+                },
+            };
+            match PersistedPendingCallMessages::from(&pending).into_pending() {
+                Some(PendingCallMessages::Hangup { call_id, received }) => {
+                    assert_eq!(CallId::new(7), call_id);
+                    assert_eq!(hangup, received.hangup);
+                    assert_eq!(2, received.sender_device_id);
+                }
+                _ => panic!("hangup {} not restored", hangup),
+            }
+        }
+
+        assert!(PersistedPendingCallMessages::Hangup {
+            call_id: 7,
+            hangup_type: 100,
+            hangup_device_id: None,
+            sender_device_id: 2,
+        }
+        .into_pending()
+        .is_none());
+    }
+
     #[test]
     fn test_validate_group_ring_intention_based_on_age() {
         let valid_message = CallMessage {
@@ -667,8 +667,11 @@ where
             let mut webrtc = self.webrtc.lock()?;
 
             // Create a stats observer object.
-            let stats_observer =
-                create_stats_observer(self.call_id(), self.poll_stats_config.get_initial_offset());
+            let stats_observer = create_stats_observer(
+                self.call_id(),
+                false,
+                self.poll_stats_config.get_initial_offset(),
+            );
             webrtc.stats_observer = Some(stats_observer);
 
             let peer_connection = webrtc.peer_connection()?;
@@ -771,8 +774,11 @@ where
             let mut webrtc = self.webrtc.lock()?;
 
             // Create a stats observer object.
-            let stats_observer =
-                create_stats_observer(self.call_id(), self.poll_stats_config.get_initial_offset());
+            let stats_observer = create_stats_observer(
+                self.call_id(),
+                false,
+                self.poll_stats_config.get_initial_offset(),
+            );
             webrtc.stats_observer = Some(stats_observer);
 
             let peer_connection = webrtc.peer_connection()?;
@@ -14,6 +14,8 @@ use sha2::Sha256;
 use subtle::ConstantTimeEq;
 use thiserror::Error;
 
+use crate::common::counters::{self, CounterShare};
+
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum Error {
     #[error("no receiver state could be found matching the provided data")]
@@ -236,6 +238,8 @@ pub struct Context {
     sender_state: SenderState,
     next_frame_counter: FrameCounter,
     remote_states_by_id: HashMap<SenderId, Vec<ReceiverState>>,
+    sender_count: CounterShare,
+    receiver_state_count: CounterShare,
 }
 
 impl Context {
@@ -246,6 +250,8 @@ impl Context {
             sender_state,
             next_frame_counter: 1,
             remote_states_by_id: HashMap::new(),
+            sender_count: CounterShare::new(&counters::CRYPTO_SENDERS),
+            receiver_state_count: CounterShare::new(&counters::CRYPTO_RECEIVER_STATES),
         }
     }
 
@@ -350,9 +356,15 @@ impl Context {
             states.pop();
         }
         states.insert(0, ReceiverState::new(ratchet_counter, secret));
+        self.receiver_state_count
+            .set(self.remote_states_by_id.values().map(Vec::len).sum());
     }
 
     fn get_mut_ref_state_vec_by_id(&mut self, sender_id: SenderId) -> &mut Vec<ReceiverState> {
+        // Senders are never removed, so this is only counted when one is added.
+        if !self.remote_states_by_id.contains_key(&sender_id) {
+            self.sender_count.set(self.remote_states_by_id.len() + 1);
+        }
         self.remote_states_by_id
             .entry(sender_id)
             .or_insert_with(|| Vec::with_capacity(MAX_RECEIVER_STATES_TO_RETAIN))
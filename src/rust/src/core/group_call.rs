@@ -27,12 +27,14 @@ use x25519_dalek::{EphemeralSecret, PublicKey};
 use crate::{
     common::{
         actor::{Actor, Stopper},
+        counters::{self, CounterShare},
         units::DataRate,
         CallId, DataMode, Result,
     },
     core::{call_mutex::CallMutex, crypto as frame_crypto, signaling, util::uuid_to_string},
     error::RingRtcError,
     lite::{
+        call_links::BreakoutRooms,
         http, sfu,
         sfu::{
             ClientStatus, DemuxId, GroupMember, MemberMap, MembershipProof, ObfuscatedResolver,
@@ -44,7 +46,8 @@ use crate::{
     webrtc::{
         self,
         media::{
-            AudioEncoderConfig, AudioTrack, VideoFrame, VideoFrameMetadata, VideoSink, VideoTrack,
+            AudioEncoderConfig, AudioTrack, VideoEncoderConfig, VideoEncoderLayer, VideoFrame,
+            VideoFrameMetadata, VideoSink, VideoTrack,
         },
         peer_connection::{AudioLevel, PeerConnection, Protocol, ReceivedAudioLevel, SendRates},
         peer_connection_factory::{self as pcf, AudioJitterBufferConfig, PeerConnectionFactory},
@@ -59,6 +62,18 @@ use crate::{
     },
 };
 
+mod chat;
+mod polls;
+mod video_layout;
+use chat::ChatReceiver;
+pub use chat::{ChatMessage, ChatMessageId, CHAT_MESSAGE_MAX_SIZE};
+use polls::PollsState;
+pub use polls::{
+    Poll, PollId, PollOption, POLL_MAX_OPTIONS, POLL_MIN_OPTIONS, POLL_OPTION_MAX_SIZE,
+    POLL_QUESTION_MAX_SIZE,
+};
+pub use video_layout::{TileSize, VideoLayout, Viewport, ViewportMode};
+
 // Each instance of a group_call::Client has an ID for logging and passing events
 // around (such as callbacks to the Observer).  It's just very convenient to have.
 pub type ClientId = u32;
@@ -139,6 +154,37 @@ pub enum RingCancelReason {
     Busy,
 }
 
+/// How a recipient has responded to a ring sent by the local device.
+///
+/// Statuses are ordered by progress. Once a recipient has declined, is busy, or has accepted, that
+/// is kept for the rest of the ring, except that a recipient who joins anyway is still accepted.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, TryFromPrimitive)]
+pub enum RingRecipientStatus {
+    /// The ring was sent, but nothing has been heard back yet.
+    Sent = 0,
+    /// The app reported that the ring was delivered to the recipient.
+    Delivered,
+    /// At least one of the recipient's devices is ringing.
+    Ringing,
+    /// The recipient declined the ring.
+    Declined,
+    /// The recipient is in another call.
+    Busy,
+    /// The recipient joined the call from one of their devices.
+    Accepted,
+}
+
+impl RingRecipientStatus {
+    fn is_final(self) -> bool {
+        matches!(self, Self::Declined | Self::Busy | Self::Accepted)
+    }
+
+    fn should_replace(self, previous: Self) -> bool {
+        self == Self::Accepted || (!previous.is_final() && self > previous)
+    }
+}
+
 /// Indicates whether a signaling message should be marked for immediate processing
 /// even if the receiving app isn't running.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -222,6 +268,8 @@ pub enum RemoteDevicesChangedReason {
     HeartbeatStateChanged(DemuxId),
     ForwardedVideosChanged,
     HigherResolutionPendingChanged,
+    // The given device took over the call from another device of the same user.
+    HandedOff(DemuxId),
 }
 
 // The callbacks from the Call to the Observer of the call.
@@ -260,6 +308,14 @@ pub trait Observer {
     fn handle_network_route_changed(&self, client_id: ClientId, network_route: NetworkRoute);
     fn handle_join_state_changed(&self, client_id: ClientId, join_state: JoinState);
     fn handle_send_rates_changed(&self, _client_id: ClientId, _send_rates: SendRates) {}
+    // Which layers of the simulcast ladder the SFU is using changed (see
+    // Client::set_video_encoder_config).
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        _client_id: ClientId,
+        _layers: &ForwardedVideoLayers,
+    ) {
+    }
 
     // The following notify the observer of state changes to the remote devices.
     fn handle_remote_devices_changed(
@@ -306,6 +362,61 @@ pub trait Observer {
 
     fn handle_raised_hands(&self, client_id: ClientId, raised_hands: Vec<DemuxId>);
 
+    // The raised hands or the current speaker changed (see Client::call_next_speaker). Called
+    // after handle_raised_hands when both apply.
+    fn handle_speaker_queue_changed(&self, _client_id: ClientId, _queue: &SpeakerQueue) {}
+
+    // The devices an admin wants everyone to feature, in order (see Client::set_spotlight).
+    fn handle_spotlight_changed(&self, _client_id: ClientId, _spotlight: Vec<DemuxId>) {}
+
+    // An admin muted the local device (see Client::mute_client). The outgoing audio has already
+    // been marked as muted; the app is expected to mute its audio track and update its UI.
+    fn handle_muted_by_admin(&self, _client_id: ClientId, _admin_demux_id: DemuxId) {}
+    // An admin asked the local user to unmute (see Client::ask_client_to_unmute). Nothing changes
+    // unless the user agrees, in which case the app unmutes as usual.
+    fn handle_unmute_requested(&self, _client_id: ClientId, _admin_demux_id: DemuxId) {}
+
+    // A chat message is ready to be shown (see Client::send_chat_message). Messages sent by the
+    // local device are included, and each device's messages arrive in the order it sent them.
+    fn handle_chat_message(&self, _client_id: ClientId, _message: &ChatMessage) {}
+
+    // The call's polls changed (see Client::create_poll), or the local device's latest poll
+    // action took effect. Includes every poll, open or closed, in the order they were created.
+    fn handle_polls_changed(&self, _client_id: ClientId, _polls: &[Poll]) {}
+
+    // While waiting for admin approval, what can be seen of the call changed.
+    fn handle_lobby_changed(&self, _client_id: ClientId, _lobby: &LobbyInfo) {}
+
+    // An admin started, changed, or ended (None) breakout rooms (see Client::start_breakout_rooms).
+    // The app moves the local device by leaving this call and joining the assigned room's call.
+    fn handle_breakout_rooms_changed(
+        &self,
+        _client_id: ClientId,
+        _breakout_rooms: Option<BreakoutRooms>,
+    ) {
+    }
+
+    // Called on the device taking over a call (see Client::join_by_handoff) once the
+    // other device has sent its state. The raised hand and video requests have already
+    // been applied; the app is expected to apply the mute state to its local tracks.
+    fn handle_handoff_state_received(&self, _client_id: ClientId, _state: HandoffState) {}
+    // Called on the device handing off a call, right before it leaves.
+    fn handle_handed_off(&self, _client_id: ClientId, _new_demux_id: DemuxId) {}
+
+    // Called when a viewport is set (see Client::set_viewport) and the devices to show in
+    // its tiles change.
+    fn handle_video_layout_changed(&self, _client_id: ClientId, _layout: &VideoLayout) {}
+
+    // Called on the device that rang the group whenever a recipient's status changes.
+    // Recipients the app has not heard from yet are included as RingRecipientStatus::Sent.
+    fn handle_ring_recipients_changed(
+        &self,
+        _client_id: ClientId,
+        _ring_id: RingId,
+        _recipients: &HashMap<UserId, RingRecipientStatus>,
+    ) {
+    }
+
     fn handle_rtc_stats_report(&self, report_json: String);
 
     // This will be the last callback.
@@ -513,6 +624,94 @@ pub enum EndReason {
 
 const ADMIN_LOG_TAG: &str = "AdminAction";
 
+/// What a device waiting for admin approval (JoinState::Pending) can see of the call.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LobbyInfo {
+    /// The number of devices in the call, not counting those still waiting.
+    pub participant_count: usize,
+    /// The call link admins currently in the call.
+    pub admins: Vec<UserId>,
+    /// How many devices have been waiting longer than the local device.
+    pub position: Option<usize>,
+    /// The note an admin left for waiting devices (see Client::send_lobby_message).
+    pub message: Option<String>,
+}
+
+impl LobbyInfo {
+    fn new(peek_info: &PeekInfo, local_demux_id: DemuxId, message: Option<String>) -> Self {
+        let mut admins: Vec<UserId> = Vec::new();
+        for device in &peek_info.devices {
+            if let Some(user_id) = &device.user_id {
+                if peek_info.admin_demux_ids.contains(&device.demux_id) && !admins.contains(user_id)
+                {
+                    admins.push(user_id.clone());
+                }
+            }
+        }
+        Self {
+            participant_count: peek_info.devices.len(),
+            admins,
+            position: peek_info
+                .pending_devices
+                .iter()
+                .position(|device| device.demux_id == local_demux_id),
+            message,
+        }
+    }
+}
+
+/// The raised hands in a call, in the order they were raised, and who was called on to speak.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpeakerQueue {
+    pub raised_hands: Vec<DemuxId>,
+    /// The device an admin called on (see Client::call_next_speaker).
+    pub current_speaker: Option<DemuxId>,
+}
+
+impl SpeakerQueue {
+    /// How many hands were raised before this device's, if its hand is raised.
+    pub fn position(&self, demux_id: DemuxId) -> Option<usize> {
+        self.raised_hands.iter().position(|id| *id == demux_id)
+    }
+
+    pub fn next_speaker(&self) -> Option<DemuxId> {
+        self.raised_hands.first().copied()
+    }
+}
+
+/// Which layers of the simulcast ladder the SFU is using, lowest layer first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForwardedVideoLayers {
+    /// How many of the local device's layers the SFU needs, from the height it asks for, which
+    /// is the tallest it forwards to anyone. Zero if no one is receiving the local video.
+    pub outgoing_layer_count: usize,
+    /// For each remote device whose video is forwarded to the local device, the height the SFU
+    /// allocated to it (CurrentDevices.allocated_heights). Remote devices may use a different
+    /// ladder, so this is left as a height rather than mapped to a local layer.
+    pub incoming_heights: HashMap<DemuxId, u16>,
+}
+
+impl ForwardedVideoLayers {
+    fn new(
+        config: &VideoEncoderConfig,
+        requested_height: u16,
+        forwarding_videos: &HashMap<DemuxId, u16>,
+    ) -> Self {
+        Self {
+            outgoing_layer_count: if requested_height == 0 {
+                0
+            } else {
+                config.layer_for_height(requested_height) + 1
+            },
+            incoming_heights: forwarding_videos
+                .iter()
+                .filter(|(_, height)| **height > 0)
+                .map(|(demux_id, height)| (*demux_id, *height))
+                .collect(),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Debug)]
 pub struct Reaction {
@@ -529,6 +728,9 @@ pub trait SfuClient {
     // Notifies the client of the new membership proof.
     fn set_membership_proof(&mut self, proof: MembershipProof);
     fn set_group_members(&mut self, members: Vec<GroupMember>);
+
+    // Hints to the SFU that the next join takes over from another device of the same user.
+    fn set_handoff_source(&mut self, _from_demux_id: Option<DemuxId>) {}
 }
 
 pub struct Joined {
@@ -552,6 +754,7 @@ pub struct HttpSfuClient {
     auth_header: Option<String>,
     member_resolver: Arc<dyn sfu::MemberResolver + Send + Sync>,
     deferred_join: Option<(String, String, [u8; 32], Client)>,
+    handoff_from_demux_id: Option<DemuxId>,
 }
 
 impl HttpSfuClient {
@@ -571,6 +774,7 @@ impl HttpSfuClient {
             auth_header: None,
             member_resolver: Arc::new(sfu::MemberMap::default()),
             deferred_join: None,
+            handoff_from_demux_id: None,
         }
     }
 
@@ -600,6 +804,7 @@ impl HttpSfuClient {
             self.room_id_header.clone(),
             auth_header,
             self.admin_passkey.as_deref(),
+            self.handoff_from_demux_id,
             ice_ufrag,
             ice_pwd,
             dhe_pub_key,
@@ -694,6 +899,10 @@ impl SfuClient for HttpSfuClient {
         info!("SfuClient set_group_members: {} members", members.len());
         self.set_member_resolver(Arc::new(sfu::MemberMap::new(&members)));
     }
+
+    fn set_handoff_source(&mut self, from_demux_id: Option<DemuxId>) {
+        self.handoff_from_demux_id = from_demux_id;
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -778,6 +987,15 @@ impl RemoteDeviceState {
         as_unix_millis(Some(self.added_time))
     }
 
+    // Used when this device took over the call from `old_device`, which belongs to the same user.
+    fn inherit_handed_off_state(&mut self, old_device: &RemoteDeviceState) {
+        self.added_time = self.added_time.min(old_device.added_time);
+        self.speaker_time = self.speaker_time.max(old_device.speaker_time);
+        if self.heartbeat_rtp_timestamp.is_none() {
+            self.heartbeat_state = old_device.heartbeat_state.clone();
+        }
+    }
+
     fn recalculate_higher_resolution_pending(&mut self) {
         let was_pending = self.is_higher_resolution_pending;
         self.is_higher_resolution_pending =
@@ -794,7 +1012,7 @@ impl RemoteDeviceState {
 
 /// These can be sent to the SFU to request different resolutions of
 /// video for different remote dem
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VideoRequest {
     pub demux_id: DemuxId,
     pub width: u16,
@@ -803,6 +1021,64 @@ pub struct VideoRequest {
     pub framerate: Option<u16>,
 }
 
+/// The state a device hands over when another device of the same user takes over
+/// the call (see [`Client::join_by_handoff`]).
+#[derive(Clone, Debug, Default)]
+pub struct HandoffState {
+    pub audio_muted: Option<bool>,
+    pub video_muted: Option<bool>,
+    pub hand_raised: bool,
+    pub video_requests: Vec<VideoRequest>,
+    pub active_speaker_height: Option<u16>,
+}
+
+impl From<protobuf::group_call::device_to_device::handoff::State> for HandoffState {
+    fn from(proto: protobuf::group_call::device_to_device::handoff::State) -> Self {
+        Self {
+            audio_muted: proto.audio_muted,
+            video_muted: proto.video_muted,
+            hand_raised: proto.hand_raised.unwrap_or(false),
+            video_requests: proto
+                .video_requests
+                .into_iter()
+                .filter_map(|request| {
+                    Some(VideoRequest {
+                        demux_id: request.demux_id?,
+                        width: request.width?.try_into().ok()?,
+                        height: request.height?.try_into().ok()?,
+                        framerate: request.framerate.and_then(|f| f.try_into().ok()),
+                    })
+                })
+                .collect(),
+            active_speaker_height: proto
+                .active_speaker_height
+                .and_then(|height| height.try_into().ok()),
+        }
+    }
+}
+
+impl From<HandoffState> for protobuf::group_call::device_to_device::handoff::State {
+    fn from(state: HandoffState) -> Self {
+        use protobuf::group_call::device_to_device::handoff;
+        Self {
+            audio_muted: state.audio_muted,
+            video_muted: state.video_muted,
+            hand_raised: Some(state.hand_raised),
+            video_requests: state
+                .video_requests
+                .into_iter()
+                .map(|request| handoff::VideoRequest {
+                    demux_id: Some(request.demux_id),
+                    width: Some(request.width.into()),
+                    height: Some(request.height.into()),
+                    framerate: request.framerate.map(Into::into),
+                })
+                .collect(),
+            active_speaker_height: state.active_speaker_height.map(Into::into),
+        }
+    }
+}
+
 // This must stay in sync with the data PT in SfuClient.
 const RTP_DATA_PAYLOAD_TYPE: rtp::PayloadType = 101;
 // This must stay in sync with the data SSRC offset in SfuClient.
@@ -827,6 +1103,9 @@ const SCREENSHARE_MAX_SEND_RATE: DataRate = DataRate::from_mbps(2);
 
 const LOW_MAX_RECEIVE_RATE: DataRate = DataRate::from_kbps(500);
 
+// The same limit as WebRTC's AudioSourceInterface::SetVolume.
+const MAX_REMOTE_AUDIO_GAIN: f32 = 10.0;
+
 const NORMAL_MAX_RECEIVE_RATE: DataRate = DataRate::from_mbps(20);
 
 const BWE_THRESHOLD_FOR_LOW_NOTIFICATION: DataRate = DataRate::from_kbps(70);
@@ -941,6 +1220,16 @@ enum OutgoingRingState {
     NotPermittedToRing,
 }
 
+/// The recipients of the ring sent by the local device, tracked until the local device leaves.
+///
+/// Unlike OutgoingRingState, this survives other people joining, so that recipients who haven't
+/// responded can be rung again from within the call.
+#[derive(Debug)]
+struct OutgoingRingRecipients {
+    ring_id: RingId,
+    statuses: HashMap<UserId, RingRecipientStatus>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GroupCallKind {
     SignalGroup,
@@ -977,6 +1266,48 @@ struct RaiseHandState {
     pub outstanding: bool,
 }
 
+/// How the local device plays a remote device's audio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct RemoteAudioPlayout {
+    gain: f32,
+    muted: bool,
+}
+
+impl Default for RemoteAudioPlayout {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl RemoteAudioPlayout {
+    fn effective_gain(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.gain
+        }
+    }
+}
+
+/// Progress of moving the call between two devices of the same user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandoffProgress {
+    None,
+    /// Taking over the call from another device. Once joined and connected, we keep
+    /// requesting its state at `next_request_time` until it answers or leaves.
+    Receiving {
+        from_demux_id: DemuxId,
+        next_request_time: Option<Instant>,
+    },
+    /// Handing the call over to another device, which is announced when leaving.
+    Sending {
+        to_demux_id: DemuxId,
+    },
+}
+
 /// The state inside the Actor
 struct State {
     // Things passed in that never change
@@ -1020,6 +1351,9 @@ struct State {
     sfu_info: Option<SfuInfo>,
     peer_connection: PeerConnection,
     peer_connection_observer_impl: Box<PeerConnectionObserverImpl>,
+    /// Only used to stop sending audio when an admin mutes us; otherwise the app
+    /// controls the outgoing audio track.
+    outgoing_audio_track: AudioTrack,
     rtp_data_to_sfu_next_seqnum: u32,
     rtp_data_through_sfu_next_seqnum: u32,
     next_heartbeat_time: Option<Instant>,
@@ -1084,6 +1418,9 @@ struct State {
     video_requests: Option<Vec<VideoRequest>>,
     active_speaker_height: Option<u16>,
     on_demand_video_request_sent_since_last_heartbeat: bool,
+    // If set, video_requests are derived from it and the remote devices on every tick.
+    viewport: Option<Viewport>,
+    video_layout: Option<VideoLayout>,
     speaker_rtp_timestamp: Option<rtp::Timestamp>,
 
     send_rates: SendRates,
@@ -1093,14 +1430,41 @@ struct State {
     data_mode: DataMode,
     // Demux IDs where video is being forward from, mapped to the server allocated height.
     forwarding_videos: HashMap<DemuxId, u16>,
+    video_encoder_config: VideoEncoderConfig,
+    // Remote devices whose audio the local user turned down, up, or off.
+    remote_audio_playout: HashMap<DemuxId, RemoteAudioPlayout>,
+    // The height the SFU last asked the local device to send.
+    requested_video_height: u16,
+    forwarded_video_layers: ForwardedVideoLayers,
 
     outgoing_ring_state: OutgoingRingState,
+    outgoing_ring_recipients: Option<OutgoingRingRecipients>,
 
     reactions: Vec<Reaction>,
     raised_hands: Vec<DemuxId>,
     raise_hand_state: RaiseHandState,
+    speaker_queue: SpeakerQueue,
+    spotlight: Vec<DemuxId>,
+    breakout_rooms: Option<BreakoutRooms>,
+    lobby_message: Option<String>,
+    // The last lobby info the observer was told about, if we're pending.
+    lobby: Option<LobbyInfo>,
+
+    chat_next_seqnum: u64,
+    chat_receiver: ChatReceiver,
+    // Sent messages that will be sent once more, in case a packet was lost.
+    chat_resends: Vec<(Instant, ChatMessage)>,
+    polls: PollsState,
+
+    handoff: HandoffProgress,
+    // Devices that announced they were replaced by a device we don't know about yet,
+    // keyed by the replacing demux ID, so the new device can take over their state.
+    handed_off_devices: HashMap<DemuxId, (Instant, RemoteDeviceState)>,
 
     sfu_reliable_stream: MrpStream<Vec<u8>, (rtp::Header, SfuToDevice)>,
+    // The lengths of the stream's windows, as of the last tick.
+    sfu_reliable_send_window: CounterShare,
+    sfu_reliable_receive_window: CounterShare,
     actor: Actor<State>,
 }
 
@@ -1178,6 +1542,16 @@ const DELAYED_BWE_CHECK: Duration = Duration::from_secs(10);
 
 const REACTION_STRING_MAX_SIZE: usize = 256;
 
+const LOBBY_MESSAGE_MAX_SIZE: usize = 256;
+
+// How long to wait before sending a chat message again.
+const CHAT_RESEND_DELAY: Duration = Duration::from_millis(500);
+
+// How often the device taking over a call asks the other device for its state.
+const HANDOFF_REQUEST_INTERVAL: Duration = Duration::from_secs(2);
+// How long to wait for the device taking over a call to show up in the list of devices.
+const HANDED_OFF_DEVICE_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct ClientStartParams {
     pub group_id: GroupId,
     pub client_id: ClientId,
@@ -1260,7 +1634,7 @@ impl Client {
                         &AudioJitterBufferConfig::default(),
                         audio_rtcp_report_interval_ms,
                         &ice_servers,
-                        outgoing_audio_track,
+                        outgoing_audio_track.clone(),
                         outgoing_video_track,
                     )
                     .inspect_err(|_| {
@@ -1311,6 +1685,7 @@ impl Client {
                     sfu_info: None,
                     peer_connection_observer_impl,
                     peer_connection,
+                    outgoing_audio_track,
                     rtp_data_to_sfu_next_seqnum: 1,
                     rtp_data_through_sfu_next_seqnum: 1,
 
@@ -1320,6 +1695,7 @@ impl Client {
                     get_stats_interval: DEFAULT_STATS_INTERVAL,
                     stats_observer: create_stats_observer(
                         call_id_for_stats,
+                        true,
                         DEFAULT_STATS_INTERVAL,
                     ),
 
@@ -1344,6 +1720,8 @@ impl Client {
 
                     video_requests: None,
                     active_speaker_height: None,
+                    viewport: None,
+                    video_layout: None,
                     on_demand_video_request_sent_since_last_heartbeat: false,
                     speaker_rtp_timestamp: None,
 
@@ -1353,14 +1731,33 @@ impl Client {
                     max_receive_rate: Some(NORMAL_MAX_RECEIVE_RATE),
                     data_mode: DataMode::Normal,
                     forwarding_videos: HashMap::default(),
+                    video_encoder_config: VideoEncoderConfig::default(),
+                    remote_audio_playout: HashMap::new(),
+                    requested_video_height: 0,
+                    forwarded_video_layers: ForwardedVideoLayers::default(),
 
                     outgoing_ring_state: OutgoingRingState::Unknown,
+                    outgoing_ring_recipients: None,
 
                     reactions: Vec::new(),
                     raised_hands: Vec::new(),
+                    spotlight: Vec::new(),
+                    breakout_rooms: None,
+                    lobby_message: None,
+                    lobby: None,
+                    chat_next_seqnum: 1,
+                    chat_receiver: ChatReceiver::default(),
+                    chat_resends: Vec::new(),
+                    polls: PollsState::default(),
                     raise_hand_state: RaiseHandState::default(),
+                    speaker_queue: SpeakerQueue::default(),
+
+                    handoff: HandoffProgress::None,
+                    handed_off_devices: HashMap::new(),
 
                     sfu_reliable_stream: MrpStream::with_capacity_limit(RELIABLE_RTP_BUFFER_SIZE),
+                    sfu_reliable_send_window: CounterShare::new(&counters::MRP_SEND_WINDOW),
+                    sfu_reliable_receive_window: CounterShare::new(&counters::MRP_RECEIVE_WINDOW),
 
                     actor,
                 })
@@ -1411,6 +1808,48 @@ impl Client {
         });
     }
 
+    // Replaces the simulcast ladder and codec preference for outgoing video. While not sharing
+    // the screen, the max send rate follows the ladder instead of the built-in limits.
+    // Fails without changing anything if the config is invalid or this build can't apply it.
+    pub fn set_video_encoder_config(&self, config: VideoEncoderConfig) -> Result<()> {
+        debug!(
+            "group_call::Client(outer)::set_video_encoder_config(client_id: {})",
+            self.client_id
+        );
+        if !PeerConnection::video_encoder_config_supported() {
+            return Err(anyhow::anyhow!(
+                "video encoder config needs the video_encoder_config feature"
+            ));
+        }
+        config.validate()?;
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_video_encoder_config(client_id: {})",
+                state.client_id
+            );
+            if state.video_encoder_config == config {
+                return;
+            }
+            state.video_encoder_config = config;
+            if matches!(state.join_state, JoinState::Joined(_)) {
+                state
+                    .peer_connection
+                    .configure_video_encoders(&state.video_encoder_config);
+                let send_rates = Self::compute_send_rates(
+                    state.remote_devices.len(),
+                    state
+                        .outgoing_heartbeat_state
+                        .sharing_screen
+                        .unwrap_or(false),
+                    &state.video_encoder_config.layers,
+                );
+                Self::set_send_rates_inner(state, send_rates);
+            }
+            Self::update_forwarded_video_layers(state);
+        });
+        Ok(())
+    }
+
     // Pulled into a named private method so we can call it recursively.
     fn tick(state: &mut State) {
         let now = Instant::now();
@@ -1422,6 +1861,16 @@ impl Client {
 
         Self::request_remote_devices_from_sfu_if_older_than(state, Duration::from_secs(10));
 
+        Self::update_video_layout(state);
+        Self::tick_chat(state, now);
+
+        state
+            .sfu_reliable_send_window
+            .set(state.sfu_reliable_stream.send_len());
+        state
+            .sfu_reliable_receive_window
+            .set(state.sfu_reliable_stream.receive_len());
+
         if let Some(next_heartbeat_time) = state.next_heartbeat_time {
             if now >= next_heartbeat_time {
                 if let Err(err) = Self::send_heartbeat(state) {
@@ -1574,6 +2023,11 @@ impl Client {
             }
         }
 
+        Self::request_handoff_state_if_needed(state, now);
+        state
+            .handed_off_devices
+            .retain(|_, (left_at, _)| now < *left_at + HANDED_OFF_DEVICE_TIMEOUT);
+
         let State {
             join_state,
             client_id,
@@ -1792,38 +2246,71 @@ impl Client {
                 "group_call::Client(inner)::join(client_id: {})",
                 state.client_id
             );
-            match state.join_state {
-                JoinState::Joining | JoinState::Pending(_) | JoinState::Joined(_) => {
-                    warn!("Already attempted to join.");
-                }
-                JoinState::NotJoined(ring_id) => {
-                    if Self::take_busy(state) {
-                        Self::set_join_state_and_notify_observer(state, JoinState::Joining);
-                        Self::accept_ring_if_needed(state, ring_id);
-
-                        if state.kind == GroupCallKind::SignalGroup {
-                            // Request group membership refresh before joining.
-                            // The Join request will then proceed once SfuClient has the token.
-                            state.observer.request_membership_proof(state.client_id);
-                            state.next_membership_proof_request_time =
-                                Some(Instant::now() + MEMBERSHIP_PROOF_REQUEST_INTERVAL);
-                        }
+            Self::join_inner(state, callback, None);
+        });
+    }
 
-                        let client_secret = EphemeralSecret::random_from_rng(OsRng);
-                        let client_pub_key = PublicKey::from(&client_secret);
-                        state.dhe_state = DheState::start(client_secret);
-                        state.sfu_client.join(
-                            &state.local_ice_ufrag,
-                            &state.local_ice_pwd,
-                            *client_pub_key.as_bytes(),
-                            callback,
-                        );
-                    } else {
-                        Self::end(state, EndReason::CallManagerIsBusy);
+    /// Joins the call, taking it over from another device of the same user that is
+    /// currently in the call as `from_demux_id`.
+    ///
+    /// Once joined and connected, this device asks the other one for its state
+    /// (see [`Observer::handle_handoff_state_received`]), after which the other
+    /// device leaves. Remote devices carry the state of the old device over to the new one.
+    pub fn join_by_handoff(&self, from_demux_id: DemuxId) {
+        debug!(
+            "group_call::Client(outer)::join_by_handoff(client_id: {}, from_demux_id: {})",
+            self.client_id, from_demux_id
+        );
+        let callback = self.clone();
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::join_by_handoff(client_id: {}, from_demux_id: {})",
+                state.client_id, from_demux_id
+            );
+            Self::join_inner(state, callback, Some(from_demux_id));
+        });
+    }
+
+    fn join_inner(state: &mut State, callback: Client, handoff_from_demux_id: Option<DemuxId>) {
+        match state.join_state {
+            JoinState::Joining | JoinState::Pending(_) | JoinState::Joined(_) => {
+                warn!("Already attempted to join.");
+            }
+            JoinState::NotJoined(ring_id) => {
+                if Self::take_busy(state) {
+                    Self::set_join_state_and_notify_observer(state, JoinState::Joining);
+                    Self::accept_ring_if_needed(state, ring_id);
+
+                    if state.kind == GroupCallKind::SignalGroup {
+                        // Request group membership refresh before joining.
+                        // The Join request will then proceed once SfuClient has the token.
+                        state.observer.request_membership_proof(state.client_id);
+                        state.next_membership_proof_request_time =
+                            Some(Instant::now() + MEMBERSHIP_PROOF_REQUEST_INTERVAL);
                     }
+
+                    let client_secret = EphemeralSecret::random_from_rng(OsRng);
+                    let client_pub_key = PublicKey::from(&client_secret);
+                    state.dhe_state = DheState::start(client_secret);
+                    state.handoff = match handoff_from_demux_id {
+                        Some(from_demux_id) => HandoffProgress::Receiving {
+                            from_demux_id,
+                            next_request_time: None,
+                        },
+                        None => HandoffProgress::None,
+                    };
+                    state.sfu_client.set_handoff_source(handoff_from_demux_id);
+                    state.sfu_client.join(
+                        &state.local_ice_ufrag,
+                        &state.local_ice_pwd,
+                        *client_pub_key.as_bytes(),
+                        callback,
+                    );
+                } else {
+                    Self::end(state, EndReason::CallManagerIsBusy);
                 }
             }
-        });
+        }
     }
 
     fn accept_ring_if_needed(state: &mut State, ring_id: Option<RingId>) {
@@ -1859,6 +2346,10 @@ impl Client {
             join_state
         );
         state.join_state = join_state;
+        if !matches!(join_state, JoinState::Pending(_)) {
+            state.lobby = None;
+            state.lobby_message = None;
+        }
         state
             .observer
             .handle_join_state_changed(state.client_id, join_state);
@@ -1905,6 +2396,22 @@ impl Client {
         state.next_audio_levels_time = None;
         state.next_speaking_audio_levels_time = None;
         state.next_membership_proof_request_time = None;
+        state.handoff = HandoffProgress::None;
+        state.outgoing_ring_recipients = None;
+        // The SFU will send the polls, the spotlight, and the speaker queue again if we rejoin.
+        state.polls = PollsState::default();
+        if state.speaker_queue != SpeakerQueue::default() {
+            state.speaker_queue = SpeakerQueue::default();
+            state
+                .observer
+                .handle_speaker_queue_changed(state.client_id, &state.speaker_queue);
+        }
+        if !state.spotlight.is_empty() {
+            state.spotlight.clear();
+            state
+                .observer
+                .handle_spotlight_changed(state.client_id, Vec::new());
+        }
     }
 
     pub fn disconnect(&self) {
@@ -1959,6 +2466,18 @@ impl Client {
                         Default::default(),
                     );
 
+                    let self_uuid = state.self_uuid.lock().expect("can read UUID").clone();
+                    state.outgoing_ring_recipients = Some(OutgoingRingRecipients {
+                        ring_id,
+                        statuses: state
+                            .known_members
+                            .iter()
+                            .filter(|user_id| Some(*user_id) != self_uuid.as_ref())
+                            .map(|user_id| (user_id.clone(), RingRecipientStatus::Sent))
+                            .collect(),
+                    });
+                    Self::notify_ring_recipients_changed(state);
+
                     if state.remote_devices.is_empty() {
                         // If you're the only one in the call at the time of the ring,
                         // and then you leave before anyone joins, the ring is auto-cancelled.
@@ -1994,58 +2513,200 @@ impl Client {
         }
     }
 
-    pub fn set_outgoing_audio_muted(&self, muted: bool) {
+    /// Rings the given recipients of the local device's ring again, using the same ring ID.
+    ///
+    /// Only recipients who haven't declined, been busy, or joined are rung. If `recipients` is
+    /// empty, all such recipients are rung.
+    pub fn ring_again(&self, recipients: Vec<UserId>) {
         debug!(
-            "group_call::Client(outer)::set_audio_muted(client_id: {}, muted: {})",
-            self.client_id, muted
+            "group_call::Client(outer)::ring_again(client_id: {}, recipients: {})",
+            self.client_id,
+            recipients.len(),
         );
         self.actor.send(move |state| {
             debug!(
-                "group_call::Client(inner)::set_audio_muted(client_id: {}, muted: {})",
-                state.client_id, muted
+                "group_call::Client(inner)::ring_again(client_id: {})",
+                state.client_id
             );
-            // We don't modify the outgoing audio track.  We expect the app to handle that.
-            state.outgoing_heartbeat_state.audio_muted = Some(muted);
-            if let Err(err) = Self::send_heartbeat(state) {
+
+            let Some(outgoing_ring_recipients) = &mut state.outgoing_ring_recipients else {
                 warn!(
-                    "Failed to send heartbeat after updating audio mute state: {:?}",
-                    err
+                    "cannot ring again without having rung first (client_id: {})",
+                    state.client_id
+                );
+                return;
+            };
+            let ring_id = outgoing_ring_recipients.ring_id;
+
+            let to_ring: HashSet<UserId> = outgoing_ring_recipients
+                .statuses
+                .iter_mut()
+                .filter(|(user_id, status)| {
+                    !status.is_final() && (recipients.is_empty() || recipients.contains(*user_id))
+                })
+                .map(|(user_id, status)| {
+                    *status = RingRecipientStatus::Sent;
+                    user_id.clone()
+                })
+                .collect();
+            if to_ring.is_empty() {
+                // An empty override would ring the whole group.
+                info!(
+                    "no recipients left to ring (client_id: {})",
+                    state.client_id
                 );
+                return;
             }
+
+            info!(
+                "ringing {} recipients again (client_id: {}, ring_id: {})",
+                to_ring.len(),
+                state.client_id,
+                ring_id
+            );
+            let message = protobuf::signaling::CallMessage {
+                ring_intention: Some(protobuf::signaling::call_message::RingIntention {
+                    group_id: Some(state.group_id.clone()),
+                    ring_id: Some(ring_id.into()),
+                    r#type: Some(
+                        protobuf::signaling::call_message::ring_intention::Type::Ring.into(),
+                    ),
+                }),
+                ..Default::default()
+            };
+            state.observer.send_signaling_message_to_group(
+                state.group_id.clone(),
+                message,
+                SignalingMessageUrgency::HandleImmediately,
+                to_ring,
+            );
+            Self::notify_ring_recipients_changed(state);
         });
     }
 
-    pub fn set_outgoing_video_muted(&self, muted: bool) {
-        debug!(
-            "group_call::Client(outer)::set_video_muted(client_id: {}, muted: {})",
-            self.client_id, muted
-        );
+    /// Called by the app when the message carrying the local device's ring was delivered to
+    /// `recipient`.
+    pub fn on_ring_delivered(&self, recipient: UserId) {
         self.actor.send(move |state| {
-            debug!(
-                "group_call::Client(inner)::set_video_muted(client_id: {}, muted: {})",
-                state.client_id, muted
+            Self::update_ring_recipient_status(
+                state,
+                recipient,
+                None,
+                RingRecipientStatus::Delivered,
             );
-            // We don't modify the outgoing video track.  We expect the app to handle that.
-            state.outgoing_heartbeat_state.video_muted = Some(muted);
-            if let Err(err) = Self::send_heartbeat(state) {
-                warn!(
-                    "Failed to send heartbeat after updating video mute state: {:?}",
-                    err
-                );
-            }
         });
     }
 
-    pub fn set_presenting(&self, presenting: bool) {
-        debug!(
-            "group_call::Client(outer)::set_presenting(client_id: {}, presenting: {})",
-            self.client_id, presenting
-        );
+    /// Called by the CallManager when another user responded to the local device's ring.
+    pub fn on_ring_response_received(
+        &self,
+        sender: UserId,
+        ring_id: RingId,
+        status: RingRecipientStatus,
+    ) {
         self.actor.send(move |state| {
-            debug!(
-                "group_call::Client(inner)::set_presenting(client_id: {}, presenting: {})",
-                state.client_id, presenting
-            );
+            Self::update_ring_recipient_status(state, sender, Some(ring_id), status);
+        });
+    }
+
+    fn update_ring_recipient_status(
+        state: &mut State,
+        recipient: UserId,
+        ring_id: Option<RingId>,
+        status: RingRecipientStatus,
+    ) {
+        let Some(outgoing_ring_recipients) = &mut state.outgoing_ring_recipients else {
+            info!(
+                "ignoring ring response for a ring we're not tracking (client_id: {})",
+                state.client_id
+            );
+            return;
+        };
+        if ring_id.is_some_and(|ring_id| ring_id != outgoing_ring_recipients.ring_id) {
+            info!(
+                "ignoring ring response for old ring {:?} (client_id: {})",
+                ring_id, state.client_id
+            );
+            return;
+        }
+
+        // Responses can come from people who weren't known members when the ring was sent.
+        let previous = outgoing_ring_recipients
+            .statuses
+            .entry(recipient)
+            .or_insert(RingRecipientStatus::Sent);
+        if status.should_replace(*previous) {
+            *previous = status;
+            Self::notify_ring_recipients_changed(state);
+        }
+    }
+
+    fn notify_ring_recipients_changed(state: &State) {
+        if let Some(outgoing_ring_recipients) = &state.outgoing_ring_recipients {
+            state.observer.handle_ring_recipients_changed(
+                state.client_id,
+                outgoing_ring_recipients.ring_id,
+                &outgoing_ring_recipients.statuses,
+            );
+        }
+    }
+
+    pub fn set_outgoing_audio_muted(&self, muted: bool) {
+        debug!(
+            "group_call::Client(outer)::set_audio_muted(client_id: {}, muted: {})",
+            self.client_id, muted
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_audio_muted(client_id: {}, muted: {})",
+                state.client_id, muted
+            );
+            Self::set_outgoing_audio_muted_inner(state, muted);
+        });
+    }
+
+    fn set_outgoing_audio_muted_inner(state: &mut State, muted: bool) {
+        // We don't modify the outgoing audio track.  We expect the app to handle that.
+        state.outgoing_heartbeat_state.audio_muted = Some(muted);
+        if let Err(err) = Self::send_heartbeat(state) {
+            warn!(
+                "Failed to send heartbeat after updating audio mute state: {:?}",
+                err
+            );
+        }
+    }
+
+    pub fn set_outgoing_video_muted(&self, muted: bool) {
+        debug!(
+            "group_call::Client(outer)::set_video_muted(client_id: {}, muted: {})",
+            self.client_id, muted
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_video_muted(client_id: {}, muted: {})",
+                state.client_id, muted
+            );
+            // We don't modify the outgoing video track.  We expect the app to handle that.
+            state.outgoing_heartbeat_state.video_muted = Some(muted);
+            if let Err(err) = Self::send_heartbeat(state) {
+                warn!(
+                    "Failed to send heartbeat after updating video mute state: {:?}",
+                    err
+                );
+            }
+        });
+    }
+
+    pub fn set_presenting(&self, presenting: bool) {
+        debug!(
+            "group_call::Client(outer)::set_presenting(client_id: {}, presenting: {})",
+            self.client_id, presenting
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_presenting(client_id: {}, presenting: {})",
+                state.client_id, presenting
+            );
             state.outgoing_heartbeat_state.presenting = Some(presenting);
             if let Err(err) = Self::send_heartbeat(state) {
                 warn!(
@@ -2073,7 +2734,11 @@ impl Client {
                     err
                 );
             }
-            let send_rates = Self::compute_send_rates(state.remote_devices.len(), sharing_screen);
+            let send_rates = Self::compute_send_rates(
+                state.remote_devices.len(),
+                sharing_screen,
+                &state.video_encoder_config.layers,
+            );
             Self::set_send_rates_inner(state, send_rates);
         });
     }
@@ -2196,13 +2861,162 @@ impl Client {
                 "group_call::Client(inner)::request_video(client_id: {})",
                 state.client_id
             );
-            state.video_requests = Some(requests);
-            state.active_speaker_height = Some(active_speaker_height);
-            if !state.on_demand_video_request_sent_since_last_heartbeat {
-                Self::send_video_requests_to_sfu(state);
-                state.on_demand_video_request_sent_since_last_heartbeat = true;
+            if state.viewport.take().is_some() {
+                info!("explicit video requests replace the viewport");
+                state.video_layout = None;
             }
+            Self::set_video_requests(state, requests, active_speaker_height);
+        });
+    }
+
+    /// Scales the local playout volume of one remote device's audio, where 1.0 leaves it
+    /// unchanged. Only affects what the local device hears. Fails if the gain isn't a number or
+    /// this build can't apply it.
+    pub fn set_remote_audio_gain(&self, demux_id: DemuxId, gain: f32) -> Result<()> {
+        debug!(
+            "group_call::Client(outer)::set_remote_audio_gain(client_id: {}, demux_id: {}, gain: {})",
+            self.client_id, demux_id, gain,
+        );
+        Self::check_remote_audio_playout_supported()?;
+        if !gain.is_finite() {
+            return Err(anyhow::anyhow!("invalid remote audio gain {}", gain));
+        }
+        let gain = gain.clamp(0.0, MAX_REMOTE_AUDIO_GAIN);
+        self.actor.send(move |state| {
+            info!(
+                "group_call::Client(inner)::set_remote_audio_gain(client_id: {})",
+                state.client_id
+            );
+            Self::update_remote_audio_playout(state, demux_id, |playout| playout.gain = gain);
+        });
+        Ok(())
+    }
+
+    /// Silences one remote device's audio for the local device only, keeping its gain for when
+    /// it's unmuted. Fails if this build can't apply it.
+    pub fn set_remote_audio_muted(&self, demux_id: DemuxId, muted: bool) -> Result<()> {
+        debug!(
+            "group_call::Client(outer)::set_remote_audio_muted(client_id: {}, demux_id: {}, muted: {})",
+            self.client_id, demux_id, muted,
+        );
+        Self::check_remote_audio_playout_supported()?;
+        self.actor.send(move |state| {
+            info!(
+                "group_call::Client(inner)::set_remote_audio_muted(client_id: {})",
+                state.client_id
+            );
+            Self::update_remote_audio_playout(state, demux_id, |playout| playout.muted = muted);
         });
+        Ok(())
+    }
+
+    fn check_remote_audio_playout_supported() -> Result<()> {
+        if !PeerConnection::incoming_audio_gain_supported() {
+            return Err(anyhow::anyhow!(
+                "remote audio playout needs the incoming_audio_gain feature"
+            ));
+        }
+        Ok(())
+    }
+
+    fn update_remote_audio_playout(
+        state: &mut State,
+        demux_id: DemuxId,
+        update: impl FnOnce(&mut RemoteAudioPlayout),
+    ) {
+        if state.remote_devices.find_by_demux_id(demux_id).is_none() {
+            warn!(
+                "Ignoring audio playout change for unknown device {}",
+                demux_id
+            );
+            return;
+        }
+        let playout = state.remote_audio_playout.entry(demux_id).or_default();
+        update(playout);
+        let gain = playout.effective_gain();
+        if *playout == RemoteAudioPlayout::default() {
+            state.remote_audio_playout.remove(&demux_id);
+        }
+        state
+            .peer_connection
+            .set_incoming_audio_gain(demux_id, gain);
+    }
+
+    fn set_video_requests(
+        state: &mut State,
+        requests: Vec<VideoRequest>,
+        active_speaker_height: u16,
+    ) {
+        state.video_requests = Some(requests);
+        state.active_speaker_height = Some(active_speaker_height);
+        if !state.on_demand_video_request_sent_since_last_heartbeat {
+            Self::send_video_requests_to_sfu(state);
+            state.on_demand_video_request_sent_since_last_heartbeat = true;
+        }
+    }
+
+    /// Lets the client choose which devices to show and request their video, instead of the app
+    /// calling [`Self::request_video`]. The layout is updated as the remote devices change and
+    /// reported through [`Observer::handle_video_layout_changed`].
+    ///
+    /// Passing `None` stops updating the layout but keeps the last requests.
+    pub fn set_viewport(&self, viewport: Option<Viewport>) {
+        debug!(
+            "group_call::Client(outer)::set_viewport(client_id: {}, viewport: {:?})",
+            self.client_id, viewport,
+        );
+        self.actor.send(move |state| {
+            info!(
+                "group_call::Client(inner)::set_viewport(client_id: {})",
+                state.client_id
+            );
+            state.viewport = viewport;
+            state.video_layout = None;
+            Self::update_video_layout(state);
+        });
+    }
+
+    fn update_video_layout(state: &mut State) {
+        let Some(viewport) = &state.viewport else {
+            return;
+        };
+        // Spotlighted devices are shown ahead of the app's own pins.
+        let mut layout = Viewport {
+            pinned: state
+                .spotlight
+                .iter()
+                .chain(&viewport.pinned)
+                .copied()
+                .collect(),
+            ..viewport.clone()
+        }
+        .layout(&state.remote_devices);
+        // Spotlighted devices are requested at the size of the largest tile even when shown in a
+        // smaller one, which hints the SFU to allocate them more.
+        if let Some(largest) = viewport.tiles.iter().max_by_key(|tile| tile.height) {
+            for request in &mut layout.video_requests {
+                if state.spotlight.contains(&request.demux_id)
+                    && request.height > 0
+                    && request.height < largest.height
+                {
+                    request.width = largest.width;
+                    request.height = largest.height;
+                }
+            }
+        }
+        if state.video_layout.as_ref() == Some(&layout) {
+            return;
+        }
+
+        Self::set_video_requests(
+            state,
+            layout.video_requests.clone(),
+            layout.active_speaker_height,
+        );
+        state
+            .observer
+            .handle_video_layout_changed(state.client_id, &layout);
+        state.video_layout = Some(layout);
     }
 
     fn send_video_requests_to_sfu(state: &mut State) {
@@ -2402,6 +3216,267 @@ impl Client {
         });
     }
 
+    // Mutes the audio of another device in the call. Like other admin actions, the calling server
+    // checks that the local user is an admin.
+    pub fn mute_client(&self, other_client: DemuxId) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, GenericAdminAction};
+        debug!(
+            "group_call::Client(outer)::mute_client(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::mute_client(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::Mute(GenericAdminAction {
+                    target_demux_id: Some(other_client),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send mute for {other_client}: {e:?}");
+            } else {
+                info!("{ADMIN_LOG_TAG}: Sent mute for {other_client}");
+            }
+        });
+    }
+
+    // Asks another device in the call to unmute. Unlike muting, it's up to the other user.
+    pub fn ask_client_to_unmute(&self, other_client: DemuxId) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, GenericAdminAction};
+        debug!(
+            "group_call::Client(outer)::ask_client_to_unmute(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::ask_client_to_unmute(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::AskToUnmute(GenericAdminAction {
+                    target_demux_id: Some(other_client),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send ask-to-unmute for {other_client}: {e:?}");
+            } else {
+                info!("{ADMIN_LOG_TAG}: Sent ask-to-unmute for {other_client}");
+            }
+        });
+    }
+
+    // Lowers another device's raised hand. Only admins may do this.
+    pub fn lower_hand(&self, other_client: DemuxId) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, GenericAdminAction};
+        debug!(
+            "group_call::Client(outer)::lower_hand(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::lower_hand(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::LowerHand(GenericAdminAction {
+                    target_demux_id: Some(other_client),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send lower hand for {other_client}: {e:?}");
+            } else {
+                info!("{ADMIN_LOG_TAG}: Sent lower hand for {other_client}");
+            }
+        });
+    }
+
+    // Calls on the first device in the speaker queue, lowering its hand, or clears the current
+    // speaker if no hands are raised. If ask_to_unmute is set, the new speaker is also asked to
+    // unmute, as with ask_client_to_unmute. Only admins may do this.
+    pub fn call_next_speaker(&self, ask_to_unmute: bool) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, CallNextSpeaker};
+        debug!(
+            "group_call::Client(outer)::call_next_speaker(client_id: {}, ask_to_unmute: {})",
+            self.client_id, ask_to_unmute
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::call_next_speaker(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::CallNextSpeaker(CallNextSpeaker {
+                    ask_to_unmute: Some(ask_to_unmute),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send call next speaker: {e:?}");
+            } else {
+                info!(
+                    "{ADMIN_LOG_TAG}: Sent call next speaker (expected: {:?})",
+                    state.speaker_queue.next_speaker()
+                );
+            }
+        });
+    }
+
+    // Replaces the devices everyone in the call is asked to feature. Only admins may do this;
+    // the calling server ignores the request otherwise.
+    pub fn set_spotlight(&self, demux_ids: Vec<DemuxId>) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, Spotlight};
+        debug!(
+            "group_call::Client(outer)::set_spotlight(client_id: {}, demux_ids: {:?})",
+            self.client_id, demux_ids
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::set_spotlight(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::Spotlight(Spotlight {
+                    demux_ids: demux_ids.clone(),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send spotlight for {demux_ids:?}: {e:?}");
+            } else {
+                info!("{ADMIN_LOG_TAG}: Sent spotlight for {demux_ids:?}");
+            }
+        });
+    }
+
+    // Splits the call into `room_count` breakout rooms. Devices can be assigned individually or by
+    // user, in which case every device of that user in the call is assigned; device assignments
+    // win. If `duration` is set, everyone is asked to return to the main room after it.
+    //
+    // Only admins may do this; the calling server ignores the request otherwise.
+    pub fn start_breakout_rooms(
+        &self,
+        room_count: u32,
+        device_assignments: HashMap<DemuxId, u32>,
+        user_assignments: HashMap<UserId, u32>,
+        duration: Option<Duration>,
+    ) {
+        debug!(
+            "group_call::Client(outer)::start_breakout_rooms(client_id: {}, room_count: {})",
+            self.client_id, room_count
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::start_breakout_rooms(client_id: {})",
+                state.client_id
+            );
+
+            let mut assignments: HashMap<DemuxId, u32> = state
+                .remote_devices
+                .iter()
+                .filter_map(|device| {
+                    let room_index = user_assignments.get(&device.user_id)?;
+                    Some((device.demux_id, *room_index))
+                })
+                .collect();
+            assignments.extend(device_assignments);
+            let breakout_rooms = BreakoutRooms {
+                room_count,
+                assignments,
+                return_at: duration.map(|duration| SystemTime::now() + duration),
+            };
+            Self::send_breakout_rooms(state, Some(&breakout_rooms));
+        });
+    }
+
+    // Asks everyone in this call to return to the main room. In a breakout room, this only reaches
+    // the devices in that room.
+    pub fn end_breakout_rooms(&self) {
+        debug!(
+            "group_call::Client(outer)::end_breakout_rooms(client_id: {})",
+            self.client_id
+        );
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::end_breakout_rooms(client_id: {})",
+                state.client_id
+            );
+            Self::send_breakout_rooms(state, None);
+        });
+    }
+
+    fn send_breakout_rooms(state: &mut State, breakout_rooms: Option<&BreakoutRooms>) {
+        use protobuf::group_call::device_to_sfu::AdminAction;
+
+        let msg = DeviceToSfu {
+            admin_action: Some(AdminAction::BreakoutRooms(BreakoutRooms::to_proto(
+                breakout_rooms,
+            ))),
+            ..Default::default()
+        };
+
+        if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+            warn!("{ADMIN_LOG_TAG}: Failed to send breakout rooms: {e:?}");
+        } else {
+            info!(
+                "{ADMIN_LOG_TAG}: Sent breakout rooms: {:?}",
+                breakout_rooms.map(|breakout_rooms| breakout_rooms.room_count)
+            );
+        }
+    }
+
+    // Leaves a short note for devices waiting for approval, replacing any previous one. An empty
+    // message clears it. Only admins may do this; the calling server ignores the request otherwise.
+    pub fn send_lobby_message(&self, message: String) {
+        use protobuf::group_call::device_to_sfu::{AdminAction, LobbyMessage};
+        debug!(
+            "group_call::Client(outer)::send_lobby_message(client_id: {})",
+            self.client_id
+        );
+
+        if message.len() > LOBBY_MESSAGE_MAX_SIZE {
+            warn!(
+                "group_call::Client(outer)::send_lobby_message message size of {} exceeded allowed size of {}",
+                message.len(),
+                LOBBY_MESSAGE_MAX_SIZE
+            );
+            return;
+        }
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::send_lobby_message(client_id: {})",
+                state.client_id
+            );
+
+            let msg = DeviceToSfu {
+                admin_action: Some(AdminAction::LobbyMessage(LobbyMessage {
+                    text: Some(message),
+                })),
+                ..Default::default()
+            };
+
+            if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+                warn!("{ADMIN_LOG_TAG}: Failed to send lobby message: {e:?}");
+            } else {
+                info!("{ADMIN_LOG_TAG}: Sent lobby message");
+            }
+        });
+    }
+
     pub fn set_group_members(&self, group_members: Vec<GroupMember>) {
         debug!(
             "group_call::Client(outer)::set_group_members(client_id: {})",
@@ -2474,6 +3549,143 @@ impl Client {
         }
     }
 
+    pub fn send_chat_message(&self, text: String, reply_to: Option<ChatMessageId>) {
+        debug!(
+            "group_call::Client(outer)::send_chat_message(client_id: {}, reply_to: {:?})",
+            self.client_id, reply_to
+        );
+
+        if text.is_empty() {
+            warn!("group_call::Client(outer)::send_chat_message text is empty");
+        } else if text.len() > CHAT_MESSAGE_MAX_SIZE {
+            warn!(
+                "group_call::Client(outer)::send_chat_message text size of {} exceeded allowed size of {}",
+                text.len(),
+                CHAT_MESSAGE_MAX_SIZE
+            );
+        } else {
+            self.actor.send(move |state| {
+                debug!(
+                    "group_call::Client(inner)::send_chat_message(client_id: {})",
+                    state.client_id
+                );
+                let JoinState::Joined(local_demux_id) = state.join_state else {
+                    warn!("Can't send chat message before joining");
+                    return;
+                };
+                let message = ChatMessage {
+                    id: ChatMessageId {
+                        demux_id: local_demux_id,
+                        seqnum: state.chat_next_seqnum,
+                    },
+                    text,
+                    reply_to,
+                };
+                state.chat_next_seqnum += 1;
+                if let Err(err) = Self::send_chat_fragments(state, &message) {
+                    warn!("Failed to send chat message: {:?}", err);
+                }
+                state
+                    .chat_resends
+                    .push((Instant::now() + CHAT_RESEND_DELAY, message.clone()));
+                state
+                    .observer
+                    .handle_chat_message(state.client_id, &message);
+            });
+        }
+    }
+
+    pub fn create_poll(&self, question: String, options: Vec<String>) {
+        use protobuf::group_call::device_to_sfu::poll_action::{Action, Create};
+        debug!(
+            "group_call::Client(outer)::create_poll(client_id: {}, options: {})",
+            self.client_id,
+            options.len()
+        );
+
+        if let Err(err) = polls::validate_new_poll(&question, &options) {
+            warn!("group_call::Client(outer)::create_poll {}", err);
+            return;
+        }
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::create_poll(client_id: {})",
+                state.client_id
+            );
+            Self::send_poll_action(
+                state,
+                Action::Create(Create {
+                    question: Some(question),
+                    options,
+                }),
+            );
+        });
+    }
+
+    /// Votes for the option at `option_index`, replacing any earlier vote in the same poll, or
+    /// withdraws the vote if `option_index` is None.
+    pub fn vote_in_poll(&self, poll_id: PollId, option_index: Option<u32>) {
+        use protobuf::group_call::device_to_sfu::poll_action::{Action, Vote};
+        debug!(
+            "group_call::Client(outer)::vote_in_poll(client_id: {}, poll_id: {}, option_index: {:?})",
+            self.client_id, poll_id, option_index
+        );
+
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::vote_in_poll(client_id: {})",
+                state.client_id
+            );
+            match state.polls.find(poll_id) {
+                Some(poll) if poll.closed => {
+                    warn!("Can't vote in closed poll {}", poll_id);
+                }
+                Some(poll)
+                    if option_index.is_some_and(|index| index as usize >= poll.options.len()) =>
+                {
+                    warn!(
+                        "Can't vote for option {:?} of poll {}",
+                        option_index, poll_id
+                    );
+                }
+                Some(_) => {
+                    Self::send_poll_action(
+                        state,
+                        Action::Vote(Vote {
+                            poll_id: Some(poll_id),
+                            option_index,
+                        }),
+                    );
+                }
+                None => {
+                    warn!("Can't vote in unknown poll {}", poll_id);
+                }
+            }
+        });
+    }
+
+    /// Stops a poll from accepting votes. Only the poll's creator or an admin can do this.
+    pub fn close_poll(&self, poll_id: PollId) {
+        use protobuf::group_call::device_to_sfu::poll_action::{Action, Close};
+        debug!(
+            "group_call::Client(outer)::close_poll(client_id: {}, poll_id: {})",
+            self.client_id, poll_id
+        );
+
+        self.actor.send(move |state| {
+            debug!(
+                "group_call::Client(inner)::close_poll(client_id: {})",
+                state.client_id
+            );
+            Self::send_poll_action(
+                state,
+                Action::Close(Close {
+                    poll_id: Some(poll_id),
+                }),
+            );
+        });
+    }
+
     pub fn raise_hand(&self, raise: bool) {
         debug!(
             "group_call::Client(outer)::raise_hand(client_id: {} raise: {})",
@@ -2700,6 +3912,11 @@ impl Client {
         state
             .peer_connection
             .configure_audio_encoders(&AudioEncoderConfig::default());
+        if state.video_encoder_config != VideoEncoderConfig::default() {
+            state
+                .peer_connection
+                .configure_video_encoders(&state.video_encoder_config);
+        }
     }
 
     pub fn on_signaling_message_received(
@@ -2755,12 +3972,21 @@ impl Client {
                     group_id: Some(group_id),
                     leaving: Some(protobuf::group_call::device_to_device::Leaving {
                         demux_id: Some(leaving_demux_id),
-                        ..
+                        replaced_by_demux_id,
                     }),
                     ..
                 } => {
                     if group_id == state.group_id {
-                        Self::handle_leaving_received(state, leaving_demux_id);
+                        Self::handle_leaving_received(state, leaving_demux_id, replaced_by_demux_id);
+                    }
+                }
+                protobuf::group_call::DeviceToDevice {
+                    group_id: Some(group_id),
+                    handoff: Some(handoff),
+                    ..
+                } => {
+                    if group_id == state.group_id {
+                        Self::handle_handoff_received(state, sender_user_id, handoff);
                     }
                 }
                 _ => {
@@ -2993,7 +4219,17 @@ impl Client {
                                 return existing_remote_device;
                             }
                         }
-                        RemoteDeviceState::new(device.demux_id, user_id.clone(), added_time)
+                        let mut new_remote_device =
+                            RemoteDeviceState::new(device.demux_id, user_id.clone(), added_time);
+                        // Take over the state of the device this one replaced, if any.
+                        if let Some((_, handed_off_device)) =
+                            state.handed_off_devices.remove(&device.demux_id)
+                        {
+                            if &handed_off_device.user_id == user_id {
+                                new_remote_device.inherit_handed_off_state(&handed_off_device);
+                            }
+                        }
+                        new_remote_device
                     })
                 })
                 .collect();
@@ -3005,6 +4241,12 @@ impl Client {
                 new_demux_ids.difference(&old_demux_ids).copied().collect();
 
             let demux_ids_changed = old_demux_ids != new_demux_ids;
+            state
+                .chat_receiver
+                .retain_senders(|demux_id| new_demux_ids.contains(&demux_id));
+            state
+                .remote_audio_playout
+                .retain(|demux_id, _| new_demux_ids.contains(demux_id));
             // If demux IDs changed, let the PeerConnection know that related SSRCs changed as well
             if demux_ids_changed {
                 info!(
@@ -3146,6 +4388,7 @@ impl Client {
                         .outgoing_heartbeat_state
                         .sharing_screen
                         .unwrap_or(false),
+                    &state.video_encoder_config.layers,
                 );
                 Self::set_send_rates_inner(state, send_rates);
             }
@@ -3164,9 +4407,26 @@ impl Client {
 
         // Do this later so that we can use new_user_ids above without running into
         // referencing issues
+        if let Some(outgoing_ring_recipients) = &mut state.outgoing_ring_recipients {
+            let mut changed = false;
+            for user_id in &new_user_ids {
+                if let Some(status) = outgoing_ring_recipients.statuses.get_mut(user_id) {
+                    if RingRecipientStatus::Accepted.should_replace(*status) {
+                        *status = RingRecipientStatus::Accepted;
+                        changed = true;
+                    }
+                }
+            }
+            if changed {
+                Self::notify_ring_recipients_changed(state);
+            }
+        }
         state.joined_members = new_user_ids;
         state.pending_users_signature = new_pending_users_signature;
 
+        Self::update_video_layout(state);
+        Self::update_lobby(state);
+
         if should_request_again {
             // Something occurred while we were waiting for this update.
             // We should request again.
@@ -3176,7 +4436,14 @@ impl Client {
     }
 
     // Returns (min, start, max)
-    fn compute_send_rates(joined_member_count: usize, sharing_screen: bool) -> SendRates {
+    fn compute_send_rates(
+        joined_member_count: usize,
+        sharing_screen: bool,
+        video_layers: &[VideoEncoderLayer],
+    ) -> SendRates {
+        let sum_of_layers = |layers: &[VideoEncoderLayer]| {
+            DataRate::from_bps(layers.iter().map(|layer| layer.max_bitrate.as_bps()).sum())
+        };
         match (joined_member_count, sharing_screen) {
             (0, _) => SendRates {
                 max: Some(ALL_ALONE_MAX_SEND_RATE),
@@ -3187,10 +4454,23 @@ impl Client {
                 start: Some(SCREENSHARE_START_SEND_RATE),
                 max: Some(SCREENSHARE_MAX_SEND_RATE),
             },
+            (1..=7, _) if !video_layers.is_empty() => SendRates {
+                max: Some(sum_of_layers(video_layers)),
+                ..SendRates::default()
+            },
             (1..=7, _) => SendRates {
                 max: Some(SMALL_CALL_MAX_SEND_RATE),
                 ..SendRates::default()
             },
+            // Like LARGE_CALL_MAX_SEND_RATE, leave out the top layer.
+            (_, _) if video_layers.len() > 1 => SendRates {
+                max: Some(sum_of_layers(&video_layers[..video_layers.len() - 1])),
+                ..SendRates::default()
+            },
+            (_, _) if !video_layers.is_empty() => SendRates {
+                max: Some(sum_of_layers(video_layers)),
+                ..SendRates::default()
+            },
             _ => SendRates {
                 max: Some(LARGE_CALL_MAX_SEND_RATE),
                 ..SendRates::default()
@@ -3214,6 +4494,12 @@ impl Client {
         state
             .peer_connection
             .update_transceivers(&remote_demux_ids)?;
+        // New transceivers start out at full volume.
+        for (demux_id, playout) in &state.remote_audio_playout {
+            state
+                .peer_connection
+                .set_incoming_audio_gain(*demux_id, playout.effective_gain());
+        }
 
         // Call create_offer for the side effect of setting up the state of the RtpTransceivers
         // potentially created above.
@@ -3647,6 +4933,50 @@ impl Client {
         Self::broadcast_data_through_sfu(state, &heartbeat_msg.encode_to_vec())
     }
 
+    fn send_chat_fragments(state: &mut State, message: &ChatMessage) -> Result<()> {
+        for chat_fragment in message.to_fragments() {
+            let msg = protobuf::group_call::DeviceToDevice {
+                chat_fragment: Some(chat_fragment),
+                ..Default::default()
+            };
+            Self::broadcast_data_through_sfu(state, &msg.encode_to_vec())?;
+        }
+        Ok(())
+    }
+
+    fn tick_chat(state: &mut State, now: Instant) {
+        let (due, not_due): (Vec<_>, Vec<_>) = std::mem::take(&mut state.chat_resends)
+            .into_iter()
+            .partition(|(resend_at, _)| now >= *resend_at);
+        state.chat_resends = not_due;
+        for (_, message) in due {
+            if let Err(err) = Self::send_chat_fragments(state, &message) {
+                warn!("Failed to resend chat message: {:?}", err);
+            }
+        }
+
+        for message in state.chat_receiver.expire(now) {
+            state
+                .observer
+                .handle_chat_message(state.client_id, &message);
+        }
+    }
+
+    fn handle_chat_fragment_received(
+        state: &mut State,
+        demux_id: DemuxId,
+        chat_fragment: protobuf::group_call::device_to_device::ChatFragment,
+    ) {
+        for message in state
+            .chat_receiver
+            .receive(demux_id, chat_fragment, Instant::now())
+        {
+            state
+                .observer
+                .handle_chat_message(state.client_id, &message);
+        }
+    }
+
     fn send_reaction(state: &mut State, value: String) -> Result<()> {
         let react_msg = protobuf::group_call::DeviceToDevice {
             reaction: {
@@ -3657,6 +4987,28 @@ impl Client {
         Self::broadcast_data_through_sfu(state, &react_msg.encode_to_vec())
     }
 
+    fn send_poll_action(
+        state: &mut State,
+        action: protobuf::group_call::device_to_sfu::poll_action::Action,
+    ) {
+        if !matches!(state.join_state, JoinState::Joined(_)) {
+            warn!("Can't send poll action before joining");
+            return;
+        }
+        let poll_action = state.polls.next_action(action);
+        info!(
+            "group_call::Client(inner)::send_poll_action(client_id: {}, seqnum: {:?})",
+            state.client_id, poll_action.seqnum
+        );
+        let msg = DeviceToSfu {
+            poll_action: Some(poll_action),
+            ..Default::default()
+        };
+        if let Err(e) = Self::reliable_send_to_sfu(state, msg) {
+            warn!("Failed to send poll action: {:?}", e);
+        }
+    }
+
     fn send_raise_hand(state: &mut State) {
         use protobuf::group_call::device_to_sfu::RaiseHand;
         let msg = DeviceToSfu {
@@ -3670,8 +5022,170 @@ impl Client {
         }
         .encode_to_vec();
 
-        if let Err(e) = Self::send_data_to_sfu(state, &msg) {
-            warn!("Failed to send raise hand: {:?}", e);
+        if let Err(e) = Self::send_data_to_sfu(state, &msg) {
+            warn!("Failed to send raise hand: {:?}", e);
+        }
+    }
+
+    fn send_handoff_message_to_self(
+        state: &mut State,
+        handoff: protobuf::group_call::device_to_device::Handoff,
+    ) {
+        let Some(self_uuid) = state.self_uuid.lock().expect("can read UUID").clone() else {
+            error!("self UUID unknown; cannot send handoff message");
+            return;
+        };
+        let call_message = protobuf::signaling::CallMessage {
+            group_call_message: Some(protobuf::group_call::DeviceToDevice {
+                group_id: Some(state.group_id.clone()),
+                handoff: Some(handoff),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        state.observer.send_signaling_message(
+            self_uuid,
+            call_message,
+            SignalingMessageUrgency::HandleImmediately,
+        );
+    }
+
+    fn request_handoff_state_if_needed(state: &mut State, now: Instant) {
+        use protobuf::group_call::device_to_device::{handoff::Request, Handoff};
+
+        let HandoffProgress::Receiving {
+            from_demux_id,
+            next_request_time,
+        } = state.handoff
+        else {
+            return;
+        };
+        let JoinState::Joined(local_demux_id) = state.join_state else {
+            return;
+        };
+        if state.connection_state != ConnectionState::Connected
+            || next_request_time.is_some_and(|next_request_time| now < next_request_time)
+        {
+            return;
+        }
+        if next_request_time.is_some()
+            && state
+                .remote_devices
+                .find_by_demux_id(from_demux_id)
+                .is_none()
+        {
+            warn!(
+                "Giving up on handoff; demux_id {} left without sending its state",
+                from_demux_id
+            );
+            state.handoff = HandoffProgress::None;
+            return;
+        }
+
+        info!(
+            "Requesting handoff state from demux_id {} for demux_id {}",
+            from_demux_id, local_demux_id
+        );
+        Self::send_handoff_message_to_self(
+            state,
+            Handoff {
+                from_demux_id: Some(from_demux_id),
+                to_demux_id: Some(local_demux_id),
+                request: Some(Request {}),
+                ..Default::default()
+            },
+        );
+        state.handoff = HandoffProgress::Receiving {
+            from_demux_id,
+            next_request_time: Some(now + HANDOFF_REQUEST_INTERVAL),
+        };
+    }
+
+    fn handle_handoff_received(
+        state: &mut State,
+        sender_user_id: UserId,
+        handoff: protobuf::group_call::device_to_device::Handoff,
+    ) {
+        use protobuf::group_call::device_to_device::Handoff;
+
+        let self_uuid = state.self_uuid.lock().expect("can read UUID").clone();
+        if self_uuid.as_ref() != Some(&sender_user_id) {
+            warn!("Ignoring handoff message from another user");
+            return;
+        }
+        let local_demux_id = match state.join_state {
+            JoinState::Pending(local_demux_id) | JoinState::Joined(local_demux_id) => {
+                local_demux_id
+            }
+            JoinState::NotJoined(_) | JoinState::Joining => {
+                warn!("Ignoring handoff message when not joined");
+                return;
+            }
+        };
+
+        match handoff {
+            Handoff {
+                from_demux_id: Some(from_demux_id),
+                to_demux_id: Some(to_demux_id),
+                request: Some(_),
+                ..
+            } if from_demux_id == local_demux_id => {
+                info!("Handing off the call to demux_id {}", to_demux_id);
+                let handoff_state = HandoffState {
+                    audio_muted: state.outgoing_heartbeat_state.audio_muted,
+                    video_muted: state.outgoing_heartbeat_state.video_muted,
+                    hand_raised: state.raise_hand_state.raise,
+                    video_requests: state.video_requests.clone().unwrap_or_default(),
+                    active_speaker_height: state.active_speaker_height,
+                };
+                Self::send_handoff_message_to_self(
+                    state,
+                    Handoff {
+                        from_demux_id: Some(from_demux_id),
+                        to_demux_id: Some(to_demux_id),
+                        state: Some(handoff_state.into()),
+                        ..Default::default()
+                    },
+                );
+                state
+                    .observer
+                    .handle_handed_off(state.client_id, to_demux_id);
+                state.handoff = HandoffProgress::Sending { to_demux_id };
+                Self::leave_inner(state);
+            }
+            Handoff {
+                from_demux_id: Some(from_demux_id),
+                to_demux_id: Some(to_demux_id),
+                state: Some(handoff_state),
+                ..
+            } if to_demux_id == local_demux_id
+                && matches!(
+                    state.handoff,
+                    HandoffProgress::Receiving { from_demux_id: expected, .. } if expected == from_demux_id
+                ) =>
+            {
+                info!("Received handoff state from demux_id {}", from_demux_id);
+                state.handoff = HandoffProgress::None;
+                let handoff_state = HandoffState::from(handoff_state);
+                if handoff_state.hand_raised != state.raise_hand_state.raise {
+                    state.raise_hand_state.seqnum += 1;
+                    state.raise_hand_state.raise = handoff_state.hand_raised;
+                    state.raise_hand_state.outstanding = true;
+                    Self::send_raise_hand(state);
+                }
+                // Requests made by the app on this device take precedence.
+                if state.video_requests.is_none() && !handoff_state.video_requests.is_empty() {
+                    state.video_requests = Some(handoff_state.video_requests.clone());
+                    state.active_speaker_height = handoff_state.active_speaker_height;
+                    Self::send_video_requests_to_sfu(state);
+                }
+                state
+                    .observer
+                    .handle_handoff_state_received(state.client_id, handoff_state);
+            }
+            _ => {
+                warn!("Ignoring unexpected handoff message");
+            }
         }
     }
 
@@ -3700,8 +5214,15 @@ impl Client {
             state.client_id, local_demux_id,
         );
 
+        let replaced_by_demux_id = match state.handoff {
+            HandoffProgress::Sending { to_demux_id } => Some(to_demux_id),
+            HandoffProgress::None | HandoffProgress::Receiving { .. } => None,
+        };
         let msg = DeviceToDevice {
-            leaving: Some(Leaving::default()),
+            leaving: Some(Leaving {
+                demux_id: None,
+                replaced_by_demux_id,
+            }),
             ..DeviceToDevice::default()
         };
         if Self::broadcast_data_through_sfu(state, &msg.encode_to_vec()).is_err() {
@@ -3715,6 +5236,7 @@ impl Client {
                 group_id: Some(state.group_id.clone()),
                 leaving: Some(Leaving {
                     demux_id: Some(local_demux_id),
+                    replaced_by_demux_id,
                 }),
                 ..Default::default()
             }),
@@ -3914,14 +5436,23 @@ impl Client {
                         if let Some(heartbeat) = msg.heartbeat {
                             self.handle_heartbeat_received(demux_id, header.timestamp, heartbeat);
                         }
-                        if let Some(_leaving) = msg.leaving {
+                        if let Some(leaving) = msg.leaving {
                             self.actor.send(move |state| {
-                                Self::handle_leaving_received(state, demux_id);
+                                Self::handle_leaving_received(
+                                    state,
+                                    demux_id,
+                                    leaving.replaced_by_demux_id,
+                                );
                             });
                         }
                         if let Some(reaction) = msg.reaction {
                             self.handle_reaction(demux_id, reaction);
                         }
+                        if let Some(chat_fragment) = msg.chat_fragment {
+                            self.actor.send(move |state| {
+                                Self::handle_chat_fragment_received(state, demux_id, chat_fragment);
+                            });
+                        }
                     } else {
                         warn!(
                             "Ignoring received RTP data because decoding failed. demux_id: {}",
@@ -3993,7 +5524,8 @@ impl Client {
 
     fn handle_sfu_to_device_inner(actor: &Actor<State>, header: rtp::Header, msg: SfuToDevice) {
         use protobuf::group_call::sfu_to_device::{
-            CurrentDevices, DeviceJoinedOrLeft, RaisedHands, Removed, Speaker,
+            AdminMuteRequest, CurrentDevices, DeviceJoinedOrLeft, LobbyMessage, RaisedHands,
+            Removed, Speaker, Spotlight, VideoRequest,
         };
         // TODO: Use video_request to throttle down how much we send when it's not needed.
         let SfuToDevice {
@@ -4001,11 +5533,17 @@ impl Client {
             device_joined_or_left,
             current_devices,
             stats,
-            video_request: _,
+            video_request,
             removed,
             raised_hands,
             mrp_header: _,
             content,
+            spotlight,
+            muted_by_admin,
+            unmute_requested,
+            breakout_rooms,
+            lobby_message,
+            polls,
         } = msg;
 
         if let Some(content) = content {
@@ -4056,6 +5594,12 @@ impl Client {
         {
             Self::handle_forwarding_video_received(actor, demux_ids_with_video, allocated_heights);
         }
+        if let Some(VideoRequest {
+            height: Some(height),
+        }) = video_request
+        {
+            Self::handle_video_request_received(actor, height.try_into().unwrap_or(u16::MAX));
+        }
         if let Some(stats) = stats {
             info!(
                 "ringrtc_stats!,sfu,recv,{},{},{}",
@@ -4071,12 +5615,143 @@ impl Client {
             demux_ids,
             seqnums: _,
             target_seqnum: Some(target_seqnum),
+            current_speaker_demux_id,
         }) = raised_hands
         {
-            Self::handle_raised_hands(actor, demux_ids, target_seqnum);
+            Self::handle_raised_hands(actor, demux_ids, target_seqnum, current_speaker_demux_id);
+        }
+        if let Some(Spotlight { demux_ids }) = spotlight {
+            Self::handle_spotlight_received(actor, demux_ids);
+        }
+        if let Some(AdminMuteRequest {
+            admin_demux_id: Some(admin_demux_id),
+        }) = muted_by_admin
+        {
+            Self::handle_muted_by_admin_received(actor, admin_demux_id);
+        }
+        if let Some(AdminMuteRequest {
+            admin_demux_id: Some(admin_demux_id),
+        }) = unmute_requested
+        {
+            actor.send(move |state| {
+                info!("{ADMIN_LOG_TAG}: {admin_demux_id} asked us to unmute");
+                state
+                    .observer
+                    .handle_unmute_requested(state.client_id, admin_demux_id);
+            });
+        }
+        if let Some(breakout_rooms) = breakout_rooms {
+            Self::handle_breakout_rooms_received(actor, BreakoutRooms::from_proto(breakout_rooms));
+        }
+        if let Some(LobbyMessage { text }) = lobby_message {
+            Self::handle_lobby_message_received(actor, text.filter(|text| !text.is_empty()));
+        }
+        if let Some(polls) = polls {
+            Self::handle_polls_received(actor, polls);
+        }
+    }
+
+    fn handle_polls_received(
+        actor: &Actor<State>,
+        polls: protobuf::group_call::sfu_to_device::Polls,
+    ) {
+        actor.send(move |state| {
+            if state.polls.receive(polls) {
+                info!(
+                    "group_call::Client(inner)::handle_polls_received(client_id: {} polls: {} outstanding: {})",
+                    state.client_id,
+                    state.polls.polls().len(),
+                    state.polls.has_outstanding_actions()
+                );
+                state
+                    .observer
+                    .handle_polls_changed(state.client_id, state.polls.polls());
+            }
+        });
+    }
+
+    fn handle_lobby_message_received(actor: &Actor<State>, message: Option<String>) {
+        actor.send(move |state| {
+            if !matches!(state.join_state, JoinState::Pending(_)) {
+                debug!("Ignoring lobby message while not pending");
+                return;
+            }
+            if message.as_ref().map_or(0, String::len) > LOBBY_MESSAGE_MAX_SIZE {
+                warn!("Ignoring lobby message that exceeds the allowed size");
+                return;
+            }
+            state.lobby_message = message;
+            Self::update_lobby(state);
+        });
+    }
+
+    fn update_lobby(state: &mut State) {
+        let JoinState::Pending(local_demux_id) = state.join_state else {
+            return;
+        };
+        let lobby = match &state.last_peek_info {
+            Some(peek_info) => {
+                LobbyInfo::new(peek_info, local_demux_id, state.lobby_message.clone())
+            }
+            None => LobbyInfo {
+                message: state.lobby_message.clone(),
+                ..Default::default()
+            },
+        };
+        if state.lobby.as_ref() != Some(&lobby) {
+            info!(
+                "group_call::Client(inner)::update_lobby(client_id: {}, participant_count: {}, position: {:?})",
+                state.client_id, lobby.participant_count, lobby.position
+            );
+            state.observer.handle_lobby_changed(state.client_id, &lobby);
+            state.lobby = Some(lobby);
         }
     }
 
+    fn handle_breakout_rooms_received(actor: &Actor<State>, breakout_rooms: Option<BreakoutRooms>) {
+        actor.send(move |state| {
+            if state.breakout_rooms != breakout_rooms {
+                info!(
+                    "group_call::Client(inner)::handle_breakout_rooms_received(client_id: {}, breakout_rooms: {:?})",
+                    state.client_id, breakout_rooms
+                );
+                state.breakout_rooms = breakout_rooms;
+                state
+                    .observer
+                    .handle_breakout_rooms_changed(state.client_id, state.breakout_rooms.clone());
+            }
+        });
+    }
+
+    fn handle_muted_by_admin_received(actor: &Actor<State>, admin_demux_id: DemuxId) {
+        actor.send(move |state| {
+            info!("{ADMIN_LOG_TAG}: muted by {admin_demux_id}");
+            // Don't rely on the app to stop sending audio; it will enable the track again when
+            // the user unmutes.
+            state.outgoing_audio_track.set_enabled(false);
+            Self::set_outgoing_audio_muted_inner(state, true);
+            state
+                .observer
+                .handle_muted_by_admin(state.client_id, admin_demux_id);
+        });
+    }
+
+    fn handle_spotlight_received(actor: &Actor<State>, spotlight: Vec<DemuxId>) {
+        actor.send(move |state| {
+            if state.spotlight != spotlight {
+                info!(
+                    "group_call::Client(inner)::handle_spotlight_received(client_id: {}, spotlight: {:?})",
+                    state.client_id, spotlight
+                );
+                state.spotlight = spotlight;
+                state
+                    .observer
+                    .handle_spotlight_changed(state.client_id, state.spotlight.clone());
+                Self::update_video_layout(state);
+            }
+        });
+    }
+
     fn handle_removed_received(actor: &Actor<State>) {
         actor.send(move |state| {
             if matches!(state.join_state, JoinState::Joined(_)) {
@@ -4174,11 +5849,41 @@ impl Client {
                     state.client_id,
                     &state.remote_devices,
                     RemoteDevicesChangedReason::ForwardedVideosChanged,
-                )
+                );
+                Self::update_forwarded_video_layers(state);
+            }
+        })
+    }
+
+    fn handle_video_request_received(actor: &Actor<State>, height: u16) {
+        actor.send(move |state| {
+            if state.requested_video_height != height {
+                debug!("SFU requested video height {}", height);
+                state.requested_video_height = height;
+                Self::update_forwarded_video_layers(state);
             }
         })
     }
 
+    fn update_forwarded_video_layers(state: &mut State) {
+        let layers = ForwardedVideoLayers::new(
+            &state.video_encoder_config,
+            state.requested_video_height,
+            &state.forwarding_videos,
+        );
+        if state.forwarded_video_layers != layers {
+            info!(
+                "Forwarded video layers changed: outgoing: {}, incoming: {:?}",
+                layers.outgoing_layer_count, layers.incoming_heights
+            );
+            state.forwarded_video_layers = layers;
+            state.observer.handle_forwarded_video_layers_changed(
+                state.client_id,
+                &state.forwarded_video_layers,
+            );
+        }
+    }
+
     fn handle_heartbeat_received(
         &self,
         demux_id: DemuxId,
@@ -4216,7 +5921,15 @@ impl Client {
         });
     }
 
-    fn handle_leaving_received(state: &mut State, demux_id: DemuxId) {
+    fn handle_leaving_received(
+        state: &mut State,
+        demux_id: DemuxId,
+        replaced_by_demux_id: Option<DemuxId>,
+    ) {
+        if let Some(replaced_by_demux_id) = replaced_by_demux_id {
+            Self::handle_handed_off_device(state, demux_id, replaced_by_demux_id);
+        }
+
         // It's likely we haven't received an update from the SFU about this demux_id leaving.
         debug!(
             "Request devices because we just received a leaving message from demux_id = {}",
@@ -4239,6 +5952,59 @@ impl Client {
         }
     }
 
+    // Lets the device that took over a call inherit the state of the device it replaced, so
+    // that the app sees one participant moving to a new demux ID instead of a leave and a join.
+    fn handle_handed_off_device(
+        state: &mut State,
+        demux_id: DemuxId,
+        replaced_by_demux_id: DemuxId,
+    ) {
+        let Some(old_device) = state.remote_devices.find_by_demux_id(demux_id).cloned() else {
+            return;
+        };
+        if old_device.leaving_received {
+            // Already handled when the leaving message arrived by another route.
+            return;
+        }
+        if matches!(
+            state.join_state,
+            JoinState::Pending(local_demux_id) | JoinState::Joined(local_demux_id)
+                if local_demux_id == replaced_by_demux_id
+        ) {
+            // We are the device that took over.
+            return;
+        }
+        info!(
+            "demux_id {} was handed off to demux_id {}",
+            demux_id, replaced_by_demux_id
+        );
+        match state
+            .remote_devices
+            .find_by_demux_id_mut(replaced_by_demux_id)
+        {
+            Some(new_device) if new_device.user_id == old_device.user_id => {
+                new_device.inherit_handed_off_state(&old_device);
+                state.observer.handle_remote_devices_changed(
+                    state.client_id,
+                    &state.remote_devices,
+                    RemoteDevicesChangedReason::HandedOff(replaced_by_demux_id),
+                );
+            }
+            Some(_) => {
+                warn!(
+                    "Ignoring handoff from demux_id {} to a device of another user",
+                    demux_id
+                );
+            }
+            None => {
+                // We'll apply it when the new device shows up.
+                state
+                    .handed_off_devices
+                    .insert(replaced_by_demux_id, (Instant::now(), old_device));
+            }
+        }
+    }
+
     fn handle_reaction(
         &self,
         demux_id: DemuxId,
@@ -4263,11 +6029,29 @@ impl Client {
         }
     }
 
-    fn handle_raised_hands(actor: &Actor<State>, raised_hands: Vec<DemuxId>, server_seqnum: u32) {
+    fn handle_raised_hands(
+        actor: &Actor<State>,
+        raised_hands: Vec<DemuxId>,
+        server_seqnum: u32,
+        current_speaker: Option<DemuxId>,
+    ) {
         actor.send(move |state| {
+            // An update from before the server saw our latest raise or lower is stale, so the
+            // raised hands below aren't taken from it, and neither is the speaker queue.
+            let accepted = server_seqnum >= state.raise_hand_state.seqnum;
+
             // The server has previously received a hand raise request from the client or admin
             if server_seqnum != 0 {
                 if server_seqnum >= state.raise_hand_state.seqnum {
+                    // An admin may have lowered our hand, which bumps the server seqnum past ours.
+                    if server_seqnum > state.raise_hand_state.seqnum
+                        && !state.raise_hand_state.outstanding
+                    {
+                        if let JoinState::Joined(local_demux_id) = state.join_state {
+                            state.raise_hand_state.raise = raised_hands.contains(&local_demux_id);
+                        }
+                    }
+
                     // Set the local raised hand seqnum to the latest from the server
                     state.raise_hand_state.seqnum = server_seqnum;
 
@@ -4321,6 +6105,24 @@ impl Client {
                         .handle_raised_hands(state.client_id, state.raised_hands.clone());
                 }
             }
+
+            if !accepted {
+                return;
+            }
+            let speaker_queue = SpeakerQueue {
+                raised_hands: state.raised_hands.clone(),
+                current_speaker,
+            };
+            if state.speaker_queue != speaker_queue {
+                info!(
+                    "group_call::Client(inner)::handle_raised_hands(client_id: {} current_speaker: {:?})",
+                    state.client_id, speaker_queue.current_speaker
+                );
+                state.speaker_queue = speaker_queue;
+                state
+                    .observer
+                    .handle_speaker_queue_changed(state.client_id, &state.speaker_queue);
+            }
         });
     }
 
@@ -4726,8 +6528,9 @@ mod tests {
 
     use super::*;
     use crate::{
-        lite::sfu::PeekDeviceInfo, protobuf::group_call::MrpHeader,
-        webrtc::sim::media::FAKE_AUDIO_TRACK,
+        lite::sfu::PeekDeviceInfo,
+        protobuf::group_call::MrpHeader,
+        webrtc::{media::VideoCodec, sim::media::FAKE_AUDIO_TRACK},
     };
 
     #[derive(Clone)]
@@ -4909,6 +6712,18 @@ mod tests {
         send_rates: Arc<CallMutex<Option<SendRates>>>,
         ended: Waitable<EndReason>,
         reactions: Arc<CallMutex<Vec<Reaction>>>,
+        handoff_state: Waitable<HandoffState>,
+        handed_off: Waitable<DemuxId>,
+        video_layout: Arc<CallMutex<Option<VideoLayout>>>,
+        spotlight: Arc<CallMutex<Vec<DemuxId>>>,
+        muted_by_admin: Waitable<DemuxId>,
+        breakout_rooms: Arc<CallMutex<Option<BreakoutRooms>>>,
+        lobby: Arc<CallMutex<Option<LobbyInfo>>>,
+        chat_messages: Arc<CallMutex<Vec<ChatMessage>>>,
+        polls: Waitable<Vec<Poll>>,
+        speaker_queue: Waitable<SpeakerQueue>,
+        forwarded_video_layers: Waitable<ForwardedVideoLayers>,
+        ring_recipients: Arc<CallMutex<HashMap<UserId, RingRecipientStatus>>>,
 
         request_membership_proof_invocation_count: Arc<AtomicU64>,
         request_group_members_invocation_count: Arc<AtomicU64>,
@@ -4952,6 +6767,21 @@ mod tests {
                 send_rates: Arc::new(CallMutex::new(None, "FakeObserver send rates")),
                 ended: Waitable::default(),
                 reactions: Arc::new(CallMutex::new(Default::default(), "FakeObserver reactions")),
+                handoff_state: Waitable::default(),
+                handed_off: Waitable::default(),
+                video_layout: Arc::new(CallMutex::new(None, "FakeObserver video layout")),
+                spotlight: Arc::new(CallMutex::new(Vec::new(), "FakeObserver spotlight")),
+                muted_by_admin: Waitable::default(),
+                breakout_rooms: Arc::new(CallMutex::new(None, "FakeObserver breakout rooms")),
+                lobby: Arc::new(CallMutex::new(None, "FakeObserver lobby")),
+                chat_messages: Arc::new(CallMutex::new(Vec::new(), "FakeObserver chat messages")),
+                polls: Waitable::default(),
+                speaker_queue: Waitable::default(),
+                forwarded_video_layers: Waitable::default(),
+                ring_recipients: Arc::new(CallMutex::new(
+                    HashMap::new(),
+                    "FakeObserver ring recipients",
+                )),
                 request_membership_proof_invocation_count: Default::default(),
                 request_group_members_invocation_count: Default::default(),
                 handle_remote_devices_changed_invocation_count: Default::default(),
@@ -5164,6 +6994,18 @@ mod tests {
 
         fn handle_raised_hands(&self, _client_id: ClientId, _raised_hands: Vec<DemuxId>) {}
 
+        fn handle_speaker_queue_changed(&self, _client_id: ClientId, queue: &SpeakerQueue) {
+            self.speaker_queue.set(queue.clone());
+        }
+
+        fn handle_forwarded_video_layers_changed(
+            &self,
+            _client_id: ClientId,
+            layers: &ForwardedVideoLayers,
+        ) {
+            self.forwarded_video_layers.set(layers.clone());
+        }
+
         fn handle_rtc_stats_report(&self, _report_json: String) {}
 
         fn handle_peek_changed(
@@ -5308,6 +7150,67 @@ mod tests {
         ) {
         }
 
+        fn handle_handoff_state_received(&self, _client_id: ClientId, state: HandoffState) {
+            self.handoff_state.set(state);
+        }
+
+        fn handle_handed_off(&self, _client_id: ClientId, new_demux_id: DemuxId) {
+            self.handed_off.set(new_demux_id);
+        }
+
+        fn handle_muted_by_admin(&self, _client_id: ClientId, admin_demux_id: DemuxId) {
+            self.muted_by_admin.set(admin_demux_id);
+        }
+
+        fn handle_chat_message(&self, _client_id: ClientId, message: &ChatMessage) {
+            self.chat_messages
+                .lock()
+                .expect("Lock chat messages to add one")
+                .push(message.clone());
+        }
+
+        fn handle_polls_changed(&self, _client_id: ClientId, polls: &[Poll]) {
+            self.polls.set(polls.to_vec());
+        }
+
+        fn handle_lobby_changed(&self, _client_id: ClientId, lobby: &LobbyInfo) {
+            *self.lobby.lock().expect("Lock lobby to set it") = Some(lobby.clone());
+        }
+
+        fn handle_breakout_rooms_changed(
+            &self,
+            _client_id: ClientId,
+            breakout_rooms: Option<BreakoutRooms>,
+        ) {
+            *self
+                .breakout_rooms
+                .lock()
+                .expect("Lock breakout rooms to set it") = breakout_rooms;
+        }
+
+        fn handle_spotlight_changed(&self, _client_id: ClientId, spotlight: Vec<DemuxId>) {
+            *self.spotlight.lock().expect("Lock spotlight to set it") = spotlight;
+        }
+
+        fn handle_video_layout_changed(&self, _client_id: ClientId, layout: &VideoLayout) {
+            *self
+                .video_layout
+                .lock()
+                .expect("Lock video_layout to set it") = Some(layout.clone());
+        }
+
+        fn handle_ring_recipients_changed(
+            &self,
+            _client_id: ClientId,
+            _ring_id: RingId,
+            recipients: &HashMap<UserId, RingRecipientStatus>,
+        ) {
+            *self
+                .ring_recipients
+                .lock()
+                .expect("Lock ring_recipients to set it") = recipients.clone();
+        }
+
         fn handle_ended(&self, _client_id: ClientId, reason: EndReason) {
             self.ended.set(reason);
         }
@@ -6140,6 +8043,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handoff() {
+        let phone = TestClient::new(vec![1], 1);
+        phone.connect_join_and_wait_until_joined();
+        let other = TestClient::new(vec![2], 2);
+        other.connect_join_and_wait_until_joined();
+        set_group_and_wait_until_applied(&[&phone, &other]);
+
+        phone.client.set_outgoing_audio_muted(true);
+        phone.client.raise_hand(true);
+        phone.wait_for_client_to_process();
+        let phone_added_time = other.observer.remote_devices()[0].added_time;
+
+        std::thread::sleep(Duration::from_millis(10));
+        let desktop = TestClient::new(vec![1], 3);
+        desktop.client.connect();
+        desktop.client.join_by_handoff(phone.demux_id);
+        desktop
+            .client
+            .set_peek_result(Ok(desktop.default_peek_info.clone()));
+        assert!(desktop.observer.joined.wait(Duration::from_secs(5)));
+        set_group_and_wait_until_applied(&[&phone, &other, &desktop]);
+
+        let desktop_device = other
+            .observer
+            .remote_devices()
+            .into_iter()
+            .find(|device| device.demux_id == desktop.demux_id)
+            .expect("desktop is a remote device");
+        assert!(desktop_device.added_time > phone_added_time);
+
+        // The request for the phone's state is only sent once connected.
+        desktop.client.actor.send(|state| {
+            Client::set_connection_state_and_notify_observer(state, ConnectionState::Connected);
+        });
+
+        assert_eq!(
+            Some(desktop.demux_id),
+            phone.observer.handed_off.wait(Duration::from_secs(5))
+        );
+        let handoff_state = desktop
+            .observer
+            .handoff_state
+            .wait(Duration::from_secs(5))
+            .expect("handoff state received");
+        assert_eq!(Some(true), handoff_state.audio_muted);
+        assert!(handoff_state.hand_raised);
+
+        phone.wait_for_client_to_process();
+        other.wait_for_client_to_process();
+        desktop.wait_for_client_to_process();
+
+        let (sender, receiver) = mpsc::channel();
+        phone.client.actor.send(move |state| {
+            sender.send(state.join_state).unwrap();
+        });
+        assert_eq!(
+            JoinState::NotJoined(None),
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap()
+        );
+        let (sender, receiver) = mpsc::channel();
+        desktop.client.actor.send(move |state| {
+            sender.send(state.raise_hand_state.raise).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_secs(1)).unwrap());
+
+        let desktop_device = other
+            .observer
+            .remote_devices()
+            .into_iter()
+            .find(|device| device.demux_id == desktop.demux_id)
+            .expect("desktop is a remote device");
+        assert_eq!(phone_added_time, desktop_device.added_time);
+
+        phone.disconnect_and_wait_until_ended();
+        other.disconnect_and_wait_until_ended();
+        desktop.disconnect_and_wait_until_ended();
+    }
+
     fn hash_set<T: std::hash::Hash + Eq + Clone>(vals: impl IntoIterator<Item = T>) -> HashSet<T> {
         vals.into_iter().collect()
     }
@@ -6154,18 +8136,93 @@ mod tests {
 
         set_group_and_wait_until_applied(&[&client1, &client2]);
 
-        let value = "hello".to_string();
+        let value = "hello".to_string();
+
+        client1.client.react(value.clone());
+        assert!(client2
+            .observer
+            .reactions_called
+            .wait(Duration::from_secs(5)));
+        assert_eq!(1, client2.observer.handle_reactions_invocation_count());
+        assert_eq!(1, client2.observer.reactions_count());
+        assert_eq!(1, client2.observer.reactions().len());
+        assert_eq!(value, client2.observer.reactions()[0].value.to_string());
+        assert_eq!(1, client2.observer.reactions()[0].demux_id)
+    }
+
+    #[test]
+    fn chat_messages() {
+        let client1 = TestClient::new(vec![1], 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+
+        // Long enough to need several packets.
+        let url = format!("https://example.com/{}", "a".repeat(3000));
+        client1.client.send_chat_message(url.clone(), None);
+        let first_id = ChatMessageId {
+            demux_id: 1,
+            seqnum: 1,
+        };
+        client1
+            .client
+            .send_chat_message("that's the link".to_string(), Some(first_id));
+        client1.wait_for_client_to_process();
+        client2.wait_for_client_to_process();
+
+        let expected = vec![
+            ChatMessage {
+                id: first_id,
+                text: url,
+                reply_to: None,
+            },
+            ChatMessage {
+                id: ChatMessageId {
+                    demux_id: 1,
+                    seqnum: 2,
+                },
+                text: "that's the link".to_string(),
+                reply_to: Some(first_id),
+            },
+        ];
+        assert_eq!(
+            expected,
+            *client2
+                .observer
+                .chat_messages
+                .lock()
+                .expect("finished processing")
+        );
+        // The sender sees its own messages too.
+        assert_eq!(
+            expected,
+            *client1
+                .observer
+                .chat_messages
+                .lock()
+                .expect("finished processing")
+        );
+
+        // Messages that are too long aren't sent.
+        client1
+            .client
+            .send_chat_message("a".repeat(CHAT_MESSAGE_MAX_SIZE + 1), None);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            2,
+            client1
+                .observer
+                .chat_messages
+                .lock()
+                .expect("finished processing")
+                .len()
+        );
 
-        client1.client.react(value.clone());
-        assert!(client2
-            .observer
-            .reactions_called
-            .wait(Duration::from_secs(5)));
-        assert_eq!(1, client2.observer.handle_reactions_invocation_count());
-        assert_eq!(1, client2.observer.reactions_count());
-        assert_eq!(1, client2.observer.reactions().len());
-        assert_eq!(value, client2.observer.reactions()[0].value.to_string());
-        assert_eq!(1, client2.observer.reactions()[0].demux_id)
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
     }
 
     #[test]
@@ -6191,6 +8248,7 @@ mod tests {
             era_id: None,
             max_devices: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         };
         client.client.set_peek_result(Ok(peek_info));
         client.wait_for_client_to_process();
@@ -6223,6 +8281,7 @@ mod tests {
             era_id: None,
             max_devices: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
 
         assert!(client
@@ -6275,6 +8334,7 @@ mod tests {
                 devices: vec![],
                 max_devices: None,
                 call_link_state: None,
+                admin_demux_ids: vec![],
             },
             &HashSet::default(),
         );
@@ -6290,6 +8350,7 @@ mod tests {
                 devices: vec![],
                 max_devices: None,
                 call_link_state: None,
+                admin_demux_ids: vec![],
             },
             &([joiner1.user_id.clone(), joiner2.user_id.clone()]
                 .iter()
@@ -6458,6 +8519,50 @@ mod tests {
         client.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn viewport() {
+        let client1 = TestClient::new(vec![1], 1);
+        let client2 = TestClient::new(vec![2], 2);
+        let client3 = TestClient::new(vec![3], 3);
+        let client4 = TestClient::new(vec![4], 4);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3, &client4]);
+
+        let tile = TileSize {
+            width: 320,
+            height: 180,
+        };
+        client1.client.set_viewport(Some(Viewport {
+            mode: ViewportMode::Grid,
+            tiles: vec![tile; 2],
+            pinned: vec![4],
+        }));
+        client1.wait_for_client_to_process();
+        let layout = || {
+            client1
+                .observer
+                .video_layout
+                .lock()
+                .expect("finished processing")
+                .clone()
+                .expect("layout reported")
+        };
+        assert_eq!(vec![Some(4), Some(2)], layout().tiles);
+        let mut requested_heights: Vec<_> = layout()
+            .video_requests
+            .iter()
+            .map(|request| (request.demux_id, request.height))
+            .collect();
+        requested_heights.sort();
+        assert_eq!(vec![(2, 180), (3, 0), (4, 180)], requested_heights);
+
+        // The layout follows the remote devices.
+        client1.set_remotes_and_wait_until_applied(&[&client3, &client4]);
+        assert_eq!(vec![Some(4), Some(3)], layout().tiles);
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     #[ignore]
     fn request_video() {
@@ -6680,44 +8785,491 @@ mod tests {
         let (sender, receiver) = mpsc::channel();
         client1.sfu_rtp_packet_sender = Some(sender);
         client1.connect_join_and_wait_until_joined();
-        client1.set_remotes_and_wait_until_applied(&[]);
-        client1.client.remove_client(32);
+        client1.set_remotes_and_wait_until_applied(&[]);
+        client1.client.remove_client(32);
+
+        let (header, payload) = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("Get RTP packet to SFU");
+        assert_eq!(1, header.ssrc);
+        assert_eq!(
+            DeviceToSfu {
+                admin_action: Some(AdminAction::Remove(GenericAdminAction {
+                    target_demux_id: Some(32)
+                })),
+                mrp_header: Some(MrpHeader {
+                    seqnum: Some(1),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            DeviceToSfu::decode(&payload[..]).unwrap()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn device_to_sfu_block() {
+        use protobuf::group_call::{
+            device_to_sfu::{AdminAction, GenericAdminAction},
+            DeviceToSfu,
+        };
+
+        let mut client1 = TestClient::new(vec![1], 1);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[]);
+        client1.client.block_client(32);
+
+        let (header, payload) = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("Get RTP packet to SFU");
+        assert_eq!(1, header.ssrc);
+        assert_eq!(
+            DeviceToSfu {
+                admin_action: Some(AdminAction::Block(GenericAdminAction {
+                    target_demux_id: Some(32)
+                })),
+                mrp_header: Some(MrpHeader {
+                    seqnum: Some(1),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            DeviceToSfu::decode(&payload[..]).unwrap()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn mute_client_by_admin() {
+        use protobuf::group_call::{
+            device_to_sfu::{AdminAction, GenericAdminAction},
+            DeviceToSfu,
+        };
+
+        let mut client1 = TestClient::new(vec![1], 1);
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+
+        let client2 = TestClient::new(vec![2], 2);
+        client2.connect_join_and_wait_until_joined();
+
+        set_group_and_wait_until_applied(&[&client1, &client2]);
+        while receiver.try_recv().is_ok() {}
+
+        client1.client.mute_client(client2.demux_id);
+        client1.client.ask_client_to_unmute(client2.demux_id);
+        let mut admin_actions = Vec::new();
+        while admin_actions.len() < 2 {
+            let (header, payload) = receiver
+                .recv_timeout(Duration::from_secs(1))
+                .expect("Get RTP packet to SFU");
+            if header.ssrc != RTP_DATA_TO_SFU_SSRC {
+                continue;
+            }
+            if let Some(admin_action) = DeviceToSfu::decode(&payload[..])
+                .ok()
+                .and_then(|msg| msg.admin_action)
+            {
+                admin_actions.push(admin_action);
+            }
+        }
+        let target = GenericAdminAction {
+            target_demux_id: Some(client2.demux_id),
+        };
+        assert_eq!(
+            vec![
+                AdminAction::Mute(target.clone()),
+                AdminAction::AskToUnmute(target)
+            ],
+            admin_actions
+        );
+
+        // The SFU relays the mute to the target, which mutes itself and tells everyone else.
+        Client::handle_muted_by_admin_received(&client2.client.actor, client1.demux_id);
+        client2.wait_for_client_to_process();
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            Some(client1.demux_id),
+            client2.observer.muted_by_admin.wait(Duration::from_secs(1))
+        );
+        let remote_devices1 = client1.observer.remote_devices();
+        assert_eq!(1, remote_devices1.len());
+        assert_eq!(Some(true), remote_devices1[0].heartbeat_state.audio_muted);
+
+        client1.disconnect_and_wait_until_ended();
+        client2.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn speaker_queue() {
+        use protobuf::group_call::{
+            device_to_sfu::{AdminAction, CallNextSpeaker, GenericAdminAction},
+            DeviceToSfu,
+        };
+
+        let mut client1 = TestClient::new(vec![1], 1);
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+
+        client1.client.lower_hand(2);
+        client1.client.call_next_speaker(true);
+        let mut admin_actions = Vec::new();
+        while admin_actions.len() < 2 {
+            let (header, payload) = receiver
+                .recv_timeout(Duration::from_secs(1))
+                .expect("Get RTP packet to SFU");
+            if header.ssrc != RTP_DATA_TO_SFU_SSRC {
+                continue;
+            }
+            if let Some(admin_action) = DeviceToSfu::decode(&payload[..])
+                .ok()
+                .and_then(|msg| msg.admin_action)
+            {
+                admin_actions.push(admin_action);
+            }
+        }
+        assert_eq!(
+            vec![
+                AdminAction::LowerHand(GenericAdminAction {
+                    target_demux_id: Some(2)
+                }),
+                AdminAction::CallNextSpeaker(CallNextSpeaker {
+                    ask_to_unmute: Some(true)
+                }),
+            ],
+            admin_actions
+        );
+
+        let local_hand_raised = || {
+            let (sender, receiver) = mpsc::channel();
+            client1.client.actor.send(move |state| {
+                sender.send(state.raise_hand_state.raise).unwrap();
+            });
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap()
+        };
+
+        client1.client.raise_hand(true);
+        Client::handle_raised_hands(&client1.client.actor, vec![1, 2], 1, None);
+        assert_eq!(
+            Some(SpeakerQueue {
+                raised_hands: vec![1, 2],
+                current_speaker: None,
+            }),
+            client1.observer.speaker_queue.wait(Duration::from_secs(1))
+        );
+        assert!(local_hand_raised());
+
+        // An admin lowers the local device's hand, which bumps its seqnum.
+        Client::handle_raised_hands(&client1.client.actor, vec![2], 2, None);
+        let queue = client1
+            .observer
+            .speaker_queue
+            .wait(Duration::from_secs(1))
+            .expect("queue changed");
+        assert_eq!(None, queue.position(1));
+        assert_eq!(Some(2), queue.next_speaker());
+        assert!(!local_hand_raised());
+
+        // An admin calls on the next speaker.
+        Client::handle_raised_hands(&client1.client.actor, vec![], 2, Some(2));
+        assert_eq!(
+            Some(SpeakerQueue {
+                raised_hands: vec![],
+                current_speaker: Some(2),
+            }),
+            client1.observer.speaker_queue.wait(Duration::from_secs(1))
+        );
+
+        // Nothing changed.
+        Client::handle_raised_hands(&client1.client.actor, vec![], 2, Some(2));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            None,
+            client1
+                .observer
+                .speaker_queue
+                .wait(Duration::from_millis(100))
+        );
+
+        // An update from before the server saw the local device's latest raise is stale.
+        client1.client.raise_hand(true);
+        Client::handle_raised_hands(&client1.client.actor, vec![], 2, Some(1));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            None,
+            client1
+                .observer
+                .speaker_queue
+                .wait(Duration::from_millis(100))
+        );
+
+        // Leaving resets the queue.
+        client1.client.leave();
+        assert_eq!(
+            Some(SpeakerQueue::default()),
+            client1.observer.speaker_queue.wait(Duration::from_secs(1))
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn polls() {
+        use protobuf::group_call::{
+            device_to_sfu::{poll_action, PollAction},
+            sfu_to_device::{polls::Poll as ProtoPoll, Polls as ProtoPolls},
+            DeviceToSfu,
+        };
+
+        let mut client1 = TestClient::new(vec![1], 1);
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+
+        let next_poll_action = || loop {
+            let (header, payload) = receiver
+                .recv_timeout(Duration::from_secs(1))
+                .expect("Get RTP packet to SFU");
+            if header.ssrc != RTP_DATA_TO_SFU_SSRC {
+                continue;
+            }
+            if let Some(poll_action) = DeviceToSfu::decode(&payload[..])
+                .ok()
+                .and_then(|msg| msg.poll_action)
+            {
+                return poll_action;
+            }
+        };
+
+        let options = vec!["Tacos".to_string(), "Pizza".to_string()];
+        // Invalid polls aren't sent.
+        client1
+            .client
+            .create_poll("Lunch?".to_string(), options[..1].to_vec());
+        client1
+            .client
+            .create_poll("Lunch?".to_string(), options.clone());
+        assert_eq!(
+            PollAction {
+                seqnum: Some(1),
+                action: Some(poll_action::Action::Create(poll_action::Create {
+                    question: Some("Lunch?".to_string()),
+                    options: options.clone(),
+                })),
+            },
+            next_poll_action()
+        );
+
+        // The SFU assigns the poll an ID and sends it to everyone.
+        let proto_poll = |vote_counts: Vec<u32>, own_vote: Option<u32>, closed: bool| ProtoPoll {
+            poll_id: Some(1),
+            creator_demux_id: Some(client1.demux_id),
+            question: Some("Lunch?".to_string()),
+            options: options.clone(),
+            vote_counts,
+            closed: Some(closed),
+            own_vote,
+        };
+        Client::handle_polls_received(
+            &client1.client.actor,
+            ProtoPolls {
+                polls: vec![proto_poll(vec![0, 0], None, false)],
+                seqnum: Some(1),
+                target_seqnum: Some(1),
+            },
+        );
+        let polls = client1
+            .observer
+            .polls
+            .wait(Duration::from_secs(1))
+            .expect("polls changed");
+        assert_eq!(1, polls.len());
+        assert_eq!(1, polls[0].id);
+        assert_eq!(client1.demux_id, polls[0].creator);
+        assert_eq!(0, polls[0].total_votes());
+
+        // Votes for unknown polls or options aren't sent.
+        client1.client.vote_in_poll(2, Some(0));
+        client1.client.vote_in_poll(1, Some(2));
+        client1.client.vote_in_poll(1, Some(1));
+        assert_eq!(
+            PollAction {
+                seqnum: Some(2),
+                action: Some(poll_action::Action::Vote(poll_action::Vote {
+                    poll_id: Some(1),
+                    option_index: Some(1),
+                })),
+            },
+            next_poll_action()
+        );
+
+        Client::handle_polls_received(
+            &client1.client.actor,
+            ProtoPolls {
+                polls: vec![proto_poll(vec![1, 1], Some(1), false)],
+                seqnum: Some(3),
+                target_seqnum: Some(2),
+            },
+        );
+        let polls = client1
+            .observer
+            .polls
+            .wait(Duration::from_secs(1))
+            .expect("polls changed");
+        assert_eq!(Some(1), polls[0].own_vote);
+        assert_eq!(2, polls[0].total_votes());
+
+        // Updates older than what we've seen are ignored.
+        Client::handle_polls_received(
+            &client1.client.actor,
+            ProtoPolls {
+                polls: vec![proto_poll(vec![0, 1], Some(1), false)],
+                seqnum: Some(2),
+                target_seqnum: Some(2),
+            },
+        );
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            None,
+            client1.observer.polls.wait(Duration::from_millis(100))
+        );
+
+        client1.client.close_poll(1);
+        assert_eq!(
+            PollAction {
+                seqnum: Some(3),
+                action: Some(poll_action::Action::Close(poll_action::Close {
+                    poll_id: Some(1),
+                })),
+            },
+            next_poll_action()
+        );
+        Client::handle_polls_received(
+            &client1.client.actor,
+            ProtoPolls {
+                polls: vec![proto_poll(vec![1, 1], Some(1), true)],
+                seqnum: Some(4),
+                target_seqnum: Some(3),
+            },
+        );
+        let polls = client1
+            .observer
+            .polls
+            .wait(Duration::from_secs(1))
+            .expect("polls changed");
+        assert!(polls[0].closed);
+
+        // Closed polls don't take votes.
+        client1.client.vote_in_poll(1, Some(0));
+        client1.wait_for_client_to_process();
+        assert!(receiver
+            .try_iter()
+            .filter(|(header, _)| header.ssrc == RTP_DATA_TO_SFU_SSRC)
+            .all(|(_, payload)| DeviceToSfu::decode(&payload[..])
+                .expect("valid DeviceToSfu")
+                .poll_action
+                .is_none()));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn breakout_rooms() {
+        use protobuf::group_call::{device_to_sfu::AdminAction, DeviceToSfu};
+
+        let mut client1 = TestClient::new(vec![1], 1);
+        let client2 = TestClient::new(vec![2], 2);
+        let client3 = TestClient::new(vec![3], 3);
+
+        let (sender, receiver) = mpsc::channel();
+        client1.sfu_rtp_packet_sender = Some(sender);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+        client1.client.start_breakout_rooms(
+            2,
+            HashMap::from([(1, 0), (3, 0)]),
+            HashMap::from([(vec![2], 1), (vec![3], 1)]),
+            None,
+        );
 
         let (header, payload) = receiver
             .recv_timeout(Duration::from_secs(1))
             .expect("Get RTP packet to SFU");
         assert_eq!(1, header.ssrc);
+        let Some(AdminAction::BreakoutRooms(sent)) =
+            DeviceToSfu::decode(&payload[..]).unwrap().admin_action
+        else {
+            panic!("expected breakout rooms");
+        };
+        // Device assignments take precedence over user assignments.
+        let expected = BreakoutRooms {
+            room_count: 2,
+            assignments: HashMap::from([(1, 0), (2, 1), (3, 0)]),
+            return_at: None,
+        };
+        assert_eq!(Some(expected.clone()), BreakoutRooms::from_proto(sent));
+
+        // The SFU relays the assignment to everyone.
+        Client::handle_breakout_rooms_received(&client1.client.actor, Some(expected.clone()));
+        client1.wait_for_client_to_process();
         assert_eq!(
-            DeviceToSfu {
-                admin_action: Some(AdminAction::Remove(GenericAdminAction {
-                    target_demux_id: Some(32)
-                })),
-                mrp_header: Some(MrpHeader {
-                    seqnum: Some(1),
-                    ..Default::default()
-                }),
-                ..Default::default()
-            },
-            DeviceToSfu::decode(&payload[..]).unwrap()
+            Some(expected),
+            *client1
+                .observer
+                .breakout_rooms
+                .lock()
+                .expect("finished processing")
+        );
+
+        client1.client.end_breakout_rooms();
+        let (_header, payload) = receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("Get RTP packet to SFU");
+        let Some(AdminAction::BreakoutRooms(sent)) =
+            DeviceToSfu::decode(&payload[..]).unwrap().admin_action
+        else {
+            panic!("expected breakout rooms");
+        };
+        assert_eq!(None, BreakoutRooms::from_proto(sent));
+
+        Client::handle_breakout_rooms_received(&client1.client.actor, None);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            None,
+            *client1
+                .observer
+                .breakout_rooms
+                .lock()
+                .expect("finished processing")
         );
 
         client1.disconnect_and_wait_until_ended();
     }
 
     #[test]
-    fn device_to_sfu_block() {
+    fn spotlight() {
         use protobuf::group_call::{
-            device_to_sfu::{AdminAction, GenericAdminAction},
+            device_to_sfu::{AdminAction, Spotlight},
             DeviceToSfu,
         };
 
         let mut client1 = TestClient::new(vec![1], 1);
+        let client2 = TestClient::new(vec![2], 2);
+        let client3 = TestClient::new(vec![3], 3);
 
         let (sender, receiver) = mpsc::channel();
         client1.sfu_rtp_packet_sender = Some(sender);
         client1.connect_join_and_wait_until_joined();
-        client1.set_remotes_and_wait_until_applied(&[]);
-        client1.client.block_client(32);
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+        client1.client.set_spotlight(vec![3]);
 
         let (header, payload) = receiver
             .recv_timeout(Duration::from_secs(1))
@@ -6725,9 +9277,7 @@ mod tests {
         assert_eq!(1, header.ssrc);
         assert_eq!(
             DeviceToSfu {
-                admin_action: Some(AdminAction::Block(GenericAdminAction {
-                    target_demux_id: Some(32)
-                })),
+                admin_action: Some(AdminAction::Spotlight(Spotlight { demux_ids: vec![3] })),
                 mrp_header: Some(MrpHeader {
                     seqnum: Some(1),
                     ..Default::default()
@@ -6737,6 +9287,82 @@ mod tests {
             DeviceToSfu::decode(&payload[..]).unwrap()
         );
 
+        // The SFU relays the spotlight to everyone, which takes over the viewport's first tile.
+        client1.client.set_viewport(Some(Viewport {
+            mode: ViewportMode::Speaker,
+            tiles: vec![TileSize {
+                width: 640,
+                height: 360,
+            }],
+            pinned: vec![2],
+        }));
+        client1.wait_for_client_to_process();
+        Client::handle_spotlight_received(&client1.client.actor, vec![3]);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            vec![3],
+            *client1
+                .observer
+                .spotlight
+                .lock()
+                .expect("finished processing")
+        );
+        assert_eq!(
+            vec![Some(3)],
+            client1
+                .observer
+                .video_layout
+                .lock()
+                .expect("finished processing")
+                .as_ref()
+                .expect("layout reported")
+                .tiles
+        );
+
+        // Spotlighted devices in smaller tiles are still requested at the largest tile's size.
+        client1.client.set_viewport(Some(Viewport {
+            mode: ViewportMode::Speaker,
+            tiles: vec![
+                TileSize {
+                    width: 640,
+                    height: 360,
+                },
+                TileSize {
+                    width: 320,
+                    height: 180,
+                },
+            ],
+            pinned: vec![],
+        }));
+        Client::handle_spotlight_received(&client1.client.actor, vec![2, 3]);
+        client1.wait_for_client_to_process();
+        let layout = client1
+            .observer
+            .video_layout
+            .lock()
+            .expect("finished processing")
+            .clone()
+            .expect("layout reported");
+        assert_eq!(vec![Some(2), Some(3)], layout.tiles);
+        assert_eq!(
+            vec![(2, 640, 360), (3, 640, 360)],
+            layout
+                .video_requests
+                .iter()
+                .map(|request| (request.demux_id, request.width, request.height))
+                .collect::<Vec<_>>()
+        );
+
+        // Leaving forgets the spotlight; the SFU sends it again on rejoin.
+        client1.client.leave();
+        client1.wait_for_client_to_process();
+        assert!(client1
+            .observer
+            .spotlight
+            .lock()
+            .expect("finished processing")
+            .is_empty());
+
         client1.disconnect_and_wait_until_ended();
     }
 
@@ -6976,6 +9602,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         assert_eq!(
             0,
@@ -7305,6 +9932,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lobby() {
+        let client1_demux_id = 1;
+        let mut client1 = TestClient::new(vec![1], client1_demux_id);
+        let client2 = TestClient::new(vec![2], 2);
+        client1
+            .sfu_client
+            .set_response_join_state(JoinState::Pending(client1_demux_id));
+
+        client1.client.connect();
+        client1.client.join();
+        client1.wait_for_client_to_process();
+
+        let device = |demux_id: DemuxId| PeekDeviceInfo {
+            demux_id,
+            user_id: Some(vec![demux_id as u8]),
+        };
+        let lobby = |client: &TestClient| {
+            client
+                .observer
+                .lobby
+                .lock()
+                .expect("finished processing")
+                .clone()
+        };
+
+        client1.client.set_peek_result(Ok(PeekInfo {
+            devices: vec![device(2), device(4)],
+            pending_devices: vec![device(3), device(1)],
+            admin_demux_ids: vec![2],
+            ..PeekInfo::default()
+        }));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            Some(LobbyInfo {
+                participant_count: 2,
+                admins: vec![vec![2]],
+                position: Some(1),
+                message: None,
+            }),
+            lobby(&client1)
+        );
+
+        Client::handle_lobby_message_received(
+            &client1.client.actor,
+            Some("Starting in 5 minutes".to_string()),
+        );
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            Some("Starting in 5 minutes".to_string()),
+            lobby(&client1).and_then(|lobby| lobby.message)
+        );
+
+        // Once approved, lobby messages are ignored.
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client1]);
+        assert!(client1.observer.joined.wait(Duration::from_secs(5)));
+        *client1.observer.lobby.lock().expect("reset lobby") = None;
+        Client::handle_lobby_message_received(&client1.client.actor, Some("Welcome".to_string()));
+        client1.wait_for_client_to_process();
+        assert_eq!(None, lobby(&client1));
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn removal_after_approval() {
         let client1 = TestClient::new(vec![1], 1);
@@ -7350,6 +10041,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7368,6 +10060,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7386,6 +10079,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7404,6 +10098,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7422,6 +10117,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7462,6 +10158,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7491,6 +10188,7 @@ mod tests {
             creator: None,
             era_id: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         }));
         client1.wait_for_client_to_process();
         assert_eq!(
@@ -7505,6 +10203,159 @@ mod tests {
         client1.disconnect_and_wait_until_ended();
     }
 
+    #[test]
+    fn remote_audio_playout() {
+        let client1 = TestClient::new(vec![1], 1);
+        let client2 = TestClient::new(vec![2], 2);
+        let client3 = TestClient::new(vec![3], 3);
+        client1.connect_join_and_wait_until_joined();
+        client1.set_remotes_and_wait_until_applied(&[&client2, &client3]);
+
+        let incoming_audio_gain = |demux_id: DemuxId| {
+            let (sender, receiver) = mpsc::channel();
+            client1.client.actor.send(move |state| {
+                sender
+                    .send(state.peer_connection.incoming_audio_gain(demux_id))
+                    .unwrap();
+            });
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap()
+        };
+
+        client1.client.set_remote_audio_gain(2, 0.5).unwrap();
+        assert_eq!(Some(0.5), incoming_audio_gain(2));
+        assert_eq!(None, incoming_audio_gain(3));
+
+        // Muting keeps the gain for later.
+        client1.client.set_remote_audio_muted(2, true).unwrap();
+        assert_eq!(Some(0.0), incoming_audio_gain(2));
+        client1.client.set_remote_audio_gain(2, 0.25).unwrap();
+        assert_eq!(Some(0.0), incoming_audio_gain(2));
+        client1.client.set_remote_audio_muted(2, false).unwrap();
+        assert_eq!(Some(0.25), incoming_audio_gain(2));
+
+        // Out of range gains are clamped, and invalid ones are rejected.
+        client1.client.set_remote_audio_gain(3, 100.0).unwrap();
+        assert_eq!(Some(MAX_REMOTE_AUDIO_GAIN), incoming_audio_gain(3));
+        assert!(client1.client.set_remote_audio_gain(3, f32::NAN).is_err());
+        assert_eq!(Some(MAX_REMOTE_AUDIO_GAIN), incoming_audio_gain(3));
+
+        // Unknown devices are ignored.
+        client1.client.set_remote_audio_gain(4, 0.5).unwrap();
+        assert_eq!(None, incoming_audio_gain(4));
+
+        // Devices that leave are forgotten.
+        client1.set_remotes_and_wait_until_applied(&[&client3]);
+        let (sender, receiver) = mpsc::channel();
+        client1.client.actor.send(move |state| {
+            sender
+                .send(
+                    state
+                        .remote_audio_playout
+                        .keys()
+                        .copied()
+                        .collect::<Vec<_>>(),
+                )
+                .unwrap();
+        });
+        assert_eq!(
+            vec![3],
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap()
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
+    #[test]
+    fn video_encoder_config() {
+        init_logging();
+        let client1 = TestClient::new(b"1".to_vec(), 1);
+        client1.connect_join_and_wait_until_joined();
+
+        let layer = |max_height: u16, max_kbps: u64| VideoEncoderLayer {
+            max_height,
+            max_framerate: 30,
+            max_bitrate: DataRate::from_kbps(max_kbps),
+        };
+        client1
+            .client
+            .set_video_encoder_config(VideoEncoderConfig {
+                layers: vec![layer(180, 150), layer(360, 400), layer(720, 1200)],
+                codec_preference: vec![VideoCodec::Av1, VideoCodec::Vp8],
+            })
+            .expect("valid config");
+        // Out of order layers are rejected.
+        assert!(client1
+            .client
+            .set_video_encoder_config(VideoEncoderConfig {
+                layers: vec![layer(720, 1200), layer(360, 400)],
+                codec_preference: vec![],
+            })
+            .is_err());
+
+        let peek_info = |device_count: u32| PeekInfo {
+            devices: (1..=device_count)
+                .map(|demux_id| PeekDeviceInfo {
+                    demux_id,
+                    user_id: Some(demux_id.to_string().into_bytes()),
+                })
+                .collect(),
+            max_devices: None,
+            pending_devices: vec![],
+            creator: None,
+            era_id: None,
+            call_link_state: None,
+            admin_demux_ids: vec![],
+        };
+        client1.client.set_peek_result(Ok(peek_info(2)));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            Some(SendRates {
+                min: None,
+                start: None,
+                max: Some(DataRate::from_kbps(1750)),
+            }),
+            client1.observer.send_rates()
+        );
+
+        // Large calls leave out the top layer.
+        client1.client.set_peek_result(Ok(peek_info(20)));
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            Some(SendRates {
+                min: None,
+                start: None,
+                max: Some(DataRate::from_kbps(550)),
+            }),
+            client1.observer.send_rates()
+        );
+
+        Client::handle_video_request_received(&client1.client.actor, 360);
+        assert_eq!(
+            Some(ForwardedVideoLayers {
+                outgoing_layer_count: 2,
+                incoming_heights: HashMap::new(),
+            }),
+            client1
+                .observer
+                .forwarded_video_layers
+                .wait(Duration::from_secs(1))
+        );
+
+        Client::handle_forwarding_video_received(&client1.client.actor, vec![2, 3], vec![540, 0]);
+        assert_eq!(
+            Some(ForwardedVideoLayers {
+                outgoing_layer_count: 2,
+                incoming_heights: HashMap::from([(2, 540)]),
+            }),
+            client1
+                .observer
+                .forwarded_video_layers
+                .wait(Duration::from_secs(1))
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn group_ring() {
         fn ring_once(era_id: &str) -> RingId {
@@ -7648,6 +10499,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn group_ring_recipients() {
+        let user_id = vec![1];
+        let demux_id = 1;
+        let client1 = TestClient::with_sfu_client(
+            user_id.clone(),
+            demux_id,
+            FakeSfuClient::new(demux_id, Some(user_id.clone())),
+        );
+        client1.client.set_group_members(
+            [vec![1], vec![2], vec![3], vec![4]]
+                .into_iter()
+                .map(|user_id| GroupMember {
+                    member_id: user_id.clone(),
+                    user_id,
+                })
+                .collect(),
+        );
+        client1.connect_join_and_wait_until_joined();
+        client1.client.ring(None);
+        client1.wait_for_client_to_process();
+
+        let ring_id = match &client1
+            .observer
+            .sent_group_signaling_messages
+            .lock()
+            .expect("finished processing")[..]
+        {
+            [protobuf::signaling::CallMessage {
+                ring_intention: Some(ring),
+                ..
+            }] => RingId::from(ring.ring_id.expect("should have an ID")),
+            sent_messages => panic!("unexpected group messages: {:?}", sent_messages),
+        };
+        let ring_recipients = || {
+            client1
+                .observer
+                .ring_recipients
+                .lock()
+                .expect("finished processing")
+                .clone()
+        };
+        assert_eq!(
+            HashMap::from([
+                (vec![2], RingRecipientStatus::Sent),
+                (vec![3], RingRecipientStatus::Sent),
+                (vec![4], RingRecipientStatus::Sent),
+            ]),
+            ring_recipients()
+        );
+
+        client1.client.on_ring_delivered(vec![2]);
+        client1
+            .client
+            .on_ring_response_received(vec![3], ring_id, RingRecipientStatus::Ringing);
+        client1
+            .client
+            .on_ring_response_received(vec![4], ring_id, RingRecipientStatus::Declined);
+        // Stale responses and responses for other rings don't change anything.
+        client1
+            .client
+            .on_ring_response_received(vec![4], ring_id, RingRecipientStatus::Ringing);
+        client1.client.on_ring_response_received(
+            vec![2],
+            RingId::from(i64::from(ring_id).wrapping_add(1)),
+            RingRecipientStatus::Busy,
+        );
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            HashMap::from([
+                (vec![2], RingRecipientStatus::Delivered),
+                (vec![3], RingRecipientStatus::Ringing),
+                (vec![4], RingRecipientStatus::Declined),
+            ]),
+            ring_recipients()
+        );
+
+        // Ringing again only rings the people who haven't responded.
+        client1.client.ring_again(vec![]);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            2,
+            client1
+                .observer
+                .multi_recipient_count
+                .load(Ordering::Relaxed)
+        );
+        assert_eq!(
+            HashMap::from([
+                (vec![2], RingRecipientStatus::Sent),
+                (vec![3], RingRecipientStatus::Sent),
+                (vec![4], RingRecipientStatus::Declined),
+            ]),
+            ring_recipients()
+        );
+        client1.client.ring_again(vec![vec![4]]);
+        client1.wait_for_client_to_process();
+        assert_eq!(
+            2,
+            client1
+                .observer
+                .multi_recipient_count
+                .load(Ordering::Relaxed)
+        );
+
+        // Joining counts as accepting.
+        let client2 = TestClient::new(vec![2], 2);
+        client1.set_remotes_and_wait_until_applied(&[&client2]);
+        assert_eq!(
+            Some(&RingRecipientStatus::Accepted),
+            ring_recipients().get(&vec![2])
+        );
+
+        client1.disconnect_and_wait_until_ended();
+    }
+
     #[test]
     fn group_ring_no_cancel_if_call_was_not_empty() {
         let user_id = vec![1];
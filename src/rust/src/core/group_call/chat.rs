@@ -0,0 +1,443 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! In-call chat, sent to everyone in the call through the SFU like reactions.
+//!
+//! Messages are encrypted with the call's frame crypto keys, so the SFU can't read them. Each
+//! message is split into fragments that fit in one RTP packet; the fragments carry MRP headers
+//! (seqnums count fragments from 1 and the first one carries `num_packets`) so that they can be
+//! reassembled with [`MrpStream`]'s merge buffer, whatever order they arrive in.
+//!
+//! Message IDs count up from 1 for each sending device. Receivers deliver each device's messages
+//! in order starting from 1, waiting up to [`CHAT_REORDER_TIMEOUT`] for missing ones before
+//! skipping them (as they must when they join after a device has already sent some).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+use mrp::{MrpHeader, MrpStream};
+use prost::Message;
+
+use crate::{
+    lite::sfu::DemuxId,
+    protobuf::group_call::device_to_device::{
+        ChatFragment, ChatMessage as ProtoChatMessage, ChatReplyTo,
+    },
+};
+
+/// The largest chat message, in bytes of UTF-8.
+pub const CHAT_MESSAGE_MAX_SIZE: usize = 4096;
+
+/// How much of an encoded message goes in each fragment.
+const CHAT_FRAGMENT_MAX_SIZE: usize = 1000;
+/// Enough fragments for the largest message and its encoding overhead.
+const CHAT_MAX_FRAGMENTS: usize = CHAT_MESSAGE_MAX_SIZE / CHAT_FRAGMENT_MAX_SIZE + 2;
+
+/// How long to hold a message back while waiting for earlier messages from the same device.
+pub const CHAT_REORDER_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long to wait for the rest of a partially received message.
+const CHAT_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// How many partially received messages to keep for each device until they time out.
+const CHAT_MAX_REASSEMBLIES: usize = 8;
+/// How many complete messages to hold back for each device while waiting for earlier ones.
+const CHAT_MAX_HELD: usize = 32;
+
+/// Identifies a chat message within a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChatMessageId {
+    /// The device that sent the message.
+    pub demux_id: DemuxId,
+    /// Counts up from 1 for each device.
+    pub seqnum: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub id: ChatMessageId,
+    pub text: String,
+    pub reply_to: Option<ChatMessageId>,
+}
+
+impl ChatMessage {
+    fn to_proto(&self) -> ProtoChatMessage {
+        ProtoChatMessage {
+            text: Some(self.text.clone()),
+            reply_to: self.reply_to.map(|reply_to| ChatReplyTo {
+                demux_id: Some(reply_to.demux_id),
+                seqnum: Some(reply_to.seqnum),
+            }),
+        }
+    }
+
+    fn from_proto(id: ChatMessageId, proto: ProtoChatMessage) -> Option<Self> {
+        let text = proto.text.filter(|text| !text.is_empty())?;
+        if text.len() > CHAT_MESSAGE_MAX_SIZE {
+            warn!(
+                "Ignoring chat message of size {} from {}",
+                text.len(),
+                id.demux_id
+            );
+            return None;
+        }
+        let reply_to = proto.reply_to.and_then(|reply_to| {
+            Some(ChatMessageId {
+                demux_id: reply_to.demux_id?,
+                seqnum: reply_to.seqnum?,
+            })
+        });
+        Some(Self { id, text, reply_to })
+    }
+
+    /// Splits the message into fragments to be sent in separate packets.
+    pub fn to_fragments(&self) -> Vec<ChatFragment> {
+        let encoded = self.to_proto().encode_to_vec();
+        let chunks: Vec<&[u8]> = encoded.chunks(CHAT_FRAGMENT_MAX_SIZE).collect();
+        let num_packets = chunks.len() as u32;
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| ChatFragment {
+                message_seqnum: Some(self.id.seqnum),
+                mrp_header: Some(
+                    MrpHeader::new_with_length(
+                        Some(index as u64 + 1),
+                        None,
+                        (index == 0 && num_packets > 1).then_some(num_packets),
+                    )
+                    .into(),
+                ),
+                content: Some(chunk.to_vec()),
+            })
+            .collect()
+    }
+}
+
+struct Reassembly {
+    started: Instant,
+    stream: MrpStream<(), ChatFragment>,
+}
+
+#[derive(Default)]
+struct ChatSenderState {
+    /// Zero until the first message from the device has been delivered, so that delivery starts
+    /// at 1 even if a later message arrives first.
+    last_delivered: u64,
+    reassembling: HashMap<u64, Reassembly>,
+    /// Complete messages waiting for earlier ones, with when they were completed.
+    held: BTreeMap<u64, (Instant, ChatMessage)>,
+}
+
+impl ChatSenderState {
+    fn is_done_or_held(&self, seqnum: u64) -> bool {
+        seqnum <= self.last_delivered || self.held.contains_key(&seqnum)
+    }
+
+    fn deliver_contiguous(&mut self, delivered: &mut Vec<ChatMessage>) {
+        while let Some((_, message)) = self.held.remove(&(self.last_delivered + 1)) {
+            self.last_delivered = message.id.seqnum;
+            delivered.push(message);
+        }
+    }
+}
+
+/// Reassembles and orders the chat messages received from other devices.
+#[derive(Default)]
+pub struct ChatReceiver {
+    senders: HashMap<DemuxId, ChatSenderState>,
+}
+
+impl ChatReceiver {
+    /// Returns the messages that are ready to be shown, in order.
+    pub fn receive(
+        &mut self,
+        demux_id: DemuxId,
+        fragment: ChatFragment,
+        now: Instant,
+    ) -> Vec<ChatMessage> {
+        let (Some(seqnum), Some(mrp_header)) = (fragment.message_seqnum, &fragment.mrp_header)
+        else {
+            warn!("Ignoring chat fragment without a seqnum from {}", demux_id);
+            return vec![];
+        };
+        let mrp_header: MrpHeader = mrp_header.into();
+
+        let sender = self.senders.entry(demux_id).or_default();
+        if sender.is_done_or_held(seqnum) {
+            // A resent copy of a message we already have.
+            return vec![];
+        }
+
+        if !sender.reassembling.contains_key(&seqnum)
+            && sender.reassembling.len() >= CHAT_MAX_REASSEMBLIES
+        {
+            warn!(
+                "Dropping chat message {} from {}: too many partial messages",
+                seqnum, demux_id
+            );
+            return vec![];
+        }
+        let reassembly = sender
+            .reassembling
+            .entry(seqnum)
+            .or_insert_with(|| Reassembly {
+                started: now,
+                stream: MrpStream::with_capacity_limit(CHAT_MAX_FRAGMENTS),
+            });
+        let merged = match reassembly.stream.receive_and_merge(&mrp_header, fragment) {
+            Ok(merged) => merged,
+            Err(err) => {
+                warn!(
+                    "Dropping chat message {} from {}: {:?}",
+                    seqnum, demux_id, err
+                );
+                sender.reassembling.remove(&seqnum);
+                return vec![];
+            }
+        };
+        let Some(merged) = merged.into_iter().next() else {
+            return vec![];
+        };
+        sender.reassembling.remove(&seqnum);
+
+        let id = ChatMessageId { demux_id, seqnum };
+        let Some(message) = ProtoChatMessage::decode(merged.content.unwrap_or_default().as_slice())
+            .ok()
+            .and_then(|proto| ChatMessage::from_proto(id, proto))
+        else {
+            warn!("Ignoring invalid chat message {} from {}", seqnum, demux_id);
+            return vec![];
+        };
+
+        let mut delivered = vec![];
+        if seqnum == sender.last_delivered + 1 {
+            sender.last_delivered = seqnum;
+            delivered.push(message);
+            sender.deliver_contiguous(&mut delivered);
+        } else if sender.held.len() >= CHAT_MAX_HELD {
+            warn!(
+                "Dropping chat message {} from {}: too many held messages",
+                seqnum, demux_id
+            );
+        } else {
+            sender.held.insert(seqnum, (now, message));
+        }
+        delivered
+    }
+
+    /// Gives up on missing messages that have been waited for long enough, returning the messages
+    /// that were held back behind them, in order.
+    pub fn expire(&mut self, now: Instant) -> Vec<ChatMessage> {
+        let mut delivered = vec![];
+        for sender in self.senders.values_mut() {
+            sender
+                .reassembling
+                .retain(|_, reassembly| now < reassembly.started + CHAT_REASSEMBLY_TIMEOUT);
+
+            let last_expired = sender
+                .held
+                .iter()
+                .filter(|(_, (held_at, _))| now >= *held_at + CHAT_REORDER_TIMEOUT)
+                .map(|(seqnum, _)| *seqnum)
+                .last();
+            if let Some(last_expired) = last_expired {
+                let still_held = sender.held.split_off(&(last_expired + 1));
+                for (seqnum, (_, message)) in std::mem::replace(&mut sender.held, still_held) {
+                    sender.last_delivered = seqnum;
+                    delivered.push(message);
+                }
+                sender.deliver_contiguous(&mut delivered);
+            }
+        }
+        delivered
+    }
+
+    /// Forgets devices that are no longer in the call.
+    pub fn retain_senders(&mut self, mut is_present: impl FnMut(DemuxId) -> bool) {
+        self.senders.retain(|demux_id, _| is_present(*demux_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(seqnum: u64, text: &str) -> ChatMessage {
+        ChatMessage {
+            id: ChatMessageId {
+                demux_id: 16,
+                seqnum,
+            },
+            text: text.to_string(),
+            reply_to: None,
+        }
+    }
+
+    fn receive_all(
+        receiver: &mut ChatReceiver,
+        fragments: Vec<ChatFragment>,
+        now: Instant,
+    ) -> Vec<ChatMessage> {
+        fragments
+            .into_iter()
+            .flat_map(|fragment| receiver.receive(16, fragment, now))
+            .collect()
+    }
+
+    #[test]
+    fn fragments_out_of_order() {
+        let long = ChatMessage {
+            reply_to: Some(ChatMessageId {
+                demux_id: 32,
+                seqnum: 7,
+            }),
+            ..message(1, &"🔗".repeat(CHAT_MESSAGE_MAX_SIZE / 4))
+        };
+        let mut fragments = long.to_fragments();
+        assert!(fragments.len() > 1);
+        fragments.reverse();
+
+        let mut receiver = ChatReceiver::default();
+        let now = Instant::now();
+        assert_eq!(
+            vec![long],
+            receive_all(&mut receiver, fragments.clone(), now)
+        );
+
+        // Resent copies are ignored.
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, fragments, now)
+        );
+    }
+
+    #[test]
+    fn delivery_order() {
+        let mut receiver = ChatReceiver::default();
+        let now = Instant::now();
+
+        // Messages wait for the ones before them, starting from the first.
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(2, "two").to_fragments(), now)
+        );
+        assert_eq!(
+            vec![message(1, "one"), message(2, "two")],
+            receive_all(&mut receiver, message(1, "one").to_fragments(), now)
+        );
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(5, "five").to_fragments(), now)
+        );
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(4, "four").to_fragments(), now)
+        );
+        assert_eq!(
+            vec![message(3, "three"), message(4, "four"), message(5, "five")],
+            receive_all(&mut receiver, message(3, "three").to_fragments(), now)
+        );
+
+        // Missing messages are eventually skipped.
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(7, "seven").to_fragments(), now)
+        );
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receiver.expire(now + CHAT_REORDER_TIMEOUT / 2)
+        );
+        assert_eq!(
+            vec![message(7, "seven")],
+            receiver.expire(now + CHAT_REORDER_TIMEOUT)
+        );
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(6, "six").to_fragments(), now)
+        );
+        assert_eq!(
+            vec![message(8, "eight")],
+            receive_all(&mut receiver, message(8, "eight").to_fragments(), now)
+        );
+    }
+
+    #[test]
+    fn limits_per_sender() {
+        let mut receiver = ChatReceiver::default();
+        let now = Instant::now();
+
+        // Only so many messages are held back; the rest are dropped.
+        for seqnum in 2..=(CHAT_MAX_HELD as u64 + 2) {
+            assert_eq!(
+                Vec::<ChatMessage>::new(),
+                receive_all(&mut receiver, message(seqnum, "held").to_fragments(), now)
+            );
+        }
+        let delivered = receive_all(&mut receiver, message(1, "one").to_fragments(), now);
+        assert_eq!(
+            (1..=(CHAT_MAX_HELD as u64 + 1)).collect::<Vec<_>>(),
+            delivered
+                .iter()
+                .map(|message| message.id.seqnum)
+                .collect::<Vec<_>>()
+        );
+
+        // A dropped message can be resent.
+        let dropped = CHAT_MAX_HELD as u64 + 2;
+        assert_eq!(
+            vec![message(dropped, "held")],
+            receive_all(&mut receiver, message(dropped, "held").to_fragments(), now)
+        );
+
+        // Only so many messages are reassembled at once; fragments of others are dropped.
+        let first = dropped + 1;
+        let long: Vec<Vec<ChatFragment>> = (first..=(first + CHAT_MAX_REASSEMBLIES as u64))
+            .map(|seqnum| message(seqnum, &"🔗".repeat(CHAT_MESSAGE_MAX_SIZE / 4)).to_fragments())
+            .collect();
+        for fragments in &long {
+            assert_eq!(
+                Vec::<ChatMessage>::new(),
+                receive_all(&mut receiver, fragments[..1].to_vec(), now)
+            );
+        }
+        let delivered: Vec<ChatMessage> = long
+            .into_iter()
+            .flat_map(|fragments| receive_all(&mut receiver, fragments[1..].to_vec(), now))
+            .collect();
+        assert_eq!(
+            (first..(first + CHAT_MAX_REASSEMBLIES as u64)).collect::<Vec<_>>(),
+            delivered
+                .iter()
+                .map(|message| message.id.seqnum)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn joined_late() {
+        let mut receiver = ChatReceiver::default();
+        let now = Instant::now();
+
+        // Messages sent before we joined never arrive, so the first ones we get are held until
+        // they're skipped, but not past any earlier ones that are just late.
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(5, "five").to_fragments(), now)
+        );
+        assert_eq!(
+            Vec::<ChatMessage>::new(),
+            receive_all(&mut receiver, message(4, "four").to_fragments(), now)
+        );
+        assert_eq!(
+            vec![message(4, "four"), message(5, "five")],
+            receiver.expire(now + CHAT_REORDER_TIMEOUT)
+        );
+        assert_eq!(
+            vec![message(6, "six")],
+            receive_all(&mut receiver, message(6, "six").to_fragments(), now)
+        );
+    }
+}
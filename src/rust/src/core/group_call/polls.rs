@@ -0,0 +1,297 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! In-call polls, kept by the SFU so that everyone sees the same tallies.
+//!
+//! Devices send poll actions with seqnums counting up from 1, and the SFU applies each one once.
+//! The SFU sends everyone the state of every poll, versioned with a seqnum of its own. Like with
+//! raised hands, it also echoes the latest action seqnum it applied from the receiving device,
+//! which tells the device when its actions have taken effect.
+
+use anyhow::bail;
+
+use crate::{
+    lite::sfu::DemuxId,
+    protobuf::group_call::{
+        device_to_sfu::{poll_action, PollAction},
+        sfu_to_device::{polls::Poll as ProtoPoll, Polls as ProtoPolls},
+    },
+};
+
+/// The longest poll question, in bytes of UTF-8.
+pub const POLL_QUESTION_MAX_SIZE: usize = 256;
+/// The longest poll option, in bytes of UTF-8.
+pub const POLL_OPTION_MAX_SIZE: usize = 100;
+pub const POLL_MIN_OPTIONS: usize = 2;
+pub const POLL_MAX_OPTIONS: usize = 10;
+
+/// Assigned by the SFU, counting up from 1 within a call.
+pub type PollId = u32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PollOption {
+    pub text: String,
+    pub votes: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poll {
+    pub id: PollId,
+    pub creator: DemuxId,
+    pub question: String,
+    pub options: Vec<PollOption>,
+    /// The index of the option the local device voted for, if any.
+    pub own_vote: Option<usize>,
+    /// Closed polls keep their results but no longer accept votes.
+    pub closed: bool,
+}
+
+impl Poll {
+    pub fn total_votes(&self) -> u32 {
+        self.options.iter().map(|option| option.votes).sum()
+    }
+
+    fn from_proto(proto: ProtoPoll) -> Option<Self> {
+        let ProtoPoll {
+            poll_id: Some(id),
+            creator_demux_id: Some(creator),
+            question: Some(question),
+            options,
+            vote_counts,
+            closed,
+            own_vote,
+        } = proto
+        else {
+            warn!("Ignoring poll with missing fields");
+            return None;
+        };
+        if let Err(err) = validate_new_poll(&question, &options) {
+            warn!("Ignoring invalid poll {}: {}", id, err);
+            return None;
+        }
+        if vote_counts.len() != options.len() {
+            warn!(
+                "Ignoring poll {} with {} options but {} vote counts",
+                id,
+                options.len(),
+                vote_counts.len()
+            );
+            return None;
+        }
+        let own_vote = own_vote
+            .map(|index| index as usize)
+            .filter(|index| *index < options.len());
+        Some(Self {
+            id,
+            creator,
+            question,
+            options: options
+                .into_iter()
+                .zip(vote_counts)
+                .map(|(text, votes)| PollOption { text, votes })
+                .collect(),
+            own_vote,
+            closed: closed.unwrap_or(false),
+        })
+    }
+}
+
+/// Checks a poll against the limits the SFU enforces.
+pub fn validate_new_poll(question: &str, options: &[String]) -> anyhow::Result<()> {
+    if question.is_empty() || question.len() > POLL_QUESTION_MAX_SIZE {
+        bail!("question size of {} is not allowed", question.len());
+    }
+    if !(POLL_MIN_OPTIONS..=POLL_MAX_OPTIONS).contains(&options.len()) {
+        bail!("{} options are not allowed", options.len());
+    }
+    if let Some(option) = options
+        .iter()
+        .find(|option| option.is_empty() || option.len() > POLL_OPTION_MAX_SIZE)
+    {
+        bail!("option size of {} is not allowed", option.len());
+    }
+    Ok(())
+}
+
+/// The local view of the call's polls.
+#[derive(Default)]
+pub struct PollsState {
+    /// The seqnum of the latest action sent by the local device.
+    action_seqnum: u32,
+    /// The latest action seqnum the SFU has applied.
+    applied_seqnum: u32,
+    /// The SFU's seqnum for `polls`.
+    version: u32,
+    polls: Vec<Poll>,
+}
+
+impl PollsState {
+    pub fn polls(&self) -> &[Poll] {
+        &self.polls
+    }
+
+    pub fn find(&self, id: PollId) -> Option<&Poll> {
+        self.polls.iter().find(|poll| poll.id == id)
+    }
+
+    /// Assigns the next seqnum to an action.
+    pub fn next_action(&mut self, action: poll_action::Action) -> PollAction {
+        self.action_seqnum += 1;
+        PollAction {
+            seqnum: Some(self.action_seqnum),
+            action: Some(action),
+        }
+    }
+
+    /// Whether some actions haven't been applied by the SFU yet.
+    pub fn has_outstanding_actions(&self) -> bool {
+        self.applied_seqnum < self.action_seqnum
+    }
+
+    /// Applies an update from the SFU, returning true if the observer should hear about it.
+    ///
+    /// That's whenever the polls change, and also when the last outstanding action is applied even
+    /// if it didn't change anything, so the app can update its UI either way.
+    pub fn receive(&mut self, proto: ProtoPolls) -> bool {
+        let version = proto.seqnum.unwrap_or(0);
+        if version < self.version {
+            debug!(
+                "Ignoring stale polls (seqnum {} < {})",
+                version, self.version
+            );
+            return false;
+        }
+
+        let was_outstanding = self.has_outstanding_actions();
+        let target_seqnum = proto.target_seqnum.unwrap_or(0);
+        if target_seqnum > self.applied_seqnum {
+            self.applied_seqnum = target_seqnum;
+            // The SFU may know about actions sent before a handoff.
+            self.action_seqnum = self.action_seqnum.max(target_seqnum);
+        }
+
+        let polls: Vec<Poll> = proto
+            .polls
+            .into_iter()
+            .filter_map(Poll::from_proto)
+            .collect();
+        self.version = version;
+        let changed = polls != self.polls;
+        self.polls = polls;
+        changed || (was_outstanding && !self.has_outstanding_actions())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proto_poll(id: PollId, vote_counts: Vec<u32>, own_vote: Option<u32>) -> ProtoPoll {
+        ProtoPoll {
+            poll_id: Some(id),
+            creator_demux_id: Some(16),
+            question: Some("Lunch?".to_string()),
+            options: vec!["Tacos".to_string(), "Pizza".to_string()],
+            vote_counts,
+            closed: None,
+            own_vote,
+        }
+    }
+
+    fn proto_polls(seqnum: u32, target_seqnum: u32, polls: Vec<ProtoPoll>) -> ProtoPolls {
+        ProtoPolls {
+            polls,
+            seqnum: Some(seqnum),
+            target_seqnum: Some(target_seqnum),
+        }
+    }
+
+    #[test]
+    fn poll_from_proto() {
+        let poll = Poll::from_proto(proto_poll(1, vec![2, 1], Some(1))).unwrap();
+        assert_eq!(
+            vec![
+                PollOption {
+                    text: "Tacos".to_string(),
+                    votes: 2
+                },
+                PollOption {
+                    text: "Pizza".to_string(),
+                    votes: 1
+                },
+            ],
+            poll.options
+        );
+        assert_eq!(Some(1), poll.own_vote);
+        assert_eq!(3, poll.total_votes());
+        assert!(!poll.closed);
+
+        assert_eq!(
+            None,
+            Poll::from_proto(proto_poll(1, vec![2, 1], Some(2)))
+                .unwrap()
+                .own_vote
+        );
+        assert_eq!(None, Poll::from_proto(proto_poll(1, vec![2], None)));
+        assert_eq!(
+            None,
+            Poll::from_proto(ProtoPoll {
+                options: vec!["Tacos".to_string()],
+                vote_counts: vec![0],
+                ..proto_poll(1, vec![], None)
+            })
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let options = |count: usize| vec!["Yes".to_string(); count];
+        assert!(validate_new_poll("Lunch?", &options(POLL_MIN_OPTIONS)).is_ok());
+        assert!(validate_new_poll("Lunch?", &options(POLL_MAX_OPTIONS)).is_ok());
+        assert!(validate_new_poll("Lunch?", &options(POLL_MIN_OPTIONS - 1)).is_err());
+        assert!(validate_new_poll("Lunch?", &options(POLL_MAX_OPTIONS + 1)).is_err());
+        assert!(validate_new_poll("", &options(2)).is_err());
+        assert!(validate_new_poll(&"?".repeat(POLL_QUESTION_MAX_SIZE + 1), &options(2)).is_err());
+        assert!(validate_new_poll(
+            "Lunch?",
+            &["Yes".to_string(), "!".repeat(POLL_OPTION_MAX_SIZE + 1)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn receive() {
+        let mut state = PollsState::default();
+        assert!(!state.receive(proto_polls(0, 0, vec![])));
+
+        let action = state.next_action(poll_action::Action::Vote(poll_action::Vote {
+            poll_id: Some(1),
+            option_index: Some(0),
+        }));
+        assert_eq!(Some(1), action.seqnum);
+        assert!(state.has_outstanding_actions());
+
+        // Someone else's poll shows up before our vote is applied.
+        assert!(state.receive(proto_polls(1, 0, vec![proto_poll(1, vec![0, 0], None)])));
+        assert!(state.has_outstanding_actions());
+
+        // Our vote is applied.
+        assert!(state.receive(proto_polls(2, 1, vec![proto_poll(1, vec![1, 0], Some(0))])));
+        assert!(!state.has_outstanding_actions());
+        assert_eq!(Some(0), state.find(1).unwrap().own_vote);
+
+        // Stale updates are ignored.
+        assert!(!state.receive(proto_polls(1, 0, vec![proto_poll(1, vec![0, 0], None)])));
+        assert_eq!(1, state.find(1).unwrap().total_votes());
+
+        // A vote that doesn't change anything (repeating our vote) still reports back once applied.
+        state.next_action(poll_action::Action::Vote(poll_action::Vote {
+            poll_id: Some(1),
+            option_index: Some(0),
+        }));
+        assert!(state.receive(proto_polls(2, 2, vec![proto_poll(1, vec![1, 0], Some(0))])));
+        assert!(!state.receive(proto_polls(2, 2, vec![proto_poll(1, vec![1, 0], Some(0))])));
+    }
+}
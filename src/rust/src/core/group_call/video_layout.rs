@@ -0,0 +1,263 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Chooses which remote devices to show in the app's video tiles, and at what resolution.
+//!
+//! Apps describe what they can display with a [`Viewport`] (see [`super::Client::set_viewport`])
+//! and the client turns it into [`VideoRequest`]s whenever the remote devices change.
+
+use std::cmp::Reverse;
+
+use num_enum::TryFromPrimitive;
+
+use super::{RemoteDeviceState, VideoRequest};
+use crate::lite::sfu::DemuxId;
+
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive)]
+pub enum ViewportMode {
+    /// The first tile shows the first pinned device or else the active speaker, whether or not
+    /// they are sending video. The other tiles show the most recent speakers.
+    Speaker = 0,
+    /// When everyone fits, tiles show devices in the order they joined, so that tiles don't move
+    /// around. Otherwise, the most recent speakers are shown, still in the order they joined.
+    Grid,
+}
+
+/// The size of a tile, in physical pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+/// What the app is able to display.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub mode: ViewportMode,
+    /// The tiles in display order. In speaker mode, the first tile is the speaker's.
+    pub tiles: Vec<TileSize>,
+    /// Devices that always get a tile, ahead of everyone else.
+    pub pinned: Vec<DemuxId>,
+}
+
+/// The result of laying out a [`Viewport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VideoLayout {
+    /// The device to show in each of the viewport's tiles; `None` if there is nobody to show.
+    pub tiles: Vec<Option<DemuxId>>,
+    /// One request per remote device. Devices without a tile are requested at zero size so that
+    /// the SFU stops forwarding their video.
+    pub video_requests: Vec<VideoRequest>,
+    pub active_speaker_height: u16,
+}
+
+impl Viewport {
+    pub fn layout(&self, remote_devices: &[RemoteDeviceState]) -> VideoLayout {
+        let candidates: Vec<&RemoteDeviceState> = remote_devices
+            .iter()
+            .filter(|device| !device.leaving_received)
+            .collect();
+
+        let mut shown: Vec<&RemoteDeviceState> = Vec::with_capacity(self.tiles.len());
+        for demux_id in &self.pinned {
+            if shown.len() == self.tiles.len() {
+                break;
+            }
+            if let Some(device) = candidates.iter().find(|d| d.demux_id == *demux_id) {
+                if !shown.iter().any(|d| d.demux_id == *demux_id) {
+                    shown.push(*device);
+                }
+            }
+        }
+        let pinned_count = shown.len();
+
+        let mut others: Vec<&RemoteDeviceState> = candidates
+            .into_iter()
+            .filter(|device| !shown.iter().any(|d| d.demux_id == device.demux_id))
+            .collect();
+        if self.mode == ViewportMode::Speaker && pinned_count == 0 && !self.tiles.is_empty() {
+            // The speaker tile always follows the active speaker, even if they have no video.
+            let speaker_index = others
+                .iter()
+                .enumerate()
+                .filter(|(_, device)| device.speaker_time.is_some())
+                .max_by_key(|(_, device)| device.speaker_time)
+                .map(|(index, _)| index);
+            if let Some(speaker_index) = speaker_index {
+                shown.push(others.remove(speaker_index));
+            }
+        }
+        // Devices that have turned their video off go last, then the most recent speakers first.
+        // This uses what the devices say rather than what the SFU forwards, since the SFU stops
+        // forwarding devices without a tile, which would keep them from ever getting one back.
+        others.sort_by_key(|device| {
+            (
+                device.heartbeat_state.video_muted == Some(true),
+                Reverse(device.speaker_time),
+                device.added_time,
+                device.demux_id,
+            )
+        });
+        let remaining_tiles = self.tiles.len() - shown.len();
+        others.truncate(remaining_tiles);
+        if self.mode == ViewportMode::Grid {
+            others.sort_by_key(|device| (device.added_time, device.demux_id));
+        }
+        shown.extend(others);
+
+        let video_requests = remote_devices
+            .iter()
+            .map(|device| {
+                let size = shown
+                    .iter()
+                    .position(|d| d.demux_id == device.demux_id)
+                    .map(|index| self.tiles[index])
+                    .unwrap_or(TileSize {
+                        width: 0,
+                        height: 0,
+                    });
+                VideoRequest {
+                    demux_id: device.demux_id,
+                    width: size.width,
+                    height: size.height,
+                    framerate: None,
+                }
+            })
+            .collect();
+
+        let active_speaker_height = match self.mode {
+            ViewportMode::Speaker => self.tiles.first().map(|tile| tile.height),
+            ViewportMode::Grid => self.tiles.iter().map(|tile| tile.height).max(),
+        }
+        .unwrap_or(0);
+
+        VideoLayout {
+            tiles: (0..self.tiles.len())
+                .map(|index| shown.get(index).map(|device| device.demux_id))
+                .collect(),
+            video_requests,
+            active_speaker_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    const BIG: TileSize = TileSize {
+        width: 1280,
+        height: 720,
+    };
+    const SMALL: TileSize = TileSize {
+        width: 320,
+        height: 180,
+    };
+
+    fn device(demux_id: DemuxId, added_secs: u64, spoke_secs: Option<u64>) -> RemoteDeviceState {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let mut device = RemoteDeviceState::new(demux_id, vec![demux_id as u8], at(added_secs));
+        device.speaker_time = spoke_secs.map(at);
+        device
+    }
+
+    fn requested_height(layout: &VideoLayout, demux_id: DemuxId) -> u16 {
+        layout
+            .video_requests
+            .iter()
+            .find(|request| request.demux_id == demux_id)
+            .expect("every device is requested")
+            .height
+    }
+
+    #[test]
+    fn speaker_mode() {
+        let devices = [
+            device(16, 1, Some(10)),
+            device(32, 2, Some(30)),
+            device(48, 3, None),
+            device(64, 4, Some(20)),
+        ];
+        let viewport = Viewport {
+            mode: ViewportMode::Speaker,
+            tiles: vec![BIG, SMALL, SMALL],
+            pinned: vec![],
+        };
+        let layout = viewport.layout(&devices);
+        assert_eq!(vec![Some(32), Some(64), Some(16)], layout.tiles);
+        assert_eq!(720, layout.active_speaker_height);
+        assert_eq!(720, requested_height(&layout, 32));
+        assert_eq!(180, requested_height(&layout, 64));
+        assert_eq!(0, requested_height(&layout, 48));
+
+        // Pinning takes over the speaker tile.
+        let layout = Viewport {
+            pinned: vec![48],
+            ..viewport
+        }
+        .layout(&devices);
+        assert_eq!(vec![Some(48), Some(32), Some(64)], layout.tiles);
+        assert_eq!(0, requested_height(&layout, 16));
+    }
+
+    #[test]
+    fn speaker_mode_keeps_speaker_without_video() {
+        let mut devices = [device(16, 1, Some(10)), device(32, 2, Some(30))];
+        devices[1].heartbeat_state.video_muted = Some(true);
+        let layout = Viewport {
+            mode: ViewportMode::Speaker,
+            tiles: vec![BIG],
+            pinned: vec![],
+        }
+        .layout(&devices);
+        assert_eq!(vec![Some(32)], layout.tiles);
+    }
+
+    #[test]
+    fn grid_mode() {
+        let mut devices = [
+            device(16, 1, Some(10)),
+            device(32, 2, Some(30)),
+            device(48, 3, Some(40)),
+            device(64, 4, Some(20)),
+            device(80, 5, None),
+        ];
+        let viewport = Viewport {
+            mode: ViewportMode::Grid,
+            tiles: vec![SMALL; 3],
+            pinned: vec![],
+        };
+
+        // The most recent speakers, in the order they were added.
+        let layout = viewport.layout(&devices);
+        assert_eq!(vec![Some(32), Some(48), Some(64)], layout.tiles);
+        assert_eq!(180, layout.active_speaker_height);
+
+        // A device without a tile (and so not forwarded) gets one when it speaks.
+        devices[0].forwarding_video = Some(false);
+        devices[0].speaker_time = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(50));
+        let layout = viewport.layout(&devices);
+        assert_eq!(vec![Some(16), Some(32), Some(48)], layout.tiles);
+        assert_eq!(180, requested_height(&layout, 16));
+        assert_eq!(0, requested_height(&layout, 64));
+
+        // Devices with their video off give way to those with video.
+        devices[2].heartbeat_state.video_muted = Some(true);
+        let layout = viewport.layout(&devices);
+        assert_eq!(vec![Some(16), Some(32), Some(64)], layout.tiles);
+        assert_eq!(0, requested_height(&layout, 48));
+
+        // Leaving devices are never shown, and extra tiles are left empty.
+        for device in &mut devices[1..] {
+            device.leaving_received = true;
+        }
+        let layout = viewport.layout(&devices);
+        assert_eq!(vec![Some(16), None, None], layout.tiles);
+        assert_eq!(devices.len(), layout.video_requests.len());
+    }
+}
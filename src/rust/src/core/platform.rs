@@ -5,7 +5,7 @@
 
 //! Platform trait describing the interface an operating system platform must
 /// implement for calling.
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::{fmt, time::Duration};
 
 use crate::{
@@ -20,6 +20,7 @@ use crate::{
         signaling,
     },
     lite::{
+        call_links::{BreakoutRooms, CallLinkRootKey},
         sfu,
         sfu::{DemuxId, PeekInfo, UserId},
     },
@@ -196,6 +197,26 @@ pub trait Platform: sfu::Delegate + fmt::Debug + fmt::Display + Send + Sized + '
         age: Duration,
     ) -> Result<()>;
 
+    /// Notify the application that the call ended because another device of the
+    /// local user accepted it, or took it over using [`CallManager::pull_call`].
+    ///
+    /// Platforms that don't track the accepting device can rely on the default, which
+    /// reports [`ApplicationEvent::EndedRemoteHangupAccepted`].
+    ///
+    /// [`CallManager::pull_call`]: crate::core::call_manager::CallManager::pull_call
+    fn on_accepted_on_another_device(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        _device_id: DeviceId,
+    ) -> Result<()> {
+        self.on_event(
+            remote_peer,
+            call_id,
+            ApplicationEvent::EndedRemoteHangupAccepted,
+        )
+    }
+
     /// Notify the application that the call is completely concluded
     fn on_call_concluded(&self, remote_peer: &Self::AppRemotePeer, call_id: CallId) -> Result<()>;
 
@@ -215,6 +236,17 @@ pub trait Platform: sfu::Delegate + fmt::Debug + fmt::Display + Send + Sized + '
         update: group_call::RingUpdate,
     );
 
+    /// Notify the application that a call link's scheduled meeting is starting.
+    ///
+    /// The update is Requested, ExpiredRequest, or BusyLocally; nothing is sent back to the ringer.
+    fn call_link_ring_update(
+        &self,
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender: UserId,
+        update: group_call::RingUpdate,
+    );
+
     fn request_membership_proof(&self, client_id: group_call::ClientId);
 
     fn request_group_members(&self, client_id: group_call::ClientId);
@@ -279,6 +311,79 @@ pub trait Platform: sfu::Delegate + fmt::Debug + fmt::Display + Send + Sized + '
 
     fn handle_raised_hands(&self, client_id: group_call::ClientId, raised_hands: Vec<DemuxId>);
 
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _layers: group_call::ForwardedVideoLayers,
+    ) {
+    }
+
+    fn handle_speaker_queue_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _queue: group_call::SpeakerQueue,
+    ) {
+    }
+
+    fn handle_handoff_state_received(
+        &self,
+        _client_id: group_call::ClientId,
+        _state: group_call::HandoffState,
+    ) {
+    }
+
+    fn handle_handed_off(&self, _client_id: group_call::ClientId, _new_demux_id: DemuxId) {}
+
+    fn handle_spotlight_changed(&self, _client_id: group_call::ClientId, _spotlight: Vec<DemuxId>) {
+    }
+
+    fn handle_muted_by_admin(&self, _client_id: group_call::ClientId, _admin_demux_id: DemuxId) {}
+
+    fn handle_unmute_requested(&self, _client_id: group_call::ClientId, _admin_demux_id: DemuxId) {}
+
+    fn handle_chat_message(
+        &self,
+        _client_id: group_call::ClientId,
+        _message: group_call::ChatMessage,
+    ) {
+    }
+
+    fn handle_polls_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _polls: Vec<group_call::Poll>,
+    ) {
+    }
+
+    fn handle_lobby_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _lobby: group_call::LobbyInfo,
+    ) {
+    }
+
+    fn handle_breakout_rooms_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _breakout_rooms: Option<BreakoutRooms>,
+    ) {
+    }
+
+    fn handle_video_layout_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _layout: group_call::VideoLayout,
+    ) {
+    }
+
+    fn handle_ring_recipients_changed(
+        &self,
+        _client_id: group_call::ClientId,
+        _ring_id: group_call::RingId,
+        _recipients: HashMap<UserId, group_call::RingRecipientStatus>,
+    ) {
+    }
+
     fn handle_rtc_stats_report(&self, _report_json: String) {}
 
     fn handle_ended(&self, client_id: group_call::ClientId, reason: group_call::EndReason);
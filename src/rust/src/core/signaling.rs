@@ -15,7 +15,7 @@ use bytes::{Bytes, BytesMut};
 use prost::Message as _;
 
 use crate::{
-    common::{CallMediaType, DeviceId, Result},
+    common::{CallId, CallMediaType, DeviceId, Result},
     protobuf,
 };
 
@@ -127,7 +127,10 @@ impl Offer {
         call_media_type: CallMediaType,
         v4: protobuf::signaling::ConnectionParametersV4,
     ) -> Result<Self> {
-        let proto = protobuf::signaling::Offer { v4: Some(v4) };
+        let proto = protobuf::signaling::Offer {
+            v4: Some(v4),
+            replaces_call_id: None,
+        };
 
         let mut opaque = BytesMut::with_capacity(proto.encoded_len());
         proto.encode(&mut opaque)?;
@@ -135,6 +138,24 @@ impl Offer {
         Self::new(call_media_type, opaque.to_vec())
     }
 
+    /// Marks the offer as taking over the given call, which was accepted
+    /// on another device of the same user.
+    pub fn with_replaced_call_id(self, replaced_call_id: CallId) -> Result<Self> {
+        let proto = protobuf::signaling::Offer {
+            replaces_call_id: Some(replaced_call_id.as_u64()),
+            ..self.proto
+        };
+
+        let mut opaque = BytesMut::with_capacity(proto.encoded_len());
+        proto.encode(&mut opaque)?;
+
+        Self::new(self.call_media_type, opaque.to_vec())
+    }
+
+    pub fn replaced_call_id(&self) -> Option<CallId> {
+        self.proto.replaces_call_id.map(CallId::new)
+    }
+
     pub fn to_v4(&self) -> Option<protobuf::signaling::ConnectionParametersV4> {
         match self {
             Self {
@@ -147,10 +168,11 @@ impl Offer {
 
     pub fn to_info_string(&self) -> String {
         format!(
-            "opaque.len={}\tproto.version={}\ttype={}",
+            "opaque.len={}\tproto.version={}\ttype={}\treplaces={:?}",
             self.opaque.len(),
             self.latest_version(),
-            self.call_media_type
+            self.call_media_type,
+            self.replaced_call_id()
         )
     }
 }
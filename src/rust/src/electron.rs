@@ -12,19 +12,19 @@ use std::{
         mpsc::{channel, Receiver, Sender},
         Arc, Mutex,
     },
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use lazy_static::lazy_static;
 use neon::{
     prelude::*,
-    types::{buffer::TypedArray, JsBigInt},
+    types::{buffer::TypedArray, JsBigInt, JsDate},
 };
 
 use crate::{
     common::{CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result},
     core::{
-        call_manager::CallManager,
+        call_manager::{CallManager, PersistedState},
         group_call,
         group_call::{GroupId, SignalingMessageUrgency},
         signaling,
@@ -32,8 +32,8 @@ use crate::{
     },
     lite::{
         call_links::{
-            self, CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkState,
-            CallLinkUpdateRequest, Empty,
+            self, CallLinkDeleteRequest, CallLinkRestrictions, CallLinkRootKey, CallLinkSchedule,
+            CallLinkState, CallLinkUpdateRequest, Empty,
         },
         http, sfu,
         sfu::{DemuxId, GroupMember, PeekInfo, UserId},
@@ -573,6 +573,7 @@ fn to_js_peek_info<'a>(
         era_id,
         max_devices,
         call_link_state: _call_link_state,
+        admin_demux_ids: _admin_demux_ids,
     } = &peek_info;
 
     let js_devices = JsArray::new(cx, devices.len());
@@ -635,6 +636,68 @@ fn to_js_peek_info<'a>(
     Ok(js_info)
 }
 
+fn to_js_date<'a>(cx: &mut FunctionContext<'a>, time: SystemTime) -> JsResult<'a, JsDate> {
+    cx.date(
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as f64,
+    )
+    .or_else(|e| cx.throw_range_error(e.to_string()))
+}
+
+fn to_js_chat_message_id<'a>(
+    cx: &mut FunctionContext<'a>,
+    id: group_call::ChatMessageId,
+) -> JsResult<'a, JsObject> {
+    let js_id = cx.empty_object();
+    let js_demux_id = cx.number(id.demux_id);
+    js_id.set(cx, "demuxId", js_demux_id)?;
+    let js_seqnum = cx.number(id.seqnum as f64);
+    js_id.set(cx, "seqnum", js_seqnum)?;
+    Ok(js_id)
+}
+
+fn to_js_call_link_schedule<'a>(
+    cx: &mut FunctionContext<'a>,
+    schedule: &CallLinkSchedule,
+) -> JsResult<'a, JsObject> {
+    let schedule_object = cx.empty_object();
+    let js_start = to_js_date(cx, schedule.start)?;
+    schedule_object.set(cx, "start", js_start)?;
+    let js_end = to_js_date(cx, schedule.end())?;
+    schedule_object.set(cx, "end", js_end)?;
+    let js_invitees = JsArray::new(cx, schedule.invitees.len());
+    for (i, invitee) in schedule.invitees.iter().enumerate() {
+        let js_invitee = to_js_buffer(cx, invitee);
+        js_invitees.set(cx, i as u32, js_invitee)?;
+    }
+    schedule_object.set(cx, "invitees", js_invitees)?;
+    Ok(schedule_object)
+}
+
+fn from_js_call_link_schedule(
+    cx: &mut FunctionContext,
+    js_schedule: Handle<JsObject>,
+) -> NeonResult<CallLinkSchedule> {
+    let start_millis = js_schedule.get::<JsDate, _, _>(cx, "start")?.value(cx);
+    let end_millis = js_schedule.get::<JsDate, _, _>(cx, "end")?.value(cx);
+    if start_millis < 0.0 || end_millis <= start_millis {
+        return cx.throw_range_error("a call link's schedule must end after it starts");
+    }
+    let js_invitees = js_schedule.get::<JsArray, _, _>(cx, "invitees")?;
+    let mut invitees = Vec::with_capacity(js_invitees.len(cx) as usize);
+    for i in 0..js_invitees.len(cx) {
+        let js_invitee = js_invitees.get::<JsBuffer, _, _>(cx, i)?;
+        invitees.push(js_invitee.as_slice(cx).to_vec());
+    }
+    let start = SystemTime::UNIX_EPOCH + Duration::from_millis(start_millis as u64);
+    Ok(CallLinkSchedule {
+        start,
+        duration: Duration::from_millis((end_millis - start_millis) as u64),
+        invitees,
+    })
+}
+
 fn to_js_call_link_state<'a>(
     cx: &mut FunctionContext<'a>,
     state: Option<&CallLinkState>,
@@ -652,16 +715,12 @@ fn to_js_call_link_state<'a>(
                 CallLinkRestrictions::Unknown => -1,
             });
             state_object.set(cx, "rawRestrictions", js_restrictions)?;
-            let js_expiration = cx
-                .date(
-                    state
-                        .expiration
-                        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_millis() as f64,
-                )
-                .or_else(|e| cx.throw_range_error(e.to_string()))?;
+            let js_expiration = to_js_date(cx, state.expiration)?;
             state_object.set(cx, "expiration", js_expiration)?;
+            if let Some(schedule) = &state.schedule {
+                let js_schedule = to_js_call_link_schedule(cx, schedule)?;
+                state_object.set(cx, "schedule", js_schedule)?;
+            }
             Ok(state_object.upcast())
         }
         None => Ok(cx.undefined().upcast()),
@@ -735,6 +794,34 @@ fn setSelfUuid(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn getPersistedState(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.getPersistedState()");
+
+    let state = with_call_endpoint(&mut cx, |endpoint| endpoint.call_manager.persisted_state())
+        .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    if state.is_empty() {
+        return Ok(cx.null().upcast());
+    }
+    Ok(to_js_buffer(&mut cx, &state.to_bytes()))
+}
+
+#[allow(non_snake_case)]
+fn restorePersistedState(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.restorePersistedState()");
+
+    let state = cx.argument::<JsBuffer>(0)?;
+    let state = state.as_slice(&cx).to_vec();
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        let state = PersistedState::from_bytes(&state)?;
+        endpoint.call_manager.restore_persisted_state(state)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn createOutgoingCall(mut cx: FunctionContext) -> JsResult<JsValue> {
     let peer_id = cx.argument::<JsString>(0)?.value(&mut cx) as PeerId;
@@ -766,6 +853,39 @@ fn createOutgoingCall(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(create_id_arg(&mut cx, call_id.as_u64()))
 }
 
+#[allow(non_snake_case)]
+fn pullCall(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let peer_id = cx.argument::<JsString>(0)?.value(&mut cx) as PeerId;
+    let replaced_call_id = CallId::new(get_id_arg(&mut cx, 1));
+    let video_enabled = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let local_device_id = cx.argument::<JsNumber>(3)?.value(&mut cx) as DeviceId;
+
+    let media_type = if video_enabled {
+        CallMediaType::Video
+    } else {
+        CallMediaType::Audio
+    };
+
+    debug!(
+        "JsCallManager.pullCall({}, {}, {}, {})",
+        peer_id, replaced_call_id, media_type, local_device_id
+    );
+
+    let call_id = CallId::random();
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.pull_call(
+            peer_id,
+            call_id,
+            replaced_call_id,
+            media_type,
+            local_device_id,
+        )?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(create_id_arg(&mut cx, call_id.as_u64()))
+}
+
 #[allow(non_snake_case)]
 fn cancelGroupRing(mut cx: FunctionContext) -> JsResult<JsValue> {
     debug!("JsCallManager.cancelGroupRing()");
@@ -802,6 +922,68 @@ fn cancelGroupRing(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn acknowledgeGroupRing(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.acknowledgeGroupRing()");
+
+    let group_id = cx.argument::<JsBuffer>(0)?;
+    let group_id = group_id.as_slice(&cx).to_vec();
+    let ring_id = cx
+        .argument::<JsString>(1)?
+        .value(&mut cx)
+        .parse::<i64>()
+        .or_else(|_| cx.throw_error("invalid serial number"))?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .acknowledge_group_ring(group_id, ring_id.into())?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn ringScheduledCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.ringScheduledCallLink()");
+
+    let root_key_bytes = cx.argument::<JsBuffer>(0)?;
+    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
+        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let js_schedule = cx.argument::<JsObject>(1)?;
+    let schedule = from_js_call_link_schedule(&mut cx, js_schedule)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .ring_scheduled_call_link(&root_key, &schedule, SystemTime::now())?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn cancelCallLinkRing(mut cx: FunctionContext) -> JsResult<JsValue> {
+    debug!("JsCallManager.cancelCallLinkRing()");
+
+    let root_key_bytes = cx.argument::<JsBuffer>(0)?;
+    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
+        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let js_schedule = cx.argument::<JsObject>(1)?;
+    let schedule = from_js_call_link_schedule(&mut cx, js_schedule)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .cancel_call_link_ring(&root_key, &schedule)?;
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn proceed(mut cx: FunctionContext) -> JsResult<JsValue> {
     let call_id = CallId::new(get_id_arg(&mut cx, 0));
@@ -1499,6 +1681,22 @@ fn join(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn joinByHandoff(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let from_demux_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as DemuxId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.outgoing_video_track.set_content_hint(false);
+        endpoint
+            .call_manager
+            .join_by_handoff(client_id, from_demux_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn leave(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1630,6 +1828,144 @@ fn groupRing(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn groupRingAgain(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let js_recipients = cx.argument::<JsArray>(1)?;
+
+    let mut recipients = Vec::with_capacity(js_recipients.len(&mut cx) as usize);
+    for i in 0..js_recipients.len(&mut cx) {
+        let js_recipient = js_recipients.get::<JsBuffer, _, _>(&mut cx, i)?;
+        recipients.push(js_recipient.as_slice(&cx).to_vec());
+    }
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .group_ring_again(client_id, recipients);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn groupRingDelivered(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let recipient = cx.argument::<JsBuffer>(1)?.as_slice(&cx).to_vec();
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .group_ring_delivered(client_id, recipient);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn sendLobbyMessage(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let message = cx.argument::<JsString>(1)?.value(&mut cx);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.send_lobby_message(client_id, message);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn sendChatMessage(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let text = cx.argument::<JsString>(1)?.value(&mut cx);
+    let reply_to = cx.argument::<JsValue>(2)?;
+    let reply_to = if reply_to.is_a::<JsUndefined, _>(&mut cx) {
+        None
+    } else {
+        let reply_to = reply_to.downcast_or_throw::<JsObject, _>(&mut cx)?;
+        let demux_id = reply_to
+            .get::<JsNumber, _, _>(&mut cx, "demuxId")?
+            .value(&mut cx) as DemuxId;
+        let seqnum = reply_to
+            .get::<JsNumber, _, _>(&mut cx, "seqnum")?
+            .value(&mut cx) as u64;
+        Some(group_call::ChatMessageId { demux_id, seqnum })
+    };
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .send_chat_message(client_id, text, reply_to);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn createPoll(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let question = cx.argument::<JsString>(1)?.value(&mut cx);
+    let js_options = cx.argument::<JsArray>(2)?;
+
+    let mut options = Vec::with_capacity(js_options.len(&mut cx) as usize);
+    for i in 0..js_options.len(&mut cx) {
+        let option = js_options.get::<JsString, _, _>(&mut cx, i)?.value(&mut cx);
+        options.push(option);
+    }
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .create_poll(client_id, question, options);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn voteInPoll(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let poll_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as group_call::PollId;
+    // undefined withdraws the vote.
+    let option_index = cx.argument::<JsValue>(2)?;
+    let option_index = if option_index.is_a::<JsUndefined, _>(&mut cx) {
+        None
+    } else {
+        Some(
+            option_index
+                .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                .value(&mut cx) as u32,
+        )
+    };
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .vote_in_poll(client_id, poll_id, option_index);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn closePoll(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let poll_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as group_call::PollId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.close_poll(client_id, poll_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn groupReact(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1728,6 +2064,81 @@ fn requestVideo(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn setSpotlight(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let js_demux_ids = cx.argument::<JsArray>(1)?;
+
+    let mut demux_ids = Vec::with_capacity(js_demux_ids.len(&mut cx) as usize);
+    for i in 0..js_demux_ids.len(&mut cx) {
+        let demux_id = js_demux_ids
+            .get::<JsNumber, _, _>(&mut cx, i)?
+            .value(&mut cx);
+        demux_ids.push(demux_id as DemuxId);
+    }
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.set_spotlight(client_id, demux_ids);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn setViewport(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let viewport_or_null = cx.argument::<JsValue>(1)?;
+    let viewport = match viewport_or_null.downcast::<JsNull, _>(&mut cx) {
+        Ok(_) => None,
+        Err(_) => {
+            let js_viewport = viewport_or_null.downcast_or_throw::<JsObject, _>(&mut cx)?;
+
+            let mode = js_viewport
+                .get::<JsNumber, _, _>(&mut cx, "mode")?
+                .value(&mut cx);
+            let mode = group_call::ViewportMode::try_from(mode as i32)
+                .or_else(|err| cx.throw_error(err.to_string()))?;
+
+            let js_tiles = js_viewport.get::<JsArray, _, _>(&mut cx, "tiles")?;
+            let mut tiles = Vec::with_capacity(js_tiles.len(&mut cx) as usize);
+            for i in 0..js_tiles.len(&mut cx) {
+                let js_tile = js_tiles.get::<JsObject, _, _>(&mut cx, i)?;
+                let width = js_tile
+                    .get::<JsNumber, _, _>(&mut cx, "width")?
+                    .value(&mut cx);
+                let height = js_tile
+                    .get::<JsNumber, _, _>(&mut cx, "height")?
+                    .value(&mut cx);
+                tiles.push(group_call::TileSize {
+                    width: width as u16,
+                    height: height as u16,
+                });
+            }
+
+            let js_pinned = js_viewport.get::<JsArray, _, _>(&mut cx, "pinned")?;
+            let mut pinned = Vec::with_capacity(js_pinned.len(&mut cx) as usize);
+            for i in 0..js_pinned.len(&mut cx) {
+                let demux_id = js_pinned.get::<JsNumber, _, _>(&mut cx, i)?.value(&mut cx);
+                pinned.push(demux_id as DemuxId);
+            }
+
+            Some(group_call::Viewport {
+                mode,
+                tiles,
+                pinned,
+            })
+        }
+    };
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.set_viewport(client_id, viewport);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn approveUser(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1784,6 +2195,62 @@ fn blockClient(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn muteClient(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let other_demux_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as DemuxId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.mute_client(client_id, other_demux_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn askClientToUnmute(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let other_demux_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as DemuxId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .ask_client_to_unmute(client_id, other_demux_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn lowerHand(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let other_demux_id = cx.argument::<JsNumber>(1)?.value(&mut cx) as DemuxId;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint.call_manager.lower_hand(client_id, other_demux_id);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
+#[allow(non_snake_case)]
+fn callNextSpeaker(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
+    let ask_to_unmute = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        endpoint
+            .call_manager
+            .call_next_speaker(client_id, ask_to_unmute);
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn setGroupMembers(mut cx: FunctionContext) -> JsResult<JsValue> {
     let client_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as group_call::ClientId;
@@ -1995,6 +2462,45 @@ fn createCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(cx.undefined().upcast())
 }
 
+#[allow(non_snake_case)]
+fn createBreakoutRoomLink(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let request_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let sfu_url = cx.argument::<JsString>(1)?.value(&mut cx);
+    let create_presentation = cx.argument::<JsBuffer>(2)?;
+    let create_presentation = create_presentation.as_slice(&cx).to_vec();
+    let root_key_bytes = cx.argument::<JsBuffer>(3)?;
+    let root_key = CallLinkRootKey::try_from(root_key_bytes.as_slice(&cx))
+        .or_else(|e| cx.throw_type_error(e.to_string()))?;
+    let room_index = cx.argument::<JsNumber>(4)?.value(&mut cx) as u32;
+    let admin_passkey = cx.argument::<JsBuffer>(5)?;
+    let admin_passkey = admin_passkey.as_slice(&cx).to_vec();
+    let public_zkparams = cx.argument::<JsBuffer>(6)?;
+    let public_zkparams = public_zkparams.as_slice(&cx).to_vec();
+    let restrictions = cx.argument::<JsValue>(7)?;
+    let restrictions = jsvalue_to_restrictions(restrictions, &mut cx)?;
+
+    with_call_endpoint(&mut cx, |endpoint| {
+        let event_reporter = endpoint.event_reporter.clone();
+        call_links::create_breakout_room_link(
+            endpoint.call_manager.http_client(),
+            &sfu_url,
+            &root_key,
+            room_index,
+            &create_presentation,
+            &admin_passkey,
+            &public_zkparams,
+            restrictions,
+            Box::new(move |result| {
+                // Ignore errors, that can only mean we're shutting down.
+                let _ = event_reporter.send(Event::CallLinkResponse { request_id, result });
+            }),
+        );
+        Ok(())
+    })
+    .or_else(|err: anyhow::Error| cx.throw_error(format!("{}", err)))?;
+    Ok(cx.undefined().upcast())
+}
+
 #[allow(non_snake_case)]
 fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
     let request_id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
@@ -2035,6 +2541,20 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
         )
     };
 
+    // undefined leaves the schedule unchanged, and null removes it.
+    let new_schedule = cx.argument::<JsValue>(8)?;
+    let new_schedule = if new_schedule.is_a::<JsUndefined, _>(&mut cx) {
+        None
+    } else if new_schedule.is_a::<JsNull, _>(&mut cx) {
+        Some(vec![])
+    } else {
+        let new_schedule = new_schedule.downcast_or_throw::<JsObject, _>(&mut cx)?;
+        Some(
+            from_js_call_link_schedule(&mut cx, new_schedule)?
+                .encrypt(&root_key, rand::rngs::OsRng),
+        )
+    };
+
     with_call_endpoint(&mut cx, |endpoint| {
         let event_reporter = endpoint.event_reporter.clone();
         call_links::update_call_link(
@@ -2047,6 +2567,7 @@ fn updateCallLink(mut cx: FunctionContext) -> JsResult<JsValue> {
                 encrypted_name: new_name.as_deref(),
                 restrictions: new_restrictions,
                 revoked: new_revoked,
+                encrypted_schedule: new_schedule.as_deref(),
             },
             Box::new(move |result| {
                 // Ignore errors, that can only mean we're shutting down.
@@ -2334,7 +2855,7 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                     EndReason::ConnectionFailure => "ConnectionFailure",
                     EndReason::InternalFailure => "InternalFailure",
                     EndReason::Timeout => "Timeout",
-                    EndReason::AcceptedOnAnotherDevice => "AcceptedOnAnotherDevice",
+                    EndReason::AcceptedOnAnotherDevice { .. } => "AcceptedOnAnotherDevice",
                     EndReason::DeclinedOnAnotherDevice => "DeclinedOnAnotherDevice",
                     EndReason::BusyOnAnotherDevice => "BusyOnAnotherDevice",
                 };
@@ -2342,11 +2863,18 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                     EndReason::ReceivedOfferExpired { age } => age,
                     _ => Duration::ZERO,
                 };
+                let accepted_on_device_id = match reason {
+                    EndReason::AcceptedOnAnotherDevice {
+                        device_id: Some(device_id),
+                    } => cx.number(device_id).upcast(),
+                    _ => cx.null().upcast(),
+                };
                 let args = [
                     cx.string(peer_id).upcast(),
                     create_id_arg(&mut cx, call_id.as_u64()),
                     cx.string(reason_string).upcast(),
                     cx.number(age.as_secs_f64()).upcast(),
+                    accepted_on_device_id,
                 ];
                 let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
                 method.call(&mut cx, observer, args)?;
@@ -2582,30 +3110,10 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
 
                 let js_request_id = cx.number(request_id);
                 let (status, state_object) = match result {
-                    Ok(state) => {
-                        let state_object = cx.empty_object();
-                        let js_name = cx.string(state.name);
-                        state_object.set(&mut cx, "name", js_name)?;
-                        let js_revoked = cx.boolean(state.revoked);
-                        state_object.set(&mut cx, "revoked", js_revoked)?;
-                        let js_restrictions = cx.number(match state.restrictions {
-                            call_links::CallLinkRestrictions::None => 0,
-                            call_links::CallLinkRestrictions::AdminApproval => 1,
-                            call_links::CallLinkRestrictions::Unknown => -1,
-                        });
-                        state_object.set(&mut cx, "rawRestrictions", js_restrictions)?;
-                        let js_expiration = cx
-                            .date(
-                                state
-                                    .expiration
-                                    .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_millis() as f64,
-                            )
-                            .or_else(|e| cx.throw_range_error(e.to_string()))?;
-                        state_object.set(&mut cx, "expiration", js_expiration)?;
-                        (cx.number(200), state_object.upcast())
-                    }
+                    Ok(state) => (
+                        cx.number(200),
+                        to_js_call_link_state(&mut cx, Some(&state))?,
+                    ),
                     Err(status_code) => (cx.number(status_code.code), cx.undefined().upcast()),
                 };
 
@@ -2779,6 +3287,23 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
                 method.call(&mut cx, observer, args)?;
             }
+            Event::GroupUpdate(GroupUpdate::CallLinkRing {
+                root_key,
+                ring_id,
+                sender_id,
+                update,
+            }) => {
+                let method_name = "callLinkRingUpdate";
+
+                let args = [
+                    to_js_buffer(&mut cx, &root_key.bytes()).upcast::<JsValue>(),
+                    JsBigInt::from_i64(&mut cx, ring_id.into()).upcast(),
+                    to_js_buffer(&mut cx, &sender_id).upcast(),
+                    cx.number(update as i32).upcast(),
+                ];
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
 
             Event::GroupUpdate(GroupUpdate::AudioLevels(
                 client_id,
@@ -2853,6 +3378,276 @@ fn processEvents(mut cx: FunctionContext) -> JsResult<JsValue> {
                 method.call(&mut cx, observer, args)?;
             }
 
+            Event::GroupUpdate(GroupUpdate::SpeakerQueueChanged(client_id, queue)) => {
+                let js_queue = cx.empty_object();
+                let js_raised_hands = JsArray::new(&mut cx, queue.raised_hands.len());
+                for (i, raised_hand) in queue.raised_hands.into_iter().enumerate() {
+                    let js_demux_id = cx.number(raised_hand);
+                    js_raised_hands.set(&mut cx, i as u32, js_demux_id)?;
+                }
+                js_queue.set(&mut cx, "raisedHands", js_raised_hands)?;
+                if let Some(current_speaker) = queue.current_speaker {
+                    let js_current_speaker = cx.number(current_speaker);
+                    js_queue.set(&mut cx, "currentSpeaker", js_current_speaker)?;
+                }
+
+                let method_name = "handleSpeakerQueueChanged";
+                let args = [cx.number(client_id).upcast(), js_queue.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::ForwardedVideoLayersChanged(client_id, layers)) => {
+                let js_layers = cx.empty_object();
+                let js_outgoing_layer_count = cx.number(layers.outgoing_layer_count as u32);
+                js_layers.set(&mut cx, "outgoingLayerCount", js_outgoing_layer_count)?;
+                let js_incoming_heights = JsArray::new(&mut cx, layers.incoming_heights.len());
+                for (i, (demux_id, height)) in layers.incoming_heights.into_iter().enumerate() {
+                    let js_incoming_height = cx.empty_object();
+                    let js_demux_id = cx.number(demux_id);
+                    js_incoming_height.set(&mut cx, "demuxId", js_demux_id)?;
+                    let js_height = cx.number(height);
+                    js_incoming_height.set(&mut cx, "height", js_height)?;
+                    js_incoming_heights.set(&mut cx, i as u32, js_incoming_height)?;
+                }
+                js_layers.set(&mut cx, "incomingHeights", js_incoming_heights)?;
+
+                let method_name = "handleForwardedVideoLayersChanged";
+                let args = [cx.number(client_id).upcast(), js_layers.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::SpotlightChanged(client_id, spotlight)) => {
+                let js_spotlight = JsArray::new(&mut cx, spotlight.len());
+                for (i, demux_id) in spotlight.into_iter().enumerate() {
+                    let js_demux_id = cx.number(demux_id);
+                    js_spotlight.set(&mut cx, i as u32, js_demux_id)?;
+                }
+
+                let method_name = "handleSpotlightChanged";
+                let args = [cx.number(client_id).upcast(), js_spotlight.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::ChatMessage(client_id, message)) => {
+                let js_message = cx.empty_object();
+                let js_id = to_js_chat_message_id(&mut cx, message.id)?;
+                js_message.set(&mut cx, "id", js_id)?;
+                let js_text = cx.string(message.text);
+                js_message.set(&mut cx, "text", js_text)?;
+                if let Some(reply_to) = message.reply_to {
+                    let js_reply_to = to_js_chat_message_id(&mut cx, reply_to)?;
+                    js_message.set(&mut cx, "replyTo", js_reply_to)?;
+                }
+
+                let method_name = "handleChatMessage";
+                let args = [cx.number(client_id).upcast(), js_message.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::PollsChanged(client_id, polls)) => {
+                let js_polls = JsArray::new(&mut cx, polls.len());
+                for (i, poll) in polls.into_iter().enumerate() {
+                    let js_poll = cx.empty_object();
+                    let js_id = cx.number(poll.id);
+                    js_poll.set(&mut cx, "id", js_id)?;
+                    let js_creator = cx.number(poll.creator);
+                    js_poll.set(&mut cx, "creator", js_creator)?;
+                    let js_question = cx.string(poll.question);
+                    js_poll.set(&mut cx, "question", js_question)?;
+
+                    let js_options = JsArray::new(&mut cx, poll.options.len());
+                    for (j, option) in poll.options.into_iter().enumerate() {
+                        let js_option = cx.empty_object();
+                        let js_text = cx.string(option.text);
+                        js_option.set(&mut cx, "text", js_text)?;
+                        let js_votes = cx.number(option.votes);
+                        js_option.set(&mut cx, "votes", js_votes)?;
+                        js_options.set(&mut cx, j as u32, js_option)?;
+                    }
+                    js_poll.set(&mut cx, "options", js_options)?;
+
+                    if let Some(own_vote) = poll.own_vote {
+                        let js_own_vote = cx.number(own_vote as u32);
+                        js_poll.set(&mut cx, "ownVote", js_own_vote)?;
+                    }
+                    let js_closed = cx.boolean(poll.closed);
+                    js_poll.set(&mut cx, "closed", js_closed)?;
+                    js_polls.set(&mut cx, i as u32, js_poll)?;
+                }
+
+                let method_name = "handlePollsChanged";
+                let args = [cx.number(client_id).upcast(), js_polls.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::LobbyChanged(client_id, lobby)) => {
+                let js_lobby = cx.empty_object();
+                let js_participant_count = cx.number(lobby.participant_count as u32);
+                js_lobby.set(&mut cx, "participantCount", js_participant_count)?;
+
+                let js_admins = JsArray::new(&mut cx, lobby.admins.len());
+                for (i, user_id) in lobby.admins.iter().enumerate() {
+                    let js_user_id = to_js_buffer(&mut cx, user_id);
+                    js_admins.set(&mut cx, i as u32, js_user_id)?;
+                }
+                js_lobby.set(&mut cx, "admins", js_admins)?;
+
+                if let Some(position) = lobby.position {
+                    let js_position = cx.number(position as u32);
+                    js_lobby.set(&mut cx, "position", js_position)?;
+                }
+                if let Some(message) = lobby.message {
+                    let js_message = cx.string(message);
+                    js_lobby.set(&mut cx, "message", js_message)?;
+                }
+
+                let method_name = "handleLobbyChanged";
+                let args = [cx.number(client_id).upcast(), js_lobby.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::BreakoutRoomsChanged(client_id, breakout_rooms)) => {
+                let js_breakout_rooms = match breakout_rooms {
+                    None => cx.undefined().upcast::<JsValue>(),
+                    Some(breakout_rooms) => {
+                        let js_breakout_rooms = cx.empty_object();
+                        let js_room_count = cx.number(breakout_rooms.room_count);
+                        js_breakout_rooms.set(&mut cx, "roomCount", js_room_count)?;
+
+                        let js_assignments =
+                            JsArray::new(&mut cx, breakout_rooms.assignments.len());
+                        for (i, (demux_id, room_index)) in
+                            breakout_rooms.assignments.into_iter().enumerate()
+                        {
+                            let js_assignment = cx.empty_object();
+                            let js_demux_id = cx.number(demux_id);
+                            js_assignment.set(&mut cx, "demuxId", js_demux_id)?;
+                            let js_room_index = cx.number(room_index);
+                            js_assignment.set(&mut cx, "roomIndex", js_room_index)?;
+                            js_assignments.set(&mut cx, i as u32, js_assignment)?;
+                        }
+                        js_breakout_rooms.set(&mut cx, "assignments", js_assignments)?;
+
+                        if let Some(return_at) = breakout_rooms.return_at {
+                            let js_return_at = to_js_date(&mut cx, return_at)?;
+                            js_breakout_rooms.set(&mut cx, "returnAt", js_return_at)?;
+                        }
+                        js_breakout_rooms.upcast()
+                    }
+                };
+
+                let method_name = "handleBreakoutRoomsChanged";
+                let args = [cx.number(client_id).upcast(), js_breakout_rooms];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::MutedByAdmin(client_id, admin_demux_id)) => {
+                let method_name = "handleMutedByAdmin";
+                let args = [
+                    cx.number(client_id).upcast(),
+                    cx.number(admin_demux_id).upcast(),
+                ];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::UnmuteRequested(client_id, admin_demux_id)) => {
+                let method_name = "handleUnmuteRequested";
+                let args = [
+                    cx.number(client_id).upcast(),
+                    cx.number(admin_demux_id).upcast(),
+                ];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::VideoLayoutChanged(client_id, tiles)) => {
+                let js_tiles = JsArray::new(&mut cx, tiles.len());
+                for (i, tile) in tiles.into_iter().enumerate() {
+                    let js_demux_id: Handle<JsValue> = match tile {
+                        Some(demux_id) => cx.number(demux_id).upcast(),
+                        None => cx.undefined().upcast(),
+                    };
+                    js_tiles.set(&mut cx, i as u32, js_demux_id)?;
+                }
+
+                let method_name = "handleVideoLayoutChanged";
+                let args = [cx.number(client_id).upcast(), js_tiles.upcast()];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::RingRecipientsChanged {
+                client_id,
+                ring_id,
+                recipients,
+            }) => {
+                let js_recipients = JsArray::new(&mut cx, recipients.len());
+                for (i, (user_id, status)) in recipients.into_iter().enumerate() {
+                    let js_recipient = JsObject::new(&mut cx);
+                    let js_user_id = to_js_buffer(&mut cx, &user_id);
+                    js_recipient.set(&mut cx, "userId", js_user_id)?;
+                    let js_status = cx.number(status as i32);
+                    js_recipient.set(&mut cx, "status", js_status)?;
+                    js_recipients.set(&mut cx, i as u32, js_recipient)?;
+                }
+
+                let method_name = "handleRingRecipientsChanged";
+                let args = [
+                    cx.number(client_id).upcast::<JsValue>(),
+                    JsBigInt::from_i64(&mut cx, ring_id.into()).upcast(),
+                    js_recipients.upcast(),
+                ];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::HandoffStateReceived(client_id, state)) => {
+                let audio_muted: neon::handle::Handle<JsValue> = match state.audio_muted {
+                    None => cx.undefined().upcast(),
+                    Some(muted) => cx.boolean(muted).upcast(),
+                };
+                let video_muted: neon::handle::Handle<JsValue> = match state.video_muted {
+                    None => cx.undefined().upcast(),
+                    Some(muted) => cx.boolean(muted).upcast(),
+                };
+
+                let method_name = "handleHandoffStateReceived";
+                let args = [cx.number(client_id).upcast(), audio_muted, video_muted];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
+            Event::GroupUpdate(GroupUpdate::HandedOff(client_id, new_demux_id)) => {
+                let method_name = "handleHandedOff";
+                let args = [
+                    cx.number(client_id).upcast(),
+                    cx.number(new_demux_id).upcast(),
+                ];
+
+                let method = observer.get::<JsFunction, _, _>(&mut cx, method_name)?;
+                method.call(&mut cx, observer, args)?;
+            }
+
             Event::GroupUpdate(GroupUpdate::RtcStatsReportComplete { report_json }) => {
                 let method_name = "handleRtcStatsReportComplete";
                 let args = [cx.string(report_json).upcast()];
@@ -2932,6 +3727,21 @@ fn CallLinkRootKey_deriveRoomId(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     }
 }
 
+#[allow(non_snake_case)]
+fn CallLinkRootKey_deriveBreakoutRoomKey(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let bytes = cx.argument::<JsBuffer>(0)?;
+    let room_index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    match CallLinkRootKey::try_from(bytes.as_slice(&cx)) {
+        Ok(key) => {
+            let room_key = key.derive_breakout_room_key(room_index).bytes();
+            let mut buffer = cx.buffer(room_key.len())?;
+            buffer.as_mut_slice(&mut cx).copy_from_slice(&room_key);
+            Ok(buffer)
+        }
+        Err(e) => cx.throw_error(e.to_string()),
+    }
+}
+
 #[allow(non_snake_case)]
 fn CallLinkRootKey_toFormattedString(mut cx: FunctionContext) -> JsResult<JsString> {
     let bytes = cx.argument::<JsBuffer>(0)?;
@@ -2957,6 +3767,10 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
         CallLinkRootKey_generateAdminPasskey,
     )?;
     cx.export_function("CallLinkRootKey_deriveRoomId", CallLinkRootKey_deriveRoomId)?;
+    cx.export_function(
+        "CallLinkRootKey_deriveBreakoutRoomKey",
+        CallLinkRootKey_deriveBreakoutRoomKey,
+    )?;
     cx.export_function(
         "CallLinkRootKey_toFormattedString",
         CallLinkRootKey_toFormattedString,
@@ -2966,8 +3780,14 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_value("callEndpointPropertyKey", js_property_key)?;
 
     cx.export_function("cm_setSelfUuid", setSelfUuid)?;
+    cx.export_function("cm_getPersistedState", getPersistedState)?;
+    cx.export_function("cm_restorePersistedState", restorePersistedState)?;
     cx.export_function("cm_createOutgoingCall", createOutgoingCall)?;
+    cx.export_function("cm_pullCall", pullCall)?;
     cx.export_function("cm_cancelGroupRing", cancelGroupRing)?;
+    cx.export_function("cm_acknowledgeGroupRing", acknowledgeGroupRing)?;
+    cx.export_function("cm_ringScheduledCallLink", ringScheduledCallLink)?;
+    cx.export_function("cm_cancelCallLinkRing", cancelCallLinkRing)?;
     cx.export_function("cm_proceed", proceed)?;
     cx.export_function("cm_accept", accept)?;
     cx.export_function("cm_ignore", ignore)?;
@@ -2997,6 +3817,7 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("cm_deleteGroupCallClient", deleteGroupCallClient)?;
     cx.export_function("cm_connect", connect)?;
     cx.export_function("cm_join", join)?;
+    cx.export_function("cm_joinByHandoff", joinByHandoff)?;
     cx.export_function("cm_leave", leave)?;
     cx.export_function("cm_disconnect", disconnect)?;
     cx.export_function("cm_setOutgoingAudioMuted", setOutgoingAudioMuted)?;
@@ -3007,21 +3828,35 @@ fn register(mut cx: ModuleContext) -> NeonResult<()> {
         setOutgoingGroupCallVideoIsScreenShare,
     )?;
     cx.export_function("cm_groupRing", groupRing)?;
+    cx.export_function("cm_groupRingAgain", groupRingAgain)?;
+    cx.export_function("cm_groupRingDelivered", groupRingDelivered)?;
     cx.export_function("cm_groupReact", groupReact)?;
+    cx.export_function("cm_sendChatMessage", sendChatMessage)?;
+    cx.export_function("cm_createPoll", createPoll)?;
+    cx.export_function("cm_voteInPoll", voteInPoll)?;
+    cx.export_function("cm_closePoll", closePoll)?;
+    cx.export_function("cm_sendLobbyMessage", sendLobbyMessage)?;
     cx.export_function("cm_groupRaiseHand", groupRaiseHand)?;
     cx.export_function("cm_resendMediaKeys", resendMediaKeys)?;
     cx.export_function("cm_setDataMode", setDataMode)?;
     cx.export_function("cm_requestVideo", requestVideo)?;
+    cx.export_function("cm_setViewport", setViewport)?;
+    cx.export_function("cm_setSpotlight", setSpotlight)?;
     cx.export_function("cm_approveUser", approveUser)?;
     cx.export_function("cm_denyUser", denyUser)?;
     cx.export_function("cm_removeClient", removeClient)?;
     cx.export_function("cm_blockClient", blockClient)?;
+    cx.export_function("cm_muteClient", muteClient)?;
+    cx.export_function("cm_askClientToUnmute", askClientToUnmute)?;
+    cx.export_function("cm_lowerHand", lowerHand)?;
+    cx.export_function("cm_callNextSpeaker", callNextSpeaker)?;
     cx.export_function("cm_setGroupMembers", setGroupMembers)?;
     cx.export_function("cm_setMembershipProof", setMembershipProof)?;
     cx.export_function("cm_peekGroupCall", peekGroupCall)?;
     cx.export_function("cm_peekCallLinkCall", peekCallLinkCall)?;
     cx.export_function("cm_readCallLink", readCallLink)?;
     cx.export_function("cm_createCallLink", createCallLink)?;
+    cx.export_function("cm_createBreakoutRoomLink", createBreakoutRoomLink)?;
     cx.export_function("cm_updateCallLink", updateCallLink)?;
     cx.export_function("cm_deleteCallLink", deleteCallLink)?;
     cx.export_function("cm_getAudioInputs", getAudioInputs)?;
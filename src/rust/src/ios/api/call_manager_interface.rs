@@ -13,7 +13,11 @@ use crate::{
     common::{CallConfig, CallMediaType, DataMode, DeviceId},
     core::{group_call, signaling},
     ios::{call_manager, call_manager::IosCallManager},
-    lite::{call_links::CallLinkRootKey, http, sfu, sfu::DemuxId},
+    lite::{
+        call_links::{CallLinkRootKey, CallLinkSchedule},
+        http, sfu,
+        sfu::DemuxId,
+    },
     webrtc::{self, media, peer_connection::AudioLevel, peer_connection_factory as pcf},
 };
 
@@ -284,6 +288,125 @@ pub struct AppRaisedHandsArray {
     pub count: size_t,
 }
 
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppSpeakerQueue {
+    pub raisedHands: AppRaisedHandsArray,
+    pub currentSpeaker: AppOptionalUInt32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppIncomingVideoHeight {
+    pub demuxId: DemuxId,
+    pub height: u16,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppForwardedVideoLayers {
+    pub outgoingLayerCount: u32,
+    pub incomingHeights: *const AppIncomingVideoHeight,
+    pub incomingHeightsCount: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppRingRecipient {
+    pub userId: AppByteSlice,
+    pub status: i32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppRingRecipientArray {
+    pub recipients: *const AppRingRecipient,
+    pub count: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppBreakoutRoomAssignment {
+    pub demuxId: DemuxId,
+    pub roomIndex: u32,
+}
+
+/// A `roomCount` of zero means the breakout has ended, and a negative `returnAtEpochMillis`
+/// means there's no countdown.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppBreakoutRooms {
+    pub roomCount: u32,
+    pub assignments: *const AppBreakoutRoomAssignment,
+    pub assignmentsCount: size_t,
+    pub returnAtEpochMillis: i64,
+}
+
+/// Seqnums start at 1, so a zero `seqnum` means there's no message (for `replyTo`).
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppChatMessageId {
+    pub demuxId: DemuxId,
+    pub seqnum: u64,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppChatMessage {
+    pub id: AppChatMessageId,
+    pub text: AppByteSlice,
+    pub replyTo: AppChatMessageId,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppPollOption {
+    pub text: AppByteSlice,
+    pub votes: u32,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppPoll {
+    pub id: group_call::PollId,
+    pub creator: DemuxId,
+    pub question: AppByteSlice,
+    pub options: *const AppPollOption,
+    pub optionsCount: size_t,
+    pub ownVote: AppOptionalUInt32,
+    pub closed: bool,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppPollArray {
+    pub polls: *const AppPoll,
+    pub count: size_t,
+}
+
+/// A null `message` means there's no note from an admin.
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppLobbyInfo {
+    pub participantCount: u32,
+    pub admins: AppUuidArray,
+    pub position: AppOptionalUInt32,
+    pub message: AppByteSlice,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[allow(non_snake_case)]
@@ -326,6 +449,49 @@ pub struct AppVideoRequestArray {
     pub count: size_t,
 }
 
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppStringArray {
+    pub strings: *const AppByteSlice,
+    pub count: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppDemuxIdArray {
+    pub demuxIds: *const DemuxId,
+    pub count: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppTileSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppViewport {
+    pub mode: i32,
+    pub tiles: *const AppTileSize,
+    pub tilesCount: size_t,
+    pub pinned: *const DemuxId,
+    pub pinnedCount: size_t,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct AppVideoLayoutTileArray {
+    pub tiles: *const AppOptionalUInt32,
+    pub count: size_t,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[allow(non_snake_case)]
@@ -354,6 +520,8 @@ pub struct AppInterface {
     ),
     pub onLowBandwidthForVideo:
         extern "C" fn(object: *mut c_void, remote: *const c_void, recovered: bool),
+    pub onAcceptedOnAnotherDevice:
+        extern "C" fn(object: *mut c_void, remote: *const c_void, deviceId: u32),
     pub onSendOffer: extern "C" fn(
         object: *mut c_void,
         callId: u64,
@@ -497,6 +665,59 @@ pub struct AppInterface {
         extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, reason: i32),
     pub handleSpeakingNotification:
         extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, event: i32),
+    pub handleHandoffStateReceived: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        audioMuted: AppOptionalBool,
+        videoMuted: AppOptionalBool,
+    ),
+    pub handleHandedOff:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, newDemuxId: DemuxId),
+    pub callLinkRingUpdate: extern "C" fn(
+        object: *mut c_void,
+        rootKey: AppByteSlice,
+        ringId: i64,
+        senderUuid: AppByteSlice,
+        ringUpdate: i32,
+    ),
+    pub handleRingRecipientsChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        ringId: i64,
+        recipients: AppRingRecipientArray,
+    ),
+    pub handleVideoLayoutChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        tiles: AppVideoLayoutTileArray,
+    ),
+    pub handleSpotlightChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        spotlight: AppDemuxIdArray,
+    ),
+    pub handleMutedByAdmin:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, adminDemuxId: DemuxId),
+    pub handleUnmuteRequested:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, adminDemuxId: DemuxId),
+    pub handleBreakoutRoomsChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        breakoutRooms: AppBreakoutRooms,
+    ),
+    pub handleLobbyChanged:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, lobby: AppLobbyInfo),
+    pub handleChatMessage:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, message: AppChatMessage),
+    pub handlePollsChanged:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, polls: AppPollArray),
+    pub handleSpeakerQueueChanged:
+        extern "C" fn(object: *mut c_void, clientId: group_call::ClientId, queue: AppSpeakerQueue),
+    pub handleForwardedVideoLayersChanged: extern "C" fn(
+        object: *mut c_void,
+        clientId: group_call::ClientId,
+        layers: AppForwardedVideoLayers,
+    ),
 }
 
 // Add an empty Send trait to allow transfer of ownership between threads.
@@ -553,6 +774,66 @@ pub extern "C" fn ringrtcSetSelfUuid(callManager: *mut c_void, uuid: AppByteSlic
     }
 }
 
+/// Calls `onPersistedState` with the state to save across app restarts, or with a null slice
+/// if there's nothing worth saving. Returns false if the state couldn't be taken.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGetPersistedState(
+    callManager: *mut c_void,
+    callbackContext: *mut c_void,
+    onPersistedState: extern "C" fn(AppByteSlice, *mut c_void),
+) -> bool {
+    match call_manager::persisted_state(callManager as *mut IosCallManager) {
+        Ok(state) if state.is_empty() => {
+            onPersistedState(
+                AppByteSlice {
+                    bytes: ptr::null(),
+                    len: 0,
+                },
+                callbackContext,
+            );
+            true
+        }
+        Ok(state) => {
+            let bytes = state.to_bytes();
+            onPersistedState(
+                AppByteSlice {
+                    bytes: bytes.as_ptr(),
+                    len: bytes.len(),
+                },
+                callbackContext,
+            );
+            true
+        }
+        Err(e) => {
+            error!("{:?}", e);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRestorePersistedState(
+    callManager: *mut c_void,
+    state: AppByteSlice,
+) -> *mut c_void {
+    let Some(state) = state.as_slice() else {
+        error!("Missing persisted state");
+        return ptr::null_mut();
+    };
+    match call_manager::restore_persisted_state(callManager as *mut IosCallManager, state) {
+        Ok(_) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(e) => {
+            error!("{:?}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcCall(
@@ -575,6 +856,30 @@ pub extern "C" fn ringrtcCall(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcPullCall(
+    callManager: *mut c_void,
+    appRemote: *const c_void,
+    replacedCallId: u64,
+    callMediaType: i32,
+    appLocalDevice: u32,
+) -> *mut c_void {
+    match call_manager::pull_call(
+        callManager as *mut IosCallManager,
+        appRemote,
+        replacedCallId,
+        CallMediaType::from_i32(callMediaType),
+        appLocalDevice as DeviceId,
+    ) {
+        Ok(_v) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_e) => ptr::null_mut(),
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcProceed(
@@ -682,6 +987,126 @@ pub extern "C" fn ringrtcCancelGroupRing(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcAcknowledgeGroupRing(
+    callManager: *mut c_void,
+    groupId: AppByteSlice,
+    ringId: i64,
+) -> *mut c_void {
+    let groupId = match byte_vec_from_app_slice(&groupId) {
+        Some(groupId) => groupId,
+        None => {
+            error!("Missing groupId");
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::acknowledge_group_ring(
+        callManager as *mut IosCallManager,
+        groupId,
+        ringId.into(),
+    ) {
+        Ok(_) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// `invitees` holds the schedule's invitees as concatenated 16-byte UUIDs.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcRingScheduledCallLink(
+    callManager: *mut c_void,
+    rootKeyBytes: AppByteSlice,
+    startEpochSeconds: u64,
+    durationSeconds: u64,
+    invitees: AppByteSlice,
+) -> *mut c_void {
+    let root_key = match rootKeyBytes
+        .as_slice()
+        .and_then(|bytes| CallLinkRootKey::try_from(bytes).ok())
+    {
+        Some(root_key) => root_key,
+        None => {
+            error!("Invalid rootKey");
+            return ptr::null_mut();
+        }
+    };
+
+    let schedule = match CallLinkSchedule::from_flattened(
+        startEpochSeconds,
+        durationSeconds,
+        invitees.as_slice().unwrap_or_default(),
+    ) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Invalid schedule: {}", e);
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::ring_scheduled_call_link(
+        callManager as *mut IosCallManager,
+        &root_key,
+        &schedule,
+    ) {
+        Ok(_) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// `invitees` holds the schedule's invitees as concatenated 16-byte UUIDs.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcCancelCallLinkRing(
+    callManager: *mut c_void,
+    rootKeyBytes: AppByteSlice,
+    startEpochSeconds: u64,
+    durationSeconds: u64,
+    invitees: AppByteSlice,
+) -> *mut c_void {
+    let root_key = match rootKeyBytes
+        .as_slice()
+        .and_then(|bytes| CallLinkRootKey::try_from(bytes).ok())
+    {
+        Some(root_key) => root_key,
+        None => {
+            error!("Invalid rootKey");
+            return ptr::null_mut();
+        }
+    };
+
+    let schedule = match CallLinkSchedule::from_flattened(
+        startEpochSeconds,
+        durationSeconds,
+        invitees.as_slice().unwrap_or_default(),
+    ) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            error!("Invalid schedule: {}", e);
+            return ptr::null_mut();
+        }
+    };
+
+    match call_manager::cancel_call_link_ring(
+        callManager as *mut IosCallManager,
+        &root_key,
+        &schedule,
+    ) {
+        Ok(_) => {
+            // Return the object reference back as indication of success.
+            callManager
+        }
+        Err(_) => ptr::null_mut(),
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcReceivedAnswer(
@@ -1140,6 +1565,20 @@ pub extern "C" fn ringrtcJoin(callManager: *mut c_void, clientId: group_call::Cl
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcJoinByHandoff(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    fromDemuxId: DemuxId,
+) {
+    let result =
+        call_manager::join_by_handoff(callManager as *mut IosCallManager, clientId, fromDemuxId);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcLeave(callManager: *mut c_void, clientId: group_call::ClientId) {
@@ -1172,6 +1611,52 @@ pub extern "C" fn ringrtcGroupRing(
     }
 }
 
+/// `recipients` holds the users to ring again as concatenated 16-byte UUIDs.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGroupRingAgain(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    recipients: AppByteSlice,
+) {
+    const UUID_LEN: usize = 16;
+    let recipients = recipients.as_slice().unwrap_or_default();
+    if recipients.len() % UUID_LEN != 0 {
+        error!("Invalid recipients length: {}", recipients.len());
+        return;
+    }
+    let recipients = recipients
+        .chunks_exact(UUID_LEN)
+        .map(<[u8]>::to_vec)
+        .collect();
+    let result =
+        call_manager::group_ring_again(callManager as *mut IosCallManager, clientId, recipients);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcGroupRingDelivered(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    recipient: AppByteSlice,
+) {
+    let recipient = match byte_vec_from_app_slice(&recipient) {
+        Some(recipient) => recipient,
+        None => {
+            error!("Missing recipient");
+            return;
+        }
+    };
+    let result =
+        call_manager::group_ring_delivered(callManager as *mut IosCallManager, clientId, recipient);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcSetOutgoingAudioMuted(
@@ -1266,6 +1751,74 @@ pub extern "C" fn ringrtcRequestVideo(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetSpotlight(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    demuxIds: AppDemuxIdArray,
+) {
+    let demux_ids = if demuxIds.count == 0 {
+        vec![]
+    } else {
+        unsafe { slice::from_raw_parts(demuxIds.demuxIds, demuxIds.count) }.to_vec()
+    };
+
+    let result =
+        call_manager::set_spotlight(callManager as *mut IosCallManager, clientId, demux_ids);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+/// A null `appViewport` goes back to the app calling ringrtcRequestVideo directly.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSetViewport(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    appViewport: *const AppViewport,
+) {
+    let viewport = if appViewport.is_null() {
+        None
+    } else {
+        let app_viewport = unsafe { &*appViewport };
+        let mode = match group_call::ViewportMode::try_from(app_viewport.mode) {
+            Ok(mode) => mode,
+            Err(e) => {
+                error!("Invalid mode: {}", e);
+                return;
+            }
+        };
+        let tiles = if app_viewport.tilesCount == 0 {
+            vec![]
+        } else {
+            unsafe { slice::from_raw_parts(app_viewport.tiles, app_viewport.tilesCount) }
+                .iter()
+                .map(|tile| group_call::TileSize {
+                    width: tile.width,
+                    height: tile.height,
+                })
+                .collect()
+        };
+        let pinned = if app_viewport.pinnedCount == 0 {
+            vec![]
+        } else {
+            unsafe { slice::from_raw_parts(app_viewport.pinned, app_viewport.pinnedCount) }.to_vec()
+        };
+        Some(group_call::Viewport {
+            mode,
+            tiles,
+            pinned,
+        })
+    };
+
+    let result = call_manager::set_viewport(callManager as *mut IosCallManager, clientId, viewport);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcApproveUser(
@@ -1336,6 +1889,71 @@ pub extern "C" fn ringrtcBlockClient(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcMuteClient(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    otherClientDemuxId: DemuxId,
+) {
+    let result = call_manager::mute_client(
+        callManager as *mut IosCallManager,
+        clientId,
+        otherClientDemuxId,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcAskClientToUnmute(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    otherClientDemuxId: DemuxId,
+) {
+    let result = call_manager::ask_client_to_unmute(
+        callManager as *mut IosCallManager,
+        clientId,
+        otherClientDemuxId,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcLowerHand(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    otherClientDemuxId: DemuxId,
+) {
+    let result = call_manager::lower_hand(
+        callManager as *mut IosCallManager,
+        clientId,
+        otherClientDemuxId,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcCallNextSpeaker(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    askToUnmute: bool,
+) {
+    let result =
+        call_manager::call_next_speaker(callManager as *mut IosCallManager, clientId, askToUnmute);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcSetGroupMembers(
@@ -1420,6 +2038,135 @@ pub extern "C" fn ringrtcReact(
     }
 }
 
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSendChatMessage(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    text: AppByteSlice,
+    replyTo: AppChatMessageId,
+) {
+    let text = string_from_app_slice(&text);
+    if text.is_none() {
+        error!("Invalid chat message text");
+        return;
+    }
+
+    let reply_to = (replyTo.seqnum > 0).then_some(group_call::ChatMessageId {
+        demux_id: replyTo.demuxId,
+        seqnum: replyTo.seqnum,
+    });
+
+    let result = call_manager::send_chat_message(
+        callManager as *mut IosCallManager,
+        clientId,
+        text.unwrap(),
+        reply_to,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcCreatePoll(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    question: AppByteSlice,
+    options: AppStringArray,
+) {
+    let question = string_from_app_slice(&question);
+    if question.is_none() {
+        error!("Invalid poll question");
+        return;
+    }
+
+    let app_options: &[AppByteSlice] = if options.count == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(options.strings, options.count) }
+    };
+    let options: Option<Vec<String>> = app_options.iter().map(string_from_app_slice).collect();
+    if options.is_none() {
+        error!("Invalid poll option");
+        return;
+    }
+
+    let result = call_manager::create_poll(
+        callManager as *mut IosCallManager,
+        clientId,
+        question.unwrap(),
+        options.unwrap(),
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+/// An invalid `optionIndex` withdraws the vote.
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcVoteInPoll(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    pollId: group_call::PollId,
+    optionIndex: AppOptionalUInt32,
+) {
+    let option_index = optionIndex.valid.then_some(optionIndex.value);
+
+    let result = call_manager::vote_in_poll(
+        callManager as *mut IosCallManager,
+        clientId,
+        pollId,
+        option_index,
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcClosePoll(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    pollId: group_call::PollId,
+) {
+    let result = call_manager::close_poll(callManager as *mut IosCallManager, clientId, pollId);
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
+#[no_mangle]
+#[allow(non_snake_case)]
+pub extern "C" fn ringrtcSendLobbyMessage(
+    callManager: *mut c_void,
+    clientId: group_call::ClientId,
+    message: AppByteSlice,
+) {
+    // An empty message clears the note, and Swift may pass it as a null slice.
+    let message = if message.bytes.is_null() {
+        Some(String::new())
+    } else {
+        string_from_app_slice(&message)
+    };
+    if message.is_none() {
+        error!("Invalid lobby message");
+        return;
+    }
+
+    let result = call_manager::send_lobby_message(
+        callManager as *mut IosCallManager,
+        clientId,
+        message.unwrap(),
+    );
+    if result.is_err() {
+        error!("{:?}", result.err());
+    }
+}
+
 #[no_mangle]
 #[allow(non_snake_case)]
 pub extern "C" fn ringrtcRaiseHand(
@@ -5,7 +5,11 @@
 
 //! iOS Call Manager
 
-use std::{ffi::c_void, sync::Arc, time::Duration};
+use std::{
+    ffi::c_void,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use anyhow::anyhow;
 
@@ -13,7 +17,7 @@ use crate::{
     common::{CallConfig, CallId, CallMediaType, DataMode, DeviceId, Result},
     core::{
         call_manager,
-        call_manager::CallManager,
+        call_manager::{CallManager, PersistedState},
         group_call, signaling,
         util::{ptr_as_box, ptr_as_mut},
     },
@@ -23,7 +27,7 @@ use crate::{
         ios_platform::IosPlatform,
     },
     lite::{
-        call_links::CallLinkRootKey,
+        call_links::{CallLinkRootKey, CallLinkSchedule},
         http,
         sfu::{DemuxId, GroupMember, UserId},
     },
@@ -51,6 +55,18 @@ pub fn set_self_uuid(call_manager: *mut IosCallManager, uuid: UserId) -> Result<
     call_manager.set_self_uuid(uuid)
 }
 
+/// Application request for the state to save across app restarts.
+pub fn persisted_state(call_manager: *mut IosCallManager) -> Result<PersistedState> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.persisted_state()
+}
+
+/// Application notification to restore state saved before the app restarted.
+pub fn restore_persisted_state(call_manager: *mut IosCallManager, state: &[u8]) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.restore_persisted_state(PersistedState::from_bytes(state)?)
+}
+
 /// Application notification to start a new call.
 pub fn call(
     call_manager: *mut IosCallManager,
@@ -66,6 +82,24 @@ pub fn call(
     )
 }
 
+/// Application notification to take over a call accepted on another device
+pub fn pull_call(
+    call_manager: *mut IosCallManager,
+    app_remote: *const c_void,
+    replaced_call_id: u64,
+    call_media_type: CallMediaType,
+    app_local_device: DeviceId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.pull_call(
+        AppObject::from(app_remote),
+        CallId::random(),
+        CallId::new(replaced_call_id),
+        call_media_type,
+        app_local_device,
+    )
+}
+
 /// Application notification to proceed with a new call
 pub fn proceed(
     call_manager: *mut IosCallManager,
@@ -115,6 +149,37 @@ pub fn cancel_group_ring(
     call_manager.cancel_group_ring(group_id, ring_id, reason)
 }
 
+/// Application request to tell the sender of a group ring that this device is ringing.
+pub fn acknowledge_group_ring(
+    call_manager: *mut IosCallManager,
+    group_id: group_call::GroupId,
+    ring_id: group_call::RingId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.acknowledge_group_ring(group_id, ring_id)
+}
+
+/// Application request to ring the invitees of a scheduled call link.
+pub fn ring_scheduled_call_link(
+    call_manager: *mut IosCallManager,
+    root_key: &CallLinkRootKey,
+    schedule: &CallLinkSchedule,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.ring_scheduled_call_link(root_key, schedule, SystemTime::now())?;
+    Ok(())
+}
+
+/// Application request to stop the ring of a scheduled call link.
+pub fn cancel_call_link_ring(
+    call_manager: *mut IosCallManager,
+    root_key: &CallLinkRootKey,
+    schedule: &CallLinkSchedule,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.cancel_call_link_ring(root_key, schedule)
+}
+
 /// Application notification of received answer message
 #[allow(clippy::too_many_arguments)]
 pub fn received_answer(
@@ -478,6 +543,16 @@ pub fn join(call_manager: *mut IosCallManager, client_id: group_call::ClientId)
     Ok(())
 }
 
+pub fn join_by_handoff(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    from_demux_id: DemuxId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.join_by_handoff(client_id, from_demux_id);
+    Ok(())
+}
+
 pub fn leave(call_manager: *mut IosCallManager, client_id: group_call::ClientId) -> Result<()> {
     let call_manager = unsafe { ptr_as_mut(call_manager)? };
     call_manager.leave(client_id);
@@ -523,6 +598,26 @@ pub fn group_ring(
     Ok(())
 }
 
+pub fn group_ring_again(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    recipients: Vec<UserId>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.group_ring_again(client_id, recipients);
+    Ok(())
+}
+
+pub fn group_ring_delivered(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    recipient: UserId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.group_ring_delivered(client_id, recipient);
+    Ok(())
+}
+
 pub fn resend_media_keys(
     call_manager: *mut IosCallManager,
     client_id: group_call::ClientId,
@@ -553,6 +648,26 @@ pub fn request_video(
     Ok(())
 }
 
+pub fn set_spotlight(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    demux_ids: Vec<DemuxId>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_spotlight(client_id, demux_ids);
+    Ok(())
+}
+
+pub fn set_viewport(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    viewport: Option<group_call::Viewport>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.set_viewport(client_id, viewport);
+    Ok(())
+}
+
 pub fn approve_user(
     call_manager: *mut IosCallManager,
     client_id: group_call::ClientId,
@@ -593,6 +708,46 @@ pub fn block_client(
     Ok(())
 }
 
+pub fn mute_client(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: DemuxId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.mute_client(client_id, other_client_demux_id);
+    Ok(())
+}
+
+pub fn ask_client_to_unmute(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: DemuxId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.ask_client_to_unmute(client_id, other_client_demux_id);
+    Ok(())
+}
+
+pub fn lower_hand(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    other_client_demux_id: DemuxId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.lower_hand(client_id, other_client_demux_id);
+    Ok(())
+}
+
+pub fn call_next_speaker(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    ask_to_unmute: bool,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.call_next_speaker(client_id, ask_to_unmute);
+    Ok(())
+}
+
 pub fn set_group_members(
     call_manager: *mut IosCallManager,
     client_id: group_call::ClientId,
@@ -623,6 +778,59 @@ pub fn react(
     Ok(())
 }
 
+pub fn send_chat_message(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    text: String,
+    reply_to: Option<group_call::ChatMessageId>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.send_chat_message(client_id, text, reply_to);
+    Ok(())
+}
+
+pub fn create_poll(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    question: String,
+    options: Vec<String>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.create_poll(client_id, question, options);
+    Ok(())
+}
+
+pub fn vote_in_poll(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    poll_id: group_call::PollId,
+    option_index: Option<u32>,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.vote_in_poll(client_id, poll_id, option_index);
+    Ok(())
+}
+
+pub fn close_poll(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    poll_id: group_call::PollId,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.close_poll(client_id, poll_id);
+    Ok(())
+}
+
+pub fn send_lobby_message(
+    call_manager: *mut IosCallManager,
+    client_id: group_call::ClientId,
+    message: String,
+) -> Result<()> {
+    let call_manager = unsafe { ptr_as_mut(call_manager)? };
+    call_manager.send_lobby_message(client_id, message);
+    Ok(())
+}
+
 pub fn raise_hand(
     call_manager: *mut IosCallManager,
     client_id: group_call::ClientId,
@@ -5,7 +5,12 @@
 
 //! iOS Platform
 
-use std::{collections::HashSet, fmt, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use crate::{
     common::{
@@ -21,15 +26,20 @@ use crate::{
     },
     ios::{
         api::call_manager_interface::{
-            AppByteSlice, AppCallContext, AppConnectionInterface, AppIceCandidateArray,
-            AppInterface, AppObject, AppOptionalBool, AppOptionalUInt32, AppRaisedHandsArray,
-            AppReaction, AppReactionsArray, AppReceivedAudioLevel, AppReceivedAudioLevelArray,
-            AppRemoteDeviceState, AppRemoteDeviceStateArray, AppUuidArray,
+            AppBreakoutRoomAssignment, AppBreakoutRooms, AppByteSlice, AppCallContext,
+            AppChatMessage, AppChatMessageId, AppConnectionInterface, AppDemuxIdArray,
+            AppForwardedVideoLayers, AppIceCandidateArray, AppIncomingVideoHeight, AppInterface,
+            AppLobbyInfo, AppObject, AppOptionalBool, AppOptionalUInt32, AppPoll, AppPollArray,
+            AppPollOption, AppRaisedHandsArray, AppReaction, AppReactionsArray,
+            AppReceivedAudioLevel, AppReceivedAudioLevelArray, AppRemoteDeviceState,
+            AppRemoteDeviceStateArray, AppRingRecipient, AppRingRecipientArray, AppSpeakerQueue,
+            AppUuidArray, AppVideoLayoutTileArray,
         },
         error::IosError,
         ios_media_stream::IosMediaStream,
     },
     lite::{
+        call_links::{BreakoutRooms, CallLinkRootKey},
         sfu,
         sfu::{DemuxId, PeekInfo, PeekResult, UserId},
     },
@@ -522,6 +532,23 @@ impl Platform for IosPlatform {
         self.on_event(remote_peer, call_id, ApplicationEvent::ReceivedOfferExpired)
     }
 
+    fn on_accepted_on_another_device(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        _call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        info!("on_accepted_on_another_device(): device_id: {}", device_id);
+
+        (self.app_interface.onAcceptedOnAnotherDevice)(
+            self.app_interface.object,
+            remote_peer.ptr,
+            device_id,
+        );
+
+        Ok(())
+    }
+
     fn on_call_concluded(&self, remote_peer: &Self::AppRemotePeer, _call_id: CallId) -> Result<()> {
         info!("on_call_concluded():");
 
@@ -550,6 +577,25 @@ impl Platform for IosPlatform {
         );
     }
 
+    fn call_link_ring_update(
+        &self,
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender: UserId,
+        update: group_call::RingUpdate,
+    ) {
+        let root_key = root_key.bytes().to_vec();
+        let root_key = app_slice_from_bytes(Some(&root_key));
+        let sender = app_slice_from_bytes(Some(&sender));
+        (self.app_interface.callLinkRingUpdate)(
+            self.app_interface.object,
+            root_key,
+            ring_id.into(),
+            sender,
+            update as i32,
+        );
+    }
+
     fn request_membership_proof(&self, client_id: group_call::ClientId) {
         (self.app_interface.requestMembershipProof)(self.app_interface.object, client_id);
     }
@@ -716,6 +762,290 @@ impl Platform for IosPlatform {
         );
     }
 
+    fn handle_speaker_queue_changed(
+        &self,
+        client_id: group_call::ClientId,
+        queue: group_call::SpeakerQueue,
+    ) {
+        info!(
+            "handle_speaker_queue_changed(): {:?}, current_speaker: {:?}",
+            queue.raised_hands, queue.current_speaker
+        );
+
+        let app_queue = AppSpeakerQueue {
+            raisedHands: AppRaisedHandsArray {
+                raised_hands: queue.raised_hands.as_ptr(),
+                count: queue.raised_hands.len(),
+            },
+            currentSpeaker: app_option_from_u32(queue.current_speaker),
+        };
+
+        (self.app_interface.handleSpeakerQueueChanged)(
+            self.app_interface.object,
+            client_id,
+            app_queue,
+        );
+    }
+
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layers: group_call::ForwardedVideoLayers,
+    ) {
+        info!(
+            "handle_forwarded_video_layers_changed(): outgoing: {}, incoming: {:?}",
+            layers.outgoing_layer_count, layers.incoming_heights
+        );
+
+        let app_incoming_heights: Vec<AppIncomingVideoHeight> = layers
+            .incoming_heights
+            .into_iter()
+            .map(|(demux_id, height)| AppIncomingVideoHeight {
+                demuxId: demux_id,
+                height,
+            })
+            .collect();
+        let app_layers = AppForwardedVideoLayers {
+            outgoingLayerCount: layers.outgoing_layer_count as u32,
+            incomingHeights: app_incoming_heights.as_ptr(),
+            incomingHeightsCount: app_incoming_heights.len(),
+        };
+
+        (self.app_interface.handleForwardedVideoLayersChanged)(
+            self.app_interface.object,
+            client_id,
+            app_layers,
+        );
+    }
+
+    fn handle_breakout_rooms_changed(
+        &self,
+        client_id: group_call::ClientId,
+        breakout_rooms: Option<BreakoutRooms>,
+    ) {
+        info!(
+            "handle_breakout_rooms_changed(): {:?}",
+            breakout_rooms
+                .as_ref()
+                .map(|breakout_rooms| breakout_rooms.room_count)
+        );
+
+        let (room_count, assignments, return_at_epoch_millis) = match breakout_rooms {
+            None => (0, Vec::new(), -1),
+            Some(breakout_rooms) => (
+                breakout_rooms.room_count,
+                breakout_rooms
+                    .assignments
+                    .into_iter()
+                    .map(|(demux_id, room_index)| AppBreakoutRoomAssignment {
+                        demuxId: demux_id,
+                        roomIndex: room_index,
+                    })
+                    .collect(),
+                breakout_rooms.return_at.map_or(-1, |return_at| {
+                    return_at
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as i64
+                }),
+            ),
+        };
+
+        let app_breakout_rooms = AppBreakoutRooms {
+            roomCount: room_count,
+            assignments: assignments.as_ptr(),
+            assignmentsCount: assignments.len(),
+            returnAtEpochMillis: return_at_epoch_millis,
+        };
+
+        (self.app_interface.handleBreakoutRoomsChanged)(
+            self.app_interface.object,
+            client_id,
+            app_breakout_rooms,
+        );
+    }
+
+    fn handle_chat_message(
+        &self,
+        client_id: group_call::ClientId,
+        message: group_call::ChatMessage,
+    ) {
+        trace!("handle_chat_message(): {:?}", message.id);
+
+        let reply_to = message.reply_to.unwrap_or(group_call::ChatMessageId {
+            demux_id: 0,
+            seqnum: 0,
+        });
+
+        let app_message = AppChatMessage {
+            id: AppChatMessageId {
+                demuxId: message.id.demux_id,
+                seqnum: message.id.seqnum,
+            },
+            text: app_slice_from_str(Some(&message.text)),
+            replyTo: AppChatMessageId {
+                demuxId: reply_to.demux_id,
+                seqnum: reply_to.seqnum,
+            },
+        };
+
+        (self.app_interface.handleChatMessage)(self.app_interface.object, client_id, app_message);
+    }
+
+    fn handle_polls_changed(&self, client_id: group_call::ClientId, polls: Vec<group_call::Poll>) {
+        info!("handle_polls_changed(): {} polls", polls.len());
+
+        // The options have to stay alive until the app has copied them.
+        let app_options: Vec<Vec<AppPollOption>> = polls
+            .iter()
+            .map(|poll| {
+                poll.options
+                    .iter()
+                    .map(|option| AppPollOption {
+                        text: app_slice_from_str(Some(&option.text)),
+                        votes: option.votes,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let app_polls: Vec<AppPoll> = polls
+            .iter()
+            .zip(&app_options)
+            .map(|(poll, options)| AppPoll {
+                id: poll.id,
+                creator: poll.creator,
+                question: app_slice_from_str(Some(&poll.question)),
+                options: options.as_ptr(),
+                optionsCount: options.len(),
+                ownVote: app_option_from_u32(poll.own_vote.map(|own_vote| own_vote as u32)),
+                closed: poll.closed,
+            })
+            .collect();
+
+        let app_polls_array = AppPollArray {
+            polls: app_polls.as_ptr(),
+            count: app_polls.len(),
+        };
+
+        (self.app_interface.handlePollsChanged)(
+            self.app_interface.object,
+            client_id,
+            app_polls_array,
+        );
+    }
+
+    fn handle_lobby_changed(&self, client_id: group_call::ClientId, lobby: group_call::LobbyInfo) {
+        info!(
+            "handle_lobby_changed(): participant_count: {}, position: {:?}",
+            lobby.participant_count, lobby.position
+        );
+
+        let app_admins: Vec<AppByteSlice> = lobby
+            .admins
+            .iter()
+            .map(|admin| app_slice_from_bytes(Some(admin)))
+            .collect();
+
+        let app_lobby = AppLobbyInfo {
+            participantCount: lobby.participant_count as u32,
+            admins: AppUuidArray {
+                uuids: app_admins.as_ptr(),
+                count: app_admins.len(),
+            },
+            position: app_option_from_u32(lobby.position.map(|position| position as u32)),
+            message: app_slice_from_str(lobby.message.as_ref()),
+        };
+
+        (self.app_interface.handleLobbyChanged)(self.app_interface.object, client_id, app_lobby);
+    }
+
+    fn handle_muted_by_admin(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!("handle_muted_by_admin(): {}", admin_demux_id);
+
+        (self.app_interface.handleMutedByAdmin)(
+            self.app_interface.object,
+            client_id,
+            admin_demux_id,
+        );
+    }
+
+    fn handle_unmute_requested(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!("handle_unmute_requested(): {}", admin_demux_id);
+
+        (self.app_interface.handleUnmuteRequested)(
+            self.app_interface.object,
+            client_id,
+            admin_demux_id,
+        );
+    }
+
+    fn handle_spotlight_changed(&self, client_id: group_call::ClientId, spotlight: Vec<DemuxId>) {
+        info!("handle_spotlight_changed(): {:?}", spotlight);
+
+        let app_spotlight_array = AppDemuxIdArray {
+            demuxIds: spotlight.as_ptr(),
+            count: spotlight.len(),
+        };
+
+        (self.app_interface.handleSpotlightChanged)(
+            self.app_interface.object,
+            client_id,
+            app_spotlight_array,
+        );
+    }
+
+    fn handle_video_layout_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layout: group_call::VideoLayout,
+    ) {
+        info!("handle_video_layout_changed(): {:?}", layout.tiles);
+
+        let app_tiles: Vec<AppOptionalUInt32> =
+            layout.tiles.into_iter().map(app_option_from_u32).collect();
+
+        let app_tile_array = AppVideoLayoutTileArray {
+            tiles: app_tiles.as_ptr(),
+            count: app_tiles.len(),
+        };
+
+        (self.app_interface.handleVideoLayoutChanged)(
+            self.app_interface.object,
+            client_id,
+            app_tile_array,
+        );
+    }
+
+    fn handle_ring_recipients_changed(
+        &self,
+        client_id: group_call::ClientId,
+        ring_id: group_call::RingId,
+        recipients: HashMap<UserId, group_call::RingRecipientStatus>,
+    ) {
+        info!("handle_ring_recipients_changed(): ring_id: {}", ring_id);
+
+        let app_recipients: Vec<AppRingRecipient> = recipients
+            .iter()
+            .map(|(user_id, status)| AppRingRecipient {
+                userId: app_slice_from_bytes(Some(user_id)),
+                status: *status as i32,
+            })
+            .collect();
+
+        let app_recipient_array = AppRingRecipientArray {
+            recipients: app_recipients.as_ptr(),
+            count: app_recipients.len(),
+        };
+
+        (self.app_interface.handleRingRecipientsChanged)(
+            self.app_interface.object,
+            client_id,
+            ring_id.into(),
+            app_recipient_array,
+        );
+    }
+
     fn handle_incoming_video_track(
         &self,
         client_id: group_call::ClientId,
@@ -783,6 +1113,27 @@ impl Platform for IosPlatform {
         (self.app_interface.handleEnded)(self.app_interface.object, client_id, reason as i32);
     }
 
+    fn handle_handoff_state_received(
+        &self,
+        client_id: group_call::ClientId,
+        state: group_call::HandoffState,
+    ) {
+        info!("handle_handoff_state_received(): {:?}", state);
+
+        (self.app_interface.handleHandoffStateReceived)(
+            self.app_interface.object,
+            client_id,
+            app_option_from_bool(state.audio_muted),
+            app_option_from_bool(state.video_muted),
+        );
+    }
+
+    fn handle_handed_off(&self, client_id: group_call::ClientId, new_demux_id: DemuxId) {
+        info!("handle_handed_off(): {}", new_demux_id);
+
+        (self.app_interface.handleHandedOff)(self.app_interface.object, client_id, new_demux_id);
+    }
+
     fn handle_speaking_notification(
         &self,
         client_id: group_call::ClientId,
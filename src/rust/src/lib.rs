@@ -116,6 +116,7 @@ pub mod webrtc {
     pub use ptr::RefCounted;
     pub mod rtp;
     pub mod sdp_observer;
+    pub mod stats_exporter;
     pub mod stats_observer;
     #[cfg(not(feature = "sim"))]
     mod ffi {
@@ -4,8 +4,10 @@
 //
 
 mod base16;
+mod breakout;
 mod member_resolver;
 mod root_key;
+mod schedule;
 
 use std::{
     collections::HashMap,
@@ -13,8 +15,10 @@ use std::{
 };
 
 use base64::{engine::general_purpose::STANDARD as base64, Engine};
+pub use breakout::BreakoutRooms;
 pub use member_resolver::CallLinkMemberResolver;
 pub use root_key::CallLinkRootKey;
+pub use schedule::{CallLinkSchedule, SCHEDULED_RING_WINDOW};
 use serde::{self, Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -48,6 +52,8 @@ pub struct CallLinkResponse<'a> {
     pub revoked: bool,
     #[serde(rename = "expiration")]
     pub expiration_unix_timestamp: u64,
+    #[serde(rename = "schedule", default, borrow)]
+    pub encrypted_schedule: Option<&'a [u8]>,
 }
 
 impl<'a> TryFrom<&'a sfu_to_device::peek_info::CallLinkState> for CallLinkResponse<'a> {
@@ -67,6 +73,7 @@ impl<'a> TryFrom<&'a sfu_to_device::peek_info::CallLinkState> for CallLinkRespon
             restrictions: value.restrictions().into(),
             revoked: value.revoked(),
             expiration_unix_timestamp: value.expiration_unix_timestamp(),
+            encrypted_schedule: value.encrypted_schedule.as_ref().map(|s| s.as_bytes()),
         })
     }
 }
@@ -77,6 +84,7 @@ pub struct CallLinkState {
     pub restrictions: CallLinkRestrictions,
     pub revoked: bool,
     pub expiration: SystemTime,
+    pub schedule: Option<CallLinkSchedule>,
 }
 
 impl CallLinkState {
@@ -94,12 +102,28 @@ impl CallLinkState {
                     Default::default()
                 })
         };
+        let schedule = deserialized
+            .encrypted_schedule
+            .filter(|encrypted_schedule| !encrypted_schedule.is_empty())
+            .and_then(|encrypted_schedule| {
+                base64
+                    .decode(encrypted_schedule)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|encrypted_bytes| {
+                        CallLinkSchedule::decrypt(&encrypted_bytes, root_key)
+                    })
+                    .inspect_err(|err| {
+                        warn!("encrypted schedule of call failed to decrypt: {}", err);
+                    })
+                    .ok()
+            });
         CallLinkState {
             name,
             restrictions: deserialized.restrictions,
             revoked: deserialized.revoked,
             expiration: SystemTime::UNIX_EPOCH
                 + Duration::from_secs(deserialized.expiration_unix_timestamp),
+            schedule,
         }
     }
 }
@@ -182,6 +206,11 @@ pub struct CallLinkUpdateRequest<'a> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub revoked: Option<bool>,
+
+    /// An encrypted [`CallLinkSchedule`], or empty to remove the schedule.
+    #[serde(rename = "schedule", skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<serde_with::base64::Base64>")]
+    pub encrypted_schedule: Option<&'a [u8]>,
 }
 
 #[serde_as]
@@ -235,6 +264,36 @@ pub fn create_call_link(
     )
 }
 
+/// Creates the call link for one of the breakout rooms of `root_key`'s call (see
+/// [`BreakoutRooms`]).
+///
+/// The room's root key and admin passkey are derived from the main room's, so every device can
+/// find the room from its index and the main room's admins can manage it. The credential must be
+/// for the breakout room's ID (`root_key.derive_breakout_room_key(room_index).derive_room_id()`).
+#[allow(clippy::too_many_arguments)]
+pub fn create_breakout_room_link(
+    http_client: &dyn http::Client,
+    sfu_url: &str,
+    root_key: &CallLinkRootKey,
+    room_index: u32,
+    create_credential_presentation: &[u8],
+    admin_passkey: &[u8],
+    public_zkparams: &[u8],
+    restrictions: Option<CallLinkRestrictions>,
+    result_callback: ReadCallLinkResultCallback,
+) {
+    create_call_link(
+        http_client,
+        sfu_url,
+        root_key.derive_breakout_room_key(room_index),
+        create_credential_presentation,
+        &CallLinkRootKey::derive_breakout_admin_passkey(admin_passkey, room_index),
+        public_zkparams,
+        restrictions,
+        result_callback,
+    )
+}
+
 pub fn update_call_link(
     http_client: &dyn http::Client,
     sfu_url: &str,
@@ -308,7 +367,7 @@ pub mod ios {
     use crate::lite::{
         ffi::ios::{cstr, rtc_Bytes, rtc_OptionalU16, rtc_String},
         http,
-        sfu::ios::rtc_sfu_Response,
+        sfu::ios::{rtc_UserIds, rtc_sfu_Response},
     };
 
     pub type Client = http::DelegatingClient;
@@ -387,6 +446,30 @@ pub mod ios {
         }
     }
 
+    #[no_mangle]
+    pub extern "C" fn rtc_calllinks_CallLinkRootKey_deriveBreakoutRoomKey(
+        root_key_bytes: rtc_Bytes,
+        room_index: u32,
+        context: *mut c_void,
+        callback: extern "C" fn(context: *mut c_void, result: rtc_Bytes),
+    ) -> *const c_char {
+        match CallLinkRootKey::try_from(root_key_bytes.as_slice()) {
+            Ok(root_key) => {
+                callback(
+                    context,
+                    rtc_Bytes::from(
+                        root_key
+                            .derive_breakout_room_key(room_index)
+                            .bytes()
+                            .as_slice(),
+                    ),
+                );
+                std::ptr::null()
+            }
+            Err(_) => cstr!("invalid root key").as_ptr(),
+        }
+    }
+
     #[no_mangle]
     pub extern "C" fn rtc_calllinks_CallLinkRootKey_toFormattedString(
         root_key_bytes: rtc_Bytes,
@@ -412,10 +495,20 @@ pub mod ios {
         pub expiration_epoch_seconds: u64,
         pub raw_restrictions: i8,
         pub revoked: bool,
+        pub schedule: rtc_calllinks_CallLinkSchedule<'a>,
+    }
+
+    /// A zero duration means the link has no schedule.
+    #[repr(C)]
+    #[derive(Default, Debug)]
+    pub struct rtc_calllinks_CallLinkSchedule<'a> {
+        pub start_epoch_seconds: u64,
+        pub duration_seconds: u64,
+        pub invitees: rtc_UserIds<'a>,
     }
 
-    impl<'a> From<&'a CallLinkState> for rtc_calllinks_CallLinkState<'a> {
-        fn from(value: &'a CallLinkState) -> Self {
+    impl<'a> rtc_calllinks_CallLinkState<'a> {
+        fn new(value: &'a CallLinkState, invitees: &'a Vec<rtc_Bytes<'a>>) -> Self {
             Self {
                 name: value.name.as_str().into(),
                 expiration_epoch_seconds: value
@@ -429,6 +522,15 @@ pub mod ios {
                     CallLinkRestrictions::Unknown => -1,
                 },
                 revoked: value.revoked,
+                schedule: value
+                    .schedule
+                    .as_ref()
+                    .map(|schedule| rtc_calllinks_CallLinkSchedule {
+                        start_epoch_seconds: schedule.start_epoch_seconds(),
+                        duration_seconds: schedule.duration.as_secs(),
+                        invitees: rtc_UserIds::from(invitees),
+                    })
+                    .unwrap_or_default(),
             }
         }
     }
@@ -450,10 +552,16 @@ pub mod ios {
             request_id: u32,
             result: Result<CallLinkState, http::ResponseStatus>,
         ) {
+            let invitees: Vec<rtc_Bytes> = result
+                .iter()
+                .flat_map(|state| state.schedule.iter())
+                .flat_map(|schedule| schedule.invitees.iter())
+                .map(rtc_Bytes::from)
+                .collect();
             let response = match result.as_ref() {
                 Ok(state) => rtc_sfu_Response {
                     error_status_code: rtc_OptionalU16::default(),
-                    value: state.into(),
+                    value: rtc_calllinks_CallLinkState::new(state, &invitees),
                 },
                 Err(status) => rtc_sfu_Response {
                     error_status_code: status.code.into(),
@@ -588,6 +696,54 @@ pub mod ios {
         }
     }
 
+    /// # Safety
+    ///
+    /// - `http_client` must come from `rtc_http_Client_create` and not already be destroyed
+    /// - `sfu_url` must be a valid, non-null C string.
+    #[no_mangle]
+    pub unsafe extern "C" fn rtc_sfu_createBreakoutRoomLink(
+        http_client: *const http::ios::Client,
+        request_id: u32,
+        sfu_url: *const c_char,
+        create_credential_presentation: rtc_Bytes,
+        link_root_key: rtc_Bytes,
+        room_index: u32,
+        admin_passkey: rtc_Bytes,
+        call_link_public_params: rtc_Bytes,
+        restrictions: i8,
+        delegate: rtc_sfu_CallLinkDelegate,
+    ) {
+        info!("rtc_sfu_createBreakoutRoomLink():");
+
+        let restrictions = from_i8_to_restrictions(restrictions);
+        if let Some(http_client) = http_client.as_ref() {
+            if let Ok(sfu_url) = CStr::from_ptr(sfu_url).to_str() {
+                if let Ok(link_root_key) = CallLinkRootKey::try_from(link_root_key.as_slice()) {
+                    create_breakout_room_link(
+                        http_client,
+                        sfu_url,
+                        &link_root_key,
+                        room_index,
+                        create_credential_presentation.as_slice(),
+                        admin_passkey.as_slice(),
+                        call_link_public_params.as_slice(),
+                        restrictions,
+                        Box::new(move |result| delegate.handle_response(request_id, result)),
+                    )
+                } else {
+                    error!("invalid link_root_key");
+                }
+            } else {
+                error!("invalid sfu_url");
+            }
+        } else {
+            error!("null http_client passed into rtc_sfu_createBreakoutRoomLink");
+        }
+    }
+
+    /// A negative `new_schedule_duration_seconds` leaves the schedule unchanged, and zero removes
+    /// it. Otherwise `new_schedule_invitees` holds the invitees as concatenated 16-byte UUIDs.
+    ///
     /// # Safety
     ///
     /// - `http_client` must come from `rtc_http_Client_create` and not already be destroyed
@@ -603,6 +759,9 @@ pub mod ios {
         new_name: *const c_char,
         new_restrictions: i8,
         new_revoked: i8,
+        new_schedule_start_epoch_seconds: u64,
+        new_schedule_duration_seconds: i64,
+        new_schedule_invitees: rtc_Bytes,
         delegate: rtc_sfu_CallLinkDelegate,
     ) {
         info!("rtc_sfu_updateCallLink():");
@@ -623,6 +782,23 @@ pub mod ios {
                             link_root_key.encrypt(name_bytes, rand::rngs::OsRng)
                         }
                     });
+                    let encrypted_schedule = match new_schedule_duration_seconds {
+                        ..=-1 => None,
+                        0 => Some(vec![]),
+                        duration_seconds => match CallLinkSchedule::from_flattened(
+                            new_schedule_start_epoch_seconds,
+                            duration_seconds as u64,
+                            new_schedule_invitees.as_slice(),
+                        ) {
+                            Ok(schedule) => {
+                                Some(schedule.encrypt(&link_root_key, rand::rngs::OsRng))
+                            }
+                            Err(err) => {
+                                error!("invalid new schedule: {}", err);
+                                return;
+                            }
+                        },
+                    };
                     update_call_link(
                         http_client,
                         sfu_url,
@@ -637,6 +813,7 @@ pub mod ios {
                                 1 => Some(true),
                                 _ => None,
                             },
+                            encrypted_schedule: encrypted_schedule.as_deref(),
                         },
                         Box::new(move |result| delegate.handle_response(request_id, result)),
                     )
@@ -0,0 +1,159 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use crate::{lite::sfu::DemuxId, protobuf};
+
+/// How an admin has split a call link call into breakout rooms.
+///
+/// Room `i` is the call link whose root key is
+/// [`derive_breakout_room_key(i)`](super::CallLinkRootKey::derive_breakout_room_key) of the main
+/// room's key; an admin creates it with
+/// [`create_breakout_room_link`](super::create_breakout_room_link) before starting the breakout.
+/// Devices move between rooms by leaving one call and joining the other; apps drive
+/// the return to the main room from a timer using [`BreakoutRooms::time_until_return`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakoutRooms {
+    pub room_count: u32,
+    /// The room each device in the main room should move to. Devices without an assignment stay.
+    pub assignments: HashMap<DemuxId, u32>,
+    /// When everyone should return to the main room, if there's a countdown.
+    pub return_at: Option<SystemTime>,
+}
+
+impl BreakoutRooms {
+    /// The room the device should move to, if any.
+    pub fn room_for(&self, demux_id: DemuxId) -> Option<u32> {
+        self.assignments
+            .get(&demux_id)
+            .copied()
+            .filter(|room_index| *room_index < self.room_count)
+    }
+
+    /// How long until everyone should return to the main room; zero once it's time.
+    ///
+    /// Returns `None` if there is no countdown.
+    pub fn time_until_return(&self, now: SystemTime) -> Option<Duration> {
+        self.return_at
+            .map(|return_at| return_at.duration_since(now).unwrap_or_default())
+    }
+
+    /// Converts a message from the SFU. `None` means the breakout has ended.
+    pub fn from_proto(proto: protobuf::group_call::BreakoutRooms) -> Option<Self> {
+        let room_count = proto.room_count.unwrap_or(0);
+        if room_count == 0 {
+            return None;
+        }
+        let assignments = proto
+            .assignments
+            .into_iter()
+            .filter_map(|assignment| Some((assignment.demux_id?, assignment.room_index?)))
+            .collect();
+        let return_at = proto
+            .return_at_epoch_millis
+            .map(|millis| SystemTime::UNIX_EPOCH + Duration::from_millis(millis));
+        Some(Self {
+            room_count,
+            assignments,
+            return_at,
+        })
+    }
+
+    /// Converts to a message for the SFU. `None` ends the breakout.
+    pub fn to_proto(breakout_rooms: Option<&Self>) -> protobuf::group_call::BreakoutRooms {
+        use protobuf::group_call::breakout_rooms::Assignment;
+
+        let Some(breakout_rooms) = breakout_rooms else {
+            return protobuf::group_call::BreakoutRooms {
+                room_count: Some(0),
+                ..Default::default()
+            };
+        };
+        let mut assignments: Vec<Assignment> = breakout_rooms
+            .assignments
+            .iter()
+            .map(|(demux_id, room_index)| Assignment {
+                demux_id: Some(*demux_id),
+                room_index: Some(*room_index),
+            })
+            .collect();
+        assignments.sort_by_key(|assignment| assignment.demux_id);
+        protobuf::group_call::BreakoutRooms {
+            room_count: Some(breakout_rooms.room_count),
+            assignments,
+            return_at_epoch_millis: breakout_rooms.return_at.map(|return_at| {
+                return_at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breakout_rooms() -> BreakoutRooms {
+        BreakoutRooms {
+            room_count: 2,
+            assignments: HashMap::from([(16, 0), (32, 1), (48, 2)]),
+            return_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_800_000_000)),
+        }
+    }
+
+    #[test]
+    fn test_room_for() {
+        let breakout_rooms = breakout_rooms();
+        assert_eq!(Some(0), breakout_rooms.room_for(16));
+        assert_eq!(Some(1), breakout_rooms.room_for(32));
+        // Out of range.
+        assert_eq!(None, breakout_rooms.room_for(48));
+        // Unassigned.
+        assert_eq!(None, breakout_rooms.room_for(64));
+    }
+
+    #[test]
+    fn test_time_until_return() {
+        let breakout_rooms = breakout_rooms();
+        let return_at = breakout_rooms.return_at.unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            breakout_rooms.time_until_return(return_at - Duration::from_secs(60))
+        );
+        assert_eq!(
+            Some(Duration::ZERO),
+            breakout_rooms.time_until_return(return_at + Duration::from_secs(60))
+        );
+
+        let no_countdown = BreakoutRooms {
+            return_at: None,
+            ..breakout_rooms
+        };
+        assert_eq!(None, no_countdown.time_until_return(return_at));
+    }
+
+    #[test]
+    fn test_proto_round_trip() {
+        let breakout_rooms = breakout_rooms();
+        assert_eq!(
+            Some(breakout_rooms.clone()),
+            BreakoutRooms::from_proto(BreakoutRooms::to_proto(Some(&breakout_rooms)))
+        );
+        assert_eq!(
+            None,
+            BreakoutRooms::from_proto(BreakoutRooms::to_proto(None))
+        );
+        assert_eq!(
+            None,
+            BreakoutRooms::from_proto(protobuf::group_call::BreakoutRooms::default())
+        );
+    }
+}
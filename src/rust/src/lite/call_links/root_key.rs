@@ -61,6 +61,19 @@ impl CallLinkRootKey {
         result.to_vec()
     }
 
+    /// Derives the admin passkey of one of this call link's breakout rooms from the main room's
+    /// passkey, so that whoever administers the main room also administers its breakout rooms.
+    pub fn derive_breakout_admin_passkey(admin_passkey: &[u8], room_index: u32) -> Vec<u8> {
+        let mut result = [0u8; 16];
+        Hkdf::<Sha256>::new(Some(&room_index.to_be_bytes()), admin_passkey)
+            .expand(
+                b"20261018-Signal-CallLinkRootKey-BreakoutAdminPasskey",
+                &mut result,
+            )
+            .expect("valid output length");
+        result.to_vec()
+    }
+
     pub fn derive_room_id(&self) -> Vec<u8> {
         // There are no constraints on the room ID, other than not being unreasonably long.
         // It's never shown to users, but it does appear in HTTP requests to the calling server.
@@ -74,6 +87,30 @@ impl CallLinkRootKey {
         room_id_bytes.to_vec()
     }
 
+    /// Derives the root key of one of this call link's breakout rooms.
+    ///
+    /// Every device derives the same key for the same index, so breakout assignments only need to
+    /// carry room indices. Like generated keys, derived keys never have repeated chunks, so they
+    /// survive a round trip through [`CallLinkRootKey::try_from`].
+    pub fn derive_breakout_room_key(&self, room_index: u32) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(Some(&room_index.to_be_bytes()), &self.bytes);
+        let mut bytes = [0u8; 16];
+        for attempt in 0u32.. {
+            hkdf.expand_multi_info(
+                &[
+                    b"20261018-Signal-CallLinkRootKey-BreakoutRoom".as_slice(),
+                    &attempt.to_be_bytes(),
+                ],
+                &mut bytes,
+            )
+            .expect("valid output length");
+            if !Self::has_repeated_chunk(&bytes) {
+                break;
+            }
+        }
+        Self { bytes }
+    }
+
     fn make_cipher(&self) -> Aes256GcmSiv {
         let mut key = Key::<Aes256GcmSiv>::default();
         Hkdf::<Sha256>::new(None, &self.bytes)
@@ -185,6 +222,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_derive_breakout_room_key() {
+        let key = CallLinkRootKey::generate(rand::thread_rng());
+        let room0 = key.derive_breakout_room_key(0);
+        assert_eq!(room0.bytes(), key.derive_breakout_room_key(0).bytes());
+        assert_ne!(room0.bytes(), key.bytes());
+        assert_ne!(room0.bytes(), key.derive_breakout_room_key(1).bytes());
+        assert_ne!(room0.derive_room_id(), key.derive_room_id());
+
+        for room_index in 0..100 {
+            let room_key = key.derive_breakout_room_key(room_index);
+            assert!(CallLinkRootKey::try_from(room_key.bytes().as_slice()).is_ok());
+        }
+
+        let passkey = CallLinkRootKey::generate_admin_passkey(rand::thread_rng());
+        let room0_passkey = CallLinkRootKey::derive_breakout_admin_passkey(&passkey, 0);
+        assert_eq!(
+            room0_passkey,
+            CallLinkRootKey::derive_breakout_admin_passkey(&passkey, 0)
+        );
+        assert_ne!(room0_passkey, passkey);
+        assert_ne!(
+            room0_passkey,
+            CallLinkRootKey::derive_breakout_admin_passkey(&passkey, 1)
+        );
+    }
+
     #[test]
     fn test_encrypt() {
         let key = CallLinkRootKey::generate(rand::thread_rng());
@@ -0,0 +1,220 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::Sha256;
+
+use super::CallLinkRootKey;
+use crate::lite::sfu::UserId;
+
+/// How long after the scheduled start invitees are still rung.
+///
+/// Apps drive ringing from a timer, which may fire late (or the app may only be launched after
+/// the start time), but ringing people long after a meeting started isn't useful.
+pub const SCHEDULED_RING_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// How far the clock of a device ringing invitees may be from ours.
+const RING_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// The length of each invitee in the flattened form used by the Android and iOS bridges.
+const FLATTENED_INVITEE_LEN: usize = 16;
+
+/// When a call link's meeting takes place and who is invited to it.
+///
+/// Stored on the server encrypted under the link's [`CallLinkRootKey`], alongside the name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallLinkSchedule {
+    pub start: SystemTime,
+    pub duration: Duration,
+    pub invitees: Vec<UserId>,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializedCallLinkSchedule {
+    start_epoch_seconds: u64,
+    duration_seconds: u64,
+    #[serde_as(as = "Vec<serde_with::base64::Base64>")]
+    invitees: Vec<UserId>,
+}
+
+impl CallLinkSchedule {
+    pub fn encrypt(&self, root_key: &CallLinkRootKey, rng: impl RngCore + CryptoRng) -> Vec<u8> {
+        let serialized = serde_json::to_vec(&SerializedCallLinkSchedule {
+            start_epoch_seconds: self.start_epoch_seconds(),
+            duration_seconds: self.duration.as_secs(),
+            invitees: self.invitees.clone(),
+        })
+        .expect("cannot fail to serialize");
+        root_key.encrypt(&serialized, rng)
+    }
+
+    pub fn decrypt(encrypted: &[u8], root_key: &CallLinkRootKey) -> anyhow::Result<Self> {
+        let serialized = root_key
+            .decrypt(encrypted)
+            .map_err(|_| anyhow!("failed to decrypt call link schedule"))?;
+        let deserialized: SerializedCallLinkSchedule = serde_json::from_slice(&serialized)?;
+        Ok(Self {
+            start: SystemTime::UNIX_EPOCH + Duration::from_secs(deserialized.start_epoch_seconds),
+            duration: Duration::from_secs(deserialized.duration_seconds),
+            invitees: deserialized.invitees,
+        })
+    }
+
+    /// Builds a schedule from the form used by the Android and iOS bridges, where invitees are
+    /// concatenated 16-byte UUIDs.
+    pub fn from_flattened(
+        start_epoch_seconds: u64,
+        duration_seconds: u64,
+        flattened_invitees: &[u8],
+    ) -> anyhow::Result<Self> {
+        if flattened_invitees.len() % FLATTENED_INVITEE_LEN != 0 {
+            return Err(anyhow!(
+                "invitees are not a multiple of {} bytes: {}",
+                FLATTENED_INVITEE_LEN,
+                flattened_invitees.len()
+            ));
+        }
+        Ok(Self {
+            start: SystemTime::UNIX_EPOCH + Duration::from_secs(start_epoch_seconds),
+            duration: Duration::from_secs(duration_seconds),
+            invitees: flattened_invitees
+                .chunks_exact(FLATTENED_INVITEE_LEN)
+                .map(<[u8]>::to_vec)
+                .collect(),
+        })
+    }
+
+    pub fn start_epoch_seconds(&self) -> u64 {
+        self.start
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    pub fn end(&self) -> SystemTime {
+        self.start + self.duration
+    }
+
+    /// Whether invitees should be rung at `now`.
+    pub fn is_ring_due(&self, now: SystemTime) -> bool {
+        match now.duration_since(self.start) {
+            Ok(since_start) => since_start < SCHEDULED_RING_WINDOW.min(self.duration),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether a ring sent at `sent` could have come from a device following this schedule,
+    /// allowing for that device's clock being a little off.
+    pub fn is_ring_plausible(&self, sent: SystemTime) -> bool {
+        let earliest = self.start - RING_CLOCK_SKEW;
+        let latest = self.start + SCHEDULED_RING_WINDOW.min(self.duration) + RING_CLOCK_SKEW;
+        earliest <= sent && sent < latest
+    }
+
+    /// The ring ID to use when ringing invitees for this occurrence of the meeting.
+    ///
+    /// Every device of every admin derives the same ID, so recipients treat rings sent by several
+    /// of them as one ring. It is derived from the root key so that it can't be guessed by anyone
+    /// who doesn't have the link.
+    pub fn ring_id(&self, root_key: &CallLinkRootKey) -> i64 {
+        let mut ring_id_bytes = [0u8; 8];
+        Hkdf::<Sha256>::new(
+            Some(&self.start_epoch_seconds().to_be_bytes()),
+            &root_key.bytes(),
+        )
+        .expand(
+            b"20261018-Signal-CallLinkSchedule-RingId",
+            &mut ring_id_bytes,
+        )
+        .expect("valid output length");
+        i64::from_be_bytes(ring_id_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> CallLinkSchedule {
+        CallLinkSchedule {
+            start: SystemTime::UNIX_EPOCH + Duration::from_secs(1_800_000_000),
+            duration: Duration::from_secs(15 * 60),
+            invitees: vec![vec![1; 16], vec![2; 16]],
+        }
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let key = CallLinkRootKey::generate(rand::thread_rng());
+        let schedule = schedule();
+        let encrypted = schedule.encrypt(&key, rand::thread_rng());
+        assert_eq!(
+            schedule,
+            CallLinkSchedule::decrypt(&encrypted, &key).unwrap()
+        );
+
+        let other_key = CallLinkRootKey::generate(rand::thread_rng());
+        assert!(CallLinkSchedule::decrypt(&encrypted, &other_key).is_err());
+    }
+
+    #[test]
+    fn test_from_flattened() {
+        let schedule = schedule();
+        assert_eq!(
+            schedule,
+            CallLinkSchedule::from_flattened(1_800_000_000, 15 * 60, &[[1; 16], [2; 16]].concat())
+                .unwrap()
+        );
+        assert!(CallLinkSchedule::from_flattened(1_800_000_000, 15 * 60, &[1; 17]).is_err());
+    }
+
+    #[test]
+    fn test_is_ring_due() {
+        let schedule = schedule();
+        assert!(!schedule.is_ring_due(schedule.start - Duration::from_secs(1)));
+        assert!(schedule.is_ring_due(schedule.start));
+        assert!(schedule.is_ring_due(schedule.start + Duration::from_secs(60)));
+        assert!(!schedule.is_ring_due(schedule.start + SCHEDULED_RING_WINDOW));
+
+        let short = CallLinkSchedule {
+            duration: Duration::from_secs(60),
+            ..schedule
+        };
+        assert!(!short.is_ring_due(short.end()));
+    }
+
+    #[test]
+    fn test_is_ring_plausible() {
+        let schedule = schedule();
+        assert!(schedule.is_ring_plausible(schedule.start - Duration::from_secs(30)));
+        assert!(schedule.is_ring_plausible(schedule.start + SCHEDULED_RING_WINDOW));
+        assert!(!schedule.is_ring_plausible(schedule.start - Duration::from_secs(5 * 60)));
+        assert!(!schedule.is_ring_plausible(schedule.start + Duration::from_secs(60 * 60)));
+    }
+
+    #[test]
+    fn test_ring_id() {
+        let key = CallLinkRootKey::generate(rand::thread_rng());
+        let schedule = schedule();
+        assert_eq!(schedule.ring_id(&key), schedule.clone().ring_id(&key));
+
+        let next_day = CallLinkSchedule {
+            start: schedule.start + Duration::from_secs(24 * 60 * 60),
+            ..schedule.clone()
+        };
+        assert_ne!(schedule.ring_id(&key), next_day.ring_id(&key));
+
+        let other_key = CallLinkRootKey::generate(rand::thread_rng());
+        assert_ne!(schedule.ring_id(&key), schedule.ring_id(&other_key));
+    }
+}
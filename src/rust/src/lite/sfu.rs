@@ -45,6 +45,8 @@ pub struct PeekInfo {
     pub max_devices: Option<u32>,
     /// The call link state of the group call
     pub call_link_state: Option<CallLinkState>,
+    /// The devices whose users are admins of the call link
+    pub admin_demux_ids: Vec<DemuxId>,
 }
 
 impl PeekInfo {
@@ -108,6 +110,7 @@ impl PeekInfo {
                 .transpose()
                 .ok()
                 .flatten(),
+            admin_demux_ids: proto.admin_demux_ids,
         };
 
         if serialized_peek.devices.len() != expected_devices
@@ -146,6 +149,8 @@ struct SerializedPeekInfo<'a> {
     pending_clients: Vec<SerializedPeekDeviceInfo>,
     #[serde(rename = "callLinkState", borrow)]
     call_link_state: Option<CallLinkResponse<'a>>,
+    #[serde(rename = "adminDemuxIds", default)]
+    admin_demux_ids: Vec<DemuxId>,
 }
 
 /// Form of PeekDeviceInfo sent over HTTP.
@@ -190,6 +195,7 @@ impl SerializedPeekInfo<'_> {
             era_id: self.era_id,
             max_devices: self.max_devices,
             call_link_state: state,
+            admin_demux_ids: self.admin_demux_ids,
         }
     }
 }
@@ -613,6 +619,10 @@ struct JoinRequest<'a> {
 
     #[serde_as(as = "serde_with::hex::Hex")]
     hkdf_extra_info: &'a [u8],
+
+    /// Set when the client is taking over the call from another device of the same user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replaces_demux_id: Option<DemuxId>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -622,6 +632,7 @@ pub fn join(
     room_id_header: Option<String>,
     auth_header: String,
     admin_passkey: Option<&[u8]>,
+    replaces_demux_id: Option<DemuxId>,
     client_ice_ufrag: &str,
     client_ice_pwd: &str,
     client_dhe_pub_key: &[u8],
@@ -651,6 +662,7 @@ pub fn join(
                     ice_pwd: client_ice_pwd,
                     dhe_public_key: client_dhe_pub_key,
                     hkdf_extra_info,
+                    replaces_demux_id,
                 })
                 .expect("always valid"),
             ),
@@ -887,6 +899,16 @@ pub mod ios {
         phantom: std::marker::PhantomData<&'a rtc_UserIds<'a>>,
     }
 
+    impl Default for rtc_UserIds<'_> {
+        fn default() -> Self {
+            Self {
+                ptr: std::ptr::null(),
+                count: 0,
+                phantom: std::marker::PhantomData,
+            }
+        }
+    }
+
     impl<'a, T: AsRef<[rtc_Bytes<'a>]>> From<&'a T> for rtc_UserIds<'a> {
         fn from(user_ids: &'a T) -> Self {
             let user_ids = user_ids.as_ref();
@@ -937,6 +959,7 @@ mod tests {
             pending_clients: vec![],
             creator: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         };
 
         let peek_info = peek_response.deobfuscate(&user_map, None);
@@ -981,6 +1004,7 @@ mod tests {
             ],
             creator: None,
             call_link_state: None,
+            admin_demux_ids: vec![],
         };
 
         let peek_info = peek_response.deobfuscate(&user_map, None);
@@ -1047,6 +1071,7 @@ mod tests {
             ],
             creator: Some("u1".to_string()),
             call_link_state: None,
+            admin_demux_ids: vec![],
         };
 
         let peek_info = PeekInfo::deobfuscate_proto(proto_peek, &obfuscated_resolver);
@@ -1077,6 +1102,7 @@ mod tests {
                 era_id: Some("paleozoic".to_string()),
                 max_devices: Some(16),
                 call_link_state: None,
+                admin_demux_ids: vec![],
             })
         );
 
@@ -1115,6 +1141,7 @@ mod tests {
             ],
             creator: Some("u1".to_string()),
             call_link_state: None,
+            admin_demux_ids: vec![],
         };
 
         let peek_info = PeekInfo::deobfuscate_proto(proto_peek, &obfuscated_resolver);
@@ -1149,6 +1176,7 @@ mod tests {
                 era_id: Some("paleozoic".to_string()),
                 max_devices: Some(16),
                 call_link_state: None,
+                admin_demux_ids: vec![],
             })
         );
     }
@@ -1193,6 +1221,7 @@ mod tests {
                 pending_clients: vec![],
                 creator: None,
                 call_link_state: None,
+                admin_demux_ids: vec![],
             };
 
             let peek_info = peek_response.deobfuscate(&resolver, Some(&root_key));
@@ -3,7 +3,11 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 //
 
-use std::{collections::HashSet, fmt, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    time::Duration,
+};
 
 use crate::{
     common::{
@@ -17,6 +21,7 @@ use crate::{
         signaling,
     },
     lite::{
+        call_links::{BreakoutRooms, CallLinkRootKey},
         sfu,
         sfu::{DemuxId, PeekInfo, PeekResult, UserId},
     },
@@ -189,7 +194,7 @@ pub enum EndReason {
     ConnectionFailure,
     InternalFailure,
     Timeout,
-    AcceptedOnAnotherDevice,
+    AcceptedOnAnotherDevice { device_id: Option<DeviceId> },
     DeclinedOnAnotherDevice,
     BusyOnAnotherDevice,
 }
@@ -212,7 +217,7 @@ impl fmt::Display for EndReason {
             EndReason::ConnectionFailure => "ConnectionFailure",
             EndReason::InternalFailure => "InternalFailure",
             EndReason::Timeout => "Timeout",
-            EndReason::AcceptedOnAnotherDevice => "AcceptedOnAnotherDevice",
+            EndReason::AcceptedOnAnotherDevice { .. } => "AcceptedOnAnotherDevice",
             EndReason::DeclinedOnAnotherDevice => "DeclinedOnAnotherDevice",
             EndReason::BusyOnAnotherDevice => "BusyOnAnotherDevice",
         };
@@ -247,6 +252,12 @@ pub enum GroupUpdate {
         sender_id: UserId,
         update: group_call::RingUpdate,
     },
+    CallLinkRing {
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender_id: UserId,
+        update: group_call::RingUpdate,
+    },
     NetworkRouteChanged(group_call::ClientId, NetworkRoute),
     AudioLevels(group_call::ClientId, AudioLevel, Vec<ReceivedAudioLevel>),
     LowBandwidthForVideo {
@@ -255,9 +266,26 @@ pub enum GroupUpdate {
     },
     Reactions(group_call::ClientId, Vec<group_call::Reaction>),
     RaisedHands(group_call::ClientId, Vec<DemuxId>),
+    SpeakerQueueChanged(group_call::ClientId, group_call::SpeakerQueue),
+    ForwardedVideoLayersChanged(group_call::ClientId, group_call::ForwardedVideoLayers),
     RtcStatsReportComplete {
         report_json: String,
     },
+    HandoffStateReceived(group_call::ClientId, group_call::HandoffState),
+    HandedOff(group_call::ClientId, DemuxId),
+    VideoLayoutChanged(group_call::ClientId, Vec<Option<DemuxId>>),
+    SpotlightChanged(group_call::ClientId, Vec<DemuxId>),
+    MutedByAdmin(group_call::ClientId, DemuxId),
+    UnmuteRequested(group_call::ClientId, DemuxId),
+    BreakoutRoomsChanged(group_call::ClientId, Option<BreakoutRooms>),
+    LobbyChanged(group_call::ClientId, group_call::LobbyInfo),
+    ChatMessage(group_call::ClientId, group_call::ChatMessage),
+    PollsChanged(group_call::ClientId, Vec<group_call::Poll>),
+    RingRecipientsChanged {
+        client_id: group_call::ClientId,
+        ring_id: group_call::RingId,
+        recipients: HashMap<UserId, group_call::RingRecipientStatus>,
+    },
     SpeechEvent(group_call::ClientId, group_call::SpeechEvent),
 }
 
@@ -273,6 +301,7 @@ impl fmt::Display for GroupUpdate {
             GroupUpdate::PeekResult { .. } => "PeekResult".to_string(),
             GroupUpdate::Ended(_, reason) => format!("Ended({:?})", reason),
             GroupUpdate::Ring { update, .. } => format!("Ring({:?})", update),
+            GroupUpdate::CallLinkRing { update, .. } => format!("CallLinkRing({:?})", update),
             GroupUpdate::NetworkRouteChanged(_, network_route) => {
                 format!("NetworkRouteChanged({:?})", network_route)
             }
@@ -288,7 +317,52 @@ impl fmt::Display for GroupUpdate {
             GroupUpdate::RaisedHands(_, raised_hands) => {
                 format!("RaisedHands({:?})", raised_hands)
             }
+            GroupUpdate::SpeakerQueueChanged(_, queue) => {
+                format!(
+                    "SpeakerQueueChanged({:?}, current_speaker: {:?})",
+                    queue.raised_hands, queue.current_speaker
+                )
+            }
+            GroupUpdate::ForwardedVideoLayersChanged(_, layers) => {
+                format!(
+                    "ForwardedVideoLayersChanged(outgoing: {}, incoming: {:?})",
+                    layers.outgoing_layer_count, layers.incoming_heights
+                )
+            }
             GroupUpdate::RtcStatsReportComplete { .. } => "RtcStatsReportComplete".to_string(),
+            GroupUpdate::HandoffStateReceived(_, state) => {
+                format!("HandoffStateReceived({:?})", state)
+            }
+            GroupUpdate::HandedOff(_, new_demux_id) => format!("HandedOff({})", new_demux_id),
+            GroupUpdate::VideoLayoutChanged(_, tiles) => format!("VideoLayoutChanged({:?})", tiles),
+            GroupUpdate::SpotlightChanged(_, spotlight) => {
+                format!("SpotlightChanged({:?})", spotlight)
+            }
+            GroupUpdate::MutedByAdmin(_, admin_demux_id) => {
+                format!("MutedByAdmin({})", admin_demux_id)
+            }
+            GroupUpdate::UnmuteRequested(_, admin_demux_id) => {
+                format!("UnmuteRequested({})", admin_demux_id)
+            }
+            GroupUpdate::ChatMessage(_, message) => format!("ChatMessage({:?})", message.id),
+            GroupUpdate::PollsChanged(_, polls) => format!("PollsChanged({} polls)", polls.len()),
+            GroupUpdate::LobbyChanged(_, lobby) => {
+                format!(
+                    "LobbyChanged(participant_count: {}, position: {:?})",
+                    lobby.participant_count, lobby.position
+                )
+            }
+            GroupUpdate::BreakoutRoomsChanged(_, breakout_rooms) => {
+                format!(
+                    "BreakoutRoomsChanged({:?})",
+                    breakout_rooms
+                        .as_ref()
+                        .map(|breakout_rooms| breakout_rooms.room_count)
+                )
+            }
+            GroupUpdate::RingRecipientsChanged { recipients, .. } => {
+                format!("RingRecipientsChanged({} recipients)", recipients.len())
+            }
             GroupUpdate::SpeechEvent(_, event) => {
                 format!("SpeechEvent({:?}", event)
             }
@@ -611,7 +685,7 @@ impl Platform for NativePlatform {
             ApplicationEvent::EndedRemoteHangupAccepted => self.send_state(
                 remote_peer,
                 call_id,
-                CallState::Ended(EndReason::AcceptedOnAnotherDevice),
+                CallState::Ended(EndReason::AcceptedOnAnotherDevice { device_id: None }),
             ),
             ApplicationEvent::EndedRemoteHangupDeclined => self.send_state(
                 remote_peer,
@@ -701,6 +775,27 @@ impl Platform for NativePlatform {
         Ok(())
     }
 
+    fn on_accepted_on_another_device(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        info!(
+            "NativePlatform::on_accepted_on_another_device(): remote_peer: {}, device_id: {}",
+            remote_peer, device_id
+        );
+
+        self.send_state(
+            remote_peer,
+            call_id,
+            CallState::Ended(EndReason::AcceptedOnAnotherDevice {
+                device_id: Some(device_id),
+            }),
+        )?;
+        Ok(())
+    }
+
     fn on_call_concluded(&self, remote_peer: &Self::AppRemotePeer, call_id: CallId) -> Result<()> {
         info!(
             "NativePlatform::on_call_concluded(): remote_peer: {}",
@@ -958,6 +1053,190 @@ impl Platform for NativePlatform {
         }
     }
 
+    fn handle_speaker_queue_changed(
+        &self,
+        client_id: group_call::ClientId,
+        queue: group_call::SpeakerQueue,
+    ) {
+        info!(
+            "NativePlatform::handle_speaker_queue_changed(): id: {}",
+            client_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::SpeakerQueueChanged(client_id, queue));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_forwarded_video_layers_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layers: group_call::ForwardedVideoLayers,
+    ) {
+        info!(
+            "NativePlatform::handle_forwarded_video_layers_changed(): id: {}",
+            client_id
+        );
+
+        let result =
+            self.send_group_update(GroupUpdate::ForwardedVideoLayersChanged(client_id, layers));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_spotlight_changed(&self, client_id: group_call::ClientId, spotlight: Vec<DemuxId>) {
+        info!(
+            "NativePlatform::handle_spotlight_changed(): id: {}",
+            client_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::SpotlightChanged(client_id, spotlight));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_chat_message(
+        &self,
+        client_id: group_call::ClientId,
+        message: group_call::ChatMessage,
+    ) {
+        trace!("NativePlatform::handle_chat_message(): id: {}", client_id);
+
+        let result = self.send_group_update(GroupUpdate::ChatMessage(client_id, message));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_polls_changed(&self, client_id: group_call::ClientId, polls: Vec<group_call::Poll>) {
+        info!("NativePlatform::handle_polls_changed(): id: {}", client_id);
+
+        let result = self.send_group_update(GroupUpdate::PollsChanged(client_id, polls));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_lobby_changed(&self, client_id: group_call::ClientId, lobby: group_call::LobbyInfo) {
+        info!("NativePlatform::handle_lobby_changed(): id: {}", client_id);
+
+        let result = self.send_group_update(GroupUpdate::LobbyChanged(client_id, lobby));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_breakout_rooms_changed(
+        &self,
+        client_id: group_call::ClientId,
+        breakout_rooms: Option<BreakoutRooms>,
+    ) {
+        info!(
+            "NativePlatform::handle_breakout_rooms_changed(): id: {}",
+            client_id
+        );
+
+        let result =
+            self.send_group_update(GroupUpdate::BreakoutRoomsChanged(client_id, breakout_rooms));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_muted_by_admin(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!(
+            "NativePlatform::handle_muted_by_admin(): id: {}, admin: {}",
+            client_id, admin_demux_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::MutedByAdmin(client_id, admin_demux_id));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_unmute_requested(&self, client_id: group_call::ClientId, admin_demux_id: DemuxId) {
+        info!(
+            "NativePlatform::handle_unmute_requested(): id: {}, admin: {}",
+            client_id, admin_demux_id
+        );
+
+        let result =
+            self.send_group_update(GroupUpdate::UnmuteRequested(client_id, admin_demux_id));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_video_layout_changed(
+        &self,
+        client_id: group_call::ClientId,
+        layout: group_call::VideoLayout,
+    ) {
+        info!(
+            "NativePlatform::handle_video_layout_changed(): id: {}",
+            client_id
+        );
+
+        let result =
+            self.send_group_update(GroupUpdate::VideoLayoutChanged(client_id, layout.tiles));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_ring_recipients_changed(
+        &self,
+        client_id: group_call::ClientId,
+        ring_id: group_call::RingId,
+        recipients: HashMap<UserId, group_call::RingRecipientStatus>,
+    ) {
+        info!(
+            "NativePlatform::handle_ring_recipients_changed(): id: {}",
+            client_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::RingRecipientsChanged {
+            client_id,
+            ring_id,
+            recipients,
+        });
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_handoff_state_received(
+        &self,
+        client_id: group_call::ClientId,
+        state: group_call::HandoffState,
+    ) {
+        info!(
+            "NativePlatform::handle_handoff_state_received(): id: {}",
+            client_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::HandoffStateReceived(client_id, state));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
+    fn handle_handed_off(&self, client_id: group_call::ClientId, new_demux_id: DemuxId) {
+        info!(
+            "NativePlatform::handle_handed_off(): id: {}, new_demux_id: {}",
+            client_id, new_demux_id
+        );
+
+        let result = self.send_group_update(GroupUpdate::HandedOff(client_id, new_demux_id));
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
+
     fn handle_join_state_changed(
         &self,
         client_id: group_call::ClientId,
@@ -1061,6 +1340,26 @@ impl Platform for NativePlatform {
             error!("{:?}", result.err());
         }
     }
+
+    fn call_link_ring_update(
+        &self,
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender_id: UserId,
+        update: group_call::RingUpdate,
+    ) {
+        info!("NativePlatform::call_link_ring_update(): id: {}", ring_id);
+
+        let result = self.send_group_update(GroupUpdate::CallLinkRing {
+            root_key,
+            ring_id,
+            sender_id,
+            update,
+        });
+        if result.is_err() {
+            error!("{:?}", result.err());
+        }
+    }
 }
 
 impl sfu::Delegate for NativePlatform {
@@ -56,6 +56,17 @@ pub mod group_call {
         }
     }
 
+    impl Extend<device_to_device::ChatFragment> for device_to_device::ChatFragment {
+        fn extend<T: IntoIterator<Item = device_to_device::ChatFragment>>(&mut self, iter: T) {
+            let content = self.content.get_or_insert_with(Vec::new);
+            for fragment in iter {
+                if let Some(other_content) = fragment.content {
+                    content.extend(other_content);
+                }
+            }
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
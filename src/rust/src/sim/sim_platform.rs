@@ -28,6 +28,7 @@ use crate::{
         signaling,
     },
     lite::{
+        call_links::CallLinkRootKey,
         sfu,
         sfu::{DemuxId, PeekInfo, PeekResult, UserId},
     },
@@ -85,6 +86,14 @@ pub struct GroupCallRingUpdate {
     pub update: group_call::RingUpdate,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct CallLinkRingUpdate {
+    pub root_key: [u8; 16],
+    pub ring_id: group_call::RingId,
+    pub sender_id: UserId,
+    pub update: group_call::RingUpdate,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct OutgoingCallMessage {
     pub recipient_id: UserId,
@@ -104,10 +113,14 @@ pub struct SimPlatform {
     force_signaling_fault: Arc<AtomicBool>,
     /// Track event frequencies
     event_map: Arc<Mutex<HashMap<ApplicationEvent, usize>>>,
+    /// Track the devices reported by on_accepted_on_another_device
+    accepted_on_another_device: Arc<Mutex<Vec<DeviceId>>>,
     /// Track whether disconnecting of incoming media happened
     incoming_media_disconnected: Arc<AtomicBool>,
     /// Track group call ring updates
     group_call_ring_updates: Arc<Mutex<Vec<GroupCallRingUpdate>>>,
+    /// Track call link ring updates
+    call_link_ring_updates: Arc<Mutex<Vec<CallLinkRingUpdate>>>,
     /// Track outgoing opaque messages
     outgoing_call_messages: Arc<Mutex<Vec<OutgoingCallMessage>>>,
     /// Call Manager
@@ -215,6 +228,24 @@ impl Platform for SimPlatform {
         Ok(())
     }
 
+    fn on_accepted_on_another_device(
+        &self,
+        remote_peer: &Self::AppRemotePeer,
+        call_id: CallId,
+        device_id: DeviceId,
+    ) -> Result<()> {
+        info!("on_accepted_on_another_device(): device_id: {}", device_id);
+        self.accepted_on_another_device
+            .lock()
+            .unwrap()
+            .push(device_id);
+        self.on_event(
+            remote_peer,
+            call_id,
+            ApplicationEvent::EndedRemoteHangupAccepted,
+        )
+    }
+
     fn on_network_route_changed(
         &self,
         _remote_peer: &Self::AppRemotePeer,
@@ -631,6 +662,24 @@ impl Platform for SimPlatform {
                 update,
             });
     }
+
+    fn call_link_ring_update(
+        &self,
+        root_key: CallLinkRootKey,
+        ring_id: group_call::RingId,
+        sender_id: UserId,
+        update: group_call::RingUpdate,
+    ) {
+        self.call_link_ring_updates
+            .lock()
+            .unwrap()
+            .push(CallLinkRingUpdate {
+                root_key: root_key.bytes(),
+                ring_id,
+                sender_id,
+                update,
+            });
+    }
 }
 
 impl sfu::Delegate for SimPlatform {
@@ -721,6 +770,10 @@ impl SimPlatform {
         ends
     }
 
+    pub fn take_accepted_on_another_device(&self) -> Vec<DeviceId> {
+        std::mem::take(&mut self.accepted_on_another_device.lock().unwrap())
+    }
+
     pub fn offers_sent(&self) -> usize {
         self.stats.offers_sent.load(Ordering::Acquire)
     }
@@ -791,6 +844,10 @@ impl SimPlatform {
         std::mem::take(&mut *self.group_call_ring_updates.lock().unwrap())
     }
 
+    pub fn take_call_link_ring_updates(&self) -> Vec<CallLinkRingUpdate> {
+        std::mem::take(&mut *self.call_link_ring_updates.lock().unwrap())
+    }
+
     pub fn take_outgoing_call_messages(&self) -> Vec<OutgoingCallMessage> {
         std::mem::take(&mut *self.outgoing_call_messages.lock().unwrap())
     }
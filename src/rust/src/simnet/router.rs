@@ -133,6 +133,27 @@ impl Router {
         Ok(())
     }
 
+    // Changes how packets sent from and received to the ip behave from now on.
+    // Packets already queued or delayed are not affected.
+    pub fn set_interface_configs(
+        &self,
+        ip: IpAddr,
+        send_config: LinkConfig,
+        receive_config: LinkConfig,
+    ) {
+        self.actor.send(move |state| {
+            if let (Some(send_link), Some(receive_link)) = (
+                state.send_link_by_ip.get(&ip),
+                state.receive_link_by_ip.get(&ip),
+            ) {
+                send_link.set_config(send_config);
+                receive_link.set_config(receive_config);
+            } else {
+                debug!("Ignored link configs for unknown IP ({:?}).", ip);
+            }
+        });
+    }
+
     pub fn send_packet(&self, packet: Packet) {
         self.actor.send(move |state| {
             if let Some(send_link) = state.send_link_by_ip.get(&packet.source.ip()) {
@@ -193,14 +214,7 @@ impl Link {
         receiver: Box<dyn PacketReceiver>,
         stopper: Stopper,
     ) -> Result<Self> {
-        // Could also be mean + std_dev
-        // let delay_distribution = distributions::Normal::new(
-        //     config.delay_mean.as_secs_f64(),
-        //     config.delay_std_dev.as_secs_f64(),
-        // );
-        let delay_distribution = distributions::Uniform::from(
-            (config.delay_min.as_millis() as u64)..(config.delay_max.as_millis() as u64),
-        );
+        let delay_distribution = Self::delay_distribution(&config);
         let leaky_bucket = LeakyBucket::start(config.clone(), receiver, stopper.clone())?;
         Ok(Self {
             actor: Actor::start("simnet-Link", stopper, move |actor| {
@@ -216,6 +230,28 @@ impl Link {
         })
     }
 
+    #[allow(deprecated)]
+    fn delay_distribution(config: &LinkConfig) -> distributions::uniform::Uniform<u64> {
+        // Could also be mean + std_dev
+        // let delay_distribution = distributions::Normal::new(
+        //     config.delay_mean.as_secs_f64(),
+        //     config.delay_std_dev.as_secs_f64(),
+        // );
+        // Inclusive so that a fixed delay (delay_min == delay_max) works.
+        distributions::Uniform::new_inclusive(
+            config.delay_min.as_millis() as u64,
+            config.delay_max.as_millis() as u64,
+        )
+    }
+
+    fn set_config(&self, config: LinkConfig) {
+        self.actor.send(move |state| {
+            state.delay_distribution = Self::delay_distribution(&config);
+            state.leaky_bucket.config = config.clone();
+            state.config = config;
+        });
+    }
+
     fn send_packet(&self, packet: Packet) {
         self.actor.send(move |state| {
             let loss_probability = if state.previous_packet_dropped {
@@ -7,6 +7,8 @@
 
 use std::os::raw::c_char;
 
+#[cfg(feature = "video_encoder_config")]
+use crate::webrtc::media::{RffiVideoEncoderLayer, VideoCodec};
 use crate::{
     webrtc,
     webrtc::{
@@ -77,6 +79,13 @@ extern "C" {
         enabled: bool,
     );
 
+    #[cfg(feature = "incoming_audio_gain")]
+    pub fn Rust_setIncomingAudioGain(
+        peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
+        demux_id: u32,
+        gain: f32,
+    );
+
     pub fn Rust_setAudioRecordingEnabled(
         peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
         enabled: bool,
@@ -144,6 +153,15 @@ extern "C" {
         config: webrtc::ptr::Borrowed<RffiAudioEncoderConfig>,
     );
 
+    #[cfg(feature = "video_encoder_config")]
+    pub fn Rust_configureVideoEncoders(
+        peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
+        layers_data: webrtc::ptr::Borrowed<RffiVideoEncoderLayer>,
+        layers_len: usize,
+        codec_preference_data: webrtc::ptr::Borrowed<VideoCodec>,
+        codec_preference_len: usize,
+    );
+
     pub fn Rust_getAudioLevels(
         peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
         captured_out: webrtc::ptr::Borrowed<RffiAudioLevel>,
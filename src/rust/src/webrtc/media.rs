@@ -5,6 +5,7 @@
 
 use std::slice;
 
+use anyhow::bail;
 pub use media::{RffiAudioTrack, RffiMediaStream, RffiVideoFrameBuffer, RffiVideoTrack};
 
 #[cfg(not(feature = "sim"))]
@@ -12,7 +13,11 @@ use crate::webrtc::ffi::media;
 pub use crate::webrtc::peer_connection_factory::RffiPeerConnectionFactoryOwner;
 #[cfg(feature = "sim")]
 use crate::webrtc::sim::media;
-use crate::{lite::sfu::DemuxId, webrtc};
+use crate::{
+    common::{units::DataRate, Result},
+    lite::sfu::DemuxId,
+    webrtc,
+};
 
 /// Rust wrapper around WebRTC C++ MediaStream object.
 #[derive(Clone, Debug)]
@@ -433,3 +438,94 @@ impl AudioEncoderConfig {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum VideoCodec {
+    Vp8 = 8,
+    Vp9 = 9,
+    Av1 = 10,
+}
+
+// Same as webrtc::rffi::VideoEncoderLayer in the RingRTC fork of WebRTC.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RffiVideoEncoderLayer {
+    max_height: u32,
+    max_framerate: u32,
+    max_bitrate_bps: u32,
+}
+
+// One rung of the simulcast ladder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VideoEncoderLayer {
+    pub max_height: u16,
+    pub max_framerate: u8,
+    pub max_bitrate: DataRate,
+}
+
+impl VideoEncoderLayer {
+    pub fn rffi(&self) -> RffiVideoEncoderLayer {
+        RffiVideoEncoderLayer {
+            max_height: self.max_height.into(),
+            max_framerate: self.max_framerate.into(),
+            max_bitrate_bps: self.max_bitrate.as_bps().try_into().unwrap_or(u32::MAX),
+        }
+    }
+}
+
+// The heights WebRTC sends when no ladder is configured.
+pub const DEFAULT_VIDEO_LAYER_HEIGHTS: [u16; 3] = [180, 360, 720];
+// The SFU forwards at most this many layers.
+pub const MAX_VIDEO_LAYERS: usize = 3;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VideoEncoderConfig {
+    // Lowest first. If empty, WebRTC's default ladder is used.
+    pub layers: Vec<VideoEncoderLayer>,
+    // Most preferred first. Codecs that can't be used are skipped, falling back to VP8.
+    pub codec_preference: Vec<VideoCodec>,
+}
+
+impl VideoEncoderConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.layers.len() > MAX_VIDEO_LAYERS {
+            bail!(
+                "{} video layers is more than {}",
+                self.layers.len(),
+                MAX_VIDEO_LAYERS
+            );
+        }
+        if self.layers.iter().any(|layer| {
+            layer.max_height == 0 || layer.max_framerate == 0 || layer.max_bitrate.as_bps() == 0
+        }) {
+            bail!("video layers must have a height, framerate, and bitrate");
+        }
+        if self
+            .layers
+            .windows(2)
+            .any(|pair| pair[0].max_height >= pair[1].max_height)
+        {
+            bail!("video layers must be ordered from lowest to highest");
+        }
+        Ok(())
+    }
+
+    pub fn layer_heights(&self) -> Vec<u16> {
+        if self.layers.is_empty() {
+            DEFAULT_VIDEO_LAYER_HEIGHTS.to_vec()
+        } else {
+            self.layers.iter().map(|layer| layer.max_height).collect()
+        }
+    }
+
+    // The index of the lowest layer that is at least as tall as the given height, or the
+    // highest layer if none is.
+    pub fn layer_for_height(&self, height: u16) -> usize {
+        let heights = self.layer_heights();
+        heights
+            .iter()
+            .position(|max_height| *max_height >= height)
+            .unwrap_or(heights.len() - 1)
+    }
+}
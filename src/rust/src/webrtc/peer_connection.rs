@@ -21,7 +21,7 @@ use crate::{
     webrtc,
     webrtc::{
         ice_gatherer::IceGatherer,
-        media::AudioEncoderConfig,
+        media::{AudioEncoderConfig, VideoEncoderConfig},
         network::RffiIpPort,
         peer_connection_factory::RffiPeerConnectionFactoryOwner,
         peer_connection_observer::RffiPeerConnectionObserver,
@@ -190,6 +190,32 @@ impl PeerConnection {
         unsafe { pc::Rust_setAudioPlayoutEnabled(self.rffi.as_borrowed(), enabled) };
     }
 
+    // Scales the playout volume of the audio received from one remote device in a group call,
+    // where 1.0 leaves it unchanged and 0.0 silences it.
+    pub fn set_incoming_audio_gain(&self, demux_id: u32, gain: f32) {
+        #[cfg(any(feature = "sim", feature = "incoming_audio_gain"))]
+        unsafe {
+            pc::Rust_setIncomingAudioGain(self.rffi.as_borrowed(), demux_id, gain)
+        };
+        #[cfg(not(any(feature = "sim", feature = "incoming_audio_gain")))]
+        warn!(
+            "PeerConnection.set_incoming_audio_gain({}, {}): needs the incoming_audio_gain feature",
+            demux_id, gain
+        );
+    }
+
+    // Whether set_incoming_audio_gain does anything in this build.
+    pub const fn incoming_audio_gain_supported() -> bool {
+        cfg!(any(feature = "sim", feature = "incoming_audio_gain"))
+    }
+
+    #[cfg(feature = "sim")]
+    pub fn incoming_audio_gain(&self, demux_id: u32) -> Option<f32> {
+        unsafe { self.rffi.as_borrowed().as_ref() }
+            .unwrap()
+            .incoming_audio_gain(demux_id)
+    }
+
     pub fn set_audio_recording_enabled(&self, enabled: bool) {
         unsafe { pc::Rust_setAudioRecordingEnabled(self.rffi.as_borrowed(), enabled) };
     }
@@ -371,6 +397,37 @@ impl PeerConnection {
         };
     }
 
+    pub fn configure_video_encoders(&self, video_encoder_config: &VideoEncoderConfig) {
+        info!(
+            "PeerConnection.configure_video_encoders({:?})",
+            video_encoder_config
+        );
+        #[cfg(any(feature = "sim", feature = "video_encoder_config"))]
+        {
+            let layers: Vec<_> = video_encoder_config
+                .layers
+                .iter()
+                .map(|layer| layer.rffi())
+                .collect();
+            unsafe {
+                pc::Rust_configureVideoEncoders(
+                    self.rffi.as_borrowed(),
+                    webrtc::ptr::Borrowed::from_ptr(layers.as_ptr()),
+                    layers.len(),
+                    webrtc::ptr::Borrowed::from_ptr(video_encoder_config.codec_preference.as_ptr()),
+                    video_encoder_config.codec_preference.len(),
+                )
+            };
+        }
+        #[cfg(not(any(feature = "sim", feature = "video_encoder_config")))]
+        warn!("PeerConnection.configure_video_encoders(): needs the video_encoder_config feature");
+    }
+
+    // Whether configure_video_encoders does anything in this build.
+    pub const fn video_encoder_config_supported() -> bool {
+        cfg!(any(feature = "sim", feature = "video_encoder_config"))
+    }
+
     pub fn get_audio_levels(&self) -> (AudioLevel, Vec<RffiReceivedAudioLevel>) {
         let captured_level: RffiAudioLevel = 0;
         let mut received_levels: Vec<RffiReceivedAudioLevel> = Vec::with_capacity(100);
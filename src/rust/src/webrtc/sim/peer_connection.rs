@@ -6,6 +6,7 @@
 //! WebRTC Simulation Peer Connection Interface
 
 use std::{
+    collections::HashMap,
     net::SocketAddr,
     os::raw::c_char,
     sync::{Arc, Mutex},
@@ -17,7 +18,7 @@ use crate::{
     core::platform::PlatformItem,
     webrtc,
     webrtc::{
-        media::RffiAudioEncoderConfig,
+        media::{RffiAudioEncoderConfig, RffiVideoEncoderLayer, VideoCodec},
         network::RffiIpPort,
         peer_connection::{RffiAudioLevel, RffiReceivedAudioLevel},
         rtp,
@@ -65,6 +66,7 @@ impl RffiPeerConnection {
                 removed_ice_candidates: vec![],
                 max_bitrate_bps: None,
                 last_sent_rtp_data: None,
+                incoming_audio_gains: HashMap::new(),
             })),
         }
     }
@@ -115,6 +117,16 @@ impl RffiPeerConnection {
         state.removed_ice_candidates.clone()
     }
 
+    fn set_incoming_audio_gain(&self, demux_id: u32, gain: f32) {
+        let mut state = self.state.lock().unwrap();
+        state.incoming_audio_gains.insert(demux_id, gain);
+    }
+
+    pub fn incoming_audio_gain(&self, demux_id: u32) -> Option<f32> {
+        let state = self.state.lock().unwrap();
+        state.incoming_audio_gains.get(&demux_id).copied()
+    }
+
     pub fn max_bitrate_bps(&self) -> Option<i32> {
         let state = self.state.lock().unwrap();
         state.max_bitrate_bps
@@ -145,6 +157,7 @@ struct RffiPeerConnectionState {
     removed_ice_candidates: Vec<SocketAddr>,
     max_bitrate_bps: Option<i32>,
     last_sent_rtp_data: Option<Vec<u8>>,
+    incoming_audio_gains: HashMap<u32, f32>,
 }
 
 #[allow(non_snake_case, clippy::missing_safety_doc)]
@@ -220,6 +233,16 @@ pub unsafe fn Rust_setAudioPlayoutEnabled(
     info!("Rust_setAudioPlayoutEnabled:");
 }
 
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_setIncomingAudioGain(
+    peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
+    demux_id: u32,
+    gain: f32,
+) {
+    info!("Rust_setIncomingAudioGain: {} {}", demux_id, gain);
+    (*peer_connection.as_ptr()).set_incoming_audio_gain(demux_id, gain);
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub unsafe fn Rust_setAudioRecordingEnabled(
     _peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
@@ -344,6 +367,20 @@ pub unsafe fn Rust_configureAudioEncoders(
     info!("Rust_configureAudioEncoders:");
 }
 
+#[allow(non_snake_case, clippy::missing_safety_doc)]
+pub unsafe fn Rust_configureVideoEncoders(
+    _peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
+    _layers_data: webrtc::ptr::Borrowed<RffiVideoEncoderLayer>,
+    layers_len: usize,
+    _codec_preference_data: webrtc::ptr::Borrowed<VideoCodec>,
+    codec_preference_len: usize,
+) {
+    info!(
+        "Rust_configureVideoEncoders: layers: {}, codecs: {}",
+        layers_len, codec_preference_len
+    );
+}
+
 #[allow(non_snake_case, clippy::missing_safety_doc)]
 pub fn Rust_getAudioLevels(
     _peer_connection: webrtc::ptr::BorrowedRc<RffiPeerConnection>,
@@ -0,0 +1,417 @@
+//
+// Copyright 2026 Signal Messenger, LLC
+// SPDX-License-Identifier: AGPL-3.0-only
+//
+
+//! Export of WebRTC statistics as time series
+//!
+//! Each interval, the [`StatsObserver`](super::stats_observer::StatsObserver) logs the rates and
+//! averages it computed for the connection and each stream. If an exporter has been started with
+//! [`init`], the same values are also written out in a structured format, so that tools don't
+//! have to scrape them from the log. The values have the names and units of the log's headers.
+//!
+//! Each series is labeled with the call ID (as logged) and, for streams, the direction, the SSRC,
+//! and in group calls the demux ID of the client the stream belongs to.
+//!
+//! Writing happens on a thread of its own, so that a slow reader can't hold up the calls.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs,
+    io::Write,
+    path::PathBuf,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::common::{CallId, Result};
+
+/// In group calls, the SSRCs of each client's streams are its demux ID plus a small offset.
+const DEMUX_ID_MASK: u32 = !0xf;
+
+/// How long the latest record of a call stays in an OpenMetrics file without being replaced,
+/// after which the call is assumed to have ended.
+const LATEST_RECORD_MAX_AGE_MS: u64 = 60_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "call_sim", derive(clap::ValueEnum))]
+pub enum StatsExportFormat {
+    /// An exposition in the OpenMetrics text format for each interval.
+    OpenMetrics,
+    /// A JSON object for each series in each interval, one per line.
+    JsonLines,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatsExportTarget {
+    /// JSON lines are appended to the file. OpenMetrics expositions replace the contents of the
+    /// file, so that it always holds the latest of every call in progress.
+    File(PathBuf),
+    /// Everything is written to a listening socket, in order.
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsKind {
+    Connection,
+    System,
+    Audio,
+    Video,
+}
+
+impl StatsKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Connection => "connection",
+            Self::System => "system",
+            Self::Audio => "audio",
+            Self::Video => "video",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsDirection {
+    Send,
+    Recv,
+}
+
+impl StatsDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::Recv => "recv",
+        }
+    }
+}
+
+/// The values computed for the connection, the system, or a stream over one interval.
+#[derive(Clone, Debug)]
+pub struct StatsSeries {
+    pub kind: StatsKind,
+    /// Only for streams, as is the SSRC.
+    pub direction: Option<StatsDirection>,
+    pub ssrc: Option<u32>,
+    pub values: Vec<(&'static str, f64)>,
+}
+
+/// Everything computed by a stats observer in one interval.
+#[derive(Debug)]
+struct StatsRecord {
+    time_ms: u64,
+    call_id: CallId,
+    is_group_call: bool,
+    series: Vec<StatsSeries>,
+}
+
+/// The number OpenMetrics expects for a value.
+fn open_metrics_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+impl StatsRecord {
+    fn demux_id(&self, ssrc: u32) -> Option<u32> {
+        self.is_group_call.then_some(ssrc & DEMUX_ID_MASK)
+    }
+
+    /// The labels of a series, as names and values.
+    fn labels(&self, series: &StatsSeries) -> Vec<(&'static str, String)> {
+        let mut labels = vec![("call_id", self.call_id.to_string())];
+        if let Some(direction) = series.direction {
+            labels.push(("direction", direction.as_str().to_string()));
+        }
+        if let Some(ssrc) = series.ssrc {
+            labels.push(("ssrc", ssrc.to_string()));
+            if let Some(demux_id) = self.demux_id(ssrc) {
+                labels.push(("demux_id", demux_id.to_string()));
+            }
+        }
+        labels
+    }
+
+    /// Each series as a JSON object on a line of its own, along with its labels and the time.
+    fn to_json_lines(&self) -> String {
+        let mut buf = String::new();
+        for series in &self.series {
+            let mut object = serde_json::Map::new();
+            object.insert("time_ms".to_string(), self.time_ms.into());
+            object.insert("call_id".to_string(), self.call_id.to_string().into());
+            object.insert("kind".to_string(), series.kind.as_str().into());
+            if let Some(direction) = series.direction {
+                object.insert("direction".to_string(), direction.as_str().into());
+            }
+            if let Some(ssrc) = series.ssrc {
+                object.insert("ssrc".to_string(), ssrc.into());
+                if let Some(demux_id) = self.demux_id(ssrc) {
+                    object.insert("demux_id".to_string(), demux_id.into());
+                }
+            }
+            for (name, value) in &series.values {
+                // Values that aren't finite become null.
+                object.insert(name.to_string(), (*value).into());
+            }
+            let _ = writeln!(buf, "{}", serde_json::Value::Object(object));
+        }
+        buf
+    }
+}
+
+/// An OpenMetrics exposition of the records, with every series a gauge named by its kind and
+/// value, such as `ringrtc_audio_bitrate`.
+fn to_open_metrics<'a>(records: impl IntoIterator<Item = &'a StatsRecord>) -> String {
+    // Samples of the same metric have to be together.
+    let mut metrics: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for record in records {
+        for series in &record.series {
+            let labels = record
+                .labels(series)
+                .into_iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, value))
+                .collect::<Vec<_>>()
+                .join(",");
+            for (name, value) in &series.values {
+                metrics
+                    .entry(format!("ringrtc_{}_{}", series.kind.as_str(), name))
+                    .or_default()
+                    .push(format!(
+                        "{{{}}} {} {}.{:03}",
+                        labels,
+                        open_metrics_value(*value),
+                        record.time_ms / 1000,
+                        record.time_ms % 1000
+                    ));
+            }
+        }
+    }
+
+    let mut buf = String::new();
+    for (metric, samples) in metrics {
+        let _ = writeln!(buf, "# TYPE {} gauge", metric);
+        for sample in samples {
+            let _ = writeln!(buf, "{}{}", metric, sample);
+        }
+    }
+    buf.push_str("# EOF\n");
+    buf
+}
+
+enum Output {
+    /// Appended to.
+    File(fs::File),
+    /// Replaced each time.
+    LatestFile(PathBuf),
+    #[cfg(unix)]
+    UnixSocket(std::os::unix::net::UnixStream),
+}
+
+impl Output {
+    fn open(format: StatsExportFormat, target: StatsExportTarget) -> Result<Self> {
+        Ok(match (format, target) {
+            (StatsExportFormat::OpenMetrics, StatsExportTarget::File(path)) => {
+                // Make sure it can be written to.
+                fs::write(&path, "# EOF\n")?;
+                Self::LatestFile(path)
+            }
+            (StatsExportFormat::JsonLines, StatsExportTarget::File(path)) => Self::File(
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?,
+            ),
+            #[cfg(unix)]
+            (_, StatsExportTarget::UnixSocket(path)) => {
+                Self::UnixSocket(std::os::unix::net::UnixStream::connect(path)?)
+            }
+        })
+    }
+
+    fn write(&mut self, data: &str) -> std::io::Result<()> {
+        match self {
+            Self::File(file) => file.write_all(data.as_bytes()),
+            Self::LatestFile(path) => {
+                // Replace the file in one step, so that it's never read half written.
+                let mut temp_path = path.clone().into_os_string();
+                temp_path.push(".tmp");
+                fs::write(&temp_path, data)?;
+                fs::rename(&temp_path, path)
+            }
+            #[cfg(unix)]
+            Self::UnixSocket(stream) => stream.write_all(data.as_bytes()),
+        }
+    }
+}
+
+static EXPORTER: Mutex<Option<mpsc::Sender<StatsRecord>>> = Mutex::new(None);
+
+/// Starts exporting stats, replacing any exporter that was already started. Fails if the target
+/// can't be opened. If writing to it fails later on, exporting stops.
+pub fn init(format: StatsExportFormat, target: StatsExportTarget) -> Result<()> {
+    info!("Exporting stats as {:?} to {:?}", format, target);
+
+    let mut output = Output::open(format, target)?;
+    let (sender, receiver) = mpsc::channel::<StatsRecord>();
+    thread::Builder::new()
+        .name("stats-exporter".to_string())
+        .spawn(move || {
+            // The latest record of each call, for a file that only holds the latest.
+            let mut latest: Vec<StatsRecord> = vec![];
+            for record in receiver {
+                let data = match (format, &output) {
+                    (StatsExportFormat::OpenMetrics, Output::LatestFile(_)) => {
+                        latest.retain(|other| {
+                            other.call_id != record.call_id
+                                && record.time_ms.saturating_sub(other.time_ms)
+                                    < LATEST_RECORD_MAX_AGE_MS
+                        });
+                        latest.push(record);
+                        to_open_metrics(&latest)
+                    }
+                    (StatsExportFormat::OpenMetrics, _) => to_open_metrics([&record]),
+                    (StatsExportFormat::JsonLines, _) => record.to_json_lines(),
+                };
+                if let Err(err) = output.write(&data) {
+                    warn!("Stopped exporting stats: {}", err);
+                    return;
+                }
+            }
+        })?;
+
+    *EXPORTER.lock().unwrap() = Some(sender);
+    Ok(())
+}
+
+/// Stops exporting stats. What was already computed is still written.
+pub fn stop() {
+    *EXPORTER.lock().unwrap() = None;
+}
+
+/// Exports the series computed in one interval, if an exporter was started.
+pub(super) fn export(call_id: CallId, is_group_call: bool, series: Vec<StatsSeries>) {
+    let mut exporter = EXPORTER.lock().unwrap();
+    if let Some(sender) = exporter.as_ref() {
+        let time_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let record = StatsRecord {
+            time_ms,
+            call_id,
+            is_group_call,
+            series,
+        };
+        if sender.send(record).is_err() {
+            // The thread stopped.
+            *exporter = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(is_group_call: bool) -> StatsRecord {
+        StatsRecord {
+            time_ms: 1_700_000_000_123,
+            call_id: CallId::new(0x1234),
+            is_group_call,
+            series: vec![
+                StatsSeries {
+                    kind: StatsKind::Connection,
+                    direction: None,
+                    ssrc: None,
+                    values: vec![("current_round_trip_time", 45.0)],
+                },
+                StatsSeries {
+                    kind: StatsKind::Audio,
+                    direction: Some(StatsDirection::Send),
+                    ssrc: Some(0x71),
+                    values: vec![("bitrate", 32000.0), ("remote_jitter", f64::NAN)],
+                },
+                StatsSeries {
+                    kind: StatsKind::Audio,
+                    direction: Some(StatsDirection::Recv),
+                    ssrc: Some(0x82),
+                    values: vec![("bitrate", 31000.5)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn open_metrics() {
+        assert_eq!(
+            to_open_metrics([&record(true)]),
+            "# TYPE ringrtc_audio_bitrate gauge\n\
+             ringrtc_audio_bitrate{call_id=\"0x1234\",direction=\"send\",ssrc=\"113\",demux_id=\"112\"} 32000 1700000000.123\n\
+             ringrtc_audio_bitrate{call_id=\"0x1234\",direction=\"recv\",ssrc=\"130\",demux_id=\"128\"} 31000.5 1700000000.123\n\
+             # TYPE ringrtc_audio_remote_jitter gauge\n\
+             ringrtc_audio_remote_jitter{call_id=\"0x1234\",direction=\"send\",ssrc=\"113\",demux_id=\"112\"} NaN 1700000000.123\n\
+             # TYPE ringrtc_connection_current_round_trip_time gauge\n\
+             ringrtc_connection_current_round_trip_time{call_id=\"0x1234\"} 45 1700000000.123\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn open_metrics_for_concurrent_calls() {
+        let mut other = record(false);
+        other.call_id = CallId::new(0x5678);
+        other.series.truncate(1);
+        assert_eq!(
+            to_open_metrics(&[record(false), other])
+                .lines()
+                .filter(|line| line.contains("current_round_trip_time"))
+                .collect::<Vec<_>>(),
+            vec![
+                "# TYPE ringrtc_connection_current_round_trip_time gauge",
+                "ringrtc_connection_current_round_trip_time{call_id=\"0x1234\"} 45 1700000000.123",
+                "ringrtc_connection_current_round_trip_time{call_id=\"0x5678\"} 45 1700000000.123",
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines() {
+        let json_lines = record(false).to_json_lines();
+        let lines: Vec<serde_json::Value> = json_lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(3, lines.len());
+        assert_eq!(
+            serde_json::json!({
+                "time_ms": 1_700_000_000_123u64,
+                "call_id": "0x1234",
+                "kind": "connection",
+                "current_round_trip_time": 45.0,
+            }),
+            lines[0]
+        );
+        assert_eq!(
+            serde_json::json!({
+                "time_ms": 1_700_000_000_123u64,
+                "call_id": "0x1234",
+                "kind": "audio",
+                "direction": "send",
+                "ssrc": 113,
+                "bitrate": 32000.0,
+                "remote_jitter": null,
+            }),
+            lines[1]
+        );
+        // Demux IDs are only known in group calls.
+        assert!(lines.iter().all(|line| line.get("demux_id").is_none()));
+    }
+}
@@ -21,7 +21,13 @@ pub use crate::webrtc::ffi::stats_observer::RffiStatsObserver;
 use crate::webrtc::sim::stats_observer as stats;
 #[cfg(feature = "sim")]
 pub use crate::webrtc::sim::stats_observer::RffiStatsObserver;
-use crate::{common::CallId, webrtc};
+use crate::{
+    common::CallId,
+    webrtc::{
+        self,
+        stats_exporter::{self, StatsDirection, StatsKind, StatsSeries},
+    },
+};
 
 /// How often to clean up old stats.
 const CLEAN_UP_STATS_TICKS: u32 = 60;
@@ -47,6 +53,8 @@ struct Stats {
 #[derive(Debug)]
 pub struct StatsObserver {
     call_id: CallId,
+    /// Whether SSRCs are derived from demux IDs, for exporting.
+    is_group_call: bool,
     rffi: webrtc::Arc<RffiStatsObserver>,
     stats: Stats,
     stats_initial_offset: Duration,
@@ -133,149 +141,286 @@ impl StatsObserver {
         );
     }
 
-    fn print_connection(&self, media_statistics: &MediaStatistics) {
+    fn print_connection(&self, media_statistics: &MediaStatistics) -> StatsSeries {
+        let current_round_trip_time = media_statistics
+            .connection_statistics
+            .current_round_trip_time
+            * 1000.0;
+        let available_outgoing_bitrate = media_statistics
+            .connection_statistics
+            .available_outgoing_bitrate;
+
         info!(
             "ringrtc_stats!,connection,{call_id},{timestamp_us},{current_round_trip_time:.0}ms,{available_outgoing_bitrate:.0}bps",
             call_id = self.call_id,
             timestamp_us = media_statistics.timestamp_us,
-            current_round_trip_time = media_statistics
-                .connection_statistics
-                .current_round_trip_time
-                * 1000.0,
-            available_outgoing_bitrate = media_statistics
-                .connection_statistics
-                .available_outgoing_bitrate,
         );
-    }
 
-    fn print_system(&mut self) {
-        #[cfg(not(target_os = "android"))]
-        {
-            // Be careful adding new stats;
-            // some have a fair amount of persistent state that raises memory usage.
-            self.system_stats.refresh_cpu_usage();
-            info!(
-                "ringrtc_stats!,system,{cpu_pct:.0}%",
-                cpu_pct = self.system_stats.global_cpu_usage(),
-            )
+        StatsSeries {
+            kind: StatsKind::Connection,
+            direction: None,
+            ssrc: None,
+            values: vec![
+                ("current_round_trip_time", current_round_trip_time),
+                ("available_outgoing_bitrate", available_outgoing_bitrate),
+            ],
         }
     }
 
+    #[cfg(not(target_os = "android"))]
+    fn print_system(&mut self) -> Option<StatsSeries> {
+        // Be careful adding new stats;
+        // some have a fair amount of persistent state that raises memory usage.
+        self.system_stats.refresh_cpu_usage();
+        let cpu_pct = self.system_stats.global_cpu_usage();
+        info!("ringrtc_stats!,system,{cpu_pct:.0}%");
+
+        Some(StatsSeries {
+            kind: StatsKind::System,
+            direction: None,
+            ssrc: None,
+            values: vec![("cpu_usage_pct", cpu_pct as f64)],
+        })
+    }
+
+    #[cfg(target_os = "android")]
+    fn print_system(&mut self) -> Option<StatsSeries> {
+        None
+    }
+
     fn print_audio_sender(
         audio_sender: &AudioSenderStatistics,
         prev_audio_sender: &AudioSenderStatistics,
         seconds_elapsed: f32,
-    ) {
+    ) -> StatsSeries {
         let packets_lost = audio_sender.remote_packets_lost - prev_audio_sender.remote_packets_lost;
         let packets_sent = audio_sender.packets_sent - prev_audio_sender.packets_sent;
         let bytes_sent = audio_sender.bytes_sent - prev_audio_sender.bytes_sent;
 
+        let ssrc = audio_sender.ssrc;
+        let packets_per_second = packets_sent as f32 / seconds_elapsed;
+        let average_packet_size = if packets_sent > 0 {
+            bytes_sent as f32 / packets_sent as f32
+        } else {
+            0.0
+        };
+        let bitrate = bytes_sent as f32 * 8.0 / seconds_elapsed;
+        let remote_packets_lost_pct =
+            Self::compute_packets_lost_pct(packets_lost, packets_sent as i32);
+        let remote_jitter = audio_sender.remote_jitter * 1000.0;
+        let remote_round_trip_time = audio_sender.remote_round_trip_time * 1000.0;
+        let audio_energy = audio_sender.total_audio_energy - prev_audio_sender.total_audio_energy;
+
         info!(
             "ringrtc_stats!,audio,send,{ssrc},{packets_per_second:.1},{average_packet_size:.1},{bitrate:.1}bps,{remote_packets_lost_pct:.1}%,{remote_jitter:.0}ms,{remote_round_trip_time:.0}ms,{audio_energy:.3}",
-            ssrc = audio_sender.ssrc,
-            packets_per_second = packets_sent as f32 / seconds_elapsed,
-            average_packet_size = if packets_sent > 0 { bytes_sent as f32 / packets_sent as f32 } else { 0.0 },
-            bitrate = bytes_sent as f32 * 8.0 / seconds_elapsed,
-            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_sent as i32),
-            remote_jitter = audio_sender.remote_jitter * 1000.0,
-            remote_round_trip_time = audio_sender.remote_round_trip_time * 1000.0,
-            audio_energy = audio_sender.total_audio_energy - prev_audio_sender.total_audio_energy,
         );
+
+        StatsSeries {
+            kind: StatsKind::Audio,
+            direction: Some(StatsDirection::Send),
+            ssrc: Some(ssrc),
+            values: vec![
+                ("packets_per_second", packets_per_second as f64),
+                ("average_packet_size", average_packet_size as f64),
+                ("bitrate", bitrate as f64),
+                ("remote_packets_lost_pct", remote_packets_lost_pct as f64),
+                ("remote_jitter", remote_jitter),
+                ("remote_round_trip_time", remote_round_trip_time),
+                ("audio_energy", audio_energy),
+            ],
+        }
     }
 
     fn print_video_sender(
         video_sender: &VideoSenderStatistics,
         prev_video_sender: &VideoSenderStatistics,
         seconds_elapsed: f32,
-    ) {
+    ) -> StatsSeries {
         let packets_lost = video_sender.remote_packets_lost - prev_video_sender.remote_packets_lost;
         let packets_sent = video_sender.packets_sent - prev_video_sender.packets_sent;
         let bytes_sent = video_sender.bytes_sent - prev_video_sender.bytes_sent;
         let frames_encoded = video_sender.frames_encoded - prev_video_sender.frames_encoded;
 
+        let ssrc = video_sender.ssrc;
+        let packets_per_second = packets_sent as f32 / seconds_elapsed;
+        let average_packet_size = if packets_sent > 0 {
+            bytes_sent as f32 / packets_sent as f32
+        } else {
+            0.0
+        };
+        let bitrate = bytes_sent as f32 * 8.0 / seconds_elapsed;
+        let framerate = frames_encoded as f32 / seconds_elapsed;
+        let key_frames_encoded =
+            video_sender.key_frames_encoded - prev_video_sender.key_frames_encoded;
+        let encode_time_per_frame = if frames_encoded > 0 {
+            (video_sender.total_encode_time - prev_video_sender.total_encode_time) * 1000.0
+                / frames_encoded as f64
+        } else {
+            0.0
+        };
+        let width = video_sender.frame_width;
+        let height = video_sender.frame_height;
+        let retransmitted_packets_sent =
+            video_sender.retransmitted_packets_sent - prev_video_sender.retransmitted_packets_sent;
+        let retransmitted_bitrate = (video_sender.retransmitted_bytes_sent
+            - prev_video_sender.retransmitted_bytes_sent)
+            as f32
+            / seconds_elapsed;
+        let send_delay_per_packet = if packets_sent > 0 {
+            (video_sender.total_packet_send_delay - prev_video_sender.total_packet_send_delay)
+                * 1000.0
+                / packets_sent as f64
+        } else {
+            0.0
+        };
+        let nack_count = video_sender.nack_count - prev_video_sender.nack_count;
+        let pli_count = video_sender.pli_count - prev_video_sender.pli_count;
+        let quality_limitation_reason = video_sender.quality_limitation_reason_description();
+        let quality_limitation_resolution_changes = video_sender
+            .quality_limitation_resolution_changes
+            - prev_video_sender.quality_limitation_resolution_changes;
+        let remote_packets_lost_pct =
+            Self::compute_packets_lost_pct(packets_lost, packets_sent as i32);
+        let remote_jitter = video_sender.remote_jitter * 1000.0;
+        let remote_round_trip_time = video_sender.remote_round_trip_time * 1000.0;
+
         info!(
             "ringrtc_stats!,video,send,{ssrc},{packets_per_second:.1},{average_packet_size:.1},{bitrate:.0}bps,{framerate:.1}fps,{key_frames_encoded},{encode_time_per_frame:.1}ms,{width}x{height},{retransmitted_packets_sent},{retransmitted_bitrate:.1}bps,{send_delay_per_packet:.1}ms,{nack_count},{pli_count},{quality_limitation_reason},{quality_limitation_resolution_changes},{remote_packets_lost_pct:.1}%,{remote_jitter:.1}ms,{remote_round_trip_time:.1}ms",
-            ssrc = video_sender.ssrc,
-            packets_per_second = packets_sent as f32 / seconds_elapsed,
-            average_packet_size = if packets_sent > 0 { bytes_sent as f32 / packets_sent as f32 } else { 0.0 },
-            bitrate = bytes_sent as f32 * 8.0 / seconds_elapsed,
-            framerate = frames_encoded as f32 / seconds_elapsed,
-            key_frames_encoded = video_sender.key_frames_encoded - prev_video_sender.key_frames_encoded,
-            encode_time_per_frame = if frames_encoded > 0 { (video_sender.total_encode_time - prev_video_sender.total_encode_time) * 1000.0 / frames_encoded as f64 } else { 0.0 },
-            width = video_sender.frame_width,
-            height = video_sender.frame_height,
-            retransmitted_packets_sent = video_sender.retransmitted_packets_sent - prev_video_sender.retransmitted_packets_sent,
-            retransmitted_bitrate = (video_sender.retransmitted_bytes_sent - prev_video_sender.retransmitted_bytes_sent) as f32 / seconds_elapsed,
-            send_delay_per_packet = if packets_sent > 0 { (video_sender.total_packet_send_delay - prev_video_sender.total_packet_send_delay) * 1000.0 / packets_sent as f64 } else { 0.0 },
-            nack_count = video_sender.nack_count - prev_video_sender.nack_count,
-            pli_count = video_sender.pli_count - prev_video_sender.pli_count,
-            quality_limitation_reason = video_sender.quality_limitation_reason_description(),
-            quality_limitation_resolution_changes = video_sender.quality_limitation_resolution_changes - prev_video_sender.quality_limitation_resolution_changes,
-            remote_packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_sent as i32),
-            remote_jitter = video_sender.remote_jitter * 1000.0,
-            remote_round_trip_time = video_sender.remote_round_trip_time * 1000.0,
         );
+
+        StatsSeries {
+            kind: StatsKind::Video,
+            direction: Some(StatsDirection::Send),
+            ssrc: Some(ssrc),
+            values: vec![
+                ("packets_per_second", packets_per_second as f64),
+                ("average_packet_size", average_packet_size as f64),
+                ("bitrate", bitrate as f64),
+                ("framerate", framerate as f64),
+                ("key_frames_encoded", key_frames_encoded as f64),
+                ("encode_time_per_frame", encode_time_per_frame),
+                ("width", width as f64),
+                ("height", height as f64),
+                (
+                    "retransmitted_packets_sent",
+                    retransmitted_packets_sent as f64,
+                ),
+                ("retransmitted_bitrate", retransmitted_bitrate as f64),
+                ("send_delay_per_packet", send_delay_per_packet),
+                ("nack_count", nack_count as f64),
+                ("pli_count", pli_count as f64),
+                // See https://w3c.github.io/webrtc-stats/#rtcqualitylimitationreason-enum.
+                (
+                    "quality_limitation_reason",
+                    video_sender.quality_limitation_reason as f64,
+                ),
+                (
+                    "quality_limitation_resolution_changes",
+                    quality_limitation_resolution_changes as f64,
+                ),
+                ("remote_packets_lost_pct", remote_packets_lost_pct as f64),
+                ("remote_jitter", remote_jitter),
+                ("remote_round_trip_time", remote_round_trip_time),
+            ],
+        }
     }
 
     fn print_audio_receiver(
         audio_receiver: &AudioReceiverStatistics,
         prev_audio_receiver: &AudioReceiverStatistics,
         seconds_elapsed: f32,
-    ) {
+    ) -> StatsSeries {
         let packets_lost = audio_receiver.packets_lost - prev_audio_receiver.packets_lost;
         let packets_received =
             audio_receiver.packets_received - prev_audio_receiver.packets_received;
         let jitter_buffer_emitted_count = audio_receiver.jitter_buffer_emitted_count
             - prev_audio_receiver.jitter_buffer_emitted_count;
 
+        let ssrc = audio_receiver.ssrc;
+        let packets_per_second = packets_received as f32 / seconds_elapsed;
+        let packets_lost_pct =
+            Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost);
+        let bitrate = (audio_receiver.bytes_received - prev_audio_receiver.bytes_received) as f32
+            * 8.0
+            / seconds_elapsed;
+        let jitter = audio_receiver.jitter * 1000.0;
+        let audio_energy =
+            audio_receiver.total_audio_energy - prev_audio_receiver.total_audio_energy;
+        let jitter_buffer_delay = if jitter_buffer_emitted_count > 0 {
+            (audio_receiver.jitter_buffer_delay - prev_audio_receiver.jitter_buffer_delay)
+                / (jitter_buffer_emitted_count as f64)
+                * 1000.0
+        } else {
+            0.0
+        };
+
         info!(
             "ringrtc_stats!,audio,recv,{ssrc},{packets_per_second:.1},{packets_lost_pct:.1}%,{bitrate:.1}bps,{jitter:.0}ms,{audio_energy:.3},{jitter_buffer_delay:.0}ms",
-            ssrc = audio_receiver.ssrc,
-            packets_per_second = (audio_receiver.packets_received - prev_audio_receiver.packets_received) as f32
-                / seconds_elapsed,
-            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost),
-            bitrate = (audio_receiver.bytes_received - prev_audio_receiver.bytes_received) as f32 * 8.0
-                / seconds_elapsed,
-            jitter = audio_receiver.jitter * 1000.0,
-            audio_energy = audio_receiver.total_audio_energy - prev_audio_receiver.total_audio_energy,
-            jitter_buffer_delay = if jitter_buffer_emitted_count > 0 {
-                (audio_receiver.jitter_buffer_delay - prev_audio_receiver.jitter_buffer_delay)
-                    / (jitter_buffer_emitted_count as f64)
-                    * 1000.0
-            } else {
-                0.0
-            },
         );
+
+        StatsSeries {
+            kind: StatsKind::Audio,
+            direction: Some(StatsDirection::Recv),
+            ssrc: Some(ssrc),
+            values: vec![
+                ("packets_per_second", packets_per_second as f64),
+                ("packets_lost_pct", packets_lost_pct as f64),
+                ("bitrate", bitrate as f64),
+                ("jitter", jitter),
+                ("audio_energy", audio_energy),
+                ("jitter_buffer_delay", jitter_buffer_delay),
+            ],
+        }
     }
 
     fn print_video_receiver(
         video_receiver: &VideoReceiverStatistics,
         prev_video_receiver: &VideoReceiverStatistics,
         seconds_elapsed: f32,
-    ) {
+    ) -> StatsSeries {
         let packets_lost = video_receiver.packets_lost - prev_video_receiver.packets_lost;
         let packets_received =
             video_receiver.packets_received - prev_video_receiver.packets_received;
         let frames_decoded = video_receiver.frames_decoded - prev_video_receiver.frames_decoded;
 
+        let ssrc = video_receiver.ssrc;
+        let packets_per_second = packets_received as f32 / seconds_elapsed;
+        let packets_lost_pct =
+            Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost);
+        let bitrate = (video_receiver.bytes_received - prev_video_receiver.bytes_received) as f32
+            * 8.0
+            / seconds_elapsed;
+        let framerate = frames_decoded as f32 / seconds_elapsed;
+        let key_frames_decoded =
+            video_receiver.key_frames_decoded - prev_video_receiver.key_frames_decoded;
+        let decode_time_per_frame = if frames_decoded > 0 {
+            (video_receiver.total_decode_time - prev_video_receiver.total_decode_time) * 1000.0
+                / frames_decoded as f64
+        } else {
+            0.0
+        };
+        let width = video_receiver.frame_width;
+        let height = video_receiver.frame_height;
+
         info!(
             "ringrtc_stats!,video,recv,{ssrc},{packets_per_second:.1},{packets_lost_pct:.1}%,{bitrate:.0}bps,{framerate:.1}fps,{key_frames_decoded},{decode_time_per_frame:.1}ms,{width}x{height}",
-            ssrc = video_receiver.ssrc,
-            packets_per_second = (video_receiver.packets_received - prev_video_receiver.packets_received) as f32
-                / seconds_elapsed,
-            packets_lost_pct = Self::compute_packets_lost_pct(packets_lost, packets_received as i32 + packets_lost),
-            bitrate = (video_receiver.bytes_received - prev_video_receiver.bytes_received) as f32 * 8.0
-                / seconds_elapsed,
-            framerate = frames_decoded as f32 / seconds_elapsed,
-            key_frames_decoded = video_receiver.key_frames_decoded - prev_video_receiver.key_frames_decoded,
-            decode_time_per_frame = if frames_decoded > 0 {
-                (video_receiver.total_decode_time - prev_video_receiver.total_decode_time) * 1000.0 / frames_decoded as f64
-            } else {
-                0.0
-            },
-            width = video_receiver.frame_width,
-            height = video_receiver.frame_height,
         );
+
+        StatsSeries {
+            kind: StatsKind::Video,
+            direction: Some(StatsDirection::Recv),
+            ssrc: Some(ssrc),
+            values: vec![
+                ("packets_per_second", packets_per_second as f64),
+                ("packets_lost_pct", packets_lost_pct as f64),
+                ("bitrate", bitrate as f64),
+                ("framerate", framerate as f64),
+                ("key_frames_decoded", key_frames_decoded as f64),
+                ("decode_time_per_frame", decode_time_per_frame),
+                ("width", width as f64),
+                ("height", height as f64),
+            ],
+        }
     }
 
     fn compute_packets_lost_pct(packets_lost: i32, packets: i32) -> f32 {
@@ -293,7 +438,7 @@ impl StatsObserver {
     }
 
     /// Create a new StatsObserver.
-    fn new(call_id: CallId, stats_initial_offset: Duration) -> Self {
+    fn new(call_id: CallId, is_group_call: bool, stats_initial_offset: Duration) -> Self {
         Self::print_headers();
 
         #[cfg(not(target_os = "android"))]
@@ -308,6 +453,7 @@ impl StatsObserver {
 
         Self {
             call_id,
+            is_group_call,
             rffi: webrtc::Arc::null(),
             stats: Default::default(),
             stats_initial_offset,
@@ -325,8 +471,8 @@ impl StatsObserver {
             self.stats_initial_offset.as_secs() as f32
         };
 
-        self.print_connection(media_statistics);
-        self.print_system();
+        let mut series = vec![self.print_connection(media_statistics)];
+        series.extend(self.print_system());
 
         let stats = &mut self.stats;
         let mut stats_report_json = stats.report_json.lock().unwrap();
@@ -347,7 +493,11 @@ impl StatsObserver {
             for audio_sender in audio_senders.iter() {
                 let prev_audio_send_stats = stats.audio_send.entry(audio_sender.ssrc).or_default();
 
-                Self::print_audio_sender(audio_sender, prev_audio_send_stats, seconds_elapsed);
+                series.push(Self::print_audio_sender(
+                    audio_sender,
+                    prev_audio_send_stats,
+                    seconds_elapsed,
+                ));
 
                 *prev_audio_send_stats = audio_sender.clone();
             }
@@ -371,7 +521,11 @@ impl StatsObserver {
                     *prev_video_send_stats = Default::default();
                 }
 
-                Self::print_video_sender(video_sender, prev_video_send_stats, seconds_elapsed);
+                series.push(Self::print_video_sender(
+                    video_sender,
+                    prev_video_send_stats,
+                    seconds_elapsed,
+                ));
 
                 *prev_video_send_stats = video_sender.clone();
             }
@@ -394,7 +548,11 @@ impl StatsObserver {
                     .entry(audio_receiver.ssrc)
                     .or_insert_with(|| (Instant::now(), Default::default()));
 
-                Self::print_audio_receiver(audio_receiver, prev_audio_recv_stats, seconds_elapsed);
+                series.push(Self::print_audio_receiver(
+                    audio_receiver,
+                    prev_audio_recv_stats,
+                    seconds_elapsed,
+                ));
 
                 *updated_at = Instant::now();
                 *prev_audio_recv_stats = audio_receiver.clone();
@@ -418,7 +576,11 @@ impl StatsObserver {
                     .entry(video_receiver.ssrc)
                     .or_insert_with(|| (Instant::now(), Default::default()));
 
-                Self::print_video_receiver(video_receiver, prev_video_recv_stats, seconds_elapsed);
+                series.push(Self::print_video_receiver(
+                    video_receiver,
+                    prev_video_recv_stats,
+                    seconds_elapsed,
+                ));
 
                 *updated_at = Instant::now();
                 *prev_video_recv_stats = video_receiver.clone();
@@ -427,6 +589,8 @@ impl StatsObserver {
 
         stats.timestamp_us = media_statistics.timestamp_us;
 
+        stats_exporter::export(self.call_id, self.is_group_call, series);
+
         self.stats_received_count += 1;
 
         if self.stats_received_count % CLEAN_UP_STATS_TICKS == 0 {
@@ -630,11 +794,18 @@ const STATS_OBSERVER_CBS_PTR: *const StatsObserverCallbacks = &STATS_OBSERVER_CB
 /// Creates a new WebRTC C++ StatsObserver object,
 /// registering the collector callbacks to this module, and wraps the
 /// result in a Rust StatsObserver object.
+///
+/// In group calls, exported stats are labeled with the demux IDs of their streams.
 pub fn create_stats_observer(
     call_id: CallId,
+    is_group_call: bool,
     stats_initial_offset: Duration,
 ) -> Box<StatsObserver> {
-    let stats_observer = Box::new(StatsObserver::new(call_id, stats_initial_offset));
+    let stats_observer = Box::new(StatsObserver::new(
+        call_id,
+        is_group_call,
+        stats_initial_offset,
+    ));
     let stats_observer_ptr = Box::into_raw(stats_observer);
     let rffi_stats_observer = webrtc::Arc::from_owned(unsafe {
         stats::Rust_createStatsObserver(
@@ -14,6 +14,7 @@ use std::{
 };
 
 use lazy_static::lazy_static;
+use prost::Message;
 use rand::{
     distributions::{Distribution, Standard},
     Rng, SeedableRng,
@@ -270,6 +271,45 @@ impl TestContext {
     }
 }
 
+/// Checks that the only call message sent was the "ringing" response to the sender of a group
+/// ring, and clears it.
+pub fn expect_ringing_response_to_ringer(
+    cm: &CallManager<SimPlatform>,
+    ringer: &[u8],
+    group_id: &[u8],
+    ring_id: group_call::RingId,
+) {
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+    match &messages[..] {
+        [message] => {
+            assert_eq!(ringer, &message.recipient_id[..]);
+            assert_eq!(
+                group_call::SignalingMessageUrgency::HandleImmediately,
+                message.urgency
+            );
+            let call_message = protobuf::signaling::CallMessage::decode(&message.message[..])
+                .expect(error_line!());
+            assert_eq!(
+                protobuf::signaling::CallMessage {
+                    ring_response: Some(protobuf::signaling::call_message::RingResponse {
+                        group_id: Some(group_id.to_vec()),
+                        ring_id: Some(ring_id.into()),
+                        r#type: Some(
+                            protobuf::signaling::call_message::ring_response::Type::Ringing.into()
+                        ),
+                    }),
+                    ..Default::default()
+                },
+                call_message
+            );
+        }
+        _ => panic!("unexpected messages: {:?}", messages),
+    }
+}
+
 pub fn random_received_offer(_prng: &Prng, age: Duration) -> signaling::ReceivedOffer {
     let local_public_key = rand::thread_rng().gen::<[u8; 32]>().to_vec();
     let offer = signaling::Offer::from_v4(
@@ -27,7 +27,10 @@ use ringrtc::{
 
 #[macro_use]
 mod common;
-use common::{random_received_ice_candidate, random_received_offer, test_init, TestContext};
+use common::{
+    expect_ringing_response_to_ringer, random_received_ice_candidate, random_received_offer,
+    test_init, TestContext,
+};
 
 // Create an inbound call session up to the ConnectingBeforeAccepted state.
 //
@@ -208,6 +211,12 @@ fn inbound_call_hangup_accepted() {
         context.event_count(ApplicationEvent::EndedRemoteHangupAccepted),
         1
     );
+    assert_eq!(
+        vec![2],
+        cm.platform()
+            .expect(error_line!())
+            .take_accepted_on_another_device()
+    );
     assert!(!cm.busy());
 }
 
@@ -1380,9 +1389,9 @@ fn group_call_ring() {
         [update] => {
             assert_eq!(
                 &ringrtc::sim::sim_platform::GroupCallRingUpdate {
-                    group_id,
+                    group_id: group_id.clone(),
                     ring_id,
-                    sender_id,
+                    sender_id: sender_id.clone(),
                     update: group_call::RingUpdate::Requested
                 },
                 update
@@ -1391,6 +1400,16 @@ fn group_call_ring() {
         _ => panic!("unexpected ring updates: {:?}", ring_updates),
     }
 
+    // Nothing goes back to the ringer unless the app acknowledges the ring.
+    assert!(cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages()
+        .is_empty());
+    cm.acknowledge_group_ring(group_id.clone(), ring_id)
+        .expect(error_line!());
+    expect_ringing_response_to_ringer(&cm, &sender_id, &group_id, ring_id);
+
     assert!(!cm.busy());
 }
 
@@ -1450,6 +1469,175 @@ fn group_call_ring_expired() {
     assert!(!cm.busy());
 }
 
+fn call_link_ring_message(
+    root_key: &ringrtc::lite::call_links::CallLinkRootKey,
+    schedule: &ringrtc::lite::call_links::CallLinkSchedule,
+    ring_id: i64,
+    cancelled: bool,
+) -> Vec<u8> {
+    let message = protobuf::signaling::CallMessage {
+        call_link_ring: Some(protobuf::signaling::call_message::CallLinkRing {
+            root_key: Some(root_key.bytes().to_vec()),
+            ring_id: Some(ring_id),
+            encrypted_schedule: Some(schedule.encrypt(root_key, rand::thread_rng())),
+            cancelled: Some(cancelled),
+        }),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    message
+        .encode(&mut buf)
+        .expect("cannot fail encoding to Vec");
+    buf
+}
+
+#[test]
+fn call_link_ring() {
+    use std::time::SystemTime;
+
+    use ringrtc::lite::call_links::{CallLinkRootKey, CallLinkSchedule};
+
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let root_key = CallLinkRootKey::generate(rand::thread_rng());
+    let sender_id = vec![1, 2, 3];
+    let schedule = CallLinkSchedule {
+        start: SystemTime::now() - Duration::from_secs(2 * 60),
+        duration: Duration::from_secs(30 * 60),
+        invitees: vec![sender_id.clone(), self_uuid],
+    };
+    let ring_id = group_call::RingId::from(schedule.ring_id(&root_key));
+    let buf = call_link_ring_message(&root_key, &schedule, ring_id.into(), false);
+
+    cm.received_call_message(sender_id.clone(), 1, 2, buf.clone(), Duration::ZERO)
+        .expect(error_line!());
+    cm.received_call_message(
+        sender_id.clone(),
+        1,
+        2,
+        buf,
+        ringrtc::core::call_manager::MAX_MESSAGE_AGE + Duration::from_millis(1),
+    )
+    .expect(error_line!());
+    cm.received_call_message(
+        sender_id.clone(),
+        1,
+        2,
+        call_link_ring_message(&root_key, &schedule, ring_id.into(), true),
+        Duration::ZERO,
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let ring_updates = cm
+        .platform()
+        .expect(error_line!())
+        .take_call_link_ring_updates();
+    assert_eq!(
+        vec![
+            ringrtc::sim::sim_platform::CallLinkRingUpdate {
+                root_key: root_key.bytes(),
+                ring_id,
+                sender_id: sender_id.clone(),
+                update: group_call::RingUpdate::Requested
+            },
+            ringrtc::sim::sim_platform::CallLinkRingUpdate {
+                root_key: root_key.bytes(),
+                ring_id,
+                sender_id: sender_id.clone(),
+                update: group_call::RingUpdate::ExpiredRequest
+            },
+            ringrtc::sim::sim_platform::CallLinkRingUpdate {
+                root_key: root_key.bytes(),
+                ring_id,
+                sender_id,
+                update: group_call::RingUpdate::CancelledByRinger
+            },
+        ],
+        ring_updates
+    );
+
+    // Nothing is sent back to the ringer.
+    assert_eq!(
+        &[] as &[ringrtc::sim::sim_platform::OutgoingCallMessage],
+        &cm.platform()
+            .expect(error_line!())
+            .take_outgoing_call_messages()[..]
+    );
+    assert!(cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_ring_updates()
+        .is_empty());
+}
+
+#[test]
+fn call_link_ring_dropped() {
+    use std::time::SystemTime;
+
+    use ringrtc::lite::call_links::{CallLinkRootKey, CallLinkSchedule};
+
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let root_key = CallLinkRootKey::generate(rand::thread_rng());
+    let sender_id = vec![1, 2, 3];
+    let schedule = CallLinkSchedule {
+        start: SystemTime::now(),
+        duration: Duration::from_secs(30 * 60),
+        invitees: vec![sender_id.clone(), self_uuid],
+    };
+    let ring_id = schedule.ring_id(&root_key);
+
+    // A ring ID that wasn't derived from the schedule.
+    let mismatched = call_link_ring_message(&root_key, &schedule, ring_id + 1, false);
+    // A meeting that isn't starting now.
+    let yesterday = CallLinkSchedule {
+        start: schedule.start - Duration::from_secs(24 * 60 * 60),
+        ..schedule.clone()
+    };
+    let late = call_link_ring_message(&root_key, &yesterday, yesterday.ring_id(&root_key), false);
+    // A meeting we're not invited to.
+    let uninvited = CallLinkSchedule {
+        invitees: vec![sender_id.clone()],
+        ..schedule.clone()
+    };
+    let not_invited = call_link_ring_message(&root_key, &uninvited, ring_id, false);
+    // A schedule encrypted under another link's key.
+    let other_key = CallLinkRootKey::generate(rand::thread_rng());
+    let mut wrong_key = call_link_ring_message(&other_key, &schedule, ring_id, false);
+    let mut message =
+        protobuf::signaling::CallMessage::decode(&wrong_key[..]).expect(error_line!());
+    message.call_link_ring.as_mut().unwrap().root_key = Some(root_key.bytes().to_vec());
+    wrong_key.clear();
+    message
+        .encode(&mut wrong_key)
+        .expect("cannot fail encoding to Vec");
+
+    for buf in [mismatched, late, not_invited, wrong_key] {
+        cm.received_call_message(sender_id.clone(), 1, 2, buf, Duration::ZERO)
+            .expect(error_line!());
+    }
+    cm.synchronize().expect(error_line!());
+
+    assert!(cm
+        .platform()
+        .expect(error_line!())
+        .take_call_link_ring_updates()
+        .is_empty());
+}
+
 #[test]
 fn group_call_ring_busy_in_direct_call() {
     test_init();
@@ -1805,6 +1993,93 @@ fn group_call_ring_timeout() {
     );
 }
 
+#[test]
+fn group_call_ring_restored_after_restart() {
+    test_init();
+
+    let self_uuid = vec![1, 0, 1];
+    let group_id = vec![1, 1, 1];
+    let sender_id = vec![1, 2, 3];
+    let ring_id = group_call::RingId::from(42);
+
+    let persisted_state = {
+        let context = TestContext::new();
+        let mut cm = context.cm();
+        cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+        let message = protobuf::signaling::CallMessage {
+            ring_intention: Some(protobuf::signaling::call_message::RingIntention {
+                group_id: Some(group_id.clone()),
+                ring_id: Some(ring_id.into()),
+                r#type: Some(protobuf::signaling::call_message::ring_intention::Type::Ring.into()),
+            }),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        message
+            .encode(&mut buf)
+            .expect("cannot fail encoding to Vec");
+
+        cm.received_call_message(sender_id.clone(), 1, 2, buf, Duration::ZERO)
+            .expect(error_line!());
+        cm.synchronize().expect(error_line!());
+
+        cm.persisted_state().expect(error_line!()).to_bytes()
+    };
+
+    // Simulate the process being killed and a new CallManager being created.
+    let context = TestContext::new();
+    let mut cm = context.cm();
+    cm.restore_persisted_state(
+        ringrtc::core::call_manager::PersistedState::from_bytes(&persisted_state)
+            .expect(error_line!()),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    let ring_updates = cm
+        .platform()
+        .expect(error_line!())
+        .take_group_call_ring_updates();
+    assert_eq!(
+        &[] as &[ringrtc::sim::sim_platform::GroupCallRingUpdate],
+        ring_updates
+    );
+
+    // Joining should accept the restored ring on behalf of the restored user.
+    let group_call_id = context
+        .create_group_call(group_id.clone())
+        .expect(error_line!());
+    cm.join(group_call_id);
+    cm.synchronize().expect(error_line!());
+
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+    match &messages[..] {
+        [message] => {
+            assert_eq!(&self_uuid[..], &message.recipient_id[..]);
+            let call_message = protobuf::signaling::CallMessage::decode(&message.message[..])
+                .expect(error_line!());
+            assert_eq!(
+                protobuf::signaling::CallMessage {
+                    ring_response: Some(protobuf::signaling::call_message::RingResponse {
+                        group_id: Some(group_id),
+                        ring_id: Some(ring_id.into()),
+                        r#type: Some(
+                            protobuf::signaling::call_message::ring_response::Type::Accepted.into()
+                        ),
+                    }),
+                    ..Default::default()
+                },
+                call_message
+            );
+        }
+        _ => panic!("unexpected messages: {:?}", messages),
+    }
+}
+
 #[test]
 fn received_status_before_accepted() {
     let context = start_inbound_call();
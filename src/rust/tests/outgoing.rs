@@ -2816,6 +2816,41 @@ fn glare_after_connect_loser() {
     assert_eq!(context.call_concluded_count(), 1);
 }
 
+// Another device of the callee takes over the call after it was accepted.
+#[test]
+fn handoff_after_accepted() {
+    test_init();
+
+    let context = connected_and_accepted_outbound_call();
+    let mut cm = context.cm();
+    let active_call = context.active_call();
+
+    let remote_peer = {
+        let remote_peer = active_call.remote_peer().expect(error_line!());
+        remote_peer.to_owned()
+    };
+
+    let mut received = random_received_offer(&context.prng, Duration::from_secs(0));
+    received.sender_device_id = 2;
+    received.offer = received
+        .offer
+        .with_replaced_call_id(active_call.call_id())
+        .expect(error_line!());
+
+    let call_id = CallId::new(context.prng.gen::<u64>());
+    cm.received_offer(remote_peer, call_id, received)
+        .expect(error_line!());
+
+    cm.synchronize().expect(error_line!());
+
+    assert_eq!(context.error_count(), 0);
+    assert_eq!(context.event_count(ApplicationEvent::EndedRemoteReCall), 1);
+    assert_eq!(context.accepted_hangups_sent(), 1);
+    assert_eq!(context.busys_sent(), 0);
+    assert_eq!(context.start_incoming_count(), 1);
+    assert_eq!(context.active_call().call_id(), call_id);
+}
+
 // Two users call each other at the same time, offer received before the
 // outgoing call gets an ICE connection. Call-ids are unexpectedly equal.
 #[test]
@@ -3038,6 +3073,163 @@ fn cancel_group_ring() {
     );
 }
 
+#[test]
+fn cancel_group_ring_tells_ringer() {
+    use group_call::{RingCancelReason, RingId};
+
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 0, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let group_id = vec![1, 1, 1];
+    let sender = vec![1, 2, 3];
+    let ring_id = RingId::from(42);
+
+    let message = protobuf::signaling::CallMessage {
+        ring_intention: Some(protobuf::signaling::call_message::RingIntention {
+            group_id: Some(group_id.clone()),
+            ring_id: Some(ring_id.into()),
+            r#type: Some(protobuf::signaling::call_message::ring_intention::Type::Ring.into()),
+        }),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    message
+        .encode(&mut buf)
+        .expect("cannot fail encoding to Vec");
+
+    cm.received_call_message(sender, 1, 2, buf, Duration::ZERO)
+        .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+
+    cm.cancel_group_ring(
+        group_id.clone(),
+        ring_id,
+        Some(RingCancelReason::DeclinedByUser),
+    )
+    .expect(error_line!());
+    cm.synchronize().expect(error_line!());
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+
+    let declined_message = protobuf::signaling::CallMessage {
+        ring_response: Some(protobuf::signaling::call_message::RingResponse {
+            group_id: Some(group_id),
+            ring_id: Some(ring_id.into()),
+            r#type: Some(protobuf::signaling::call_message::ring_response::Type::Declined.into()),
+        }),
+        ..Default::default()
+    };
+    match &messages[..] {
+        [to_ringer, to_self] => {
+            assert_eq!(&sender[..], &to_ringer.recipient_id[..]);
+            assert_eq!(&self_uuid[..], &to_self.recipient_id[..]);
+            for message in [to_ringer, to_self] {
+                let call_message = protobuf::signaling::CallMessage::decode(&message.message[..])
+                    .expect(error_line!());
+                assert_eq!(declined_message, call_message);
+            }
+        }
+        _ => panic!("unexpected messages: {:?}", messages),
+    }
+}
+
+#[test]
+fn ring_scheduled_call_link() {
+    use std::time::SystemTime;
+
+    use group_call::{RingId, SignalingMessageUrgency};
+    use ringrtc::lite::call_links::{CallLinkRootKey, CallLinkSchedule};
+
+    test_init();
+
+    let context = TestContext::new();
+    let mut cm = context.cm();
+
+    let self_uuid = vec![1, 1, 1];
+    cm.set_self_uuid(self_uuid.clone()).expect(error_line!());
+
+    let root_key = CallLinkRootKey::generate(rand::thread_rng());
+    let schedule = CallLinkSchedule {
+        start: SystemTime::now(),
+        duration: Duration::from_secs(30 * 60),
+        invitees: vec![self_uuid, vec![2, 2, 2], vec![3, 3, 3]],
+    };
+
+    // Too early.
+    let ring_id = cm
+        .ring_scheduled_call_link(
+            &root_key,
+            &schedule,
+            schedule.start - Duration::from_secs(60),
+        )
+        .expect(error_line!());
+    assert_eq!(None, ring_id);
+    assert_eq!(
+        &[] as &[ringrtc::sim::sim_platform::OutgoingCallMessage],
+        &cm.platform()
+            .expect(error_line!())
+            .take_outgoing_call_messages()[..]
+    );
+
+    let ring_id = cm
+        .ring_scheduled_call_link(&root_key, &schedule, schedule.start)
+        .expect(error_line!());
+    assert_eq!(Some(RingId::from(schedule.ring_id(&root_key))), ring_id);
+
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+    // We don't ring ourselves.
+    assert_eq!(
+        vec![vec![2, 2, 2], vec![3, 3, 3]],
+        messages
+            .iter()
+            .map(|message| message.recipient_id.clone())
+            .collect::<Vec<_>>()
+    );
+    for message in messages {
+        assert_eq!(SignalingMessageUrgency::HandleImmediately, message.urgency);
+        let call_message =
+            protobuf::signaling::CallMessage::decode(&message.message[..]).expect(error_line!());
+        let call_link_ring = call_message.call_link_ring.expect(error_line!());
+        assert_eq!(Some(root_key.bytes().to_vec()), call_link_ring.root_key);
+        assert_eq!(ring_id.map(Into::into), call_link_ring.ring_id);
+        assert_eq!(Some(false), call_link_ring.cancelled);
+        // The schedule goes along so that recipients can check the ring against it.
+        assert_eq!(
+            schedule,
+            CallLinkSchedule::decrypt(
+                &call_link_ring.encrypted_schedule.expect(error_line!()),
+                &root_key
+            )
+            .expect(error_line!())
+        );
+    }
+
+    cm.cancel_call_link_ring(&root_key, &schedule)
+        .expect(error_line!());
+    let messages = cm
+        .platform()
+        .expect(error_line!())
+        .take_outgoing_call_messages();
+    assert_eq!(2, messages.len());
+    for message in messages {
+        let call_message =
+            protobuf::signaling::CallMessage::decode(&message.message[..]).expect(error_line!());
+        let call_link_ring = call_message.call_link_ring.expect(error_line!());
+        assert_eq!(ring_id.map(Into::into), call_link_ring.ring_id);
+        assert_eq!(Some(true), call_link_ring.cancelled);
+    }
+}
+
 #[test]
 fn group_call_ring_accepted() {
     test_init();
@@ -3358,7 +3550,7 @@ fn group_call_ring_last_ring_wins() {
         .encode(&mut buf)
         .expect("cannot fail encoding to Vec");
 
-    cm.received_call_message(sender, 1, 2, buf, Duration::ZERO)
+    cm.received_call_message(sender.clone(), 1, 2, buf, Duration::ZERO)
         .expect(error_line!());
     cm.synchronize().expect(error_line!());
 